@@ -1,32 +1,68 @@
+pub mod config_export;
+pub mod crash_report;
 pub mod data_farmer;
-pub mod filter;
+pub mod debug_stats;
 pub mod frozen_state;
 pub mod layout_manager;
 mod process_killer;
+#[cfg(target_family = "unix")]
+mod process_priority;
+pub mod session;
+pub mod state_dump;
 pub mod states;
 
 use std::{
+    borrow::Cow,
     cmp::{max, min},
-    time::Instant,
+    net::SocketAddr,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime},
 };
 
 use anyhow::bail;
 use concat_string::concat_string;
 use data_farmer::*;
-use filter::*;
+use debug_stats::DebugStats;
 use frozen_state::FrozenState;
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use layout_manager::*;
 pub use states::*;
+use tui::layout::Rect;
 use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
+#[cfg(feature = "plugins")]
+use crate::data_collection::plugins::{PluginInfo, PluginWidgetKind};
+#[cfg(target_os = "linux")]
+use crate::data_collection::processes::{linux::namespace::Namespaces, ProcessFields};
 use crate::{
-    canvas::components::time_chart::LegendPosition,
-    constants, convert_mem_data_points, convert_swap_data_points,
-    data_collection::{processes::Pid, temperature},
-    data_conversion::ConvertedData,
-    get_network_points,
-    utils::data_units::DataUnit,
+    canvas::{
+        components::time_chart::{GraphMarker, LegendPosition},
+        terminal::TerminalFeatures,
+    },
+    constants,
+    data_collection::{
+        processes::Pid, synthetic::StressConfig, temperature, CollectionIssue, DataFilters,
+        SourceStatus, UsedWidgets,
+    },
+    data_conversion::{
+        convert_mem_data_points, convert_mem_label, convert_network_points,
+        convert_swap_data_points, get_network_points, ConvertedData,
+    },
+    event::{
+        keybinds::{Action, KeyBindings},
+        ClickTracker, ScrollTracker,
+    },
+    i18n::{tr, tr_args, Locale},
+    options::{
+        config::{
+            meter_header::MeterHeader,
+            mouse::{GraphScroll, MouseSettings},
+            network::NetworkYAxis,
+            status_bar::StatusBar,
+        },
+        ConfigSource,
+    },
+    utils::{clipboard::copy_to_clipboard, data_units::DataUnit, time_format::TimeFormat},
     widgets::{ProcWidgetColumn, ProcWidgetMode},
 };
 
@@ -43,7 +79,9 @@ pub enum AxisScaling {
 pub struct AppConfigFields {
     pub update_rate: u64,
     pub temperature_type: temperature::TemperatureType,
-    pub use_dot: bool,
+    pub extra_sensors: temperature::ExtraSensors,
+    pub graph_marker: GraphMarker,
+    pub graph_gradients: bool,
     pub cpu_left_legend: bool,
     pub show_average_cpu: bool, // TODO: Unify this in CPU options
     pub use_current_cpu_total: bool,
@@ -56,9 +94,13 @@ pub struct AppConfigFields {
     pub use_old_network_legend: bool,
     pub table_gap: u16,
     pub disable_click: bool,
+    pub double_click_action: DoubleClickAction,
     pub enable_gpu: bool,
     pub enable_cache_memory: bool,
     pub show_table_scroll_position: bool,
+    pub show_table_scrollbar: bool,
+    pub table_stripes: bool,
+    pub table_row_separators: bool,
     pub is_advanced_kill: bool,
     pub memory_legend_position: Option<LegendPosition>,
     // TODO: Remove these, move network details state-side.
@@ -66,17 +108,176 @@ pub struct AppConfigFields {
     pub network_legend_position: Option<LegendPosition>,
     pub network_scale_type: AxisScaling,
     pub network_use_binary_prefix: bool,
+    pub network_y_axis: NetworkYAxis,
+    pub network_namespaces: bool,
     pub retention_ms: u64,
+    pub long_term_retention_ms: Option<u64>,
     pub dedicated_average_row: bool,
+    pub http_address: Option<SocketAddr>,
+    pub dump_path: Option<PathBuf>,
+    pub idle_rate: Option<u64>,
+    /// The most memory graph history may use, in bytes.
+    pub max_history_memory: Option<u64>,
+    pub max_processes: Option<usize>,
+    pub pause_on_blur: bool,
+    pub mobile: bool,
+    /// Whether to draw on the alternate screen rather than inline.
+    pub alt_screen: bool,
+    pub stress: Option<StressConfig>,
+    pub save_session: bool,
+    pub key_bindings: KeyBindings,
+    pub status_bar: Option<StatusBar>,
+    pub meter_header: Option<MeterHeader>,
+    pub mouse: MouseSettings,
+    /// What the terminal supports, as detected or set with flags.
+    pub terminal: TerminalFeatures,
+    pub locale: Locale,
+    pub time_format: TimeFormat,
 }
 
-/// For filtering out information
-#[derive(Debug, Clone)]
-pub struct DataFilters {
-    pub disk_filter: Option<Filter>,
-    pub mount_filter: Option<Filter>,
-    pub temp_filter: Option<Filter>,
-    pub net_filter: Option<Filter>,
+impl AppConfigFields {
+    /// The height of each table row; rows are taller in mobile mode so they're
+    /// easier to tap.
+    pub fn table_row_height(&self) -> u16 {
+        if self.mobile {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// How many lines each row takes up in the process, disk, and temperature
+    /// tables, including the line after it if rows are separated.
+    pub fn table_row_stride(&self) -> u16 {
+        self.table_row_height() + u16::from(self.table_row_separators)
+    }
+
+    /// Returns the settings in use, after command-line arguments, the config
+    /// file, and defaults have been merged, for the diagnostics viewer. Key
+    /// bindings are left out, as the help menu already lists them.
+    pub fn effective_settings(&self) -> Vec<(&'static str, String)> {
+        fn or_none<T: std::fmt::Debug>(value: &Option<T>) -> String {
+            value
+                .as_ref()
+                .map_or_else(|| "none".to_string(), |value| format!("{value:?}"))
+        }
+
+        vec![
+            ("update_rate", format!("{}ms", self.update_rate)),
+            ("temperature_type", format!("{:?}", self.temperature_type)),
+            ("extra_sensors", format!("{:?}", self.extra_sensors)),
+            ("graph_marker", format!("{:?}", self.graph_marker)),
+            ("graph_gradients", self.graph_gradients.to_string()),
+            ("cpu_left_legend", self.cpu_left_legend.to_string()),
+            ("show_average_cpu", self.show_average_cpu.to_string()),
+            (
+                "use_current_cpu_total",
+                self.use_current_cpu_total.to_string(),
+            ),
+            ("unnormalized_cpu", self.unnormalized_cpu.to_string()),
+            (
+                "dedicated_average_row",
+                self.dedicated_average_row.to_string(),
+            ),
+            ("use_basic_mode", self.use_basic_mode.to_string()),
+            (
+                "default_time_value",
+                format!("{}ms", self.default_time_value),
+            ),
+            ("time_interval", format!("{}ms", self.time_interval)),
+            ("hide_time", self.hide_time.to_string()),
+            ("autohide_time", self.autohide_time.to_string()),
+            (
+                "use_old_network_legend",
+                self.use_old_network_legend.to_string(),
+            ),
+            ("table_gap", self.table_gap.to_string()),
+            ("disable_click", self.disable_click.to_string()),
+            (
+                "double_click_action",
+                format!("{:?}", self.double_click_action),
+            ),
+            ("enable_gpu", self.enable_gpu.to_string()),
+            ("enable_cache_memory", self.enable_cache_memory.to_string()),
+            (
+                "show_table_scroll_position",
+                self.show_table_scroll_position.to_string(),
+            ),
+            (
+                "show_table_scrollbar",
+                self.show_table_scrollbar.to_string(),
+            ),
+            ("table_stripes", self.table_stripes.to_string()),
+            (
+                "table_row_separators",
+                self.table_row_separators.to_string(),
+            ),
+            ("is_advanced_kill", self.is_advanced_kill.to_string()),
+            (
+                "memory_legend_position",
+                or_none(&self.memory_legend_position),
+            ),
+            ("network_unit_type", format!("{:?}", self.network_unit_type)),
+            (
+                "network_legend_position",
+                or_none(&self.network_legend_position),
+            ),
+            (
+                "network_scale_type",
+                format!("{:?}", self.network_scale_type),
+            ),
+            (
+                "network_use_binary_prefix",
+                self.network_use_binary_prefix.to_string(),
+            ),
+            ("network_y_axis", format!("{:?}", self.network_y_axis)),
+            ("network_namespaces", self.network_namespaces.to_string()),
+            ("retention", format!("{}ms", self.retention_ms)),
+            (
+                "long_term_retention",
+                self.long_term_retention_ms
+                    .map_or_else(|| "none".to_string(), |ms| format!("{ms}ms")),
+            ),
+            (
+                "http_address",
+                self.http_address
+                    .map_or_else(|| "none".to_string(), |address| address.to_string()),
+            ),
+            (
+                "dump_path",
+                self.dump_path.as_ref().map_or_else(
+                    || state_dump::default_dump_path().display().to_string(),
+                    |path| path.display().to_string(),
+                ),
+            ),
+            (
+                "idle_rate",
+                self.idle_rate
+                    .map_or_else(|| "none".to_string(), |ms| format!("{ms}ms")),
+            ),
+            (
+                "max_history_memory",
+                self.max_history_memory
+                    .map_or_else(|| "none".to_string(), |bytes| format!("{bytes}B")),
+            ),
+            (
+                "max_processes",
+                self.max_processes
+                    .map_or_else(|| "none".to_string(), |count| count.to_string()),
+            ),
+            ("pause_on_blur", self.pause_on_blur.to_string()),
+            ("mobile", self.mobile.to_string()),
+            ("alt_screen", self.alt_screen.to_string()),
+            ("save_session", self.save_session.to_string()),
+            ("status_bar", self.status_bar.is_some().to_string()),
+            ("meter_header", self.meter_header.is_some().to_string()),
+            ("mouse", format!("{:?}", self.mouse)),
+            ("terminal", format!("{:?}", self.terminal)),
+            ("locale", self.locale.code().to_string()),
+            ("time_format", self.time_format.to_string()),
+            ("stress", or_none(&self.stress)),
+        ]
+    }
 }
 
 cfg_if::cfg_if! {
@@ -119,8 +320,54 @@ pub struct App {
     pub app_config_fields: AppConfigFields,
     pub widget_map: HashMap<u64, BottomWidget>,
     pub current_widget: BottomWidget,
+    pub pages: Vec<PageState>,
+    pub current_page: usize,
+    /// The IDs of widgets hidden at runtime.
+    pub hidden_widgets: HashSet<u64>,
     pub used_widgets: UsedWidgets,
     pub filters: DataFilters,
+    pub time_range_selection: Option<TimeRangeSelection>,
+    pub layout_borders: Vec<LayoutBorder>,
+    pub layout_drag: Option<LayoutDrag>,
+    pub inspect_cursor: Option<InspectCursor>,
+    pub mouse_hover: Option<MouseHover>,
+    pub click_tracker: ClickTracker,
+    pub scroll_tracker: ScrollTracker,
+    pub context_menu: Option<ContextMenu>,
+    /// The process whose details are being shown.
+    pub process_details: Option<Pid>,
+    pub global_search: Option<GlobalSearch>,
+    /// The scroll position of the config and diagnostics viewer, if shown.
+    pub diagnostics: Option<ParagraphScrollState>,
+    /// Where the config in use came from.
+    pub config_source: ConfigSource,
+    /// Whether the config in use should be exported. This is done by the
+    /// caller, as the current layout is kept by the painter.
+    pub is_exporting_config: bool,
+    /// Where the config was last exported to, or why it couldn't be.
+    pub config_export: Option<Result<PathBuf, String>>,
+    /// Whether to stop bottom, as with Ctrl+Z. This is done by the caller, as the
+    /// terminal has to be cleaned up first.
+    pub is_suspending: bool,
+    /// Where the state was last dumped to, or why it couldn't be.
+    pub state_dump: Option<Result<PathBuf, String>>,
+    /// The names of the profiles in the config.
+    pub profiles: Vec<String>,
+    /// The profile in use, if any.
+    pub profile: Option<String>,
+    /// Whether to switch to the next profile. This is done by the caller, as
+    /// the app is set up again from the config.
+    pub is_switching_profile: bool,
+    /// Why switching profiles last failed, if it did.
+    pub profile_error: Option<String>,
+    /// How each data source fared in the latest collection.
+    pub source_statuses: Vec<(&'static str, SourceStatus)>,
+    /// Problems data sources ran into in the latest collection.
+    pub collection_issues: Vec<CollectionIssue>,
+    pub debug_stats: DebugStats,
+    stale_widgets: UsedWidgets,
+    #[cfg(feature = "plugins")]
+    plugins: Vec<PluginInfo>,
 }
 
 impl App {
@@ -128,7 +375,7 @@ impl App {
     pub fn new(
         app_config_fields: AppConfigFields, states: AppWidgetStates,
         widget_map: HashMap<u64, BottomWidget>, current_widget: BottomWidget,
-        used_widgets: UsedWidgets, filters: DataFilters, is_expanded: bool,
+        used_widgets: UsedWidgets, filters: DataFilters, is_expanded: bool, pages: Vec<PageState>,
     ) -> Self {
         Self {
             awaiting_second_char: false,
@@ -149,13 +396,309 @@ impl App {
             app_config_fields,
             widget_map,
             current_widget,
+            pages,
+            current_page: 0,
+            hidden_widgets: HashSet::default(),
             used_widgets,
             filters,
+            time_range_selection: None,
+            layout_borders: Vec::new(),
+            layout_drag: None,
+            inspect_cursor: None,
+            mouse_hover: None,
+            click_tracker: ClickTracker::default(),
+            scroll_tracker: ScrollTracker::default(),
+            context_menu: None,
+            process_details: None,
+            global_search: None,
+            diagnostics: None,
+            config_source: ConfigSource::default(),
+            is_exporting_config: false,
+            config_export: None,
+            is_suspending: false,
+            state_dump: None,
+            profiles: Vec::new(),
+            profile: None,
+            is_switching_profile: false,
+            profile_error: None,
+            source_statuses: Vec::new(),
+            collection_issues: Vec::new(),
+            debug_stats: DebugStats::default(),
+            stale_widgets: UsedWidgets::default(),
+            #[cfg(feature = "plugins")]
+            plugins: Vec::new(),
+        }
+    }
+
+    /// Returns the profile after the one in use, going back to using no profile
+    /// after the last one.
+    pub fn next_profile(&self) -> Option<&str> {
+        let next = match &self.profile {
+            Some(profile) => self
+                .profiles
+                .iter()
+                .position(|name| name == profile)
+                .map_or(0, |index| index + 1),
+            None => 0,
+        };
+
+        self.profiles.get(next).map(String::as_str)
+    }
+
+    /// Carries the collected data and other runtime state over from `previous`,
+    /// which this app replaces after switching profiles. `previous` is left
+    /// as-is if this fails.
+    pub fn take_over(&mut self, previous: &mut App) -> anyhow::Result<()> {
+        #[cfg(feature = "plugins")]
+        self.set_plugins(previous.plugins.clone())?;
+
+        self.data_collection = std::mem::take(&mut previous.data_collection);
+        self.config_source = std::mem::take(&mut previous.config_source);
+        self.config_export = previous.config_export.take();
+        self.state_dump = previous.state_dump.take();
+        self.source_statuses = std::mem::take(&mut previous.source_statuses);
+        self.collection_issues = std::mem::take(&mut previous.collection_issues);
+        self.debug_stats = std::mem::take(&mut previous.debug_stats);
+
+        // The terminal was already set up for one or the other.
+        self.app_config_fields.alt_screen = previous.app_config_fields.alt_screen;
+
+        self.convert_visible_data();
+        self.is_force_redraw = true;
+
+        Ok(())
+    }
+
+    /// Sets up the loaded plugins, adding their columns to all process widgets.
+    /// This fails if the layout uses a plugin widget that no plugin provides.
+    #[cfg(feature = "plugins")]
+    pub fn set_plugins(&mut self, plugins: Vec<PluginInfo>) -> anyhow::Result<()> {
+        for widget in self.widget_map.values() {
+            if let BottomWidgetType::Plugin(name) = &widget.widget_type {
+                if !plugins
+                    .iter()
+                    .any(|plugin| &plugin.name == name && plugin.widget.is_some())
+                {
+                    bail!("no loaded plugin provides a widget named '{name}'");
+                }
+            }
+        }
+
+        for (index, plugin) in plugins.iter().enumerate() {
+            if let Some(header) = &plugin.column_header {
+                // Column headers have to live as long as the widgets, which is
+                // the rest of the program anyways.
+                let header: &'static str = Box::leak(header.clone().into_boxed_str());
+
+                for proc_widget_state in self.states.proc_state.widget_states.values_mut() {
+                    proc_widget_state.add_plugin_column(index, header);
+                }
+            }
+        }
+
+        self.plugins = plugins;
+
+        Ok(())
+    }
+
+    /// Returns the table headers of the widget of the plugin named `name`, if
+    /// it has a table widget.
+    #[cfg(feature = "plugins")]
+    pub fn plugin_widget_headers(&self, name: &str) -> Option<&[String]> {
+        self.plugins
+            .iter()
+            .find(|plugin| plugin.name == name)
+            .and_then(|plugin| match &plugin.widget {
+                Some(PluginWidgetKind::Table { headers }) => Some(headers.as_slice()),
+                _ => None,
+            })
+    }
+
+    /// Returns the widgets that are currently visible, which are those on the
+    /// current page that aren't hidden, along with anything the status bar or
+    /// meter header needs.
+    pub fn visible_widgets(&self) -> UsedWidgets {
+        use BottomWidgetType::*;
+
+        let visible = if self.is_expanded {
+            UsedWidgets::for_widget_type(&self.current_widget.widget_type)
+        } else if self.app_config_fields.use_basic_mode {
+            let basic_widgets = UsedWidgets::for_widget_type(&BasicCpu)
+                .union(&UsedWidgets::for_widget_type(&BasicMem))
+                .union(&UsedWidgets::for_widget_type(&BasicNet));
+
+            match &self.states.basic_table_widget_state {
+                Some(state) => basic_widgets.union(&UsedWidgets::for_widget_type(
+                    &state.currently_displayed_widget_type,
+                )),
+                None => basic_widgets,
+            }
+        } else if let Some(page) = self.pages.get(self.current_page) {
+            page.widget_ids
+                .clone()
+                .filter_map(|id| self.widget_map.get(&id))
+                .filter(|widget| !self.hidden_widgets.contains(&widget.parent_id()))
+                .fold(UsedWidgets::default(), |visible, widget| {
+                    visible.union(&UsedWidgets::for_widget_type(&widget.widget_type))
+                })
+        } else {
+            return self.used_widgets;
+        };
+
+        // The status bar and meter header are shown on every page.
+        let status_bar = self.app_config_fields.status_bar.as_ref();
+        let meter_header = self.app_config_fields.meter_header.as_ref();
+        let visible = UsedWidgets {
+            use_cpu: visible.use_cpu
+                || status_bar.is_some_and(StatusBar::uses_cpu)
+                || meter_header.is_some_and(MeterHeader::uses_cpu),
+            use_mem: visible.use_mem
+                || status_bar.is_some_and(StatusBar::uses_mem)
+                || meter_header.is_some_and(MeterHeader::uses_mem),
+            ..visible
+        };
+
+        self.used_widgets.intersection(&visible)
+    }
+
+    /// Returns the optional process details that need to be collected for what
+    /// the process widgets and the process details popup show.
+    #[cfg(target_os = "linux")]
+    pub fn process_fields(&self) -> ProcessFields {
+        let details = ProcessFields {
+            details_pid: self.process_details,
+            ..ProcessFields::default()
+        };
+
+        self.states
+            .proc_state
+            .widget_states
+            .values()
+            .fold(details, |fields, proc| fields.union(proc.process_fields()))
+    }
+
+    /// Converts the data for all visible widgets into a displayable form. Hidden
+    /// widgets are skipped, and will instead be converted once they become
+    /// visible.
+    pub fn convert_visible_data(&mut self) {
+        let visible = self.visible_widgets();
+        self.convert_data(visible);
+        self.stale_widgets = self.used_widgets.difference(&visible);
+    }
+
+    /// Converts the data for any widgets that were hidden during the last
+    /// conversion but are now visible.
+    fn convert_stale_data(&mut self) {
+        let widgets = self.stale_widgets.intersection(&self.visible_widgets());
+        if !widgets.is_empty() {
+            self.convert_data(widgets);
+            self.stale_widgets = self.stale_widgets.difference(&widgets);
+        }
+    }
+
+    /// Converts the data for the given widgets into a displayable form.
+    fn convert_data(&mut self, widgets: UsedWidgets) {
+        let data_source = match &self.frozen_state {
+            FrozenState::NotFrozen => &self.data_collection,
+            FrozenState::Frozen(data) => data,
+        };
+
+        if widgets.use_net {
+            let network_data = convert_network_points(
+                data_source,
+                self.app_config_fields.use_basic_mode
+                    || self.app_config_fields.use_old_network_legend,
+                &self.app_config_fields.network_scale_type,
+                &self.app_config_fields.network_unit_type,
+                self.app_config_fields.network_use_binary_prefix,
+            );
+            self.converted_data.network_data_rx = network_data.rx;
+            self.converted_data.network_data_tx = network_data.tx;
+            self.converted_data.rx_display = network_data.rx_display;
+            self.converted_data.tx_display = network_data.tx_display;
+            if let Some(total_rx_display) = network_data.total_rx_display {
+                self.converted_data.total_rx_display = total_rx_display;
+            }
+            if let Some(total_tx_display) = network_data.total_tx_display {
+                self.converted_data.total_tx_display = total_tx_display;
+            }
+            self.converted_data.link = data_source
+                .network_harvest
+                .link_capacity()
+                .map(|speed| (speed, data_source.network_harvest.link_duplex()));
+        }
+
+        if widgets.use_disk {
+            self.converted_data.convert_disk_data(data_source);
+
+            for disk in self.states.disk_state.widget_states.values_mut() {
+                disk.force_data_update();
+            }
+        }
+
+        if widgets.use_temp {
+            self.converted_data
+                .convert_temp_data(data_source, self.app_config_fields.temperature_type);
+
+            for temp in self.states.temp_state.widget_states.values_mut() {
+                temp.force_data_update();
+            }
+        }
+
+        if widgets.use_mem {
+            self.converted_data.mem_data = convert_mem_data_points(data_source);
+
+            #[cfg(not(target_os = "windows"))]
+            {
+                self.converted_data.cache_data =
+                    crate::data_conversion::convert_cache_data_points(data_source);
+            }
+
+            self.converted_data.swap_data = convert_swap_data_points(data_source);
+
+            #[cfg(feature = "zfs")]
+            {
+                self.converted_data.arc_data =
+                    crate::data_conversion::convert_arc_data_points(data_source);
+            }
+
+            #[cfg(feature = "gpu")]
+            {
+                self.converted_data.gpu_data =
+                    crate::data_conversion::convert_gpu_data(data_source);
+            }
+
+            self.converted_data.mem_labels = convert_mem_label(&data_source.memory_harvest);
+
+            self.converted_data.swap_labels = convert_mem_label(&data_source.swap_harvest);
+
+            #[cfg(not(target_os = "windows"))]
+            {
+                self.converted_data.cache_labels = convert_mem_label(&data_source.cache_harvest);
+            }
+
+            #[cfg(feature = "zfs")]
+            {
+                self.converted_data.arc_labels = convert_mem_label(&data_source.arc_harvest);
+            }
+        }
+
+        if widgets.use_cpu {
+            self.converted_data.convert_cpu_data(data_source);
+            self.converted_data.load_avg_data = data_source.load_avg_harvest;
+        }
+
+        if widgets.use_proc {
+            for proc in self.states.proc_state.widget_states.values_mut() {
+                proc.force_data_update();
+            }
         }
     }
 
     /// Update the data in the [`App`].
     pub fn update_data(&mut self) {
+        self.convert_stale_data();
+
         let data_source = match &self.frozen_state {
             FrozenState::NotFrozen => &self.data_collection,
             FrozenState::Frozen(data) => data,
@@ -179,8 +722,14 @@ impl App {
         // FIXME: This is a bit of a temp hack to move data over.
         {
             let data = &self.converted_data.cpu_data;
-            for cpu in self.states.cpu_state.widget_states.values_mut() {
-                cpu.update_table(data);
+            for (widget_id, cpu) in self.states.cpu_state.widget_states.iter_mut() {
+                let inspected_values = self
+                    .inspect_cursor
+                    .filter(|cursor| cursor.widget_id == *widget_id)
+                    .and_then(|cursor| data_source.timed_data_at(cursor.time))
+                    .map(|data| data.cpu_data.as_slice());
+
+                cpu.update_table(data, inspected_values);
             }
         }
         {
@@ -207,17 +756,20 @@ impl App {
             self.converted_data.mem_data = convert_mem_data_points(data_source);
             #[cfg(not(target_os = "windows"))]
             {
-                self.converted_data.cache_data = crate::convert_cache_data_points(data_source);
+                self.converted_data.cache_data =
+                    crate::data_conversion::convert_cache_data_points(data_source);
             }
             self.converted_data.swap_data = convert_swap_data_points(data_source);
             #[cfg(feature = "zfs")]
             {
-                self.converted_data.arc_data = crate::convert_arc_data_points(data_source);
+                self.converted_data.arc_data =
+                    crate::data_conversion::convert_arc_data_points(data_source);
             }
 
             #[cfg(feature = "gpu")]
             {
-                self.converted_data.gpu_data = crate::convert_gpu_data(data_source);
+                self.converted_data.gpu_data =
+                    crate::data_conversion::convert_gpu_data(data_source);
             }
             self.states.mem_state.force_update = None;
         }
@@ -240,7 +792,7 @@ impl App {
         self.reset_multi_tap_keys();
 
         // Reset dialog state
-        self.help_dialog_state.is_showing_help = false;
+        self.help_dialog_state.close();
         self.delete_dialog_state.is_showing_dd = false;
 
         // Close all searches and reset it
@@ -283,15 +835,34 @@ impl App {
     pub fn on_esc(&mut self) {
         self.reset_multi_tap_keys();
         if self.is_in_dialog() {
-            if self.help_dialog_state.is_showing_help {
-                self.help_dialog_state.is_showing_help = false;
-                self.help_dialog_state.scroll_state.current_scroll_index = 0;
+            if self.context_menu.is_some() {
+                self.context_menu = None;
+            } else if self.global_search.is_some() {
+                self.global_search = None;
+            } else if self.process_details.is_some() {
+                self.process_details = None;
+            } else if self.diagnostics.is_some() {
+                self.diagnostics = None;
+            } else if self.help_dialog_state.is_showing_help {
+                if self.help_dialog_state.is_searching || !self.help_dialog_state.query.is_empty() {
+                    self.help_dialog_state.clear_search();
+                } else {
+                    self.help_dialog_state.close();
+                }
             } else {
                 self.close_dd();
             }
 
             self.is_force_redraw = true;
         } else {
+            if self
+                .inspect_cursor
+                .is_some_and(|cursor| cursor.widget_id == self.current_widget.widget_id)
+            {
+                self.inspect_cursor = None;
+                return;
+            }
+
             match self.current_widget.widget_type {
                 BottomWidgetType::Proc => {
                     if let Some(pws) = self
@@ -345,6 +916,14 @@ impl App {
         }
     }
 
+    /// Whether keys that type text should be typed into a search, rather than
+    /// trigger actions.
+    pub fn is_typing(&self) -> bool {
+        self.is_in_search_widget()
+            || self.global_search.is_some()
+            || self.help_dialog_state.is_searching
+    }
+
     pub fn is_in_search_widget(&self) -> bool {
         matches!(
             self.current_widget.widget_type,
@@ -358,10 +937,15 @@ impl App {
     }
 
     fn is_in_dialog(&self) -> bool {
-        self.help_dialog_state.is_showing_help || self.delete_dialog_state.is_showing_dd
+        self.help_dialog_state.is_showing_help
+            || self.delete_dialog_state.is_showing_dd
+            || self.context_menu.is_some()
+            || self.process_details.is_some()
+            || self.global_search.is_some()
+            || self.diagnostics.is_some()
     }
 
-    fn ignore_normal_keybinds(&self) -> bool {
+    pub fn ignore_normal_keybinds(&self) -> bool {
         self.is_in_dialog()
     }
 
@@ -398,11 +982,16 @@ impl App {
                         self.is_force_redraw = true;
                     }
                 }
-                _ => {}
+                _ => self.global_search = Some(GlobalSearch::default()),
             }
         }
     }
 
+    /// Toggles the debug overlay, which shows how long recent ticks took.
+    pub fn toggle_debug_overlay(&mut self) {
+        self.debug_stats.is_showing = !self.debug_stats.is_showing;
+    }
+
     pub fn toggle_sort_menu(&mut self) {
         let widget_id = self.current_widget.widget_id
             - match &self.current_widget.widget_type {
@@ -525,18 +1114,30 @@ impl App {
                 }
                 ProcWidgetMode::Normal => {
                     proc_widget_state.mode = ProcWidgetMode::Tree {
-                        collapsed_pids: Default::default(),
+                        collapsed: Default::default(),
                     };
                     proc_widget_state.force_rerender_and_update();
                 }
-                ProcWidgetMode::Grouped => {}
+                ProcWidgetMode::Grouped | ProcWidgetMode::UserGrouped { .. } => {}
             }
         }
     }
 
     /// One of two functions allowed to run while in a dialog...
     pub fn on_enter(&mut self) {
-        if self.delete_dialog_state.is_showing_dd {
+        if self.context_menu.is_some() {
+            self.activate_context_menu_action();
+        } else if let Some(search) = self.global_search.take() {
+            if let Some(result) = search.selected_result() {
+                self.jump_to_search_result(result);
+            }
+        } else if self.help_dialog_state.is_searching {
+            self.help_dialog_state.is_searching = false;
+        } else if self.process_details.is_some() {
+            self.process_details = None;
+        } else if self.diagnostics.is_some() {
+            self.diagnostics = None;
+        } else if self.delete_dialog_state.is_showing_dd {
             if self.dd_err.is_some() {
                 self.close_dd();
             } else if self.delete_dialog_state.selected_signal != KillSignal::Cancel {
@@ -624,6 +1225,17 @@ impl App {
     }
 
     pub fn on_backspace(&mut self) {
+        if self.help_dialog_state.is_searching {
+            self.help_dialog_state.query.pop();
+            self.help_dialog_state.scroll_state.current_scroll_index = 0;
+            return;
+        }
+        if let Some(search) = &mut self.global_search {
+            search.query.pop();
+            self.update_global_search();
+            return;
+        }
+
         if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             let is_in_search_widget = self.is_in_search_widget();
             if let Some(proc_widget_state) = self
@@ -703,6 +1315,12 @@ impl App {
     pub fn on_up_key(&mut self) {
         if !self.is_in_dialog() {
             self.decrement_position_count();
+        } else if let Some(menu) = &mut self.context_menu {
+            menu.select_previous();
+        } else if let Some(search) = &mut self.global_search {
+            search.select_previous();
+        } else if let Some(scroll) = &mut self.diagnostics {
+            scroll.current_scroll_index = scroll.current_scroll_index.saturating_sub(1);
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
         } else if self.delete_dialog_state.is_showing_dd {
@@ -724,6 +1342,13 @@ impl App {
     pub fn on_down_key(&mut self) {
         if !self.is_in_dialog() {
             self.increment_position_count();
+        } else if let Some(menu) = &mut self.context_menu {
+            menu.select_next();
+        } else if let Some(search) = &mut self.global_search {
+            search.select_next();
+        } else if let Some(scroll) = &mut self.diagnostics {
+            scroll.current_scroll_index =
+                (scroll.current_scroll_index + 1).min(scroll.max_scroll_index);
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
         } else if self.delete_dialog_state.is_showing_dd {
@@ -777,6 +1402,9 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::Cpu | BottomWidgetType::Mem | BottomWidgetType::Net => {
+                    self.move_inspect_cursor(true);
+                }
                 _ => {}
             }
         } else if self.delete_dialog_state.is_showing_dd {
@@ -843,6 +1471,9 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::Cpu | BottomWidgetType::Mem | BottomWidgetType::Net => {
+                    self.move_inspect_cursor(false);
+                }
                 _ => {}
             }
         } else if self.delete_dialog_state.is_showing_dd {
@@ -1025,7 +1656,13 @@ impl App {
     }
 
     pub fn clear_search(&mut self) {
-        if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
+        if self.help_dialog_state.is_searching {
+            self.help_dialog_state.query.clear();
+            self.help_dialog_state.scroll_state.current_scroll_index = 0;
+        } else if let Some(search) = &mut self.global_search {
+            search.query.clear();
+            self.update_global_search();
+        } else if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             if let Some(proc_widget_state) = self
                 .states
                 .proc_state
@@ -1100,29 +1737,85 @@ impl App {
     pub fn start_killing_process(&mut self) {
         self.reset_multi_tap_keys();
 
-        if let Some(pws) = self
+        if let Some(current_process) = self.highlighted_processes() {
+            self.to_delete_process_list = Some(current_process);
+            self.delete_dialog_state.is_showing_dd = true;
+            self.is_determining_widget_boundary = true;
+        }
+        // FIXME: This should handle errors.
+    }
+
+    /// Returns the name and PIDs of the selected process in the current
+    /// process widget. There is more than one PID if processes are grouped.
+    fn highlighted_processes(&self) -> Option<(String, Vec<Pid>)> {
+        let pws = self
             .states
             .proc_state
             .widget_states
-            .get(&self.current_widget.widget_id)
-        {
-            if let Some(current) = pws.table.current_item() {
-                let id = current.id.to_string();
-                if let Some(pids) = pws
-                    .id_pid_map
-                    .get(&id)
-                    .cloned()
-                    .or_else(|| Some(vec![current.pid]))
-                {
-                    let current_process = (id, pids);
+            .get(&self.current_widget.widget_id)?;
+        let current = pws.table.current_item()?;
+        Some((current.id.to_string(), pws.row_pids(current)))
+    }
+
+    /// Runs the selected action in the context menu. The menu is closed,
+    /// unless the action failed.
+    fn activate_context_menu_action(&mut self) {
+        let Some(menu) = self.context_menu.take() else {
+            return;
+        };
 
-                    self.to_delete_process_list = Some(current_process);
-                    self.delete_dialog_state.is_showing_dd = true;
-                    self.is_determining_widget_boundary = true;
+        let result = match menu.selected_action() {
+            ContextMenuAction::Kill => {
+                self.to_delete_process_list = Some((menu.name.clone(), menu.pids.clone()));
+                self.delete_dialog_state.is_showing_dd = true;
+                self.is_determining_widget_boundary = true;
+                Ok(())
+            }
+            #[cfg(target_family = "unix")]
+            ContextMenuAction::RaisePriority => menu
+                .pids
+                .iter()
+                .try_for_each(|pid| process_priority::renice_process_given_pid(*pid, -1)),
+            #[cfg(target_family = "unix")]
+            ContextMenuAction::LowerPriority => menu
+                .pids
+                .iter()
+                .try_for_each(|pid| process_priority::renice_process_given_pid(*pid, 1)),
+            #[cfg(target_os = "linux")]
+            ContextMenuAction::RaiseIoPriority => menu
+                .pids
+                .iter()
+                .try_for_each(|pid| process_priority::change_io_priority_given_pid(*pid, true)),
+            #[cfg(target_os = "linux")]
+            ContextMenuAction::LowerIoPriority => menu
+                .pids
+                .iter()
+                .try_for_each(|pid| process_priority::change_io_priority_given_pid(*pid, false)),
+            ContextMenuAction::Details => {
+                self.process_details = menu.pids.first().copied();
+                Ok(())
+            }
+            #[cfg(target_os = "linux")]
+            ContextMenuAction::SameNamespaces => {
+                self.search_same_namespaces(menu.widget_id, menu.pids.first().copied())
+            }
+            ContextMenuAction::CopyPid => {
+                copy_to_clipboard(&pids_text(&menu.pids)).map_err(Into::into)
+            }
+            ContextMenuAction::CopyCommand => {
+                match menu.pids.first().and_then(|pid| self.process_command(*pid)) {
+                    Some(command) => copy_to_clipboard(command).map_err(Into::into),
+                    None => Err(anyhow::anyhow!("the process no longer exists.")),
                 }
             }
+        };
+
+        if let Err(err) = result {
+            self.context_menu = Some(ContextMenu {
+                error: Some(err.to_string()),
+                ..menu
+            });
         }
-        // FIXME: This should handle errors.
     }
 
     pub fn on_char_key(&mut self, caught_char: char) {
@@ -1179,6 +1872,9 @@ impl App {
                 }
             }
             self.handle_char(caught_char);
+        } else if self.help_dialog_state.is_searching {
+            self.help_dialog_state.query.push(caught_char);
+            self.help_dialog_state.scroll_state.current_scroll_index = 0;
         } else if self.help_dialog_state.is_showing_help {
             match caught_char {
                 '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
@@ -1193,6 +1889,16 @@ impl App {
                     }
                 }
                 'j' | 'k' | 'g' | 'G' => self.handle_char(caught_char),
+                '/' => self.help_dialog_state.is_searching = true,
+                _ => {}
+            }
+        } else if let Some(search) = &mut self.global_search {
+            search.query.push(caught_char);
+            self.update_global_search();
+        } else if self.context_menu.is_some() {
+            match caught_char {
+                'j' => self.on_down_key(),
+                'k' => self.on_up_key(),
                 _ => {}
             }
         } else if self.delete_dialog_state.is_showing_dd {
@@ -1228,10 +1934,32 @@ impl App {
     }
 
     // FIXME: Refactor this system...
+    /// Selects and shows the table at `index` in basic mode's order of tables,
+    /// if there is one.
+    fn show_basic_table(&mut self, index: usize) {
+        let Some(basic_table_widget_state) = &mut self.states.basic_table_widget_state else {
+            return;
+        };
+        let Some(widget) = basic_table_widget_state
+            .table_ids
+            .get(index)
+            .and_then(|widget_id| self.widget_map.get(widget_id))
+        else {
+            return;
+        };
+
+        basic_table_widget_state.currently_displayed_widget_id = widget.widget_id;
+        basic_table_widget_state.currently_displayed_widget_type = widget.widget_type.clone();
+        self.current_widget = widget.clone();
+        self.reset_multi_tap_keys();
+    }
+
     fn handle_char(&mut self, caught_char: char) {
         match caught_char {
-            '/' => {
-                self.on_slash();
+            '1'..='9' if self.states.basic_table_widget_state.is_some() => {
+                if let Some(index) = caught_char.to_digit(10) {
+                    self.show_basic_table(index as usize - 1);
+                }
             }
             'd' => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
@@ -1277,9 +2005,6 @@ impl App {
             'G' => self.skip_to_last(),
             'k' => self.on_up_key(),
             'j' => self.on_down_key(),
-            'f' => {
-                self.frozen_state.toggle(&self.data_collection); // TODO: Thawing should force a full data refresh and redraw immediately.
-            }
             'c' => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     if let Some(proc_widget_state) = self
@@ -1377,10 +2102,6 @@ impl App {
                     }
                 }
             }
-            '?' => {
-                self.help_dialog_state.is_showing_help = true;
-                self.is_force_redraw = true;
-            }
             'H' | 'A' => self.move_widget_selection(&WidgetDirection::Left),
             'L' | 'D' => self.move_widget_selection(&WidgetDirection::Right),
             'K' | 'W' => self.move_widget_selection(&WidgetDirection::Up),
@@ -1403,10 +2124,6 @@ impl App {
                     disk.set_index(4);
                 }
             }
-            '+' => self.on_plus(),
-            '-' => self.on_minus(),
-            '=' => self.reset_zoom(),
-            'e' => self.toggle_expand_widget(),
             's' => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     self.toggle_sort_menu()
@@ -1447,8 +2164,24 @@ impl App {
                     disk.set_index(7);
                 }
             }
-            'I' => self.invert_sort(),
-            '%' => self.toggle_percentages(),
+            '[' => {
+                let changed = self.frozen_state.step_back(1);
+                self.on_frozen_data_scrubbed(changed);
+            }
+            ']' => {
+                let changed = self.frozen_state.step_forward(1);
+                self.on_frozen_data_scrubbed(changed);
+            }
+            '{' => {
+                let delta = Duration::from_millis(self.app_config_fields.time_interval);
+                let changed = self.frozen_state.step_back_by(delta);
+                self.on_frozen_data_scrubbed(changed);
+            }
+            '}' => {
+                let delta = Duration::from_millis(self.app_config_fields.time_interval);
+                let changed = self.frozen_state.step_forward_by(delta);
+                self.on_frozen_data_scrubbed(changed);
+            }
             _ => {}
         }
 
@@ -1459,64 +2192,571 @@ impl App {
         }
     }
 
-    pub fn kill_highlighted_process(&mut self) -> anyhow::Result<()> {
-        if let BottomWidgetType::Proc = self.current_widget.widget_type {
-            if let Some((_, pids)) = &self.to_delete_process_list {
-                #[cfg(target_family = "unix")]
-                let signal = match self.delete_dialog_state.selected_signal {
-                    KillSignal::Kill(sig) => sig,
-                    KillSignal::Cancel => 15, // should never happen, so just TERM
-                };
-                for pid in pids {
-                    #[cfg(target_family = "unix")]
-                    {
-                        process_killer::kill_process_given_pid(*pid, signal)?;
-                    }
-                    #[cfg(target_os = "windows")]
-                    {
-                        process_killer::kill_process_given_pid(*pid)?;
-                    }
+    /// Performs an action bound to a key. Quitting and resetting are handled
+    /// by the caller, as they affect more than just the app state.
+    pub fn on_action(&mut self, action: Action) {
+        self.reset_multi_tap_keys();
+
+        match action {
+            Action::Quit | Action::Reset => {}
+            Action::Search => self.on_slash(),
+            Action::Kill => self.start_killing_process(),
+            Action::TreeToggle => self.toggle_tree_mode(),
+            Action::SortMenu => self.toggle_sort_menu(),
+            Action::Help => {
+                self.help_dialog_state.is_showing_help = true;
+                self.is_force_redraw = true;
+            }
+            Action::Expand => self.toggle_expand_widget(),
+            Action::Freeze => {
+                self.frozen_state.toggle(&self.data_collection); // TODO: Thawing should force a full data refresh and redraw immediately.
+            }
+            Action::ZoomIn => self.on_plus(),
+            Action::ZoomOut => self.on_minus(),
+            Action::ResetZoom => self.reset_zoom(),
+            Action::CollapseBranch => self.collapse_process_branch(),
+            Action::CollapseAll => self.set_all_process_branches_collapsed(true),
+            Action::ExpandAll => self.set_all_process_branches_collapsed(false),
+            Action::TreeTotals => {
+                if let Some(pws) = self
+                    .states
+                    .proc_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    pws.toggle_tree_totals();
                 }
             }
-            self.to_delete_process_list = None;
-            Ok(())
-        } else {
-            bail!("Cannot kill processes if the current widget is not the Process widget!");
-        }
-    }
-
-    pub fn get_to_delete_processes(&self) -> Option<(String, Vec<Pid>)> {
-        self.to_delete_process_list.clone()
-    }
-
-    fn toggle_expand_widget(&mut self) {
-        if self.is_expanded {
-            self.is_expanded = false;
-            self.is_force_redraw = true;
-        } else {
-            self.expand_widget();
-        }
-    }
-
-    fn expand_widget(&mut self) {
-        // TODO: [BASIC] Expansion in basic mode.
-        if !self.ignore_normal_keybinds() && !self.app_config_fields.use_basic_mode {
-            // Pop-out mode.  We ignore if in process search.
-
-            match self.current_widget.widget_type {
-                BottomWidgetType::ProcSearch => {}
-                _ => {
-                    self.is_expanded = true;
-                    self.is_force_redraw = true;
+            Action::GroupByUser => {
+                if let Some(pws) = self
+                    .states
+                    .proc_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    pws.toggle_user_grouping();
                 }
             }
-        }
-    }
-
-    pub fn move_widget_selection(&mut self, direction: &WidgetDirection) {
-        // Since we only want to call reset once, we do it like this to avoid
+            Action::PinMount => {
+                if let Some(disk) = self
+                    .states
+                    .disk_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    disk.toggle_pin_current();
+                }
+            }
+            Action::CycleMount => {
+                if let Some(disk) = self
+                    .states
+                    .disk_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    disk.focus_next_mount();
+                }
+            }
+            Action::HideSensor => {
+                if let Some(temp) = self
+                    .states
+                    .temp_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    temp.toggle_hide_current();
+                }
+            }
+            Action::ShowHiddenSensors => {
+                if let Some(temp) = self
+                    .states
+                    .temp_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    temp.toggle_show_hidden();
+                }
+            }
+            Action::InvertSort => self.invert_sort(),
+            Action::Inspect => self.toggle_inspect_cursor(),
+            Action::Percentages => self.toggle_percentages(),
+            Action::DebugOverlay => self.toggle_debug_overlay(),
+            Action::LogLevel => {
+                // The debug overlay shows the new level.
+                crate::utils::logging::cycle_log_level();
+                self.debug_stats.is_showing = true;
+            }
+            Action::Diagnostics => {
+                self.diagnostics = Some(ParagraphScrollState::default());
+                self.is_force_redraw = true;
+            }
+            Action::ExportConfig => {
+                // Show how the export went in the diagnostics viewer.
+                self.is_exporting_config = true;
+                self.diagnostics = Some(ParagraphScrollState::default());
+                self.is_force_redraw = true;
+            }
+            Action::NextProfile => {
+                if self.profiles.is_empty() {
+                    self.profile_error = Some("the config has no profiles.".to_string());
+                    self.diagnostics = Some(ParagraphScrollState::default());
+                    self.is_force_redraw = true;
+                } else {
+                    self.is_switching_profile = true;
+                }
+            }
+            Action::IgnoreCase => self.toggle_ignore_case(),
+            Action::WholeWord => self.toggle_search_whole_word(),
+            Action::Regex => self.toggle_search_regex(),
+            Action::NextPage => self.next_page(),
+            Action::PreviousPage => self.previous_page(),
+            Action::HideWidget => self.hide_widget(),
+            Action::ShowWidgets => self.show_widgets(),
+            Action::CopyRow => self.copy_selected_row(),
+            Action::CopyPid => self.copy_selected_pids(),
+            Action::CopyCommand => self.copy_selected_command(),
+        }
+    }
+
+    /// Copies the selected row of the current table widget to the clipboard,
+    /// with its columns separated by tabs.
+    fn copy_selected_row(&self) {
+        let widget_id = self.current_widget.widget_id;
+        let text = match self.current_widget.widget_type {
+            BottomWidgetType::Proc => self
+                .states
+                .proc_state
+                .get_widget_state(widget_id)
+                .and_then(|state| state.table.current_row_text()),
+            BottomWidgetType::CpuLegend => self
+                .states
+                .cpu_state
+                .get_widget_state(widget_id - 1)
+                .and_then(|state| state.table.current_row_text()),
+            BottomWidgetType::Temp => self
+                .states
+                .temp_state
+                .get_widget_state(widget_id)
+                .and_then(|state| state.table.current_row_text()),
+            BottomWidgetType::Disk => self
+                .states
+                .disk_state
+                .get_widget_state(widget_id)
+                .and_then(|state| state.table.current_row_text()),
+            _ => None,
+        };
+
+        if let Some(text) = text {
+            // Failing to write to the terminal isn't worth surfacing here.
+            let _ = copy_to_clipboard(&text);
+        }
+    }
+
+    /// Copies the PIDs of the selected process, or all the processes in a
+    /// selected group, to the clipboard.
+    fn copy_selected_pids(&self) {
+        if let Some((_, pids)) = self.highlighted_processes() {
+            let _ = copy_to_clipboard(&pids_text(&pids));
+        }
+    }
+
+    /// Copies the command of the selected process to the clipboard.
+    fn copy_selected_command(&self) {
+        let command = self
+            .highlighted_processes()
+            .and_then(|(_, pids)| self.process_command(*pids.first()?));
+
+        if let Some(command) = command {
+            let _ = copy_to_clipboard(command);
+        }
+    }
+
+    /// Returns the command of a process, from frozen data if frozen.
+    /// Searches the process widget with the given ID for processes in the same
+    /// namespaces as the process with the given PID.
+    #[cfg(target_os = "linux")]
+    fn search_same_namespaces(&mut self, widget_id: u64, pid: Option<Pid>) -> anyhow::Result<()> {
+        let data = match &self.frozen_state {
+            FrozenState::NotFrozen => &self.data_collection,
+            FrozenState::Frozen(data) => data,
+        };
+
+        let process = pid
+            .and_then(|pid| data.process_data.process_harvest.get(&pid))
+            .ok_or_else(|| anyhow::anyhow!("the process no longer exists."))?;
+
+        // Namespaces are only collected while something uses them, so they're
+        // read here in case they weren't.
+        let query = Namespaces::read_process(process.pid)
+            .search_query()
+            .ok_or_else(|| anyhow::anyhow!("its namespaces couldn't be read."))?;
+
+        if let Some(state) = self.states.proc_state.get_mut_widget_state(widget_id) {
+            state.proc_search.search_state.is_enabled = true;
+            state.set_search_query(query);
+        }
+
+        Ok(())
+    }
+
+    fn process_command(&self, pid: Pid) -> Option<&str> {
+        let data = match &self.frozen_state {
+            FrozenState::NotFrozen => &self.data_collection,
+            FrozenState::Frozen(data) => data,
+        };
+
+        data.process_data
+            .process_harvest
+            .get(&pid)
+            .map(|process| &*process.command)
+    }
+
+    /// Switches to the next layout page, wrapping around at the end.
+    pub fn next_page(&mut self) {
+        if !self.pages.is_empty() {
+            self.switch_to_page((self.current_page + 1) % self.pages.len());
+        }
+    }
+
+    /// Switches to the previous layout page, wrapping around at the start.
+    pub fn previous_page(&mut self) {
+        if !self.pages.is_empty() {
+            self.switch_to_page((self.current_page + self.pages.len() - 1) % self.pages.len());
+        }
+    }
+
+    /// Switches to the layout page at `index`, selecting the widget that was
+    /// last selected on it.
+    pub fn switch_to_page(&mut self, index: usize) {
+        if index == self.current_page || index >= self.pages.len() {
+            return;
+        }
+
+        self.pages[self.current_page].selected_widget_id = self.current_widget.widget_id;
+        self.current_page = index;
+
+        if let Some(widget) = self.widget_map.get(&self.pages[index].selected_widget_id) {
+            self.current_widget = widget.clone();
+        }
+
+        self.is_expanded = false;
+        self.layout_drag = None;
+        self.reset_multi_tap_keys();
+        self.is_force_redraw = true;
+    }
+
+    /// Hides the selected widget, letting the rest of its layout page take up
+    /// its space, and selects a nearby widget instead. The last widget in a
+    /// page can't be hidden.
+    pub fn hide_widget(&mut self) {
+        if self.ignore_normal_keybinds()
+            || self.is_expanded
+            || self.app_config_fields.use_basic_mode
+        {
+            return;
+        }
+
+        let Some(page) = self.pages.get(self.current_page) else {
+            return;
+        };
+
+        let widget_id = self.current_widget.parent_id();
+        let is_selectable = |id: &u64| {
+            *id != widget_id
+                && !self.hidden_widgets.contains(id)
+                && self.widget_map.get(id).is_some_and(|widget| {
+                    widget.parent_reflector.is_none()
+                        && widget.widget_type != BottomWidgetType::Empty
+                })
+        };
+
+        let neighbours = [
+            self.current_widget.left_neighbour,
+            self.current_widget.right_neighbour,
+            self.current_widget.up_neighbour,
+            self.current_widget.down_neighbour,
+        ];
+        let next_widget = neighbours
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.widget_map.get(&id))
+            .map(|widget| widget.parent_id())
+            .chain(page.widget_ids.clone())
+            .find(is_selectable)
+            .and_then(|id| self.widget_map.get(&id));
+
+        if let Some(next_widget) = next_widget {
+            self.current_widget = next_widget.clone();
+            self.hidden_widgets.insert(widget_id);
+            self.reset_multi_tap_keys();
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Shows any widgets that were hidden.
+    pub fn show_widgets(&mut self) {
+        if !self.hidden_widgets.is_empty() {
+            self.hidden_widgets.clear();
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Returns the index of the layout page with the widget `widget_id`.
+    pub fn page_of_widget(&self, widget_id: u64) -> Option<usize> {
+        self.pages
+            .iter()
+            .position(|page| page.widget_ids.contains(&widget_id))
+    }
+
+    /// Returns the widget of `widget_type` that the global search looks in,
+    /// preferring one on the current page.
+    fn global_search_widget(&self, widget_type: BottomWidgetType) -> Option<u64> {
+        self.widget_map
+            .values()
+            .filter(|widget| widget.widget_type == widget_type)
+            .map(|widget| widget.widget_id)
+            .min_by_key(|id| (self.page_of_widget(*id) != Some(self.current_page), *id))
+    }
+
+    /// Finds everything matching the global search query.
+    fn update_global_search(&mut self) {
+        let Some(search) = &self.global_search else {
+            return;
+        };
+
+        let mut results = vec![];
+
+        if let Some(widget_id) = self.global_search_widget(BottomWidgetType::Proc) {
+            if let Some(pws) = self.states.proc_state.get_widget_state(widget_id) {
+                results.extend(pws.table.data().iter().filter_map(|process| {
+                    let name = process.id.as_str();
+                    search.matches(name).then(|| GlobalSearchResult {
+                        widget_id,
+                        target: GlobalSearchTarget::Process(process.pid),
+                        label: format!("{name} ({})", process.pid),
+                    })
+                }));
+            }
+        }
+
+        if let Some(widget_id) = self.global_search_widget(BottomWidgetType::Disk) {
+            if let Some(disk_widget) = self.states.disk_state.get_widget_state(widget_id) {
+                results.extend(
+                    disk_widget
+                        .table
+                        .data()
+                        .iter()
+                        .filter(|disk| {
+                            search.matches(&disk.mount_point) || search.matches(&disk.name)
+                        })
+                        .map(|disk| GlobalSearchResult {
+                            widget_id,
+                            target: GlobalSearchTarget::Disk(disk.mount_point.to_string()),
+                            label: format!("{} ({})", disk.mount_point, disk.name),
+                        }),
+                );
+            }
+        }
+
+        if let Some(widget_id) = self.global_search_widget(BottomWidgetType::Temp) {
+            if let Some(temp_widget) = self.states.temp_state.get_widget_state(widget_id) {
+                results.extend(
+                    temp_widget
+                        .table
+                        .data()
+                        .iter()
+                        .filter(|sensor| search.matches(&sensor.sensor))
+                        .map(|sensor| GlobalSearchResult {
+                            widget_id,
+                            target: GlobalSearchTarget::Temperature(sensor.sensor.to_string()),
+                            label: sensor.sensor.to_string(),
+                        }),
+                );
+            }
+        }
+
+        if let Some(widget_id) = self.global_search_widget(BottomWidgetType::Net) {
+            let data = match &self.frozen_state {
+                FrozenState::NotFrozen => &self.data_collection,
+                FrozenState::Frozen(data) => data,
+            };
+
+            results.extend(
+                data.network_harvest
+                    .interfaces
+                    .iter()
+                    .filter(|interface| search.matches(interface))
+                    .map(|interface| GlobalSearchResult {
+                        widget_id,
+                        target: GlobalSearchTarget::Network,
+                        label: interface.clone(),
+                    }),
+            );
+        }
+
+        if let Some(search) = &mut self.global_search {
+            search.set_results(results);
+        }
+    }
+
+    /// Selects the widget a global search result is in, switching pages and
+    /// showing the widget if needed, and then selects its row.
+    fn jump_to_search_result(&mut self, result: &GlobalSearchResult) {
+        let Some(widget) = self.widget_map.get(&result.widget_id).cloned() else {
+            return;
+        };
+
+        if let Some(page) = self.page_of_widget(widget.widget_id) {
+            self.switch_to_page(page);
+        }
+        self.hidden_widgets.remove(&widget.widget_id);
+        if self.current_widget.widget_id != widget.widget_id {
+            self.is_expanded = false;
+        }
+
+        self.current_widget = widget;
+        if let Some(basic_table_widget_state) = &mut self.states.basic_table_widget_state {
+            if self.current_widget.widget_type.is_widget_table() {
+                basic_table_widget_state.currently_displayed_widget_id =
+                    self.current_widget.widget_id;
+                basic_table_widget_state.currently_displayed_widget_type =
+                    self.current_widget.widget_type.clone();
+            }
+        }
+
+        let widget_id = result.widget_id;
+        match &result.target {
+            GlobalSearchTarget::Process(pid) => {
+                if let Some(pws) = self.states.proc_state.get_mut_widget_state(widget_id) {
+                    if let Some(index) = pws.table.data().iter().position(|p| p.pid == *pid) {
+                        pws.table.set_position(index);
+                    }
+                }
+            }
+            GlobalSearchTarget::Disk(mount_point) => {
+                if let Some(disk_widget) = self.states.disk_state.get_mut_widget_state(widget_id) {
+                    if let Some(index) = disk_widget
+                        .table
+                        .data()
+                        .iter()
+                        .position(|disk| disk.mount_point == *mount_point)
+                    {
+                        disk_widget.table.set_position(index);
+                    }
+                }
+            }
+            GlobalSearchTarget::Temperature(sensor) => {
+                if let Some(temp_widget) = self.states.temp_state.get_mut_widget_state(widget_id) {
+                    if let Some(index) = temp_widget
+                        .table
+                        .data()
+                        .iter()
+                        .position(|temp| temp.sensor == *sensor)
+                    {
+                        temp_widget.table.set_position(index);
+                    }
+                }
+            }
+            GlobalSearchTarget::Network => {}
+        }
+
+        self.reset_multi_tap_keys();
+        self.is_force_redraw = true;
+    }
+
+    /// Forces the graphs and tables to update if the frozen data's current time
+    /// was moved.
+    fn on_frozen_data_scrubbed(&mut self, changed: bool) {
+        if changed {
+            let widget_id = self.current_widget.widget_id;
+
+            self.states.cpu_state.force_update = Some(widget_id);
+            self.states.mem_state.force_update = Some(widget_id);
+            self.states.net_state.force_update = Some(widget_id);
+            self.convert_visible_data();
+        }
+    }
+
+    pub fn kill_highlighted_process(&mut self) -> anyhow::Result<()> {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some((_, pids)) = &self.to_delete_process_list {
+                #[cfg(target_family = "unix")]
+                let signal = match self.delete_dialog_state.selected_signal {
+                    KillSignal::Kill(sig) => sig,
+                    KillSignal::Cancel => 15, // should never happen, so just TERM
+                };
+                for pid in pids {
+                    #[cfg(target_family = "unix")]
+                    {
+                        process_killer::kill_process_given_pid(*pid, signal)?;
+                    }
+                    #[cfg(target_os = "windows")]
+                    {
+                        process_killer::kill_process_given_pid(*pid)?;
+                    }
+                }
+            }
+            self.to_delete_process_list = None;
+            Ok(())
+        } else {
+            bail!("Cannot kill processes if the current widget is not the Process widget!");
+        }
+    }
+
+    pub fn get_to_delete_processes(&self) -> Option<(String, Vec<Pid>)> {
+        self.to_delete_process_list.clone()
+    }
+
+    fn toggle_expand_widget(&mut self) {
+        if self.is_expanded {
+            self.is_expanded = false;
+            self.is_force_redraw = true;
+        } else {
+            self.expand_widget();
+        }
+    }
+
+    fn expand_widget(&mut self) {
+        // TODO: [BASIC] Expansion in basic mode.
+        if !self.ignore_normal_keybinds() && !self.app_config_fields.use_basic_mode {
+            // Pop-out mode.  We ignore if in process search.
+
+            match self.current_widget.widget_type {
+                BottomWidgetType::ProcSearch => {}
+                _ => {
+                    self.is_expanded = true;
+                    self.is_force_redraw = true;
+                }
+            }
+        }
+    }
+
+    pub fn move_widget_selection(&mut self, direction: &WidgetDirection) {
+        // Since we only want to call reset once, we do it like this to avoid
         // redundant calls on recursion.
+        let widget_id = self.current_widget.widget_id;
         self.move_widget_selection_logic(direction);
+
+        // Keep going past any hidden widgets, or stay put if there's nothing
+        // visible in that direction.
+        for _ in 0..self.widget_map.len() {
+            if !self
+                .hidden_widgets
+                .contains(&self.current_widget.parent_id())
+            {
+                break;
+            }
+            self.move_widget_selection_logic(direction);
+        }
+        if self
+            .hidden_widgets
+            .contains(&self.current_widget.parent_id())
+        {
+            if let Some(widget) = self.widget_map.get(&widget_id) {
+                self.current_widget = widget.clone();
+            }
+        }
+
         self.reset_multi_tap_keys();
     }
 
@@ -1672,42 +2912,35 @@ impl App {
                                                 .get(&(new_widget_id - *offset))
                                             {
                                                 match &new_widget.widget_type {
-                                                    BottomWidgetType::ProcSearch => {
-                                                        if !proc_widget_state.is_search_enabled() {
-                                                            if let Some(next_neighbour_id) =
-                                                                option_next_neighbour_id
+                                                    BottomWidgetType::ProcSearch
+                                                        if !proc_widget_state
+                                                            .is_search_enabled() =>
+                                                    {
+                                                        if let Some(next_neighbour_id) =
+                                                            option_next_neighbour_id
+                                                        {
+                                                            if let Some(next_neighbour_widget) =
+                                                                self.widget_map
+                                                                    .get(&next_neighbour_id)
                                                             {
-                                                                if let Some(next_neighbour_widget) =
-                                                                    self.widget_map
-                                                                        .get(&next_neighbour_id)
-                                                                {
-                                                                    self.current_widget =
-                                                                        next_neighbour_widget
-                                                                            .clone();
-                                                                }
+                                                                self.current_widget =
+                                                                    next_neighbour_widget.clone();
                                                             }
-                                                        } else {
-                                                            self.current_widget =
-                                                                new_widget.clone();
                                                         }
                                                     }
-                                                    BottomWidgetType::ProcSort => {
-                                                        if !proc_widget_state.is_sort_open {
-                                                            if let Some(next_neighbour_id) =
-                                                                option_next_neighbour_id
+                                                    BottomWidgetType::ProcSort
+                                                        if !proc_widget_state.is_sort_open =>
+                                                    {
+                                                        if let Some(next_neighbour_id) =
+                                                            option_next_neighbour_id
+                                                        {
+                                                            if let Some(next_neighbour_widget) =
+                                                                self.widget_map
+                                                                    .get(&next_neighbour_id)
                                                             {
-                                                                if let Some(next_neighbour_widget) =
-                                                                    self.widget_map
-                                                                        .get(&next_neighbour_id)
-                                                                {
-                                                                    self.current_widget =
-                                                                        next_neighbour_widget
-                                                                            .clone();
-                                                                }
+                                                                self.current_widget =
+                                                                    next_neighbour_widget.clone();
                                                             }
-                                                        } else {
-                                                            self.current_widget =
-                                                                new_widget.clone();
                                                         }
                                                     }
                                                     _ => {
@@ -1752,32 +2985,27 @@ impl App {
                                                 .get(&(new_widget_id - *offset))
                                             {
                                                 match &new_widget.widget_type {
-                                                    BottomWidgetType::ProcSearch => {
-                                                        if !proc_widget_state.is_search_enabled() {
-                                                            if let Some(parent_proc_widget) = self
-                                                                .widget_map
-                                                                .get(&(new_widget_id - *offset))
-                                                            {
-                                                                self.current_widget =
-                                                                    parent_proc_widget.clone();
-                                                            }
-                                                        } else {
+                                                    BottomWidgetType::ProcSearch
+                                                        if !proc_widget_state
+                                                            .is_search_enabled() =>
+                                                    {
+                                                        if let Some(parent_proc_widget) = self
+                                                            .widget_map
+                                                            .get(&(new_widget_id - *offset))
+                                                        {
                                                             self.current_widget =
-                                                                new_widget.clone();
+                                                                parent_proc_widget.clone();
                                                         }
                                                     }
-                                                    BottomWidgetType::ProcSort => {
-                                                        if !proc_widget_state.is_sort_open {
-                                                            if let Some(parent_proc_widget) = self
-                                                                .widget_map
-                                                                .get(&(new_widget_id - *offset))
-                                                            {
-                                                                self.current_widget =
-                                                                    parent_proc_widget.clone();
-                                                            }
-                                                        } else {
+                                                    BottomWidgetType::ProcSort
+                                                        if !proc_widget_state.is_sort_open =>
+                                                    {
+                                                        if let Some(parent_proc_widget) = self
+                                                            .widget_map
+                                                            .get(&(new_widget_id - *offset))
+                                                        {
                                                             self.current_widget =
-                                                                new_widget.clone();
+                                                                parent_proc_widget.clone();
                                                         }
                                                     }
                                                     _ => {
@@ -1823,15 +3051,15 @@ impl App {
                                     .get(&(self.current_widget.widget_id - *offset))
                                 {
                                     match &self.current_widget.widget_type {
-                                        BottomWidgetType::ProcSearch => {
-                                            if !proc_widget_state.is_search_enabled() {
-                                                reflection_dir = Some(parent_direction.clone());
-                                            }
+                                        BottomWidgetType::ProcSearch
+                                            if !proc_widget_state.is_search_enabled() =>
+                                        {
+                                            reflection_dir = Some(parent_direction.clone());
                                         }
-                                        BottomWidgetType::ProcSort => {
-                                            if !proc_widget_state.is_sort_open {
-                                                reflection_dir = Some(parent_direction.clone());
-                                            }
+                                        BottomWidgetType::ProcSort
+                                            if !proc_widget_state.is_sort_open =>
+                                        {
+                                            reflection_dir = Some(parent_direction.clone());
                                         }
                                         _ => {}
                                     }
@@ -2198,6 +3426,18 @@ impl App {
     }
 
     pub fn handle_scroll_up(&mut self) {
+        if let Some(menu) = &mut self.context_menu {
+            menu.select_previous();
+            return;
+        }
+        if let Some(search) = &mut self.global_search {
+            search.select_previous();
+            return;
+        }
+        if self.diagnostics.is_some() {
+            self.on_up_key();
+            return;
+        }
         if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
             {
@@ -2208,13 +3448,26 @@ impl App {
         if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
         } else if self.current_widget.widget_type.is_widget_graph() {
-            self.zoom_in();
+            self.scroll_graph(true);
         } else if self.current_widget.widget_type.is_widget_table() {
-            self.decrement_position_count();
+            let rows = i64::from(self.app_config_fields.mouse.table_scroll_rows);
+            self.change_position_count(-rows);
         }
     }
 
     pub fn handle_scroll_down(&mut self) {
+        if let Some(menu) = &mut self.context_menu {
+            menu.select_next();
+            return;
+        }
+        if let Some(search) = &mut self.global_search {
+            search.select_next();
+            return;
+        }
+        if self.diagnostics.is_some() {
+            self.on_down_key();
+            return;
+        }
         if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
             {
@@ -2225,9 +3478,33 @@ impl App {
         if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
         } else if self.current_widget.widget_type.is_widget_graph() {
-            self.zoom_out();
+            self.scroll_graph(false);
         } else if self.current_widget.widget_type.is_widget_table() {
-            self.increment_position_count();
+            let rows = i64::from(self.app_config_fields.mouse.table_scroll_rows);
+            self.change_position_count(rows);
+        }
+    }
+
+    /// Scrolls over a graph, which does whatever it's set to in the mouse
+    /// settings. Scrolling up zooms in or steps back in time.
+    fn scroll_graph(&mut self, is_up: bool) {
+        match self.app_config_fields.mouse.graph_scroll {
+            GraphScroll::Zoom => {
+                if is_up {
+                    self.zoom_in();
+                } else {
+                    self.zoom_out();
+                }
+            }
+            GraphScroll::Scrub => {
+                let changed = if is_up {
+                    self.frozen_state.step_back(1)
+                } else {
+                    self.frozen_state.step_forward(1)
+                };
+                self.on_frozen_data_scrubbed(changed);
+            }
+            GraphScroll::None => {}
         }
     }
 
@@ -2260,7 +3537,35 @@ impl App {
         }
     }
 
+    fn collapse_process_branch(&mut self) {
+        if let Some(pws) = self
+            .states
+            .proc_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            pws.collapse_current_tree_branch();
+        }
+    }
+
+    fn set_all_process_branches_collapsed(&mut self, is_collapsed: bool) {
+        if let Some(pws) = self
+            .states
+            .proc_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            pws.set_all_tree_branches_collapsed(is_collapsed);
+        }
+    }
+
     fn zoom_out(&mut self) {
+        // If we're keeping downsampled data around, allow zooming out to see all of it.
+        let max_display_time = self
+            .app_config_fields
+            .long_term_retention_ms
+            .unwrap_or(self.app_config_fields.retention_ms);
+
         match self.current_widget.widget_type {
             BottomWidgetType::Cpu => {
                 if let Some(cpu_widget_state) = self
@@ -2273,16 +3578,14 @@ impl App {
                         .current_display_time
                         .saturating_add(self.app_config_fields.time_interval);
 
-                    if new_time <= self.app_config_fields.retention_ms {
+                    if new_time <= max_display_time {
                         cpu_widget_state.current_display_time = new_time;
                         self.states.cpu_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
                             cpu_widget_state.autohide_timer = Some(Instant::now());
                         }
-                    } else if cpu_widget_state.current_display_time
-                        != self.app_config_fields.retention_ms
-                    {
-                        cpu_widget_state.current_display_time = self.app_config_fields.retention_ms;
+                    } else if cpu_widget_state.current_display_time != max_display_time {
+                        cpu_widget_state.current_display_time = max_display_time;
                         self.states.cpu_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
                             cpu_widget_state.autohide_timer = Some(Instant::now());
@@ -2301,16 +3604,14 @@ impl App {
                         .current_display_time
                         .saturating_add(self.app_config_fields.time_interval);
 
-                    if new_time <= self.app_config_fields.retention_ms {
+                    if new_time <= max_display_time {
                         mem_widget_state.current_display_time = new_time;
                         self.states.mem_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
                             mem_widget_state.autohide_timer = Some(Instant::now());
                         }
-                    } else if mem_widget_state.current_display_time
-                        != self.app_config_fields.retention_ms
-                    {
-                        mem_widget_state.current_display_time = self.app_config_fields.retention_ms;
+                    } else if mem_widget_state.current_display_time != max_display_time {
+                        mem_widget_state.current_display_time = max_display_time;
                         self.states.mem_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
                             mem_widget_state.autohide_timer = Some(Instant::now());
@@ -2329,16 +3630,14 @@ impl App {
                         .current_display_time
                         .saturating_add(self.app_config_fields.time_interval);
 
-                    if new_time <= self.app_config_fields.retention_ms {
+                    if new_time <= max_display_time {
                         net_widget_state.current_display_time = new_time;
                         self.states.net_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
                             net_widget_state.autohide_timer = Some(Instant::now());
                         }
-                    } else if net_widget_state.current_display_time
-                        != self.app_config_fields.retention_ms
-                    {
-                        net_widget_state.current_display_time = self.app_config_fields.retention_ms;
+                    } else if net_widget_state.current_display_time != max_display_time {
+                        net_widget_state.current_display_time = max_display_time;
                         self.states.net_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
                             net_widget_state.autohide_timer = Some(Instant::now());
@@ -2448,6 +3747,7 @@ impl App {
             .get_mut(&self.current_widget.widget_id)
         {
             cpu_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            cpu_widget_state.display_offset = 0;
             self.states.cpu_state.force_update = Some(self.current_widget.widget_id);
             if self.app_config_fields.autohide_time {
                 cpu_widget_state.autohide_timer = Some(Instant::now());
@@ -2463,6 +3763,7 @@ impl App {
             .get_mut(&self.current_widget.widget_id)
         {
             mem_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            mem_widget_state.display_offset = 0;
             self.states.mem_state.force_update = Some(self.current_widget.widget_id);
             if self.app_config_fields.autohide_time {
                 mem_widget_state.autohide_timer = Some(Instant::now());
@@ -2478,6 +3779,7 @@ impl App {
             .get_mut(&self.current_widget.widget_id)
         {
             net_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            net_widget_state.display_offset = 0;
             self.states.net_state.force_update = Some(self.current_widget.widget_id);
             if self.app_config_fields.autohide_time {
                 net_widget_state.autohide_timer = Some(Instant::now());
@@ -2494,6 +3796,342 @@ impl App {
         }
     }
 
+    /// Starts dragging a layout border if the mouse was pressed on one. Returns
+    /// whether it was.
+    pub fn start_layout_drag(&mut self, x: u16, y: u16) -> bool {
+        self.layout_drag = self
+            .layout_borders
+            .iter()
+            .find(|border| border.contains(x, y))
+            .map(|border| LayoutDrag::new(*border, x, y));
+
+        self.layout_drag.is_some()
+    }
+
+    /// Moves the layout border being dragged, if there is one. Returns whether
+    /// there was.
+    pub fn update_layout_drag(&mut self, x: u16, y: u16) -> bool {
+        match &mut self.layout_drag {
+            Some(drag) => {
+                drag.move_to(x, y);
+                self.is_force_redraw = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stops dragging a layout border. Returns whether one was being dragged.
+    pub fn finish_layout_drag(&mut self) -> bool {
+        self.layout_drag.take().is_some()
+    }
+
+    /// Starts selecting a time range if the mouse was pressed within the graph
+    /// of the current widget.
+    pub fn start_time_range_selection(&mut self, x: u16, y: u16) {
+        let widget_id = self.current_widget.widget_id;
+
+        self.time_range_selection = self
+            .current_graph_view()
+            .filter(|(area, _, _)| {
+                (x >= area.left() && y >= area.top()) && (x < area.right() && y < area.bottom())
+            })
+            .map(|_| TimeRangeSelection {
+                widget_id,
+                start_x: x,
+                end_x: x,
+            });
+    }
+
+    /// Returns the graph area, display offset, and display time of the current
+    /// widget if it is a time graph.
+    fn current_graph_view(&self) -> Option<(Rect, u64, u64)> {
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Cpu => {
+                self.states
+                    .cpu_state
+                    .get_widget_state(widget_id)
+                    .map(|state| {
+                        (
+                            state.graph_area,
+                            state.display_offset,
+                            state.current_display_time,
+                        )
+                    })
+            }
+            BottomWidgetType::Mem => {
+                self.states
+                    .mem_state
+                    .widget_states
+                    .get(&widget_id)
+                    .map(|state| {
+                        (
+                            state.graph_area,
+                            state.display_offset,
+                            state.current_display_time,
+                        )
+                    })
+            }
+            BottomWidgetType::Net => {
+                self.states
+                    .net_state
+                    .widget_states
+                    .get(&widget_id)
+                    .map(|state| {
+                        (
+                            state.graph_area,
+                            state.display_offset,
+                            state.current_display_time,
+                        )
+                    })
+            }
+            _ => None,
+        }
+    }
+
+    /// Toggles the inspection cursor in the current widget, if it is a time
+    /// graph. The cursor starts at the right edge of the graph.
+    fn toggle_inspect_cursor(&mut self) {
+        let widget_id = self.current_widget.widget_id;
+
+        if self
+            .inspect_cursor
+            .is_some_and(|cursor| cursor.widget_id == widget_id)
+        {
+            self.inspect_cursor = None;
+        } else if let Some((_, display_offset, _)) = self.current_graph_view() {
+            self.inspect_cursor = Some(InspectCursor {
+                widget_id,
+                time: display_offset,
+            });
+        }
+    }
+
+    /// Moves the inspection cursor in the current widget back or forward in
+    /// time by one update interval. Returns whether there was a cursor to move.
+    fn move_inspect_cursor(&mut self, back: bool) -> bool {
+        let widget_id = self.current_widget.widget_id;
+        let step = self.app_config_fields.update_rate;
+
+        let Some((_, display_offset, display_time)) = self.current_graph_view() else {
+            return false;
+        };
+
+        let Some(cursor) = self
+            .inspect_cursor
+            .as_mut()
+            .filter(|cursor| cursor.widget_id == widget_id)
+        else {
+            return false;
+        };
+
+        let time = if back {
+            cursor.time.saturating_add(step)
+        } else {
+            cursor.time.saturating_sub(step)
+        };
+        cursor.time = time.clamp(display_offset, display_offset + display_time);
+
+        true
+    }
+
+    /// Moves the inspection cursor in the current widget to the given
+    /// position, if it is in the widget's graph. Returns whether the cursor
+    /// was moved.
+    pub fn move_inspect_cursor_to(&mut self, x: u16, y: u16) -> bool {
+        let widget_id = self.current_widget.widget_id;
+
+        if !self
+            .inspect_cursor
+            .is_some_and(|cursor| cursor.widget_id == widget_id)
+        {
+            return false;
+        }
+
+        match self.current_graph_view() {
+            Some((area, display_offset, display_time))
+                if (x >= area.left() && y >= area.top())
+                    && (x < area.right() && y < area.bottom()) =>
+            {
+                self.inspect_cursor = Some(InspectCursor {
+                    widget_id,
+                    time: graph_time_at(area, display_offset, display_time, x),
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the stored data at the inspection cursor, if the cursor is in
+    /// the given widget.
+    pub fn inspected_data(&self, widget_id: u64) -> Option<&TimedData> {
+        let cursor = self
+            .inspect_cursor
+            .filter(|cursor| cursor.widget_id == widget_id)?;
+
+        let data_source = match &self.frozen_state {
+            FrozenState::NotFrozen => &self.data_collection,
+            FrozenState::Frozen(data) => data,
+        };
+
+        data_source.timed_data_at(cursor.time)
+    }
+
+    /// Returns when the data at the right edge of graphs was collected, which
+    /// is in the past if frozen.
+    pub fn displayed_time(&self) -> SystemTime {
+        let data_source = match &self.frozen_state {
+            FrozenState::NotFrozen => &self.data_collection,
+            FrozenState::Frozen(data) => data,
+        };
+
+        let now = SystemTime::now();
+        now.checked_sub(data_source.current_instant.elapsed())
+            .unwrap_or(now)
+    }
+
+    /// Whether any of the data sources shown by a widget of the given type ran
+    /// into problems in the latest collection.
+    pub fn has_collection_issues(&self, widget_type: &BottomWidgetType) -> bool {
+        let sources = widget_type.data_sources();
+        self.collection_issues
+            .iter()
+            .any(|issue| sources.contains(&issue.source))
+    }
+
+    /// Explains why a widget of the given type has nothing to show, with a hint
+    /// on what to do about it where there is one.
+    pub fn empty_message(&self, widget_type: &BottomWidgetType) -> Cow<'static, str> {
+        use BottomWidgetType::*;
+
+        let sources = widget_type.data_sources();
+        if let Some(issue) = self
+            .collection_issues
+            .iter()
+            .find(|issue| sources.contains(&issue.source))
+        {
+            let diagnostics_key = self
+                .app_config_fields
+                .key_bindings
+                .iter()
+                .find(|(action, _)| *action == Action::Diagnostics)
+                .and_then(|(_, keys)| keys.first());
+
+            return match diagnostics_key {
+                Some(key) => tr_args(
+                    "{issue}; press {key} for details",
+                    &[("issue", &issue.message), ("key", key)],
+                )
+                .into(),
+                None => issue.message.clone().into(),
+            };
+        }
+
+        let status = self
+            .source_statuses
+            .iter()
+            .find(|(name, _)| sources.first() == Some(name))
+            .map(|(_, status)| *status);
+
+        let message = match (widget_type, status) {
+            (_, None) => "Waiting for data...",
+            (Temp, Some(SourceStatus::Active)) if self.filters.temp_filter.is_some() => {
+                "No sensors match the filter; see [temperature.sensor_filter] in the config"
+            }
+            (Temp, _) => "No temperature sensors were found",
+            (Disk, Some(SourceStatus::Active))
+                if self.filters.disk_filter.is_some() || self.filters.mount_filter.is_some() =>
+            {
+                "No disks match the filters; see [disk.name_filter] and [disk.mount_filter] in the config"
+            }
+            (Disk, _) => "No disks were found",
+            (Battery, _) => "No batteries were found",
+            (Proc, _) => "No processes were found",
+            _ => "No data",
+        };
+
+        tr(message).into()
+    }
+
+    /// Updates the time range being selected as the mouse is dragged.
+    pub fn update_time_range_selection(&mut self, x: u16) {
+        if let Some(selection) = &mut self.time_range_selection {
+            selection.end_x = x;
+        }
+    }
+
+    /// Zooms the graph to the selected time range once the mouse is released.
+    pub fn finish_time_range_selection(&mut self, x: u16) {
+        let Some(mut selection) = self.time_range_selection.take() else {
+            return;
+        };
+        selection.end_x = x;
+
+        let widget_id = selection.widget_id;
+        let autohide_timer = self.app_config_fields.autohide_time.then(Instant::now);
+
+        match self
+            .widget_map
+            .get(&widget_id)
+            .map(|widget| &widget.widget_type)
+        {
+            Some(BottomWidgetType::Cpu) => {
+                if let Some(cpu_widget_state) =
+                    self.states.cpu_state.get_mut_widget_state(widget_id)
+                {
+                    if let Some((offset, time)) = selection.time_range(
+                        cpu_widget_state.graph_area,
+                        cpu_widget_state.display_offset,
+                        cpu_widget_state.current_display_time,
+                    ) {
+                        cpu_widget_state.display_offset = offset;
+                        cpu_widget_state.current_display_time =
+                            max(time, constants::STALE_MIN_MILLISECONDS);
+                        cpu_widget_state.autohide_timer = autohide_timer;
+                        self.states.cpu_state.force_update = Some(widget_id);
+                    }
+                }
+            }
+            Some(BottomWidgetType::Mem) => {
+                if let Some(mem_widget_state) =
+                    self.states.mem_state.widget_states.get_mut(&widget_id)
+                {
+                    if let Some((offset, time)) = selection.time_range(
+                        mem_widget_state.graph_area,
+                        mem_widget_state.display_offset,
+                        mem_widget_state.current_display_time,
+                    ) {
+                        mem_widget_state.display_offset = offset;
+                        mem_widget_state.current_display_time =
+                            max(time, constants::STALE_MIN_MILLISECONDS);
+                        mem_widget_state.autohide_timer = autohide_timer;
+                        self.states.mem_state.force_update = Some(widget_id);
+                    }
+                }
+            }
+            Some(BottomWidgetType::Net) => {
+                if let Some(net_widget_state) =
+                    self.states.net_state.widget_states.get_mut(&widget_id)
+                {
+                    if let Some((offset, time)) = selection.time_range(
+                        net_widget_state.graph_area,
+                        net_widget_state.display_offset,
+                        net_widget_state.current_display_time,
+                    ) {
+                        net_widget_state.display_offset = offset;
+                        net_widget_state.current_display_time =
+                            max(time, constants::STALE_MIN_MILLISECONDS);
+                        net_widget_state.autohide_timer = autohide_timer;
+                        self.states.net_state.force_update = Some(widget_id);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Moves the mouse to the widget that was clicked on, then propagates the
     /// click down to be handled by the widget specifically.
     pub fn on_left_mouse_up(&mut self, x: u16, y: u16) {
@@ -2505,12 +4143,12 @@ impl App {
         // everything is grouped up as an app state.  We should separate stuff
         // like event state and gui state and etc.
 
-        // TODO: [MOUSE] double click functionality...?  We would do this above all
-        // other actions and SC if needed.
-
         // Short circuit if we're in basic table... we might have to handle the basic
         // table arrow case here...
 
+        // The arrows are only a character wide, so widen them in mobile mode.
+        let arrow_padding = if self.app_config_fields.mobile { 2 } else { 0 };
+
         if let Some(bt) = &mut self.states.basic_table_widget_state {
             if let (
                 Some((left_tlc_x, left_tlc_y)),
@@ -2519,7 +4157,9 @@ impl App {
                 Some((right_brc_x, right_brc_y)),
             ) = (bt.left_tlc, bt.left_brc, bt.right_tlc, bt.right_brc)
             {
-                if (x >= left_tlc_x && y >= left_tlc_y) && (x < left_brc_x && y < left_brc_y) {
+                if (x.saturating_add(arrow_padding) >= left_tlc_x && y >= left_tlc_y)
+                    && (x < left_brc_x.saturating_add(arrow_padding) && y < left_brc_y)
+                {
                     // Case for the left "button" in the simple arrow.
                     if let Some(new_widget) =
                         self.widget_map.get(&(bt.currently_displayed_widget_id))
@@ -2541,8 +4181,8 @@ impl App {
                         self.move_widget_selection(&WidgetDirection::Left);
                         return;
                     }
-                } else if (x >= right_tlc_x && y >= right_tlc_y)
-                    && (x < right_brc_x && y < right_brc_y)
+                } else if (x.saturating_add(arrow_padding) >= right_tlc_x && y >= right_tlc_y)
+                    && (x < right_brc_x.saturating_add(arrow_padding) && y < right_brc_y)
                 {
                     // Case for the right "button" in the simple arrow.
                     if let Some(new_widget) =
@@ -2654,7 +4294,13 @@ impl App {
                         let header_offset = self.header_offset(&self.current_widget);
                         let offset = border_offset + header_offset;
                         if clicked_entry >= offset {
-                            let offset_clicked_entry = clicked_entry - offset;
+                            let row_stride = match &self.current_widget.widget_type {
+                                BottomWidgetType::ProcSort | BottomWidgetType::CpuLegend => {
+                                    self.app_config_fields.table_row_height()
+                                }
+                                _ => self.app_config_fields.table_row_stride(),
+                            };
+                            let offset_clicked_entry = (clicked_entry - offset) / row_stride;
                             match &self.current_widget.widget_type {
                                 BottomWidgetType::Proc => {
                                     if let Some(proc_widget_state) = self
@@ -2665,23 +4311,9 @@ impl App {
                                         if let Some(visual_index) =
                                             proc_widget_state.table.ratatui_selected()
                                         {
-                                            let is_tree_mode = matches!(
-                                                proc_widget_state.mode,
-                                                ProcWidgetMode::Tree { .. }
+                                            self.change_process_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
                                             );
-                                            let change =
-                                                offset_clicked_entry as i64 - visual_index as i64;
-
-                                            self.change_process_position(change);
-
-                                            // If in tree mode, also check to see if this click is
-                                            // on
-                                            // the same entry as the already selected one - if it
-                                            // is,
-                                            // then we minimize.
-                                            if is_tree_mode && change == 0 {
-                                                self.toggle_collapsing_process_branch();
-                                            }
                                         }
                                     }
                                 }
@@ -2825,6 +4457,162 @@ impl App {
         }
     }
 
+    /// Tracks which widget the mouse is over, so that it can be highlighted.
+    pub fn on_mouse_move(&mut self, x: u16, y: u16) {
+        self.mouse_hover = if self.is_in_dialog() {
+            None
+        } else {
+            self.widget_at(x, y).map(|widget| MouseHover {
+                widget_id: widget.widget_id,
+                x,
+                y,
+            })
+        };
+    }
+
+    /// Returns where the mouse is, if it is over the widget with the given ID.
+    pub fn hover_position(&self, widget_id: u64) -> Option<(u16, u16)> {
+        self.mouse_hover
+            .filter(|hover| hover.widget_id == widget_id)
+            .map(|hover| (hover.x, hover.y))
+    }
+
+    fn widget_at(&self, x: u16, y: u16) -> Option<&BottomWidget> {
+        self.widget_map.values().find(|widget| {
+            if let (Some((tlc_x, tlc_y)), Some((brc_x, brc_y))) =
+                (widget.top_left_corner, widget.bottom_right_corner)
+            {
+                (x >= tlc_x && y >= tlc_y) && (x < brc_x && y < brc_y)
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Selects the process under the mouse and opens a context menu for it.
+    /// If a context menu is already open, it is closed instead.
+    pub fn on_right_mouse_down(&mut self, x: u16, y: u16) {
+        if self.context_menu.take().is_some() || self.is_in_dialog() {
+            return;
+        }
+
+        let Some(widget) = self
+            .widget_at(x, y)
+            .filter(|widget| matches!(widget.widget_type, BottomWidgetType::Proc))
+            .cloned()
+        else {
+            return;
+        };
+        let Some(row) = self.table_row_at(&widget, y) else {
+            return;
+        };
+
+        self.current_widget = widget;
+        if let Some(basic_table_widget_state) = &mut self.states.basic_table_widget_state {
+            basic_table_widget_state.currently_displayed_widget_id = self.current_widget.widget_id;
+            basic_table_widget_state.currently_displayed_widget_type =
+                self.current_widget.widget_type.clone();
+        }
+
+        // Unlike a left click, this shouldn't collapse tree branches.
+        if let Some(visual_index) = self
+            .states
+            .proc_state
+            .get_widget_state(self.current_widget.widget_id)
+            .and_then(|pws| pws.table.ratatui_selected())
+        {
+            self.change_process_position(i64::from(row) - visual_index as i64);
+        }
+
+        if let Some((name, pids)) = self.highlighted_processes() {
+            self.context_menu = Some(ContextMenu::new(
+                self.current_widget.widget_id,
+                name,
+                pids,
+                x,
+                y,
+            ));
+        }
+    }
+
+    /// Runs the configured action on a double-clicked table entry, which the
+    /// first click will have already selected.
+    pub fn on_double_click(&mut self, x: u16, y: u16) {
+        let is_on_entry = self
+            .widget_at(x, y)
+            .filter(|widget| widget.widget_id == self.current_widget.widget_id)
+            .and_then(|widget| self.table_row_at(widget, y))
+            .is_some();
+        if !is_on_entry {
+            return;
+        }
+
+        match (
+            self.app_config_fields.double_click_action,
+            &self.current_widget.widget_type,
+        ) {
+            (DoubleClickAction::Collapse, BottomWidgetType::Proc) => {
+                self.toggle_collapsing_process_branch();
+            }
+            (DoubleClickAction::Details, BottomWidgetType::Proc) => {
+                self.process_details = self
+                    .highlighted_processes()
+                    .and_then(|(_, pids)| pids.first().copied());
+            }
+            (DoubleClickAction::Details, BottomWidgetType::Disk | BottomWidgetType::Temp) => {
+                self.expand_widget();
+            }
+            (DoubleClickAction::Kill, BottomWidgetType::Proc) => self.start_killing_process(),
+            _ => {}
+        }
+    }
+
+    /// Returns which row of a table widget's entries is at the given
+    /// y-coordinate, if any. The header and borders aren't part of any row.
+    fn table_row_at(&self, widget: &BottomWidget, y: u16) -> Option<u16> {
+        let (Some((_tlc_x, tlc_y)), Some((_brc_x, brc_y))) =
+            (widget.top_left_corner, widget.bottom_right_corner)
+        else {
+            return None;
+        };
+
+        let border_offset = u16::from(self.is_drawing_border());
+        let offset = border_offset + self.header_offset(widget);
+        let clicked_entry = y.checked_sub(tlc_y)?;
+        if clicked_entry < offset || y >= brc_y - border_offset {
+            return None;
+        }
+
+        Some((clicked_entry - offset) / self.app_config_fields.table_row_stride())
+    }
+
+    /// Handles a left click while a context menu, process details, the global
+    /// search, or the diagnostics viewer are shown. Returns whether any were.
+    pub fn on_popup_click(&mut self, x: u16, y: u16) -> bool {
+        if let Some(menu) = &mut self.context_menu {
+            match menu.action_at(x, y) {
+                Some(index) => {
+                    menu.selected = index;
+                    self.activate_context_menu_action();
+                }
+                None if menu.contains(x, y) => {}
+                None => self.context_menu = None,
+            }
+            true
+        } else if self.process_details.is_some() {
+            self.process_details = None;
+            true
+        } else if self.global_search.is_some() {
+            self.global_search = None;
+            true
+        } else if self.diagnostics.is_some() {
+            self.diagnostics = None;
+            true
+        } else {
+            false
+        }
+    }
+
     fn is_drawing_border(&self) -> bool {
         self.is_expanded || !self.app_config_fields.use_basic_mode
     }
@@ -2850,6 +4638,21 @@ impl App {
         // Partially copy-pasted from the single-char variant; should probably clean up
         // this process in the future. In particular, encapsulate this entire
         // logic and add some tests to make it less potentially error-prone.
+        if self.help_dialog_state.is_searching {
+            self.help_dialog_state
+                .query
+                .extend(paste.chars().filter(|c| !c.is_control()));
+            self.help_dialog_state.scroll_state.current_scroll_index = 0;
+            return;
+        }
+        if let Some(search) = &mut self.global_search {
+            search
+                .query
+                .extend(paste.chars().filter(|c| !c.is_control()));
+            self.update_global_search();
+            return;
+        }
+
         let is_in_search_widget = self.is_in_search_widget();
         if let Some(proc_widget_state) = self
             .states
@@ -2883,3 +4686,11 @@ impl App {
         }
     }
 }
+
+/// Joins PIDs with spaces, so they can be pasted as arguments to other tools.
+fn pids_text(pids: &[Pid]) -> String {
+    pids.iter()
+        .map(|pid| pid.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
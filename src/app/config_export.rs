@@ -0,0 +1,426 @@
+//! Exporting the config in use to a file, for the `export_config` action.
+//!
+//! The export starts from the config file in use, so its comments and any
+//! settings that can't change at runtime are kept. Flags are replaced with the
+//! values in use, including ones set by command-line arguments or toggled at
+//! runtime, and the layout is replaced with the current one, including any
+//! resizing.
+
+use std::{fs, path::PathBuf};
+
+use anyhow::Context;
+use serde::Serialize;
+use toml_edit::{DocumentMut, Item};
+
+use super::{
+    layout_manager::{BottomLayout, IntermediaryConstraint},
+    App, AxisScaling,
+};
+use crate::{
+    canvas::components::time_chart::GraphMarker,
+    data_collection::temperature::TemperatureType,
+    options::{
+        config::{
+            flags::FlagConfig,
+            layout::{Page, Row, RowChildren},
+            StringOrNum,
+        },
+        ConfigSource, MAIN_PAGE_NAME,
+    },
+    utils::data_units::DataUnit,
+    widgets::{ProcWidgetColumn, ProcWidgetMode},
+};
+
+/// The exported file's name. It's put next to the config file in use.
+const EXPORT_FILE_NAME: &str = "bottom-export.toml";
+
+/// Returns where the config is exported to, which is next to the config file
+/// in use, or in the current directory if there isn't one.
+pub fn export_path(source: &ConfigSource) -> PathBuf {
+    match source {
+        ConfigSource::File(path) | ConfigSource::Created(path) => {
+            path.with_file_name(EXPORT_FILE_NAME)
+        }
+        ConfigSource::Defaults => PathBuf::from(EXPORT_FILE_NAME),
+    }
+}
+
+/// Returns the flags in use. Flags for process widgets are taken from the
+/// selected one, or the first one if another widget is selected. Flags that
+/// can't be worked out from the app are left unset.
+fn flags_in_use(app: &App) -> FlagConfig {
+    let fields = &app.app_config_fields;
+
+    let mut flags = FlagConfig {
+        hide_avg_cpu: Some(!fields.show_average_cpu),
+        temperature_type: Some(
+            match fields.temperature_type {
+                TemperatureType::Celsius => "celsius",
+                TemperatureType::Kelvin => "kelvin",
+                TemperatureType::Fahrenheit => "fahrenheit",
+            }
+            .to_string(),
+        ),
+        rate: Some(StringOrNum::Num(fields.update_rate)),
+        cpu_left_legend: Some(fields.cpu_left_legend),
+        current_usage: Some(fields.use_current_cpu_total),
+        unnormalized_cpu: Some(fields.unnormalized_cpu),
+        basic: Some(fields.use_basic_mode),
+        default_time_value: Some(StringOrNum::Num(fields.default_time_value)),
+        time_delta: Some(StringOrNum::Num(fields.time_interval)),
+        time_format: Some(fields.time_format.to_string()),
+        autohide_time: Some(fields.autohide_time),
+        hide_time: Some(fields.hide_time),
+        expanded: Some(app.is_expanded),
+        use_old_network_legend: Some(fields.use_old_network_legend),
+        hide_table_gap: Some(fields.table_gap == 0),
+        disable_click: Some(fields.disable_click),
+        show_table_scroll_position: Some(fields.show_table_scroll_position),
+        disable_advanced_kill: Some(!fields.is_advanced_kill),
+        network_use_bytes: Some(fields.network_unit_type == DataUnit::Byte),
+        network_use_log: Some(fields.network_scale_type == AxisScaling::Log),
+        network_use_binary_prefix: Some(fields.network_use_binary_prefix),
+        retention: Some(StringOrNum::Num(fields.retention_ms)),
+        long_term_retention: fields.long_term_retention_ms.map(StringOrNum::Num),
+        average_cpu_row: Some(fields.dedicated_average_row),
+        http: fields.http_address.map(|address| address.to_string()),
+        dump_path: fields
+            .dump_path
+            .as_ref()
+            .map(|path| path.display().to_string()),
+        idle_rate: fields.idle_rate.map(StringOrNum::Num),
+        pause_on_blur: Some(fields.pause_on_blur),
+        mobile: Some(fields.mobile),
+        save_session: Some(fields.save_session),
+        graph_gradients: Some(fields.graph_gradients),
+        graph_style: Some(
+            match fields.graph_marker {
+                GraphMarker::Braille => "braille",
+                GraphMarker::Dot => "dot",
+                GraphMarker::Block => "block",
+                GraphMarker::Line => "line",
+            }
+            .to_string(),
+        ),
+        status_bar: Some(fields.status_bar.is_some()),
+        meter_header: Some(fields.meter_header.is_some()),
+        show_table_scrollbar: Some(fields.show_table_scrollbar),
+        table_stripes: Some(fields.table_stripes),
+        table_row_separators: Some(fields.table_row_separators),
+        ..Default::default()
+    };
+
+    // These are always off on some builds or platforms, so leave them alone
+    // there rather than turning them off elsewhere too.
+    #[cfg(feature = "gpu")]
+    {
+        flags.disable_gpu = Some(!fields.enable_gpu);
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        flags.enable_cache_memory = Some(fields.enable_cache_memory);
+    }
+
+    let proc_states = &app.states.proc_state.widget_states;
+    let proc_state = proc_states.get(&app.current_widget.widget_id).or_else(|| {
+        proc_states
+            .iter()
+            .min_by_key(|(id, _)| **id)
+            .map(|(_, state)| state)
+    });
+
+    if let Some(state) = proc_state {
+        flags.group_processes = Some(matches!(state.mode, ProcWidgetMode::Grouped));
+        flags.tree = Some(matches!(state.mode, ProcWidgetMode::Tree { .. }));
+        flags.tree_totals = Some(state.show_tree_totals);
+        flags.process_command = Some(state.is_showing_command());
+        if state.column_mapping.contains(&ProcWidgetColumn::Mem) {
+            flags.process_memory_as_value = Some(!state.is_mem_percent());
+        }
+        flags.case_sensitive = Some(!state.proc_search.is_ignoring_case);
+        flags.whole_word = Some(state.proc_search.is_searching_whole_word);
+        flags.regex = Some(state.proc_search.is_searching_with_regex);
+    }
+
+    flags
+}
+
+/// Returns the ratio of a row or column, if it has one.
+fn ratio(constraint: &IntermediaryConstraint) -> Option<u32> {
+    match constraint {
+        IntermediaryConstraint::PartialRatio(ratio) => Some(*ratio),
+        IntermediaryConstraint::CanvasHandled { .. } | IntermediaryConstraint::Grow { .. } => None,
+    }
+}
+
+/// Returns `rows` with the ratios of its rows and columns replaced with the
+/// ones in `layout`, which was made from them and may have been resized since.
+fn resized_rows(rows: &[Row], layout: &BottomLayout) -> Vec<Row> {
+    let mut rows = rows.to_vec();
+
+    for (row, bottom_row) in rows.iter_mut().zip(&layout.rows) {
+        if let Some(new_ratio) = ratio(&bottom_row.constraint) {
+            if new_ratio != row.ratio.unwrap_or(1) {
+                row.ratio = Some(new_ratio);
+            }
+        }
+
+        for (child, bottom_col) in row.child.iter_mut().flatten().zip(&bottom_row.children) {
+            let Some(new_ratio) = ratio(&bottom_col.constraint) else {
+                continue;
+            };
+
+            let child_ratio = match child {
+                RowChildren::Widget(widget) => &mut widget.ratio,
+                RowChildren::Col { ratio, .. } => ratio,
+            };
+            if new_ratio != child_ratio.unwrap_or(1) {
+                *child_ratio = Some(new_ratio);
+            }
+        }
+    }
+
+    rows
+}
+
+/// The layout part of an exported config.
+#[derive(Serialize)]
+struct ExportedLayout {
+    row: Option<Vec<Row>>,
+    page: Option<Vec<Page>>,
+}
+
+impl ExportedLayout {
+    /// Makes the layout from each page's name and rows. The main page's rows
+    /// are written as the top-level `[[row]]` entries, and the rest as pages.
+    fn new(pages: Vec<(&str, Vec<Row>)>) -> Self {
+        let mut pages = pages.into_iter().peekable();
+        let row = pages
+            .next_if(|(name, _)| *name == MAIN_PAGE_NAME)
+            .map(|(_, rows)| rows);
+        let page: Vec<Page> = pages
+            .map(|(name, row)| Page {
+                name: name.to_string(),
+                row,
+            })
+            .collect();
+
+        Self {
+            row,
+            page: (!page.is_empty()).then_some(page),
+        }
+    }
+}
+
+/// Returns `base`, the text of a config file, with `flags` set in it, and its
+/// layout replaced with `layout` if there is one. Flags that `flags` doesn't
+/// set are left as they are, and comments are kept where possible.
+fn exported_text(
+    base: &str, flags: &FlagConfig, layout: Option<ExportedLayout>,
+) -> anyhow::Result<String> {
+    let mut document: DocumentMut = base.parse()?;
+    let flags: DocumentMut = toml_edit::ser::to_string_pretty(flags)?.parse()?;
+
+    let table = document
+        .entry("flags")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .context("'flags' in the config file isn't a table")?;
+
+    for (key, value) in flags.iter() {
+        let mut value = value.clone();
+
+        // Keep any comment after the old value.
+        if let (Some(old_value), Some(new_value)) = (
+            table.get(key).and_then(Item::as_value),
+            value.as_value_mut(),
+        ) {
+            *new_value.decor_mut() = old_value.decor().clone();
+        }
+
+        table.insert(key, value);
+    }
+
+    let Some(layout) = layout else {
+        return Ok(document.to_string());
+    };
+
+    // A preset would be used over the exported layout.
+    table.remove("preset");
+    document.remove("row");
+    document.remove("page");
+
+    let mut text = document.to_string();
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text.push_str(&toml_edit::ser::to_string_pretty(&layout)?);
+
+    Ok(text)
+}
+
+/// Exports the config in use next to the config file, returning where it was
+/// written. `layouts` is the current layout of each page.
+pub fn export_config(app: &App, layouts: &[BottomLayout]) -> anyhow::Result<PathBuf> {
+    let base = match &app.config_source {
+        ConfigSource::File(path) | ConfigSource::Created(path) => fs::read_to_string(path)
+            .with_context(|| format!("couldn't read the config file at '{}'", path.display()))?,
+        ConfigSource::Defaults => String::new(),
+    };
+
+    // Pages without rows use the basic layout, which can't be written out.
+    let pages: Vec<_> = app
+        .pages
+        .iter()
+        .zip(layouts)
+        .filter(|(page, _)| !page.rows.is_empty())
+        .map(|(page, layout)| (page.name.as_str(), resized_rows(&page.rows, layout)))
+        .collect();
+    let layout = (!pages.is_empty()).then(|| ExportedLayout::new(pages));
+
+    let text = exported_text(&base, &flags_in_use(app), layout)?;
+    let path = export_path(&app.config_source);
+    fs::write(&path, text)
+        .with_context(|| format!("couldn't export the config to '{}'", path.display()))?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{
+        app::layout_manager::{BottomCol, BottomRow},
+        options::Config,
+    };
+
+    fn rows(text: &str) -> Vec<Row> {
+        toml_edit::de::from_str::<Config>(text)
+            .unwrap()
+            .row
+            .unwrap()
+    }
+
+    #[test]
+    fn export_paths() {
+        assert_eq!(
+            export_path(&ConfigSource::File(PathBuf::from(
+                "/tmp/bottom/bottom.toml"
+            ))),
+            Path::new("/tmp/bottom/bottom-export.toml")
+        );
+        assert_eq!(
+            export_path(&ConfigSource::Defaults),
+            Path::new("bottom-export.toml")
+        );
+    }
+
+    #[test]
+    fn resized_rows_keep_unchanged_ratios() {
+        let rows = rows(
+            r#"
+            [[row]]
+              [[row.child]]
+              type = "cpu"
+              [[row.child]]
+              ratio = 2
+              child = [{ type = "mem" }, { type = "net" }]
+            [[row]]
+              [[row.child]]
+              type = "proc"
+            "#,
+        );
+        let layout = BottomLayout {
+            rows: vec![
+                BottomRow::new(vec![
+                    BottomCol::new(vec![]).ratio(1),
+                    BottomCol::new(vec![]).ratio(5),
+                ])
+                .ratio(3),
+                BottomRow::new(vec![BottomCol::new(vec![]).ratio(1)]).ratio(1),
+            ],
+            total_row_height_ratio: 4,
+        };
+
+        let resized = resized_rows(&rows, &layout);
+
+        assert_eq!(resized[0].ratio, Some(3));
+        assert_eq!(resized[1].ratio, None);
+
+        let children = resized[0].child.as_ref().unwrap();
+        assert!(matches!(&children[0], RowChildren::Widget(widget) if widget.ratio.is_none()));
+        assert!(matches!(&children[1], RowChildren::Col { ratio, .. } if *ratio == Some(5)));
+    }
+
+    #[test]
+    fn exports_keep_comments() {
+        let base = "# My config.\n[flags]\nrate = 500 # Fast.\nbasic = true\n";
+        let flags = FlagConfig {
+            rate: Some(StringOrNum::Num(1000)),
+            tree: Some(true),
+            ..Default::default()
+        };
+
+        let text = exported_text(base, &flags, None).unwrap();
+
+        assert!(text.starts_with("# My config.\n[flags]\n"));
+        assert!(text.contains("rate = 1000 # Fast.\n"));
+        assert!(text.contains("basic = true\n"));
+        assert!(text.contains("tree = true\n"));
+    }
+
+    #[test]
+    fn exports_round_trip() {
+        let base = r#"
+            [flags]
+            preset = "minimal"
+
+            [[row]]
+              [[row.child]]
+              type = "cpu"
+        "#;
+        let layout = ExportedLayout::new(vec![
+            (
+                MAIN_PAGE_NAME,
+                rows(
+                    r#"
+                    [[row]]
+                    ratio = 2
+                      [[row.child]]
+                      type = "proc"
+                      filter = "btm"
+                    "#,
+                ),
+            ),
+            (
+                "extra",
+                rows(
+                    r#"
+                    [[row]]
+                      [[row.child]]
+                      type = "mem"
+                    "#,
+                ),
+            ),
+        ]);
+
+        let text = exported_text(base, &FlagConfig::default(), Some(layout)).unwrap();
+        let config: Config = toml_edit::de::from_str(&text).unwrap();
+
+        assert!(config.flags.unwrap().preset.is_none());
+
+        let row = config.row.unwrap();
+        assert_eq!(row.len(), 1);
+        assert_eq!(row[0].ratio, Some(2));
+        assert!(matches!(
+            &row[0].child.as_ref().unwrap()[0],
+            RowChildren::Widget(widget) if widget.widget_type == "proc" && widget.filter.as_deref() == Some("btm")
+        ));
+
+        let page = config.page.unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].name, "extra");
+    }
+}
@@ -0,0 +1,209 @@
+//! Writing a crash report when bottom panics, to attach to bug reports. The
+//! panic hook can't get at the [`App`], so what it was doing is recorded every
+//! tick. Anything identifying, like hostnames and command lines, is redacted
+//! from the report.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    io::Write,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+
+use super::{debug_stats::TickStats, App};
+use crate::{options::ConfigSource, utils::general::create_private_file};
+
+/// What bottom was last doing, as of the latest tick.
+static CONTEXT: Mutex<Option<CrashContext>> = Mutex::new(None);
+
+/// What bottom was doing, as recorded by [`record`].
+#[derive(Clone, Debug, Serialize)]
+struct CrashContext {
+    /// The config file in use, which is only hashed, as its path and contents
+    /// may be identifying.
+    #[serde(skip)]
+    config_path: Option<PathBuf>,
+    profile: Option<String>,
+    current_page: usize,
+    current_widget: u64,
+    is_expanded: bool,
+    is_frozen: bool,
+    is_basic: bool,
+    widgets: Vec<WidgetSummary>,
+    /// How long each data source took in the latest collection, in
+    /// milliseconds.
+    collection_timings: Vec<(&'static str, f64)>,
+    /// The command lines of the processes last seen, to redact. Names alone
+    /// aren't, as short ones would redact unrelated text.
+    #[serde(skip)]
+    commands: Vec<Arc<str>>,
+}
+
+/// A widget in the layout, and where it was last drawn.
+#[derive(Clone, Debug, Serialize)]
+struct WidgetSummary {
+    id: u64,
+    widget_type: String,
+    is_hidden: bool,
+    top_left_corner: Option<(u16, u16)>,
+    bottom_right_corner: Option<(u16, u16)>,
+}
+
+/// A crash report, as written to a file.
+#[derive(Debug, Serialize)]
+struct CrashReport {
+    version: &'static str,
+    os: String,
+    arch: &'static str,
+    message: String,
+    location: Option<String>,
+    /// A hash of the config file, to tell whether reports used the same
+    /// config without needing it.
+    config_hash: Option<String>,
+    context: Option<CrashContext>,
+    backtrace: String,
+}
+
+/// Records what `app` is doing, with the timings of the latest `tick`, in case
+/// bottom panics.
+pub fn record(app: &App, tick: &TickStats) {
+    let mut widgets: Vec<WidgetSummary> = app
+        .widget_map
+        .values()
+        .map(|widget| WidgetSummary {
+            id: widget.widget_id,
+            widget_type: widget.widget_type.get_pretty_name().to_string(),
+            is_hidden: app.hidden_widgets.contains(&widget.widget_id),
+            top_left_corner: widget.top_left_corner,
+            bottom_right_corner: widget.bottom_right_corner,
+        })
+        .collect();
+    widgets.sort_unstable_by_key(|widget| widget.id);
+
+    let config_path = match &app.config_source {
+        ConfigSource::File(path) | ConfigSource::Created(path) => Some(path.clone()),
+        ConfigSource::Defaults => None,
+    };
+
+    let commands = app
+        .data_collection
+        .process_data
+        .process_harvest
+        .values()
+        .filter(|process| process.command.contains(' '))
+        .map(|process| process.command.clone())
+        .collect();
+
+    let context = CrashContext {
+        config_path,
+        profile: app.profile.clone(),
+        current_page: app.current_page,
+        current_widget: app.current_widget.widget_id,
+        is_expanded: app.is_expanded,
+        is_frozen: app.frozen_state.is_frozen(),
+        is_basic: app.app_config_fields.use_basic_mode,
+        widgets,
+        collection_timings: tick
+            .collection
+            .iter()
+            .map(|(name, duration)| (*name, duration.as_secs_f64() * 1000.0))
+            .collect(),
+        commands,
+    };
+
+    if let Ok(mut guard) = CONTEXT.lock() {
+        *guard = Some(context);
+    }
+}
+
+/// Writes a crash report for a panic with the given `message`, returning where
+/// it was written to.
+pub fn write_report(
+    message: &str, location: Option<String>, backtrace: &str,
+) -> anyhow::Result<PathBuf> {
+    // The lock may be poisoned if the panic happened while recording.
+    let context = match CONTEXT.lock() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    };
+
+    let config_hash = context
+        .as_ref()
+        .and_then(|context| context.config_path.as_ref())
+        .and_then(|path| fs::read(path).ok())
+        .map(|contents| {
+            let mut hasher = DefaultHasher::new();
+            contents.hash(&mut hasher);
+            format!("{:016x}", hasher.finish())
+        });
+
+    let mut secrets: Vec<String> = [
+        sysinfo::System::host_name(),
+        dirs::home_dir().map(|home| home.to_string_lossy().into_owned()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if let Some(context) = &context {
+        secrets.extend(context.commands.iter().map(|command| command.to_string()));
+    }
+
+    let report = CrashReport {
+        version: env!("CARGO_PKG_VERSION"),
+        os: sysinfo::System::long_os_version().unwrap_or_else(|| std::env::consts::OS.to_string()),
+        arch: std::env::consts::ARCH,
+        message: redact(message, &secrets),
+        location: location.map(|location| redact(&location, &secrets)),
+        config_hash,
+        context,
+        backtrace: redact(backtrace, &secrets),
+    };
+
+    let path = std::env::temp_dir().join(format!("bottom-crash-{}.json", std::process::id()));
+    create_private_file(&path)?.write_all(serde_json::to_string_pretty(&report)?.as_bytes())?;
+
+    Ok(path)
+}
+
+/// Replaces every occurrence of the `secrets` in `text`. Longer ones are
+/// replaced first, so a secret containing another is replaced whole.
+fn redact(text: &str, secrets: &[String]) -> String {
+    let mut secrets: Vec<&str> = secrets
+        .iter()
+        .map(|secret| secret.trim())
+        .filter(|secret| secret.len() > 1)
+        .collect();
+    secrets.sort_unstable_by_key(|secret| std::cmp::Reverse(secret.len()));
+
+    secrets.into_iter().fold(text.to_string(), |text, secret| {
+        text.replace(secret, "<redacted>")
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn redacting() {
+        let secrets = [
+            "host".to_string(),
+            "/usr/bin/app --token abc".to_string(),
+            "/usr/bin/app".to_string(),
+            " ".to_string(),
+        ];
+
+        assert_eq!(
+            redact(
+                "'/usr/bin/app --token abc' on host failed, /usr/bin/app too",
+                &secrets
+            ),
+            "'<redacted>' on <redacted> failed, <redacted> too"
+        );
+        assert_eq!(redact("nothing to hide", &secrets), "nothing to hide");
+    }
+}
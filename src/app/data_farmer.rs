@@ -13,19 +13,27 @@
 //! memory usage and higher CPU usage - you will be trying to process more and
 //! more points as this is used!
 
-use std::{collections::BTreeMap, time::Instant, vec::Vec};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+    vec::Vec,
+};
 
 use hashbrown::HashMap;
 
 #[cfg(feature = "battery")]
 use crate::data_collection::batteries;
+#[cfg(feature = "plugins")]
+use crate::data_collection::plugins::{PluginWidgetData, PluginWidgetHarvest};
 use crate::{
+    constants::{DOWNSAMPLE_INTERVAL_MILLISECONDS, TABLE_HISTORY_LENGTH},
     data_collection::{
         cpu, disks, memory, network,
         processes::{Pid, ProcessHarvest},
         temperature, Data,
     },
-    dec_bytes_per_second_string,
+    data_conversion::dec_bytes_per_second_string,
 };
 
 #[derive(Debug, Default, Clone)]
@@ -41,6 +49,94 @@ pub struct TimedData {
     pub arc_data: Option<f64>,
     #[cfg(feature = "gpu")]
     pub gpu_data: Vec<Option<f64>>,
+    /// For a downsampled entry, whose values are averages, the lowest and
+    /// highest values of the entries it was made from.
+    pub range: Option<Box<DataRange>>,
+}
+
+/// The lowest and highest values of the entries merged into a downsampled
+/// entry, so spikes aren't lost to averaging.
+#[derive(Debug, Default, Clone)]
+pub struct DataRange {
+    pub min: TimedData,
+    pub max: TimedData,
+}
+
+impl TimedData {
+    /// Roughly how much memory an entry takes up, including its timestamp.
+    fn estimated_size(&self) -> u64 {
+        let size = std::mem::size_of::<(Instant, TimedData)>()
+            + self.cpu_data.capacity() * std::mem::size_of::<f64>();
+        #[cfg(feature = "gpu")]
+        let size = size + self.gpu_data.capacity() * std::mem::size_of::<Option<f64>>();
+
+        let range_size = self.range.as_ref().map_or(0, |range| {
+            range.min.estimated_size() + range.max.estimated_size()
+        });
+
+        size as u64 + range_size
+    }
+
+    /// Merges a group of entries into one, where each value is the average of
+    /// that value across all the entries, along with its minimum and maximum.
+    fn downsampled(entries: &[(Instant, TimedData)]) -> TimedData {
+        fn avg(values: &mut dyn Iterator<Item = f64>) -> Option<f64> {
+            let (sum, count) =
+                values.fold((0.0, 0u32), |(sum, count), value| (sum + value, count + 1));
+            (count > 0).then_some(sum / f64::from(count))
+        }
+
+        let data = entries.iter().map(|(_, e)| e);
+        let min = Self::merge(
+            data.clone()
+                .map(|e| e.range.as_ref().map_or(e, |range| &range.min)),
+            |values| values.reduce(f64::min),
+        );
+        let max = Self::merge(
+            data.clone()
+                .map(|e| e.range.as_ref().map_or(e, |range| &range.max)),
+            |values| values.reduce(f64::max),
+        );
+
+        TimedData {
+            range: Some(Box::new(DataRange { min, max })),
+            ..Self::merge(data, avg)
+        }
+    }
+
+    /// Merges entries into one, combining each value across the entries that
+    /// have it with `reduce`.
+    fn merge<'a>(
+        entries: impl Iterator<Item = &'a TimedData> + Clone,
+        reduce: impl Fn(&mut dyn Iterator<Item = f64>) -> Option<f64>,
+    ) -> TimedData {
+        let reduce_opt = |get: &dyn Fn(&'a TimedData) -> Option<f64>| {
+            reduce(&mut entries.clone().filter_map(get))
+        };
+
+        let num_cpus = entries.clone().map(|e| e.cpu_data.len()).max().unwrap_or(0);
+        #[cfg(feature = "gpu")]
+        let num_gpus = entries.clone().map(|e| e.gpu_data.len()).max().unwrap_or(0);
+
+        TimedData {
+            rx_data: reduce(&mut entries.clone().map(|e| e.rx_data)).unwrap_or(0.0),
+            tx_data: reduce(&mut entries.clone().map(|e| e.tx_data)).unwrap_or(0.0),
+            cpu_data: (0..num_cpus)
+                .map(|i| reduce_opt(&|e| e.cpu_data.get(i).copied()).unwrap_or(0.0))
+                .collect(),
+            mem_data: reduce_opt(&|e| e.mem_data),
+            #[cfg(not(target_os = "windows"))]
+            cache_data: reduce_opt(&|e| e.cache_data),
+            swap_data: reduce_opt(&|e| e.swap_data),
+            #[cfg(feature = "zfs")]
+            arc_data: reduce_opt(&|e| e.arc_data),
+            #[cfg(feature = "gpu")]
+            gpu_data: (0..num_gpus)
+                .map(|i| reduce_opt(&|e| e.gpu_data.get(i).copied().flatten()))
+                .collect(),
+            range: None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -57,21 +153,24 @@ pub struct ProcessData {
 
 impl ProcessData {
     fn ingest(&mut self, list_of_processes: Vec<ProcessHarvest>) {
-        self.process_parent_mapping.clear();
+        // Rather than clearing the whole map, we just clear the child lists so their
+        // allocations can be reused next time; ones that end up empty are removed after.
+        self.process_parent_mapping
+            .values_mut()
+            .for_each(|children| children.clear());
 
         // Reverse as otherwise the pid mappings are in the wrong order.
         list_of_processes.iter().rev().for_each(|process_harvest| {
             if let Some(parent_pid) = process_harvest.parent_pid {
-                if let Some(entry) = self.process_parent_mapping.get_mut(&parent_pid) {
-                    entry.push(process_harvest.pid);
-                } else {
-                    self.process_parent_mapping
-                        .insert(parent_pid, vec![process_harvest.pid]);
-                }
+                self.process_parent_mapping
+                    .entry(parent_pid)
+                    .or_default()
+                    .push(process_harvest.pid);
             }
         });
 
-        self.process_parent_mapping.shrink_to_fit();
+        self.process_parent_mapping
+            .retain(|_, children| !children.is_empty());
 
         let process_pid_map = list_of_processes
             .into_iter()
@@ -82,17 +181,31 @@ impl ProcessData {
         // We collect all processes that either:
         // - Do not have a parent PID (that is, they are orphan processes)
         // - Have a parent PID but we don't have the parent (we promote them as orphans)
-        self.orphan_pids = self
-            .process_harvest
-            .iter()
-            .filter_map(|(pid, process_harvest)| match process_harvest.parent_pid {
-                Some(parent_pid) if self.process_harvest.contains_key(&parent_pid) => None,
-                _ => Some(*pid),
-            })
-            .collect();
+        self.orphan_pids.clear();
+        self.orphan_pids
+            .extend(
+                self.process_harvest
+                    .iter()
+                    .filter_map(|(pid, process_harvest)| match process_harvest.parent_pid {
+                        Some(parent_pid) if self.process_harvest.contains_key(&parent_pid) => None,
+                        _ => Some(*pid),
+                    }),
+            );
     }
 }
 
+/// The data shown by the process, disk, and temperature tables at one point in
+/// time. The last [`TABLE_HISTORY_LENGTH`] of these are kept so the tables can
+/// be stepped back through along with the graphs while frozen.
+#[derive(Clone, Debug)]
+pub struct TableSnapshot {
+    pub time: Instant,
+    pub process_data: Arc<ProcessData>,
+    pub disk_harvest: Vec<disks::DiskHarvest>,
+    pub io_labels: Vec<(String, String)>,
+    pub temp_harvest: Vec<temperature::TempHarvest>,
+}
+
 /// AppCollection represents the pooled data stored within the main app
 /// thread.  Basically stores a (occasionally cleaned) record of the data
 /// collected, and what is needed to convert into a displayable form.
@@ -107,6 +220,9 @@ impl ProcessData {
 pub struct DataCollection {
     pub current_instant: Instant,
     pub timed_data_vec: Vec<(Instant, TimedData)>,
+    /// How many entries at the start of `timed_data_vec` have already been
+    /// downsampled, so they aren't looked at again.
+    pub(crate) downsampled_len: usize,
     pub network_harvest: network::NetworkHarvest,
     pub memory_harvest: memory::MemHarvest,
     #[cfg(not(target_os = "windows"))]
@@ -114,18 +230,23 @@ pub struct DataCollection {
     pub swap_harvest: memory::MemHarvest,
     pub cpu_harvest: cpu::CpuHarvest,
     pub load_avg_harvest: cpu::LoadAvgHarvest,
-    pub process_data: ProcessData,
+    pub process_data: Arc<ProcessData>,
     pub disk_harvest: Vec<disks::DiskHarvest>,
     pub io_harvest: disks::IoHarvest,
     pub io_labels_and_prev: Vec<((u64, u64), (u64, u64))>,
     pub io_labels: Vec<(String, String)>,
     pub temp_harvest: Vec<temperature::TempHarvest>,
+    /// The table data of the latest updates, oldest first.
+    pub table_history: VecDeque<TableSnapshot>,
     #[cfg(feature = "battery")]
     pub battery_harvest: Vec<batteries::BatteryData>,
     #[cfg(feature = "zfs")]
     pub arc_harvest: memory::MemHarvest,
     #[cfg(feature = "gpu")]
     pub gpu_harvest: Vec<(String, memory::MemHarvest)>,
+    /// The data for each plugin widget, by plugin name.
+    #[cfg(feature = "plugins")]
+    pub plugin_data: HashMap<String, PluginWidgetData>,
 }
 
 impl Default for DataCollection {
@@ -133,6 +254,7 @@ impl Default for DataCollection {
         DataCollection {
             current_instant: Instant::now(),
             timed_data_vec: Vec::default(),
+            downsampled_len: 0,
             network_harvest: network::NetworkHarvest::default(),
             memory_harvest: memory::MemHarvest::default(),
             #[cfg(not(target_os = "windows"))]
@@ -146,12 +268,15 @@ impl Default for DataCollection {
             io_labels_and_prev: Vec::default(),
             io_labels: Vec::default(),
             temp_harvest: Vec::default(),
+            table_history: VecDeque::default(),
             #[cfg(feature = "battery")]
             battery_harvest: Vec::default(),
             #[cfg(feature = "zfs")]
             arc_harvest: memory::MemHarvest::default(),
             #[cfg(feature = "gpu")]
             gpu_harvest: Vec::default(),
+            #[cfg(feature = "plugins")]
+            plugin_data: HashMap::default(),
         }
     }
 }
@@ -159,6 +284,7 @@ impl Default for DataCollection {
 impl DataCollection {
     pub fn reset(&mut self) {
         self.timed_data_vec = Vec::default();
+        self.downsampled_len = 0;
         self.network_harvest = network::NetworkHarvest::default();
         self.memory_harvest = memory::MemHarvest::default();
         self.swap_harvest = memory::MemHarvest::default();
@@ -168,6 +294,7 @@ impl DataCollection {
         self.io_harvest = disks::IoHarvest::default();
         self.io_labels_and_prev = Vec::default();
         self.temp_harvest = Vec::default();
+        self.table_history = VecDeque::default();
         #[cfg(feature = "battery")]
         {
             self.battery_harvest = Vec::default();
@@ -180,12 +307,151 @@ impl DataCollection {
         {
             self.gpu_harvest = Vec::default();
         }
+        #[cfg(feature = "plugins")]
+        {
+            self.plugin_data = HashMap::default();
+        }
+    }
+
+    /// Returns the index of the entry in `timed_data_vec` corresponding to the
+    /// current time, if it exists.
+    pub fn current_index(&self) -> Option<usize> {
+        self.timed_data_vec
+            .binary_search_by(|(instant, _)| instant.cmp(&self.current_instant))
+            .ok()
+    }
+
+    /// Shows the table data kept from the current time in the tables, or the
+    /// oldest kept if the current time is further back than that.
+    pub fn show_tables_at_current_time(&mut self) {
+        let index = self
+            .table_history
+            .partition_point(|snapshot| snapshot.time <= self.current_instant)
+            .saturating_sub(1);
+
+        if let Some(snapshot) = self.table_history.get(index) {
+            self.process_data = snapshot.process_data.clone();
+            self.disk_harvest = snapshot.disk_harvest.clone();
+            self.io_labels = snapshot.io_labels.clone();
+            self.temp_harvest = snapshot.temp_harvest.clone();
+        }
+    }
+
+    /// Returns how long before the current time the tables' data is from, if
+    /// they can't go back as far as the current time.
+    pub fn table_lag(&self) -> Option<Duration> {
+        let oldest = self.table_history.front()?.time;
+        (oldest > self.current_instant).then(|| oldest.duration_since(self.current_instant))
+    }
+
+    /// Returns the [`TimedData`] corresponding to the current time. Normally
+    /// this is the latest entry, but may not be if the data has been scrubbed
+    /// back in time while frozen.
+    pub fn current_timed_data(&self) -> Option<&TimedData> {
+        self.current_index()
+            .and_then(|index| self.timed_data_vec.get(index))
+            .map(|(_, data)| data)
     }
 
-    pub fn clean_data(&mut self, max_time_millis: u64) {
+    /// Returns the [`TimedData`] closest to `time_back_millis` before the
+    /// current time.
+    pub fn timed_data_at(&self, time_back_millis: u64) -> Option<&TimedData> {
+        let end = self
+            .current_index()
+            .map_or(self.timed_data_vec.len(), |index| index + 1);
+        let entries = &self.timed_data_vec[..end];
+
+        let Some(target) = self
+            .current_instant
+            .checked_sub(Duration::from_millis(time_back_millis))
+        else {
+            return entries.first().map(|(_, data)| data);
+        };
+
+        let index = entries.partition_point(|(time, _)| *time < target);
+        let closest = match (
+            index.checked_sub(1).map(|i| &entries[i]),
+            entries.get(index),
+        ) {
+            (Some(older), Some(newer)) => {
+                if target.duration_since(older.0) < newer.0.duration_since(target) {
+                    older
+                } else {
+                    newer
+                }
+            }
+            (Some(older), None) => older,
+            (None, Some(newer)) => newer,
+            (None, None) => return None,
+        };
+
+        Some(&closest.1)
+    }
+
+    /// Removes data older than `max_time_millis`.
+    ///
+    /// If `long_term_max_time_millis` is set, then data older than
+    /// `max_time_millis` is instead downsampled into one entry per
+    /// [`DOWNSAMPLE_INTERVAL_MILLISECONDS`], holding the average, minimum, and
+    /// maximum of each value, and only data older than
+    /// `long_term_max_time_millis` is removed.
+    pub fn clean_data(&mut self, max_time_millis: u64, long_term_max_time_millis: Option<u64>) {
         let current_time = Instant::now();
 
-        let remove_index = match self
+        let remove_index = self.index_older_than(
+            current_time,
+            long_term_max_time_millis.map_or(max_time_millis, |ms| ms.max(max_time_millis)),
+        );
+        self.drain_oldest(remove_index);
+
+        if long_term_max_time_millis.is_some() {
+            let downsample_index = self.index_older_than(current_time, max_time_millis);
+            self.downsample(
+                downsample_index,
+                Duration::from_millis(DOWNSAMPLE_INTERVAL_MILLISECONDS),
+            );
+        }
+
+        self.timed_data_vec.shrink_to_fit();
+    }
+
+    /// Drops the oldest entries if the history would take up more than roughly
+    /// `max_bytes`, going by the size of the latest entry. To avoid shifting
+    /// the history every update, it's trimmed to 90% of the limit at a time.
+    pub fn cap_history(&mut self, max_bytes: u64) {
+        let Some((_, latest)) = self.timed_data_vec.last() else {
+            return;
+        };
+
+        let max_entries = (max_bytes / latest.estimated_size()).max(1) as usize;
+        if self.timed_data_vec.len() > max_entries {
+            let keep = (max_entries * 9 / 10).max(1);
+            self.drain_oldest(self.timed_data_vec.len() - keep);
+        }
+    }
+
+    /// Removes the oldest `count` entries.
+    fn drain_oldest(&mut self, count: usize) {
+        self.timed_data_vec.drain(0..count);
+        self.downsampled_len = self.downsampled_len.saturating_sub(count);
+
+        // Tables aren't kept from further back than the graphs.
+        while let Some(snapshot) = self.table_history.front() {
+            if self
+                .timed_data_vec
+                .first()
+                .is_some_and(|(time, _)| snapshot.time >= *time)
+            {
+                break;
+            }
+            self.table_history.pop_front();
+        }
+    }
+
+    /// Returns the index of the first entry that is at most `max_time_millis`
+    /// older than `current_time`.
+    fn index_older_than(&self, current_time: Instant, max_time_millis: u64) -> usize {
+        match self
             .timed_data_vec
             .binary_search_by(|(instant, _timed_data)| {
                 current_time
@@ -196,10 +462,40 @@ impl DataCollection {
             }) {
             Ok(index) => index,
             Err(index) => index,
-        };
+        }
+    }
 
-        self.timed_data_vec.drain(0..remove_index);
-        self.timed_data_vec.shrink_to_fit();
+    /// Merges the entries before `end` into downsampled entries, each covering
+    /// at most `interval` worth of time.
+    ///
+    /// A group is only merged once it is complete - that is, once there is an
+    /// entry at least `interval` after its start. Entries that were already
+    /// merged are skipped, and merged entries are written over the ones they
+    /// replace, so only the new entries are looked at each time.
+    fn downsample(&mut self, end: usize, interval: Duration) {
+        let first = self.downsampled_len.min(self.timed_data_vec.len());
+        let mut start = first;
+        let mut written = first;
+
+        while start < end {
+            let start_time = self.timed_data_vec[start].0;
+            let group_end = start
+                + self.timed_data_vec[start..]
+                    .partition_point(|(time, _)| time.duration_since(start_time) < interval);
+
+            if group_end > end || group_end == self.timed_data_vec.len() {
+                // This group isn't complete yet, so leave it alone for now.
+                break;
+            }
+
+            let merged = TimedData::downsampled(&self.timed_data_vec[start..group_end]);
+            self.timed_data_vec[written] = (start_time, merged);
+            written += 1;
+            start = group_end;
+        }
+
+        self.timed_data_vec.drain(written..start);
+        self.downsampled_len = written;
     }
 
     #[allow(
@@ -271,9 +567,31 @@ impl DataCollection {
             }
         }
 
+        #[cfg(feature = "plugins")]
+        if let Some(plugins) = harvested_data.plugins {
+            self.eat_plugins(plugins);
+        }
+
         // And we're done eating.  Update time and push the new entry!
         self.current_instant = harvested_time;
         self.timed_data_vec.push((harvested_time, new_entry));
+        self.push_table_snapshot(harvested_time);
+    }
+
+    /// Keeps the current table data, dropping the oldest kept past
+    /// [`TABLE_HISTORY_LENGTH`].
+    fn push_table_snapshot(&mut self, time: Instant) {
+        if self.table_history.len() >= TABLE_HISTORY_LENGTH {
+            self.table_history.pop_front();
+        }
+
+        self.table_history.push_back(TableSnapshot {
+            time,
+            process_data: self.process_data.clone(),
+            disk_harvest: self.disk_harvest.clone(),
+            io_labels: self.io_labels.clone(),
+            temp_harvest: self.temp_harvest.clone(),
+        });
     }
 
     fn eat_memory_and_swap(
@@ -339,7 +657,7 @@ impl DataCollection {
                 {
                     match &device.volume_name {
                         Some(volume_name) => Some(volume_name.as_str()),
-                        None => device.name.split('/').last(),
+                        None => device.name.split('/').next_back(),
                     }
                 }
                 #[cfg(not(target_os = "windows"))]
@@ -350,12 +668,12 @@ impl DataCollection {
                             Some(device.name.as_str()) // use the whole zfs
                                                        // dataset name
                         } else {
-                            device.name.split('/').last()
+                            device.name.split('/').next_back()
                         }
                     }
                     #[cfg(not(feature = "zfs"))]
                     {
-                        device.name.split('/').last()
+                        device.name.split('/').next_back()
                     }
                 }
             };
@@ -441,7 +759,12 @@ impl DataCollection {
     }
 
     fn eat_proc(&mut self, list_of_processes: Vec<ProcessHarvest>) {
-        self.process_data.ingest(list_of_processes);
+        // The previous data is usually still kept in the table history, in which
+        // case this starts from scratch rather than reusing its allocations.
+        let mut process_data =
+            Arc::try_unwrap(std::mem::take(&mut self.process_data)).unwrap_or_default();
+        process_data.ingest(list_of_processes);
+        self.process_data = Arc::new(process_data);
     }
 
     #[cfg(feature = "battery")]
@@ -449,6 +772,17 @@ impl DataCollection {
         self.battery_harvest = list_of_batteries;
     }
 
+    #[cfg(feature = "plugins")]
+    fn eat_plugins(&mut self, plugins: Vec<(String, PluginWidgetHarvest)>) {
+        for (name, harvest) in plugins {
+            if let Some(data) = self.plugin_data.get_mut(&name) {
+                data.update(harvest);
+            } else {
+                self.plugin_data.insert(name, harvest.into());
+            }
+        }
+    }
+
     #[cfg(feature = "zfs")]
     fn eat_arc(&mut self, arc: memory::MemHarvest, new_entry: &mut TimedData) {
         new_entry.arc_data = arc.checked_percent();
@@ -466,3 +800,219 @@ impl DataCollection {
         self.gpu_harvest = gpu;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(cpu: f64, mem: Option<f64>) -> TimedData {
+        TimedData {
+            cpu_data: vec![cpu],
+            mem_data: mem,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn downsample_timed_data() {
+        let now = Instant::now();
+        let a = (now, entry(10.0, Some(20.0)));
+        let b = (now, entry(30.0, None));
+        let c = (now, entry(50.0, Some(40.0)));
+
+        let merged = TimedData::downsampled(&[a, b.clone(), c]);
+        assert_eq!(merged.cpu_data, vec![30.0]);
+        assert_eq!(merged.mem_data, Some(30.0));
+
+        let range = merged.range.as_ref().unwrap();
+        assert_eq!(range.min.cpu_data, vec![10.0]);
+        assert_eq!(range.max.cpu_data, vec![50.0]);
+        assert_eq!(range.min.mem_data, Some(20.0));
+        assert_eq!(range.max.mem_data, Some(40.0));
+        assert!(range.min.range.is_none());
+
+        // Merging downsampled entries keeps their extremes rather than their
+        // averages.
+        let merged = TimedData::downsampled(&[(now, merged), (now, entry(35.0, None))]);
+        let range = merged.range.as_ref().unwrap();
+        assert_eq!(range.min.cpu_data, vec![10.0]);
+        assert_eq!(range.max.cpu_data, vec![50.0]);
+
+        let merged = TimedData::downsampled(&[b]);
+        assert_eq!(merged.mem_data, None);
+        assert_eq!(merged.range.unwrap().max.mem_data, None);
+    }
+
+    #[test]
+    fn timed_data_at() {
+        let start = Instant::now();
+        let mut data = DataCollection::default();
+        assert!(data.timed_data_at(0).is_none());
+
+        for i in 0..10 {
+            let time = start + Duration::from_secs(i);
+            data.timed_data_vec.push((time, entry(i as f64, None)));
+            data.current_instant = time;
+        }
+
+        let cpu_at =
+            |data: &DataCollection, time_back| data.timed_data_at(time_back).unwrap().cpu_data[0];
+        assert_eq!(cpu_at(&data, 0), 9.0);
+        assert_eq!(cpu_at(&data, 3_000), 6.0);
+        assert_eq!(cpu_at(&data, 3_400), 6.0);
+        assert_eq!(cpu_at(&data, 3_600), 5.0);
+        assert_eq!(cpu_at(&data, 100_000), 0.0);
+
+        // This should be relative to the current time, not the latest data.
+        data.current_instant = start + Duration::from_secs(5);
+        assert_eq!(cpu_at(&data, 0), 5.0);
+        assert_eq!(cpu_at(&data, 1_000), 4.0);
+    }
+
+    #[test]
+    fn cap_history() {
+        let start = Instant::now();
+        let mut data = DataCollection::default();
+        for i in 0..100 {
+            data.timed_data_vec
+                .push((start + Duration::from_secs(i), entry(i as f64, None)));
+        }
+        let entry_size = data.timed_data_vec[0].1.estimated_size();
+
+        // Under the limit, nothing changes.
+        data.cap_history(entry_size * 100);
+        assert_eq!(data.timed_data_vec.len(), 100);
+
+        // Over it, the oldest entries are dropped down to 90% of the limit.
+        data.cap_history(entry_size * 50);
+        assert_eq!(data.timed_data_vec.len(), 45);
+        assert_eq!(data.timed_data_vec[0].1.cpu_data, vec![55.0]);
+
+        // The latest entry is always kept.
+        data.cap_history(1);
+        assert_eq!(data.timed_data_vec.len(), 1);
+        assert_eq!(data.timed_data_vec[0].1.cpu_data, vec![99.0]);
+    }
+
+    #[test]
+    fn downsample_complete_groups() {
+        let start = Instant::now();
+        let interval = Duration::from_secs(60);
+        let mut data = DataCollection::default();
+        for i in 0..150 {
+            data.timed_data_vec
+                .push((start + Duration::from_secs(i), entry(i as f64, None)));
+        }
+
+        // Only the first two minutes form complete groups.
+        data.downsample(130, interval);
+        assert_eq!(data.timed_data_vec.len(), 2 + 30);
+        assert_eq!(data.timed_data_vec[0].0, start);
+        assert_eq!(data.timed_data_vec[0].1.cpu_data, vec![29.5]);
+        let range = data.timed_data_vec[0].1.range.as_ref().unwrap();
+        assert_eq!(range.min.cpu_data, vec![0.0]);
+        assert_eq!(range.max.cpu_data, vec![59.0]);
+        assert_eq!(data.timed_data_vec[1].0, start + interval);
+        assert_eq!(data.timed_data_vec[1].1.cpu_data, vec![89.5]);
+        assert_eq!(data.timed_data_vec[2].0, start + interval * 2);
+
+        // Downsampling again shouldn't touch what was already downsampled.
+        let before = data.timed_data_vec.clone();
+        data.downsample(10, interval);
+        assert_eq!(data.timed_data_vec.len(), before.len());
+        assert!(data
+            .timed_data_vec
+            .iter()
+            .zip(&before)
+            .all(|(a, b)| a.0 == b.0 && a.1.cpu_data == b.1.cpu_data));
+
+        // Only newly complete groups are merged later on, even after the oldest
+        // entries are dropped.
+        for i in 150..200 {
+            data.timed_data_vec
+                .push((start + Duration::from_secs(i), entry(i as f64, None)));
+        }
+        data.drain_oldest(1);
+        assert_eq!(data.downsampled_len, 1);
+
+        data.downsample(data.timed_data_vec.len(), interval);
+        assert_eq!(data.timed_data_vec.len(), 2 + 20);
+        assert_eq!(data.timed_data_vec[0].1.cpu_data, vec![89.5]);
+        assert_eq!(data.timed_data_vec[1].0, start + interval * 2);
+        assert_eq!(data.timed_data_vec[1].1.cpu_data, vec![149.5]);
+        assert_eq!(data.timed_data_vec[2].0, start + interval * 3);
+    }
+
+    #[test]
+    fn process_ingest_across_runs() {
+        fn process(pid: Pid, parent_pid: Option<Pid>) -> ProcessHarvest {
+            ProcessHarvest {
+                pid,
+                parent_pid,
+                ..Default::default()
+            }
+        }
+
+        let mut data = ProcessData::default();
+        data.ingest(vec![
+            process(1, None),
+            process(2, Some(1)),
+            process(3, Some(1)),
+            process(4, Some(2)),
+        ]);
+        assert_eq!(data.process_parent_mapping[&1], vec![3, 2]);
+        assert_eq!(data.process_parent_mapping[&2], vec![4]);
+        assert_eq!(data.orphan_pids, vec![1]);
+
+        // Stale children and parents shouldn't stick around.
+        data.ingest(vec![
+            process(1, None),
+            process(3, Some(1)),
+            process(4, Some(2)),
+        ]);
+        assert_eq!(data.process_parent_mapping[&1], vec![3]);
+        assert_eq!(data.process_parent_mapping[&2], vec![4]);
+        assert_eq!(data.orphan_pids, vec![1, 4]);
+
+        data.ingest(vec![process(5, None)]);
+        assert!(data.process_parent_mapping.is_empty());
+        assert_eq!(data.orphan_pids, vec![5]);
+    }
+    #[test]
+    fn table_history() {
+        let start = Instant::now();
+        let mut data = DataCollection::default();
+        let num_updates = TABLE_HISTORY_LENGTH + 10;
+        for i in 0..num_updates {
+            let time = start + Duration::from_secs(i as u64);
+            data.eat_proc(vec![ProcessHarvest {
+                pid: i as Pid,
+                ..Default::default()
+            }]);
+            data.timed_data_vec.push((time, TimedData::default()));
+            data.current_instant = time;
+            data.push_table_snapshot(time);
+        }
+
+        let shown_pid =
+            |data: &DataCollection| *data.process_data.process_harvest.keys().next().unwrap();
+        assert_eq!(data.table_history.len(), TABLE_HISTORY_LENGTH);
+        assert_eq!(data.table_lag(), None);
+
+        data.current_instant = start + Duration::from_secs(20);
+        data.show_tables_at_current_time();
+        assert_eq!(shown_pid(&data), 20);
+        assert_eq!(data.table_lag(), None);
+
+        // Past the oldest kept, the oldest is shown.
+        data.current_instant = start + Duration::from_secs(5);
+        data.show_tables_at_current_time();
+        assert_eq!(shown_pid(&data), 10);
+        assert_eq!(data.table_lag(), Some(Duration::from_secs(5)));
+
+        // Tables aren't kept from further back than the graphs.
+        data.drain_oldest(30);
+        assert_eq!(data.table_history.len(), num_updates - 30);
+        assert_eq!(data.table_history[0].time, start + Duration::from_secs(30));
+    }
+}
@@ -0,0 +1,181 @@
+//! Timings of recent ticks, shown in the debug overlay.
+
+use std::{collections::VecDeque, fmt, time::Duration};
+
+use crate::data_collection::CollectionTimings;
+
+/// How many ticks to keep timings for.
+pub const DEBUG_STATS_TICKS: usize = 30;
+
+/// How long each part of a single tick (collecting, converting, and drawing a
+/// new set of data) took.
+#[derive(Clone, Debug, Default)]
+pub struct TickStats {
+    /// How long each data source took to collect.
+    pub collection: Vec<(&'static str, Duration)>,
+    /// How long it took to convert the data for the widgets.
+    pub conversion: Duration,
+    /// How long it took to draw.
+    pub draw: Duration,
+    /// How long the data waited after being collected before the event loop
+    /// got to it.
+    pub latency: Duration,
+}
+
+impl TickStats {
+    /// Creates a new [`TickStats`] from the timings of a collection.
+    pub fn new(timings: CollectionTimings) -> Self {
+        Self {
+            latency: timings
+                .finished
+                .map(|finished| finished.elapsed())
+                .unwrap_or_default(),
+            collection: timings.sources,
+            ..Default::default()
+        }
+    }
+
+    fn durations(&self) -> impl Iterator<Item = (&'static str, Duration)> + '_ {
+        self.collection.iter().copied().chain([
+            ("conversion", self.conversion),
+            ("draw", self.draw),
+            ("latency", self.latency),
+        ])
+    }
+}
+
+/// A summary of how long something took over the recorded ticks.
+#[derive(Debug, PartialEq, Eq)]
+pub struct DurationSummary {
+    pub name: &'static str,
+    pub avg: Duration,
+    pub max: Duration,
+}
+
+impl DurationSummary {
+    /// Returns a header that lines up with how [`DurationSummary`] is
+    /// displayed.
+    pub fn header() -> String {
+        format!("{:<14}{:>11}{:>11}", "", "avg", "max")
+    }
+}
+
+impl fmt::Display for DurationSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn format_duration(duration: Duration) -> String {
+            format!("{:.2}ms", duration.as_secs_f64() * 1000.0)
+        }
+
+        write!(
+            f,
+            "{:<14}{:>11}{:>11}",
+            self.name,
+            format_duration(self.avg),
+            format_duration(self.max)
+        )
+    }
+}
+
+/// Keeps the timings of the last [`DEBUG_STATS_TICKS`] ticks, and whether to
+/// show them.
+#[derive(Debug, Default)]
+pub struct DebugStats {
+    pub is_showing: bool,
+    ticks: VecDeque<TickStats>,
+}
+
+impl DebugStats {
+    /// Records a tick, dropping the oldest one if needed.
+    pub fn push(&mut self, tick: TickStats) {
+        if self.ticks.len() >= DEBUG_STATS_TICKS {
+            self.ticks.pop_front();
+        }
+        self.ticks.push_back(tick);
+    }
+
+    /// Returns the number of recorded ticks.
+    pub fn num_ticks(&self) -> usize {
+        self.ticks.len()
+    }
+
+    /// Returns the average and max of each timing over the recorded ticks.
+    pub fn summary(&self) -> Vec<DurationSummary> {
+        let mut summary: Vec<DurationSummary> = Vec::new();
+
+        for (name, duration) in self.ticks.iter().flat_map(TickStats::durations) {
+            match summary.iter_mut().find(|entry| entry.name == name) {
+                Some(entry) => {
+                    entry.avg += duration;
+                    entry.max = entry.max.max(duration);
+                }
+                None => summary.push(DurationSummary {
+                    name,
+                    avg: duration,
+                    max: duration,
+                }),
+            }
+        }
+
+        let num_ticks = self.ticks.len() as u32;
+        for entry in &mut summary {
+            entry.avg /= num_ticks;
+        }
+
+        summary
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tick(cpu_ms: u64, draw_ms: u64) -> TickStats {
+        TickStats {
+            collection: vec![("cpu", Duration::from_millis(cpu_ms))],
+            draw: Duration::from_millis(draw_ms),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn summarize_ticks() {
+        let mut stats = DebugStats::default();
+        assert!(stats.summary().is_empty());
+
+        stats.push(tick(2, 10));
+        stats.push(tick(4, 20));
+
+        let summary = stats.summary();
+        assert_eq!(summary.len(), 4);
+        assert_eq!(
+            summary[0],
+            DurationSummary {
+                name: "cpu",
+                avg: Duration::from_millis(3),
+                max: Duration::from_millis(4),
+            }
+        );
+        assert_eq!(
+            summary[2],
+            DurationSummary {
+                name: "draw",
+                avg: Duration::from_millis(15),
+                max: Duration::from_millis(20),
+            }
+        );
+    }
+
+    #[test]
+    fn only_keep_recent_ticks() {
+        let mut stats = DebugStats::default();
+        for i in 0..(DEBUG_STATS_TICKS as u64 + 5) {
+            stats.push(tick(i, 0));
+        }
+
+        assert_eq!(stats.num_ticks(), DEBUG_STATS_TICKS);
+        assert_eq!(
+            stats.summary()[0].max,
+            Duration::from_millis(DEBUG_STATS_TICKS as u64 + 4)
+        );
+    }
+}
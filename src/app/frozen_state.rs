@@ -1,19 +1,21 @@
+use std::time::Duration;
+
 use super::DataCollection;
 
 /// The [`FrozenState`] indicates whether the application state should be
 /// frozen. It is either not frozen or frozen and containing a copy of the state
 /// at the time.
+///
+/// While frozen, the copy's current time can be moved back and forth through
+/// its stored history, which lets us "scrub" through old data. Tables follow
+/// along as far back as their own, shorter, history goes.
+#[derive(Default)]
 pub enum FrozenState {
+    #[default]
     NotFrozen,
     Frozen(Box<DataCollection>),
 }
 
-impl Default for FrozenState {
-    fn default() -> Self {
-        Self::NotFrozen
-    }
-}
-
 pub type IsFrozen = bool;
 
 impl FrozenState {
@@ -43,4 +45,200 @@ impl FrozenState {
             true
         }
     }
+
+    /// Returns how far back from the time of freezing we are currently viewing,
+    /// if frozen.
+    pub fn scrub_offset(&self) -> Option<Duration> {
+        match self {
+            FrozenState::NotFrozen => None,
+            FrozenState::Frozen(data) => Some(
+                data.timed_data_vec
+                    .last()
+                    .map(|(latest, _)| latest.saturating_duration_since(data.current_instant))
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+
+    /// Returns how much later than the graphs the tables are showing, if frozen
+    /// and the tables can't go back as far as the graphs.
+    pub fn table_lag(&self) -> Option<Duration> {
+        match self {
+            FrozenState::NotFrozen => None,
+            FrozenState::Frozen(data) => data.table_lag(),
+        }
+    }
+
+    /// Moves the frozen data's current time back by `steps` data points.
+    /// Returns whether anything changed.
+    pub fn step_back(&mut self, steps: usize) -> bool {
+        self.scrub(|_, index| index.saturating_sub(steps))
+    }
+
+    /// Moves the frozen data's current time forward by `steps` data points.
+    /// Returns whether anything changed.
+    pub fn step_forward(&mut self, steps: usize) -> bool {
+        self.scrub(|_, index| index.saturating_add(steps))
+    }
+
+    /// Moves the frozen data's current time back by (roughly) `duration`.
+    /// Returns whether anything changed.
+    pub fn step_back_by(&mut self, duration: Duration) -> bool {
+        self.scrub(|data, index| {
+            match data.current_instant.checked_sub(duration) {
+                // Go to the first point at or after the target time, but always move at least one.
+                Some(target) => data
+                    .timed_data_vec
+                    .partition_point(|(time, _)| *time < target)
+                    .min(index.saturating_sub(1)),
+                None => 0,
+            }
+        })
+    }
+
+    /// Moves the frozen data's current time forward by (roughly) `duration`.
+    /// Returns whether anything changed.
+    pub fn step_forward_by(&mut self, duration: Duration) -> bool {
+        self.scrub(|data, index| {
+            match data.current_instant.checked_add(duration) {
+                // Go to the last point at or before the target time, but always move at least one.
+                Some(target) => data
+                    .timed_data_vec
+                    .partition_point(|(time, _)| *time <= target)
+                    .saturating_sub(1)
+                    .max(index.saturating_add(1)),
+                None => usize::MAX,
+            }
+        })
+    }
+
+    /// Move the frozen data's current time to the data point at the index
+    /// returned by `new_index`, which is given the data and the current index.
+    /// The tables are moved to the table data kept from then.
+    fn scrub(&mut self, new_index: impl FnOnce(&DataCollection, usize) -> usize) -> bool {
+        let FrozenState::Frozen(data) = self else {
+            return false;
+        };
+
+        let Some(current_index) = data.current_index() else {
+            return false;
+        };
+
+        let last_index = data.timed_data_vec.len().saturating_sub(1);
+        let index = new_index(data, current_index).min(last_index);
+
+        if index == current_index {
+            false
+        } else {
+            data.current_instant = data.timed_data_vec[index].0;
+            data.show_tables_at_current_time();
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::app::data_farmer::{TableSnapshot, TimedData};
+
+    fn frozen_data(points: u64) -> FrozenState {
+        let start = Instant::now();
+        let mut data = DataCollection::default();
+        for i in 0..points {
+            let time = start + Duration::from_secs(i);
+            data.timed_data_vec.push((time, TimedData::default()));
+            data.current_instant = time;
+        }
+
+        FrozenState::Frozen(Box::new(data))
+    }
+
+    #[test]
+    fn not_frozen_does_nothing() {
+        let mut state = FrozenState::NotFrozen;
+
+        assert!(!state.step_back(1));
+        assert!(!state.step_forward(1));
+        assert_eq!(state.scrub_offset(), None);
+    }
+
+    #[test]
+    fn step_by_points() {
+        let mut state = frozen_data(10);
+        assert_eq!(state.scrub_offset(), Some(Duration::ZERO));
+
+        // Can't go past the latest point.
+        assert!(!state.step_forward(1));
+
+        assert!(state.step_back(3));
+        assert_eq!(state.scrub_offset(), Some(Duration::from_secs(3)));
+
+        assert!(state.step_back(100));
+        assert_eq!(state.scrub_offset(), Some(Duration::from_secs(9)));
+        assert!(!state.step_back(1));
+
+        assert!(state.step_forward(4));
+        assert_eq!(state.scrub_offset(), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn step_by_duration() {
+        let mut state = frozen_data(60);
+
+        assert!(state.step_back_by(Duration::from_secs(15)));
+        assert_eq!(state.scrub_offset(), Some(Duration::from_secs(15)));
+
+        // Smaller than the gap between points should still move us.
+        assert!(state.step_back_by(Duration::from_millis(100)));
+        assert_eq!(state.scrub_offset(), Some(Duration::from_secs(16)));
+
+        assert!(state.step_forward_by(Duration::from_secs(10)));
+        assert_eq!(state.scrub_offset(), Some(Duration::from_secs(6)));
+
+        assert!(state.step_forward_by(Duration::from_secs(100)));
+        assert_eq!(state.scrub_offset(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn step_tables() {
+        let start = Instant::now();
+        let mut data = DataCollection::default();
+        for i in 0..10 {
+            let time = start + Duration::from_secs(i);
+            data.timed_data_vec.push((time, TimedData::default()));
+            data.current_instant = time;
+
+            // Tables are only kept for the last few updates.
+            if i >= 5 {
+                data.table_history.push_back(TableSnapshot {
+                    time,
+                    process_data: Default::default(),
+                    disk_harvest: Vec::new(),
+                    io_labels: vec![(i.to_string(), i.to_string())],
+                    temp_harvest: Vec::new(),
+                });
+            }
+        }
+        let mut state = FrozenState::Frozen(Box::new(data));
+
+        let shown_label = |state: &FrozenState| match state {
+            FrozenState::Frozen(data) => data.io_labels[0].0.clone(),
+            FrozenState::NotFrozen => unreachable!(),
+        };
+
+        assert!(state.step_back(2));
+        assert_eq!(shown_label(&state), "7");
+        assert_eq!(state.table_lag(), None);
+
+        assert!(state.step_back(5));
+        assert_eq!(shown_label(&state), "5");
+        assert_eq!(state.table_lag(), Some(Duration::from_secs(3)));
+
+        assert!(state.step_forward_by(Duration::from_secs(100)));
+        assert_eq!(shown_label(&state), "9");
+        assert_eq!(state.table_lag(), None);
+    }
 }
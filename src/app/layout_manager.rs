@@ -1,6 +1,15 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, ops::RangeInclusive};
 
-use crate::{constants::DEFAULT_WIDGET_ID, options::OptionError};
+use hashbrown::HashSet;
+
+use crate::{
+    constants::DEFAULT_WIDGET_ID,
+    data_collection::UsedWidgets,
+    options::{
+        config::{layout::Row, style::StyleConfig},
+        OptionError,
+    },
+};
 
 /// Represents a more usable representation of the layout, derived from the
 /// config.
@@ -10,6 +19,21 @@ pub struct BottomLayout {
     pub total_row_height_ratio: u32,
 }
 
+/// A named [`BottomLayout`]. There can be several pages, which can be
+/// switched between at runtime.
+#[derive(Clone, Debug)]
+pub struct LayoutPage {
+    pub name: String,
+    pub layout: BottomLayout,
+    /// The IDs of the widgets in this page.
+    pub widget_ids: RangeInclusive<u64>,
+    /// The ID of the widget to select when first switching to this page.
+    pub default_widget_id: u64,
+    /// The config rows the page was made from, for exporting the config. This
+    /// is empty for the basic layout.
+    pub rows: Vec<Row>,
+}
+
 // Represents a start and end coordinate in some dimension.
 type LineSegment = (u32, u32);
 
@@ -531,114 +555,79 @@ impl BottomLayout {
         }
     }
 
-    pub fn init_basic_default(use_battery: bool) -> Self {
-        let table_widgets = if use_battery {
-            let disk_widget = BottomWidget::new(BottomWidgetType::Disk, 4)
-                .canvas_handled()
-                .up_neighbour(Some(100))
-                .left_neighbour(Some(8))
-                .right_neighbour(Some(DEFAULT_WIDGET_ID + 2));
-
-            let proc_sort = BottomWidget::new(BottomWidgetType::ProcSort, DEFAULT_WIDGET_ID + 2)
-                .canvas_handled()
-                .up_neighbour(Some(100))
-                .down_neighbour(Some(DEFAULT_WIDGET_ID + 1))
-                .left_neighbour(Some(4))
-                .right_neighbour(Some(DEFAULT_WIDGET_ID))
-                .ratio(1)
-                .parent_reflector(Some((WidgetDirection::Right, 2)));
-
-            let proc = BottomWidget::new(BottomWidgetType::Proc, DEFAULT_WIDGET_ID)
-                .canvas_handled()
-                .up_neighbour(Some(100))
-                .down_neighbour(Some(DEFAULT_WIDGET_ID + 1))
-                .left_neighbour(Some(DEFAULT_WIDGET_ID + 2))
-                .right_neighbour(Some(7))
-                .ratio(2);
-
-            let proc_search =
-                BottomWidget::new(BottomWidgetType::ProcSearch, DEFAULT_WIDGET_ID + 1)
-                    .canvas_handled()
-                    .up_neighbour(Some(DEFAULT_WIDGET_ID))
-                    .left_neighbour(Some(4))
-                    .right_neighbour(Some(7))
-                    .parent_reflector(Some((WidgetDirection::Up, 1)));
-
-            let temp = BottomWidget::new(BottomWidgetType::Temp, 7)
-                .canvas_handled()
-                .up_neighbour(Some(100))
-                .left_neighbour(Some(DEFAULT_WIDGET_ID))
-                .right_neighbour(Some(8));
-
-            let battery = BottomWidget::new(BottomWidgetType::Battery, 8)
-                .canvas_handled()
-                .up_neighbour(Some(100))
-                .left_neighbour(Some(7))
-                .right_neighbour(Some(4));
-
-            vec![
-                BottomCol::new(vec![BottomColRow::new(vec![disk_widget]).canvas_handled()])
-                    .canvas_handled(),
-                BottomCol::new(vec![
-                    BottomColRow::new(vec![proc_sort, proc])
-                        .canvas_handled()
-                        .total_widget_ratio(3),
-                    BottomColRow::new(vec![proc_search]).canvas_handled(),
-                ])
-                .canvas_handled(),
-                BottomCol::new(vec![BottomColRow::new(vec![temp]).canvas_handled()])
-                    .canvas_handled(),
-                BottomCol::new(vec![BottomColRow::new(vec![battery]).canvas_handled()])
-                    .canvas_handled(),
-            ]
-        } else {
-            let disk = BottomWidget::new(BottomWidgetType::Disk, 4)
-                .canvas_handled()
-                .up_neighbour(Some(100))
-                .left_neighbour(Some(7))
-                .right_neighbour(Some(DEFAULT_WIDGET_ID + 2));
-
-            let proc_sort = BottomWidget::new(BottomWidgetType::ProcSort, DEFAULT_WIDGET_ID + 2)
-                .canvas_handled()
-                .up_neighbour(Some(100))
-                .down_neighbour(Some(DEFAULT_WIDGET_ID + 1))
-                .left_neighbour(Some(4))
-                .right_neighbour(Some(DEFAULT_WIDGET_ID))
-                .parent_reflector(Some((WidgetDirection::Right, 2)));
-
-            let proc = BottomWidget::new(BottomWidgetType::Proc, DEFAULT_WIDGET_ID)
-                .canvas_handled()
-                .up_neighbour(Some(100))
-                .down_neighbour(Some(DEFAULT_WIDGET_ID + 1))
-                .left_neighbour(Some(DEFAULT_WIDGET_ID + 2))
-                .right_neighbour(Some(7));
-
-            let proc_search =
-                BottomWidget::new(BottomWidgetType::ProcSearch, DEFAULT_WIDGET_ID + 1)
-                    .canvas_handled()
-                    .up_neighbour(Some(DEFAULT_WIDGET_ID))
-                    .left_neighbour(Some(4))
-                    .right_neighbour(Some(7))
-                    .parent_reflector(Some((WidgetDirection::Up, 1)));
-
-            let temp = BottomWidget::new(BottomWidgetType::Temp, 7)
-                .canvas_handled()
-                .up_neighbour(Some(100))
-                .left_neighbour(Some(DEFAULT_WIDGET_ID))
-                .right_neighbour(Some(4));
-
-            vec![
-                BottomCol::new(vec![BottomColRow::new(vec![disk]).canvas_handled()])
-                    .canvas_handled(),
-                BottomCol::new(vec![
-                    BottomColRow::new(vec![proc_sort, proc]).canvas_handled(),
-                    BottomColRow::new(vec![proc_search]).canvas_handled(),
-                ])
-                .canvas_handled(),
-                BottomCol::new(vec![BottomColRow::new(vec![temp]).canvas_handled()])
-                    .canvas_handled(),
-            ]
+    /// Creates the basic mode layout, where the table widgets in `tables` are
+    /// shown one at a time and switched between in that order.
+    pub fn init_basic_default(tables: &[BottomWidgetType]) -> Self {
+        // The ID to move to when entering a table from its left, or its right.
+        let left_entry = |widget_type: &BottomWidgetType| match widget_type {
+            BottomWidgetType::Proc => DEFAULT_WIDGET_ID + 2,
+            widget_type => basic_table_id(widget_type),
         };
+        let right_entry = basic_table_id;
+
+        let table_widgets = tables
+            .iter()
+            .enumerate()
+            .map(|(index, widget_type)| {
+                // With only one table, there is nothing to switch to.
+                let (left, right) = if tables.len() > 1 {
+                    let left = &tables[(index + tables.len() - 1) % tables.len()];
+                    let right = &tables[(index + 1) % tables.len()];
+                    (Some(right_entry(left)), Some(left_entry(right)))
+                } else {
+                    (None, None)
+                };
+
+                match widget_type {
+                    BottomWidgetType::Proc => {
+                        let proc_sort =
+                            BottomWidget::new(BottomWidgetType::ProcSort, DEFAULT_WIDGET_ID + 2)
+                                .canvas_handled()
+                                .up_neighbour(Some(100))
+                                .down_neighbour(Some(DEFAULT_WIDGET_ID + 1))
+                                .left_neighbour(left)
+                                .right_neighbour(Some(DEFAULT_WIDGET_ID))
+                                .ratio(1)
+                                .parent_reflector(Some((WidgetDirection::Right, 2)));
+
+                        let proc = BottomWidget::new(BottomWidgetType::Proc, DEFAULT_WIDGET_ID)
+                            .canvas_handled()
+                            .up_neighbour(Some(100))
+                            .down_neighbour(Some(DEFAULT_WIDGET_ID + 1))
+                            .left_neighbour(Some(DEFAULT_WIDGET_ID + 2))
+                            .right_neighbour(right)
+                            .ratio(2);
+
+                        let proc_search =
+                            BottomWidget::new(BottomWidgetType::ProcSearch, DEFAULT_WIDGET_ID + 1)
+                                .canvas_handled()
+                                .up_neighbour(Some(DEFAULT_WIDGET_ID))
+                                .left_neighbour(left)
+                                .right_neighbour(right)
+                                .parent_reflector(Some((WidgetDirection::Up, 1)));
+
+                        BottomCol::new(vec![
+                            BottomColRow::new(vec![proc_sort, proc])
+                                .canvas_handled()
+                                .total_widget_ratio(3),
+                            BottomColRow::new(vec![proc_search]).canvas_handled(),
+                        ])
+                        .canvas_handled()
+                    }
+                    widget_type => {
+                        let widget =
+                            BottomWidget::new(widget_type.clone(), basic_table_id(widget_type))
+                                .canvas_handled()
+                                .up_neighbour(Some(100))
+                                .left_neighbour(left)
+                                .right_neighbour(right);
+
+                        BottomCol::new(vec![BottomColRow::new(vec![widget]).canvas_handled()])
+                            .canvas_handled()
+                    }
+                }
+            })
+            .collect();
 
         let cpu = BottomWidget::new(BottomWidgetType::BasicCpu, 1)
             .canvas_handled()
@@ -683,6 +672,64 @@ impl BottomLayout {
             ],
         }
     }
+
+    /// Returns a copy of the layout without the widgets in `hidden`, or any
+    /// rows and columns left empty by removing them. The remaining rows and
+    /// columns take up the freed space, in proportion to their ratios.
+    pub fn without_widgets(&self, hidden: &HashSet<u64>) -> BottomLayout {
+        let mut layout = self.clone();
+
+        layout.rows.retain_mut(|row| {
+            row.children.retain_mut(|col| {
+                col.children.retain_mut(|col_row| {
+                    col_row.children.retain(|widget| {
+                        let is_hidden = hidden.contains(&widget.parent_id());
+                        if is_hidden {
+                            col_row.total_widget_ratio = col_row
+                                .total_widget_ratio
+                                .saturating_sub(widget.constraint.ratio());
+                        }
+                        !is_hidden
+                    });
+
+                    let is_empty = col_row.children.is_empty();
+                    if is_empty {
+                        col.total_col_row_ratio = col
+                            .total_col_row_ratio
+                            .saturating_sub(col_row.constraint.ratio());
+                    }
+                    !is_empty
+                });
+
+                let is_empty = col.children.is_empty();
+                if is_empty {
+                    row.total_col_ratio =
+                        row.total_col_ratio.saturating_sub(col.constraint.ratio());
+                }
+                !is_empty
+            });
+
+            let is_empty = row.children.is_empty();
+            if is_empty {
+                layout.total_row_height_ratio = layout
+                    .total_row_height_ratio
+                    .saturating_sub(row.constraint.ratio());
+            }
+            !is_empty
+        });
+
+        layout
+    }
+}
+
+/// The ID of a table widget in the basic mode layout.
+pub(crate) fn basic_table_id(widget_type: &BottomWidgetType) -> u64 {
+    match widget_type {
+        BottomWidgetType::Disk => 4,
+        BottomWidgetType::Temp => 7,
+        BottomWidgetType::Battery => 8,
+        _ => DEFAULT_WIDGET_ID,
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -859,6 +906,18 @@ pub struct BottomWidget {
 
     /// Bottom right corner when drawn, for mouse click detection. (x, y)
     pub bottom_right_corner: Option<(u16, u16)>,
+
+    /// A title to show instead of the widget type's usual one.
+    pub title: Option<String>,
+
+    /// The search query a process widget starts with.
+    pub filter: Option<String>,
+
+    /// Styles to use for this widget over the ones set in `[styles]`.
+    pub(crate) styles: Option<Box<StyleConfig>>,
+
+    /// Whether to draw usage as bar gauges instead of a table or graph.
+    pub gauge: bool,
 }
 
 impl BottomWidget {
@@ -874,9 +933,33 @@ impl BottomWidget {
             parent_reflector: None,
             top_left_corner: None,
             bottom_right_corner: None,
+            title: None,
+            filter: None,
+            styles: None,
+            gauge: false,
         }
     }
 
+    pub(crate) fn title(mut self, title: Option<String>) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub(crate) fn filter(mut self, filter: Option<String>) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub(crate) fn styles(mut self, styles: Option<Box<StyleConfig>>) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    pub(crate) fn gauge(mut self, gauge: bool) -> Self {
+        self.gauge = gauge;
+        self
+    }
+
     pub(crate) fn left_neighbour(mut self, left_neighbour: Option<u64>) -> Self {
         self.left_neighbour = left_neighbour;
         self
@@ -923,6 +1006,15 @@ impl BottomWidget {
         self.parent_reflector = parent_reflector;
         self
     }
+
+    /// The ID of the widget this one is a part of, such as the process widget
+    /// for its search and sort widgets, or its own ID otherwise.
+    pub fn parent_id(&self) -> u64 {
+        match self.parent_reflector {
+            Some((_, offset)) => self.widget_id - offset,
+            None => self.widget_id,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
@@ -943,9 +1035,27 @@ pub enum BottomWidgetType {
     BasicNet,
     BasicTables,
     Battery,
+    /// A widget provided by the plugin with the given name.
+    #[cfg(feature = "plugins")]
+    Plugin(String),
 }
 
 impl BottomWidgetType {
+    /// How important the widget is to keep when the terminal is too small to
+    /// fit the whole layout. Widgets with lower priorities are left out first.
+    pub fn keep_priority(&self) -> u8 {
+        use BottomWidgetType::*;
+
+        match self {
+            Proc | ProcSearch | ProcSort => 5,
+            Cpu | CpuLegend | BasicCpu => 4,
+            Mem | BasicMem => 3,
+            Net | BasicNet => 2,
+            Temp | Disk | BasicTables => 1,
+            _ => 0,
+        }
+    }
+
     pub fn is_widget_table(&self) -> bool {
         use BottomWidgetType::*;
         matches!(self, Disk | Proc | ProcSort | Temp | CpuLegend)
@@ -956,6 +1066,24 @@ impl BottomWidgetType {
         matches!(self, Cpu | Net | Mem)
     }
 
+    /// The names of the data sources the widget shows data from, used to tell
+    /// whether any of them ran into problems.
+    pub fn data_sources(&self) -> &'static [&'static str] {
+        use BottomWidgetType::*;
+        match self {
+            Cpu | CpuLegend | BasicCpu => &["cpu"],
+            Mem | BasicMem => &["memory", "gpu"],
+            Net | BasicNet => &["network"],
+            Proc | ProcSearch | ProcSort => &["processes"],
+            Temp => &["temperature", "gpu"],
+            Disk => &["disks"],
+            Battery => &["battery"],
+            #[cfg(feature = "plugins")]
+            Plugin(_) => &["plugins"],
+            Empty | BasicTables => &[],
+        }
+    }
+
     pub fn get_pretty_name(&self) -> &str {
         use BottomWidgetType::*;
         match self {
@@ -966,6 +1094,8 @@ impl BottomWidgetType {
             Temp => "Temperature",
             Disk => "Disks",
             Battery => "Battery",
+            #[cfg(feature = "plugins")]
+            Plugin(name) => name,
             _ => "",
         }
     }
@@ -975,6 +1105,14 @@ impl std::str::FromStr for BottomWidgetType {
     type Err = OptionError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        #[cfg(feature = "plugins")]
+        if let Some((_, name)) = s
+            .split_once(':')
+            .filter(|(prefix, _)| prefix.eq_ignore_ascii_case("plugin"))
+        {
+            return Ok(BottomWidgetType::Plugin(name.to_string()));
+        }
+
         let lower_case = s.to_lowercase();
         match lower_case.as_str() {
             "cpu" => Ok(BottomWidgetType::Cpu),
@@ -1042,15 +1180,22 @@ Supported widget names:
     }
 }
 
-#[derive(Clone, Default, Debug, Copy)]
-pub struct UsedWidgets {
-    pub use_cpu: bool,
-    pub use_mem: bool,
-    pub use_cache: bool,
-    pub use_gpu: bool,
-    pub use_net: bool,
-    pub use_proc: bool,
-    pub use_disk: bool,
-    pub use_temp: bool,
-    pub use_battery: bool,
+impl UsedWidgets {
+    /// Returns the widgets needed to display a widget of the given type.
+    pub fn for_widget_type(widget_type: &BottomWidgetType) -> Self {
+        use BottomWidgetType::*;
+
+        let is_mem = matches!(widget_type, Mem | BasicMem);
+        Self {
+            use_cpu: matches!(widget_type, Cpu | CpuLegend | BasicCpu),
+            use_mem: is_mem,
+            use_cache: is_mem,
+            use_gpu: is_mem,
+            use_net: matches!(widget_type, Net | BasicNet),
+            use_proc: matches!(widget_type, Proc | ProcSearch | ProcSort),
+            use_disk: matches!(widget_type, Disk),
+            use_temp: matches!(widget_type, Temp),
+            use_battery: matches!(widget_type, Battery),
+        }
+    }
 }
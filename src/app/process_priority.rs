@@ -0,0 +1,84 @@
+//! This file is meant to house (OS specific) implementations on how to change
+//! the priority of processes.
+
+use anyhow::anyhow;
+#[cfg(any(target_os = "illumos", target_os = "solaris"))]
+use libc::___errno as errno_location;
+#[cfg(any(target_os = "openbsd", target_os = "netbsd"))]
+use libc::__errno as errno_location;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use libc::__errno_location as errno_location;
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+use libc::__error as errno_location;
+
+#[cfg(target_os = "linux")]
+use crate::data_collection::processes::linux::io_priority::IoPriority;
+use crate::data_collection::processes::Pid;
+
+/// The lowest (highest priority) nice value.
+const MIN_NICE: i32 = -20;
+
+/// The highest (lowest priority) nice value.
+const MAX_NICE: i32 = 19;
+
+/// Describes the error from a failed priority call.
+fn priority_error(err: std::io::Error) -> anyhow::Error {
+    let err_code = err.raw_os_error();
+    let err = match err_code {
+        Some(libc::ESRCH) => "the target process did not exist.",
+        Some(libc::EPERM) | Some(libc::EACCES) => "the calling process does not have the permissions to change the priority of the target process.",
+        _ => "Unknown error occurred.",
+    };
+
+    if let Some(err_code) = err_code {
+        anyhow!("Error code {err_code} - {err}")
+    } else {
+        anyhow!("Error code unknown - {err}")
+    }
+}
+
+/// Gets the nice value of a process, given a PID.
+fn get_nice(pid: Pid) -> anyhow::Result<i32> {
+    // SAFETY: errno is thread-local. As -1 is also a valid nice value, errno has
+    // to be cleared beforehand to tell if getpriority failed.
+    let nice = unsafe {
+        *errno_location() = 0;
+        libc::getpriority(libc::PRIO_PROCESS, pid as libc::id_t)
+    };
+
+    if nice == -1 && std::io::Error::last_os_error().raw_os_error() != Some(0) {
+        return Err(priority_error(std::io::Error::last_os_error()));
+    }
+
+    Ok(nice)
+}
+
+/// Adds `increment` to the nice value of a process, given a PID. Lowering the
+/// nice value usually needs elevated permissions.
+pub fn renice_process_given_pid(pid: Pid, increment: i32) -> anyhow::Result<()> {
+    let nice = (get_nice(pid)? + increment).clamp(MIN_NICE, MAX_NICE);
+
+    // SAFETY: the arguments are valid, and we act properly on an error (return
+    // value not 0).
+    let output = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) };
+    if output != 0 {
+        return Err(priority_error(std::io::Error::last_os_error()));
+    }
+
+    Ok(())
+}
+
+/// Moves the I/O priority of a process one level up or down, given a PID. A
+/// process without one set is treated as having the level its nice value maps
+/// to.
+#[cfg(target_os = "linux")]
+pub fn change_io_priority_given_pid(pid: Pid, raise: bool) -> anyhow::Result<()> {
+    let current = IoPriority::read(pid, get_nice(pid)?).map_err(priority_error)?;
+    let new = if raise {
+        current.raised()
+    } else {
+        current.lowered()
+    };
+
+    new.set(pid).map_err(priority_error)
+}
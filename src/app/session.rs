@@ -0,0 +1,444 @@
+//! Runtime state that can be saved on exit and restored on the next launch,
+//! if `save_session` is enabled.
+//!
+//! This is kept in its own state file, separate from the config file, as it's
+//! written by bottom rather than the user.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use super::{layout_manager::BottomWidgetType, App};
+use crate::{
+    canvas::components::data_table::{
+        ColumnHeader, DataToCell, SortDataTable, SortOrder, SortsRow,
+    },
+    constants::STALE_MIN_MILLISECONDS,
+    widgets::{ProcWidgetMode, ProcWidgetState},
+};
+
+/// The state file sub-path.
+const SESSION_FILE_LOCATION: &str = "bottom/session.toml";
+
+/// Returns where the state file is kept. This is the system state folder if
+/// there is one, and the local data folder otherwise.
+pub fn session_path() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .map(|mut path| {
+            path.push(SESSION_FILE_LOCATION);
+            path
+        })
+}
+
+/// The sort column and direction of a table.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableSort {
+    /// The column's header text.
+    column: String,
+    descending: bool,
+}
+
+impl TableSort {
+    fn capture<D, H>(table: &SortDataTable<D, H>) -> Option<Self>
+    where
+        D: DataToCell<H>,
+        H: ColumnHeader + SortsRow<DataType = D>,
+    {
+        table.sort_column_text().map(|column| Self {
+            column: column.into_owned(),
+            descending: table.order() == SortOrder::Descending,
+        })
+    }
+
+    fn restore<D, H>(&self, table: &mut SortDataTable<D, H>)
+    where
+        D: DataToCell<H>,
+        H: ColumnHeader + SortsRow<DataType = D>,
+    {
+        let order = if self.descending {
+            SortOrder::Descending
+        } else {
+            SortOrder::Ascending
+        };
+
+        table.set_sort_column_by_text(&self.column, order);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcMode {
+    Normal,
+    Grouped,
+    UserGrouped,
+    Tree,
+}
+
+/// The saved state of a process widget.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcSession {
+    widget_id: u64,
+    mode: ProcMode,
+    is_command: bool,
+    sort: Option<TableSort>,
+    search_query: String,
+    is_search_enabled: bool,
+    is_ignoring_case: bool,
+    is_searching_whole_word: bool,
+    is_searching_with_regex: bool,
+}
+
+impl ProcSession {
+    fn capture(widget_id: u64, state: &ProcWidgetState) -> Self {
+        Self {
+            widget_id,
+            mode: match state.mode {
+                ProcWidgetMode::Normal => ProcMode::Normal,
+                ProcWidgetMode::Grouped => ProcMode::Grouped,
+                ProcWidgetMode::UserGrouped { .. } => ProcMode::UserGrouped,
+                ProcWidgetMode::Tree { .. } => ProcMode::Tree,
+            },
+            is_command: state.is_showing_command(),
+            sort: TableSort::capture(&state.table),
+            search_query: state.current_search_query().to_string(),
+            is_search_enabled: state.is_search_enabled(),
+            is_ignoring_case: state.proc_search.is_ignoring_case,
+            is_searching_whole_word: state.proc_search.is_searching_whole_word,
+            is_searching_with_regex: state.proc_search.is_searching_with_regex,
+        }
+    }
+
+    fn restore(&self, state: &mut ProcWidgetState, keep_search: bool) {
+        // Grouping swaps the PID column for a count, so do this before sorting.
+        // Go back to the normal mode first, as grouping only toggles from it.
+        match state.mode {
+            ProcWidgetMode::Normal => {}
+            ProcWidgetMode::Grouped => state.toggle_tab(),
+            ProcWidgetMode::UserGrouped { .. } => state.toggle_user_grouping(),
+            ProcWidgetMode::Tree { .. } => state.mode = ProcWidgetMode::Normal,
+        }
+        match self.mode {
+            ProcMode::Normal => {}
+            ProcMode::Grouped => state.toggle_tab(),
+            ProcMode::UserGrouped => state.toggle_user_grouping(),
+            ProcMode::Tree => {
+                state.mode = ProcWidgetMode::Tree {
+                    collapsed: Default::default(),
+                };
+            }
+        }
+
+        if self.is_command != state.is_showing_command() {
+            state.toggle_command();
+        }
+
+        if let Some(sort) = &self.sort {
+            sort.restore(&mut state.table);
+        }
+
+        if !keep_search {
+            state.proc_search.is_ignoring_case = self.is_ignoring_case;
+            state.proc_search.is_searching_whole_word = self.is_searching_whole_word;
+            state.proc_search.is_searching_with_regex = self.is_searching_with_regex;
+            state.proc_search.search_state.is_enabled = self.is_search_enabled;
+            state.set_search_query(self.search_query.clone());
+        }
+
+        state.force_rerender_and_update();
+    }
+}
+
+/// The saved sort of a temperature or disk widget.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableSession {
+    widget_id: u64,
+    sort: TableSort,
+}
+
+/// The hidden sensors of a temperature widget.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SensorsSession {
+    widget_id: u64,
+    hidden: Vec<String>,
+}
+
+/// The saved time range of a graph widget.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphSession {
+    widget_id: u64,
+    display_time: u64,
+}
+
+/// Runtime state saved between launches. Widgets are matched up by their ID,
+/// so anything saved for a widget that no longer exists is skipped.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    current_widget: Option<u64>,
+    is_expanded: bool,
+    processes: Vec<ProcSession>,
+    tables: Vec<TableSession>,
+    sensors: Vec<SensorsSession>,
+    graphs: Vec<GraphSession>,
+}
+
+impl SessionState {
+    /// Reads the state at `path`, if there is one.
+    pub fn load(path: &Path) -> anyhow::Result<Option<Self>> {
+        let state = match fs::read_to_string(path) {
+            Ok(state) => state,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        toml_edit::de::from_str(&state)
+            .map(Some)
+            .with_context(|| format!("couldn't read the session state at '{}'", path.display()))
+    }
+
+    /// Writes the state to `path`, creating its folder if needed.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent_path) = path.parent() {
+            fs::create_dir_all(parent_path)?;
+        }
+
+        fs::write(path, toml_edit::ser::to_string_pretty(self)?)
+            .with_context(|| format!("couldn't save the session state to '{}'", path.display()))
+    }
+
+    /// Captures the current state of `app`.
+    pub fn capture(app: &App) -> Self {
+        let states = &app.states;
+
+        let processes = states
+            .proc_state
+            .widget_states
+            .iter()
+            .map(|(id, state)| ProcSession::capture(*id, state))
+            .collect();
+
+        let tables = states
+            .temp_state
+            .widget_states
+            .iter()
+            .filter_map(|(id, state)| TableSort::capture(&state.table).map(|sort| (*id, sort)))
+            .chain(
+                states
+                    .disk_state
+                    .widget_states
+                    .iter()
+                    .filter_map(|(id, state)| {
+                        TableSort::capture(&state.table).map(|sort| (*id, sort))
+                    }),
+            )
+            .map(|(widget_id, sort)| TableSession { widget_id, sort })
+            .collect();
+
+        let sensors = states
+            .temp_state
+            .widget_states
+            .iter()
+            .filter(|(_, state)| !state.hidden_sensors.is_empty())
+            .map(|(id, state)| SensorsSession {
+                widget_id: *id,
+                hidden: state.hidden_sensors.clone(),
+            })
+            .collect();
+
+        let graphs = states
+            .cpu_state
+            .widget_states
+            .iter()
+            .map(|(id, state)| (*id, state.current_display_time))
+            .chain(
+                states
+                    .mem_state
+                    .widget_states
+                    .iter()
+                    .map(|(id, state)| (*id, state.current_display_time)),
+            )
+            .chain(
+                states
+                    .net_state
+                    .widget_states
+                    .iter()
+                    .map(|(id, state)| (*id, state.current_display_time)),
+            )
+            .map(|(widget_id, display_time)| GraphSession {
+                widget_id,
+                display_time,
+            })
+            .collect();
+
+        let mut state = Self {
+            current_widget: Some(app.current_widget.widget_id),
+            is_expanded: app.is_expanded,
+            processes,
+            tables,
+            sensors,
+            graphs,
+        };
+
+        // Keep the file stable between runs, as hash maps aren't ordered.
+        state.processes.sort_by_key(|session| session.widget_id);
+        state.tables.sort_by_key(|session| session.widget_id);
+        state.sensors.sort_by_key(|session| session.widget_id);
+        state.graphs.sort_by_key(|session| session.widget_id);
+
+        state
+    }
+
+    /// Restores the saved state into `app`. If `keep_searches` is set, the
+    /// process searches in `app` are kept rather than restored, such as when
+    /// one was given on the command line.
+    pub fn restore(&self, app: &mut App, keep_searches: bool) {
+        for session in &self.processes {
+            if let Some(state) = app
+                .states
+                .proc_state
+                .widget_states
+                .get_mut(&session.widget_id)
+            {
+                session.restore(state, keep_searches);
+            }
+        }
+
+        for session in &self.tables {
+            if let Some(state) = app
+                .states
+                .temp_state
+                .widget_states
+                .get_mut(&session.widget_id)
+            {
+                session.sort.restore(&mut state.table);
+                state.force_data_update();
+            } else if let Some(state) = app
+                .states
+                .disk_state
+                .widget_states
+                .get_mut(&session.widget_id)
+            {
+                session.sort.restore(&mut state.table);
+                state.force_data_update();
+            }
+        }
+
+        for session in &self.sensors {
+            if let Some(state) = app
+                .states
+                .temp_state
+                .widget_states
+                .get_mut(&session.widget_id)
+            {
+                state.hidden_sensors = session.hidden.clone();
+                state.force_data_update();
+            }
+        }
+
+        let max_display_time = app
+            .app_config_fields
+            .long_term_retention_ms
+            .unwrap_or(app.app_config_fields.retention_ms);
+        for session in &self.graphs {
+            let display_time = session
+                .display_time
+                .min(max_display_time)
+                .max(STALE_MIN_MILLISECONDS);
+            let widget_id = session.widget_id;
+
+            if let Some(state) = app.states.cpu_state.widget_states.get_mut(&widget_id) {
+                state.current_display_time = display_time;
+                app.states.cpu_state.force_update = Some(widget_id);
+            } else if let Some(state) = app.states.mem_state.widget_states.get_mut(&widget_id) {
+                state.current_display_time = display_time;
+                app.states.mem_state.force_update = Some(widget_id);
+            } else if let Some(state) = app.states.net_state.widget_states.get_mut(&widget_id) {
+                state.current_display_time = display_time;
+                app.states.net_state.force_update = Some(widget_id);
+            }
+        }
+
+        // Search and sort widgets may not be open anymore, so don't restore to them.
+        if let Some(widget) = self
+            .current_widget
+            .and_then(|widget_id| app.widget_map.get(&widget_id))
+            .filter(|widget| {
+                !matches!(
+                    widget.widget_type,
+                    BottomWidgetType::ProcSearch | BottomWidgetType::ProcSort
+                )
+            })
+            .cloned()
+        {
+            if let Some(page) = app.page_of_widget(widget.widget_id) {
+                app.switch_to_page(page);
+            }
+            app.current_widget = widget;
+        }
+
+        app.is_expanded = self.is_expanded && !app.app_config_fields.use_basic_mode;
+        app.is_force_redraw = true;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn session_round_trips() {
+        let state = SessionState {
+            current_widget: Some(3),
+            is_expanded: true,
+            processes: vec![ProcSession {
+                widget_id: 3,
+                mode: ProcMode::Tree,
+                is_command: true,
+                sort: Some(TableSort {
+                    column: "CPU%".to_string(),
+                    descending: true,
+                }),
+                search_query: "btm".to_string(),
+                is_search_enabled: true,
+                is_ignoring_case: true,
+                is_searching_whole_word: false,
+                is_searching_with_regex: false,
+            }],
+            tables: vec![TableSession {
+                widget_id: 7,
+                sort: TableSort {
+                    column: "Temp".to_string(),
+                    descending: false,
+                },
+            }],
+            sensors: vec![SensorsSession {
+                widget_id: 7,
+                hidden: vec!["acpitz".to_string()],
+            }],
+            graphs: vec![GraphSession {
+                widget_id: 1,
+                display_time: 30_000,
+            }],
+        };
+
+        let text = toml_edit::ser::to_string_pretty(&state).unwrap();
+        assert_eq!(
+            toml_edit::de::from_str::<SessionState>(&text).unwrap(),
+            state
+        );
+    }
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let state = toml_edit::de::from_str::<SessionState>("is_expanded = true").unwrap();
+
+        assert!(state.is_expanded);
+        assert_eq!(state.current_widget, None);
+        assert!(state.processes.is_empty());
+    }
+}
@@ -0,0 +1,75 @@
+//! Dumping the current state to a JSON file, for when bottom is sent
+//! `SIGUSR1`. This lets scripts and monitoring hooks grab what bottom is showing
+//! without needing the HTTP server.
+
+use std::{fs, io::Write, path::PathBuf};
+
+use anyhow::Context;
+use serde::Serialize;
+
+use super::{frozen_state::FrozenState, App};
+use crate::{
+    data_collection::processes::ProcessHarvest, http::MetricsSnapshot,
+    utils::general::create_private_file,
+};
+
+/// Returns where the state is dumped to if no path is set. This is named after
+/// bottom's PID, so several instances don't overwrite each other's dumps.
+pub fn default_dump_path() -> PathBuf {
+    std::env::temp_dir().join(format!("bottom-{}.json", std::process::id()))
+}
+
+/// The state that is dumped. If frozen, the data is the frozen data, as that is
+/// what is being shown.
+#[derive(Debug, Serialize)]
+struct StateDump<'a> {
+    pid: u32,
+    is_frozen: bool,
+    profile: Option<&'a str>,
+    /// The name of the selected widget's type.
+    current_widget: &'a str,
+    metrics: MetricsSnapshot,
+    processes: Vec<&'a ProcessHarvest>,
+}
+
+impl<'a> StateDump<'a> {
+    fn new(app: &'a App) -> Self {
+        let data = match &app.frozen_state {
+            FrozenState::NotFrozen => &app.data_collection,
+            FrozenState::Frozen(data) => data,
+        };
+
+        Self {
+            pid: std::process::id(),
+            is_frozen: app.frozen_state.is_frozen(),
+            profile: app.profile.as_deref(),
+            current_widget: app.current_widget.widget_type.get_pretty_name(),
+            metrics: MetricsSnapshot::new(data),
+            processes: data.process_data.process_harvest.values().collect(),
+        }
+    }
+}
+
+/// Dumps the current state of `app`, returning where it was dumped to.
+pub fn dump_state(app: &App) -> anyhow::Result<PathBuf> {
+    let json = serde_json::to_string_pretty(&StateDump::new(app))?;
+
+    // The default path is in the shared temporary directory, so it's written
+    // as a new file rather than through whatever is there.
+    let path = match &app.app_config_fields.dump_path {
+        Some(path) => {
+            fs::write(path, json)
+                .with_context(|| format!("bottom couldn't write to '{}'", path.display()))?;
+            path.clone()
+        }
+        None => {
+            let path = default_dump_path();
+            create_private_file(&path)
+                .and_then(|mut file| file.write_all(json.as_bytes()))
+                .with_context(|| format!("bottom couldn't write to '{}'", path.display()))?;
+            path
+        }
+    };
+
+    Ok(path)
+}
@@ -1,13 +1,20 @@
-use std::{ops::Range, time::Instant};
+use std::{
+    ops::{Range, RangeInclusive},
+    str::FromStr,
+    time::Instant,
+};
 
 use hashbrown::HashMap;
 use indexmap::IndexMap;
+use tui::layout::{Position, Rect};
 use unicode_ellipsis::grapheme_width;
 use unicode_segmentation::{GraphemeCursor, GraphemeIncomplete, UnicodeSegmentation};
 
 use crate::{
     app::layout_manager::BottomWidgetType,
     constants,
+    data_collection::processes::Pid,
+    options::config::layout::Row,
     widgets::{
         query::ProcessQuery, BatteryWidgetState, CpuWidgetState, DiskTableWidget, MemWidgetState,
         NetWidgetState, ProcWidgetState, TempWidgetState,
@@ -64,6 +71,10 @@ pub struct AppHelpDialogState {
     pub height: u16,
     pub scroll_state: ParagraphScrollState,
     pub index_shortcuts: Vec<u16>,
+    /// Whether keys are being typed into the search query.
+    pub is_searching: bool,
+    /// Only help lines containing this are shown, if it isn't empty.
+    pub query: String,
 }
 
 impl Default for AppHelpDialogState {
@@ -73,10 +84,40 @@ impl Default for AppHelpDialogState {
             height: 0,
             scroll_state: ParagraphScrollState::default(),
             index_shortcuts: vec![0; constants::HELP_TEXT.len()],
+            is_searching: false,
+            query: String::new(),
         }
     }
 }
 
+impl AppHelpDialogState {
+    /// Hides the help menu, resetting its scroll position and search.
+    pub fn close(&mut self) {
+        self.is_showing_help = false;
+        self.scroll_state.current_scroll_index = 0;
+        self.clear_search();
+    }
+
+    /// Stops searching and shows the entire help menu again.
+    pub fn clear_search(&mut self) {
+        self.is_searching = false;
+        self.query.clear();
+        self.scroll_state.current_scroll_index = 0;
+    }
+}
+
+/// The state of a layout page.
+pub struct PageState {
+    pub name: String,
+    /// The IDs of the widgets in this page.
+    pub widget_ids: RangeInclusive<u64>,
+    /// The widget that was selected when last on this page.
+    pub selected_widget_id: u64,
+    /// The config rows the page was made from, for exporting the config. This
+    /// is empty for the basic layout.
+    pub rows: Vec<Row>,
+}
+
 /// AppSearchState deals with generic searching (I might do this in the future).
 pub struct AppSearchState {
     pub is_enabled: bool,
@@ -375,6 +416,9 @@ pub struct BasicTableWidgetState {
     // then we can expand outwards with a normal BasicTableState and a hashmap
     pub currently_displayed_widget_type: BottomWidgetType,
     pub currently_displayed_widget_id: u64,
+    /// The IDs of the tables that can be shown, in the order they're switched
+    /// between.
+    pub table_ids: Vec<u64>,
     pub left_tlc: Option<(u16, u16)>,
     pub left_brc: Option<(u16, u16)>,
     pub right_tlc: Option<(u16, u16)>,
@@ -395,6 +439,339 @@ impl AppBatteryState {
     }
 }
 
+/// A border between two rows of the layout, or two columns in a row, which can
+/// be dragged with the mouse to resize them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutBorder {
+    /// The row with the columns the border is between, or [`None`] if the
+    /// border is between two rows.
+    pub row: Option<usize>,
+    /// The index of the row or column before the border.
+    pub index: usize,
+    /// Where the border can be grabbed.
+    pub area: Rect,
+}
+
+impl LayoutBorder {
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        (x >= self.area.left() && y >= self.area.top())
+            && (x < self.area.right() && y < self.area.bottom())
+    }
+}
+
+/// A [`LayoutBorder`] being dragged with the mouse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutDrag {
+    pub border: LayoutBorder,
+    /// Where the mouse is, along the direction being resized.
+    pub position: u16,
+}
+
+impl LayoutDrag {
+    pub fn new(border: LayoutBorder, x: u16, y: u16) -> Self {
+        let mut drag = Self {
+            border,
+            position: 0,
+        };
+        drag.move_to(x, y);
+        drag
+    }
+
+    pub fn move_to(&mut self, x: u16, y: u16) {
+        self.position = if self.border.row.is_some() { x } else { y };
+    }
+}
+
+/// A time range being selected in a time graph widget by dragging the mouse
+/// across it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeRangeSelection {
+    pub widget_id: u64,
+    pub start_x: u16,
+    pub end_x: u16,
+}
+
+impl TimeRangeSelection {
+    /// Returns the selected range of x-coordinates if the selection is in the
+    /// widget with the given ID.
+    pub fn x_range_for(&self, widget_id: u64) -> Option<(u16, u16)> {
+        (self.widget_id == widget_id).then_some((self.start_x, self.end_x))
+    }
+
+    /// Converts the selection to a new display offset and display time, given
+    /// the graph's current area, display offset, and display time. Returns
+    /// [`None`] if nothing was actually selected.
+    pub fn time_range(
+        &self, graph_area: Rect, display_offset: u64, display_time: u64,
+    ) -> Option<(u64, u64)> {
+        if graph_area.width < 2 {
+            return None;
+        }
+
+        let clamp = |x: u16| x.clamp(graph_area.left(), graph_area.right() - 1);
+        let left = clamp(self.start_x.min(self.end_x));
+        let right = clamp(self.start_x.max(self.end_x));
+        if left == right {
+            return None;
+        }
+
+        let time_at = |x| graph_time_at(graph_area, display_offset, display_time, x);
+        let new_offset = time_at(right);
+        Some((new_offset, time_at(left) - new_offset))
+    }
+}
+
+/// Returns how far back from the latest data the x-coordinate `x` is in a time
+/// graph drawn in `graph_area`, in milliseconds.
+pub fn graph_time_at(graph_area: Rect, display_offset: u64, display_time: u64, x: u16) -> u64 {
+    if graph_area.width < 2 {
+        return display_offset;
+    }
+
+    // The right edge of the graph is `display_offset` milliseconds back.
+    let x = x.clamp(graph_area.left(), graph_area.right() - 1);
+    let last_x = u64::from(graph_area.width - 1);
+    display_offset + display_time * (last_x - u64::from(x - graph_area.left())) / last_x
+}
+
+/// The position of the inspection cursor in a time graph widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InspectCursor {
+    pub widget_id: u64,
+
+    /// How far back from the latest data the cursor is, in milliseconds.
+    pub time: u64,
+}
+
+/// Where the mouse is, if it is over a widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseHover {
+    pub widget_id: u64,
+    pub x: u16,
+    pub y: u16,
+}
+
+/// What double-clicking a table entry does.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DoubleClickAction {
+    /// Collapses or expands a process's children in tree mode. This is the
+    /// **default**.
+    #[default]
+    Collapse,
+    /// Shows a process's details, or expands disk and temperature widgets.
+    Details,
+    /// Opens the dialog to kill a process.
+    Kill,
+    None,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseDoubleClickActionError;
+
+impl FromStr for DoubleClickAction {
+    type Err = ParseDoubleClickActionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "collapse" => Ok(Self::Collapse),
+            "details" => Ok(Self::Details),
+            "kill" => Ok(Self::Kill),
+            "none" => Ok(Self::None),
+            _ => Err(ParseDoubleClickActionError),
+        }
+    }
+}
+
+/// An action that can be picked from a process's context menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextMenuAction {
+    Kill,
+    #[cfg(target_family = "unix")]
+    RaisePriority,
+    #[cfg(target_family = "unix")]
+    LowerPriority,
+    #[cfg(target_os = "linux")]
+    RaiseIoPriority,
+    #[cfg(target_os = "linux")]
+    LowerIoPriority,
+    Details,
+    #[cfg(target_os = "linux")]
+    SameNamespaces,
+    CopyPid,
+    CopyCommand,
+}
+
+impl ContextMenuAction {
+    /// Every action, in the order they are listed in the menu.
+    pub const ALL: &[ContextMenuAction] = &[
+        ContextMenuAction::Kill,
+        #[cfg(target_family = "unix")]
+        ContextMenuAction::RaisePriority,
+        #[cfg(target_family = "unix")]
+        ContextMenuAction::LowerPriority,
+        #[cfg(target_os = "linux")]
+        ContextMenuAction::RaiseIoPriority,
+        #[cfg(target_os = "linux")]
+        ContextMenuAction::LowerIoPriority,
+        ContextMenuAction::Details,
+        #[cfg(target_os = "linux")]
+        ContextMenuAction::SameNamespaces,
+        ContextMenuAction::CopyPid,
+        ContextMenuAction::CopyCommand,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ContextMenuAction::Kill => "Kill",
+            #[cfg(target_family = "unix")]
+            ContextMenuAction::RaisePriority => "Raise priority (nice -1)",
+            #[cfg(target_family = "unix")]
+            ContextMenuAction::LowerPriority => "Lower priority (nice +1)",
+            #[cfg(target_os = "linux")]
+            ContextMenuAction::RaiseIoPriority => "Raise I/O priority",
+            #[cfg(target_os = "linux")]
+            ContextMenuAction::LowerIoPriority => "Lower I/O priority",
+            ContextMenuAction::Details => "Details",
+            #[cfg(target_os = "linux")]
+            ContextMenuAction::SameNamespaces => "Show same namespaces",
+            ContextMenuAction::CopyPid => "Copy PID",
+            ContextMenuAction::CopyCommand => "Copy command",
+        }
+    }
+}
+
+/// A context menu opened by right-clicking a process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextMenu {
+    pub widget_id: u64,
+    /// The name or command of the process, as shown in the table.
+    pub name: String,
+    /// The processes the menu acts on - more than one if they are grouped.
+    pub pids: Vec<Pid>,
+    /// Where the menu was opened.
+    pub x: u16,
+    pub y: u16,
+    pub selected: usize,
+    /// Where the menu was last drawn, including its border.
+    pub area: Option<Rect>,
+    pub error: Option<String>,
+}
+
+impl ContextMenu {
+    pub fn new(widget_id: u64, name: String, pids: Vec<Pid>, x: u16, y: u16) -> Self {
+        Self {
+            widget_id,
+            name,
+            pids,
+            x,
+            y,
+            selected: 0,
+            area: None,
+            error: None,
+        }
+    }
+
+    pub fn selected_action(&self) -> ContextMenuAction {
+        ContextMenuAction::ALL[self.selected]
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self
+            .selected
+            .checked_sub(1)
+            .unwrap_or(ContextMenuAction::ALL.len() - 1);
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % ContextMenuAction::ALL.len();
+    }
+
+    /// Returns the index of the action drawn at the given position, if any.
+    pub fn action_at(&self, x: u16, y: u16) -> Option<usize> {
+        let area = self.area?;
+        if x <= area.left() || x + 1 >= area.right() || y <= area.top() {
+            return None;
+        }
+
+        let index = usize::from(y - area.top() - 1);
+        (index < ContextMenuAction::ALL.len()).then_some(index)
+    }
+
+    /// Whether the given position is within the menu.
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        self.area
+            .is_some_and(|area| area.contains(Position { x, y }))
+    }
+}
+
+/// What a global search result refers to, and how to find its row again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GlobalSearchTarget {
+    Process(Pid),
+    /// A disk, by its mount point.
+    Disk(String),
+    /// A temperature sensor, by its name.
+    Temperature(String),
+    /// A network interface. Interfaces aren't listed in the network widget, so
+    /// only the widget is selected.
+    Network,
+}
+
+impl GlobalSearchTarget {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            GlobalSearchTarget::Process(_) => "Process",
+            GlobalSearchTarget::Disk(_) => "Disk",
+            GlobalSearchTarget::Temperature(_) => "Temp",
+            GlobalSearchTarget::Network => "Network",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalSearchResult {
+    pub widget_id: u64,
+    pub target: GlobalSearchTarget,
+    /// The text shown for the result.
+    pub label: String,
+}
+
+/// A search across the process, disk, temperature, and network widgets.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GlobalSearch {
+    pub query: String,
+    pub results: Vec<GlobalSearchResult>,
+    pub selected: usize,
+}
+
+impl GlobalSearch {
+    /// Whether `text` contains the query, ignoring case. Nothing matches an
+    /// empty query.
+    pub fn matches(&self, text: &str) -> bool {
+        !self.query.is_empty() && text.to_lowercase().contains(&self.query.to_lowercase())
+    }
+
+    /// Replaces the results, keeping the selection in bounds.
+    pub fn set_results(&mut self, results: Vec<GlobalSearchResult>) {
+        self.results = results;
+        self.selected = self.selected.min(self.results.len().saturating_sub(1));
+    }
+
+    pub fn selected_result(&self) -> Option<&GlobalSearchResult> {
+        self.results.get(self.selected)
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.results.len() {
+            self.selected += 1;
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct ParagraphScrollState {
     pub current_scroll_index: u16,
@@ -405,6 +782,98 @@ pub struct ParagraphScrollState {
 mod test {
     use super::*;
 
+    #[test]
+    fn layout_drag_position() {
+        let row_border = LayoutBorder {
+            row: None,
+            index: 0,
+            area: Rect::new(0, 9, 40, 2),
+        };
+        assert!(row_border.contains(5, 10));
+        assert!(!row_border.contains(5, 11));
+
+        let mut drag = LayoutDrag::new(row_border, 5, 10);
+        assert_eq!(drag.position, 10);
+        drag.move_to(7, 14);
+        assert_eq!(drag.position, 14);
+
+        let col_border = LayoutBorder {
+            row: Some(1),
+            index: 0,
+            area: Rect::new(19, 0, 2, 20),
+        };
+        let mut drag = LayoutDrag::new(col_border, 20, 3);
+        assert_eq!(drag.position, 20);
+        drag.move_to(25, 8);
+        assert_eq!(drag.position, 25);
+    }
+
+    #[test]
+    fn context_menu_selection() {
+        let mut menu = ContextMenu::new(1, "bash".to_string(), vec![1], 10, 5);
+        assert_eq!(menu.selected_action(), ContextMenuAction::Kill);
+
+        menu.select_previous();
+        assert_eq!(menu.selected_action(), ContextMenuAction::CopyCommand);
+        menu.select_next();
+        assert_eq!(menu.selected_action(), ContextMenuAction::Kill);
+        menu.select_next();
+        assert_ne!(menu.selected_action(), ContextMenuAction::Kill);
+    }
+
+    #[test]
+    fn context_menu_hit_testing() {
+        let mut menu = ContextMenu::new(1, "bash".to_string(), vec![1], 10, 5);
+        assert_eq!(menu.action_at(11, 6), None);
+        assert!(!menu.contains(10, 5));
+
+        let len = ContextMenuAction::ALL.len() as u16;
+        menu.area = Some(Rect::new(10, 5, 20, len + 2));
+        assert!(menu.contains(10, 5));
+        assert!(!menu.contains(30, 5));
+
+        // The border isn't part of any action.
+        assert_eq!(menu.action_at(10, 6), None);
+        assert_eq!(menu.action_at(29, 6), None);
+        assert_eq!(menu.action_at(11, 5), None);
+        assert_eq!(menu.action_at(11, 6 + len), None);
+
+        assert_eq!(menu.action_at(11, 6), Some(0));
+        assert_eq!(menu.action_at(28, 5 + len), Some(usize::from(len) - 1));
+    }
+
+    #[test]
+    fn time_range_selection() {
+        let graph_area = Rect::new(10, 0, 11, 5);
+        let selection = |start_x, end_x| TimeRangeSelection {
+            widget_id: 1,
+            start_x,
+            end_x,
+        };
+
+        assert_eq!(selection(12, 12).time_range(graph_area, 0, 60_000), None);
+        assert_eq!(selection(12, 12).x_range_for(1), Some((12, 12)));
+        assert_eq!(selection(12, 12).x_range_for(2), None);
+
+        // The whole graph.
+        assert_eq!(
+            selection(10, 20).time_range(graph_area, 0, 60_000),
+            Some((0, 60_000))
+        );
+
+        // The left half, selected from right to left and going past the graph.
+        assert_eq!(
+            selection(15, 0).time_range(graph_area, 0, 60_000),
+            Some((30_000, 30_000))
+        );
+
+        // The right half, with an existing offset.
+        assert_eq!(
+            selection(15, 20).time_range(graph_area, 10_000, 60_000),
+            Some((10_000, 30_000))
+        );
+    }
+
     fn move_right(state: &mut AppSearchState) {
         state.walk_forward();
         state.cursor_direction = CursorDirection::Right;
@@ -488,4 +957,38 @@ mod test {
         assert_eq!(state.grapheme_cursor.cur_cursor(), 0);
         assert_eq!(state.display_start_char_index, 0);
     }
+
+    #[test]
+    fn global_search_matching() {
+        let mut search = GlobalSearch::default();
+        assert!(!search.matches("firefox"));
+
+        search.query = "FIRE".to_string();
+        assert!(search.matches("firefox"));
+        assert!(!search.matches("chrome"));
+    }
+
+    #[test]
+    fn global_search_selection() {
+        let result = |label: &str| GlobalSearchResult {
+            widget_id: 1,
+            target: GlobalSearchTarget::Network,
+            label: label.to_string(),
+        };
+
+        let mut search = GlobalSearch::default();
+        search.select_next();
+        assert_eq!(search.selected_result(), None);
+
+        search.set_results(vec![result("eth0"), result("eth1"), result("eth2")]);
+        search.select_next();
+        search.select_next();
+        search.select_next();
+        assert_eq!(search.selected, 2);
+
+        search.set_results(vec![result("eth0")]);
+        assert_eq!(search.selected_result(), Some(&result("eth0")));
+        search.select_previous();
+        assert_eq!(search.selected, 0);
+    }
 }
@@ -0,0 +1,1152 @@
+//! The terminal UI: setting up and cleaning up the terminal, the threads that
+//! feed it events, the event loop, and batch mode.
+
+use std::{
+    boxed::Box,
+    io::{self, stderr, stdout, Stdout, Write},
+    panic::{self, PanicHookInfo},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor::{Hide, Show},
+    event::{
+        poll, read, DisableBracketedPaste, DisableFocusChange, DisableMouseCapture,
+        EnableBracketedPaste, EnableFocusChange, EnableMouseCapture, Event, KeyEventKind,
+        MouseEventKind,
+    },
+    execute,
+    style::Print,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use tui::{backend::CrosstermBackend, Terminal, TerminalOptions, Viewport};
+
+use crate::{
+    app::{
+        config_export, crash_report,
+        debug_stats::{DurationSummary, TickStats},
+        session::{self, SessionState},
+        state_dump, App, AppConfigFields,
+    },
+    batch, canvas, constants,
+    data_collection::{
+        self,
+        source::DataSource,
+        synthetic::{StressConfig, SyntheticCollector},
+        CollectionIssue, UsedWidgets,
+    },
+    event::{
+        handle_key_event_or_break, handle_mouse_event, keybinds::Action, BottomEvent,
+        CollectionSettings, CollectionThreadEvent, IdleTracker, RedrawLimiter, ResizeDebouncer,
+    },
+    http, i18n,
+    options::{self, args, get_locale, get_or_create_config, init_app, read_config},
+    utils::{
+        self,
+        cancellation_token::CancellationToken,
+        logging::{init_logger, LogFilter},
+    },
+};
+
+// Used for heap allocation debugging purposes.
+// #[global_allocator]
+// static ALLOC: dhat::Alloc = dhat::Alloc;
+
+/// Try drawing. If not, clean up the terminal and return an error.
+fn try_drawing(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>, app: &mut App,
+    painter: &mut canvas::Painter,
+) -> anyhow::Result<()> {
+    if let Err(err) = painter.draw_data(terminal, app) {
+        cleanup_terminal(terminal, &app.app_config_fields)?;
+        Err(err.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Set up the terminal for drawing, or set it up again after it was cleaned up
+/// to suspend bottom.
+fn setup_terminal(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>, app_config_fields: &AppConfigFields,
+) -> anyhow::Result<()> {
+    execute!(terminal.backend_mut(), Hide, EnableBracketedPaste)?;
+    if app_config_fields.alt_screen {
+        execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+    }
+    if app_config_fields.terminal.mouse {
+        execute!(terminal.backend_mut(), EnableMouseCapture)?;
+    }
+    if app_config_fields.idle_rate.is_some() || app_config_fields.pause_on_blur {
+        execute!(terminal.backend_mut(), EnableFocusChange)?;
+    }
+    enable_raw_mode()?;
+
+    terminal.clear()?;
+    terminal.hide_cursor()?;
+
+    Ok(())
+}
+
+/// Clean up the terminal before returning it to the user.
+fn cleanup_terminal(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>, app_config_fields: &AppConfigFields,
+) -> anyhow::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        DisableMouseCapture,
+        DisableFocusChange,
+        Show,
+    )?;
+    if app_config_fields.alt_screen {
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    } else {
+        // Leave the last frame in place, and start the shell's prompt below it.
+        let last_row = terminal.get_frame().area().bottom().saturating_sub(1);
+        terminal.set_cursor_position((0, last_row))?;
+        execute!(terminal.backend_mut(), Print("\n"))?;
+    }
+    terminal.show_cursor()?;
+
+    Ok(())
+}
+
+/// How bottom is exiting, which decides what's cleaned up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Exit {
+    /// Quitting, including from `SIGINT`, `SIGTERM`, or Ctrl+C on Windows.
+    Quit,
+    /// The terminal was closed, from `SIGHUP` or the console being closed on
+    /// Windows, so there's no terminal left to restore or print to.
+    Hangup,
+}
+
+/// Cleans up before exiting with `restore_terminal` and `save_session`. The
+/// terminal is only restored if it's still there, and the session is saved
+/// either way, even if restoring the terminal fails.
+fn clean_up_on_exit(
+    exit: Exit, restore_terminal: impl FnOnce() -> anyhow::Result<()>,
+    save_session: impl FnOnce() -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let restored = match exit {
+        Exit::Quit => restore_terminal(),
+        Exit::Hangup => Ok(()),
+    };
+
+    if let Err(err) = save_session() {
+        if exit == Exit::Quit {
+            eprintln!("Note: bottom couldn't save the session: {err:#}");
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    crate::event::termination::finish_cleanup();
+
+    restored
+}
+
+/// Clean up the terminal and stop bottom, like the shell would on Ctrl+Z if
+/// raw mode didn't stop it from doing so. Once continued, the terminal is set up
+/// again and everything is redrawn.
+#[cfg(target_family = "unix")]
+fn suspend(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>, app: &mut App,
+) -> anyhow::Result<()> {
+    cleanup_terminal(terminal, &app.app_config_fields)?;
+
+    // The signal thread handles SIGTSTP, so do what it would have done by
+    // default, which blocks until bottom is continued.
+    signal_hook::low_level::emulate_default_handler(signal_hook::consts::SIGTSTP)?;
+
+    resume(terminal, app)
+}
+
+/// Set up the terminal again after bottom was stopped and continued, and redraw
+/// everything, as whatever ran in the meantime may have changed the terminal.
+fn resume(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>, app: &mut App,
+) -> anyhow::Result<()> {
+    setup_terminal(terminal, &app.app_config_fields)?;
+    app.is_force_redraw = true;
+
+    Ok(())
+}
+
+/// Whether to print summaries instead of running the UI, which is the case
+/// with `--batch`, or if stdout isn't a terminal (like when piped) unless
+/// `--force_tty` is set.
+fn use_batch_mode(args: &args::GeneralArgs) -> bool {
+    use crossterm::tty::IsTty;
+
+    if args.batch {
+        return true;
+    }
+    if args.force_tty || stdout().is_tty() {
+        return false;
+    }
+
+    // Only mention it if there's someone around to read it.
+    if stderr().is_tty() {
+        eprintln!(
+            "Note: bottom's output isn't a terminal, so it's printing summaries instead of running \
+            the UI. Use --force_tty to run the UI anyway."
+        );
+    }
+    true
+}
+
+/// This manually resets stdout back to normal state.
+pub fn reset_stdout() -> Stdout {
+    let mut stdout = stdout();
+    let _ = disable_raw_mode();
+    let _ = execute!(
+        stdout,
+        DisableBracketedPaste,
+        DisableMouseCapture,
+        DisableFocusChange,
+        LeaveAlternateScreen,
+        Show,
+    );
+
+    stdout
+}
+
+/// A panic hook to properly restore the terminal in the case of a panic.
+/// Originally based on [spotify-tui's implementation](https://github.com/Rigellute/spotify-tui/blob/master/src/main.rs).
+fn panic_hook(panic_info: &PanicHookInfo<'_>) {
+    let msg = match panic_info.payload().downcast_ref::<&'static str>() {
+        Some(s) => *s,
+        None => match panic_info.payload().downcast_ref::<String>() {
+            Some(s) => &s[..],
+            None => "Box<Any>",
+        },
+    };
+
+    let backtrace = format!("{:?}", backtrace::Backtrace::new());
+
+    reset_stdout();
+
+    // Print stack trace. Must be done after!
+    if let Some(panic_info) = panic_info.location() {
+        println!("thread '<unnamed>' panicked at '{msg}', {panic_info}\n\r{backtrace}")
+    }
+
+    let location = panic_info.location().map(|location| location.to_string());
+    match crash_report::write_report(msg, location, &backtrace) {
+        Ok(path) => println!(
+            "A crash report was written to '{}', with hostnames and command lines redacted. \
+            Please attach it if you report this bug.",
+            path.display()
+        ),
+        Err(err) => println!("bottom couldn't write a crash report: {err:#}"),
+    }
+
+    // TODO: Might be cleaner in the future to use a cancellation token, but that causes some fun issues with
+    // lifetimes; for now if it panics then shut down the main program entirely ASAP.
+    std::process::exit(1);
+}
+
+/// Create a thread to poll for user inputs and forward them to the main thread.
+fn create_input_thread(
+    sender: Sender<BottomEvent>, cancellation_token: Arc<CancellationToken>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut mouse_timer = Instant::now();
+        let mut resize_debouncer = ResizeDebouncer::new(
+            Duration::from_millis(constants::RESIZE_SETTLE_IN_MILLISECONDS),
+            Duration::from_millis(constants::RESIZE_MAX_WAIT_IN_MILLISECONDS),
+        );
+
+        loop {
+            // We don't block.
+            if let Some(is_terminated) = cancellation_token.try_check() {
+                if is_terminated {
+                    break;
+                }
+            }
+
+            if resize_debouncer.check(Instant::now()) && sender.send(BottomEvent::Resize).is_err() {
+                break;
+            }
+
+            if let Ok(poll) = poll(Duration::from_millis(20)) {
+                if poll {
+                    if let Ok(event) = read() {
+                        match event {
+                            Event::Resize(_, _) => {
+                                // The size is read again when drawing, so only when to
+                                // redraw matters.
+                                resize_debouncer.on_resize(Instant::now());
+                            }
+                            Event::Paste(paste) => {
+                                if sender.send(BottomEvent::PasteEvent(paste)).is_err() {
+                                    break;
+                                }
+                            }
+                            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                                // For now, we only care about key down events. This may change in
+                                // the future.
+                                if sender.send(BottomEvent::KeyInput(key)).is_err() {
+                                    break;
+                                }
+                            }
+                            Event::Mouse(mouse) => match mouse.kind {
+                                // Scrolling is debounced by the app instead, as how
+                                // long for is configurable.
+                                MouseEventKind::Moved | MouseEventKind::Drag(..) => {
+                                    if Instant::now().duration_since(mouse_timer).as_millis() >= 20
+                                    {
+                                        if sender.send(BottomEvent::MouseInput(mouse)).is_err() {
+                                            break;
+                                        }
+                                        mouse_timer = Instant::now();
+                                    }
+                                }
+                                _ => {
+                                    if sender.send(BottomEvent::MouseInput(mouse)).is_err() {
+                                        break;
+                                    }
+                                }
+                            },
+                            Event::Key(_) => {}
+                            Event::FocusGained => {
+                                if sender.send(BottomEvent::FocusGained).is_err() {
+                                    break;
+                                }
+                            }
+                            Event::FocusLost => {
+                                if sender.send(BottomEvent::FocusLost).is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Create a thread to handle data collection.
+fn create_collection_thread(
+    sender: Sender<BottomEvent>, control_receiver: Receiver<CollectionThreadEvent>,
+    cancellation_token: Arc<CancellationToken>, settings: CollectionSettings,
+    extra_sources: Vec<Box<dyn DataSource + Send>>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut data_state = data_collection::DataCollector::new(settings.filters.clone());
+        let mut state = CollectionThreadState::new(&settings);
+
+        state.apply(settings, &mut data_state);
+        for source in extra_sources {
+            data_state.add_source(source);
+        }
+
+        data_state.init();
+
+        'collection: loop {
+            // Check once at the very top... don't block though.
+            if let Some(is_terminated) = cancellation_token.try_check() {
+                if is_terminated {
+                    break;
+                }
+            }
+
+            state.handle_messages(&control_receiver, &mut data_state);
+
+            // While paused, don't collect anything; just wait to be resumed or terminated.
+            while state.is_paused {
+                if cancellation_token
+                    .sleep_with_cancellation(Duration::from_millis(state.update_rate))
+                {
+                    break 'collection;
+                }
+
+                state.handle_messages(&control_receiver, &mut data_state);
+            }
+
+            let collection_start = Instant::now();
+            data_state.update_data();
+
+            // Warn if collecting can't keep up with the refresh rate, as then it's
+            // effectively running all the time.
+            let collection_time = collection_start.elapsed();
+            if collection_time > Duration::from_millis(state.update_rate) {
+                data_state.data.timings.issues.push(CollectionIssue {
+                    source: "collection",
+                    message: format!(
+                        "Collecting took {}ms, longer than the {}ms refresh rate",
+                        collection_time.as_millis(),
+                        state.update_rate
+                    ),
+                });
+            }
+
+            // Yet another check to bail if needed... do not block!
+            if let Some(is_terminated) = cancellation_token.try_check() {
+                if is_terminated {
+                    break;
+                }
+            }
+
+            let event = BottomEvent::Update(Box::from(data_state.data));
+            data_state.data = data_collection::Data::default();
+            if sender.send(event).is_err() {
+                break;
+            }
+
+            // Sleep while allowing for interruptions...
+            if state.is_idle {
+                // While idle, still wake up at the normal rate to check if we should go back
+                // to it, so that we can snap back immediately.
+                let wake_time = Instant::now() + Duration::from_millis(state.idle_rate);
+                while state.is_idle {
+                    let remaining = wake_time.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        break;
+                    }
+
+                    if cancellation_token.sleep_with_cancellation(
+                        remaining.min(Duration::from_millis(state.update_rate)),
+                    ) {
+                        break 'collection;
+                    }
+
+                    state.handle_messages(&control_receiver, &mut data_state);
+                }
+            } else if cancellation_token
+                .sleep_with_cancellation(Duration::from_millis(state.update_rate))
+            {
+                break;
+            }
+        }
+    })
+}
+
+/// Create a thread that sends synthetic data at the update rate rather than
+/// collecting anything, for stress testing.
+fn create_stress_thread(
+    sender: Sender<BottomEvent>, cancellation_token: Arc<CancellationToken>,
+    app_config_fields: &AppConfigFields, stress: StressConfig,
+) -> JoinHandle<()> {
+    let update_time = app_config_fields.update_rate;
+    let show_average_cpu = app_config_fields.show_average_cpu;
+
+    thread::spawn(move || {
+        let mut collector = SyntheticCollector::new(stress.num_processes, show_average_cpu);
+
+        loop {
+            let event = BottomEvent::Update(Box::from(collector.next_data()));
+            if sender.send(event).is_err() {
+                break;
+            }
+
+            if cancellation_token.sleep_with_cancellation(Duration::from_millis(update_time)) {
+                break;
+            }
+        }
+    })
+}
+
+/// Create a thread to forward signals to the main thread. `SIGUSR1` and
+/// `SIGUSR2` let scripts dump the state or toggle freezing, `SIGTSTP` and
+/// `SIGCONT` let the terminal be cleaned up before bottom is stopped and set up
+/// again after it's continued, and `SIGINT`, `SIGTERM`, and `SIGHUP` let bottom
+/// clean up before exiting.
+///
+/// The thread blocks until a signal arrives, and stops once the returned handle
+/// is closed.
+#[cfg(target_family = "unix")]
+fn create_signal_thread(
+    sender: Sender<BottomEvent>,
+) -> anyhow::Result<signal_hook::iterator::Handle> {
+    use signal_hook::{
+        consts::{SIGCONT, SIGHUP, SIGINT, SIGTERM, SIGTSTP, SIGUSR1, SIGUSR2},
+        iterator::Signals,
+    };
+
+    let mut signals = Signals::new([SIGUSR1, SIGUSR2, SIGTSTP, SIGCONT, SIGINT, SIGTERM, SIGHUP])?;
+    let handle = signals.handle();
+
+    thread::spawn(move || {
+        for signal in signals.forever() {
+            let event = match signal {
+                SIGUSR1 => BottomEvent::DumpState,
+                SIGUSR2 => BottomEvent::ToggleFreeze,
+                SIGTSTP => BottomEvent::Suspend,
+                SIGCONT => BottomEvent::Resume,
+                SIGHUP => BottomEvent::Hangup,
+                _ => BottomEvent::Terminate,
+            };
+
+            if sender.send(event).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(handle)
+}
+
+/// Prints a plain-text summary of the collected data every refresh instead of
+/// running the terminal UI, until `iterations` summaries have been printed if
+/// given.
+fn run_batch(
+    mut app: App, extra_sources: Vec<Box<dyn DataSource + Send>>, iterations: Option<u64>,
+    show_command: bool,
+) -> anyhow::Result<()> {
+    let cancellation_token = Arc::new(CancellationToken::default());
+    let (sender, receiver) = mpsc::channel();
+    let (_collection_thread_ctrl_sender, collection_thread_ctrl_receiver) = mpsc::channel();
+
+    // The summary always has these, no matter what widgets are in the layout.
+    let mut settings = CollectionSettings::new(&app);
+    settings.used_widgets = UsedWidgets {
+        use_cpu: true,
+        use_mem: true,
+        use_net: true,
+        use_proc: true,
+        ..settings.used_widgets
+    };
+
+    let _collection_thread = create_collection_thread(
+        sender,
+        collection_thread_ctrl_receiver,
+        cancellation_token.clone(),
+        settings,
+        extra_sources,
+    );
+
+    let mut stdout_val = stdout().lock();
+    let mut printed: u64 = 0;
+
+    #[cfg(target_os = "linux")]
+    let mut supervisor = batch::Supervisor::new();
+
+    let result = loop {
+        if iterations.is_some_and(|iterations| printed >= iterations) {
+            break Ok(());
+        }
+
+        let Ok(BottomEvent::Update(data)) = receiver.recv() else {
+            break Ok(());
+        };
+        #[cfg(target_os = "linux")]
+        supervisor.report_issues(&data.timings.issues);
+        app.data_collection.eat_data(data);
+
+        match batch::write_summary(&mut stdout_val, &app.data_collection, show_command)
+            .and_then(|_| stdout_val.flush())
+        {
+            Ok(()) => {
+                printed += 1;
+                #[cfg(target_os = "linux")]
+                supervisor.on_summary();
+            }
+            // Whatever we're piped to stopped reading, like `head`.
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => break Ok(()),
+            Err(err) => break Err(err.into()),
+        }
+    };
+
+    #[cfg(target_os = "linux")]
+    supervisor.on_stop();
+    cancellation_token.cancel();
+
+    result
+}
+
+/// Tracks how the collection thread should currently be collecting data, as
+/// controlled by [`CollectionThreadEvent`]s.
+struct CollectionThreadState {
+    is_idle: bool,
+    is_paused: bool,
+    update_rate: u64,
+    idle_rate: u64,
+}
+
+impl CollectionThreadState {
+    fn new(settings: &CollectionSettings) -> Self {
+        Self {
+            is_idle: false,
+            is_paused: false,
+            update_rate: settings.update_rate,
+            idle_rate: settings.idle_rate,
+        }
+    }
+
+    /// Collects data with `settings` from now on.
+    fn apply(
+        &mut self, settings: CollectionSettings, data_state: &mut data_collection::DataCollector,
+    ) {
+        self.update_rate = settings.update_rate;
+        self.idle_rate = settings.idle_rate;
+
+        data_state.set_data_collection(settings.used_widgets);
+        data_state.set_filters(settings.filters);
+        data_state.set_temperature_type(settings.temperature_type);
+        data_state.set_extra_sensors(settings.extra_sensors);
+        data_state.set_use_current_cpu_total(settings.use_current_cpu_total);
+        data_state.set_unnormalized_cpu(settings.unnormalized_cpu);
+        data_state.set_show_average_cpu(settings.show_average_cpu);
+        data_state.set_network_namespaces(settings.network_namespaces);
+        data_state.set_max_processes(settings.max_processes);
+        #[cfg(target_os = "linux")]
+        data_state.set_process_fields(settings.process_fields);
+    }
+
+    /// Handles all messages currently sent to the collection thread, without blocking.
+    fn handle_messages(
+        &mut self, control_receiver: &Receiver<CollectionThreadEvent>,
+        data_state: &mut data_collection::DataCollector,
+    ) {
+        while let Ok(message) = control_receiver.try_recv() {
+            // trace!("Received message in collection thread: {message:?}");
+            match message {
+                CollectionThreadEvent::Reset => {
+                    data_state.data.cleanup();
+                }
+                CollectionThreadEvent::Reconfigure(settings) => {
+                    self.apply(*settings, data_state);
+                }
+                CollectionThreadEvent::Idle => {
+                    self.is_idle = true;
+                }
+                CollectionThreadEvent::Active => {
+                    self.is_idle = false;
+                }
+                CollectionThreadEvent::Pause => {
+                    self.is_paused = true;
+                }
+                CollectionThreadEvent::Resume => {
+                    self.is_paused = false;
+                }
+                #[cfg(target_os = "linux")]
+                CollectionThreadEvent::ProcessFields(fields) => {
+                    data_state.set_process_fields(fields);
+                }
+            }
+        }
+    }
+}
+
+/// Sets up the app and painter again from the config with `profile` applied,
+/// keeping the data collected so far. Nothing is changed if this fails.
+fn switch_profile(
+    args: &args::BottomArgs, app: &mut App, painter: &mut canvas::Painter, profile: Option<String>,
+) -> anyhow::Result<()> {
+    let config = read_config(&app.config_source, profile.as_deref())?;
+    let profiles = config.profile_names();
+
+    // Text is translated as the app is set up, so the locale is switched first,
+    // and switched back if setting up fails.
+    let previous_locale = i18n::locale();
+    i18n::set_locale(get_locale(args, &config)?);
+    let result = init_app(args.clone(), config).and_then(
+        |(mut new_app, widget_layouts, styling, widget_styling)| {
+            let new_painter = canvas::Painter::init(
+                widget_layouts,
+                styling,
+                widget_styling,
+                new_app.app_config_fields.terminal,
+            )?;
+            new_app.take_over(app)?;
+
+            Ok((new_app, new_painter))
+        },
+    );
+    let (mut new_app, new_painter) = result.inspect_err(|_| i18n::set_locale(previous_locale))?;
+    new_app.profiles = profiles;
+    new_app.profile = profile;
+
+    *app = new_app;
+    *painter = new_painter;
+
+    Ok(())
+}
+
+/// Main code to call.
+#[inline]
+pub fn start_bottom() -> anyhow::Result<()> {
+    // let _profiler = dhat::Profiler::new_heap();
+
+    let args = args::get_args();
+
+    // Set the locale from the environment early, so errors from reading the
+    // config are translated. It's set again once the config has been read.
+    i18n::set_locale(
+        args.general
+            .locale
+            .as_deref()
+            .and_then(i18n::Locale::parse)
+            .unwrap_or_else(i18n::Locale::from_env),
+    );
+
+    // The local time zone can't always be read once other threads are running,
+    // so read it now for showing times of day.
+    utils::time_format::local_offset();
+
+    if let Some(log_file) = &args.general.log_file {
+        let filter = match &args.general.log_level {
+            Some(filter) => filter.parse().map_err(|err| anyhow::anyhow!("{err}"))?,
+            None => LogFilter::default(),
+        };
+
+        if let Err(err) = init_logger(filter, log_file, constants::MAX_LOG_FILE_SIZE) {
+            eprintln!(
+                "Note: bottom couldn't log to '{}': {err:#}",
+                log_file.display()
+            );
+        }
+    }
+
+    if args.general.print_default_config {
+        print!("{}", constants::CONFIG_TEXT);
+        return Ok(());
+    }
+
+    if args.general.check_config {
+        return options::check_config(args);
+    }
+
+    // Read from config file.
+    let (config, config_source) = get_or_create_config(
+        args.general.config_location.as_deref(),
+        args.general.profile.as_deref(),
+    )?;
+    let profiles = config.profile_names();
+
+    // Load plugins before the config is consumed.
+    #[cfg(feature = "plugins")]
+    let plugins = options::get_plugins(&config)?;
+
+    // Create the "app" and initialize a bunch of stuff.
+    i18n::set_locale(get_locale(&args, &config)?);
+    let (mut app, widget_layouts, styling, widget_styling) = init_app(args.clone(), config)?;
+    app.config_source = config_source;
+    app.profiles = profiles;
+    app.profile = args.general.profile.clone();
+
+    #[cfg(feature = "plugins")]
+    app.set_plugins(plugins.iter().map(|plugin| plugin.info().clone()).collect())?;
+
+    let session_path = if app.app_config_fields.save_session {
+        session::session_path()
+    } else {
+        None
+    };
+    if let Some(path) = &session_path {
+        match SessionState::load(path) {
+            Ok(Some(session)) => session.restore(&mut app, args.filter.filter.is_some()),
+            Ok(None) => {}
+            Err(err) => eprintln!("Note: bottom couldn't restore the last session: {err:#}"),
+        }
+    }
+
+    #[cfg(feature = "plugins")]
+    let extra_sources: Vec<Box<dyn DataSource + Send>> = vec![Box::new(
+        data_collection::plugins::PluginSource::new(plugins),
+    )];
+    #[cfg(not(feature = "plugins"))]
+    let extra_sources: Vec<Box<dyn DataSource + Send>> = Vec::new();
+
+    if use_batch_mode(&args.general) {
+        return run_batch(
+            app,
+            extra_sources,
+            args.general.iterations,
+            args.process.process_command,
+        );
+    }
+
+    // Create painter and set colours.
+    let mut painter = canvas::Painter::init(
+        widget_layouts,
+        styling,
+        widget_styling,
+        app.app_config_fields.terminal,
+    )?;
+
+    let cancellation_token = Arc::new(CancellationToken::default());
+    let (sender, receiver) = mpsc::channel();
+
+    // Set up the event loop thread; we set this up early to speed up
+    // first-time-to-data.
+    let (collection_thread_ctrl_sender, collection_thread_ctrl_receiver) = mpsc::channel();
+    let _collection_thread = match app.app_config_fields.stress {
+        Some(stress) => create_stress_thread(
+            sender.clone(),
+            cancellation_token.clone(),
+            &app.app_config_fields,
+            stress,
+        ),
+        None => create_collection_thread(
+            sender.clone(),
+            collection_thread_ctrl_receiver,
+            cancellation_token.clone(),
+            CollectionSettings::new(&app),
+            extra_sources,
+        ),
+    };
+
+    // Set up the input handling loop thread.
+    let _input_thread = create_input_thread(sender.clone(), cancellation_token.clone());
+
+    // Set up the thread forwarding signals for dumping the state, freezing,
+    // suspending, and terminating.
+    #[cfg(target_family = "unix")]
+    let signal_handle = create_signal_thread(sender.clone())?;
+
+    // Set up the HTTP server thread if requested.
+    let http_snapshot = match app.app_config_fields.http_address {
+        Some(address) => {
+            let listener = http::bind(address)?;
+            let snapshot = http::SharedSnapshot::default();
+            let _http_thread =
+                http::create_http_thread(listener, snapshot.clone(), cancellation_token.clone());
+
+            Some(snapshot)
+        }
+        None => None,
+    };
+
+    // Set up the cleaning loop thread.
+    let _cleaning_thread = {
+        let cancellation_token = cancellation_token.clone();
+        let cleaning_sender = sender.clone();
+        let offset_wait_time = app.app_config_fields.retention_ms + 60000;
+        thread::spawn(move || loop {
+            if cancellation_token.sleep_with_cancellation(Duration::from_millis(offset_wait_time)) {
+                break;
+            }
+
+            if cleaning_sender.send(BottomEvent::Clean).is_err() {
+                break;
+            }
+        })
+    };
+
+    // Set up tui and crossterm
+    let backend = CrosstermBackend::new(stdout());
+    let mut terminal = if app.app_config_fields.alt_screen {
+        Terminal::new(backend)?
+    } else {
+        // Draw over the whole screen, pushing what was on it up into the scrollback.
+        let (_, height) = crossterm::terminal::size()?;
+        Terminal::with_options(
+            backend,
+            TerminalOptions {
+                viewport: Viewport::Inline(height),
+            },
+        )?
+    };
+    setup_terminal(&mut terminal, &app.app_config_fields)?;
+
+    #[cfg(target_os = "freebsd")]
+    let _stderr_fd = {
+        // A really ugly band-aid to suppress stderr warnings on FreeBSD due to sysinfo.
+        // For more information, see https://github.com/ClementTsang/bottom/issues/798.
+        use std::fs::OpenOptions;
+
+        use filedescriptor::{FileDescriptor, StdioDescriptor};
+
+        let path = OpenOptions::new().write(true).open("/dev/null")?;
+        FileDescriptor::redirect_stdio(&path, StdioDescriptor::Stderr)?
+    };
+
+    // Set panic hook
+    panic::set_hook(Box::new(panic_hook));
+
+    // Set termination hook. On Unix-like systems, the signal thread handles this.
+    #[cfg(target_os = "windows")]
+    crate::event::termination::set_console_handler(sender)?;
+
+    let mut first_run = true;
+    let mut exit = Exit::Quit;
+    let mut num_ticks: u64 = 0;
+    #[cfg(target_os = "linux")]
+    let mut process_fields = app.process_fields();
+
+    let mut idle_tracker = app
+        .app_config_fields
+        .idle_rate
+        .map(|_| IdleTracker::new(Duration::from_millis(constants::IDLE_TIMEOUT_MILLISECONDS)));
+
+    let mut redraw_limiter = RedrawLimiter::new(Duration::from_millis(
+        constants::MIN_REDRAW_INTERVAL_IN_MILLISECONDS,
+    ));
+
+    // Draw once first to initialize the canvas, so it doesn't feel like it's
+    // frozen.
+    try_drawing(&mut terminal, &mut app, &mut painter)?;
+    redraw_limiter.on_draw(Instant::now());
+
+    loop {
+        // Input only requests a redraw, so don't wait for events past when it's due.
+        let recv = match redraw_limiter.wait_time(Instant::now()) {
+            Some(wait) => receiver.recv_timeout(wait).ok(),
+            None => receiver.recv().ok(),
+        };
+
+        if let Some(recv) = recv {
+            if let Some(idle_tracker) = &mut idle_tracker {
+                let now = Instant::now();
+                idle_tracker.on_event(&recv, now);
+
+                if let Some(event) = idle_tracker.check(now) {
+                    let _ = collection_thread_ctrl_sender.send(event);
+                }
+            }
+
+            match recv {
+                BottomEvent::Terminate => {
+                    break;
+                }
+                BottomEvent::Hangup => {
+                    exit = Exit::Hangup;
+                    break;
+                }
+                BottomEvent::Resize => {
+                    // Some terminals reflow their contents on resizing, so redraw everything
+                    // from scratch, recomputing the layout and column widths.
+                    terminal.clear()?;
+                    app.is_force_redraw = true;
+                    try_drawing(&mut terminal, &mut app, &mut painter)?;
+                    redraw_limiter.on_draw(Instant::now());
+                }
+                BottomEvent::KeyInput(event) => {
+                    if handle_key_event_or_break(event, &mut app, &collection_thread_ctrl_sender) {
+                        break;
+                    }
+                    #[cfg(target_family = "unix")]
+                    if std::mem::take(&mut app.is_suspending) {
+                        suspend(&mut terminal, &mut app)?;
+                    }
+                    if std::mem::take(&mut app.is_exporting_config) {
+                        app.config_export = Some(
+                            config_export::export_config(&app, painter.layouts())
+                                .map_err(|err| format!("{err:#}")),
+                        );
+                    }
+                    if std::mem::take(&mut app.is_switching_profile) {
+                        let profile = app.next_profile().map(str::to_string);
+                        if let Err(err) = switch_profile(&args, &mut app, &mut painter, profile) {
+                            app.profile_error = Some(format!("{err:#}"));
+                            app.diagnostics = Some(Default::default());
+                            app.is_force_redraw = true;
+                        } else {
+                            let settings = CollectionSettings::new(&app);
+                            let _ = collection_thread_ctrl_sender
+                                .send(CollectionThreadEvent::Reconfigure(Box::new(settings)));
+                        }
+                    }
+                    app.update_data();
+                    redraw_limiter.request();
+                }
+                BottomEvent::MouseInput(event) => {
+                    handle_mouse_event(event, &mut app);
+                    app.update_data();
+                    redraw_limiter.request();
+                }
+                BottomEvent::PasteEvent(paste) => {
+                    app.handle_paste(paste);
+                    app.update_data();
+                    redraw_limiter.request();
+                }
+                BottomEvent::Update(mut data) => {
+                    app.source_statuses = std::mem::take(&mut data.timings.statuses);
+                    let issues = std::mem::take(&mut data.timings.issues);
+                    app.collection_issues = issues;
+                    let mut tick = TickStats::new(std::mem::take(&mut data.timings));
+                    app.data_collection.eat_data(data);
+                    if let Some(max_bytes) = app.app_config_fields.max_history_memory {
+                        app.data_collection.cap_history(max_bytes);
+                    }
+
+                    if let Some(http_snapshot) = &http_snapshot {
+                        let snapshot = http::MetricsSnapshot::new(&app.data_collection);
+                        if let Ok(mut guard) = http_snapshot.lock() {
+                            *guard = Some(snapshot);
+                        }
+                    }
+
+                    // This thing is required as otherwise, some widgets can't draw correctly w/o
+                    // some data (or they need to be re-drawn).
+                    if first_run {
+                        first_run = false;
+                        app.is_force_redraw = true;
+                    }
+
+                    if !app.frozen_state.is_frozen() {
+                        // Convert all data into data for the visible widgets. Any others will
+                        // be converted once they become visible.
+                        let conversion_start = Instant::now();
+                        app.convert_visible_data();
+                        app.update_data();
+                        tick.conversion = conversion_start.elapsed();
+
+                        let draw_start = Instant::now();
+                        try_drawing(&mut terminal, &mut app, &mut painter)?;
+                        tick.draw = draw_start.elapsed();
+                        redraw_limiter.on_draw(Instant::now());
+                    }
+
+                    crash_report::record(&app, &tick);
+                    app.debug_stats.push(tick);
+
+                    num_ticks += 1;
+                    if let Some(max_ticks) = app
+                        .app_config_fields
+                        .stress
+                        .and_then(|stress| stress.num_ticks)
+                    {
+                        if num_ticks >= max_ticks {
+                            break;
+                        }
+                    }
+                }
+                BottomEvent::Clean => {
+                    app.data_collection.clean_data(
+                        app.app_config_fields.retention_ms,
+                        app.app_config_fields.long_term_retention_ms,
+                    );
+                }
+                BottomEvent::DumpState => {
+                    app.state_dump =
+                        Some(state_dump::dump_state(&app).map_err(|err| format!("{err:#}")));
+                }
+                BottomEvent::Suspend => {
+                    #[cfg(target_family = "unix")]
+                    suspend(&mut terminal, &mut app)?;
+                    try_drawing(&mut terminal, &mut app, &mut painter)?;
+                    redraw_limiter.on_draw(Instant::now());
+                }
+                BottomEvent::Resume => {
+                    resume(&mut terminal, &mut app)?;
+                    try_drawing(&mut terminal, &mut app, &mut painter)?;
+                    redraw_limiter.on_draw(Instant::now());
+                }
+                BottomEvent::ToggleFreeze => {
+                    app.on_action(Action::Freeze);
+                    app.update_data();
+                    redraw_limiter.request();
+                }
+                BottomEvent::FocusGained => {
+                    if app.app_config_fields.pause_on_blur {
+                        let _ = collection_thread_ctrl_sender.send(CollectionThreadEvent::Resume);
+                    }
+                }
+                BottomEvent::FocusLost => {
+                    if app.app_config_fields.pause_on_blur {
+                        let _ = collection_thread_ctrl_sender.send(CollectionThreadEvent::Pause);
+                    }
+                }
+            }
+
+            // Optional process details are only collected while shown, so
+            // tell the collection thread when that changes.
+            #[cfg(target_os = "linux")]
+            {
+                let fields = app.process_fields();
+                if fields != process_fields {
+                    process_fields = fields;
+                    let _ = collection_thread_ctrl_sender
+                        .send(CollectionThreadEvent::ProcessFields(fields));
+                }
+            }
+        }
+
+        if redraw_limiter.is_due(Instant::now()) {
+            try_drawing(&mut terminal, &mut app, &mut painter)?;
+            redraw_limiter.on_draw(Instant::now());
+        }
+    }
+
+    // I think doing it in this order is safe...
+    // TODO: maybe move the cancellation token to the ctrl-c handler?
+    cancellation_token.cancel();
+    #[cfg(target_family = "unix")]
+    signal_handle.close();
+
+    clean_up_on_exit(
+        exit,
+        || cleanup_terminal(&mut terminal, &app.app_config_fields),
+        || match &session_path {
+            Some(path) => SessionState::capture(&app).save(path),
+            None => Ok(()),
+        },
+    )?;
+
+    if exit == Exit::Hangup {
+        return Ok(());
+    }
+
+    if app.app_config_fields.stress.is_some() {
+        println!("{}", DurationSummary::header());
+        for entry in app.debug_stats.summary() {
+            println!("{entry}");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    /// Exits with `exit`, returning the cleanup steps taken in order, and
+    /// whether cleaning up succeeded.
+    fn exit_steps(exit: Exit, restore_fails: bool) -> (Vec<&'static str>, bool) {
+        let steps = RefCell::new(Vec::new());
+        let result = clean_up_on_exit(
+            exit,
+            || {
+                steps.borrow_mut().push("restore terminal");
+                if restore_fails {
+                    anyhow::bail!("the terminal is gone");
+                }
+                Ok(())
+            },
+            || {
+                steps.borrow_mut().push("save session");
+                Ok(())
+            },
+        );
+
+        (steps.into_inner(), result.is_ok())
+    }
+
+    #[test]
+    fn quitting_restores_the_terminal_and_saves() {
+        assert_eq!(
+            exit_steps(Exit::Quit, false),
+            (vec!["restore terminal", "save session"], true)
+        );
+    }
+
+    #[test]
+    fn quitting_saves_even_if_restoring_fails() {
+        assert_eq!(
+            exit_steps(Exit::Quit, true),
+            (vec!["restore terminal", "save session"], false)
+        );
+    }
+
+    #[test]
+    fn hanging_up_only_saves() {
+        assert_eq!(
+            exit_steps(Exit::Hangup, false),
+            (vec!["save session"], true)
+        );
+    }
+}
@@ -0,0 +1,312 @@
+//! Plain-text summaries of the collected data for `--batch`, which are printed
+//! every refresh instead of drawing the terminal UI, similar to `top -b`.
+//!
+//! Each summary is a short header about the whole system, followed by a table
+//! with one line per process, sorted by CPU usage. This is meant to be easy to
+//! log or to filter with tools like `grep`, so each process is kept to a single
+//! line.
+
+#[cfg(target_os = "linux")]
+use std::time::{Duration, Instant};
+use std::{
+    io::{self, Write},
+    time::SystemTime,
+};
+
+#[cfg(target_os = "linux")]
+use hashbrown::HashSet;
+
+use crate::{
+    app::data_farmer::DataCollection,
+    data_collection::{cpu::CpuDataType, memory::MemHarvest, processes::ProcessHarvest},
+    utils::{
+        data_prefixes::{get_binary_bytes, get_decimal_prefix},
+        general::partial_ordering_desc,
+        strings::fit_to_width,
+    },
+};
+#[cfg(target_os = "linux")]
+use crate::{data_collection::CollectionIssue, utils::systemd};
+
+/// Writes a summary of the data in `data` to `out`. If `show_command` is set,
+/// processes are listed by their full command rather than their name.
+pub(crate) fn write_summary(
+    out: &mut impl Write, data: &DataCollection, show_command: bool,
+) -> io::Result<()> {
+    let [one, five, fifteen] = data.load_avg_harvest;
+    writeln!(
+        out,
+        "btm - {}, load average: {one:.2}, {five:.2}, {fifteen:.2}",
+        humantime::format_rfc3339_seconds(SystemTime::now())
+    )?;
+
+    let processes = &data.process_data.process_harvest;
+    writeln!(out, "Tasks: {} total", processes.len())?;
+    writeln!(out, "CPU: {:.1}% avg", average_cpu(data))?;
+    writeln!(out, "Mem: {}", memory_text(&data.memory_harvest))?;
+    writeln!(out, "Swap: {}", memory_text(&data.swap_harvest))?;
+
+    let (rx, rx_unit) = get_decimal_prefix(data.network_harvest.rx, "b");
+    let (tx, tx_unit) = get_decimal_prefix(data.network_harvest.tx, "b");
+    writeln!(out, "Net: {rx:.1}{rx_unit}/s rx, {tx:.1}{tx_unit}/s tx")?;
+    writeln!(out)?;
+
+    writeln!(
+        out,
+        "{:>8} {:<12} {:>6} {:>6} {:>10} {:<10} {}",
+        "PID",
+        "USER",
+        "CPU%",
+        "MEM%",
+        "MEM",
+        "STATE",
+        if show_command { "COMMAND" } else { "NAME" }
+    )?;
+
+    let mut processes: Vec<&ProcessHarvest> = processes.values().collect();
+    processes.sort_by(|a, b| {
+        partial_ordering_desc(a.cpu_usage_percent, b.cpu_usage_percent).then(a.pid.cmp(&b.pid))
+    });
+
+    for process in processes {
+        let (mem, mem_unit) = get_binary_bytes(process.mem_usage_bytes);
+        let name = if show_command {
+            &process.command
+        } else {
+            &process.name
+        };
+
+        writeln!(
+            out,
+            "{:>8} {} {:>6.1} {:>6.1} {:>10} {} {}",
+            process.pid,
+            fit_to_width(&process.user, 12),
+            process.cpu_usage_percent,
+            process.mem_usage_percent,
+            format!("{mem:.1}{mem_unit}"),
+            fit_to_width(&process.process_state.0, 10),
+            name.replace('\n', " ")
+        )?;
+    }
+
+    writeln!(out)
+}
+
+/// Returns the average CPU usage, either from the average entry if there is
+/// one, or by averaging all the cores.
+fn average_cpu(data: &DataCollection) -> f64 {
+    if let Some(avg) = data
+        .cpu_harvest
+        .iter()
+        .find(|cpu| matches!(cpu.data_type, CpuDataType::Avg))
+    {
+        return avg.cpu_usage;
+    }
+
+    if data.cpu_harvest.is_empty() {
+        0.0
+    } else {
+        let total: f64 = data.cpu_harvest.iter().map(|cpu| cpu.cpu_usage).sum();
+        total / data.cpu_harvest.len() as f64
+    }
+}
+
+fn memory_text(harvest: &MemHarvest) -> String {
+    let (used, used_unit) = get_binary_bytes(harvest.used_bytes);
+    let (total, total_unit) = get_binary_bytes(harvest.total_bytes);
+
+    format!(
+        "{used:.1}{used_unit} used, {total:.1}{total_unit} total ({:.1}%)",
+        harvest.checked_percent().unwrap_or(0.0)
+    )
+}
+
+/// Keeps systemd informed when batch mode runs as a service: readiness once
+/// the first summary is printed, watchdog pings as summaries keep coming, and
+/// collection issues as structured journal entries.
+///
+/// Note that pings are only sent after each refresh, so the refresh rate
+/// should be well below the watchdog interval.
+#[cfg(target_os = "linux")]
+pub(crate) struct Supervisor {
+    is_ready: bool,
+    watchdog_interval: Option<Duration>,
+    last_ping: Instant,
+    is_journal_stream: bool,
+    /// Issues already sent to the journal, so each is only logged once.
+    reported: HashSet<CollectionIssue>,
+}
+
+#[cfg(target_os = "linux")]
+impl Supervisor {
+    pub(crate) fn new() -> Self {
+        Self {
+            is_ready: false,
+            watchdog_interval: systemd::watchdog_interval(),
+            last_ping: Instant::now(),
+            is_journal_stream: systemd::is_journal_stream(),
+            reported: HashSet::new(),
+        }
+    }
+
+    /// Signals readiness after the first summary, and pings the watchdog if
+    /// it's been half its interval since the last ping.
+    pub(crate) fn on_summary(&mut self) {
+        if !self.is_ready {
+            self.is_ready = true;
+            systemd::notify("READY=1");
+        }
+
+        if let Some(interval) = self.watchdog_interval {
+            if self.last_ping.elapsed() >= interval / 2 {
+                self.last_ping = Instant::now();
+                systemd::notify("WATCHDOG=1");
+            }
+        }
+    }
+
+    /// Sends any new collection issues to the journal, if stderr goes there.
+    pub(crate) fn report_issues(&mut self, issues: &[CollectionIssue]) {
+        if !self.is_journal_stream {
+            return;
+        }
+
+        for issue in issues {
+            if self.reported.insert(issue.clone()) {
+                // Failing to log isn't worth stopping for.
+                let _ = systemd::journal_warn(&issue.message, &[("BTM_SOURCE", issue.source)]);
+            }
+        }
+    }
+
+    pub(crate) fn on_stop(&self) {
+        systemd::notify("STOPPING=1");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{data_collection::cpu::CpuData, utils::strings::str_width};
+
+    fn summary(data: &DataCollection, show_command: bool) -> String {
+        let mut out = Vec::new();
+        write_summary(&mut out, data, show_command).unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    fn process(pid: u32, name: &str, cpu_usage_percent: f32) -> ProcessHarvest {
+        ProcessHarvest {
+            pid: pid as _,
+            name: name.into(),
+            command: format!("/usr/bin/{name} --flag").into(),
+            cpu_usage_percent,
+            user: "root".into(),
+            process_state: ("Running".into(), 'R'),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn header() {
+        let data = DataCollection {
+            cpu_harvest: vec![
+                CpuData {
+                    data_type: CpuDataType::Cpu(0),
+                    cpu_usage: 10.0,
+                },
+                CpuData {
+                    data_type: CpuDataType::Cpu(1),
+                    cpu_usage: 30.0,
+                },
+            ],
+            memory_harvest: MemHarvest {
+                used_bytes: 1024 * 1024,
+                total_bytes: 4 * 1024 * 1024,
+            },
+            ..Default::default()
+        };
+
+        let text = summary(&data, false);
+        let lines: Vec<_> = text.lines().collect();
+
+        assert!(lines[0].starts_with("btm - "));
+        assert_eq!(lines[1], "Tasks: 0 total");
+        assert_eq!(lines[2], "CPU: 20.0% avg");
+        assert_eq!(lines[3], "Mem: 1.0MiB used, 4.0MiB total (25.0%)");
+        assert_eq!(lines[4], "Swap: 0.0B used, 0.0B total (0.0%)");
+    }
+
+    #[test]
+    fn processes() {
+        let mut data = DataCollection::default();
+        for process in [
+            process(1, "init", 0.5),
+            process(20, "firefox", 30.0),
+            process(300, "btm", 2.0),
+        ] {
+            Arc::make_mut(&mut data.process_data)
+                .process_harvest
+                .insert(process.pid, process);
+        }
+
+        let text = summary(&data, false);
+        let names: Vec<_> = text
+            .lines()
+            .skip_while(|line| !line.trim_start().starts_with("PID"))
+            .skip(1)
+            .filter_map(|line| line.split_whitespace().last())
+            .collect();
+        assert_eq!(names, vec!["firefox", "btm", "init"]);
+
+        let text = summary(&data, true);
+        assert!(text.contains("COMMAND"));
+        assert!(text.contains("/usr/bin/firefox --flag"));
+    }
+
+    #[test]
+    fn mixed_width_columns() {
+        let mut data = DataCollection::default();
+        for (pid, user, state, name) in [
+            (1, "root", "Running", "init"),
+            (2, "用户", "Sleeping", "火狐"),
+            (3, "长长长长长长长长用户", "運行中運行中", "🦀btm"),
+            (4, "e\u{301}mile", "Zombie", "e\u{301}clair"),
+        ] {
+            let process = ProcessHarvest {
+                user: user.into(),
+                process_state: (state.into(), 'R'),
+                ..process(pid, name, 0.0)
+            };
+            Arc::make_mut(&mut data.process_data)
+                .process_harvest
+                .insert(process.pid, process);
+        }
+
+        let text = summary(&data, false);
+        let rows: Vec<_> = text
+            .lines()
+            .skip_while(|line| !line.trim_start().starts_with("PID"))
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        // Every name starts at the same column, however wide the user and state
+        // columns' contents are.
+        let name_columns: Vec<_> = rows
+            .iter()
+            .map(|row| {
+                let name_start = row.trim_end().rfind(' ').unwrap() + 1;
+                str_width(&row[..name_start])
+            })
+            .collect();
+        assert_eq!(name_columns.len(), 5);
+        assert!(name_columns.iter().all(|&column| column == name_columns[0]));
+
+        // Long users and states are cut down to fit.
+        assert!(text.contains(" 长长长长长长 "));
+        assert!(text.contains(" 運行中運行 "));
+    }
+}
@@ -1,57 +1,82 @@
 pub mod components;
 mod dialogs;
 mod drawing_utils;
+#[cfg(feature = "graphics")]
+mod graphics;
+pub mod terminal;
 mod widgets;
 
+use std::{borrow::Cow, io::Write, time::Duration};
+
+use components::time_graph::{GraphData, TimeGraph};
+use hashbrown::{HashMap, HashSet};
 use itertools::izip;
+use terminal::{ColourDepth, TerminalFeatures};
 use tui::{
     backend::Backend,
-    layout::{Constraint, Direction, Layout, Rect},
-    text::Span,
-    widgets::Paragraph,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Paragraph, Tabs},
     Frame, Terminal,
 };
 
 use crate::{
     app::{
         layout_manager::{BottomColRow, BottomLayout, BottomWidgetType, IntermediaryConstraint},
+        states::{LayoutBorder, LayoutDrag},
         App,
     },
     constants::*,
-    options::config::style::Styles,
+    i18n::tr,
+    options::config::{status_bar::StatusBarPosition, style::Styles},
+    utils::{strings::str_width, time_format::TimeFormat},
 };
 
 /// Handles the canvas' state.
 pub struct Painter {
     pub styles: Styles,
+    /// The styles of widgets that override [`Painter::styles`], by widget ID.
+    widget_styles: HashMap<u64, Styles>,
     previous_height: u16,
     previous_width: u16,
+    /// The full layout of each page, including any resizing.
+    layouts: Vec<BottomLayout>,
+    /// The layout of each page that is drawn, without any hidden widgets.
+    pages: Vec<PageLayout>,
+    current_page: usize,
+    hidden_widgets: HashSet<u64>,
+    derived_row_locs: Vec<Rect>,
+    derived_col_locs: Vec<Vec<Rect>>,
+    derived_widget_draw_locs: Vec<Vec<Vec<Vec<Rect>>>>,
+    /// How the current page is drawn instead, if the terminal is too small to
+    /// fit all of it.
+    degraded: Option<Degraded>,
+    /// Whether the terminal supports 24-bit colour.
+    truecolor: bool,
+    /// What the terminal supports, which what's drawn is restricted to.
+    terminal: TerminalFeatures,
+    /// The hostname shown in the status bar.
+    hostname: Option<String>,
+    /// When the system booted, in seconds since the Unix epoch, or 0 if
+    /// unknown. Used for the uptime meter.
+    boot_time: u64,
+    /// Draws graphs as images, if the terminal supports it.
+    #[cfg(feature = "graphics")]
+    graphics: Option<graphics::Graphics>,
+}
 
+/// The layout of a page, along with the constraints derived from it.
+struct PageLayout {
     // TODO: Redo this entire thing.
     row_constraints: Vec<LayoutConstraint>,
     col_constraints: Vec<Vec<LayoutConstraint>>,
     col_row_constraints: Vec<Vec<Vec<LayoutConstraint>>>,
     layout_constraints: Vec<Vec<Vec<Vec<LayoutConstraint>>>>,
-    derived_widget_draw_locs: Vec<Vec<Vec<Vec<Rect>>>>,
     widget_layout: BottomLayout,
 }
 
-/// The constraints of a widget relative to its parent.
-///
-/// This is used over ratatui's internal representation due to
-/// <https://github.com/ClementTsang/bottom/issues/896>.
-pub enum LayoutConstraint {
-    CanvasHandled,
-    Grow,
-    Ratio(u32, u32),
-}
-
-impl Painter {
-    pub fn init(layout: BottomLayout, styling: Styles) -> anyhow::Result<Self> {
-        // Now for modularity; we have to also initialize the base layouts!
-        // We want to do this ONCE and reuse; after this we can just construct
-        // based on the console size.
-
+impl PageLayout {
+    fn new(layout: BottomLayout) -> Self {
         let mut row_constraints = Vec::new();
         let mut col_constraints = Vec::new();
         let mut col_row_constraints = Vec::new();
@@ -130,37 +155,439 @@ impl Painter {
             col_constraints.push(new_col_constraints);
         });
 
-        let painter = Painter {
-            styles: styling,
-            previous_height: 0,
-            previous_width: 0,
+        PageLayout {
             row_constraints,
             col_constraints,
             col_row_constraints,
             layout_constraints,
             widget_layout: layout,
+        }
+    }
+
+    /// Works out where to draw the page's rows, the columns in each row, and
+    /// the widgets in each column, in `area`.
+    fn draw_locs(&self, area: Rect) -> DrawLocs {
+        let row_locs = get_constraints(Direction::Vertical, &self.row_constraints, area);
+
+        let col_locs: Vec<Vec<Rect>> = row_locs
+            .iter()
+            .zip(&self.col_constraints)
+            .map(|(draw_loc, col_constraint)| {
+                get_constraints(Direction::Horizontal, col_constraint, *draw_loc)
+            })
+            .collect();
+
+        let widget_locs = izip!(
+            &col_locs,
+            &self.col_row_constraints,
+            &self.layout_constraints
+        )
+        .map(|(col_locs, col_row_constraint, row_constraint_vec)| {
+            izip!(col_locs, col_row_constraint, row_constraint_vec)
+                .map(|(split_loc, constraint, col_constraint_vec)| {
+                    izip!(
+                        get_constraints(Direction::Vertical, constraint.as_slice(), *split_loc),
+                        col_constraint_vec
+                    )
+                    .map(|(draw_loc, col_row_constraint_vec)| {
+                        // Note that col_row_constraint_vec CONTAINS the widget
+                        // constraints
+                        get_constraints(
+                            Direction::Horizontal,
+                            col_row_constraint_vec.as_slice(),
+                            draw_loc,
+                        )
+                    })
+                    .collect()
+                })
+                .collect()
+        })
+        .collect();
+
+        (row_locs, col_locs, widget_locs)
+    }
+
+    /// Whether every widget on the page is at least the minimum size at
+    /// `widget_locs`. Widgets drawn as part of another one don't count.
+    fn fits(&self, widget_locs: &[Vec<Vec<Vec<Rect>>>]) -> bool {
+        self.widget_layout
+            .rows
+            .iter()
+            .flat_map(|row| &row.children)
+            .flat_map(|col| &col.children)
+            .zip(widget_locs.iter().flatten().flatten())
+            .all(|(col_row, locs)| {
+                col_row.children.iter().zip(locs).all(|(widget, loc)| {
+                    widget.parent_reflector.is_some()
+                        || (loc.width >= MIN_WIDGET_WIDTH && loc.height >= MIN_WIDGET_HEIGHT)
+                })
+            })
+    }
+
+    /// Returns the IDs of the widgets on the page in the order they are left
+    /// out if the terminal is too small: lowest priority first, and the later
+    /// of widgets with the same priority first.
+    fn drop_order(&self) -> Vec<u64> {
+        let mut widgets: Vec<_> = self
+            .widget_layout
+            .rows
+            .iter()
+            .flat_map(|row| &row.children)
+            .flat_map(|col| &col.children)
+            .flat_map(|col_row| &col_row.children)
+            .filter(|widget| widget.parent_reflector.is_none())
+            .collect();
+        widgets.reverse();
+        widgets.sort_by_key(|widget| widget.widget_type.keep_priority());
+
+        widgets
+            .into_iter()
+            .map(|widget| widget.parent_id())
+            .collect()
+    }
+}
+
+/// Where to draw a page's rows, the columns in each row, and the widgets in
+/// each column.
+type DrawLocs = (Vec<Rect>, Vec<Vec<Rect>>, Vec<Vec<Vec<Vec<Rect>>>>);
+
+/// The smallest width a widget in a layout is drawn at.
+const MIN_WIDGET_WIDTH: u16 = 10;
+
+/// The smallest height a widget in a layout is drawn at, which fits its
+/// borders and a line of content.
+const MIN_WIDGET_HEIGHT: u16 = 3;
+
+/// The ID used for the widgets drawn in place of the layout when the
+/// terminal is too small, which no widget in the layout has.
+const DEGRADED_HEADER_ID: u64 = u64::MAX;
+
+/// How a page is drawn when the terminal is too small to fit all of it.
+enum Degraded {
+    /// The widgets with the lowest priority are left out of the page.
+    Partial(PageLayout),
+    /// None of the page's widgets fit, so only the CPU, memory, and network
+    /// bars from basic mode are drawn.
+    Header,
+}
+
+/// Works out how to draw `page` in `area`, returning `None` if every widget
+/// fits. Otherwise, a row is taken for a hint, and widgets are left out one
+/// at a time in [`PageLayout::drop_order`] until the rest fit.
+fn fit_page(page: &PageLayout, area: Rect) -> Option<Degraded> {
+    if page.fits(&page.draw_locs(area).2) {
+        return None;
+    }
+
+    let area = split_hint(area)[0];
+    let mut dropped = HashSet::new();
+    for widget_id in page.drop_order() {
+        dropped.insert(widget_id);
+
+        let partial = PageLayout::new(page.widget_layout.without_widgets(&dropped));
+        if partial.widget_layout.rows.is_empty() {
+            break;
+        }
+        if partial.fits(&partial.draw_locs(area).2) {
+            return Some(Degraded::Partial(partial));
+        }
+    }
+
+    Some(Degraded::Header)
+}
+
+/// Splits off the bottom row of `area` for the hint shown when the terminal
+/// is too small.
+fn split_hint(area: Rect) -> [Rect; 2] {
+    Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area)
+}
+
+// TODO: Can I remove this? Does ratatui's layout constraints work properly for
+// fixing https://github.com/ClementTsang/bottom/issues/896 now?
+fn get_constraints(
+    direction: Direction, constraints: &[LayoutConstraint], area: Rect,
+) -> Vec<Rect> {
+    // Order of operations:
+    // - Ratios first + canvas-handled (which is just zero)
+    // - Then any flex-grows to take up remaining space; divide amongst
+    //   remaining hand out any remaining space
+
+    #[derive(Debug, Default, Clone, Copy)]
+    struct Size {
+        width: u16,
+        height: u16,
+    }
+
+    impl Size {
+        fn shrink_width(&mut self, amount: u16) {
+            self.width -= amount;
+        }
+
+        fn shrink_height(&mut self, amount: u16) {
+            self.height -= amount;
+        }
+    }
+
+    let mut bounds = Size {
+        width: area.width,
+        height: area.height,
+    };
+    let mut sizes = vec![Size::default(); constraints.len()];
+    let mut grow = vec![];
+    let mut num_non_ch = 0;
+
+    for (itx, (constraint, size)) in constraints.iter().zip(sizes.iter_mut()).enumerate() {
+        match constraint {
+            LayoutConstraint::Ratio(a, b) => {
+                match direction {
+                    Direction::Horizontal => {
+                        let amount = (((area.width as u32) * (*a)) / (*b)) as u16;
+                        bounds.shrink_width(amount);
+                        size.width = amount;
+                        size.height = area.height;
+                    }
+                    Direction::Vertical => {
+                        let amount = (((area.height as u32) * (*a)) / (*b)) as u16;
+                        bounds.shrink_height(amount);
+                        size.width = area.width;
+                        size.height = amount;
+                    }
+                }
+                num_non_ch += 1;
+            }
+            LayoutConstraint::Grow => {
+                // Mark it as grow in the vector and handle in second pass.
+                grow.push(itx);
+                num_non_ch += 1;
+            }
+            LayoutConstraint::CanvasHandled => {
+                // Do nothing in this case. It's already 0.
+            }
+        }
+    }
+
+    if !grow.is_empty() {
+        match direction {
+            Direction::Horizontal => {
+                let width = bounds.width / grow.len() as u16;
+                bounds.shrink_width(width * grow.len() as u16);
+                for g in grow {
+                    sizes[g] = Size {
+                        width,
+                        height: area.height,
+                    };
+                }
+            }
+            Direction::Vertical => {
+                let height = bounds.height / grow.len() as u16;
+                bounds.shrink_height(height * grow.len() as u16);
+                for g in grow {
+                    sizes[g] = Size {
+                        width: area.width,
+                        height,
+                    };
+                }
+            }
+        }
+    }
+
+    match direction {
+        Direction::Horizontal => {
+            if let Some(per_item) = bounds.width.checked_div(num_non_ch) {
+                let mut remaining_width = bounds.width % num_non_ch;
+                for (size, constraint) in sizes.iter_mut().zip(constraints) {
+                    match constraint {
+                        LayoutConstraint::CanvasHandled => {}
+                        LayoutConstraint::Grow | LayoutConstraint::Ratio(_, _) => {
+                            if remaining_width > 0 {
+                                size.width += per_item + 1;
+                                remaining_width -= 1;
+                            } else {
+                                size.width += per_item;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Direction::Vertical => {
+            if let Some(per_item) = bounds.height.checked_div(num_non_ch) {
+                let mut remaining_height = bounds.height % num_non_ch;
+                for (size, constraint) in sizes.iter_mut().zip(constraints) {
+                    match constraint {
+                        LayoutConstraint::CanvasHandled => {}
+                        LayoutConstraint::Grow | LayoutConstraint::Ratio(_, _) => {
+                            if remaining_height > 0 {
+                                size.height += per_item + 1;
+                                remaining_height -= 1;
+                            } else {
+                                size.height += per_item;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut curr_x = area.x;
+    let mut curr_y = area.y;
+    sizes
+        .into_iter()
+        .map(|size| {
+            let rect = Rect::new(curr_x, curr_y, size.width, size.height);
+            match direction {
+                Direction::Horizontal => {
+                    curr_x += size.width;
+                }
+                Direction::Vertical => {
+                    curr_y += size.height;
+                }
+            }
+
+            rect
+        })
+        .collect()
+}
+
+/// The constraints of a widget relative to its parent.
+///
+/// This is used over ratatui's internal representation due to
+/// <https://github.com/ClementTsang/bottom/issues/896>.
+pub enum LayoutConstraint {
+    CanvasHandled,
+    Grow,
+    Ratio(u32, u32),
+}
+
+impl Painter {
+    pub fn init(
+        layouts: Vec<BottomLayout>, styling: Styles, widget_styling: HashMap<u64, Styles>,
+        terminal: TerminalFeatures,
+    ) -> anyhow::Result<Self> {
+        // Now for modularity; we have to also initialize the base layouts!
+        // We want to do this ONCE and reuse; after this we can just construct
+        // based on the console size.
+        let painter = Painter {
+            styles: styling,
+            widget_styles: widget_styling,
+            previous_height: 0,
+            previous_width: 0,
+            pages: layouts.iter().cloned().map(PageLayout::new).collect(),
+            layouts,
+            current_page: 0,
+            hidden_widgets: HashSet::default(),
+            derived_row_locs: Vec::default(),
+            derived_col_locs: Vec::default(),
             derived_widget_draw_locs: Vec::default(),
+            degraded: None,
+            truecolor: terminal.colour_depth == ColourDepth::TrueColour,
+            terminal,
+            hostname: sysinfo::System::host_name(),
+            boot_time: sysinfo::System::boot_time(),
+            #[cfg(feature = "graphics")]
+            graphics: graphics::Graphics::detect(),
         };
 
         Ok(painter)
     }
 
+    /// Returns the styles to draw a widget with.
+    pub fn styles_for(&self, widget_id: u64) -> &Styles {
+        self.widget_styles.get(&widget_id).unwrap_or(&self.styles)
+    }
+
+    /// Returns the layout of each page, including any resizing done by
+    /// dragging borders.
+    pub fn layouts(&self) -> &[BottomLayout] {
+        &self.layouts
+    }
+
     /// Determines the border style.
     pub fn get_border_style(&self, widget_id: u64, selected_widget_id: u64) -> tui::style::Style {
+        let styles = self.styles_for(widget_id);
         let is_on_widget = widget_id == selected_widget_id;
         if is_on_widget {
-            self.styles.highlighted_border_style
+            styles.highlighted_border_style
         } else {
-            self.styles.border_style
+            styles.border_style
         }
     }
 
-    fn draw_frozen_indicator(&self, f: &mut Frame<'_>, draw_loc: Rect) {
+    /// Draws a time graph, as an image if the terminal supports it. Returns the
+    /// area that the data points were drawn in.
+    #[cfg_attr(not(feature = "graphics"), allow(unused_variables))]
+    fn draw_graph(
+        &self, f: &mut Frame<'_>, draw_loc: Rect, graph: TimeGraph<'_>,
+        graph_data: &[GraphData<'_>], widget_id: u64,
+    ) -> Rect {
+        #[cfg(feature = "graphics")]
+        if let Some(graphics) = self
+            .graphics
+            .as_ref()
+            .filter(|graphics| graphics.is_available())
+        {
+            // Draw everything but the data points as usual, so the legend and
+            // axes are still drawn with text.
+            let legend_data = graph_data
+                .iter()
+                .map(|data| GraphData {
+                    points: &[],
+                    style: data.style,
+                    name: data.name.clone(),
+                    symbol: data.symbol,
+                })
+                .collect::<Vec<_>>();
+            let graph_area = graph.draw_time_graph(f, draw_loc, &legend_data);
+            graphics.queue(widget_id, graph_area, &graph, graph_data);
+
+            return graph_area;
+        }
+
+        graph.draw_time_graph(f, draw_loc, graph_data)
+    }
+
+    fn draw_frozen_indicator(&self, f: &mut Frame<'_>, draw_loc: Rect, app_state: &App) {
+        let offset = app_state
+            .frozen_state
+            .scrub_offset()
+            .filter(|offset| !offset.is_zero());
+
+        let table_lag = app_state.frozen_state.table_lag();
+
+        let text = match (&app_state.app_config_fields.time_format, offset) {
+            (TimeFormat::Relative, Some(offset)) => {
+                let ago = |offset: Duration| {
+                    humantime::format_duration(Duration::from_secs(offset.as_secs()))
+                };
+                Cow::Owned(match table_lag {
+                    Some(lag) => format!(
+                        "Frozen, viewing {} ago (tables {} ago), press 'f' to unfreeze",
+                        ago(offset),
+                        ago(offset.saturating_sub(lag))
+                    ),
+                    None => format!("Frozen, viewing {} ago, press 'f' to unfreeze", ago(offset)),
+                })
+            }
+            (TimeFormat::Relative, None) => Cow::Borrowed("Frozen, press 'f' to unfreeze"),
+            (format, offset) => {
+                let displayed_time = app_state.displayed_time();
+                let time = format.format(Duration::ZERO, displayed_time);
+                Cow::Owned(match (offset, table_lag) {
+                    (Some(_), Some(lag)) => {
+                        let table_time = format.format(Duration::ZERO, displayed_time + lag);
+                        format!(
+                            "Frozen, viewing {time} (tables {table_time}), press 'f' to unfreeze"
+                        )
+                    }
+                    (Some(_), None) => format!("Frozen, viewing {time}, press 'f' to unfreeze"),
+                    (None, _) => format!("Frozen at {time}, press 'f' to unfreeze"),
+                })
+            }
+        };
+
         f.render_widget(
-            Paragraph::new(Span::styled(
-                "Frozen, press 'f' to unfreeze",
-                self.styles.selected_text_style,
-            )),
+            Paragraph::new(Span::styled(text, self.styles.selected_text_style)),
             Layout::default()
                 .horizontal_margin(1)
                 .constraints([Constraint::Length(1)])
@@ -168,11 +595,195 @@ impl Painter {
         )
     }
 
-    pub fn draw_data<B: Backend>(
+    /// Marks a widget with a `!` at the right of its top border if any of its
+    /// data sources ran into problems, which are listed in the diagnostics
+    /// viewer.
+    fn draw_issue_marker(
+        &self, f: &mut Frame<'_>, app_state: &App, widget_type: &BottomWidgetType, draw_loc: Rect,
+        widget_id: u64,
+    ) {
+        const MARKER: &str = " ! ";
+
+        if !app_state.has_collection_issues(widget_type) {
+            return;
+        }
+
+        // Leave room for the corner, and for how to go back if expanded.
+        let offset = if app_state.is_expanded {
+            1 + str_width(tr(" Esc to go back "))
+        } else {
+            1
+        } + MARKER.len();
+
+        if let Some(x) = usize::from(draw_loc.right())
+            .checked_sub(offset)
+            .filter(|&x| x > usize::from(draw_loc.left()))
+            .and_then(|x| u16::try_from(x).ok())
+        {
+            f.buffer_mut().set_string(
+                x,
+                draw_loc.top(),
+                MARKER,
+                self.styles_for(widget_id).invalid_query_style,
+            );
+        }
+    }
+
+    fn draw_page_tabs(&self, f: &mut Frame<'_>, draw_loc: Rect, app_state: &App) {
+        f.render_widget(
+            Tabs::new(
+                app_state
+                    .pages
+                    .iter()
+                    .map(|page| Line::from(page.name.as_str()))
+                    .collect::<Vec<_>>(),
+            )
+            .divider(tui::symbols::line::VERTICAL)
+            .style(self.styles.text_style)
+            .highlight_style(self.styles.selected_text_style)
+            .select(app_state.current_page),
+            draw_loc,
+        );
+    }
+
+    /// Returns the borders between rows, and between columns in each row, that
+    /// can be dragged to resize them. Each border covers the edges of the
+    /// widgets on both sides of it.
+    fn layout_borders(&self) -> Vec<LayoutBorder> {
+        let row_borders = self
+            .derived_row_locs
+            .windows(2)
+            .enumerate()
+            .map(|(index, locs)| LayoutBorder {
+                row: None,
+                index,
+                area: Rect::new(
+                    locs[0].x,
+                    locs[0].bottom().saturating_sub(1),
+                    locs[0].width,
+                    2,
+                ),
+            });
+
+        let col_borders = self
+            .derived_col_locs
+            .iter()
+            .enumerate()
+            .flat_map(|(row, locs)| {
+                locs.windows(2)
+                    .enumerate()
+                    .map(move |(index, locs)| LayoutBorder {
+                        row: Some(row),
+                        index,
+                        area: Rect::new(
+                            locs[0].right().saturating_sub(1),
+                            locs[0].y,
+                            2,
+                            locs[0].height,
+                        ),
+                    })
+            });
+
+        row_borders.chain(col_borders).collect()
+    }
+
+    /// Moves a dragged border to the mouse by resizing the rows or columns on
+    /// either side of it. This replaces the ratios of every row or column
+    /// sharing the border with their current sizes, so the rest of the layout
+    /// doesn't move. Returns whether anything was resized.
+    fn resize_layout(&mut self, drag: LayoutDrag) -> bool {
+        /// The smallest a row or column can be shrunk to, which fits a widget's
+        /// borders and a line of content.
+        const MIN_SIZE: u16 = 3;
+
+        let locs: Vec<(u16, u16)> = match drag.border.row {
+            None => self
+                .derived_row_locs
+                .iter()
+                .map(|loc| (loc.y, loc.height))
+                .collect(),
+            Some(row) => match self.derived_col_locs.get(row) {
+                Some(locs) => locs.iter().map(|loc| (loc.x, loc.width)).collect(),
+                None => return false,
+            },
+        };
+
+        let index = drag.border.index;
+        let (Some(&(start, before)), Some(&(_, after))) = (locs.get(index), locs.get(index + 1))
+        else {
+            return false;
+        };
+
+        let total = before + after;
+        let min_size = MIN_SIZE.min(total / 2);
+        let new_before = (drag.position + 1)
+            .saturating_sub(start)
+            .clamp(min_size, total - min_size);
+        if new_before == before {
+            return false;
+        }
+
+        let mut sizes: Vec<u32> = locs.iter().map(|(_, size)| u32::from(*size)).collect();
+        sizes[index] = u32::from(new_before);
+        sizes[index + 1] = u32::from(total - new_before);
+        for size in &mut sizes {
+            // Avoid a total ratio of zero if the terminal is tiny.
+            *size = (*size).max(1);
+        }
+        let total_ratio = sizes.iter().sum();
+
+        let mut layout = self.pages[self.current_page].widget_layout.clone();
+        match drag.border.row {
+            None => {
+                for (row, size) in layout.rows.iter_mut().zip(sizes) {
+                    row.constraint = IntermediaryConstraint::PartialRatio(size);
+                }
+                layout.total_row_height_ratio = total_ratio;
+            }
+            Some(row) => {
+                let row = &mut layout.rows[row];
+                for (col, size) in row.children.iter_mut().zip(sizes) {
+                    col.constraint = IntermediaryConstraint::PartialRatio(size);
+                }
+                row.total_col_ratio = total_ratio;
+            }
+        }
+
+        // While widgets are hidden, the rows and columns don't line up with the
+        // full layout, so the new sizes only last until they're shown again.
+        if self.hidden_widgets.is_empty() {
+            self.layouts[self.current_page] = layout.clone();
+        }
+        self.pages[self.current_page] = PageLayout::new(layout);
+        true
+    }
+
+    pub fn draw_data<B: Backend + Write>(
         &mut self, terminal: &mut Terminal<B>, app_state: &mut App,
     ) -> Result<(), std::io::Error> {
         use BottomWidgetType::*;
 
+        if self.current_page != app_state.current_page && app_state.current_page < self.pages.len()
+        {
+            self.current_page = app_state.current_page;
+            app_state.is_force_redraw = true;
+        }
+
+        if self.hidden_widgets != app_state.hidden_widgets {
+            self.hidden_widgets.clone_from(&app_state.hidden_widgets);
+            self.pages = self
+                .layouts
+                .iter()
+                .map(|layout| PageLayout::new(layout.without_widgets(&self.hidden_widgets)))
+                .collect();
+            app_state.is_force_redraw = true;
+        }
+
+        #[cfg(feature = "graphics")]
+        if let Some(graphics) = &mut self.graphics {
+            graphics.start_frame();
+        }
+
         terminal.draw(|f| {
             let (terminal_size, frozen_draw_loc) = if app_state.frozen_state.is_frozen() {
                 // TODO: Remove built-in cache?
@@ -183,6 +794,45 @@ impl Painter {
             } else {
                 (f.area(), None)
             };
+
+            let (terminal_size, status_bar_draw_loc) = match &app_state.app_config_fields.status_bar
+            {
+                Some(status_bar) => {
+                    let (constraints, bar_index) = match status_bar.position {
+                        StatusBarPosition::Top => ([Constraint::Length(1), Constraint::Min(0)], 0),
+                        StatusBarPosition::Bottom => {
+                            ([Constraint::Min(0), Constraint::Length(1)], 1)
+                        }
+                    };
+                    let split_loc = Layout::default()
+                        .constraints(constraints)
+                        .split(terminal_size);
+                    (split_loc[1 - bar_index], Some(split_loc[bar_index]))
+                }
+                None => (terminal_size, None),
+            };
+
+            let (terminal_size, meter_header_draw_loc) =
+                match &app_state.app_config_fields.meter_header {
+                    Some(meter_header) => {
+                        let height = self.meter_header_height(app_state, meter_header);
+                        let [header_loc, rest_loc] =
+                            Layout::vertical([Constraint::Length(height), Constraint::Min(0)])
+                                .areas(terminal_size);
+                        (rest_loc, Some(header_loc))
+                    }
+                    None => (terminal_size, None),
+                };
+
+            // Only show the page tabs if there is more than one page to switch between.
+            let (terminal_size, tabs_draw_loc) = if self.pages.len() > 1 {
+                let split_loc = Layout::default()
+                    .constraints([Constraint::Min(0), Constraint::Length(1)])
+                    .split(terminal_size);
+                (split_loc[0], Some(split_loc[1]))
+            } else {
+                (terminal_size, None)
+            };
             let terminal_height = terminal_size.height;
             let terminal_width = terminal_size.width;
 
@@ -209,6 +859,22 @@ impl Painter {
                 for battery_widget in app_state.states.battery_state.widget_states.values_mut() {
                     battery_widget.tab_click_locs = None;
                 }
+
+                // Reset layout borders...
+                app_state.layout_borders.clear();
+            }
+
+            if let (Some(draw_loc), Some(status_bar)) =
+                (status_bar_draw_loc, &app_state.app_config_fields.status_bar)
+            {
+                self.draw_status_bar(f, app_state, draw_loc, status_bar);
+            }
+
+            if let (Some(draw_loc), Some(meter_header)) = (
+                meter_header_draw_loc,
+                &app_state.app_config_fields.meter_header,
+            ) {
+                self.draw_meter_header(f, app_state, draw_loc, meter_header);
             }
 
             if app_state.help_dialog_state.is_showing_help {
@@ -285,7 +951,7 @@ impl Painter {
                     self.draw_dd_dialog(f, dd_text, app_state, middle_dialog_chunk[1]);
             } else if app_state.is_expanded {
                 if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                    self.draw_frozen_indicator(f, frozen_draw_loc, app_state);
                 }
 
                 let rect = Layout::default()
@@ -300,12 +966,24 @@ impl Painter {
                         rect[0],
                         app_state.current_widget.widget_id - 1,
                     ),
+                    Mem if app_state.current_widget.gauge => self.draw_memory_gauges(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
                     Mem | BasicMem => self.draw_memory_graph(
                         f,
                         app_state,
                         rect[0],
                         app_state.current_widget.widget_id,
                     ),
+                    Disk if app_state.current_widget.gauge => self.draw_disk_gauges(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
                     Disk => self.draw_disk_table(
                         f,
                         app_state,
@@ -340,64 +1018,34 @@ impl Painter {
                         #[cfg(feature = "battery")]
                         self.draw_battery(f, app_state, rect[0], app_state.current_widget.widget_id)
                     }
+                    #[cfg(feature = "plugins")]
+                    Plugin(name) => {
+                        let name = name.clone();
+                        self.draw_plugin(
+                            f,
+                            app_state,
+                            rect[0],
+                            app_state.current_widget.widget_id,
+                            &name,
+                        );
+                    }
                     _ => {}
                 }
+                self.draw_issue_marker(
+                    f,
+                    app_state,
+                    &app_state.current_widget.widget_type,
+                    rect[0],
+                    app_state.current_widget.widget_id,
+                );
             } else if app_state.app_config_fields.use_basic_mode {
                 // Basic mode.  This basically removes all graphs but otherwise
                 // the same info.
                 if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                    self.draw_frozen_indicator(f, frozen_draw_loc, app_state);
                 }
 
-                let actual_cpu_data_len = app_state.converted_data.cpu_data.len().saturating_sub(1);
-
-                // This fixes #397, apparently if the height is 1, it can't render the CPU
-                // bars...
-                let cpu_height = {
-                    let c = (actual_cpu_data_len / 4) as u16
-                        + u16::from(actual_cpu_data_len % 4 != 0)
-                        + u16::from(
-                            app_state.app_config_fields.dedicated_average_row
-                                && actual_cpu_data_len.saturating_sub(1) % 4 != 0,
-                        );
-
-                    if c <= 1 {
-                        1
-                    } else {
-                        c
-                    }
-                };
-
-                let mut mem_rows = 1;
-
-                if app_state.converted_data.swap_labels.is_some() {
-                    mem_rows += 1; // add row for swap
-                }
-
-                #[cfg(feature = "zfs")]
-                {
-                    if app_state.converted_data.arc_labels.is_some() {
-                        mem_rows += 1; // add row for arc
-                    }
-                }
-
-                #[cfg(not(target_os = "windows"))]
-                {
-                    if app_state.converted_data.cache_labels.is_some() {
-                        mem_rows += 1;
-                    }
-                }
-
-                #[cfg(feature = "gpu")]
-                {
-                    if let Some(gpu_data) = &app_state.converted_data.gpu_data {
-                        mem_rows += gpu_data.len() as u16; // add row(s) for gpu
-                    }
-                }
-
-                if mem_rows == 1 {
-                    mem_rows += 1; // need at least 2 rows for RX and TX
-                }
+                let (cpu_height, mem_rows) = Self::basic_header_heights(app_state);
 
                 let vertical_chunks = Layout::default()
                     .direction(Direction::Vertical)
@@ -463,242 +1111,98 @@ impl Painter {
             } else {
                 // Draws using the passed in (or default) layout.
                 if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                    self.draw_frozen_indicator(f, frozen_draw_loc, app_state);
                 }
 
-                if self.derived_widget_draw_locs.is_empty() || app_state.is_force_redraw {
-                    // TODO: Can I remove this? Does ratatui's layout constraints work properly for
-                    // fixing https://github.com/ClementTsang/bottom/issues/896 now?
-                    fn get_constraints(
-                        direction: Direction, constraints: &[LayoutConstraint], area: Rect,
-                    ) -> Vec<Rect> {
-                        // Order of operations:
-                        // - Ratios first + canvas-handled (which is just zero)
-                        // - Then any flex-grows to take up remaining space; divide amongst
-                        //   remaining hand out any remaining space
-
-                        #[derive(Debug, Default, Clone, Copy)]
-                        struct Size {
-                            width: u16,
-                            height: u16,
-                        }
+                if let Some(tabs_draw_loc) = tabs_draw_loc {
+                    self.draw_page_tabs(f, tabs_draw_loc, app_state);
+                }
 
-                        impl Size {
-                            fn shrink_width(&mut self, amount: u16) {
-                                self.width -= amount;
-                            }
+                // Rows and columns can't be resized while some of them are left out.
+                if let (Some(drag), None) = (app_state.layout_drag, &self.degraded) {
+                    if self.resize_layout(drag) {
+                        app_state.is_force_redraw = true;
+                    }
+                }
 
-                            fn shrink_height(&mut self, amount: u16) {
-                                self.height -= amount;
-                            }
+                if app_state.is_force_redraw
+                    || (self.derived_widget_draw_locs.is_empty()
+                        && !matches!(self.degraded, Some(Degraded::Header)))
+                {
+                    self.degraded = fit_page(&self.pages[self.current_page], terminal_size);
+                    (
+                        self.derived_row_locs,
+                        self.derived_col_locs,
+                        self.derived_widget_draw_locs,
+                    ) = match &self.degraded {
+                        None => self.pages[self.current_page].draw_locs(terminal_size),
+                        Some(Degraded::Partial(page)) => {
+                            page.draw_locs(split_hint(terminal_size)[0])
                         }
+                        Some(Degraded::Header) => Default::default(),
+                    };
+                }
 
-                        let mut bounds = Size {
-                            width: area.width,
-                            height: area.height,
-                        };
-                        let mut sizes = vec![Size::default(); constraints.len()];
-                        let mut grow = vec![];
-                        let mut num_non_ch = 0;
-
-                        for (itx, (constraint, size)) in
-                            constraints.iter().zip(sizes.iter_mut()).enumerate()
-                        {
-                            match constraint {
-                                LayoutConstraint::Ratio(a, b) => {
-                                    match direction {
-                                        Direction::Horizontal => {
-                                            let amount =
-                                                (((area.width as u32) * (*a)) / (*b)) as u16;
-                                            bounds.shrink_width(amount);
-                                            size.width = amount;
-                                            size.height = area.height;
-                                        }
-                                        Direction::Vertical => {
-                                            let amount =
-                                                (((area.height as u32) * (*a)) / (*b)) as u16;
-                                            bounds.shrink_height(amount);
-                                            size.width = area.width;
-                                            size.height = amount;
-                                        }
-                                    }
-                                    num_non_ch += 1;
-                                }
-                                LayoutConstraint::Grow => {
-                                    // Mark it as grow in the vector and handle in second pass.
-                                    grow.push(itx);
-                                    num_non_ch += 1;
-                                }
-                                LayoutConstraint::CanvasHandled => {
-                                    // Do nothing in this case. It's already 0.
-                                }
-                            }
-                        }
+                if let Some(degraded) = &self.degraded {
+                    let [rest_loc, hint_loc] = split_hint(terminal_size);
+                    self.draw_too_small_hint(f, hint_loc);
+                    if let Degraded::Header = degraded {
+                        self.draw_degraded_header(f, app_state, rest_loc);
+                    }
+                }
 
-                        if !grow.is_empty() {
-                            match direction {
-                                Direction::Horizontal => {
-                                    let width = bounds.width / grow.len() as u16;
-                                    bounds.shrink_width(width * grow.len() as u16);
-                                    for g in grow {
-                                        sizes[g] = Size {
-                                            width,
-                                            height: area.height,
-                                        };
-                                    }
-                                }
-                                Direction::Vertical => {
-                                    let height = bounds.height / grow.len() as u16;
-                                    bounds.shrink_height(height * grow.len() as u16);
-                                    for g in grow {
-                                        sizes[g] = Size {
-                                            width: area.width,
-                                            height,
-                                        };
-                                    }
-                                }
-                            }
-                        }
+                if app_state.should_get_widget_bounds() && self.degraded.is_none() {
+                    app_state.layout_borders = self.layout_borders();
+                }
 
-                        if num_non_ch > 0 {
-                            match direction {
-                                Direction::Horizontal => {
-                                    let per_item = bounds.width / num_non_ch;
-                                    let mut remaining_width = bounds.width % num_non_ch;
-                                    for (size, constraint) in sizes.iter_mut().zip(constraints) {
-                                        match constraint {
-                                            LayoutConstraint::CanvasHandled => {}
-                                            LayoutConstraint::Grow
-                                            | LayoutConstraint::Ratio(_, _) => {
-                                                if remaining_width > 0 {
-                                                    size.width += per_item + 1;
-                                                    remaining_width -= 1;
-                                                } else {
-                                                    size.width += per_item;
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                                Direction::Vertical => {
-                                    let per_item = bounds.height / num_non_ch;
-                                    let mut remaining_height = bounds.height % num_non_ch;
-                                    for (size, constraint) in sizes.iter_mut().zip(constraints) {
-                                        match constraint {
-                                            LayoutConstraint::CanvasHandled => {}
-                                            LayoutConstraint::Grow
-                                            | LayoutConstraint::Ratio(_, _) => {
-                                                if remaining_height > 0 {
-                                                    size.height += per_item + 1;
-                                                    remaining_height -= 1;
-                                                } else {
-                                                    size.height += per_item;
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
+                let page = match &self.degraded {
+                    Some(Degraded::Partial(page)) => page,
+                    _ => &self.pages[self.current_page],
+                };
+                page.widget_layout
+                    .rows
+                    .iter()
+                    .flat_map(|row| &row.children)
+                    .flat_map(|col| &col.children)
+                    .zip(self.derived_widget_draw_locs.iter().flatten().flatten())
+                    .for_each(|(widgets, widget_draw_locs)| {
+                        self.draw_widgets_with_constraints(f, app_state, widgets, widget_draw_locs);
+                    });
+            }
 
-                        let mut curr_x = area.x;
-                        let mut curr_y = area.y;
-                        sizes
-                            .into_iter()
-                            .map(|size| {
-                                let rect = Rect::new(curr_x, curr_y, size.width, size.height);
-                                match direction {
-                                    Direction::Horizontal => {
-                                        curr_x += size.width;
-                                    }
-                                    Direction::Vertical => {
-                                        curr_y += size.height;
-                                    }
-                                }
-
-                                rect
-                            })
-                            .collect()
-                    }
+            if let Some(pid) = app_state.process_details {
+                self.draw_process_details(f, app_state, pid, terminal_size);
+            }
 
-                    let draw_locs =
-                        get_constraints(Direction::Vertical, &self.row_constraints, terminal_size);
+            if let Some(search) = &app_state.global_search {
+                self.draw_global_search(f, search, terminal_size);
+            }
 
-                    self.derived_widget_draw_locs = izip!(
-                        draw_locs,
-                        &self.col_constraints,
-                        &self.col_row_constraints,
-                        &self.layout_constraints,
-                        &self.widget_layout.rows
-                    )
-                    .map(
-                        |(
-                            draw_loc,
-                            col_constraint,
-                            col_row_constraint,
-                            row_constraint_vec,
-                            cols,
-                        )| {
-                            izip!(
-                                get_constraints(Direction::Horizontal, col_constraint, draw_loc),
-                                col_row_constraint,
-                                row_constraint_vec,
-                                &cols.children
-                            )
-                            .map(|(split_loc, constraint, col_constraint_vec, col_rows)| {
-                                izip!(
-                                    get_constraints(
-                                        Direction::Vertical,
-                                        constraint.as_slice(),
-                                        split_loc
-                                    ),
-                                    col_constraint_vec,
-                                    &col_rows.children
-                                )
-                                .map(|(draw_loc, col_row_constraint_vec, widgets)| {
-                                    // Note that col_row_constraint_vec CONTAINS the widget
-                                    // constraints
-                                    let widget_draw_locs = get_constraints(
-                                        Direction::Horizontal,
-                                        col_row_constraint_vec.as_slice(),
-                                        draw_loc,
-                                    );
-
-                                    // Side effect, draw here.
-                                    self.draw_widgets_with_constraints(
-                                        f,
-                                        app_state,
-                                        widgets,
-                                        &widget_draw_locs,
-                                    );
-
-                                    widget_draw_locs
-                                })
-                                .collect()
-                            })
-                            .collect()
-                        },
-                    )
-                    .collect();
-                } else {
-                    self.widget_layout
-                        .rows
-                        .iter()
-                        .flat_map(|row| &row.children)
-                        .flat_map(|col| &col.children)
-                        .zip(self.derived_widget_draw_locs.iter().flatten().flatten())
-                        .for_each(|(widgets, widget_draw_locs)| {
-                            self.draw_widgets_with_constraints(
-                                f,
-                                app_state,
-                                widgets,
-                                widget_draw_locs,
-                            );
-                        });
-                }
+            if app_state.diagnostics.is_some() {
+                self.draw_diagnostics(f, app_state, terminal_size);
+            }
+
+            if let Some(menu) = &mut app_state.context_menu {
+                self.draw_context_menu(f, menu, terminal_size);
+            }
+
+            if app_state.debug_stats.is_showing {
+                self.draw_debug_overlay(f, app_state, terminal_size);
+            }
+
+            terminal::restrict_buffer(f.buffer_mut(), &self.terminal);
+
+            #[cfg(feature = "graphics")]
+            if let Some(graphics) = &self.graphics {
+                graphics.capture(f.buffer_mut());
             }
         })?;
 
+        #[cfg(feature = "graphics")]
+        if let Some(graphics) = &mut self.graphics {
+            graphics.write(terminal.backend_mut())?;
+        }
+
         if let Some(updated_current_widget) = app_state
             .widget_map
             .get(&app_state.current_widget.widget_id)
@@ -712,6 +1216,98 @@ impl Painter {
         Ok(())
     }
 
+    /// Returns the heights of the CPU bars and of the memory and network row
+    /// at the top of basic mode.
+    fn basic_header_heights(app_state: &App) -> (u16, u16) {
+        let actual_cpu_data_len = app_state.converted_data.cpu_data.len().saturating_sub(1);
+
+        // This fixes #397, apparently if the height is 1, it can't render the CPU
+        // bars...
+        let cpu_height = {
+            let c = (actual_cpu_data_len / 4) as u16
+                + u16::from(actual_cpu_data_len % 4 != 0)
+                + u16::from(
+                    app_state.app_config_fields.dedicated_average_row
+                        && actual_cpu_data_len.saturating_sub(1) % 4 != 0,
+                );
+
+            if c <= 1 {
+                1
+            } else {
+                c
+            }
+        };
+
+        let mut mem_rows = 1;
+
+        if app_state.converted_data.swap_labels.is_some() {
+            mem_rows += 1; // add row for swap
+        }
+
+        #[cfg(feature = "zfs")]
+        {
+            if app_state.converted_data.arc_labels.is_some() {
+                mem_rows += 1; // add row for arc
+            }
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            if app_state.converted_data.cache_labels.is_some() {
+                mem_rows += 1;
+            }
+        }
+
+        #[cfg(feature = "gpu")]
+        {
+            if let Some(gpu_data) = &app_state.converted_data.gpu_data {
+                mem_rows += gpu_data.len() as u16; // add row(s) for gpu
+            }
+        }
+
+        if mem_rows == 1 {
+            mem_rows += 1; // need at least 2 rows for RX and TX
+        }
+
+        (cpu_height, mem_rows)
+    }
+
+    /// Draws the CPU, memory, and network bars from basic mode, for when the
+    /// terminal is too small to fit any of the layout's widgets.
+    fn draw_degraded_header(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect) {
+        let (cpu_height, mem_rows) = Self::basic_header_heights(app_state);
+        let [cpu_loc, middle_loc, _] = Layout::vertical([
+            Constraint::Length(cpu_height),
+            Constraint::Length(mem_rows),
+            Constraint::Min(0),
+        ])
+        .areas(draw_loc);
+        let [mem_loc, net_loc] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(middle_loc);
+
+        // These aren't widgets in the layout, so use an ID that no widget has.
+        if cpu_loc.width >= 2 && cpu_loc.height >= 1 {
+            self.draw_basic_cpu(f, app_state, cpu_loc, DEGRADED_HEADER_ID);
+        }
+        if mem_loc.width >= 2 && mem_loc.height >= 1 {
+            self.draw_basic_memory(f, app_state, mem_loc, DEGRADED_HEADER_ID);
+        }
+        if net_loc.width >= 2 && net_loc.height >= 1 {
+            self.draw_basic_network(f, app_state, net_loc, DEGRADED_HEADER_ID);
+        }
+    }
+
+    /// Draws a hint that the terminal is too small to show everything.
+    fn draw_too_small_hint(&self, f: &mut Frame<'_>, draw_loc: Rect) {
+        f.render_widget(
+            Paragraph::new(tr("Terminal too small, enlarge it to see all widgets"))
+                .style(self.styles.text_style)
+                .alignment(Alignment::Center),
+            draw_loc,
+        );
+    }
+
     fn draw_widgets_with_constraints(
         &self, f: &mut Frame<'_>, app_state: &mut App, widgets: &BottomColRow,
         widget_draw_locs: &[Rect],
@@ -721,9 +1317,15 @@ impl Painter {
             if draw_loc.width >= 2 && draw_loc.height >= 2 {
                 match &widget.widget_type {
                     Cpu => self.draw_cpu(f, app_state, *draw_loc, widget.widget_id),
+                    Mem if widget.gauge => {
+                        self.draw_memory_gauges(f, app_state, *draw_loc, widget.widget_id)
+                    }
                     Mem => self.draw_memory_graph(f, app_state, *draw_loc, widget.widget_id),
                     Net => self.draw_network(f, app_state, *draw_loc, widget.widget_id),
                     Temp => self.draw_temp_table(f, app_state, *draw_loc, widget.widget_id),
+                    Disk if widget.gauge => {
+                        self.draw_disk_gauges(f, app_state, *draw_loc, widget.widget_id)
+                    }
                     Disk => self.draw_disk_table(f, app_state, *draw_loc, widget.widget_id),
                     Proc => self.draw_process(f, app_state, *draw_loc, widget.widget_id),
                     Battery =>
@@ -731,9 +1333,75 @@ impl Painter {
                         #[cfg(feature = "battery")]
                         self.draw_battery(f, app_state, *draw_loc, widget.widget_id)
                     }
+                    #[cfg(feature = "plugins")]
+                    Plugin(name) => {
+                        self.draw_plugin(f, app_state, *draw_loc, widget.widget_id, name)
+                    }
                     _ => {}
                 }
+                self.draw_issue_marker(
+                    f,
+                    app_state,
+                    &widget.widget_type,
+                    *draw_loc,
+                    widget.widget_id,
+                );
             }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::layout_manager::{BottomCol, BottomRow, BottomWidget};
+
+    /// A page with the CPU widget above the temperature and process widgets.
+    fn page() -> PageLayout {
+        let col = |widget_type, widget_id| {
+            BottomCol::new(vec![BottomColRow::new(vec![BottomWidget::new(
+                widget_type,
+                widget_id,
+            )])])
+        };
+
+        PageLayout::new(BottomLayout {
+            rows: vec![
+                BottomRow::new(vec![col(BottomWidgetType::Cpu, 1)]),
+                BottomRow::new(vec![
+                    col(BottomWidgetType::Temp, 2),
+                    col(BottomWidgetType::Proc, 3),
+                ])
+                .total_col_ratio(2),
+            ],
+            total_row_height_ratio: 2,
+        })
+    }
+
+    #[test]
+    fn drop_order() {
+        assert_eq!(page().drop_order(), vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn fit_page_sizes() {
+        assert!(fit_page(&page(), Rect::new(0, 0, 80, 24)).is_none());
+
+        // The temperature and process widgets are too narrow side by side.
+        let Some(Degraded::Partial(partial)) = fit_page(&page(), Rect::new(0, 0, 16, 10)) else {
+            panic!("expected some widgets to be left out");
+        };
+        assert_eq!(partial.drop_order(), vec![1, 3]);
+
+        // Only one row fits, which goes to the process widget.
+        let Some(Degraded::Partial(partial)) = fit_page(&page(), Rect::new(0, 0, 16, 5)) else {
+            panic!("expected some widgets to be left out");
+        };
+        assert_eq!(partial.drop_order(), vec![3]);
+
+        assert!(matches!(
+            fit_page(&page(), Rect::new(0, 0, 8, 3)),
+            Some(Degraded::Header)
+        ));
+    }
+}
@@ -6,7 +6,7 @@ pub mod sortable;
 pub mod state;
 pub mod styling;
 
-use std::{convert::TryInto, marker::PhantomData};
+use std::{cmp::Ordering, convert::TryInto, marker::PhantomData, num::NonZeroU16};
 
 pub use column::*;
 pub use data_type::*;
@@ -126,13 +126,12 @@ impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H
     }
 
     /// Updates the scroll position to a selected index.
-    #[expect(clippy::comparison_chain)]
     pub fn set_position(&mut self, new_index: usize) {
         let new_index = new_index.clamp_upper(self.data.len().saturating_sub(1));
-        if self.state.current_index < new_index {
-            self.state.scroll_direction = ScrollDirection::Down;
-        } else if self.state.current_index > new_index {
-            self.state.scroll_direction = ScrollDirection::Up;
+        match self.state.current_index.cmp(&new_index) {
+            Ordering::Less => self.state.scroll_direction = ScrollDirection::Down,
+            Ordering::Greater => self.state.scroll_direction = ScrollDirection::Up,
+            Ordering::Equal => {}
         }
         self.state.current_index = new_index;
     }
@@ -142,11 +141,33 @@ impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H
         self.state.current_index
     }
 
+    /// Returns the items in the table, in the order they are shown.
+    pub fn data(&self) -> &[DataType] {
+        &self.data
+    }
+
     /// Optionally returns the currently selected item, if there is one.
     pub fn current_item(&self) -> Option<&DataType> {
         self.data.get(self.state.current_index)
     }
 
+    /// Returns the untruncated text of each shown column of the currently
+    /// selected item, separated by tabs.
+    pub fn current_row_text(&self) -> Option<String> {
+        let item = self.current_item()?;
+        let cells: Vec<_> = self
+            .columns
+            .iter()
+            .filter(|column| !column.is_hidden())
+            .map(|column| {
+                item.to_cell(column.inner(), NonZeroU16::MAX)
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        Some(cells.join("\t"))
+    }
+
     /// Returns ratatui's internal selection.
     pub fn ratatui_selected(&self) -> Option<usize> {
         self.state.table_state.selected()
@@ -166,9 +187,9 @@ mod test {
 
     impl DataToCell<&'static str> for TestType {
         fn to_cell(
-            &self, _column: &&'static str, _calculated_width: NonZeroU16,
+            &self, column: &&'static str, _calculated_width: NonZeroU16,
         ) -> Option<Cow<'static, str>> {
-            None
+            Some(format!("{column}{}", self.index).into())
         }
 
         fn column_widths<C: DataTableColumn<&'static str>>(
@@ -187,10 +208,14 @@ mod test {
         let props = DataTableProps {
             title: Some("test".into()),
             table_gap: 1,
+            row_height: 1,
             left_to_right: false,
             is_basic: false,
             show_table_scroll_position: true,
+            show_table_scrollbar: false,
             show_current_entry_when_unfocused: false,
+            is_striped: false,
+            show_row_separators: false,
         };
         let styling = DataTableStyling::default();
 
@@ -249,4 +274,28 @@ mod test {
         assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
         assert_eq!(table.current_item(), Some(&TestType { index: 2 }));
     }
+
+    #[test]
+    fn test_current_row_text() {
+        let columns = [Column::hard("a", 1), Column::hard("b", 1)];
+        let props = DataTableProps {
+            title: None,
+            table_gap: 1,
+            row_height: 1,
+            left_to_right: false,
+            is_basic: false,
+            show_table_scroll_position: false,
+            show_table_scrollbar: false,
+            show_current_entry_when_unfocused: false,
+            is_striped: false,
+            show_row_separators: false,
+        };
+
+        let mut table = DataTable::new(columns, props, DataTableStyling::default());
+        assert_eq!(table.current_row_text(), None);
+
+        table.set_data((0..=2).map(|index| TestType { index }).collect::<Vec<_>>());
+        table.set_position(1);
+        assert_eq!(table.current_row_text(), Some("a1\tb1".to_string()));
+    }
 }
@@ -4,6 +4,8 @@ use std::{
     num::NonZeroU16,
 };
 
+use crate::utils::strings::str_width;
+
 /// A bound on the width of a column.
 #[derive(Clone, Copy, Debug)]
 pub enum ColumnWidthBounds {
@@ -69,7 +71,7 @@ pub trait DataTableColumn<H: ColumnHeader> {
     /// like arrows. Defaults to getting the length of
     /// [`DataTableColumn::header`].
     fn header_len(&self) -> usize {
-        self.header().len()
+        str_width(&self.header())
     }
 }
 
@@ -3,7 +3,7 @@ use std::{borrow::Cow, num::NonZeroU16};
 use tui::widgets::Row;
 
 use super::{ColumnHeader, DataTableColumn};
-use crate::canvas::Painter;
+use crate::options::config::style::Styles;
 
 pub trait DataToCell<H>
 where
@@ -18,7 +18,7 @@ where
     ///
     /// The default implementation just returns the `row` that is passed in.
     #[inline(always)]
-    fn style_row<'a>(&self, row: Row<'a>, _painter: &Painter) -> Row<'a> {
+    fn style_row<'a>(&self, row: Row<'a>, _styles: &Styles) -> Row<'a> {
         row
     }
 
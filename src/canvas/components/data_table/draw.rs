@@ -1,13 +1,16 @@
 use std::{
+    borrow::Cow,
     cmp::{max, min},
-    iter::once,
 };
 
 use concat_string::concat_string;
 use tui::{
+    buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
-    text::{Line, Span, Text},
-    widgets::{Block, Row, Table},
+    style::{Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Row, Table, Wrap},
     Frame,
 };
 
@@ -19,7 +22,8 @@ use crate::{
     app::layout_manager::BottomWidget,
     canvas::{drawing_utils::widget_block, Painter},
     constants::TABLE_GAP_HEIGHT_LIMIT,
-    utils::strings::truncate_to_text,
+    i18n::{tr, tr_args},
+    utils::strings::{str_width, truncate_to_text},
 };
 
 pub enum SelectionState {
@@ -46,6 +50,10 @@ pub struct DrawInfo {
     pub force_redraw: bool,
     pub recalculate_column_widths: bool,
     pub selection_state: SelectionState,
+    /// Where the mouse is, if it is over the table.
+    pub hover: Option<(u16, u16)>,
+    /// What to show in place of the rows if there aren't any, explaining why.
+    pub empty_message: Option<Cow<'static, str>>,
 }
 
 impl DrawInfo {
@@ -104,11 +112,13 @@ where
             let title_style = self.styling.title_style;
 
             let title = if self.props.show_table_scroll_position {
-                let pos = current_index.to_string();
-                let tot = total_items.to_string();
-                let title_string = concat_string!(title, "(", pos, " of ", tot, ") ");
+                let position = tr_args(
+                    "({current} of {total}) ",
+                    &[("current", &current_index), ("total", &total_items)],
+                );
+                let title_string = concat_string!(title, position);
 
-                if title_string.len() + 2 <= draw_loc.width.into() {
+                if str_width(&title_string) + 2 <= draw_loc.width.into() {
                     title_string
                 } else {
                     title.to_string()
@@ -120,7 +130,7 @@ where
             let left_title = Line::from(Span::styled(title, title_style)).left_aligned();
 
             let right_title = if draw_info.is_expanded() {
-                Some(Line::from(" Esc to go back ").right_aligned())
+                Some(Line::from(tr(" Esc to go back ")).right_aligned())
             } else {
                 None
             };
@@ -129,10 +139,39 @@ where
         })
     }
 
+    /// Draws a scrollbar thumb over the right border, if not every row fits.
+    fn draw_scrollbar(&self, buf: &mut Buffer, track: Rect, num_rows: usize, draw_info: &DrawInfo) {
+        let Some((thumb_start, thumb_len)) = scrollbar_thumb(
+            track.height,
+            num_rows,
+            self.data.len(),
+            self.state.display_start_index,
+        ) else {
+            return;
+        };
+
+        let style = if draw_info.is_on_widget() {
+            self.styling.highlighted_border_style
+        } else {
+            self.styling.border_style
+        };
+
+        for y in (track.y + thumb_start)..(track.y + thumb_start + thumb_len) {
+            if let Some(cell) = buf.cell_mut((track.x, y)) {
+                cell.set_symbol(symbols::line::THICK_VERTICAL)
+                    .set_style(style);
+            }
+        }
+    }
+
     pub fn draw(
         &mut self, f: &mut Frame<'_>, draw_info: &DrawInfo, widget: Option<&mut BottomWidget>,
         painter: &Painter,
     ) {
+        let styles = match &widget {
+            Some(widget) => painter.styles_for(widget.widget_id),
+            None => &painter.styles,
+        };
         let draw_loc = draw_info.loc;
         let margined_draw_loc = Layout::default()
             .constraints([Constraint::Percentage(100)])
@@ -142,17 +181,22 @@ where
 
         let block = self.block(draw_info, self.data.len());
 
-        let (inner_width, inner_height) = {
+        let (inner_width, inner_height, is_width_changed) = {
             let inner_rect = block.inner(margined_draw_loc);
+            let is_width_changed = inner_rect.width != self.state.inner_rect.width;
             self.state.inner_rect = inner_rect;
-            (inner_rect.width, inner_rect.height)
+            (inner_rect.width, inner_rect.height, is_width_changed)
         };
 
         if inner_width == 0 || inner_height == 0 {
             f.render_widget(block, margined_draw_loc);
         } else {
-            // Calculate widths
-            if draw_info.recalculate_column_widths {
+            // Calculate widths. These are also recalculated if the width changed without
+            // a force redraw, as otherwise, they won't add up to it.
+            if draw_info.recalculate_column_widths
+                || is_width_changed
+                || self.state.calculated_widths.is_empty()
+            {
                 let col_widths = DataType::column_widths(&self.data, &self.columns);
 
                 self.columns
@@ -200,13 +244,18 @@ where
                 }
 
                 let columns = &self.columns;
+                let row_stride = self.props.row_stride();
+                let row_separator_height = u16::from(self.props.show_row_separators);
+                let num_rows = usize::from(
+                    inner_height.saturating_sub(table_gap + header_height) / row_stride,
+                );
                 let rows = {
-                    let num_rows =
-                        usize::from(inner_height.saturating_sub(table_gap + header_height));
                     self.state
                         .get_start_position(num_rows, draw_info.force_redraw);
-                    let start = self.state.display_start_index;
-                    let end = min(self.data.len(), start + num_rows);
+                    // The start may be past the end if the data or height shrank since the
+                    // last draw.
+                    let end = min(self.data.len(), self.state.display_start_index + num_rows);
+                    let start = min(self.state.display_start_index, end);
                     self.state
                         .table_state
                         .select(Some(self.state.current_index.saturating_sub(start)));
@@ -221,9 +270,11 @@ where
                                         .to_cell(column.inner(), width)
                                         .map(|content| truncate_to_text(&content, width.get()))
                                 }),
-                        );
+                        )
+                        .height(self.props.row_height)
+                        .bottom_margin(row_separator_height);
 
-                        data_row.style_row(row, painter)
+                        data_row.style_row(row, styles)
                     })
                 };
 
@@ -233,10 +284,10 @@ where
                     .style(self.styling.header_style)
                     .bottom_margin(table_gap);
 
+                let is_highlighting_current =
+                    draw_info.is_on_widget() || self.props.show_current_entry_when_unfocused;
                 let widget = {
-                    let highlight_style = if draw_info.is_on_widget()
-                        || self.props.show_current_entry_when_unfocused
-                    {
+                    let highlight_style = if is_highlighting_current {
                         self.styling.highlighted_text_style
                     } else {
                         self.styling.text_style
@@ -258,15 +309,187 @@ where
 
                 let table_state = &mut self.state.table_state;
                 f.render_stateful_widget(widget, margined_draw_loc, table_state);
+
+                let inner_rect = self.state.inner_rect;
+                let rows_top = inner_rect.y + header_height + table_gap;
+                let rows_area = Rect::new(
+                    inner_rect.x,
+                    rows_top,
+                    inner_rect.width,
+                    inner_rect.bottom().saturating_sub(rows_top),
+                );
+
+                // Basic tables have no side borders to draw the scrollbar over.
+                if self.props.show_table_scrollbar && !self.props.is_basic {
+                    let track = Rect::new(inner_rect.right(), rows_top, 1, rows_area.height);
+                    self.draw_scrollbar(f.buffer_mut(), track, num_rows, draw_info);
+                }
+
+                let start = self.state.display_start_index;
+                let num_shown = self.data.len().saturating_sub(start).min(num_rows);
+
+                if self.props.is_striped || self.props.show_row_separators {
+                    let row_height = self.props.row_height.max(1);
+                    let buf = f.buffer_mut();
+
+                    for index in 0..num_shown {
+                        let y = rows_area.y + index as u16 * row_stride;
+
+                        // The selected row keeps its highlight rather than being striped.
+                        let is_highlighted =
+                            is_highlighting_current && start + index == self.state.current_index;
+                        if self.props.is_striped && (start + index) % 2 == 1 && !is_highlighted {
+                            let row_area = Rect::new(rows_area.x, y, rows_area.width, row_height)
+                                .intersection(rows_area);
+                            buf.set_style(row_area, self.styling.stripe_style);
+                        }
+
+                        if self.props.show_row_separators && index + 1 < num_shown {
+                            let separator_y = y + row_height;
+                            for x in rows_area.left()..rows_area.right() {
+                                if let Some(cell) = buf.cell_mut((x, separator_y)) {
+                                    cell.set_symbol(symbols::line::HORIZONTAL)
+                                        .set_style(self.styling.border_style);
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if self.data.is_empty() {
+                    if let Some(message) = &draw_info.empty_message {
+                        f.render_widget(
+                            Paragraph::new(message.as_ref())
+                                .style(self.styling.text_style)
+                                .wrap(Wrap { trim: true }),
+                            rows_area,
+                        );
+                    }
+                }
+
+                if let Some((_, hover_y)) = draw_info.hover {
+                    if let Some(row_area) = hovered_row(
+                        rows_area,
+                        self.props.row_height,
+                        row_stride,
+                        num_shown,
+                        hover_y,
+                    ) {
+                        f.buffer_mut().set_style(
+                            row_area,
+                            Style::default().add_modifier(Modifier::UNDERLINED),
+                        );
+                    }
+                }
             } else {
-                let table = Table::new(
-                    once(Row::new(Text::raw("No data"))),
-                    [Constraint::Percentage(100)],
-                )
-                .block(block)
-                .style(self.styling.text_style);
-                f.render_widget(table, margined_draw_loc);
+                let message = draw_info
+                    .empty_message
+                    .clone()
+                    .unwrap_or_else(|| tr("No data").into());
+                let paragraph = Paragraph::new(message)
+                    .block(block)
+                    .style(self.styling.text_style)
+                    .wrap(Wrap { trim: true });
+                f.render_widget(paragraph, margined_draw_loc);
             }
         }
     }
 }
+
+/// Returns where a scrollbar's thumb starts along its track and how long it
+/// is, given the number of rows shown, the total number of rows, and the index
+/// of the first row shown. Returns [`None`] if every row is shown.
+fn scrollbar_thumb(
+    track_len: u16, num_rows: usize, total_rows: usize, start: usize,
+) -> Option<(u16, u16)> {
+    if track_len == 0 || num_rows == 0 || total_rows <= num_rows {
+        return None;
+    }
+
+    let track_len = usize::from(track_len);
+    let thumb_len = (track_len * num_rows / total_rows).clamp(1, track_len);
+    let max_start = total_rows - num_rows;
+    let thumb_start = ((track_len - thumb_len) * start.min(max_start) + max_start / 2) / max_start;
+
+    Some((thumb_start as u16, thumb_len as u16))
+}
+
+/// Returns the area of the row shown at the given y-coordinate, if any, where
+/// each row starts `row_stride` lines after the last. Lines between rows
+/// aren't part of any row.
+fn hovered_row(
+    rows_area: Rect, row_height: u16, row_stride: u16, num_shown: usize, y: u16,
+) -> Option<Rect> {
+    if y < rows_area.top() || y >= rows_area.bottom() {
+        return None;
+    }
+
+    let row_height = row_height.max(1);
+    let row_stride = row_stride.max(row_height);
+    let index = (y - rows_area.top()) / row_stride;
+    if usize::from(index) >= num_shown || (y - rows_area.top()) % row_stride >= row_height {
+        return None;
+    }
+
+    Some(Rect::new(
+        rows_area.x,
+        rows_area.y + index * row_stride,
+        rows_area.width,
+        row_height,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_scrollbar_thumb() {
+        // Everything fits.
+        assert_eq!(scrollbar_thumb(10, 10, 10, 0), None);
+        assert_eq!(scrollbar_thumb(10, 10, 5, 0), None);
+        assert_eq!(scrollbar_thumb(0, 0, 5, 0), None);
+
+        // Half of the rows are shown.
+        assert_eq!(scrollbar_thumb(10, 10, 20, 0), Some((0, 5)));
+        assert_eq!(scrollbar_thumb(10, 10, 20, 5), Some((3, 5)));
+        assert_eq!(scrollbar_thumb(10, 10, 20, 10), Some((5, 5)));
+
+        // The thumb is always at least one cell long.
+        assert_eq!(scrollbar_thumb(10, 10, 1000, 0), Some((0, 1)));
+        assert_eq!(scrollbar_thumb(10, 10, 1000, 990), Some((9, 1)));
+        assert_eq!(scrollbar_thumb(10, 10, 1000, 2000), Some((9, 1)));
+    }
+
+    #[test]
+    fn test_hovered_row() {
+        let rows_area = Rect::new(1, 3, 20, 10);
+
+        // Outside the rows, such as over the header.
+        assert_eq!(hovered_row(rows_area, 1, 1, 10, 2), None);
+        assert_eq!(hovered_row(rows_area, 1, 1, 10, 13), None);
+
+        assert_eq!(
+            hovered_row(rows_area, 1, 1, 10, 3),
+            Some(Rect::new(1, 3, 20, 1))
+        );
+        assert_eq!(
+            hovered_row(rows_area, 1, 1, 10, 7),
+            Some(Rect::new(1, 7, 20, 1))
+        );
+        assert_eq!(
+            hovered_row(rows_area, 2, 2, 5, 6),
+            Some(Rect::new(1, 5, 20, 2))
+        );
+
+        // Below the last row shown.
+        assert_eq!(hovered_row(rows_area, 1, 1, 3, 6), None);
+
+        // Over the line separating two rows.
+        assert_eq!(hovered_row(rows_area, 1, 2, 5, 4), None);
+        assert_eq!(
+            hovered_row(rows_area, 1, 2, 5, 5),
+            Some(Rect::new(1, 5, 20, 1))
+        );
+    }
+}
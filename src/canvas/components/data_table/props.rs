@@ -7,6 +7,9 @@ pub struct DataTableProps {
     /// The size of the gap between the header and rows.
     pub table_gap: u16,
 
+    /// The height of each row.
+    pub row_height: u16,
+
     /// Whether this table determines column widths from left to right.
     pub left_to_right: bool,
 
@@ -16,6 +19,23 @@ pub struct DataTableProps {
     /// Whether to show the table scroll position.
     pub show_table_scroll_position: bool,
 
+    /// Whether to show a scrollbar if not every row fits.
+    pub show_table_scrollbar: bool,
+
     /// Whether to show the current entry as highlighted when not focused.
     pub show_current_entry_when_unfocused: bool,
+
+    /// Whether every other row has a different background.
+    pub is_striped: bool,
+
+    /// Whether to draw a line between rows.
+    pub show_row_separators: bool,
+}
+
+impl DataTableProps {
+    /// How many lines each row takes up, including the line after it if rows
+    /// are separated.
+    pub fn row_stride(&self) -> u16 {
+        self.row_height.max(1) + u16::from(self.show_row_separators)
+    }
 }
@@ -8,7 +8,7 @@ use super::{
     ColumnHeader, ColumnWidthBounds, DataTable, DataTableColumn, DataTableProps, DataTableState,
     DataTableStyling, DataToCell,
 };
-use crate::utils::strings::truncate_to_text;
+use crate::utils::strings::{str_width, truncate_to_text};
 
 /// Denotes the sort order.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -173,7 +173,7 @@ where
     }
 
     fn header_len(&self) -> usize {
-        self.header().len() + 1
+        str_width(&self.header()) + 1
     }
 }
 
@@ -322,6 +322,30 @@ where
         self.sort_type.sort_index
     }
 
+    /// Returns the text of the column currently being sorted by.
+    pub fn sort_column_text(&self) -> Option<Cow<'static, str>> {
+        self.columns
+            .get(self.sort_type.sort_index)
+            .map(|column| column.inner.text())
+    }
+
+    /// Sorts by the visible column with the given text in the given order.
+    /// Returns whether there was such a column.
+    pub fn set_sort_column_by_text(&mut self, text: &str, order: SortOrder) -> bool {
+        match self
+            .columns
+            .iter()
+            .position(|column| !column.is_hidden && column.inner.text() == text)
+        {
+            Some(index) => {
+                self.sort_type.sort_index = index;
+                self.sort_type.order = order;
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Given a `needle` coordinate, select the corresponding index and value.
     fn get_range(&self, needle: u16) -> Option<usize> {
         let mut start = self.state.inner_rect.x;
@@ -408,10 +432,14 @@ mod test {
             let inner = DataTableProps {
                 title: Some("test".into()),
                 table_gap: 1,
+                row_height: 1,
                 left_to_right: false,
                 is_basic: false,
                 show_table_scroll_position: true,
+                show_table_scrollbar: false,
                 show_current_entry_when_unfocused: false,
+                is_striped: false,
+                show_row_separators: false,
             };
 
             SortDataTableProps {
@@ -1,19 +1,21 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    time::{Duration, SystemTime},
+};
 
-use concat_string::concat_string;
 use tui::{
     layout::{Constraint, Rect},
-    style::Style,
-    symbols::Marker,
+    style::{Modifier, Style},
+    symbols,
     text::{Line, Span},
     widgets::{BorderType, GraphType},
     Frame,
 };
 
-use crate::canvas::drawing_utils::widget_block;
+use crate::{canvas::drawing_utils::widget_block, i18n::tr, utils::time_format::TimeFormat};
 
 use super::time_chart::{
-    Axis, Dataset, LegendPosition, Point, TimeChart, DEFAULT_LEGEND_CONSTRAINTS,
+    Axis, Dataset, GraphMarker, LegendPosition, Point, TimeChart, DEFAULT_LEGEND_CONSTRAINTS,
 };
 
 /// Represents the data required by the [`TimeGraph`].
@@ -21,16 +23,25 @@ pub struct GraphData<'a> {
     pub points: &'a [Point],
     pub style: Style,
     pub name: Option<Cow<'a, str>>,
+    pub symbol: Option<char>,
 }
 
 pub struct TimeGraph<'a> {
     /// The min and max x boundaries. Expects a f64 representing the time range
-    /// in milliseconds.
+    /// in milliseconds, where the min is how far back the right edge is from
+    /// the latest data.
     pub x_bounds: [u64; 2],
 
     /// Whether to hide the time/x-labels.
     pub hide_x_labels: bool,
 
+    /// How to show the time/x-labels.
+    pub time_format: &'a TimeFormat,
+
+    /// When the latest data was collected, which the time/x-labels are relative
+    /// to.
+    pub latest_time: SystemTime,
+
     /// The min and max y boundaries.
     pub y_bounds: [f64; 2],
 
@@ -65,8 +76,20 @@ pub struct TimeGraph<'a> {
     pub legend_constraints: Option<(Constraint, Constraint)>,
 
     /// The marker type. Unlike ratatui's native charts, we assume
-    /// only a single type of marker.
-    pub marker: Marker,
+    /// only a single type of marker, though each [`GraphData`] can
+    /// override it with a symbol.
+    pub marker: GraphMarker,
+
+    /// The range of x-coordinates currently being selected with the mouse, if
+    /// any.
+    pub selection: Option<(u16, u16)>,
+
+    /// Where to draw the inspection cursor, if any. Expects how far back from
+    /// the latest data the cursor is, in milliseconds.
+    pub cursor: Option<u64>,
+
+    /// Whether to fill the area under each line with a gradient.
+    pub gradient: bool,
 }
 
 impl TimeGraph<'_> {
@@ -74,18 +97,22 @@ impl TimeGraph<'_> {
     fn generate_x_axis(&self) -> Axis<'_> {
         // Due to how we display things, we need to adjust the time bound values.
         let time_start = -(self.x_bounds[1] as f64);
-        let adjusted_x_bounds = [time_start, 0.0];
+        let time_end = -(self.x_bounds[0] as f64);
+        let adjusted_x_bounds = [time_start, time_end];
 
         if self.hide_x_labels {
             Axis::default().bounds(adjusted_x_bounds)
         } else {
-            let xb_one = (self.x_bounds[1] / 1000).to_string();
-            let xb_zero = (self.x_bounds[0] / 1000).to_string();
+            let x_labels = [self.x_bounds[1], self.x_bounds[0]]
+                .into_iter()
+                .map(|ago| {
+                    let label = self
+                        .time_format
+                        .format(Duration::from_millis(ago), self.latest_time);
 
-            let x_labels = vec![
-                Span::styled(concat_string!(xb_one, "s"), self.graph_style),
-                Span::styled(concat_string!(xb_zero, "s"), self.graph_style),
-            ];
+                    Span::styled(label, self.graph_style)
+                })
+                .collect();
 
             Axis::default()
                 .bounds(adjusted_x_bounds)
@@ -116,7 +143,11 @@ impl TimeGraph<'_> {
     ///   graph.
     /// - Expects `graph_data`, which represents *what* data to draw, and
     ///   various details like style and optional legends.
-    pub fn draw_time_graph(&self, f: &mut Frame<'_>, draw_loc: Rect, graph_data: &[GraphData<'_>]) {
+    ///
+    /// Returns the area that the data points were drawn in.
+    pub fn draw_time_graph(
+        &self, f: &mut Frame<'_>, draw_loc: Rect, graph_data: &[GraphData<'_>],
+    ) -> Rect {
         let x_axis = self.generate_x_axis();
         let y_axis = self.generate_y_axis();
 
@@ -130,26 +161,83 @@ impl TimeGraph<'_> {
                 .title_top(Line::styled(self.title.as_ref(), self.title_style));
 
             if self.is_expanded {
-                b = b.title_top(Line::styled(" Esc to go back ", self.title_style).right_aligned())
+                b = b.title_top(
+                    Line::styled(tr(" Esc to go back "), self.title_style).right_aligned(),
+                )
             }
 
             b
         };
 
-        f.render_widget(
-            TimeChart::new(data)
-                .block(block)
-                .x_axis(x_axis)
-                .y_axis(y_axis)
-                .marker(self.marker)
-                .legend_style(self.graph_style)
-                .legend_position(self.legend_position)
-                .hidden_legend_constraints(
-                    self.legend_constraints
-                        .unwrap_or(DEFAULT_LEGEND_CONSTRAINTS),
-                ),
-            draw_loc,
-        )
+        let chart = TimeChart::new(data)
+            .block(block)
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .marker(self.marker)
+            .gradient(self.gradient)
+            .legend_style(self.graph_style)
+            .legend_position(self.legend_position)
+            .hidden_legend_constraints(
+                self.legend_constraints
+                    .unwrap_or(DEFAULT_LEGEND_CONSTRAINTS),
+            );
+        let graph_area = chart.graph_area(draw_loc);
+
+        f.render_widget(chart, draw_loc);
+
+        if let Some(selection_area) = self.selection_area(graph_area) {
+            f.buffer_mut().set_style(
+                selection_area,
+                Style::default().add_modifier(Modifier::REVERSED),
+            );
+        }
+
+        if let Some(cursor_area) = self.cursor_area(graph_area) {
+            let buf = f.buffer_mut();
+            for y in cursor_area.top()..cursor_area.bottom() {
+                if let Some(cell) = buf.cell_mut((cursor_area.x, y)) {
+                    // Don't draw over any data points.
+                    if cell.symbol() == " " {
+                        cell.set_symbol(symbols::line::VERTICAL)
+                            .set_style(self.graph_style);
+                    }
+                }
+            }
+        }
+
+        graph_area
+    }
+
+    /// Returns the column of `graph_area` that the inspection cursor is in, if
+    /// there is one and it is within the graph's time range.
+    fn cursor_area(&self, graph_area: Rect) -> Option<Rect> {
+        let time = self.cursor?;
+        let [start, end] = self.x_bounds;
+        if graph_area.is_empty() || end <= start || time < start || time > end {
+            return None;
+        }
+
+        let range = end - start;
+        let last_x = u64::from(graph_area.width - 1);
+        let offset = ((end - time) * last_x + range / 2) / range;
+
+        Some(Rect::new(
+            graph_area.left() + offset as u16,
+            graph_area.top(),
+            1,
+            graph_area.height,
+        ))
+    }
+
+    /// Returns the part of `graph_area` covered by the current selection, if
+    /// there is one.
+    fn selection_area(&self, graph_area: Rect) -> Option<Rect> {
+        let (start, end) = self.selection?;
+        let left = start.min(end).max(graph_area.left());
+        let right = start.max(end).min(graph_area.right().saturating_sub(1));
+
+        (left <= right && !graph_area.is_empty())
+            .then(|| Rect::new(left, graph_area.top(), right - left + 1, graph_area.height))
     }
 }
 
@@ -159,33 +247,43 @@ fn create_dataset<'a>(data: &'a GraphData<'a>) -> Dataset<'a> {
         points,
         style,
         name,
+        symbol,
     } = data;
 
     let dataset = Dataset::default()
         .style(*style)
         .data(points)
+        .symbol(*symbol)
         .graph_type(GraphType::Line);
 
-    if let Some(name) = name {
-        dataset.name(name.as_ref())
-    } else {
-        dataset
+    match (name, symbol) {
+        (Some(name), Some(symbol)) => dataset.name(format!("{symbol} {name}")),
+        (Some(name), None) => dataset.name(name.as_ref()),
+        (None, _) => dataset,
     }
 }
 
 #[cfg(test)]
 mod test {
-    use std::borrow::Cow;
+    use std::{
+        borrow::Cow,
+        time::{Duration, SystemTime},
+    };
 
     use tui::{
+        layout::Rect,
         style::{Color, Style},
-        symbols::Marker,
         text::Span,
         widgets::BorderType,
     };
 
     use super::TimeGraph;
-    use crate::canvas::components::time_chart::Axis;
+    use crate::{
+        canvas::components::time_chart::{Axis, GraphMarker},
+        utils::time_format::TimeFormat,
+    };
+
+    const RELATIVE: &TimeFormat = &TimeFormat::Relative;
 
     const Y_LABELS: [Cow<'static, str>; 3] = [
         Cow::Borrowed("0%"),
@@ -198,6 +296,8 @@ mod test {
             title: " Network ".into(),
             x_bounds: [0, 15000],
             hide_x_labels: false,
+            time_format: RELATIVE,
+            latest_time: SystemTime::UNIX_EPOCH,
             y_bounds: [0.0, 100.5],
             y_labels: &Y_LABELS,
             graph_style: Style::default().fg(Color::Red),
@@ -208,7 +308,10 @@ mod test {
             title_style: Style::default().fg(Color::Cyan),
             legend_position: None,
             legend_constraints: None,
-            marker: Marker::Braille,
+            marker: GraphMarker::Braille,
+            selection: None,
+            cursor: None,
+            gradient: false,
         }
     }
 
@@ -227,6 +330,75 @@ mod test {
         assert_eq!(x_axis.style, actual.style);
     }
 
+    #[test]
+    fn time_graph_gen_x_axis_with_offset() {
+        let mut tg = create_time_graph();
+        tg.x_bounds = [30000, 45000];
+        let style = Style::default().fg(Color::Red);
+        let x_axis = tg.generate_x_axis();
+
+        let actual = Axis::default()
+            .bounds([-45000.0, -30000.0])
+            .labels(vec![Span::styled("45s", style), Span::styled("30s", style)])
+            .style(style);
+        assert_eq!(x_axis.bounds, actual.bounds);
+        assert_eq!(x_axis.labels, actual.labels);
+    }
+
+    #[test]
+    fn time_graph_gen_x_axis_with_clock() {
+        let format: TimeFormat = "[hour]:[minute]:[second]".parse().unwrap();
+        let latest_time = SystemTime::now();
+
+        let mut tg = create_time_graph();
+        tg.time_format = &format;
+        tg.latest_time = latest_time;
+        let style = Style::default().fg(Color::Red);
+        let x_axis = tg.generate_x_axis();
+
+        let labels = [15000, 0].map(|ago| {
+            Span::styled(
+                format.format(Duration::from_millis(ago), latest_time),
+                style,
+            )
+        });
+        assert_eq!(x_axis.labels, Some(labels.to_vec()));
+        assert_eq!(x_axis.labels.unwrap()[0].content.len(), "00:00:00".len());
+    }
+
+    #[test]
+    fn time_graph_selection_area() {
+        let mut tg = create_time_graph();
+        let graph_area = Rect::new(10, 5, 20, 8);
+        assert_eq!(tg.selection_area(graph_area), None);
+
+        tg.selection = Some((25, 15));
+        assert_eq!(tg.selection_area(graph_area), Some(Rect::new(15, 5, 11, 8)));
+
+        // Selections are clamped to the graph area.
+        tg.selection = Some((0, 100));
+        assert_eq!(tg.selection_area(graph_area), Some(graph_area));
+    }
+
+    #[test]
+    fn time_graph_cursor_area() {
+        let mut tg = create_time_graph();
+        let graph_area = Rect::new(10, 5, 11, 8);
+        assert_eq!(tg.cursor_area(graph_area), None);
+
+        tg.cursor = Some(0);
+        assert_eq!(tg.cursor_area(graph_area), Some(Rect::new(20, 5, 1, 8)));
+
+        tg.cursor = Some(7500);
+        assert_eq!(tg.cursor_area(graph_area), Some(Rect::new(15, 5, 1, 8)));
+
+        tg.cursor = Some(15000);
+        assert_eq!(tg.cursor_area(graph_area), Some(Rect::new(10, 5, 1, 8)));
+
+        tg.cursor = Some(20000);
+        assert_eq!(tg.cursor_area(graph_area), None);
+    }
+
     #[test]
     fn time_graph_gen_y_axis() {
         let tg = create_time_graph();
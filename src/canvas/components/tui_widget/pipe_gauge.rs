@@ -6,8 +6,9 @@ use tui::{
     widgets::{Block, Widget},
 };
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub enum LabelLimit {
+    #[default]
     None,
     #[expect(dead_code)]
     Auto(u16),
@@ -15,12 +16,6 @@ pub enum LabelLimit {
     StartLabel,
 }
 
-impl Default for LabelLimit {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 /// A widget to measure something, using pipe characters ('|') as a unit.
 #[derive(Debug, Clone)]
 pub struct PipeGauge<'a> {
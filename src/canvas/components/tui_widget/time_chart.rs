@@ -5,6 +5,7 @@
 //! the specializations are factored out to `time_chart/points.rs`.
 
 mod canvas;
+mod gradient;
 mod points;
 
 use std::{cmp::max, str::FromStr};
@@ -14,7 +15,7 @@ use tui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Flex, Layout, Rect},
     style::{Color, Style, Styled},
-    symbols::{self, Marker},
+    symbols,
     text::{Line, Span},
     widgets::{block::BlockExt, Block, Borders, GraphType, Widget},
 };
@@ -239,6 +240,38 @@ impl FromStr for LegendPosition {
     }
 }
 
+/// The kind of character used to draw the points of a [`TimeChart`].
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum GraphMarker {
+    /// Braille patterns, which have the finest resolution. This is the
+    /// **default**.
+    #[default]
+    Braille,
+    /// A dot per cell.
+    Dot,
+    /// A full block per cell.
+    Block,
+    /// Box-drawing lines, with a cell per point.
+    Line,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseGraphMarkerError;
+
+impl FromStr for GraphMarker {
+    type Err = ParseGraphMarkerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "braille" => Ok(Self::Braille),
+            "dot" => Ok(Self::Dot),
+            "block" => Ok(Self::Block),
+            "line" => Ok(Self::Line),
+            _ => Err(ParseGraphMarkerError),
+        }
+    }
+}
+
 /// A group of data points
 ///
 /// This is the main element composing a [`TimeChart`].
@@ -262,6 +295,8 @@ pub struct Dataset<'a> {
     graph_type: GraphType,
     /// Style used to plot this dataset
     style: Style,
+    /// Symbol that overrides the marker for this dataset, if any
+    symbol: Option<char>,
 }
 
 impl<'a> Dataset<'a> {
@@ -295,7 +330,7 @@ impl<'a> Dataset<'a> {
     /// `⣿`) or half-blocks (`█`, `▄`, and `▀`). See [symbols::Marker] for
     /// more details.
     ///
-    /// Note [`Marker::Braille`] requires a font that supports Unicode Braille
+    /// Note [`symbols::Marker::Braille`] requires a font that supports Unicode Braille
     /// Patterns.
     ///
     /// This is a fluent setter method which must be chained or used as it
@@ -307,6 +342,15 @@ impl<'a> Dataset<'a> {
         self
     }
 
+    /// Sets a symbol to draw each point of this dataset with, instead of the
+    /// [`TimeChart`]'s marker. Points are then drawn at the resolution of a
+    /// cell.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn symbol(mut self, symbol: Option<char>) -> Dataset<'a> {
+        self.symbol = symbol;
+        self
+    }
+
     /// Sets how the dataset should be drawn
     ///
     /// [`TimeChart`] can draw either a [scatter](GraphType::Scatter) or
@@ -387,7 +431,9 @@ pub struct TimeChart<'a> {
     /// of `hidden_legend_constraints`
     legend_position: Option<LegendPosition>,
     /// The marker type.
-    marker: Marker,
+    marker: GraphMarker,
+    /// Whether to fill the area under each dataset with a gradient.
+    gradient: bool,
 }
 
 impl<'a> TimeChart<'a> {
@@ -404,7 +450,8 @@ impl<'a> TimeChart<'a> {
             datasets,
             hidden_legend_constraints: (Constraint::Ratio(1, 4), Constraint::Ratio(1, 4)),
             legend_position: Some(LegendPosition::default()),
-            marker: Marker::Braille,
+            marker: GraphMarker::Braille,
+            gradient: false,
         }
     }
 
@@ -455,11 +502,19 @@ impl<'a> TimeChart<'a> {
 
     /// Sets the marker type.
     #[must_use = "method moves the value of self and returns the modified value"]
-    pub fn marker(mut self, marker: Marker) -> TimeChart<'a> {
+    pub fn marker(mut self, marker: GraphMarker) -> TimeChart<'a> {
         self.marker = marker;
         self
     }
 
+    /// Sets whether to fill the area under each dataset with a vertical
+    /// gradient of its colour.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn gradient(mut self, gradient: bool) -> TimeChart<'a> {
+        self.gradient = gradient;
+        self
+    }
+
     /// Sets the constraints used to determine whether the legend should be
     /// shown or not.
     ///
@@ -496,6 +551,12 @@ impl<'a> TimeChart<'a> {
         self
     }
 
+    /// Returns the area the data points will be drawn in, if the chart is
+    /// drawn at `area`.
+    pub fn graph_area(&self, area: Rect) -> Rect {
+        self.layout(self.block.inner_if_some(area)).graph_area
+    }
+
     /// Compute the internal layout of the chart given the area. If the area is
     /// too small some elements may be automatically hidden
     fn layout(&self, area: Rect) -> ChartLayout {
@@ -777,6 +838,10 @@ impl Widget for TimeChart<'_> {
             })
             .render(graph_area, buf);
 
+        if self.gradient {
+            self.draw_gradients(graph_area, buf);
+        }
+
         if let Some((x, y)) = layout.title_x {
             let title = self.x_axis.title.as_ref().unwrap();
             let width = graph_area
@@ -1060,6 +1125,34 @@ mod tests {
         assert!(layout.legend_area.is_none());
     }
 
+    #[test]
+    fn datasets_can_have_their_own_symbols() {
+        let braille_data = [(0.0, 0.0)];
+        let symbol_data = [(4.0, 4.0)];
+        let widget = TimeChart::new(vec![
+            Dataset::default()
+                .data(&braille_data)
+                .graph_type(GraphType::Scatter),
+            Dataset::default()
+                .data(&symbol_data)
+                .symbol(Some('x'))
+                .graph_type(GraphType::Scatter),
+        ])
+        .x_axis(Axis::default().bounds([0.0, 4.0]))
+        .y_axis(Axis::default().bounds([0.0, 4.0]));
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 5, 5));
+
+        widget.render(buffer.area, &mut buffer);
+
+        let symbols = buffer
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .filter(|symbol| *symbol != " ")
+            .collect::<Vec<_>>();
+        assert_eq!(symbols, vec!["x", "⡀"]);
+    }
+
     #[test]
     fn dataset_legend_style_is_patched() {
         let long_dataset_name = Dataset::default().name("Very long name");
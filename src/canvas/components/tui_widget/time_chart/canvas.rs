@@ -27,6 +27,8 @@ use tui::{
     },
 };
 
+use super::GraphMarker;
+
 /// Interface for all shapes that may be drawn on a Canvas widget.
 pub trait Shape {
     fn draw(&self, painter: &mut Painter<'_, '_>);
@@ -281,6 +283,93 @@ impl Grid for CharGrid {
     }
 }
 
+/// A grid that draws lines with box-drawing characters, one per cell. Each
+/// painted cell is joined up with the painted cells next to it, so steps in a
+/// line are drawn with corners.
+#[derive(Debug, Clone)]
+struct LineGrid {
+    width: u16,
+    height: u16,
+    cells: Vec<bool>,
+    colors: Vec<Color>,
+}
+
+impl LineGrid {
+    fn new(width: u16, height: u16) -> LineGrid {
+        let length = usize::from(width * height);
+        LineGrid {
+            width,
+            height,
+            cells: vec![false; length],
+            colors: vec![Color::Reset; length],
+        }
+    }
+
+    fn is_painted(&self, x: usize, y: Option<usize>) -> bool {
+        match y {
+            Some(y) if x < self.width as usize => {
+                self.cells.get(y * self.width as usize + x) == Some(&true)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Grid for LineGrid {
+    fn resolution(&self) -> (f64, f64) {
+        (f64::from(self.width) - 1.0, f64::from(self.height) - 1.0)
+    }
+
+    fn save(&self) -> Layer {
+        let width = self.width as usize;
+        let string = self
+            .cells
+            .iter()
+            .enumerate()
+            .map(|(index, painted)| {
+                if !painted {
+                    return ' ';
+                }
+
+                let (x, y) = (index % width, index / width);
+                let up = self.is_painted(x, y.checked_sub(1));
+                let down = self.is_painted(x, Some(y + 1));
+                let left = x > 0 && self.is_painted(x - 1, Some(y));
+                let right = self.is_painted(x + 1, Some(y));
+
+                match (up, down, left, right) {
+                    (true, false, false, true) => '╰',
+                    (true, false, true, false) => '╯',
+                    (false, true, false, true) => '╭',
+                    (false, true, true, false) => '╮',
+                    (true, _, _, _) | (_, true, _, _) => '│',
+                    _ => '─',
+                }
+            })
+            .collect();
+
+        Layer {
+            string,
+            colors: self.colors.iter().map(|c| (*c, Color::Reset)).collect(),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.cells.fill(false);
+        self.colors.fill(Color::Reset);
+    }
+
+    fn paint(&mut self, x: usize, y: usize, color: Color) {
+        let index = y * self.width as usize + x;
+        if let Some(c) = self.cells.get_mut(index) {
+            *c = true;
+        }
+        if let Some(c) = self.colors.get_mut(index) {
+            *c = color;
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Painter<'a, 'b> {
     context: &'a mut Context<'b>,
@@ -315,6 +404,7 @@ struct HalfBlockGrid {
 impl HalfBlockGrid {
     /// Create a new [`HalfBlockGrid`] with the given width and height measured
     /// in terminal columns and rows respectively.
+    #[expect(dead_code)]
     fn new(width: u16, height: u16) -> HalfBlockGrid {
         HalfBlockGrid {
             width,
@@ -473,33 +563,64 @@ impl<'a, 'b> From<&'a mut Context<'b>> for Painter<'a, 'b> {
 /// Holds the state of the Canvas when painting to it.
 #[derive(Debug)]
 pub struct Context<'a> {
+    width: u16,
+    height: u16,
     x_bounds: [f64; 2],
     y_bounds: [f64; 2],
+    marker: GraphMarker,
     grid: Box<dyn Grid>,
+    symbol: Option<char>,
+    layers: Vec<Layer>,
     dirty: bool,
     labels: Vec<Label<'a>>,
 }
 
 impl<'a> Context<'a> {
     pub fn new(
-        width: u16, height: u16, x_bounds: [f64; 2], y_bounds: [f64; 2], marker: symbols::Marker,
+        width: u16, height: u16, x_bounds: [f64; 2], y_bounds: [f64; 2], marker: GraphMarker,
     ) -> Context<'a> {
-        let grid: Box<dyn Grid> = match marker {
-            symbols::Marker::Dot => Box::new(CharGrid::new(width, height, '•')),
-            symbols::Marker::Block => Box::new(CharGrid::new(width, height, '█')),
-            symbols::Marker::Bar => Box::new(CharGrid::new(width, height, '▄')),
-            symbols::Marker::Braille => Box::new(BrailleGrid::new(width, height)),
-            symbols::Marker::HalfBlock => Box::new(HalfBlockGrid::new(width, height)),
-        };
         Context {
+            width,
+            height,
             x_bounds,
             y_bounds,
-            grid,
+            marker,
+            grid: Self::marker_grid(width, height, marker),
+            symbol: None,
+            layers: Vec::new(),
             dirty: false,
             labels: Vec::new(),
         }
     }
 
+    fn marker_grid(width: u16, height: u16, marker: GraphMarker) -> Box<dyn Grid> {
+        match marker {
+            GraphMarker::Dot => Box::new(CharGrid::new(width, height, '•')),
+            GraphMarker::Block => Box::new(CharGrid::new(width, height, '█')),
+            GraphMarker::Braille => Box::new(BrailleGrid::new(width, height)),
+            GraphMarker::Line => Box::new(LineGrid::new(width, height)),
+        }
+    }
+
+    /// Draws anything after this with `symbol`, or with the canvas' marker if
+    /// it is [`None`]. Whatever was already drawn is kept as a layer below.
+    pub fn set_symbol(&mut self, symbol: Option<char>) {
+        if symbol == self.symbol {
+            return;
+        }
+
+        if self.dirty {
+            self.layers.push(self.grid.save());
+            self.dirty = false;
+        }
+
+        self.grid = match symbol {
+            Some(symbol) => Box::new(CharGrid::new(self.width, self.height, symbol)),
+            None => Self::marker_grid(self.width, self.height, self.marker),
+        };
+        self.symbol = symbol;
+    }
+
     /// Draw any object that may implement the Shape trait
     pub fn draw<S>(&mut self, shape: &S)
     where
@@ -522,7 +643,7 @@ where
     y_bounds: [f64; 2],
     painter: Option<F>,
     background_color: Color,
-    marker: symbols::Marker,
+    marker: GraphMarker,
 }
 
 impl<'a, F> Default for Canvas<'a, F>
@@ -536,7 +657,7 @@ where
             y_bounds: [0.0, 0.0],
             painter: None,
             background_color: Color::Reset,
-            marker: symbols::Marker::Braille,
+            marker: GraphMarker::Braille,
         }
     }
 }
@@ -588,7 +709,7 @@ where
     ///     .marker(symbols::Marker::Block)
     ///     .paint(|ctx| {});
     /// ```
-    pub fn marker(mut self, marker: symbols::Marker) -> Canvas<'a, F> {
+    pub fn marker(mut self, marker: GraphMarker) -> Canvas<'a, F> {
         self.marker = marker;
         self
     }
@@ -628,21 +749,19 @@ where
         // Paint to this context
         painter(&mut ctx);
 
-        // Paint whatever is in the ctx.
-        let layer = ctx.grid.save();
-
-        for (i, (ch, (fg, bg))) in layer
-            .string
-            .chars()
-            .zip(layer.colors.into_iter())
-            .enumerate()
-        {
-            if ch != ' ' && ch != '\u{2800}' {
-                let (x, y) = (i % width, i / width);
-                if let Some(cell) =
-                    buf.cell_mut((x as u16 + canvas_area.left(), y as u16 + canvas_area.top()))
-                {
-                    cell.set_char(ch).set_fg(fg).set_bg(bg);
+        // Paint whatever is in the ctx, from the bottom layer up.
+        let mut layers = std::mem::take(&mut ctx.layers);
+        layers.push(ctx.grid.save());
+
+        for layer in layers {
+            for (i, (ch, (fg, bg))) in layer.string.chars().zip(layer.colors).enumerate() {
+                if ch != ' ' && ch != '\u{2800}' {
+                    let (x, y) = (i % width, i / width);
+                    if let Some(cell) =
+                        buf.cell_mut((x as u16 + canvas_area.left(), y as u16 + canvas_area.top()))
+                    {
+                        cell.set_char(ch).set_fg(fg).set_bg(bg);
+                    }
                 }
             }
         }
@@ -674,3 +793,25 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn line_grid_joins_cells() {
+        let mut grid = LineGrid::new(3, 3);
+        for (x, y) in [(0, 2), (1, 2), (2, 2), (2, 1), (2, 0)] {
+            grid.paint(x, y, Color::Red);
+        }
+
+        assert_eq!(grid.save().string, "  │  │──╯");
+
+        grid.reset();
+        for (x, y) in [(0, 0), (0, 1), (1, 1), (1, 2)] {
+            grid.paint(x, y, Color::Red);
+        }
+
+        assert_eq!(grid.save().string, "│  ╰╮  │ ");
+    }
+}
@@ -0,0 +1,102 @@
+use tui::{buffer::Buffer, layout::Rect, style::Color};
+
+use super::{points::interpolate_point, Point, TimeChart};
+use crate::{canvas::drawing_utils::colour_to_rgb, utils::general::partial_ordering};
+
+/// How strongly a dataset's colour is mixed into the background at the top of
+/// the graph. This fades to nothing at the bottom.
+const MAX_INTENSITY: f64 = 0.5;
+
+impl TimeChart<'_> {
+    /// Fills the area under each dataset with a vertical gradient of its
+    /// colour. Datasets whose colour can't be converted to RGB are skipped, as
+    /// are any parts of the graph that a dataset has no data for.
+    pub(crate) fn draw_gradients(&self, graph_area: Rect, buf: &mut Buffer) {
+        let [left, right] = self.x_axis.bounds;
+        let [bottom, top] = self.y_axis.bounds;
+        if graph_area.is_empty() || right <= left || top <= bottom {
+            return;
+        }
+
+        let background = self.style.bg.and_then(colour_to_rgb).unwrap_or((0, 0, 0));
+        let width = f64::from(graph_area.width);
+        let height = f64::from(graph_area.height);
+
+        for dataset in &self.datasets {
+            let Some(colour) = dataset.style.fg.and_then(colour_to_rgb) else {
+                continue;
+            };
+
+            for column in 0..graph_area.width {
+                let x = left + (f64::from(column) + 0.5) / width * (right - left);
+                let Some(y) = value_at(dataset.data, x) else {
+                    continue;
+                };
+
+                // How many cells the area under the line covers, including a
+                // partially covered top cell.
+                let filled = ((y - bottom) / (top - bottom)).clamp(0.0, 1.0) * height;
+
+                for row in 0..graph_area.height {
+                    let coverage = (filled - f64::from(row)).min(1.0);
+                    if coverage <= 0.0 {
+                        break;
+                    }
+
+                    let intensity = MAX_INTENSITY * (f64::from(row) + 0.5) / height * coverage;
+                    let position = (graph_area.left() + column, graph_area.bottom() - 1 - row);
+                    if let Some(cell) = buf.cell_mut(position) {
+                        cell.set_bg(blend(background, colour, intensity));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the value of `data` at `x`, interpolating between points, or
+/// [`None`] if `x` is outside of the data.
+fn value_at(data: &[Point], x: f64) -> Option<f64> {
+    match data.binary_search_by(|(point_x, _y)| partial_ordering(point_x, &x)) {
+        Ok(index) => Some(data[index].1),
+        Err(index) if index > 0 && index < data.len() => {
+            Some(interpolate_point(&data[index - 1], &data[index], x))
+        }
+        Err(_) => None,
+    }
+}
+
+/// Mixes `amount` of `to` into `from`.
+fn blend(from: (u8, u8, u8), to: (u8, u8, u8), amount: f64) -> Color {
+    let mix = |from: u8, to: u8| {
+        (f64::from(from) + (f64::from(to) - f64::from(from)) * amount).round() as u8
+    };
+
+    Color::Rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gradient_values() {
+        let data = [(-2.0, 10.0), (0.0, 20.0)];
+
+        assert_eq!(value_at(&data, -3.0), None);
+        assert_eq!(value_at(&data, -2.0), Some(10.0));
+        assert_eq!(value_at(&data, -1.0), Some(15.0));
+        assert_eq!(value_at(&data, 0.0), Some(20.0));
+        assert_eq!(value_at(&data, 1.0), None);
+        assert_eq!(value_at(&[], 0.0), None);
+    }
+
+    #[test]
+    fn gradient_colours() {
+        assert_eq!(blend((0, 0, 0), (200, 100, 50), 0.0), Color::Rgb(0, 0, 0));
+        assert_eq!(
+            blend((0, 0, 0), (200, 100, 50), 0.5),
+            Color::Rgb(100, 50, 25)
+        );
+    }
+}
@@ -30,6 +30,7 @@ impl TimeChart<'_> {
 
         for dataset in &self.datasets {
             let color = dataset.style.fg.unwrap_or(Color::Reset);
+            ctx.set_symbol(dataset.symbol);
 
             let start_bound = self.x_axis.bounds[0];
             let end_bound = self.x_axis.bounds[1];
@@ -151,7 +152,7 @@ fn get_end(dataset: &Dataset<'_>, end_bound: f64) -> (usize, Option<usize>) {
 
 /// Returns the y-axis value for a given `x`, given two points to draw a line
 /// between.
-fn interpolate_point(older_point: &Point, newer_point: &Point, x: f64) -> f64 {
+pub(super) fn interpolate_point(older_point: &Point, newer_point: &Point, x: f64) -> f64 {
     let delta_x = newer_point.0 - older_point.0;
     let delta_y = newer_point.1 - older_point.1;
     let slope = delta_y / delta_x;
@@ -24,6 +24,11 @@ impl Painter {
                 current_table
             };
 
+            // There's nothing to switch to if only one table is shown.
+            if current_table.left_neighbour.is_none() && current_table.right_neighbour.is_none() {
+                return;
+            }
+
             let (left_table, right_table) = (
                 {
                     current_table
@@ -1,2 +1,6 @@
 pub mod dd_dialog;
+pub mod debug_overlay;
+pub mod diagnostics;
+pub mod global_search;
 pub mod help_dialog;
+pub mod process_popups;
@@ -11,6 +11,7 @@ use tui::{
 use crate::{
     app::{App, KillSignal, MAX_PROCESS_SIGNAL},
     canvas::{drawing_utils::dialog_block, Painter},
+    i18n::{tr, tr_args},
     widgets::ProcWidgetMode,
 };
 
@@ -161,38 +162,63 @@ impl Painter {
         if let Some(dd_err) = &app_state.dd_err {
             return Some(Text::from(vec![
                 Line::default(),
-                Line::from("Failed to kill process."),
+                Line::from(tr("Failed to kill process.")),
                 Line::from(dd_err.clone()),
-                Line::from("Please press ENTER or ESC to close this dialog."),
+                Line::from(tr("Please press ENTER or ESC to close this dialog.")),
             ]));
         } else if let Some(to_kill_processes) = app_state.get_to_delete_processes() {
             if let Some(first_pid) = to_kill_processes.1.first() {
+                let widget_state = app_state
+                    .states
+                    .proc_state
+                    .widget_states
+                    .get(&app_state.current_widget.widget_id);
+                let is_grouped = widget_state.is_some_and(|p| {
+                    matches!(
+                        p.mode,
+                        ProcWidgetMode::Grouped | ProcWidgetMode::UserGrouped { .. }
+                    )
+                });
+                let is_user = widget_state
+                    .and_then(|p| p.table.current_item())
+                    .is_some_and(|row| row.id.is_user());
+
                 return Some(Text::from(vec![
                     Line::from(""),
-                    if app_state
-                        .states
-                        .proc_state
-                        .widget_states
-                        .get(&app_state.current_widget.widget_id)
-                        .map(|p| matches!(p.mode, ProcWidgetMode::Grouped))
-                        .unwrap_or(false)
-                    {
+                    if is_user {
+                        if to_kill_processes.1.len() != 1 {
+                            Line::from(tr_args(
+                                "Kill {count} processes of the user '{name}'?  Press ENTER to confirm.",
+                                &[
+                                    ("count", &to_kill_processes.1.len()),
+                                    ("name", &to_kill_processes.0),
+                                ],
+                            ))
+                        } else {
+                            Line::from(tr_args(
+                                "Kill 1 process of the user '{name}'?  Press ENTER to confirm.",
+                                &[("name", &to_kill_processes.0)],
+                            ))
+                        }
+                    } else if is_grouped {
                         if to_kill_processes.1.len() != 1 {
-                            Line::from(format!(
-                                "Kill {} processes with the name '{}'?  Press ENTER to confirm.",
-                                to_kill_processes.1.len(),
-                                to_kill_processes.0
+                            Line::from(tr_args(
+                                "Kill {count} processes with the name '{name}'?  Press ENTER to confirm.",
+                                &[
+                                    ("count", &to_kill_processes.1.len()),
+                                    ("name", &to_kill_processes.0),
+                                ],
                             ))
                         } else {
-                            Line::from(format!(
-                                "Kill 1 process with the name '{}'?  Press ENTER to confirm.",
-                                to_kill_processes.0
+                            Line::from(tr_args(
+                                "Kill 1 process with the name '{name}'?  Press ENTER to confirm.",
+                                &[("name", &to_kill_processes.0)],
                             ))
                         }
                     } else {
-                        Line::from(format!(
-                            "Kill process '{}' with PID {}?  Press ENTER to confirm.",
-                            to_kill_processes.0, first_pid
+                        Line::from(tr_args(
+                            "Kill process '{name}' with PID {pid}?  Press ENTER to confirm.",
+                            &[("name", &to_kill_processes.0), ("pid", first_pid)],
                         ))
                     },
                 ]));
@@ -208,12 +234,12 @@ impl Painter {
         if MAX_PROCESS_SIGNAL == 1 || !app_state.app_config_fields.is_advanced_kill {
             let (yes_button, no_button) = match app_state.delete_dialog_state.selected_signal {
                 KillSignal::Kill(_) => (
-                    Span::styled("Yes", self.styles.selected_text_style),
-                    Span::styled("No", self.styles.text_style),
+                    Span::styled(tr("Yes"), self.styles.selected_text_style),
+                    Span::styled(tr("No"), self.styles.text_style),
                 ),
                 KillSignal::Cancel => (
-                    Span::styled("Yes", self.styles.text_style),
-                    Span::styled("No", self.styles.selected_text_style),
+                    Span::styled(tr("Yes"), self.styles.text_style),
+                    Span::styled(tr("No"), self.styles.selected_text_style),
                 ),
             };
 
@@ -351,9 +377,9 @@ impl Painter {
     ) -> bool {
         if let Some(dd_text) = dd_text {
             let dd_title = if app_state.dd_err.is_some() {
-                Line::styled(" Error ", self.styles.widget_title_style)
+                Line::styled(tr(" Error "), self.styles.widget_title_style)
             } else {
-                Line::styled(" Confirm Kill Process ", self.styles.widget_title_style)
+                Line::styled(tr(" Confirm Kill Process "), self.styles.widget_title_style)
             };
 
             f.render_widget(
@@ -362,7 +388,7 @@ impl Painter {
                         dialog_block(self.styles.border_type)
                             .title_top(dd_title)
                             .title_top(
-                                Line::styled(" Esc to close ", self.styles.widget_title_style)
+                                Line::styled(tr(" Esc to close "), self.styles.widget_title_style)
                                     .right_aligned(),
                             )
                             .style(self.styles.border_style)
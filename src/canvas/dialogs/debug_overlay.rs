@@ -0,0 +1,52 @@
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    app::{debug_stats::DurationSummary, App},
+    canvas::{drawing_utils::dialog_block, Painter},
+    utils::logging::log_level,
+};
+
+const OVERLAY_WIDTH: u16 = 40;
+
+impl Painter {
+    /// Draws an overlay in the top right corner of `draw_loc` with how long
+    /// recent ticks took.
+    pub fn draw_debug_overlay(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect) {
+        let summary = app_state.debug_stats.summary();
+
+        let mut lines = Vec::with_capacity(summary.len() + 2);
+        lines.push(Line::from(Span::styled(
+            DurationSummary::header(),
+            self.styles.table_header_style,
+        )));
+        lines.extend(
+            summary
+                .iter()
+                .map(|entry| Line::from(Span::styled(entry.to_string(), self.styles.text_style))),
+        );
+        if let Some(level) = log_level() {
+            let level = level.as_str().to_ascii_lowercase();
+            lines.push(Line::from(Span::styled(
+                format!("{:<14}{level:>22}", "log level"),
+                self.styles.text_style,
+            )));
+        }
+
+        let width = OVERLAY_WIDTH.min(draw_loc.width);
+        let height = (lines.len() as u16 + 2).min(draw_loc.height);
+        let area = Rect::new(draw_loc.right() - width, draw_loc.y, width, height);
+
+        let title = format!(" Debug (last {} ticks) ", app_state.debug_stats.num_ticks());
+        let block = dialog_block(self.styles.border_type)
+            .border_style(self.styles.border_style)
+            .title_top(Line::styled(title, self.styles.widget_title_style));
+
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
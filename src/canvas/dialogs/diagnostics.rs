@@ -0,0 +1,270 @@
+use std::{cmp::min, path::PathBuf, time::Duration};
+
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    app::App,
+    canvas::{drawing_utils::dialog_block, Painter},
+    data_collection::{CollectionIssue, SourceStatus},
+    i18n::tr,
+    options::ConfigSource,
+};
+
+/// The widest the diagnostics viewer gets.
+const DIAGNOSTICS_MAX_WIDTH: u16 = 80;
+
+/// The tallest the diagnostics viewer gets, including its border.
+const DIAGNOSTICS_MAX_HEIGHT: u16 = 30;
+
+/// The width of the column with each source or setting's name.
+const NAME_WIDTH: usize = 28;
+
+/// Describes where the config in use came from.
+fn config_source_text(source: &ConfigSource) -> String {
+    match source {
+        ConfigSource::File(path) => format!("Loaded from {}", path.display()),
+        ConfigSource::Created(path) => format!(
+            "None found, so a default config was created at {}",
+            path.display()
+        ),
+        ConfigSource::Defaults => {
+            "None could be read or created, so the default settings are used".to_string()
+        }
+    }
+}
+
+/// Describes how exporting the config in use went.
+fn config_export_text(export: &Result<PathBuf, String>) -> String {
+    match export {
+        Ok(path) => format!("Exported the config in use to {}", path.display()),
+        Err(err) => format!("Couldn't export the config in use: {err}"),
+    }
+}
+
+/// Describes how dumping the state went.
+fn state_dump_text(dump: &Result<PathBuf, String>) -> String {
+    match dump {
+        Ok(path) => format!("Dumped the state to {}", path.display()),
+        Err(err) => format!("Couldn't dump the state: {err}"),
+    }
+}
+
+/// Describes the profile in use, if the config has any.
+fn profile_text(profile: Option<&str>, profiles: &[String]) -> Option<String> {
+    match profile {
+        Some(profile) => Some(format!("Using the '{profile}' profile")),
+        None if profiles.is_empty() => None,
+        None => Some(format!(
+            "Not using a profile, out of: {}",
+            profiles.join(", ")
+        )),
+    }
+}
+
+/// Describes how a data source fared, along with how long it took on average
+/// if it was collected from.
+fn source_text(name: &str, status: SourceStatus, avg: Option<Duration>) -> String {
+    let status = match status {
+        SourceStatus::Active => "active",
+        SourceStatus::NoData => "no data",
+        SourceStatus::Disabled => "unused",
+    };
+
+    match avg {
+        Some(avg) => format!(
+            "{name:<NAME_WIDTH$}{status:<10}{:.2}ms",
+            avg.as_secs_f64() * 1000.0
+        ),
+        None => format!("{name:<NAME_WIDTH$}{status}"),
+    }
+}
+
+/// Describes a problem a data source ran into.
+fn issue_text(issue: &CollectionIssue) -> String {
+    format!("{}: {}", issue.source, issue.message)
+}
+
+impl Painter {
+    /// Draws the config and diagnostics viewer in the middle of `draw_loc`. It
+    /// is always the same size, so the widgets behind it don't need to be
+    /// redrawn as it scrolls.
+    pub fn draw_diagnostics(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect) {
+        let header = |text: &'static str| Line::styled(text, self.styles.table_header_style);
+        let text = |text: String| Line::styled(text, self.styles.text_style);
+
+        let mut lines = vec![
+            header("Config file"),
+            text(config_source_text(&app_state.config_source)),
+            text("Command-line arguments take priority over the config file.".to_string()),
+        ];
+        if let Some(profile) = profile_text(app_state.profile.as_deref(), &app_state.profiles) {
+            lines.push(text(profile));
+        }
+        if let Some(err) = &app_state.profile_error {
+            lines.push(text(format!("Couldn't switch profiles: {err}")));
+        }
+        if let Some(export) = &app_state.config_export {
+            lines.push(text(config_export_text(export)));
+        }
+        if let Some(dump) = &app_state.state_dump {
+            lines.push(text(state_dump_text(dump)));
+        }
+        lines.push(Line::default());
+        lines.push(header("Data sources"));
+
+        if app_state.source_statuses.is_empty() {
+            lines.push(text("Waiting for the first collection...".to_string()));
+        } else {
+            let summary = app_state.debug_stats.summary();
+            lines.extend(app_state.source_statuses.iter().map(|&(name, status)| {
+                let avg = summary
+                    .iter()
+                    .find(|entry| entry.name == name)
+                    .map(|entry| entry.avg);
+                text(source_text(name, status, avg))
+            }));
+        }
+
+        if !app_state.collection_issues.is_empty() {
+            lines.push(Line::default());
+            lines.push(header("Collection issues"));
+            lines.extend(
+                app_state
+                    .collection_issues
+                    .iter()
+                    .map(|issue| text(issue_text(issue))),
+            );
+        }
+
+        lines.push(Line::default());
+        lines.push(header("Settings in use"));
+        lines.extend(
+            app_state
+                .app_config_fields
+                .effective_settings()
+                .into_iter()
+                .map(|(name, value)| {
+                    Line::from(vec![
+                        Span::styled(format!("{name:<NAME_WIDTH$}"), self.styles.text_style),
+                        Span::styled(value, self.styles.text_style),
+                    ])
+                }),
+        );
+
+        let width = DIAGNOSTICS_MAX_WIDTH.min(draw_loc.width);
+        let height = DIAGNOSTICS_MAX_HEIGHT.min(draw_loc.height);
+        let area = Rect::new(
+            draw_loc.x + (draw_loc.width - width) / 2,
+            draw_loc.y + (draw_loc.height - height) / 2,
+            width,
+            height,
+        );
+
+        let Some(scroll) = &mut app_state.diagnostics else {
+            return;
+        };
+        scroll.max_scroll_index = (lines.len() as u16).saturating_sub(height.saturating_sub(2));
+        scroll.current_scroll_index = min(scroll.current_scroll_index, scroll.max_scroll_index);
+
+        let block = dialog_block(self.styles.border_type)
+            .border_style(self.styles.border_style)
+            .title_top(Line::styled(
+                tr(" Config and Diagnostics "),
+                self.styles.widget_title_style,
+            ))
+            .title_top(
+                Line::styled(tr(" Esc to close "), self.styles.widget_title_style).right_aligned(),
+            );
+
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .scroll((scroll.current_scroll_index, 0)),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn config_source_texts() {
+        let path = PathBuf::from("/tmp/bottom.toml");
+
+        assert_eq!(
+            config_source_text(&ConfigSource::File(path.clone())),
+            "Loaded from /tmp/bottom.toml"
+        );
+        assert!(config_source_text(&ConfigSource::Created(path)).ends_with("/tmp/bottom.toml"));
+    }
+
+    #[test]
+    fn config_export_texts() {
+        assert_eq!(
+            config_export_text(&Ok(PathBuf::from("/tmp/bottom-export.toml"))),
+            "Exported the config in use to /tmp/bottom-export.toml"
+        );
+        assert!(config_export_text(&Err("no space".to_string())).ends_with("no space"));
+    }
+
+    #[test]
+    fn state_dump_texts() {
+        assert_eq!(
+            state_dump_text(&Ok(PathBuf::from("/tmp/bottom-1.json"))),
+            "Dumped the state to /tmp/bottom-1.json"
+        );
+        assert!(state_dump_text(&Err("no space".to_string())).ends_with("no space"));
+    }
+
+    #[test]
+    fn profile_texts() {
+        let profiles = vec!["laptop".to_string(), "server".to_string()];
+
+        assert_eq!(profile_text(None, &[]), None);
+        assert_eq!(
+            profile_text(Some("laptop"), &profiles).as_deref(),
+            Some("Using the 'laptop' profile")
+        );
+        assert_eq!(
+            profile_text(None, &profiles).as_deref(),
+            Some("Not using a profile, out of: laptop, server")
+        );
+    }
+
+    #[test]
+    fn source_texts() {
+        assert_eq!(
+            source_text(
+                "cpu",
+                SourceStatus::Active,
+                Some(Duration::from_micros(1500))
+            ),
+            format!("{:<NAME_WIDTH$}{:<10}1.50ms", "cpu", "active")
+        );
+        assert_eq!(
+            source_text("gpu", SourceStatus::Disabled, None),
+            format!("{:<NAME_WIDTH$}unused", "gpu")
+        );
+    }
+
+    #[test]
+    fn issue_texts() {
+        let issue = CollectionIssue {
+            source: "temperature",
+            message: "Couldn't read any hwmon sensors, which may need root".to_string(),
+        };
+
+        assert_eq!(
+            issue_text(&issue),
+            "temperature: Couldn't read any hwmon sensors, which may need root"
+        );
+    }
+}
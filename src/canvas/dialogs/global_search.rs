@@ -0,0 +1,85 @@
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+use unicode_ellipsis::truncate_str;
+
+use crate::{
+    app::GlobalSearch,
+    canvas::{drawing_utils::dialog_block, Painter},
+    i18n::{tr, tr_args},
+};
+
+/// The widest the global search popup gets.
+const GLOBAL_SEARCH_MAX_WIDTH: u16 = 60;
+
+/// The tallest the global search popup gets, including its border.
+const GLOBAL_SEARCH_MAX_HEIGHT: u16 = 16;
+
+/// The width of the column showing what kind of result each one is.
+const KIND_WIDTH: usize = 9;
+
+impl Painter {
+    /// Draws the global search in the middle of `draw_loc`. It is always the
+    /// same size, so the widgets behind it don't need to be redrawn as the
+    /// results change.
+    pub fn draw_global_search(&self, f: &mut Frame<'_>, search: &GlobalSearch, draw_loc: Rect) {
+        let width = GLOBAL_SEARCH_MAX_WIDTH.min(draw_loc.width);
+        let height = GLOBAL_SEARCH_MAX_HEIGHT.min(draw_loc.height);
+        let area = Rect::new(
+            draw_loc.x + (draw_loc.width - width) / 2,
+            draw_loc.y + (draw_loc.height - height) / 2,
+            width,
+            height,
+        );
+        let inner_width = usize::from(width.saturating_sub(2));
+
+        let mut lines = vec![Line::from(vec![
+            Span::styled("> ", self.styles.table_header_style),
+            Span::styled(search.query.as_str(), self.styles.text_style),
+            Span::styled(" ", self.styles.selected_text_style),
+        ])];
+
+        if search.query.is_empty() {
+            lines.push(Line::styled(
+                "Search processes, disks, sensors, and network interfaces",
+                self.styles.text_style,
+            ));
+        } else if search.results.is_empty() {
+            lines.push(Line::styled("No matches", self.styles.invalid_query_style));
+        } else {
+            // Scroll so the selected result is always visible.
+            let num_shown = usize::from(height.saturating_sub(3));
+            let start = search.selected.saturating_sub(num_shown.saturating_sub(1));
+
+            lines.extend(
+                search
+                    .results
+                    .iter()
+                    .enumerate()
+                    .skip(start)
+                    .take(num_shown)
+                    .map(|(index, result)| {
+                        let style = if index == search.selected {
+                            self.styles.selected_text_style
+                        } else {
+                            self.styles.text_style
+                        };
+                        let text = format!("{:<KIND_WIDTH$}{}", result.target.kind(), result.label);
+                        Line::styled(truncate_str(&text, inner_width).to_string(), style)
+                    }),
+            );
+        }
+
+        let title = tr_args(" {count} matches ", &[("count", &search.results.len())]);
+        let block = dialog_block(self.styles.border_type)
+            .border_style(self.styles.highlighted_border_style)
+            .title_top(Line::styled(tr(" Search "), self.styles.widget_title_style))
+            .title_top(Line::styled(title, self.styles.widget_title_style).right_aligned());
+
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
@@ -1,118 +1,323 @@
-use std::cmp::{max, min};
+use std::{borrow::Cow, cmp::min};
 
 use tui::{
     layout::{Alignment, Rect},
-    text::{Line, Span},
+    text::Line,
     widgets::{Paragraph, Wrap},
     Frame,
 };
 use unicode_width::UnicodeWidthStr;
 
 use crate::{
-    app::App,
+    app::{layout_manager::BottomWidgetType, App},
     canvas::{drawing_utils::dialog_block, Painter},
-    constants::{self, HELP_TEXT},
+    constants::HELP_TEXT,
+    event::keybinds::{Action, Key, KeyBindings},
+    i18n::{tr, tr_args},
 };
 
-// TODO: [REFACTOR] Make generic dialog boxes to build off of instead?
-impl Painter {
-    fn help_text_lines(&self) -> Vec<Line<'_>> {
-        let mut styled_help_spans = Vec::new();
-
-        // Init help text:
-        HELP_TEXT.iter().enumerate().for_each(|(itx, section)| {
-            let mut section = section.iter();
-
-            if itx > 0 {
-                if let Some(header) = section.next() {
-                    styled_help_spans.push(Span::default());
-                    styled_help_spans.push(Span::styled(*header, self.styles.table_header_style));
-                }
-            }
-
-            section.for_each(|&text| {
-                styled_help_spans.push(Span::styled(text, self.styles.text_style))
-            });
-        });
-
-        styled_help_spans.into_iter().map(Line::from).collect()
+// The indices of the sections in HELP_TEXT.
+const CONTENTS_SECTION: usize = 0;
+const GENERAL_SECTION: usize = 1;
+const CPU_SECTION: usize = 2;
+const PROCESS_SECTION: usize = 3;
+const SEARCH_SECTION: usize = 4;
+const SORT_SECTION: usize = 5;
+const TEMP_SECTION: usize = 6;
+const DISK_SECTION: usize = 7;
+const BATTERY_SECTION: usize = 8;
+const BASIC_MEM_SECTION: usize = 9;
+
+/// Returns the help section specific to a widget, if it has one.
+fn widget_section(widget_type: &BottomWidgetType) -> Option<usize> {
+    match widget_type {
+        BottomWidgetType::Cpu | BottomWidgetType::CpuLegend => Some(CPU_SECTION),
+        BottomWidgetType::Proc => Some(PROCESS_SECTION),
+        BottomWidgetType::ProcSearch => Some(SEARCH_SECTION),
+        BottomWidgetType::ProcSort => Some(SORT_SECTION),
+        BottomWidgetType::Temp => Some(TEMP_SECTION),
+        BottomWidgetType::Disk => Some(DISK_SECTION),
+        BottomWidgetType::Battery => Some(BATTERY_SECTION),
+        BottomWidgetType::BasicMem => Some(BASIC_MEM_SECTION),
+        _ => None,
+    }
+}
+
+/// Returns the help sections that list a remappable action.
+fn action_sections(action: Action) -> &'static [usize] {
+    match action {
+        Action::Quit
+        | Action::Search
+        | Action::Help
+        | Action::Expand
+        | Action::Freeze
+        | Action::Reset
+        | Action::ZoomIn
+        | Action::ZoomOut
+        | Action::ResetZoom
+        | Action::Inspect
+        | Action::DebugOverlay
+        | Action::LogLevel
+        | Action::Diagnostics
+        | Action::ExportConfig
+        | Action::NextProfile
+        | Action::NextPage
+        | Action::PreviousPage
+        | Action::HideWidget
+        | Action::ShowWidgets
+        | Action::CopyRow => &[GENERAL_SECTION],
+        Action::Kill
+        | Action::TreeToggle
+        | Action::SortMenu
+        | Action::InvertSort
+        | Action::CollapseBranch
+        | Action::CollapseAll
+        | Action::ExpandAll
+        | Action::TreeTotals
+        | Action::GroupByUser
+        | Action::CopyPid
+        | Action::CopyCommand => &[PROCESS_SECTION],
+        Action::PinMount | Action::CycleMount => &[DISK_SECTION],
+        Action::HideSensor | Action::ShowHiddenSensors => &[TEMP_SECTION],
+        Action::Percentages => &[PROCESS_SECTION, BASIC_MEM_SECTION],
+        Action::IgnoreCase | Action::WholeWord | Action::Regex => &[SEARCH_SECTION],
+    }
+}
+
+/// Describes an action along with the keys currently bound to it.
+fn binding_line(action: Action, keys: &[Key]) -> String {
+    let keys = if keys.is_empty() {
+        tr("(unbound)").to_string()
+    } else {
+        keys.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    format!("{keys:<16} {}", tr(action.description()))
+}
+
+/// A line of the help menu.
+#[derive(Debug, PartialEq, Eq)]
+struct HelpLine {
+    text: Cow<'static, str>,
+    is_header: bool,
+}
+
+impl HelpLine {
+    fn new(text: impl Into<Cow<'static, str>>, is_header: bool) -> Self {
+        Self {
+            text: text.into(),
+            is_header,
+        }
     }
+}
+
+/// The lines of the help menu, along with the line each shown section starts
+/// on.
+struct HelpMenu {
+    lines: Vec<HelpLine>,
+    section_starts: Vec<Option<usize>>,
+}
+
+/// Builds the help menu. The section for the selected widget comes first, and
+/// remappable actions are listed with the keys they are currently bound to.
+///
+/// If there is a search query, only lines containing it are kept, along with
+/// their section headers. A matching header keeps its entire section.
+fn help_menu(
+    key_bindings: &KeyBindings, selected_widget: &BottomWidgetType, query: &str,
+) -> HelpMenu {
+    let query = query.to_lowercase();
+    let matches = |text: &str| text.to_lowercase().contains(&query);
 
+    let first_section = widget_section(selected_widget);
+    let mut order = vec![];
+    if query.is_empty() {
+        order.push(CONTENTS_SECTION);
+    }
+    order.extend(first_section);
+    order.extend(
+        (GENERAL_SECTION..HELP_TEXT.len()).filter(|&section| Some(section) != first_section),
+    );
+
+    let mut menu = HelpMenu {
+        lines: vec![],
+        section_starts: vec![None; HELP_TEXT.len()],
+    };
+
+    for section in order {
+        if section == CONTENTS_SECTION {
+            menu.section_starts[section] = Some(0);
+            menu.lines.extend(
+                HELP_TEXT[section]
+                    .iter()
+                    .map(|&text| HelpLine::new(tr(text), false)),
+            );
+            continue;
+        }
+
+        let header = tr(HELP_TEXT[section][0]);
+        let bindings = key_bindings
+            .iter()
+            .filter(|(action, _)| action_sections(*action).contains(&section))
+            .map(|(action, keys)| Cow::Owned(binding_line(action, keys)));
+        let body = HELP_TEXT[section][1..]
+            .iter()
+            .map(|&text| Cow::Borrowed(tr(text)))
+            .chain(bindings)
+            .filter(|text| query.is_empty() || matches(header) || matches(text.as_ref()))
+            .collect::<Vec<_>>();
+
+        if body.is_empty() && !(query.is_empty() || matches(header)) {
+            continue;
+        }
+
+        if !menu.lines.is_empty() {
+            menu.lines.push(HelpLine::new("", false));
+        }
+        menu.section_starts[section] = Some(menu.lines.len());
+        menu.lines.push(HelpLine::new(header, true));
+        menu.lines
+            .extend(body.into_iter().map(|text| HelpLine::new(text, false)));
+    }
+
+    menu
+}
+
+// TODO: [REFACTOR] Make generic dialog boxes to build off of instead?
+impl Painter {
     pub fn draw_help_dialog(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect) {
-        let styled_help_text = self.help_text_lines();
+        let help_state = &app_state.help_dialog_state;
+        let menu = help_menu(
+            &app_state.app_config_fields.key_bindings,
+            &app_state.current_widget.widget_type,
+            &help_state.query,
+        );
 
-        let block = dialog_block(self.styles.border_type)
+        let mut block = dialog_block(self.styles.border_type)
             .border_style(self.styles.border_style)
-            .title_top(Line::styled(" Help ", self.styles.widget_title_style))
+            .title_top(Line::styled(tr(" Help "), self.styles.widget_title_style))
             .title_top(
-                Line::styled(" Esc to close ", self.styles.widget_title_style).right_aligned(),
+                Line::styled(tr(" Esc to close "), self.styles.widget_title_style).right_aligned(),
             );
+        if help_state.is_searching || !help_state.query.is_empty() {
+            let cursor = if help_state.is_searching { "_" } else { "" };
+            block = block.title_top(Line::styled(
+                tr_args(
+                    " Search: {query}{cursor} ",
+                    &[("query", &help_state.query), ("cursor", &cursor)],
+                ),
+                self.styles.widget_title_style,
+            ));
+        }
 
-        if app_state.should_get_widget_bounds() {
-            // We must also recalculate how many lines are wrapping to properly get
-            // scrolling to work on small terminal sizes... oh joy.
-
-            app_state.help_dialog_state.height = block.inner(draw_loc).height;
-
-            let mut overflow_buffer = 0;
-            let paragraph_width = max(draw_loc.width.saturating_sub(2), 1);
-            let mut prev_section_len = 0;
+        // Lines may wrap on small terminals, so count the rows they take up to
+        // know where each section starts and how far we can scroll.
+        let paragraph_width = usize::from(draw_loc.width.saturating_sub(2).max(1));
+        let mut rows_before = Vec::with_capacity(menu.lines.len());
+        let mut num_rows: u16 = 0;
+        for line in &menu.lines {
+            rows_before.push(num_rows);
+            num_rows += 1 + (line.text.width().saturating_sub(1) / paragraph_width) as u16;
+        }
 
-            constants::HELP_TEXT
-                .iter()
-                .enumerate()
-                .for_each(|(itx, section)| {
-                    let mut buffer = 0;
+        let height = block.inner(draw_loc).height;
+        let help_state = &mut app_state.help_dialog_state;
+        help_state.height = height;
+        for (shortcut, start) in help_state
+            .index_shortcuts
+            .iter_mut()
+            .zip(&menu.section_starts)
+        {
+            *shortcut = start.map_or(0, |start| rows_before[start]);
+        }
 
-                    if itx == 0 {
-                        section.iter().for_each(|text_line| {
-                            buffer += UnicodeWidthStr::width(*text_line).saturating_sub(1) as u16
-                                / paragraph_width;
-                        });
+        let scroll_state = &mut help_state.scroll_state;
+        scroll_state.max_scroll_index = num_rows.saturating_sub(height);
+        scroll_state.current_scroll_index = min(
+            scroll_state.current_scroll_index,
+            scroll_state.max_scroll_index,
+        );
 
-                        app_state.help_dialog_state.index_shortcuts[itx] = 0;
+        let lines: Vec<Line<'_>> = if menu.lines.is_empty() {
+            vec![Line::styled("No matches", self.styles.invalid_query_style)]
+        } else {
+            menu.lines
+                .into_iter()
+                .map(|line| {
+                    let style = if line.is_header {
+                        self.styles.table_header_style
                     } else {
-                        section.iter().for_each(|text_line| {
-                            buffer += UnicodeWidthStr::width(*text_line).saturating_sub(1) as u16
-                                / paragraph_width;
-                        });
-
-                        app_state.help_dialog_state.index_shortcuts[itx] =
-                            app_state.help_dialog_state.index_shortcuts[itx - 1]
-                                + 1
-                                + prev_section_len;
-                    }
-                    prev_section_len = section.len() as u16 + buffer;
-                    overflow_buffer += buffer;
-                });
-
-            let max_scroll_index = &mut app_state.help_dialog_state.scroll_state.max_scroll_index;
-            *max_scroll_index = (styled_help_text.len() as u16 + 3 + overflow_buffer)
-                .saturating_sub(draw_loc.height + 1);
-
-            // Fix the scroll index if it is over-scrolled
-            let index = &mut app_state
-                .help_dialog_state
-                .scroll_state
-                .current_scroll_index;
-
-            *index = min(*index, *max_scroll_index);
-        }
+                        self.styles.text_style
+                    };
+                    Line::styled(line.text, style)
+                })
+                .collect()
+        };
 
         f.render_widget(
-            Paragraph::new(styled_help_text.clone())
+            Paragraph::new(lines)
                 .block(block)
                 .style(self.styles.text_style)
                 .alignment(Alignment::Left)
                 .wrap(Wrap { trim: true })
-                .scroll((
-                    app_state
-                        .help_dialog_state
-                        .scroll_state
-                        .current_scroll_index,
-                    0,
-                )),
+                .scroll((scroll_state.current_scroll_index, 0)),
             draw_loc,
         );
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn texts(menu: &HelpMenu) -> Vec<&str> {
+        menu.lines.iter().map(|line| line.text.as_ref()).collect()
+    }
+
+    #[test]
+    fn selected_widget_section_first() {
+        let menu = help_menu(&KeyBindings::default(), &BottomWidgetType::Disk, "");
+
+        assert_eq!(menu.section_starts[CONTENTS_SECTION], Some(0));
+        let disk_start = menu.section_starts[DISK_SECTION].unwrap();
+        let general_start = menu.section_starts[GENERAL_SECTION].unwrap();
+        assert!(disk_start < general_start);
+        assert_eq!(
+            menu.lines[disk_start],
+            HelpLine::new("7 - Disk widget", true)
+        );
+    }
+
+    #[test]
+    fn remapped_keys_are_shown() {
+        let kill = vec!["ctrl+k".to_string()];
+        let bindings = KeyBindings::new([(Action::Kill, kill.as_slice())]).unwrap();
+        let menu = help_menu(&bindings, &BottomWidgetType::Proc, "");
+
+        let kill_line = binding_line(Action::Kill, &["ctrl+k".parse().unwrap()]);
+        assert!(texts(&menu).contains(&kill_line.as_str()));
+        assert!(!texts(&menu).iter().any(|text| text.starts_with("F9 ")));
+    }
+
+    #[test]
+    fn search_filters_lines() {
+        let menu = help_menu(&KeyBindings::default(), &BottomWidgetType::Proc, "TREE");
+
+        assert_eq!(menu.section_starts[CONTENTS_SECTION], None);
+        assert_eq!(menu.section_starts[DISK_SECTION], None);
+        assert!(menu.section_starts[PROCESS_SECTION].is_some());
+        assert!(menu.lines.iter().all(|line| line.is_header
+            || line.text.is_empty()
+            || line.text.to_lowercase().contains("tree")));
+
+        let menu = help_menu(
+            &KeyBindings::default(),
+            &BottomWidgetType::Proc,
+            "nothing matches",
+        );
+        assert!(menu.lines.is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Wrap},
+    Frame,
+};
+use unicode_ellipsis::truncate_str;
+
+#[cfg(target_os = "linux")]
+use crate::data_collection::processes::linux::security::capability_names;
+use crate::{
+    app::{frozen_state::FrozenState, App, ContextMenu, ContextMenuAction},
+    canvas::{drawing_utils::dialog_block, Painter},
+    data_collection::processes::Pid,
+    data_conversion::{binary_byte_string, dec_bytes_per_second_string, dec_bytes_string},
+    i18n::tr,
+    widgets::format_time,
+};
+
+/// The widest the process details popup gets.
+const DETAILS_MAX_WIDTH: u16 = 80;
+
+impl Painter {
+    /// Draws a process's context menu where it was opened, moved back within
+    /// `draw_loc` if it would go past it.
+    pub fn draw_context_menu(&self, f: &mut Frame<'_>, menu: &mut ContextMenu, draw_loc: Rect) {
+        let mut lines: Vec<Line<'_>> = ContextMenuAction::ALL
+            .iter()
+            .enumerate()
+            .map(|(index, action)| {
+                let style = if index == menu.selected {
+                    self.styles.selected_text_style
+                } else {
+                    self.styles.text_style
+                };
+                Line::styled(format!(" {} ", action.label()), style)
+            })
+            .collect();
+        if let Some(error) = &menu.error {
+            lines.push(Line::styled(
+                format!(" {error} "),
+                self.styles.invalid_query_style,
+            ));
+        }
+
+        let content_width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+        let width = (content_width + 2).min(draw_loc.width);
+        let height = (lines.len() as u16 + 2).min(draw_loc.height);
+        let x = menu.x.clamp(draw_loc.left(), draw_loc.right() - width);
+        let y = menu.y.clamp(draw_loc.top(), draw_loc.bottom() - height);
+        let area = Rect::new(x, y, width, height);
+        menu.area = Some(area);
+
+        let title = format!(" {} ", menu.name);
+        let title = truncate_str(&title, usize::from(width.saturating_sub(2))).to_string();
+        let block = dialog_block(self.styles.border_type)
+            .border_style(self.styles.highlighted_border_style)
+            .title_top(Line::styled(title, self.styles.widget_title_style));
+
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    /// Draws the details of a process in the middle of `draw_loc`.
+    pub fn draw_process_details(
+        &self, f: &mut Frame<'_>, app_state: &App, pid: Pid, draw_loc: Rect,
+    ) {
+        let data = match &app_state.frozen_state {
+            FrozenState::NotFrozen => &app_state.data_collection,
+            FrozenState::Frozen(data) => data,
+        };
+
+        let lines = match data.process_data.process_harvest.get(&pid) {
+            Some(process) => {
+                let parent_pid = process
+                    .parent_pid
+                    .map_or_else(|| "N/A".to_string(), |parent_pid| parent_pid.to_string());
+                let mut fields = vec![
+                    ("PID", process.pid.to_string()),
+                    ("Parent PID", parent_pid),
+                    ("Name", process.name.to_string()),
+                    ("User", process.user.to_string()),
+                    ("State", process.process_state.0.to_string()),
+                    ("CPU", format!("{:.1}%", process.cpu_usage_percent)),
+                    (
+                        "Memory",
+                        format!(
+                            "{} ({:.1}%)",
+                            binary_byte_string(process.mem_usage_bytes),
+                            process.mem_usage_percent
+                        ),
+                    ),
+                    (
+                        "Read",
+                        format!(
+                            "{} (total {})",
+                            dec_bytes_per_second_string(process.read_bytes_per_sec),
+                            dec_bytes_string(process.total_read_bytes)
+                        ),
+                    ),
+                    (
+                        "Write",
+                        format!(
+                            "{} (total {})",
+                            dec_bytes_per_second_string(process.write_bytes_per_sec),
+                            dec_bytes_string(process.total_write_bytes)
+                        ),
+                    ),
+                    ("Time", format_time(process.time)),
+                ];
+
+                // These are only collected for the process whose details are
+                // shown, as they'd otherwise be read for every process.
+                #[cfg(target_os = "linux")]
+                {
+                    let security = process.security.clone().unwrap_or_default();
+                    fields.extend([
+                        (
+                            "I/O Priority",
+                            process
+                                .io_priority
+                                .map_or_else(|| "N/A".to_string(), |priority| priority.to_string()),
+                        ),
+                        (
+                            "Capabilities",
+                            security
+                                .capabilities
+                                .map_or_else(|| "N/A".to_string(), capability_names),
+                        ),
+                        (
+                            "Seccomp",
+                            security
+                                .seccomp
+                                .map_or_else(|| "N/A".to_string(), |seccomp| seccomp.to_string()),
+                        ),
+                        (
+                            "Label",
+                            process.label.as_deref().unwrap_or("N/A").to_string(),
+                        ),
+                    ]);
+                }
+
+                fields.push(("Command", process.command.to_string()));
+
+                fields
+                    .into_iter()
+                    .map(|(label, value)| {
+                        Line::from(vec![
+                            Span::styled(format!("{label:<13}"), self.styles.table_header_style),
+                            Span::styled(value, self.styles.text_style),
+                        ])
+                    })
+                    .collect::<Vec<_>>()
+            }
+            None => vec![Line::styled(
+                "This process no longer exists.",
+                self.styles.text_style,
+            )],
+        };
+
+        // Long lines, usually just the command, are wrapped.
+        let width = DETAILS_MAX_WIDTH.min(draw_loc.width);
+        let inner_width = usize::from(width.saturating_sub(2).max(1));
+        let num_lines: usize = lines
+            .iter()
+            .map(|line| line.width().max(1).div_ceil(inner_width))
+            .sum();
+        let height = (num_lines as u16 + 2).min(draw_loc.height);
+        let area = Rect::new(
+            draw_loc.x + (draw_loc.width - width) / 2,
+            draw_loc.y + (draw_loc.height - height) / 2,
+            width,
+            height,
+        );
+
+        let block = dialog_block(self.styles.border_type)
+            .border_style(self.styles.border_style)
+            .title_top(Line::styled(
+                tr(" Process Details "),
+                self.styles.widget_title_style,
+            ))
+            .title_top(
+                Line::styled(tr(" Esc to close "), self.styles.widget_title_style).right_aligned(),
+            );
+
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+}
@@ -2,6 +2,7 @@ use std::time::Instant;
 
 use tui::{
     layout::Rect,
+    style::Color,
     widgets::{Block, BorderType, Borders},
 };
 
@@ -51,6 +52,70 @@ pub fn dialog_block(border_type: BorderType) -> Block<'static> {
         .borders(Borders::all())
 }
 
+/// Whether the terminal supports 24-bit colour, given the value of the
+/// `COLORTERM` environment variable.
+pub fn supports_truecolor(colorterm: Option<&str>) -> bool {
+    matches!(colorterm, Some("truecolor" | "24bit"))
+}
+
+/// Converts a colour to RGB, using the usual xterm values for named and
+/// indexed colours.
+pub fn colour_to_rgb(colour: Color) -> Option<(u8, u8, u8)> {
+    const ANSI: [(u8, u8, u8); 16] = [
+        (0x00, 0x00, 0x00),
+        (0xcd, 0x00, 0x00),
+        (0x00, 0xcd, 0x00),
+        (0xcd, 0xcd, 0x00),
+        (0x00, 0x00, 0xee),
+        (0xcd, 0x00, 0xcd),
+        (0x00, 0xcd, 0xcd),
+        (0xe5, 0xe5, 0xe5),
+        (0x7f, 0x7f, 0x7f),
+        (0xff, 0x00, 0x00),
+        (0x00, 0xff, 0x00),
+        (0xff, 0xff, 0x00),
+        (0x5c, 0x5c, 0xff),
+        (0xff, 0x00, 0xff),
+        (0x00, 0xff, 0xff),
+        (0xff, 0xff, 0xff),
+    ];
+
+    let index = match colour {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some((r, g, b)),
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        Color::Indexed(index) => index,
+    };
+
+    Some(match index {
+        0..=15 => ANSI[usize::from(index)],
+        16..=231 => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let index = index - 16;
+            (level(index / 36), level(index / 6 % 6), level(index % 6))
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    })
+}
+
 #[cfg(test)]
 mod test {
 
@@ -91,4 +156,22 @@ mod test {
         ));
         assert!(over_timer.is_none());
     }
+
+    #[test]
+    fn test_supports_truecolor() {
+        assert!(supports_truecolor(Some("truecolor")));
+        assert!(supports_truecolor(Some("24bit")));
+        assert!(!supports_truecolor(Some("")));
+        assert!(!supports_truecolor(None));
+    }
+
+    #[test]
+    fn test_colour_to_rgb() {
+        assert_eq!(colour_to_rgb(Color::Reset), None);
+        assert_eq!(colour_to_rgb(Color::Rgb(1, 2, 3)), Some((1, 2, 3)));
+        assert_eq!(colour_to_rgb(Color::LightBlue), Some((0x5c, 0x5c, 0xff)));
+        assert_eq!(colour_to_rgb(Color::Indexed(16)), Some((0, 0, 0)));
+        assert_eq!(colour_to_rgb(Color::Indexed(196)), Some((255, 0, 0)));
+        assert_eq!(colour_to_rgb(Color::Indexed(255)), Some((238, 238, 238)));
+    }
 }
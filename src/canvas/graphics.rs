@@ -0,0 +1,574 @@
+//! Drawing time graphs as images, for terminals that support the kitty
+//! graphics protocol or Sixel.
+//!
+//! Graphs are still laid out and drawn as usual, just without their data
+//! points. The points are instead drawn into an image that is written over the
+//! graph's area once the frame has been drawn.
+
+use std::{
+    cell::RefCell,
+    io::{self, Write},
+};
+
+use tui::{
+    backend::Backend,
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    style::Modifier,
+};
+
+use super::{
+    components::{
+        time_chart::Point,
+        time_graph::{GraphData, TimeGraph},
+    },
+    drawing_utils::colour_to_rgb,
+};
+use crate::utils::strings::base64;
+
+/// The largest amount of data sent in a single kitty graphics escape code.
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// The colour to draw a series in if it doesn't have one.
+const DEFAULT_COLOUR: (u8, u8, u8) = (0xe5, 0xe5, 0xe5);
+
+/// A graphics protocol that a terminal supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GraphicsProtocol {
+    Kitty,
+    Sixel,
+}
+
+impl GraphicsProtocol {
+    /// Guesses the graphics protocol supported by the current terminal, if
+    /// any.
+    fn detect() -> Option<Self> {
+        let var = |name| std::env::var(name).ok();
+
+        Self::from_env(
+            var("TERM").as_deref(),
+            var("TERM_PROGRAM").as_deref(),
+            var("KITTY_WINDOW_ID").is_some(),
+            var("TMUX").is_some(),
+        )
+    }
+
+    /// Guesses the graphics protocol supported by a terminal from its
+    /// environment variables.
+    fn from_env(
+        term: Option<&str>, term_program: Option<&str>, is_kitty: bool, is_tmux: bool,
+    ) -> Option<Self> {
+        // tmux doesn't pass images through without extra configuration.
+        if is_tmux {
+            None
+        } else if is_kitty
+            || matches!(term, Some("xterm-kitty" | "xterm-ghostty"))
+            || matches!(term_program, Some("WezTerm" | "ghostty"))
+        {
+            Some(GraphicsProtocol::Kitty)
+        } else if term.is_some_and(|term| {
+            term.starts_with("foot") || term.starts_with("mlterm") || term.contains("sixel")
+        }) {
+            Some(GraphicsProtocol::Sixel)
+        } else {
+            None
+        }
+    }
+}
+
+/// Draws time graphs as images.
+pub(crate) struct Graphics {
+    protocol: GraphicsProtocol,
+
+    /// The size of a cell in pixels, or [`None`] if the terminal doesn't
+    /// report it, in which case graphs are drawn with cells instead.
+    cell_size: Option<(u16, u16)>,
+
+    /// The images to write once the current frame has been drawn.
+    images: RefCell<Vec<GraphImage>>,
+
+    /// The cells to redraw over the images. Only used for Sixel, as its images
+    /// are drawn over any text.
+    overlay: RefCell<Vec<(u16, u16, Cell)>>,
+
+    /// The IDs and areas of the images written for the last frame.
+    shown: Vec<(u32, Rect)>,
+}
+
+impl Graphics {
+    /// Returns a [`Graphics`] if the current terminal seems to support a
+    /// graphics protocol.
+    pub(crate) fn detect() -> Option<Self> {
+        GraphicsProtocol::detect().map(|protocol| Graphics {
+            protocol,
+            cell_size: None,
+            images: RefCell::default(),
+            overlay: RefCell::default(),
+            shown: Vec::default(),
+        })
+    }
+
+    /// Prepares for a new frame.
+    pub(crate) fn start_frame(&mut self) {
+        self.cell_size = crossterm::terminal::window_size()
+            .ok()
+            .filter(|size| size.columns > 0 && size.rows > 0)
+            .map(|size| (size.width / size.columns, size.height / size.rows))
+            .filter(|&(width, height)| width > 0 && height > 0);
+
+        self.images.get_mut().clear();
+        self.overlay.get_mut().clear();
+    }
+
+    /// Whether graphs can be drawn as images this frame.
+    pub(crate) fn is_available(&self) -> bool {
+        self.cell_size.is_some()
+    }
+
+    /// Draws the data points of `graph` in `graph_area` as an image, to be
+    /// written once the frame has been drawn.
+    pub(crate) fn queue(
+        &self, widget_id: u64, graph_area: Rect, graph: &TimeGraph<'_>,
+        graph_data: &[GraphData<'_>],
+    ) {
+        let Some(cell_size) = self.cell_size else {
+            return;
+        };
+
+        if !graph_area.is_empty() {
+            let id = u32::try_from(widget_id + 1).unwrap_or(u32::MAX);
+            self.images.borrow_mut().push(GraphImage::draw(
+                id, graph_area, cell_size, graph, graph_data,
+            ));
+        }
+    }
+
+    /// Records the cells that have to be redrawn over the images from the
+    /// finished frame.
+    pub(crate) fn capture(&self, buf: &Buffer) {
+        if self.protocol != GraphicsProtocol::Sixel {
+            return;
+        }
+
+        let images = self.images.borrow();
+        let areas = images
+            .iter()
+            .map(|image| image.area)
+            .chain(self.shown.iter().map(|(_, area)| *area));
+
+        let mut overlay = self.overlay.borrow_mut();
+        for area in areas {
+            let area = area.intersection(buf.area);
+            for y in area.top()..area.bottom() {
+                for x in area.left()..area.right() {
+                    if let Some(cell) = buf.cell((x, y)) {
+                        if cell.symbol() != " " || cell.modifier.contains(Modifier::REVERSED) {
+                            overlay.push((x, y, cell.clone()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Writes the images for the frame that was just drawn, and removes the
+    /// ones from the last frame.
+    pub(crate) fn write<B: Backend + Write>(&mut self, backend: &mut B) -> io::Result<()> {
+        let images = self.images.take();
+        if images.is_empty() && self.shown.is_empty() {
+            return Ok(());
+        }
+
+        match self.protocol {
+            GraphicsProtocol::Kitty => {
+                for (id, _) in &self.shown {
+                    write!(backend, "\x1b_Ga=d,d=I,i={id},q=2\x1b\\")?;
+                }
+
+                for image in &images {
+                    move_to(backend, image.area.left(), image.area.top())?;
+                    backend.write_all(image.kitty().as_bytes())?;
+                }
+            }
+            GraphicsProtocol::Sixel => {
+                // Sixel images can't be removed, so clear their areas instead.
+                let areas = self
+                    .shown
+                    .iter()
+                    .map(|(_, area)| *area)
+                    .chain(images.iter().map(|image| image.area));
+                for area in areas {
+                    for y in area.top()..area.bottom() {
+                        move_to(backend, area.left(), y)?;
+                        write!(backend, "\x1b[{}X", area.width)?;
+                    }
+                }
+
+                for image in &images {
+                    move_to(backend, image.area.left(), image.area.top())?;
+                    backend.write_all(image.sixel().as_bytes())?;
+                }
+
+                let overlay = self.overlay.take();
+                backend.draw(overlay.iter().map(|(x, y, cell)| (*x, *y, cell)))?;
+            }
+        }
+
+        self.shown = images.iter().map(|image| (image.id, image.area)).collect();
+
+        Write::flush(backend)
+    }
+}
+
+/// Moves the cursor to a cell.
+fn move_to(writer: &mut impl Write, x: u16, y: u16) -> io::Result<()> {
+    write!(writer, "\x1b[{};{}H", u32::from(y) + 1, u32::from(x) + 1)
+}
+
+/// The data points of a graph, drawn as an image.
+struct GraphImage {
+    id: u32,
+    area: Rect,
+    width: usize,
+    height: usize,
+
+    /// The colour of each pixel, row by row, or [`None`] if it is transparent.
+    pixels: Vec<Option<(u8, u8, u8)>>,
+}
+
+impl GraphImage {
+    /// Draws the data points of `graph` to fill `area`.
+    fn draw(
+        id: u32, area: Rect, cell_size: (u16, u16), graph: &TimeGraph<'_>,
+        graph_data: &[GraphData<'_>],
+    ) -> Self {
+        let width = usize::from(area.width) * usize::from(cell_size.0);
+        let height = usize::from(area.height) * usize::from(cell_size.1);
+        let mut image = GraphImage {
+            id,
+            area,
+            width,
+            height,
+            pixels: vec![None; width * height],
+        };
+
+        // Like the cell-based graph, the time bounds are negated so that the
+        // latest data is on the right.
+        let left = -(graph.x_bounds[1] as f64);
+        let right = -(graph.x_bounds[0] as f64);
+        let [bottom, top] = graph.y_bounds;
+        if width == 0 || height == 0 || right <= left || top <= bottom {
+            return image;
+        }
+
+        let to_pixel = |(x, y): Point| {
+            (
+                (x - left) / (right - left) * (width - 1) as f64,
+                (top - y) / (top - bottom) * (height - 1) as f64,
+            )
+        };
+        let thickness = (usize::from(cell_size.1) / 8).max(1);
+
+        for data in graph_data {
+            let colour = data
+                .style
+                .fg
+                .or(graph.graph_style.fg)
+                .and_then(colour_to_rgb)
+                .unwrap_or(DEFAULT_COLOUR);
+
+            if let [point] = data.points {
+                image.draw_line(to_pixel(*point), to_pixel(*point), colour, thickness);
+            }
+
+            for pair in data.points.windows(2) {
+                let (from, to) = (pair[0], pair[1]);
+                if (from.0 < left && to.0 < left) || (from.0 > right && to.0 > right) {
+                    continue;
+                }
+
+                image.draw_line(to_pixel(from), to_pixel(to), colour, thickness);
+            }
+        }
+
+        image
+    }
+
+    /// Draws a line between two pixels with Bresenham's line algorithm.
+    fn draw_line(
+        &mut self, from: (f64, f64), to: (f64, f64), colour: (u8, u8, u8), thickness: usize,
+    ) {
+        let (mut x, mut y) = (from.0.round() as i64, from.1.round() as i64);
+        let (end_x, end_y) = (to.0.round() as i64, to.1.round() as i64);
+
+        let dx = (end_x - x).abs();
+        let dy = -(end_y - y).abs();
+        let step_x = if x < end_x { 1 } else { -1 };
+        let step_y = if y < end_y { 1 } else { -1 };
+        let mut error = dx + dy;
+
+        loop {
+            self.plot(x, y, colour, thickness);
+
+            if x == end_x && y == end_y {
+                break;
+            }
+
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Colours a square of pixels centred on a pixel, ignoring any that are out
+    /// of bounds.
+    fn plot(&mut self, x: i64, y: i64, colour: (u8, u8, u8), thickness: usize) {
+        let thickness = thickness as i64;
+        let offset = thickness / 2;
+
+        for pixel_y in (y - offset)..(y - offset + thickness) {
+            for pixel_x in (x - offset)..(x - offset + thickness) {
+                if let (Ok(pixel_x), Ok(pixel_y)) =
+                    (usize::try_from(pixel_x), usize::try_from(pixel_y))
+                {
+                    if pixel_x < self.width && pixel_y < self.height {
+                        self.pixels[pixel_y * self.width + pixel_x] = Some(colour);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Encodes the image with the kitty graphics protocol. The image is placed
+    /// under any text, and scaled to fill its area.
+    fn kitty(&self) -> String {
+        let rgba = self
+            .pixels
+            .iter()
+            .flat_map(|pixel| match pixel {
+                Some((r, g, b)) => [*r, *g, *b, 255],
+                None => [0, 0, 0, 0],
+            })
+            .collect::<Vec<_>>();
+        let payload = base64(&rgba);
+
+        let mut encoded = String::with_capacity(payload.len() + payload.len() / 64);
+        let chunk_count = payload.len().div_ceil(KITTY_CHUNK_SIZE).max(1);
+
+        for chunk in 0..chunk_count {
+            let start = chunk * KITTY_CHUNK_SIZE;
+            let end = (start + KITTY_CHUNK_SIZE).min(payload.len());
+            let more = u8::from(chunk + 1 < chunk_count);
+
+            if chunk == 0 {
+                encoded.push_str(&format!(
+                    "\x1b_Ga=T,f=32,s={},v={},i={},c={},r={},C=1,z=-1,q=2,m={more};",
+                    self.width, self.height, self.id, self.area.width, self.area.height
+                ));
+            } else {
+                encoded.push_str(&format!("\x1b_Gm={more};"));
+            }
+
+            encoded.push_str(&payload[start..end]);
+            encoded.push_str("\x1b\\");
+        }
+
+        encoded
+    }
+
+    /// Encodes the image as Sixel, with transparent pixels left undrawn.
+    fn sixel(&self) -> String {
+        let mut palette: Vec<(u8, u8, u8)> = Vec::new();
+        let mut indices = Vec::with_capacity(self.pixels.len());
+        for pixel in &self.pixels {
+            indices.push(pixel.map(|colour| {
+                palette
+                    .iter()
+                    .position(|existing| *existing == colour)
+                    .unwrap_or_else(|| {
+                        palette.push(colour);
+                        palette.len() - 1
+                    })
+            }));
+        }
+
+        let mut encoded = format!("\x1bP0;1;0q\"1;1;{};{}", self.width, self.height);
+
+        // Sixel colours are percentages rather than bytes.
+        let percent = |value: u8| u32::from(value) * 100 / 255;
+        for (index, (r, g, b)) in palette.iter().enumerate() {
+            encoded.push_str(&format!(
+                "#{index};2;{};{};{}",
+                percent(*r),
+                percent(*g),
+                percent(*b)
+            ));
+        }
+
+        // Each band is six pixels high, and drawn once per colour.
+        for band in (0..self.height).step_by(6) {
+            for colour in 0..palette.len() {
+                let sixels = (0..self.width)
+                    .map(|x| {
+                        (0..6)
+                            .filter(|row| {
+                                let y = band + row;
+                                y < self.height && indices[y * self.width + x] == Some(colour)
+                            })
+                            .fold(0, |bits, row| bits | (1 << row))
+                    })
+                    .collect::<Vec<u8>>();
+
+                if sixels.iter().any(|bits| *bits != 0) {
+                    encoded.push_str(&format!("#{colour}"));
+                    push_sixels(&mut encoded, &sixels);
+                    encoded.push('$');
+                }
+            }
+
+            encoded.push('-');
+        }
+
+        encoded.push_str("\x1b\\");
+        encoded
+    }
+}
+
+/// Appends a row of sixels, run-length encoding repeats and skipping any
+/// trailing empty ones.
+fn push_sixels(encoded: &mut String, sixels: &[u8]) {
+    let end = sixels
+        .iter()
+        .rposition(|bits| *bits != 0)
+        .map_or(0, |index| index + 1);
+    let sixels = &sixels[..end];
+
+    let mut index = 0;
+    while index < sixels.len() {
+        let bits = sixels[index];
+        let run = sixels[index..]
+            .iter()
+            .take_while(|other| **other == bits)
+            .count();
+        let sixel = char::from(0x3f + bits);
+
+        if run > 3 {
+            encoded.push_str(&format!("!{run}{sixel}"));
+        } else {
+            encoded.extend(std::iter::repeat(sixel).take(run));
+        }
+
+        index += run;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{borrow::Cow, time::SystemTime};
+
+    use tui::{
+        style::{Color, Style},
+        widgets::BorderType,
+    };
+
+    use super::*;
+    use crate::{canvas::components::time_chart::GraphMarker, utils::time_format::TimeFormat};
+
+    const RELATIVE: &TimeFormat = &TimeFormat::Relative;
+
+    fn graph() -> TimeGraph<'static> {
+        TimeGraph {
+            x_bounds: [0, 3],
+            hide_x_labels: true,
+            time_format: RELATIVE,
+            latest_time: SystemTime::UNIX_EPOCH,
+            y_bounds: [0.0, 3.0],
+            y_labels: &[],
+            graph_style: Style::default(),
+            border_style: Style::default(),
+            border_type: BorderType::Plain,
+            title: Cow::Borrowed(""),
+            is_selected: false,
+            is_expanded: false,
+            title_style: Style::default(),
+            legend_position: None,
+            legend_constraints: None,
+            marker: GraphMarker::default(),
+            selection: None,
+            cursor: None,
+            gradient: false,
+        }
+    }
+
+    #[test]
+    fn graphics_protocols() {
+        assert_eq!(
+            GraphicsProtocol::from_env(Some("xterm-256color"), None, true, false),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            GraphicsProtocol::from_env(Some("xterm-kitty"), None, false, false),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            GraphicsProtocol::from_env(Some("xterm-256color"), Some("WezTerm"), false, false),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            GraphicsProtocol::from_env(Some("foot"), None, false, false),
+            Some(GraphicsProtocol::Sixel)
+        );
+        assert_eq!(
+            GraphicsProtocol::from_env(Some("xterm-256color"), None, false, false),
+            None
+        );
+        assert_eq!(
+            GraphicsProtocol::from_env(Some("xterm-kitty"), None, true, true),
+            None
+        );
+        assert_eq!(GraphicsProtocol::from_env(None, None, false, false), None);
+    }
+
+    #[test]
+    fn graphics_images() {
+        let points = [(-3.0, 0.0), (0.0, 3.0)];
+        let data = [GraphData {
+            points: &points,
+            style: Style::default().fg(Color::Rgb(255, 0, 0)),
+            name: None,
+            symbol: None,
+        }];
+
+        // A 4x4 image, with a diagonal line from the bottom left to the top right.
+        let image = GraphImage::draw(1, Rect::new(0, 0, 2, 2), (2, 2), &graph(), &data);
+        let red = Some((255, 0, 0));
+        assert_eq!(
+            image.pixels.iter().filter(|pixel| pixel.is_some()).count(),
+            4
+        );
+        assert_eq!(image.pixels[3], red);
+        assert_eq!(image.pixels[12], red);
+
+        assert_eq!(
+            image.sixel(),
+            "\x1bP0;1;0q\"1;1;4;4#0;2;100;0;0#0GCA@$-\x1b\\"
+        );
+        assert!(image
+            .kitty()
+            .starts_with("\x1b_Ga=T,f=32,s=4,v=4,i=1,c=2,r=2,C=1,z=-1,q=2,m=0;"));
+        assert!(image.kitty().ends_with("\x1b\\"));
+    }
+
+    #[test]
+    fn graphics_sixel_runs() {
+        let mut encoded = String::new();
+        push_sixels(&mut encoded, &[1, 1, 1, 1, 1, 2, 0, 0]);
+        assert_eq!(encoded, "!5@A");
+    }
+}
@@ -0,0 +1,435 @@
+//! Detecting what the terminal can show, so that limited terminals like serial
+//! consoles and CI logs get plain output rather than garbled escape codes and
+//! symbols.
+//!
+//! This is a best guess from `TERM`, `COLORTERM`, the locale, and the terminfo
+//! database, which can be overridden with `--color_depth` and `--ascii`.
+
+use std::str::FromStr;
+
+use tui::{buffer::Buffer, style::Color};
+
+use super::drawing_utils::{colour_to_rgb, supports_truecolor};
+
+/// How many colours the terminal can show.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColourDepth {
+    /// The 16 basic ANSI colours.
+    Basic,
+    /// The 256 xterm colours. This is the **default**, as other colours are
+    /// usually shown fine even if the terminal doesn't say it supports them.
+    #[default]
+    Indexed,
+    /// Any 24-bit colour.
+    TrueColour,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseColourDepthError;
+
+impl FromStr for ColourDepth {
+    type Err = ParseColourDepthError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "16" => Ok(Self::Basic),
+            "256" => Ok(Self::Indexed),
+            "truecolor" | "truecolour" | "24bit" => Ok(Self::TrueColour),
+            _ => Err(ParseColourDepthError),
+        }
+    }
+}
+
+/// What the terminal supports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TerminalFeatures {
+    pub colour_depth: ColourDepth,
+    /// Whether box-drawing characters, Braille, and other symbols can be
+    /// shown, or only ASCII.
+    pub unicode: bool,
+    /// Whether the terminal reports mouse events.
+    pub mouse: bool,
+}
+
+impl Default for TerminalFeatures {
+    fn default() -> Self {
+        Self {
+            colour_depth: ColourDepth::default(),
+            unicode: true,
+            mouse: true,
+        }
+    }
+}
+
+impl TerminalFeatures {
+    /// Detects what the terminal supports from the environment.
+    pub fn detect() -> Self {
+        // Keep tests from depending on the terminal they're run in.
+        if cfg!(test) {
+            return Self::default();
+        }
+
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let term = var("TERM");
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"].into_iter().find_map(var);
+        let terminfo_colours = term.as_deref().and_then(terminfo_colours);
+
+        Self::from_env(
+            term.as_deref(),
+            var("COLORTERM").as_deref(),
+            locale.as_deref(),
+            terminfo_colours,
+        )
+    }
+
+    /// Works out what the terminal supports from `TERM`, `COLORTERM`, the
+    /// locale, and the number of colours terminfo has for the terminal.
+    fn from_env(
+        term: Option<&str>, colorterm: Option<&str>, locale: Option<&str>,
+        terminfo_colours: Option<u32>,
+    ) -> Self {
+        // Consoles that can't show much besides ASCII. Without `TERM`, like on
+        // Windows, assume a modern terminal.
+        let is_limited = term.is_some_and(|term| {
+            matches!(term, "dumb" | "linux" | "ansi" | "cons25") || term.starts_with("vt")
+        });
+
+        // Plenty of terminals that can show more colours still go by `xterm` or
+        // similar, whose terminfo entries only have 8, so only trust terminfo
+        // for others.
+        let is_xterm_like = term.is_some_and(|term| {
+            ["xterm", "screen", "tmux", "rxvt"]
+                .iter()
+                .any(|prefix| term.starts_with(prefix))
+        });
+
+        let colour_depth = if supports_truecolor(colorterm) {
+            ColourDepth::TrueColour
+        } else if colorterm.is_some() {
+            ColourDepth::Indexed
+        } else if is_limited {
+            ColourDepth::Basic
+        } else if let (false, Some(colours)) = (is_xterm_like, terminfo_colours) {
+            if colours >= 256 {
+                ColourDepth::Indexed
+            } else {
+                ColourDepth::Basic
+            }
+        } else {
+            ColourDepth::Indexed
+        };
+
+        // Only a locale that's set to something other than UTF-8 rules it out.
+        let is_utf8_locale = locale.map_or(true, |locale| {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        });
+
+        Self {
+            colour_depth,
+            unicode: !is_limited && is_utf8_locale,
+            mouse: !is_limited,
+        }
+    }
+}
+
+/// Returns how many colours the terminfo entry for `term` says it has, if it
+/// can be found.
+#[cfg(unix)]
+fn terminfo_colours(term: &str) -> Option<u32> {
+    use std::{env, path::PathBuf};
+
+    let first = term.chars().next()?;
+
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Some(dir) = env::var_os("TERMINFO") {
+        dirs.push(dir.into());
+    }
+    if let Some(home) = env::var_os("HOME") {
+        dirs.push(PathBuf::from(home).join(".terminfo"));
+    }
+    if let Some(terminfo_dirs) = env::var_os("TERMINFO_DIRS") {
+        dirs.extend(env::split_paths(&terminfo_dirs).filter(|dir| !dir.as_os_str().is_empty()));
+    }
+    dirs.extend(
+        [
+            "/etc/terminfo",
+            "/lib/terminfo",
+            "/usr/share/terminfo",
+            "/usr/lib/terminfo",
+        ]
+        .map(PathBuf::from),
+    );
+
+    // Entries are in a directory named after their first letter, or its hex
+    // value on macOS.
+    dirs.iter()
+        .flat_map(|dir| {
+            [
+                dir.join(first.to_string()).join(term),
+                dir.join(format!("{:x}", u32::from(first))).join(term),
+            ]
+        })
+        .find_map(|path| std::fs::read(path).ok())
+        .and_then(|entry| parse_terminfo_colours(&entry))
+}
+
+#[cfg(not(unix))]
+fn terminfo_colours(_term: &str) -> Option<u32> {
+    None
+}
+
+/// Reads the `max_colors` number from a compiled terminfo entry, as described
+/// in `term(5)`.
+fn parse_terminfo_colours(entry: &[u8]) -> Option<u32> {
+    /// The index of `max_colors` in the numbers section.
+    const MAX_COLOURS: usize = 13;
+
+    let header = |index: usize| -> Option<usize> {
+        let bytes = entry.get(index * 2..index * 2 + 2)?;
+        Some(usize::from(u16::from_le_bytes([bytes[0], bytes[1]])))
+    };
+
+    // The original format has 16-bit numbers, and the extended one 32-bit.
+    let number_size = match header(0)? {
+        0o432 => 2,
+        0o1036 => 4,
+        _ => return None,
+    };
+    let (names_size, bools_count, numbers_count) = (header(1)?, header(2)?, header(3)?);
+    if MAX_COLOURS >= numbers_count {
+        return None;
+    }
+
+    // Numbers start on an even byte after the header, names, and booleans.
+    let mut start = 12 + names_size + bools_count;
+    start += start % 2;
+    let offset = start + MAX_COLOURS * number_size;
+    let bytes = entry.get(offset..offset + number_size)?;
+
+    let colours = if number_size == 2 {
+        i32::from(i16::from_le_bytes([bytes[0], bytes[1]]))
+    } else {
+        i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    };
+
+    // Negative values mean it's missing.
+    u32::try_from(colours).ok()
+}
+
+/// Changes what was drawn into `buffer` to only use what the terminal
+/// supports.
+pub fn restrict_buffer(buffer: &mut Buffer, features: &TerminalFeatures) {
+    let basic_colours = features.colour_depth == ColourDepth::Basic;
+    if !basic_colours && features.unicode {
+        return;
+    }
+
+    for cell in &mut buffer.content {
+        if basic_colours {
+            cell.fg = basic_colour(cell.fg);
+            cell.bg = basic_colour(cell.bg);
+        }
+
+        if !features.unicode {
+            if let Some(symbol) = ascii_symbol(cell.symbol()) {
+                cell.set_symbol(symbol);
+            }
+        }
+    }
+}
+
+/// Returns the closest of the 16 basic ANSI colours to `colour`.
+fn basic_colour(colour: Color) -> Color {
+    const BASIC: [Color; 16] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::Gray,
+        Color::DarkGray,
+        Color::LightRed,
+        Color::LightGreen,
+        Color::LightYellow,
+        Color::LightBlue,
+        Color::LightMagenta,
+        Color::LightCyan,
+        Color::White,
+    ];
+
+    match colour {
+        Color::Indexed(index) if index < 16 => BASIC[usize::from(index)],
+        Color::Indexed(_) | Color::Rgb(..) => {
+            let Some((r, g, b)) = colour_to_rgb(colour) else {
+                return colour;
+            };
+            let distance = |basic: &Color| {
+                let (br, bg, bb) = colour_to_rgb(*basic).unwrap_or_default();
+                [(r, br), (g, bg), (b, bb)]
+                    .into_iter()
+                    .map(|(a, b)| (i32::from(a) - i32::from(b)).pow(2))
+                    .sum::<i32>()
+            };
+
+            BASIC.into_iter().min_by_key(distance).unwrap_or(colour)
+        }
+        _ => colour,
+    }
+}
+
+/// Returns an ASCII stand-in for a box-drawing, block, Braille, or other
+/// symbol that bottom draws, if `symbol` is one.
+fn ascii_symbol(symbol: &str) -> Option<&'static str> {
+    let mut chars = symbol.chars();
+    let (Some(c), None) = (chars.next(), chars.next()) else {
+        return None;
+    };
+
+    Some(match c {
+        '─' | '━' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '═' | '╴' | '╶' | '╸' | '╺' | '╼' | '╾' => {
+            "-"
+        }
+        '│' | '┃' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '║' | '╵' | '╷' | '╹' | '╻' | '╽' | '╿' => {
+            "|"
+        }
+        '╱' => "/",
+        '╲' => "\\",
+        '\u{2500}'..='\u{257f}' => "+",
+        '\u{2580}'..='\u{259f}' => "#",
+        '\u{2800}' => " ",
+        '\u{2801}'..='\u{28ff}' | '•' | '●' => "*",
+        '·' | '…' => ".",
+        '▲' | '↑' => "^",
+        '▼' | '↓' => "v",
+        '◄' | '◀' | '←' => "<",
+        '►' | '▶' | '→' => ">",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn colour_depth_parsing() {
+        assert_eq!("16".parse(), Ok(ColourDepth::Basic));
+        assert_eq!("256".parse(), Ok(ColourDepth::Indexed));
+        assert_eq!("truecolor".parse(), Ok(ColourDepth::TrueColour));
+        assert!("8".parse::<ColourDepth>().is_err());
+    }
+
+    #[test]
+    fn features_from_env() {
+        let modern = TerminalFeatures::from_env(
+            Some("xterm-256color"),
+            Some("truecolor"),
+            Some("en_US.UTF-8"),
+            Some(256),
+        );
+        assert_eq!(
+            modern,
+            TerminalFeatures {
+                colour_depth: ColourDepth::TrueColour,
+                unicode: true,
+                mouse: true,
+            }
+        );
+
+        let serial = TerminalFeatures::from_env(Some("vt220"), None, Some("en_US.UTF-8"), None);
+        assert_eq!(
+            serial,
+            TerminalFeatures {
+                colour_depth: ColourDepth::Basic,
+                unicode: false,
+                mouse: false,
+            }
+        );
+
+        // Without TERM, like on Windows, everything is assumed to work.
+        assert_eq!(
+            TerminalFeatures::from_env(None, None, None, None),
+            TerminalFeatures::default()
+        );
+
+        // Plain xterm's terminfo entry only has 8 colours, but it usually
+        // supports more.
+        let xterm = TerminalFeatures::from_env(Some("xterm"), None, Some("C"), Some(8));
+        assert_eq!(xterm.colour_depth, ColourDepth::Indexed);
+        assert!(!xterm.unicode);
+
+        let other = TerminalFeatures::from_env(Some("foot"), None, None, Some(8));
+        assert_eq!(other.colour_depth, ColourDepth::Basic);
+    }
+
+    /// Builds a compiled terminfo entry with `max_colors` set to `colours`.
+    fn terminfo_entry(magic: u16, number_size: usize, colours: i32) -> Vec<u8> {
+        let names = b"test|a test terminal\0";
+        let bools = [1u8; 3];
+        let numbers_count = 15u16;
+
+        let mut entry = Vec::new();
+        for value in [
+            magic,
+            names.len() as u16,
+            bools.len() as u16,
+            numbers_count,
+            0,
+            0,
+        ] {
+            entry.extend_from_slice(&value.to_le_bytes());
+        }
+        entry.extend_from_slice(names);
+        entry.extend_from_slice(&bools);
+        if entry.len() % 2 == 1 {
+            entry.push(0);
+        }
+        for index in 0..usize::from(numbers_count) {
+            let value = if index == 13 { colours } else { -1 };
+            entry.extend_from_slice(&value.to_le_bytes()[..number_size]);
+        }
+
+        entry
+    }
+
+    #[test]
+    fn terminfo_colours() {
+        assert_eq!(
+            parse_terminfo_colours(&terminfo_entry(0o432, 2, 8)),
+            Some(8)
+        );
+        assert_eq!(
+            parse_terminfo_colours(&terminfo_entry(0o1036, 4, 0x1000000)),
+            Some(0x1000000)
+        );
+        assert_eq!(parse_terminfo_colours(&terminfo_entry(0o432, 2, -1)), None);
+        assert_eq!(parse_terminfo_colours(&terminfo_entry(0o123, 2, 8)), None);
+        assert_eq!(parse_terminfo_colours(&[]), None);
+    }
+
+    #[test]
+    fn basic_colours() {
+        assert_eq!(basic_colour(Color::Rgb(250, 10, 10)), Color::LightRed);
+        assert_eq!(basic_colour(Color::Rgb(0, 0, 0)), Color::Black);
+        assert_eq!(basic_colour(Color::Indexed(4)), Color::Blue);
+        assert_eq!(basic_colour(Color::Indexed(231)), Color::White);
+        assert_eq!(basic_colour(Color::Cyan), Color::Cyan);
+        assert_eq!(basic_colour(Color::Reset), Color::Reset);
+    }
+
+    #[test]
+    fn ascii_symbols() {
+        assert_eq!(ascii_symbol("─"), Some("-"));
+        assert_eq!(ascii_symbol("║"), Some("|"));
+        assert_eq!(ascii_symbol("╭"), Some("+"));
+        assert_eq!(ascii_symbol("█"), Some("#"));
+        assert_eq!(ascii_symbol("⣿"), Some("*"));
+        assert_eq!(ascii_symbol("⠀"), Some(" "));
+        assert_eq!(ascii_symbol("▲"), Some("^"));
+        assert_eq!(ascii_symbol("a"), None);
+        assert_eq!(ascii_symbol("é"), None);
+    }
+}
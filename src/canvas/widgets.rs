@@ -3,10 +3,15 @@ pub mod cpu_graph;
 pub mod disk_table;
 pub mod mem_basic;
 pub mod mem_graph;
+pub mod meter_header;
 pub mod network_basic;
 pub mod network_graph;
 pub mod process_table;
+pub mod status_bar;
 pub mod temperature_table;
 
 #[cfg(feature = "battery")]
 pub mod battery_display;
+
+#[cfg(feature = "plugins")]
+pub mod plugin;
@@ -3,16 +3,17 @@ use std::cmp::min;
 use tui::{
     layout::{Constraint, Direction, Layout, Rect},
     text::{Line, Span},
-    widgets::{Cell, Paragraph, Row, Table, Tabs},
+    widgets::{Cell, Paragraph, Row, Table, Tabs, Wrap},
     Frame,
 };
 use unicode_width::UnicodeWidthStr;
 
 use crate::{
-    app::App,
+    app::{layout_manager::BottomWidgetType, App},
     canvas::{drawing_utils::widget_block, Painter},
     constants::*,
     data_collection::batteries::BatteryState,
+    i18n::tr,
 };
 
 /// Calculate how many bars are to be drawn within basic mode's components.
@@ -27,7 +28,13 @@ impl Painter {
     pub fn draw_battery(
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
+        let styles = self.styles_for(widget_id);
         let should_get_widget_bounds = app_state.should_get_widget_bounds();
+        let empty_message = if app_state.data_collection.battery_harvest.is_empty() {
+            app_state.empty_message(&BottomWidgetType::Battery)
+        } else {
+            tr("No data found for this battery").into()
+        };
         if let Some(battery_widget_state) = app_state
             .states
             .battery_state
@@ -36,9 +43,9 @@ impl Painter {
         {
             let is_selected = widget_id == app_state.current_widget.widget_id;
             let border_style = if is_selected {
-                self.styles.highlighted_border_style
+                styles.highlighted_border_style
             } else {
-                self.styles.border_style
+                styles.border_style
             };
             let table_gap = if draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
                 0
@@ -50,14 +57,20 @@ impl Painter {
                 let mut block = widget_block(
                     app_state.app_config_fields.use_basic_mode,
                     is_selected,
-                    self.styles.border_type,
+                    styles.border_type,
                 )
                 .border_style(border_style)
-                .title_top(Line::styled(" Battery ", self.styles.widget_title_style));
+                .title_top(Line::styled(
+                    battery_widget_state
+                        .title
+                        .as_deref()
+                        .unwrap_or(tr(" Battery ")),
+                    styles.widget_title_style,
+                ));
 
                 if app_state.is_expanded {
                     block = block.title_top(
-                        Line::styled(" Esc to go back ", self.styles.widget_title_style)
+                        Line::styled(tr(" Esc to go back "), styles.widget_title_style)
                             .right_aligned(),
                     )
                 }
@@ -91,8 +104,8 @@ impl Painter {
                             .collect::<Vec<_>>(),
                     )
                     .divider(tui::symbols::line::VERTICAL)
-                    .style(self.styles.text_style)
-                    .highlight_style(self.styles.selected_text_style)
+                    .style(styles.text_style)
+                    .highlight_style(styles.selected_text_style)
                     .select(battery_widget_state.currently_selected_battery_index),
                     tab_draw_loc,
                 );
@@ -142,16 +155,14 @@ impl Painter {
                 );
 
                 let mut battery_charge_rows = Vec::with_capacity(2);
-                battery_charge_rows.push(Row::new([
-                    Cell::from("Charge").style(self.styles.text_style)
-                ]));
+                battery_charge_rows.push(Row::new([Cell::from("Charge").style(styles.text_style)]));
                 battery_charge_rows.push(Row::new([Cell::from(bars).style(
                     if charge_percent < 10.0 {
-                        self.styles.low_battery
+                        styles.low_battery
                     } else if charge_percent < 50.0 {
-                        self.styles.medium_battery
+                        styles.medium_battery
                     } else {
-                        self.styles.high_battery
+                        styles.high_battery
                     },
                 )]));
 
@@ -160,17 +171,15 @@ impl Painter {
                 let health = battery_details.health();
 
                 battery_rows.push(Row::new([""]).bottom_margin(table_gap + 1));
-                battery_rows
-                    .push(Row::new(["Rate", &watt_consumption]).style(self.styles.text_style));
+                battery_rows.push(Row::new(["Rate", &watt_consumption]).style(styles.text_style));
 
                 battery_rows.push(
-                    Row::new(["State", battery_details.state.as_str()])
-                        .style(self.styles.text_style),
+                    Row::new(["State", battery_details.state.as_str()]).style(styles.text_style),
                 );
 
                 let mut time: String; // Keep string lifetime in scope.
                 {
-                    let style = self.styles.text_style;
+                    let style = styles.text_style;
                     match &battery_details.state {
                         BatteryState::Charging {
                             time_to_full: Some(secs),
@@ -200,7 +209,7 @@ impl Painter {
                     }
                 }
 
-                battery_rows.push(Row::new(["Health", &health]).style(self.styles.text_style));
+                battery_rows.push(Row::new(["Health", &health]).style(styles.text_style));
 
                 let header = if app_state.data_collection.battery_harvest.len() > 1 {
                     Row::new([""]).bottom_margin(table_gap)
@@ -229,12 +238,14 @@ impl Painter {
             } else {
                 let mut contents = vec![Line::default(); table_gap.into()];
 
-                contents.push(Line::from(Span::styled(
-                    "No data found for this battery",
-                    self.styles.text_style,
-                )));
+                contents.push(Line::from(Span::styled(empty_message, styles.text_style)));
 
-                f.render_widget(Paragraph::new(contents).block(block), margined_draw_loc);
+                f.render_widget(
+                    Paragraph::new(contents)
+                        .block(block)
+                        .wrap(Wrap { trim: true }),
+                    margined_draw_loc,
+                );
             }
 
             if should_get_widget_bounds {
@@ -2,7 +2,6 @@ use std::borrow::Cow;
 
 use tui::{
     layout::{Constraint, Direction, Layout, Rect},
-    symbols::Marker,
     Frame,
 };
 
@@ -17,6 +16,8 @@ use crate::{
         Painter,
     },
     data_conversion::CpuWidgetData,
+    i18n::tr,
+    options::config::style::Styles,
     widgets::CpuWidgetState,
 };
 
@@ -120,7 +121,8 @@ impl Painter {
     }
 
     fn generate_points<'a>(
-        &self, cpu_widget_state: &CpuWidgetState, cpu_data: &'a [CpuWidgetData], show_avg_cpu: bool,
+        styles: &Styles, cpu_widget_state: &CpuWidgetState, cpu_data: &'a [CpuWidgetData],
+        show_avg_cpu: bool,
     ) -> Vec<GraphData<'a>> {
         let show_avg_offset = if show_avg_cpu { AVG_POSITION } else { 0 };
 
@@ -135,20 +137,25 @@ impl Painter {
                     match &cpu {
                         CpuWidgetData::All => None,
                         CpuWidgetData::Entry { data, .. } => {
-                            let style = if show_avg_cpu && itx == AVG_POSITION {
-                                self.styles.avg_cpu_colour
+                            let (style, symbol) = if show_avg_cpu && itx == AVG_POSITION {
+                                (styles.avg_cpu_colour, styles.series_symbol(0))
                             } else if itx == ALL_POSITION {
-                                self.styles.all_cpu_colour
+                                (styles.all_cpu_colour, None)
                             } else {
                                 let offset_position = itx - 1; // Because of the all position
-                                self.styles.cpu_colour_styles[(offset_position - show_avg_offset)
-                                    % self.styles.cpu_colour_styles.len()]
+                                let core_position = offset_position - show_avg_offset;
+                                (
+                                    styles.cpu_colour_styles
+                                        [core_position % styles.cpu_colour_styles.len()],
+                                    styles.series_symbol(core_position + 1),
+                                )
                             };
 
                             Some(GraphData {
                                 points: &data[..],
                                 style,
                                 name: None,
+                                symbol,
                             })
                         }
                     }
@@ -157,18 +164,22 @@ impl Painter {
         } else if let Some(CpuWidgetData::Entry { data, .. }) =
             cpu_data.get(current_scroll_position)
         {
-            let style = if show_avg_cpu && current_scroll_position == AVG_POSITION {
-                self.styles.avg_cpu_colour
+            let (style, symbol) = if show_avg_cpu && current_scroll_position == AVG_POSITION {
+                (styles.avg_cpu_colour, styles.series_symbol(0))
             } else {
                 let offset_position = current_scroll_position - 1; // Because of the all position
-                self.styles.cpu_colour_styles
-                    [(offset_position - show_avg_offset) % self.styles.cpu_colour_styles.len()]
+                let core_position = offset_position - show_avg_offset;
+                (
+                    styles.cpu_colour_styles[core_position % styles.cpu_colour_styles.len()],
+                    styles.series_symbol(core_position + 1),
+                )
             };
 
             vec![GraphData {
                 points: &data[..],
                 style,
                 name: None,
+                symbol,
             }]
         } else {
             vec![]
@@ -181,11 +192,17 @@ impl Painter {
         const Y_BOUNDS: [f64; 2] = [0.0, 100.5];
         const Y_LABELS: [Cow<'static, str>; 2] = [Cow::Borrowed("  0%"), Cow::Borrowed("100%")];
 
+        let styles = self.styles_for(widget_id);
+        let latest_time = app_state.displayed_time();
+
         if let Some(cpu_widget_state) = app_state.states.cpu_state.widget_states.get_mut(&widget_id)
         {
             let cpu_data = &app_state.converted_data.cpu_data;
             let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
-            let x_bounds = [0, cpu_widget_state.current_display_time];
+            let x_bounds = [
+                cpu_widget_state.display_offset,
+                cpu_widget_state.display_offset + cpu_widget_state.current_display_time,
+            ];
             let hide_x_labels = should_hide_x_label(
                 app_state.app_config_fields.hide_time,
                 app_state.app_config_fields.autohide_time,
@@ -193,7 +210,8 @@ impl Painter {
                 draw_loc,
             );
 
-            let points = self.generate_points(
+            let points = Self::generate_points(
+                styles,
                 cpu_widget_state,
                 cpu_data,
                 app_state.app_config_fields.show_average_cpu,
@@ -201,6 +219,8 @@ impl Painter {
 
             // TODO: Maybe hide load avg if too long? Or maybe the CPU part.
             let title = {
+                let name = cpu_widget_state.title.as_deref().unwrap_or(tr(" CPU "));
+
                 #[cfg(target_family = "unix")]
                 {
                     let load_avg = app_state.converted_data.load_avg_data;
@@ -209,37 +229,42 @@ impl Painter {
                         load_avg[0], load_avg[1], load_avg[2]
                     );
 
-                    concat_string::concat_string!(" CPU ", load_avg_str).into()
+                    concat_string::concat_string!(name, load_avg_str).into()
                 }
                 #[cfg(not(target_family = "unix"))]
                 {
-                    " CPU ".into()
+                    name.into()
                 }
             };
 
-            let marker = if app_state.app_config_fields.use_dot {
-                Marker::Dot
-            } else {
-                Marker::Braille
-            };
-
-            TimeGraph {
+            let graph = TimeGraph {
                 x_bounds,
                 hide_x_labels,
+                time_format: &app_state.app_config_fields.time_format,
+                latest_time,
                 y_bounds: Y_BOUNDS,
                 y_labels: &Y_LABELS,
-                graph_style: self.styles.graph_style,
+                graph_style: styles.graph_style,
                 border_style,
-                border_type: self.styles.border_type,
+                border_type: styles.border_type,
                 title,
                 is_selected: app_state.current_widget.widget_id == widget_id,
                 is_expanded: app_state.is_expanded,
-                title_style: self.styles.widget_title_style,
+                title_style: styles.widget_title_style,
                 legend_position: None,
                 legend_constraints: None,
-                marker,
-            }
-            .draw_time_graph(f, draw_loc, &points);
+                marker: app_state.app_config_fields.graph_marker,
+                selection: app_state
+                    .time_range_selection
+                    .and_then(|selection| selection.x_range_for(widget_id)),
+                cursor: app_state
+                    .inspect_cursor
+                    .filter(|cursor| cursor.widget_id == widget_id)
+                    .map(|cursor| cursor.time),
+                gradient: app_state.app_config_fields.graph_gradients && self.truecolor,
+            };
+
+            cpu_widget_state.graph_area = self.draw_graph(f, draw_loc, graph, &points, widget_id);
         }
     }
 
@@ -247,6 +272,7 @@ impl Painter {
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
         let recalculate_column_widths = app_state.should_get_widget_bounds();
+        let hover = app_state.hover_position(widget_id);
         if let Some(cpu_widget_state) = app_state
             .states
             .cpu_state
@@ -264,6 +290,8 @@ impl Painter {
                 force_redraw: app_state.is_force_redraw,
                 recalculate_column_widths,
                 selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
+                hover,
+                empty_message: None,
             };
 
             cpu_widget_state.table.draw(
@@ -1,11 +1,22 @@
-use tui::{layout::Rect, Frame};
+use tui::{
+    layout::{Constraint, Layout, Margin, Rect},
+    text::Line,
+    Frame,
+};
 
 use crate::{
-    app,
+    app::{self, layout_manager::BottomWidgetType},
     canvas::{
-        components::data_table::{DrawInfo, SelectionState},
+        components::{
+            data_table::{DrawInfo, SelectionState},
+            pipe_gauge::PipeGauge,
+        },
+        drawing_utils::widget_block,
         Painter,
     },
+    i18n::tr,
+    options::config::style::Styles,
+    widgets::DiskWidgetData,
 };
 
 impl Painter {
@@ -13,6 +24,8 @@ impl Painter {
         &self, f: &mut Frame<'_>, app_state: &mut app::App, draw_loc: Rect, widget_id: u64,
     ) {
         let recalculate_column_widths = app_state.should_get_widget_bounds();
+        let hover = app_state.hover_position(widget_id);
+        let empty_message = app_state.empty_message(&BottomWidgetType::Disk);
         if let Some(disk_widget_state) = app_state
             .states
             .disk_state
@@ -21,11 +34,29 @@ impl Painter {
         {
             let is_on_widget = app_state.current_widget.widget_id == widget_id;
 
+            // In basic mode, the focused mount's usage is shown as a gauge above the table.
+            let mut draw_loc = draw_loc;
+            if app_state.app_config_fields.use_basic_mode && draw_loc.height > 2 {
+                if let Some(disk) = disk_widget_state.focused_disk() {
+                    let [gauge_loc, table_loc] =
+                        Layout::vertical([Constraint::Length(1), Constraint::Min(0)])
+                            .areas(draw_loc);
+                    draw_loc = table_loc;
+
+                    f.render_widget(
+                        disk_gauge(disk, app_state.basic_mode_use_percent, &self.styles),
+                        gauge_loc.inner(Margin::new(1, 0)),
+                    );
+                }
+            }
+
             let draw_info = DrawInfo {
                 loc: draw_loc,
                 force_redraw: app_state.is_force_redraw,
                 recalculate_column_widths,
                 selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
+                hover,
+                empty_message: Some(empty_message),
             };
 
             disk_widget_state.table.draw(
@@ -36,4 +67,75 @@ impl Painter {
             );
         }
     }
+
+    /// Draws a disk widget as a bar gauge per mount in a bordered block,
+    /// rather than as a table.
+    pub fn draw_disk_gauges(
+        &self, f: &mut Frame<'_>, app_state: &mut app::App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let styles = self.styles_for(widget_id);
+        let is_selected = app_state.current_widget.widget_id == widget_id;
+        let Some(disk_widget_state) = app_state.states.disk_state.widget_states.get(&widget_id)
+        else {
+            return;
+        };
+
+        let mut block = widget_block(false, is_selected, styles.border_type)
+            .border_style(self.get_border_style(widget_id, app_state.current_widget.widget_id))
+            .title_top(Line::styled(
+                disk_widget_state
+                    .table
+                    .props
+                    .title
+                    .as_deref()
+                    .unwrap_or(tr(" Disks ")),
+                styles.widget_title_style,
+            ));
+        if app_state.is_expanded {
+            block = block.title_top(
+                Line::styled(tr(" Esc to go back "), styles.widget_title_style).right_aligned(),
+            );
+        }
+
+        let inner_loc = block.inner(draw_loc);
+        f.render_widget(block, draw_loc);
+
+        let disks = disk_widget_state.table.data();
+        let gauge_locs = Layout::vertical(vec![Constraint::Length(1); disks.len()])
+            .horizontal_margin(1)
+            .split(inner_loc);
+        for (disk, gauge_loc) in disks.iter().zip(gauge_locs.iter()) {
+            if gauge_loc.height > 0 {
+                f.render_widget(
+                    disk_gauge(disk, app_state.basic_mode_use_percent, styles),
+                    *gauge_loc,
+                );
+            }
+        }
+
+        if app_state.should_get_widget_bounds() {
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
+
+/// Returns a gauge of the disk's usage, labelled with its mount point.
+fn disk_gauge(disk: &DiskWidgetData, use_percent: bool, styles: &Styles) -> PipeGauge<'static> {
+    let used_percent = disk.used_percent().unwrap_or(0.0);
+    let label = if use_percent {
+        format!("{:3.0}%", used_percent.round())
+    } else {
+        format!("{}/{}", disk.used_space(), disk.total_space())
+    };
+
+    PipeGauge::default()
+        .ratio(used_percent / 100.0)
+        .start_label(disk.mount_point.to_string())
+        .inner_label(label)
+        .label_style(styles.text_style)
+        .gauge_style(styles.highlighted_border_style)
 }
@@ -1,20 +1,20 @@
 use tui::{
     layout::{Constraint, Direction, Layout, Rect},
+    text::Line,
     Frame,
 };
 
 use crate::{
     app::App,
     canvas::{components::pipe_gauge::PipeGauge, drawing_utils::widget_block, Painter},
+    i18n::tr,
+    options::config::style::Styles,
 };
 
 impl Painter {
     pub fn draw_basic_memory(
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
-        let mem_data = &app_state.converted_data.mem_data;
-        let mut draw_widgets: Vec<PipeGauge<'_>> = Vec::new();
-
         if app_state.current_widget.widget_id == widget_id {
             f.render_widget(
                 widget_block(true, true, self.styles.border_type)
@@ -23,6 +23,79 @@ impl Painter {
             );
         }
 
+        let draw_widgets = self.memory_gauges(app_state, &self.styles);
+        let margined_loc = Layout::default()
+            .constraints(vec![Constraint::Length(1); draw_widgets.len()])
+            .direction(Direction::Vertical)
+            .horizontal_margin(1)
+            .split(draw_loc);
+
+        draw_widgets
+            .into_iter()
+            .enumerate()
+            .for_each(|(index, widget)| {
+                f.render_widget(widget, margined_loc[index]);
+            });
+
+        // Update draw loc in widget map
+        if app_state.should_get_widget_bounds() {
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+
+    /// Draws a memory widget as bar gauges in a bordered block, rather than
+    /// as a graph.
+    pub fn draw_memory_gauges(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let styles = self.styles_for(widget_id);
+        let is_selected = app_state.current_widget.widget_id == widget_id;
+        let title = app_state
+            .states
+            .mem_state
+            .widget_states
+            .get(&widget_id)
+            .and_then(|state| state.title.as_deref())
+            .unwrap_or(tr(" Memory "));
+
+        let mut block = widget_block(false, is_selected, styles.border_type)
+            .border_style(self.get_border_style(widget_id, app_state.current_widget.widget_id))
+            .title_top(Line::styled(title, styles.widget_title_style));
+        if app_state.is_expanded {
+            block = block.title_top(
+                Line::styled(tr(" Esc to go back "), styles.widget_title_style).right_aligned(),
+            );
+        }
+
+        let inner_loc = block.inner(draw_loc);
+        f.render_widget(block, draw_loc);
+
+        let draw_widgets = self.memory_gauges(app_state, styles);
+        let gauge_locs = Layout::vertical(vec![Constraint::Length(1); draw_widgets.len()])
+            .horizontal_margin(1)
+            .split(inner_loc);
+        for (widget, gauge_loc) in draw_widgets.into_iter().zip(gauge_locs.iter()) {
+            f.render_widget(widget, *gauge_loc);
+        }
+
+        if app_state.should_get_widget_bounds() {
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+
+    /// Returns a gauge for RAM and each other memory type with data.
+    fn memory_gauges(&self, app_state: &App, styles: &Styles) -> Vec<PipeGauge<'static>> {
+        let mem_data = &app_state.converted_data.mem_data;
+        let mut draw_widgets: Vec<PipeGauge<'static>> = Vec::new();
+
         let ram_percentage = if let Some(mem) = mem_data.last() {
             mem.1
         } else {
@@ -47,8 +120,8 @@ impl Painter {
                 .ratio(ram_percentage / 100.0)
                 .start_label("RAM")
                 .inner_label(memory_fraction_label)
-                .label_style(self.styles.ram_style)
-                .gauge_style(self.styles.ram_style),
+                .label_style(styles.ram_style)
+                .gauge_style(styles.ram_style),
         );
 
         #[cfg(not(target_os = "windows"))]
@@ -72,8 +145,8 @@ impl Painter {
                         .ratio(cache_percentage / 100.0)
                         .start_label("CHE")
                         .inner_label(cache_fraction_label)
-                        .label_style(self.styles.cache_style)
-                        .gauge_style(self.styles.cache_style),
+                        .label_style(styles.cache_style)
+                        .gauge_style(styles.cache_style),
                 );
             }
         }
@@ -97,8 +170,8 @@ impl Painter {
                     .ratio(swap_percentage / 100.0)
                     .start_label("SWP")
                     .inner_label(swap_fraction_label)
-                    .label_style(self.styles.swap_style)
-                    .gauge_style(self.styles.swap_style),
+                    .label_style(styles.swap_style)
+                    .gauge_style(styles.swap_style),
             );
         }
 
@@ -121,8 +194,8 @@ impl Painter {
                         .ratio(arc_percentage / 100.0)
                         .start_label("ARC")
                         .inner_label(arc_fraction_label)
-                        .label_style(self.styles.arc_style)
-                        .gauge_style(self.styles.arc_style),
+                        .label_style(styles.arc_style)
+                        .gauge_style(styles.arc_style),
                 );
             }
         }
@@ -130,7 +203,7 @@ impl Painter {
         #[cfg(feature = "gpu")]
         {
             if let Some(gpu_data) = &app_state.converted_data.gpu_data {
-                let gpu_styles = &self.styles.gpu_colours;
+                let gpu_styles = &styles.gpu_colours;
                 let mut color_index = 0;
 
                 gpu_data.iter().for_each(|gpu_data_vec| {
@@ -171,26 +244,6 @@ impl Painter {
             }
         }
 
-        let margined_loc = Layout::default()
-            .constraints(vec![Constraint::Length(1); draw_widgets.len()])
-            .direction(Direction::Vertical)
-            .horizontal_margin(1)
-            .split(draw_loc);
-
         draw_widgets
-            .into_iter()
-            .enumerate()
-            .for_each(|(index, widget)| {
-                f.render_widget(widget, margined_loc[index]);
-            });
-
-        // Update draw loc in widget map
-        if app_state.should_get_widget_bounds() {
-            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
-                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
-                widget.bottom_right_corner =
-                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
-            }
-        }
     }
 }
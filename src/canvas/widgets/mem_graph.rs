@@ -2,17 +2,17 @@ use std::borrow::Cow;
 
 use tui::{
     layout::{Constraint, Rect},
-    symbols::Marker,
     Frame,
 };
 
 use crate::{
-    app::App,
+    app::{data_farmer::TimedData, App},
     canvas::{
         components::time_graph::{GraphData, TimeGraph},
         drawing_utils::should_hide_x_label,
         Painter,
     },
+    i18n::tr,
 };
 
 impl Painter {
@@ -22,16 +22,37 @@ impl Painter {
         const Y_BOUNDS: [f64; 2] = [0.0, 100.5];
         const Y_LABELS: [Cow<'static, str>; 2] = [Cow::Borrowed("  0%"), Cow::Borrowed("100%")];
 
-        if let Some(mem_widget_state) = app_state.states.mem_state.widget_states.get_mut(&widget_id)
-        {
+        let styles = self.styles_for(widget_id);
+        let latest_time = app_state.displayed_time();
+
+        // Only the autohide timer and graph area need the widget state to be
+        // mutable, so they're updated separately to let the inspected data be
+        // borrowed from the app while drawing.
+        let hide_x_labels = app_state
+            .states
+            .mem_state
+            .widget_states
+            .get_mut(&widget_id)
+            .map(|mem_widget_state| {
+                should_hide_x_label(
+                    app_state.app_config_fields.hide_time,
+                    app_state.app_config_fields.autohide_time,
+                    &mut mem_widget_state.autohide_timer,
+                    draw_loc,
+                )
+            });
+        let inspected = app_state.inspected_data(widget_id);
+        let mut graph_area = None;
+
+        if let (Some(hide_x_labels), Some(mem_widget_state)) = (
+            hide_x_labels,
+            app_state.states.mem_state.widget_states.get(&widget_id),
+        ) {
             let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
-            let x_bounds = [0, mem_widget_state.current_display_time];
-            let hide_x_labels = should_hide_x_label(
-                app_state.app_config_fields.hide_time,
-                app_state.app_config_fields.autohide_time,
-                &mut mem_widget_state.autohide_timer,
-                draw_loc,
-            );
+            let x_bounds = [
+                mem_widget_state.display_offset,
+                mem_widget_state.display_offset + mem_widget_state.current_display_time,
+            ];
             let points = {
                 let mut size = 1;
                 if app_state.converted_data.swap_labels.is_some() {
@@ -52,47 +73,69 @@ impl Painter {
 
                 let mut points = Vec::with_capacity(size);
                 if let Some((label_percent, label_frac)) = &app_state.converted_data.mem_labels {
-                    let mem_label = format!("RAM:{label_percent}{label_frac}");
+                    let mem_label = match inspected {
+                        Some(data) => inspected_label("RAM", data, |d| d.mem_data),
+                        None => format!("RAM:{label_percent}{label_frac}"),
+                    };
                     points.push(GraphData {
                         points: &app_state.converted_data.mem_data,
-                        style: self.styles.ram_style,
+                        style: styles.ram_style,
                         name: Some(mem_label.into()),
+                        symbol: styles.series_symbol(0),
                     });
                 }
                 #[cfg(not(target_os = "windows"))]
                 if let Some((label_percent, label_frac)) = &app_state.converted_data.cache_labels {
-                    let cache_label = format!("CHE:{label_percent}{label_frac}");
+                    let cache_label = match inspected {
+                        Some(data) => inspected_label("CHE", data, |d| d.cache_data),
+                        None => format!("CHE:{label_percent}{label_frac}"),
+                    };
                     points.push(GraphData {
                         points: &app_state.converted_data.cache_data,
-                        style: self.styles.cache_style,
+                        style: styles.cache_style,
                         name: Some(cache_label.into()),
+                        symbol: styles.series_symbol(1),
                     });
                 }
                 if let Some((label_percent, label_frac)) = &app_state.converted_data.swap_labels {
-                    let swap_label = format!("SWP:{label_percent}{label_frac}");
+                    let swap_label = match inspected {
+                        Some(data) => inspected_label("SWP", data, |d| d.swap_data),
+                        None => format!("SWP:{label_percent}{label_frac}"),
+                    };
                     points.push(GraphData {
                         points: &app_state.converted_data.swap_data,
-                        style: self.styles.swap_style,
+                        style: styles.swap_style,
                         name: Some(swap_label.into()),
+                        symbol: styles.series_symbol(2),
                     });
                 }
                 #[cfg(feature = "zfs")]
                 if let Some((label_percent, label_frac)) = &app_state.converted_data.arc_labels {
-                    let arc_label = format!("ARC:{label_percent}{label_frac}");
+                    let arc_label = match inspected {
+                        Some(data) => inspected_label("ARC", data, |d| d.arc_data),
+                        None => format!("ARC:{label_percent}{label_frac}"),
+                    };
                     points.push(GraphData {
                         points: &app_state.converted_data.arc_data,
-                        style: self.styles.arc_style,
+                        style: styles.arc_style,
                         name: Some(arc_label.into()),
+                        symbol: styles.series_symbol(3),
                     });
                 }
                 #[cfg(feature = "gpu")]
                 {
                     if let Some(gpu_data) = &app_state.converted_data.gpu_data {
                         let mut color_index = 0;
-                        let gpu_styles = &self.styles.gpu_colours;
-                        gpu_data.iter().for_each(|gpu| {
-                            let gpu_label =
-                                format!("{}:{}{}", gpu.name, gpu.mem_percent, gpu.mem_total);
+                        let gpu_styles = &styles.gpu_colours;
+                        gpu_data.iter().enumerate().for_each(|(index, gpu)| {
+                            let gpu_label = match inspected {
+                                Some(data) => inspected_label(&gpu.name, data, |d| {
+                                    d.gpu_data.get(index).copied().flatten()
+                                }),
+                                None => {
+                                    format!("{}:{}{}", gpu.name, gpu.mem_percent, gpu.mem_total)
+                                }
+                            };
                             let style = {
                                 if gpu_styles.is_empty() {
                                     tui::style::Style::default()
@@ -109,6 +152,7 @@ impl Painter {
                                 points: gpu.points.as_slice(),
                                 style,
                                 name: Some(gpu_label.into()),
+                                symbol: styles.series_symbol(4 + index),
                             });
                         });
                     }
@@ -117,29 +161,45 @@ impl Painter {
                 points
             };
 
-            let marker = if app_state.app_config_fields.use_dot {
-                Marker::Dot
-            } else {
-                Marker::Braille
-            };
-
-            TimeGraph {
+            let graph = TimeGraph {
                 x_bounds,
                 hide_x_labels,
+                time_format: &app_state.app_config_fields.time_format,
+                latest_time,
                 y_bounds: Y_BOUNDS,
                 y_labels: &Y_LABELS,
-                graph_style: self.styles.graph_style,
+                graph_style: styles.graph_style,
                 border_style,
-                border_type: self.styles.border_type,
-                title: " Memory ".into(),
+                border_type: styles.border_type,
+                title: mem_widget_state
+                    .title
+                    .as_deref()
+                    .unwrap_or(tr(" Memory "))
+                    .into(),
                 is_selected: app_state.current_widget.widget_id == widget_id,
                 is_expanded: app_state.is_expanded,
-                title_style: self.styles.widget_title_style,
+                title_style: styles.widget_title_style,
                 legend_position: app_state.app_config_fields.memory_legend_position,
                 legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
-                marker,
-            }
-            .draw_time_graph(f, draw_loc, &points);
+                marker: app_state.app_config_fields.graph_marker,
+                selection: app_state
+                    .time_range_selection
+                    .and_then(|selection| selection.x_range_for(widget_id)),
+                cursor: app_state
+                    .inspect_cursor
+                    .filter(|cursor| cursor.widget_id == widget_id)
+                    .map(|cursor| cursor.time),
+                gradient: app_state.app_config_fields.graph_gradients && self.truecolor,
+            };
+
+            graph_area = Some(self.draw_graph(f, draw_loc, graph, &points, widget_id));
+        }
+
+        if let (Some(graph_area), Some(mem_widget_state)) = (
+            graph_area,
+            app_state.states.mem_state.widget_states.get_mut(&widget_id),
+        ) {
+            mem_widget_state.graph_area = graph_area;
         }
 
         if app_state.should_get_widget_bounds() {
@@ -152,3 +212,23 @@ impl Painter {
         }
     }
 }
+
+/// Returns the legend label for a value at the inspection cursor.
+/// The label for a value of the inspected entry. For a downsampled entry, this
+/// also has the lowest and highest values it was made from.
+fn inspected_label(
+    name: &str, data: &TimedData, get: impl Fn(&TimedData) -> Option<f64>,
+) -> String {
+    let Some(value) = get(data) else {
+        return format!("{name}:  N/A");
+    };
+
+    match data
+        .range
+        .as_ref()
+        .and_then(|range| Some((get(&range.min)?, get(&range.max)?)))
+    {
+        Some((min, max)) => format!("{name}:{value:5.1}% ({min:.1}-{max:.1}%)"),
+        None => format!("{name}:{value:5.1}%"),
+    }
+}
@@ -0,0 +1,178 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tui::{
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::{
+    app::{data_farmer::DataCollection, frozen_state::FrozenState, App},
+    canvas::{components::pipe_gauge::PipeGauge, Painter},
+    data_collection::{cpu::CpuDataType, memory::MemHarvest},
+    options::config::meter_header::{Meter, MeterHeader},
+    utils::data_prefixes::get_binary_bytes,
+    widgets::format_time,
+};
+
+/// How many meters are drawn side by side in each row.
+const METER_COLUMNS: usize = 2;
+
+/// A single meter, as drawn in one cell of the header.
+enum MeterCell {
+    Gauge(Box<PipeGauge<'static>>),
+    Text(Line<'static>),
+}
+
+impl Painter {
+    /// Returns how many rows the meter header takes up.
+    pub fn meter_header_height(&self, app_state: &App, meter_header: &MeterHeader) -> u16 {
+        let cells = self.meter_cells(meter_data(app_state), meter_header).len();
+        cells.div_ceil(METER_COLUMNS) as u16
+    }
+
+    /// Draws a compact band of htop-style meters, regardless of which widgets
+    /// are shown.
+    pub fn draw_meter_header(
+        &self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect, meter_header: &MeterHeader,
+    ) {
+        let cells = self.meter_cells(meter_data(app_state), meter_header);
+        let rows = Layout::vertical(vec![
+            Constraint::Length(1);
+            cells.len().div_ceil(METER_COLUMNS)
+        ])
+        .split(draw_loc);
+
+        let cell_locs = rows.iter().flat_map(|row| {
+            Layout::horizontal(vec![
+                Constraint::Ratio(1, METER_COLUMNS as u32);
+                METER_COLUMNS
+            ])
+            .horizontal_margin(1)
+            .spacing(2)
+            .split(*row)
+            .to_vec()
+        });
+
+        for (cell, cell_loc) in cells.into_iter().zip(cell_locs) {
+            match cell {
+                MeterCell::Gauge(gauge) => f.render_widget(*gauge, cell_loc),
+                MeterCell::Text(line) => f.render_widget(Paragraph::new(line), cell_loc),
+            }
+        }
+    }
+
+    /// Returns a cell for each meter, with one per core for [`Meter::Cores`].
+    fn meter_cells(&self, data: &DataCollection, meter_header: &MeterHeader) -> Vec<MeterCell> {
+        let mut cells = Vec::with_capacity(meter_header.meters.len());
+        for meter in &meter_header.meters {
+            match meter {
+                Meter::Cpu => {
+                    let cpus = &data.cpu_harvest;
+                    let usage = match cpus
+                        .iter()
+                        .find(|cpu| matches!(cpu.data_type, CpuDataType::Avg))
+                    {
+                        Some(avg) => avg.cpu_usage,
+                        None if cpus.is_empty() => 0.0,
+                        None => {
+                            cpus.iter().map(|cpu| cpu.cpu_usage).sum::<f64>() / cpus.len() as f64
+                        }
+                    };
+                    cells.push(MeterCell::Gauge(Box::new(
+                        PipeGauge::default()
+                            .ratio(usage / 100.0)
+                            .start_label("CPU")
+                            .inner_label(format!("{usage:.1}%"))
+                            .label_style(self.styles.avg_cpu_colour)
+                            .gauge_style(self.styles.avg_cpu_colour),
+                    )));
+                }
+                Meter::Cores => {
+                    for cpu in &data.cpu_harvest {
+                        if let CpuDataType::Cpu(index) = cpu.data_type {
+                            let style = if self.styles.cpu_colour_styles.is_empty() {
+                                self.styles.avg_cpu_colour
+                            } else {
+                                self.styles.cpu_colour_styles
+                                    [index % self.styles.cpu_colour_styles.len()]
+                            };
+                            cells.push(MeterCell::Gauge(Box::new(
+                                PipeGauge::default()
+                                    .ratio(cpu.cpu_usage / 100.0)
+                                    .start_label(format!("{index:<3}"))
+                                    .inner_label(format!("{:.1}%", cpu.cpu_usage))
+                                    .label_style(style)
+                                    .gauge_style(style),
+                            )));
+                        }
+                    }
+                }
+                Meter::Mem => cells.push(MeterCell::Gauge(Box::new(
+                    memory_gauge("Mem", &data.memory_harvest)
+                        .label_style(self.styles.ram_style)
+                        .gauge_style(self.styles.ram_style),
+                ))),
+                Meter::Swap => cells.push(MeterCell::Gauge(Box::new(
+                    memory_gauge("Swp", &data.swap_harvest)
+                        .label_style(self.styles.swap_style)
+                        .gauge_style(self.styles.swap_style),
+                ))),
+                Meter::Load => {
+                    // Load averages aren't available on Windows.
+                    if cfg!(target_family = "unix") {
+                        let [one, five, fifteen] = data.load_avg_harvest;
+                        cells.push(MeterCell::Text(Line::from(vec![
+                            Span::styled("Load average: ", self.styles.table_header_style),
+                            Span::styled(
+                                format!("{one:.2} {five:.2} {fifteen:.2}"),
+                                self.styles.text_style,
+                            ),
+                        ])));
+                    }
+                }
+                Meter::Uptime => {
+                    if let Some(uptime) = self.uptime() {
+                        cells.push(MeterCell::Text(Line::from(vec![
+                            Span::styled("Uptime: ", self.styles.table_header_style),
+                            Span::styled(format_time(uptime), self.styles.text_style),
+                        ])));
+                    }
+                }
+            }
+        }
+
+        cells
+    }
+
+    /// How long the system has been running for, if its boot time is known.
+    fn uptime(&self) -> Option<Duration> {
+        if self.boot_time == 0 {
+            return None;
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+        Some(Duration::from_secs(now.saturating_sub(self.boot_time)))
+    }
+}
+
+/// Returns the data to draw the meters with, which is the frozen data if
+/// frozen.
+fn meter_data(app_state: &App) -> &DataCollection {
+    match &app_state.frozen_state {
+        FrozenState::NotFrozen => &app_state.data_collection,
+        FrozenState::Frozen(data) => data,
+    }
+}
+
+/// Returns an unstyled gauge of the memory usage, like `Mem [|||  1.2GiB/7.7GiB]`.
+fn memory_gauge(label: &'static str, harvest: &MemHarvest) -> PipeGauge<'static> {
+    let (used, used_unit) = get_binary_bytes(harvest.used_bytes);
+    let (total, total_unit) = get_binary_bytes(harvest.total_bytes);
+
+    PipeGauge::default()
+        .ratio(harvest.checked_percent().unwrap_or(0.0) / 100.0)
+        .start_label(label)
+        .inner_label(format!("{used:.1}{used_unit}/{total:.1}{total_unit}"))
+}
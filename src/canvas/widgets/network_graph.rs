@@ -1,6 +1,5 @@
 use tui::{
     layout::{Constraint, Direction, Layout, Rect},
-    symbols::Marker,
     text::Text,
     widgets::{Block, Borders, Row, Table},
     Frame,
@@ -16,6 +15,10 @@ use crate::{
         drawing_utils::should_hide_x_label,
         Painter,
     },
+    data_collection::network::Duplex,
+    data_conversion::network_rate_string,
+    i18n::tr,
+    options::config::network::NetworkYAxis,
     utils::{data_prefixes::*, data_units::DataUnit, general::partial_ordering},
 };
 
@@ -56,14 +59,36 @@ impl Painter {
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
         hide_legend: bool,
     ) {
+        let styles = self.styles_for(widget_id);
+        let inspected_rates = app_state.inspected_data(widget_id).map(|data| {
+            let config = &app_state.app_config_fields;
+            (
+                network_rate_string(
+                    data.rx_data,
+                    &config.network_unit_type,
+                    config.network_use_binary_prefix,
+                ),
+                network_rate_string(
+                    data.tx_data,
+                    &config.network_unit_type,
+                    config.network_use_binary_prefix,
+                ),
+            )
+        });
+        let latest_time = app_state.displayed_time();
+
         if let Some(network_widget_state) =
             app_state.states.net_state.widget_states.get_mut(&widget_id)
         {
             let network_data_rx = &app_state.converted_data.network_data_rx;
             let network_data_tx = &app_state.converted_data.network_data_tx;
-            let time_start = -(network_widget_state.current_display_time as f64);
+            let time_start = -((network_widget_state.display_offset
+                + network_widget_state.current_display_time) as f64);
             let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
-            let x_bounds = [0, network_widget_state.current_display_time];
+            let x_bounds = [
+                network_widget_state.display_offset,
+                network_widget_state.display_offset + network_widget_state.current_display_time,
+            ];
             let hide_x_labels = should_hide_x_label(
                 app_state.app_config_fields.hide_time,
                 app_state.app_config_fields.autohide_time,
@@ -77,24 +102,89 @@ impl Painter {
             // - A new time interval is better and does not fit (check from end of vector to
             //   last checked; we only want to update if it is TOO big!)
 
+            let config = &app_state.app_config_fields;
+
             // Find the maximal rx/tx so we know how to scale, and return it.
-            let (_best_time, max_entry) = get_max_entry(
-                network_data_rx,
-                network_data_tx,
-                time_start,
-                &app_state.app_config_fields.network_scale_type,
-                app_state.app_config_fields.network_use_binary_prefix,
-            );
+            let y_range = |rx: &[Point], tx: &[Point]| {
+                let (_best_time, max_entry) = get_max_entry(
+                    rx,
+                    tx,
+                    time_start,
+                    &config.network_scale_type,
+                    config.network_use_binary_prefix,
+                );
+
+                adjust_network_data_point(
+                    max_entry,
+                    &config.network_scale_type,
+                    &config.network_unit_type,
+                    config.network_use_binary_prefix,
+                )
+            };
 
-            let (max_range, labels) = adjust_network_data_point(
-                max_entry,
-                &app_state.app_config_fields.network_scale_type,
-                &app_state.app_config_fields.network_unit_type,
-                app_state.app_config_fields.network_use_binary_prefix,
-            );
+            let link_speed =
+                app_state
+                    .converted_data
+                    .link
+                    .map(|(speed, _)| match config.network_unit_type {
+                        DataUnit::Byte => speed as f64 / 8.0,
+                        DataUnit::Bit => speed as f64,
+                    });
+
+            // When mirrored, RX is drawn above the axis and TX below it. Independent
+            // halves also scale TX so its maximum lines up with RX's. The link capacity
+            // is only used if it's known.
+            let (y_bounds, labels, scaled_points) = match (config.network_y_axis, link_speed) {
+                (NetworkYAxis::Capacity, Some(link_speed)) => {
+                    let scale = 100.0 / link_speed;
+
+                    (
+                        [0.0, 100.5],
+                        vec!["  0%".to_string(), "100%".to_string()],
+                        Some((
+                            scale_points(network_data_rx, scale),
+                            scale_points(network_data_tx, scale),
+                        )),
+                    )
+                }
+                (NetworkYAxis::Shared | NetworkYAxis::Capacity, _) => {
+                    let (max_range, labels) = y_range(network_data_rx, network_data_tx);
+                    ([0.0, max_range], labels, None)
+                }
+                (NetworkYAxis::Independent, _)
+                    if config.network_scale_type == AxisScaling::Linear =>
+                {
+                    let (rx_range, rx_labels) = y_range(network_data_rx, &[]);
+                    let (tx_range, tx_labels) = y_range(&[], network_data_tx);
+
+                    (
+                        [-rx_range, rx_range],
+                        mirror_labels(&unit_labels(rx_labels), &unit_labels(tx_labels)),
+                        Some((
+                            scale_points(network_data_rx, 1.0),
+                            scale_points(network_data_tx, -rx_range / tx_range),
+                        )),
+                    )
+                }
+                (NetworkYAxis::Mirrored | NetworkYAxis::Independent, _) => {
+                    let (max_range, labels) = y_range(network_data_rx, network_data_tx);
+
+                    (
+                        [-max_range, max_range],
+                        mirror_labels(&labels, &labels),
+                        Some((
+                            scale_points(network_data_rx, 1.0),
+                            scale_points(network_data_tx, -1.0),
+                        )),
+                    )
+                }
+            };
+            let (network_data_rx, network_data_tx) = match &scaled_points {
+                Some((rx, tx)) => (rx.as_slice(), tx.as_slice()),
+                None => (network_data_rx.as_slice(), network_data_tx.as_slice()),
+            };
 
             let y_labels = labels.iter().map(|label| label.into()).collect::<Vec<_>>();
-            let y_bounds = [0.0, max_range];
 
             let legend_constraints = if hide_legend {
                 (Constraint::Ratio(0, 1), Constraint::Ratio(0, 1))
@@ -103,73 +193,131 @@ impl Painter {
             };
 
             // TODO: Add support for clicking on legend to only show that value on chart.
-            let points = if app_state.app_config_fields.use_old_network_legend && !hide_legend {
+            let mut points = if app_state.app_config_fields.use_old_network_legend && !hide_legend {
                 vec![
                     GraphData {
                         points: network_data_rx,
-                        style: self.styles.rx_style,
-                        name: Some(format!("RX: {:7}", app_state.converted_data.rx_display).into()),
+                        style: styles.rx_style,
+                        name: Some(
+                            format!(
+                                "RX: {:7}",
+                                inspected_rates
+                                    .as_ref()
+                                    .map_or(&app_state.converted_data.rx_display, |(rx, _)| rx)
+                            )
+                            .into(),
+                        ),
+                        symbol: styles.series_symbol(0),
                     },
                     GraphData {
                         points: network_data_tx,
-                        style: self.styles.tx_style,
-                        name: Some(format!("TX: {:7}", app_state.converted_data.tx_display).into()),
+                        style: styles.tx_style,
+                        name: Some(
+                            format!(
+                                "TX: {:7}",
+                                inspected_rates
+                                    .as_ref()
+                                    .map_or(&app_state.converted_data.tx_display, |(_, tx)| tx)
+                            )
+                            .into(),
+                        ),
+                        symbol: styles.series_symbol(1),
                     },
                     GraphData {
                         points: &[],
-                        style: self.styles.total_rx_style,
+                        style: styles.total_rx_style,
                         name: Some(
                             format!("Total RX: {:7}", app_state.converted_data.total_rx_display)
                                 .into(),
                         ),
+                        symbol: None,
                     },
                     GraphData {
                         points: &[],
-                        style: self.styles.total_tx_style,
+                        style: styles.total_tx_style,
                         name: Some(
                             format!("Total TX: {:7}", app_state.converted_data.total_tx_display)
                                 .into(),
                         ),
+                        symbol: None,
                     },
                 ]
             } else {
                 vec![
                     GraphData {
                         points: network_data_rx,
-                        style: self.styles.rx_style,
-                        name: Some((&app_state.converted_data.rx_display).into()),
+                        style: styles.rx_style,
+                        name: Some(match &inspected_rates {
+                            Some((rx, _)) => format!("RX: {rx}").into(),
+                            None => (&app_state.converted_data.rx_display).into(),
+                        }),
+                        symbol: styles.series_symbol(0),
                     },
                     GraphData {
                         points: network_data_tx,
-                        style: self.styles.tx_style,
-                        name: Some((&app_state.converted_data.tx_display).into()),
+                        style: styles.tx_style,
+                        name: Some(match &inspected_rates {
+                            Some((_, tx)) => format!("TX: {tx}").into(),
+                            None => (&app_state.converted_data.tx_display).into(),
+                        }),
+                        symbol: styles.series_symbol(1),
                     },
                 ]
             };
 
-            let marker = if app_state.app_config_fields.use_dot {
-                Marker::Dot
-            } else {
-                Marker::Braille
-            };
+            if let Some((speed, duplex)) = app_state.converted_data.link {
+                let speed = network_rate_string(
+                    speed as f64,
+                    &config.network_unit_type,
+                    config.network_use_binary_prefix,
+                );
+                let name = match duplex {
+                    Some(Duplex::Full) => format!("Link: {speed}, full duplex"),
+                    Some(Duplex::Half) => format!("Link: {speed}, half duplex"),
+                    Some(Duplex::Unknown) | None => format!("Link: {speed}"),
+                };
+
+                points.push(GraphData {
+                    points: &[],
+                    style: styles.text_style,
+                    name: Some(name.into()),
+                    symbol: None,
+                });
+            }
 
-            TimeGraph {
+            let graph = TimeGraph {
                 x_bounds,
                 hide_x_labels,
+                time_format: &app_state.app_config_fields.time_format,
+                latest_time,
                 y_bounds,
                 y_labels: &y_labels,
-                graph_style: self.styles.graph_style,
+                graph_style: styles.graph_style,
                 border_style,
-                border_type: self.styles.border_type,
-                title: " Network ".into(),
+                border_type: styles.border_type,
+                title: network_widget_state
+                    .title
+                    .as_deref()
+                    .unwrap_or(tr(" Network "))
+                    .into(),
                 is_selected: app_state.current_widget.widget_id == widget_id,
                 is_expanded: app_state.is_expanded,
-                title_style: self.styles.widget_title_style,
+                title_style: styles.widget_title_style,
                 legend_position: app_state.app_config_fields.network_legend_position,
                 legend_constraints: Some(legend_constraints),
-                marker,
-            }
-            .draw_time_graph(f, draw_loc, &points);
+                marker: app_state.app_config_fields.graph_marker,
+                selection: app_state
+                    .time_range_selection
+                    .and_then(|selection| selection.x_range_for(widget_id)),
+                cursor: app_state
+                    .inspect_cursor
+                    .filter(|cursor| cursor.widget_id == widget_id)
+                    .map(|cursor| cursor.time),
+                gradient: app_state.app_config_fields.graph_gradients && self.truecolor,
+            };
+
+            network_widget_state.graph_area =
+                self.draw_graph(f, draw_loc, graph, &points, widget_id);
         }
     }
 
@@ -178,6 +326,7 @@ impl Painter {
     ) {
         const NETWORK_HEADERS: [&str; 4] = ["RX", "TX", "Total RX", "Total TX"];
 
+        let styles = self.styles_for(widget_id);
         let rx_display = &app_state.converted_data.rx_display;
         let tx_display = &app_state.converted_data.tx_display;
         let total_rx_display = &app_state.converted_data.total_rx_display;
@@ -185,10 +334,10 @@ impl Painter {
 
         // Gross but I need it to work...
         let total_network = vec![Row::new([
-            Text::styled(rx_display, self.styles.rx_style),
-            Text::styled(tx_display, self.styles.tx_style),
-            Text::styled(total_rx_display, self.styles.total_rx_style),
-            Text::styled(total_tx_display, self.styles.total_tx_style),
+            Text::styled(rx_display, styles.rx_style),
+            Text::styled(tx_display, styles.tx_style),
+            Text::styled(total_rx_display, styles.total_rx_style),
+            Text::styled(total_tx_display, styles.total_tx_style),
         ])];
 
         // Draw
@@ -200,20 +349,47 @@ impl Painter {
                     .map(Constraint::Length)
                     .collect::<Vec<_>>()),
             )
-            .header(Row::new(NETWORK_HEADERS).style(self.styles.table_header_style))
+            .header(Row::new(NETWORK_HEADERS).style(styles.table_header_style))
             .block(Block::default().borders(Borders::ALL).border_style(
                 if app_state.current_widget.widget_id == widget_id {
-                    self.styles.highlighted_border_style
+                    styles.highlighted_border_style
                 } else {
-                    self.styles.border_style
+                    styles.border_style
                 },
             ))
-            .style(self.styles.text_style),
+            .style(styles.text_style),
             draw_loc,
         );
     }
 }
 
+/// Returns the labels of a y-axis with `top` above zero and `bottom` mirrored
+/// below it. Both are expected to start at zero.
+fn mirror_labels(top: &[String], bottom: &[String]) -> Vec<String> {
+    bottom.iter().skip(1).rev().chain(top).cloned().collect()
+}
+
+/// Moves the unit of linear labels from the zero label to the largest one, so
+/// each half of an independent axis shows its own unit.
+fn unit_labels(mut labels: Vec<String>) -> Vec<String> {
+    let unit = labels[0].trim().trim_start_matches('0').to_string();
+    labels[0] = format!("{:>5}", "0");
+    if let Some(largest) = labels.last_mut() {
+        *largest = format!("{}{unit}", largest.trim());
+    }
+
+    labels
+}
+
+/// Scales points by `scale`, clamping them so they stay on their side of the
+/// axis when mirrored. Log values are negative for rates under one unit.
+fn scale_points(points: &[Point], scale: f64) -> Vec<Point> {
+    points
+        .iter()
+        .map(|&(time, value)| (time, value.max(0.0) * scale))
+        .collect()
+}
+
 /// Returns the max data point and time given a time.
 fn get_max_entry(
     rx: &[Point], tx: &[Point], time_start: f64, network_scale_type: &AxisScaling,
@@ -545,3 +721,31 @@ fn adjust_network_data_point(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn independent_labels() {
+        let rx = adjust_network_data_point(
+            2.0 * MEGA_LIMIT_F64,
+            &AxisScaling::Linear,
+            &DataUnit::Bit,
+            false,
+        )
+        .1;
+        let tx = adjust_network_data_point(
+            2.0 * KILO_LIMIT_F64,
+            &AxisScaling::Linear,
+            &DataUnit::Bit,
+            false,
+        )
+        .1;
+
+        assert_eq!(
+            mirror_labels(&unit_labels(rx), &unit_labels(tx)),
+            vec!["3.0Kb", "  2.0", "  1.0", "    0", "  1.0", "  2.0", "3.0Mb"]
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use tui::{
+    layout::{Constraint, Rect},
+    text::Line,
+    widgets::{Row, Sparkline, Table},
+    Frame,
+};
+
+use crate::{
+    app::App,
+    canvas::{drawing_utils::widget_block, Painter},
+    data_collection::plugins::PluginWidgetData,
+    i18n::tr,
+};
+
+impl Painter {
+    /// Draws the widget of the plugin named `name`.
+    pub fn draw_plugin(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64, name: &str,
+    ) {
+        let styles = self.styles_for(widget_id);
+        let is_selected = widget_id == app_state.current_widget.widget_id;
+        let border_style = if is_selected {
+            styles.highlighted_border_style
+        } else {
+            styles.border_style
+        };
+
+        let block = {
+            let mut block = widget_block(
+                app_state.app_config_fields.use_basic_mode,
+                is_selected,
+                styles.border_type,
+            )
+            .border_style(border_style)
+            .title_top(Line::styled(format!(" {name} "), styles.widget_title_style));
+
+            if app_state.is_expanded {
+                block = block.title_top(
+                    Line::styled(tr(" Esc to go back "), styles.widget_title_style).right_aligned(),
+                )
+            }
+
+            block
+        };
+
+        let inner_width = block.inner(draw_loc).width as usize;
+
+        match app_state.data_collection.plugin_data.get(name) {
+            Some(PluginWidgetData::Graph(points)) => {
+                // Sparklines only take integers, so scale everything to the
+                // largest value.
+                let max = points.iter().copied().fold(0.0, f64::max);
+                let data = points
+                    .iter()
+                    .skip(points.len().saturating_sub(inner_width))
+                    .map(|point| {
+                        if max > 0.0 {
+                            (point.max(0.0) / max * 100.0) as u64
+                        } else {
+                            0
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                f.render_widget(
+                    Sparkline::default()
+                        .block(block)
+                        .data(&data)
+                        .max(100)
+                        .style(styles.ram_style),
+                    draw_loc,
+                );
+            }
+            Some(PluginWidgetData::Table(rows)) => {
+                let headers = app_state
+                    .plugin_widget_headers(name)
+                    .map(<[String]>::to_vec)
+                    .unwrap_or_default();
+                let num_columns = rows
+                    .iter()
+                    .map(Vec::len)
+                    .chain(std::iter::once(headers.len()))
+                    .max()
+                    .unwrap_or(0)
+                    .max(1);
+                let widths = vec![Constraint::Ratio(1, num_columns as u32); num_columns];
+
+                let mut table = Table::new(
+                    rows.iter()
+                        .map(|row| Row::new(row.clone()).style(styles.text_style)),
+                    widths,
+                )
+                .block(block);
+
+                if !headers.is_empty() {
+                    table = table.header(Row::new(headers).style(styles.table_header_style));
+                }
+
+                f.render_widget(table, draw_loc);
+            }
+            None => f.render_widget(block, draw_loc),
+        }
+
+        if app_state.should_get_widget_bounds() {
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
@@ -8,12 +8,13 @@ use tui::{
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
-    app::{App, AppSearchState},
+    app::{layout_manager::BottomWidgetType, App, AppSearchState},
     canvas::{
         components::data_table::{DrawInfo, SelectionState},
         drawing_utils::widget_block,
         Painter,
     },
+    i18n::tr,
 };
 
 const SORT_MENU_WIDTH: u16 = 7;
@@ -73,6 +74,8 @@ impl Painter {
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
         let should_get_widget_bounds = app_state.should_get_widget_bounds();
+        let hover = app_state.hover_position(widget_id);
+        let empty_message = app_state.empty_message(&BottomWidgetType::Proc);
         if let Some(proc_widget_state) = app_state
             .states
             .proc_state
@@ -84,11 +87,23 @@ impl Painter {
 
             let is_on_widget = widget_id == app_state.current_widget.widget_id;
 
+            let empty_message = if proc_widget_state
+                .proc_search
+                .search_state
+                .is_invalid_or_blank_search()
+            {
+                empty_message
+            } else {
+                tr("No processes match the search").into()
+            };
+
             let draw_info = DrawInfo {
                 loc: draw_loc,
                 force_redraw: app_state.is_force_redraw,
                 recalculate_column_widths,
                 selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
+                hover,
+                empty_message: Some(empty_message),
             };
 
             proc_widget_state.table.draw(
@@ -150,6 +165,7 @@ impl Painter {
             }
         }
 
+        let styles = self.styles_for(widget_id);
         let is_basic = app_state.app_config_fields.use_basic_mode;
 
         if let Some(proc_widget_state) = app_state
@@ -178,17 +194,17 @@ impl Painter {
                 &proc_widget_state.proc_search.search_state,
                 available_width,
                 is_selected,
-                self.styles.selected_text_style,
-                self.styles.text_style,
+                styles.selected_text_style,
+                styles.text_style,
             );
 
             let mut search_text = vec![Line::from({
                 let mut search_vec = vec![Span::styled(
                     SEARCH_TITLE,
                     if is_selected {
-                        self.styles.table_header_style
+                        styles.table_header_style
                     } else {
-                        self.styles.text_style
+                        styles.text_style
                     },
                 )];
                 search_vec.extend(query_with_cursor);
@@ -198,21 +214,21 @@ impl Painter {
 
             // Text options shamelessly stolen from VS Code.
             let case_style = if !proc_widget_state.proc_search.is_ignoring_case {
-                self.styles.selected_text_style
+                styles.selected_text_style
             } else {
-                self.styles.text_style
+                styles.text_style
             };
 
             let whole_word_style = if proc_widget_state.proc_search.is_searching_whole_word {
-                self.styles.selected_text_style
+                styles.selected_text_style
             } else {
-                self.styles.text_style
+                styles.text_style
             };
 
             let regex_style = if proc_widget_state.proc_search.is_searching_with_regex {
-                self.styles.selected_text_style
+                styles.selected_text_style
             } else {
-                self.styles.text_style
+                styles.text_style
             };
 
             // TODO: [MOUSE] Mouse support for these in search
@@ -240,26 +256,26 @@ impl Painter {
                 } else {
                     ""
                 },
-                self.styles.invalid_query_style,
+                styles.invalid_query_style,
             )));
             search_text.push(option_text);
 
             let current_border_style =
                 if proc_widget_state.proc_search.search_state.is_invalid_search {
-                    self.styles.invalid_query_style
+                    styles.invalid_query_style
                 } else if is_selected {
-                    self.styles.highlighted_border_style
+                    styles.highlighted_border_style
                 } else {
-                    self.styles.border_style
+                    styles.border_style
                 };
 
             let process_search_block = {
-                let mut block = widget_block(is_basic, is_selected, self.styles.border_type)
+                let mut block = widget_block(is_basic, is_selected, styles.border_type)
                     .border_style(current_border_style);
 
                 if !is_basic {
                     block = block.title_top(
-                        Line::styled(" Esc to close ", current_border_style).right_aligned(),
+                        Line::styled(tr(" Esc to close "), current_border_style).right_aligned(),
                     )
                 }
 
@@ -275,7 +291,7 @@ impl Painter {
             f.render_widget(
                 Paragraph::new(search_text)
                     .block(process_search_block)
-                    .style(self.styles.text_style)
+                    .style(styles.text_style)
                     .alignment(Alignment::Left),
                 margined_draw_loc,
             );
@@ -300,6 +316,7 @@ impl Painter {
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
         let should_get_widget_bounds = app_state.should_get_widget_bounds();
+        let hover = app_state.hover_position(widget_id);
         if let Some(pws) = app_state
             .states
             .proc_state
@@ -315,6 +332,8 @@ impl Painter {
                 force_redraw: app_state.is_force_redraw,
                 recalculate_column_widths,
                 selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
+                hover,
+                empty_message: None,
             };
 
             pws.sort_table.draw(
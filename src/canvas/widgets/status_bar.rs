@@ -0,0 +1,97 @@
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::{
+    app::{frozen_state::FrozenState, App},
+    canvas::Painter,
+    data_collection::cpu::CpuDataType,
+    options::config::status_bar::{StatusBar, StatusBarField},
+};
+
+/// The separator drawn between status bar fields.
+const SEPARATOR: &str = " │ ";
+
+impl Painter {
+    /// Draws a one-line summary of the system, regardless of which widgets are
+    /// shown.
+    pub fn draw_status_bar(
+        &self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect, status_bar: &StatusBar,
+    ) {
+        let data = match &app_state.frozen_state {
+            FrozenState::NotFrozen => &app_state.data_collection,
+            FrozenState::Frozen(data) => data,
+        };
+
+        let mut spans = Vec::with_capacity(status_bar.fields.len() * 3);
+        for field in &status_bar.fields {
+            let (label, label_style, value) = match field {
+                StatusBarField::Hostname => match &self.hostname {
+                    Some(hostname) => ("", self.styles.widget_title_style, hostname.clone()),
+                    None => continue,
+                },
+                StatusBarField::Load => {
+                    // Load averages aren't available on Windows.
+                    if cfg!(target_family = "unix") {
+                        let [one, five, fifteen] = data.load_avg_harvest;
+                        (
+                            "Load ",
+                            self.styles.table_header_style,
+                            format!("{one:.2} {five:.2} {fifteen:.2}"),
+                        )
+                    } else {
+                        continue;
+                    }
+                }
+                StatusBarField::Cpu => {
+                    let cpus = &data.cpu_harvest;
+                    let usage = match cpus
+                        .iter()
+                        .find(|cpu| matches!(cpu.data_type, CpuDataType::Avg))
+                    {
+                        Some(avg) => avg.cpu_usage,
+                        None if cpus.is_empty() => 0.0,
+                        None => {
+                            cpus.iter().map(|cpu| cpu.cpu_usage).sum::<f64>() / cpus.len() as f64
+                        }
+                    };
+
+                    ("CPU ", self.styles.avg_cpu_colour, format!("{usage:.0}%"))
+                }
+                StatusBarField::Mem => (
+                    "Mem ",
+                    self.styles.ram_style,
+                    percentage(data.memory_harvest.checked_percent()),
+                ),
+                StatusBarField::Swap => (
+                    "Swap ",
+                    self.styles.swap_style,
+                    percentage(data.swap_harvest.checked_percent()),
+                ),
+            };
+
+            if !spans.is_empty() {
+                spans.push(Span::styled(SEPARATOR, self.styles.border_style));
+            }
+            if !label.is_empty() {
+                spans.push(Span::styled(label, label_style));
+                spans.push(Span::styled(value, self.styles.text_style));
+            } else {
+                spans.push(Span::styled(value, label_style));
+            }
+        }
+
+        f.render_widget(Paragraph::new(Line::from(spans)), draw_loc);
+    }
+}
+
+/// Formats a percentage, or a placeholder if there is none.
+fn percentage(value: Option<f64>) -> String {
+    match value {
+        Some(value) => format!("{value:.0}%"),
+        None => "N/A".to_string(),
+    }
+}
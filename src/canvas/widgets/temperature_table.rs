@@ -1,7 +1,7 @@
 use tui::{layout::Rect, Frame};
 
 use crate::{
-    app,
+    app::{self, layout_manager::BottomWidgetType},
     canvas::{
         components::data_table::{DrawInfo, SelectionState},
         Painter,
@@ -13,6 +13,8 @@ impl Painter {
         &self, f: &mut Frame<'_>, app_state: &mut app::App, draw_loc: Rect, widget_id: u64,
     ) {
         let recalculate_column_widths = app_state.should_get_widget_bounds();
+        let hover = app_state.hover_position(widget_id);
+        let empty_message = app_state.empty_message(&BottomWidgetType::Temp);
         if let Some(temp_widget_state) = app_state
             .states
             .temp_state
@@ -26,6 +28,8 @@ impl Painter {
                 force_redraw: app_state.is_force_redraw,
                 recalculate_column_widths,
                 selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
+                hover,
+                empty_message: Some(empty_message),
             };
 
             temp_widget_state.table.draw(
@@ -0,0 +1,71 @@
+//! bottom's data collection, for use as a library.
+//!
+//! This works without the rest of bottom; to leave out the terminal UI and its
+//! dependencies, build without default features (other features like
+//! `battery` or `gpu` can still be enabled):
+//!
+//! ```toml
+//! bottom = { version = "0.11", default-features = false, features = ["battery"] }
+//! ```
+//!
+//! Data is collected with a [`DataCollector`], which stores the latest data in
+//! [`DataCollector::data`] each time [`DataCollector::update_data`] is called:
+//!
+//! ```no_run
+//! use bottom::collection::{DataCollector, DataFilters, UsedWidgets};
+//!
+//! let mut collector = DataCollector::new(DataFilters {
+//!     disk_filter: None,
+//!     mount_filter: None,
+//!     temp_filter: None,
+//!     net_filter: None,
+//! });
+//!
+//! // Only collect what's needed.
+//! collector.set_data_collection(UsedWidgets {
+//!     use_cpu: true,
+//!     use_mem: true,
+//!     ..Default::default()
+//! });
+//! collector.init();
+//!
+//! loop {
+//!     collector.update_data();
+//!
+//!     if let Some(memory) = &collector.data.memory {
+//!         println!("{} / {} bytes used", memory.used_bytes, memory.total_bytes);
+//!     }
+//!
+//!     std::thread::sleep(std::time::Duration::from_secs(1));
+//! }
+//! ```
+//!
+//! Rates like CPU usage are measured since the previous update, so
+//! [`DataCollector::init`] should be called first; it collects once and waits
+//! a bit so that the first real update has something to compare against.
+//!
+//! [`Data`] and the harvest types it contains can be serialized with serde. To
+//! store or send data, wrap it in a [`VersionedData`], which records the
+//! version of the format and refuses to read data in an unknown one:
+//!
+//! ```
+//! use bottom::collection::{Data, VersionedData};
+//!
+//! let json = serde_json::to_string(&VersionedData::from(Data::default())).unwrap();
+//! let data: Data = serde_json::from_str::<VersionedData>(&json).unwrap().into();
+//! ```
+
+#[cfg(feature = "battery")]
+pub use crate::data_collection::batteries::{BatteryData, BatteryState};
+pub use crate::data_collection::{
+    cpu::{CpuData, CpuDataType, CpuHarvest, LoadAvgHarvest},
+    disks::{DiskHarvest, IoData, IoHarvest},
+    filter::Filter,
+    memory::MemHarvest,
+    network::NetworkHarvest,
+    processes::{Pid, ProcessHarvest},
+    source::DataSource,
+    temperature::{TempHarvest, TemperatureType},
+    CollectionTimings, Data, DataCollector, DataFilters, UsedWidgets, VersionedData,
+    DATA_FORMAT_VERSION,
+};
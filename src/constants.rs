@@ -9,10 +9,34 @@ pub const STALE_MIN_MILLISECONDS: u64 = 30 * 1000; // Lowest is 30 seconds
 pub const TIME_CHANGE_MILLISECONDS: u64 = 15 * 1000; // How much to increment each time
 pub const AUTOHIDE_TIMEOUT_MILLISECONDS: u64 = 5000; // 5 seconds to autohide
 
+// How far apart data is kept once it is downsampled for long-term retention
+pub const DOWNSAMPLE_INTERVAL_MILLISECONDS: u64 = 60 * 1000;
+
+// How many updates' worth of table data is kept to step back through while frozen
+pub const TABLE_HISTORY_LENGTH: usize = 60;
+
 // How fast the screen refreshes
 pub const DEFAULT_REFRESH_RATE_IN_MILLISECONDS: u64 = 1000;
+
+// How long without input before we are considered idle
+pub const IDLE_TIMEOUT_MILLISECONDS: u64 = 60 * 1000;
 pub const MAX_KEY_TIMEOUT_IN_MILLISECONDS: u64 = 1000;
 
+// The shortest time between redraws caused by input, which caps them at about
+// 60 per second
+pub const MIN_REDRAW_INTERVAL_IN_MILLISECONDS: u64 = 16;
+
+// How long resizes have to stop coming in before one is handled, and how long
+// a burst of them can hold it back for at most
+pub const RESIZE_SETTLE_IN_MILLISECONDS: u64 = 50;
+pub const RESIZE_MAX_WAIT_IN_MILLISECONDS: u64 = 250;
+
+// How large the log file can get before it's rotated
+pub const MAX_LOG_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+// How soon a second click has to follow the first to count as a double click
+pub const DOUBLE_CLICK_TIMEOUT_IN_MILLISECONDS: u64 = 500;
+
 // Limits for when we should stop showing table gaps/labels (anything less means
 // not shown)
 pub const TABLE_GAP_HEIGHT_LIMIT: u16 = 7;
@@ -23,7 +47,7 @@ pub const SIDE_BORDERS: Borders = Borders::LEFT.union(Borders::RIGHT);
 
 // Help text
 const HELP_CONTENTS_TEXT: [&str; 10] = [
-    "Either scroll or press the number key to go to the corresponding help menu section:",
+    "Either scroll or press the number key to go to the corresponding help menu section, or press / to search:",
     "1 - General",
     "2 - CPU widget",
     "3 - Process widget",
@@ -35,14 +59,16 @@ const HELP_CONTENTS_TEXT: [&str; 10] = [
     "9 - Basic memory widget",
 ];
 
-// TODO [Help]: Search in help?
+// Keys for actions that can be remapped aren't listed here, as the help menu
+// adds them with whatever keys they are bound to.
 // TODO [Help]: Move to using tables for easier formatting?
-pub(crate) const GENERAL_HELP_TEXT: [&str; 32] = [
+pub(crate) const GENERAL_HELP_TEXT: [&str; 28] = [
     "1 - General",
-    "q, Ctrl-c        Quit",
+    "Ctrl-c           Quit",
+    "Ctrl-z           Suspend bottom, on Unix-like systems",
     "Esc              Close dialog windows, search, widgets, or exit expanded mode",
-    "Ctrl-r           Reset display and any collected data",
-    "f                Freeze/unfreeze updating with new data",
+    "[, ]             Step back/forward through graph and table history while frozen",
+    "{, }             Step back/forward through history by the zoom time delta while frozen",
     "Ctrl-Left,       ",
     "Shift-Left,      Move widget selection left",
     "H, A             ",
@@ -59,13 +85,8 @@ pub(crate) const GENERAL_HELP_TEXT: [&str; 32] = [
     "Down, j          Move down within widget",
     "Up, k            Move up within widget",
     "Right, l         Move right within widget",
-    "?                Open help menu",
     "gg               Jump to the first entry",
     "G                Jump to the last entry",
-    "e                Toggle expanding the currently selected widget",
-    "+                Zoom in on chart (decrease time range)",
-    "-                Zoom out on chart (increase time range)",
-    "=                Reset zoom",
     "PgUp, PgDown     Scroll up/down a table by a page",
     "Ctrl-u, Ctrl-d   Scroll up/down a table by half a page",
     "Mouse scroll     Scroll through the tables or zoom in/out of charts by scrolling up/down",
@@ -77,21 +98,18 @@ const CPU_HELP_TEXT: [&str; 2] = [
     "Mouse scroll     Scrolling over an CPU core/average shows only that entry on the chart",
 ];
 
-const PROCESS_HELP_TEXT: [&str; 17] = [
+const PROCESS_HELP_TEXT: [&str; 14] = [
     "3 - Process widget",
-    "dd, F9           Kill the selected process",
+    "dd               Kill the selected process",
     "c                Sort by CPU usage, press again to reverse",
     "m                Sort by memory usage, press again to reverse",
     "p                Sort by PID name, press again to reverse",
     "n                Sort by process name, press again to reverse",
     "Tab              Group/un-group processes with the same name",
-    "Ctrl-f, /        Open process search widget",
     "P                Toggle between showing the full command or just the process name",
-    "s, F6            Open process sort widget",
-    "I                Invert current sort",
-    "%                Toggle between values and percentages for memory usage",
-    "t, F5            Toggle tree mode",
-    "+, -, click      Collapse/expand a branch while in tree mode",
+    "s                Open process sort widget",
+    "t                Toggle tree mode",
+    "Double-click     Collapse/expand a branch while in tree mode, by default",
     "click on header  Sorts the entries by that column, click again to invert the sort",
     "C                Sort by GPU usage, press again to reverse",
     "M                Sort by GPU memory usage, press again to reverse",
@@ -107,9 +125,6 @@ const SEARCH_HELP_TEXT: [&str; 51] = [
     "Ctrl-h           Delete the character behind the cursor",
     "Backspace        Delete the character behind the cursor",
     "Delete           Delete the character at the cursor",
-    "Alt-c, F1        Toggle matching case",
-    "Alt-w, F2        Toggle matching the entire word",
-    "Alt-r, F3        Toggle using regex",
     "Left, Alt-h      Move cursor left",
     "Right, Alt-l     Move cursor right",
     "",
@@ -128,6 +143,9 @@ const SEARCH_HELP_TEXT: [&str; 51] = [
     "gpu%             ex: gpu% < 4.2",
     "gmem             ex: gmem < 100 kb",
     "gmem%            ex: gmem% < 4.2",
+    "pidns            ex: pidns = 4026531836",
+    "netns            ex: netns = 4026531840",
+    "mntns            ex: mntns = 4026531841",
     "",
     "Comparison operators:",
     "=                ex: cpu = 1",
@@ -184,10 +202,7 @@ const BATTERY_HELP_TEXT: [&str; 3] = [
     "Right            Go to next battery",
 ];
 
-const BASIC_MEM_HELP_TEXT: [&str; 2] = [
-    "9 - Basic memory widget",
-    "%                Toggle between values and percentages for memory usage",
-];
+const BASIC_MEM_HELP_TEXT: [&str; 1] = ["9 - Basic memory widget"];
 
 pub(crate) const HELP_TEXT: [&[&str]; HELP_CONTENTS_TEXT.len()] = [
     &HELP_CONTENTS_TEXT,
@@ -256,11 +271,94 @@ pub(crate) const DEFAULT_BATTERY_LAYOUT: &str = r#"
     default=true
 "#;
 
+/// The default layout under WSL, which has no temperature sensors to show.
+pub(crate) const DEFAULT_WSL_LAYOUT: &str = r#"
+[[row]]
+  ratio=30
+  [[row.child]]
+  type="cpu"
+[[row]]
+    ratio=40
+    [[row.child]]
+      ratio=4
+      type="mem"
+    [[row.child]]
+      ratio=3
+      type="disk"
+[[row]]
+  ratio=30
+  [[row.child]]
+    type="net"
+  [[row.child]]
+    type="proc"
+    default=true
+"#;
+
+/// The `minimal` preset, with just CPU, memory, and processes.
+pub(crate) const MINIMAL_PRESET_LAYOUT: &str = r#"
+[[row]]
+  ratio=1
+  [[row.child]]
+    type="cpu"
+  [[row.child]]
+    type="mem"
+[[row]]
+  ratio=2
+  [[row.child]]
+    type="proc"
+    default=true
+"#;
+
+/// The `netadmin` preset, which focuses on network usage.
+pub(crate) const NETADMIN_PRESET_LAYOUT: &str = r#"
+[[row]]
+  ratio=50
+  [[row.child]]
+    type="net"
+    default=true
+[[row]]
+  ratio=50
+  [[row.child]]
+    ratio=2
+    type="proc"
+  [[row.child]]
+    ratio=1
+    [[row.child.child]]
+      type="cpu"
+    [[row.child.child]]
+      type="disk"
+"#;
+
+/// The `battery` preset, which focuses on battery life and what drains it.
+#[cfg(feature = "battery")]
+pub(crate) const BATTERY_PRESET_LAYOUT: &str = r#"
+[[row]]
+  ratio=35
+  [[row.child]]
+    type="battery"
+    default=true
+  [[row.child]]
+    type="temp"
+[[row]]
+  ratio=25
+  [[row.child]]
+    type="cpu"
+[[row]]
+  ratio=40
+  [[row.child]]
+    type="proc"
+"#;
+
 // TODO: Eventually deprecate this, or grab from a file.
 pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bottom. All of the settings are commented
 # out by default; if you wish to change them uncomment and modify as you see
 # fit.
 
+# Other config files to merge into this one, such as themes or layouts shared
+# across machines. Relative paths are relative to this file, and settings in
+# this file take priority over included ones.
+#include = ["~/.config/bottom/colors.toml", "layout.toml"]
+
 # This group of options represents a command-line option. Flags explicitly
 # added when running (ie: btm -a) will override this config file if an option
 # is also set here.
@@ -271,6 +369,9 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Whether to use dot markers rather than braille.
 #dot_marker = false
 
+# How graph lines are drawn. One of "braille", "dot", "block", or "line". Takes precedence over dot_marker.
+#graph_style = "braille"
+
 # The update rate of the application.
 #rate = "1s"
 
@@ -280,6 +381,9 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Whether to set CPU% on a process to be based on the total CPU or just current usage.
 #current_usage = false
 
+# The column processes are sorted by at first, with an optional order of "asc" or "desc".
+#default_sort = "cpu%:desc"
+
 # Whether to set CPU% on a process to be based on the total CPU or per-core CPU% (not divided by the number of cpus).
 #unnormalized_cpu = false
 
@@ -312,6 +416,9 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Hides the time scale.
 #hide_time = false
 
+# How times on graphs are shown: "relative" (e.g. 60s), "clock" (e.g. 14:05:30), or a custom format like "[hour]:[minute]".
+#time_format = "relative"
+
 # Override layout default widget
 #default_widget_type = "proc"
 #default_widget_count = 1
@@ -334,15 +441,35 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Disable mouse clicks
 #disable_click = false
 
+# What double-clicking a process, disk, or temperature entry does. One of "collapse", "details", "kill", or "none".
+#double_click_action = "collapse"
+
+# Where to dump the current state as JSON when sent SIGUSR1. Defaults to "bottom-<PID>.json" in the temporary directory.
+#dump_path = "/tmp/bottom.json"
+
+# The language of the interface, one of "en", "de", or "es". Defaults to the language set by LC_ALL, LC_MESSAGES, or LANG.
+#locale = "en"
+
 # Show memory values in the processes widget as values by default
 #process_memory_as_value = false
 
 # Show tree mode by default in the processes widget.
 #tree = false
+# Show the totals of each branch on its parent in tree mode by default.
+#tree_totals = false
 
 # Shows an indicator in table widgets tracking where in the list you are.
 #show_table_scroll_position = false
 
+# Shows a scrollbar in table widgets with more entries than can fit.
+#show_table_scrollbar = false
+
+# Alternates the background of rows in the process, disk, and temperature widgets.
+#table_stripes = false
+
+# Draws a line between the rows of the process, disk, and temperature widgets.
+#table_row_separators = false
+
 # Show processes as their commands by default in the process widget.
 #process_command = false
 
@@ -367,17 +494,62 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # How much data is stored at once in terms of time.
 #retention = "10m"
 
+# How much downsampled data is stored at once in terms of time. Data older than the retention duration is downsampled into one point per minute, keeping its average, minimum, and maximum, and kept until it is older than this.
+#long_term_retention = "1d"
+
 # Where to place the legend for the memory widget. One of "none", "top-left", "top", "top-right", "left", "right", "bottom-left", "bottom", "bottom-right".
 #memory_legend = "top-right"
 
 # Where to place the legend for the network widget. One of "none", "top-left", "top", "top-right", "left", "right", "bottom-left", "bottom", "bottom-right".
 #network_legend = "top-right"
 
+# Serves the current data over HTTP at the given address, at "/metrics.json" and "/healthz".
+#http = "127.0.0.1:8080"
+
+# How often data is refreshed while the terminal is unfocused or there has been no input for a minute.
+#idle_rate = "5s"
+
+# Pauses data collection while the terminal is unfocused.
+#pause_on_blur = false
+
+# Uses larger click targets, such as taller table rows, for touchscreens.
+#mobile = false
+
+# Draws inline in the scrollback instead of on the alternate screen, leaving the last frame visible after quitting.
+#no_alt_screen = false
+
+# Saves the session state, such as sort columns and search queries, on exit and restores it on the next launch.
+#save_session = false
+
+# Uses a built-in layout instead of the default one or [[row]] entries. One of "minimal", "netadmin", or "battery".
+#preset = "minimal"
+
+# Fills the area under graph lines with gradients, if the terminal supports 24-bit colour.
+#graph_gradients = false
+
+# Shows a status bar with the hostname, load average, and CPU, memory, and swap usage. See [status_bar] below.
+#status_bar = false
+
+# Shows a header of htop-style meters for CPU, memory, and swap usage, load average, and uptime. See [meter_header] below.
+#meter_header = false
+
+# Caps roughly how much memory the history behind the graphs may use, in MiB, dropping the oldest data early if needed.
+#max_history_memory = 64
+
+# If there are more processes than this, only keeps this many, picking the ones using the most CPU.
+#max_processes = 2000
+
+# Only draws ASCII characters. Defaults to true if the terminal or locale doesn't seem to support Unicode.
+#ascii = false
+
+# How many colours the terminal can show, one of 16, 256, or "truecolor". Detected from COLORTERM, TERM, and terminfo by default.
+#color_depth = 256
+
 
 # Processes widget configuration
 #[processes]
-# The columns shown by the process widget. The following columns are supported (the GPU columns are only available if the GPU feature is enabled when built):
-# PID, Name, CPU%, Mem%, R/s, W/s, T.Read, T.Write, User, State, Time, GMem%, GPU%
+# The columns shown by the process widget. The following columns are supported (the GPU columns are only available if the GPU feature is enabled when built, and Label, PidNS, NetNS, MntNS, CPUWait%, IOWait%, and IOPrio only on Linux):
+# PID, Name, CPU%, Mem%, R/s, W/s, T.Read, T.Write, User, State, Time, GMem%, GPU%, Label, PidNS, NetNS, MntNS, CPUWait%, IOWait%, IOPrio
 #columns = ["PID", "Name", "CPU%", "Mem%", "R/s", "W/s", "T.Read", "T.Write", "User", "State", "GMem%", "GPU%"]
 
 
@@ -387,12 +559,54 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #default = "average"
 
 
+# Basic mode configuration
+#[basic]
+# The tables that can be switched between, in order. The number keys 1 to 9 also switch to them directly.
+# Batteries are only shown if the battery flag is enabled. The following tables are supported:
+# proc, disk, temp, battery
+#tables = ["disk", "proc", "temp", "battery"]
+
+
+# Status bar configuration, if enabled with the status_bar flag
+#[status_bar]
+# One of "top" or "bottom" (default)
+#position = "bottom"
+# The values shown, in order. The following values are supported:
+# hostname, load, cpu, mem, swap
+#fields = ["hostname", "load", "cpu", "mem", "swap"]
+
+
+# Meter header configuration, if enabled with the meter_header flag
+#[meter_header]
+# The meters shown, in order. The following meters are supported:
+# cpu, cores, mem, swap, load, uptime
+#meters = ["cpu", "mem", "swap", "load", "uptime"]
+
+
+# Mouse configuration
+#[mouse]
+# How many rows tables move by per scroll
+#table_scroll_rows = 1
+# What scrolling over a graph does. One of "zoom" (default), "scrub", or "none"
+#graph_scroll = "zoom"
+# Whether to swap scrolling up and down
+#invert_scroll = false
+# How long to ignore further scrolls for after one
+#scroll_debounce = "20ms"
+
+
 # Disk widget configuration
 #[disk]
 # The columns shown by the process widget. The following columns are supported:
 # Disk, Mount, Used, Free, Total, Used%, Free%, R/s, W/s
 #columns = ["Disk", "Mount", "Used", "Free", "Total", "Used%", "R/s", "W/s"]
 
+# Mount points that are always shown first, in this order.
+#pinned = ["/", "/home"]
+
+# The mount point whose usage is shown as a gauge in basic mode. Defaults to the first pinned mount point.
+#focus = "/"
+
 # By default, there are no disk name filters enabled. These can be turned on to filter out specific data entries if you
 # don't want to see them. An example use case is provided below.
 #[disk.name_filter]
@@ -431,6 +645,11 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 
 # Temperature widget configuration
 #[temperature]
+# On Linux, also lists voltages (such as PSU rails) and power readings (such as package power) from hwmon alongside
+# the temperature sensors.
+#voltages = false
+#power = false
+
 # By default, there are no temperature sensor filters enabled. An example use case is provided below.
 #[temperature.sensor_filter]
 # Whether to ignore any matches. Defaults to true.
@@ -451,6 +670,14 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 
 # Network widget configuration
 #[network]
+# How RX and TX share the graph's y-axis. One of "shared" (default), "mirrored" (RX above the axis, TX below it),
+# "independent" (mirrored, with each half scaled to its own data), or "capacity" (a percentage of the link speed).
+#y_axis = "shared"
+
+# On Linux, also counts the interfaces of other network namespaces, such as those of containers. They're named
+# "<namespace>/<interface>" for filtering. Seeing other users' namespaces usually requires root.
+#namespaces = false
+
 # By default, there are no network interface filters enabled. An example use case is provided below.
 #[network.interface_filter]
 # Whether to ignore any matches. Defaults to true.
@@ -469,6 +696,56 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #whole_word = false
 
 
+# Plugin configuration. Plugins are experimental, and require bottom to be built with the "plugins" feature.
+#[plugins]
+# The directory to load .lua plugins from.
+#directory = "/path/to/plugins"
+
+
+# Key bindings. Each action takes a key or a list of keys, which replaces its default keys.
+# Keys are written like "q", "F9", "ctrl+f", or "alt+shift+c". A key can't be bound to two actions.
+#[keybinds]
+#quit = "q"
+#search = ["/", "ctrl+f"]
+#kill = "F9"
+#tree_toggle = "F5"
+#sort_menu = "F6"
+#help = "?"
+#expand = "e"
+#freeze = "f"
+#reset = "ctrl+r"
+#zoom_in = "+"
+#zoom_out = "-"
+#reset_zoom = "="
+#collapse_branch = "z"
+#collapse_all = "_"
+#expand_all = "*"
+#tree_totals = "T"
+#group_by_user = "U"
+#pin_mount = "b"
+#cycle_mount = "o"
+#hide_sensor = "v"
+#show_hidden_sensors = "V"
+#invert_sort = "I"
+#inspect = "i"
+#percentages = "%"
+#debug_overlay = "F12"
+#log_level = "F4"
+#diagnostics = "ctrl+o"
+#export_config = "ctrl+s"
+#next_profile = "ctrl+p"
+#ignore_case = ["F1", "alt+c"]
+#whole_word = ["F2", "alt+w"]
+#regex = ["F3", "alt+r"]
+#next_page = ">"
+#previous_page = "<"
+#hide_widget = "x"
+#show_widgets = "X"
+#copy_row = "y"
+#copy_pid = "Y"
+#copy_command = "ctrl+y"
+
+
 # These are all the components that support custom theming.  Note that colour support
 # will depend on terminal support.
 #[styles] # Uncomment if you want to use custom styling
@@ -478,7 +755,12 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # - "gruvbox"
 # - "gruvbox-light"
 # - "nord"
-# - "nord-light".
+# - "nord-light"
+# - "dracula"
+# - "solarized"
+# - "high-contrast"
+#
+# This can also be a path to a base16 YAML scheme, ending in ".yaml" or ".yml".
 #
 # This will have the lowest precedence if a custom colour palette is set,
 # or overridden if the command-line flag for a built-in theme is set.
@@ -509,10 +791,13 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 
 #[styles.tables]
 #headers = {color = "light blue", bold = true}
+#stripes = {bg_color = "dark gray"}
 
 #[styles.graphs]
 #graph_color = "gray"
 #legend_text = {color = "gray"}
+# How graph series are told apart: "default", "deuteranopia", "protanopia", or "monochrome".
+#series_mode = "default"
 
 #[styles.widgets]
 #border_color = "gray"
@@ -529,6 +814,8 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #
 # All widgets must have the type value set to one of ["cpu", "mem", "proc", "net", "temp", "disk", "empty"].
 # All layout components have a ratio value - if this is not set, then it defaults to 1.
+# Widgets can also set a title, and process widgets a filter, which is the search query they start with.
+# Widgets can also have their own styles under [row.child.styles], which take the same options as [styles].
 # The default widget layout:
 #[[row]]
 #  ratio=30
@@ -552,6 +839,25 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #  [[row.child]]
 #    type="proc"
 #    default=true
+
+# More layouts can be added as named pages, which can be switched between with '<' and '>'.
+# If [[row]] is also set, it is the first page, named "main".
+#[[page]]
+#  name="network"
+#  [[page.row]]
+#    ratio=2
+#    [[page.row.child]]
+#      type="net"
+#  [[page.row]]
+#    [[page.row.child]]
+#      type="proc"
+
+# Profiles override the settings above when selected with --profile, or switched to while running
+# with ctrl+p. They can set anything above except for includes and plugins.
+#[profile.laptop.flags]
+#rate = "5s"
+#[profile.laptop.styles]
+#theme = "gruvbox"
 "#;
 
 #[cfg(test)]
@@ -570,9 +876,10 @@ mod test {
 
     #[test]
     fn help_menu_text_has_sections() {
+        // Sections can be just a header, as remappable keys are added when drawn.
         for (itx, line) in HELP_TEXT.iter().enumerate() {
             if itx > 0 {
-                assert!(line.len() >= 2, "each section should be at least 2 lines");
+                assert!(!line.is_empty(), "each section should have a header");
                 assert!(line[0].contains(" - "), "each section should have a header");
             }
         }
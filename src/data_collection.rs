@@ -11,10 +11,20 @@ pub mod batteries;
 pub mod cpu;
 pub mod disks;
 pub mod error;
+pub mod filter;
+#[cfg(any(target_os = "illumos", test))]
+mod kstat;
 pub mod memory;
 pub mod network;
+#[cfg(feature = "plugins")]
+pub mod plugins;
 pub mod processes;
+pub mod source;
+#[cfg(feature = "app")]
+pub mod synthetic;
 pub mod temperature;
+#[cfg(target_os = "linux")]
+pub mod wsl;
 
 use std::time::{Duration, Instant};
 
@@ -22,16 +32,65 @@ use std::time::{Duration, Instant};
 use hashbrown::HashMap;
 #[cfg(not(target_os = "windows"))]
 use processes::Pid;
-#[cfg(feature = "battery")]
-use starship_battery::{Battery, Manager};
+use serde::{Deserialize, Serialize};
+
+use self::{
+    filter::Filter,
+    source::DataSource,
+    temperature::{ExtraSensors, TemperatureType},
+};
+use crate::utils::interner::StringInterner;
+
+/// How long each part of a collection took, used for debugging.
+#[derive(Clone, Debug, Default)]
+pub struct CollectionTimings {
+    /// How long each data source took to collect, in the order collected.
+    pub sources: Vec<(&'static str, Duration)>,
+    /// How each registered data source fared, in the order registered.
+    pub statuses: Vec<(&'static str, SourceStatus)>,
+    /// Problems sources ran into that didn't stop collection, in the order
+    /// they were found.
+    pub issues: Vec<CollectionIssue>,
+    /// When collection finished.
+    pub finished: Option<Instant>,
+}
 
-use self::temperature::TemperatureType;
-use super::DataFilters;
-use crate::app::layout_manager::UsedWidgets;
+/// How a data source fared in a collection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SourceStatus {
+    /// The source was collected from and got data.
+    Active,
+    /// The source was collected from, but didn't get any data (e.g. because it
+    /// isn't supported, or reading it failed).
+    NoData,
+    /// The source wasn't collected from, as no widget uses it.
+    Disabled,
+}
+
+/// A problem a data source ran into that didn't stop collection, such as not
+/// being allowed to read some files, which would otherwise just show up as
+/// missing data.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CollectionIssue {
+    /// The name of the source that ran into the problem.
+    pub source: &'static str,
+    /// What went wrong.
+    pub message: String,
+}
 
-#[derive(Clone, Debug)]
+/// The data from a single collection. Anything that wasn't collected (e.g.
+/// because it wasn't needed or isn't supported) is [`None`].
+///
+/// This can be serialized with serde, but to store or send it, use
+/// [`VersionedData`] so that the format can be checked when reading it back.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Data {
+    /// When this was collected. This is not serialized, and is set to when it
+    /// was deserialized instead.
+    #[serde(skip, default = "Instant::now")]
     pub collection_time: Instant,
+    #[serde(skip)]
+    pub timings: CollectionTimings,
     pub cpu: Option<cpu::CpuHarvest>,
     pub load_avg: Option<cpu::LoadAvgHarvest>,
     pub memory: Option<memory::MemHarvest>,
@@ -49,12 +108,45 @@ pub struct Data {
     pub arc: Option<memory::MemHarvest>,
     #[cfg(feature = "gpu")]
     pub gpu: Option<Vec<(String, memory::MemHarvest)>>,
+    #[cfg(feature = "plugins")]
+    pub plugins: Option<Vec<(String, plugins::PluginWidgetHarvest)>>,
+}
+
+/// The current version of the serialized format of [`Data`].
+pub const DATA_FORMAT_VERSION: &str = "1";
+
+/// [`Data`], tagged with the version of its serialized format.
+///
+/// This serializes as the fields of [`Data`] along with a `"version"` field,
+/// which is currently [`DATA_FORMAT_VERSION`]. Any change to the format that
+/// older versions of bottom couldn't read will bump the version, and data in
+/// an unknown version will fail to deserialize rather than be misread.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "version")]
+pub enum VersionedData {
+    #[serde(rename = "1")]
+    V1(Data),
+}
+
+impl From<Data> for VersionedData {
+    fn from(data: Data) -> Self {
+        VersionedData::V1(data)
+    }
+}
+
+impl From<VersionedData> for Data {
+    fn from(data: VersionedData) -> Self {
+        match data {
+            VersionedData::V1(data) => data,
+        }
+    }
 }
 
 impl Default for Data {
     fn default() -> Self {
         Data {
             collection_time: Instant::now(),
+            timings: CollectionTimings::default(),
             cpu: None,
             load_avg: None,
             memory: None,
@@ -72,12 +164,15 @@ impl Default for Data {
             arc: None,
             #[cfg(feature = "gpu")]
             gpu: None,
+            #[cfg(feature = "plugins")]
+            plugins: None,
         }
     }
 }
 
 impl Data {
     pub fn cleanup(&mut self) {
+        self.timings = CollectionTimings::default();
         self.io = None;
         self.temperature_sensors = None;
         self.list_of_processes = None;
@@ -98,6 +193,10 @@ impl Data {
         {
             self.gpu = None;
         }
+        #[cfg(feature = "plugins")]
+        {
+            self.plugins = None;
+        }
     }
 }
 
@@ -108,13 +207,13 @@ impl Data {
 /// - Network usage
 /// - Processes (non-Linux)
 /// - Disk (anything outside of Linux, macOS, and FreeBSD)
-/// - Temperatures (non-Linux)
+/// - Temperatures (non-Linux, non-macOS)
 #[derive(Debug)]
 pub struct SysinfoSource {
     /// Handles CPU, memory, and processes.
     pub(crate) system: sysinfo::System,
     pub(crate) network: sysinfo::Networks,
-    #[cfg(not(target_os = "linux"))]
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
     pub(crate) temps: sysinfo::Components,
     #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")))]
     pub(crate) disks: sysinfo::Disks,
@@ -129,7 +228,7 @@ impl Default for SysinfoSource {
         Self {
             system: System::new_with_specifics(RefreshKind::new()),
             network: Networks::new(),
-            #[cfg(not(target_os = "linux"))]
+            #[cfg(not(any(target_os = "linux", target_os = "macos")))]
             temps: Components::new(),
             #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")))]
             disks: Disks::new(),
@@ -139,6 +238,90 @@ impl Default for SysinfoSource {
     }
 }
 
+/// Filters for what data to collect.
+#[derive(Debug, Clone)]
+pub struct DataFilters {
+    pub disk_filter: Option<Filter>,
+    pub mount_filter: Option<Filter>,
+    pub temp_filter: Option<Filter>,
+    pub net_filter: Option<Filter>,
+}
+
+/// Which kinds of data to collect, named after the widgets that use them.
+#[derive(Clone, Default, Debug, Copy)]
+pub struct UsedWidgets {
+    pub use_cpu: bool,
+    pub use_mem: bool,
+    pub use_cache: bool,
+    pub use_gpu: bool,
+    pub use_net: bool,
+    pub use_proc: bool,
+    pub use_disk: bool,
+    pub use_temp: bool,
+    pub use_battery: bool,
+}
+
+impl UsedWidgets {
+    /// Returns the widgets used in both `self` and `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            use_cpu: self.use_cpu && other.use_cpu,
+            use_mem: self.use_mem && other.use_mem,
+            use_cache: self.use_cache && other.use_cache,
+            use_gpu: self.use_gpu && other.use_gpu,
+            use_net: self.use_net && other.use_net,
+            use_proc: self.use_proc && other.use_proc,
+            use_disk: self.use_disk && other.use_disk,
+            use_temp: self.use_temp && other.use_temp,
+            use_battery: self.use_battery && other.use_battery,
+        }
+    }
+
+    /// Returns the widgets used in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        Self {
+            use_cpu: self.use_cpu && !other.use_cpu,
+            use_mem: self.use_mem && !other.use_mem,
+            use_cache: self.use_cache && !other.use_cache,
+            use_gpu: self.use_gpu && !other.use_gpu,
+            use_net: self.use_net && !other.use_net,
+            use_proc: self.use_proc && !other.use_proc,
+            use_disk: self.use_disk && !other.use_disk,
+            use_temp: self.use_temp && !other.use_temp,
+            use_battery: self.use_battery && !other.use_battery,
+        }
+    }
+
+    /// Returns the widgets used in either `self` or `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            use_cpu: self.use_cpu || other.use_cpu,
+            use_mem: self.use_mem || other.use_mem,
+            use_cache: self.use_cache || other.use_cache,
+            use_gpu: self.use_gpu || other.use_gpu,
+            use_net: self.use_net || other.use_net,
+            use_proc: self.use_proc || other.use_proc,
+            use_disk: self.use_disk || other.use_disk,
+            use_temp: self.use_temp || other.use_temp,
+            use_battery: self.use_battery || other.use_battery,
+        }
+    }
+
+    /// Returns whether no widgets are used.
+    pub fn is_empty(&self) -> bool {
+        !(self.use_cpu
+            || self.use_mem
+            || self.use_cache
+            || self.use_gpu
+            || self.use_net
+            || self.use_proc
+            || self.use_disk
+            || self.use_temp
+            || self.use_battery)
+    }
+}
+
+/// Collects data from the system, from each of its [`DataSource`]s.
 #[derive(Debug)]
 pub struct DataCollector {
     pub data: Data,
@@ -147,27 +330,44 @@ pub struct DataCollector {
     use_current_cpu_total: bool,
     unnormalized_cpu: bool,
     last_collection_time: Instant,
-    total_rx: u64,
-    total_tx: u64,
     show_average_cpu: bool,
     widgets_to_harvest: UsedWidgets,
     filters: DataFilters,
+    /// Whether to also count the interfaces of other network namespaces.
+    network_namespaces: bool,
+    /// Which readings to collect alongside temperatures.
+    extra_sensors: ExtraSensors,
+    /// If set, only this many processes are kept, by CPU usage.
+    max_processes: Option<usize>,
 
     #[cfg(target_os = "linux")]
     pid_mapping: HashMap<Pid, processes::PrevProcDetails>,
     #[cfg(target_os = "linux")]
+    prev_pid_mapping: HashMap<Pid, processes::PrevProcDetails>,
+    #[cfg(target_os = "linux")]
+    proc_buffer: String,
+    #[cfg(target_os = "linux")]
     prev_idle: f64,
     #[cfg(target_os = "linux")]
     prev_non_idle: f64,
+    /// The socket to get delay accounting from, if it can be used.
+    #[cfg(target_os = "linux")]
+    task_stats: Option<processes::TaskStats>,
+    /// Which optional details to collect for each process.
+    #[cfg(target_os = "linux")]
+    process_fields: processes::ProcessFields,
 
-    #[cfg(feature = "battery")]
-    battery_manager: Option<Manager>,
-    #[cfg(feature = "battery")]
-    battery_list: Option<Vec<Battery>>,
+    /// The sources to collect from, in order.
+    sources: Vec<Box<dyn DataSource>>,
+    /// The name of the source being collected from, for any issues it reports.
+    current_source: &'static str,
 
     #[cfg(target_family = "unix")]
     user_table: processes::UserTable,
 
+    /// Shared strings for process names, commands, and users.
+    interner: StringInterner,
+
     #[cfg(feature = "gpu")]
     gpu_pids: Option<Vec<HashMap<u32, (u64, u32)>>>,
     #[cfg(feature = "gpu")]
@@ -180,53 +380,60 @@ impl DataCollector {
         let now = Instant::now();
         let last_collection_time = now.checked_sub(Duration::from_secs(600)).unwrap_or(now);
 
-        DataCollector {
+        let mut collector = DataCollector {
             data: Data::default(),
             sys: SysinfoSource::default(),
             #[cfg(target_os = "linux")]
             pid_mapping: HashMap::default(),
             #[cfg(target_os = "linux")]
+            prev_pid_mapping: HashMap::default(),
+            #[cfg(target_os = "linux")]
+            proc_buffer: String::new(),
+            #[cfg(target_os = "linux")]
             prev_idle: 0_f64,
             #[cfg(target_os = "linux")]
             prev_non_idle: 0_f64,
+            #[cfg(target_os = "linux")]
+            task_stats: processes::TaskStats::open().ok(),
+            #[cfg(target_os = "linux")]
+            process_fields: processes::ProcessFields::default(),
             temperature_type: TemperatureType::Celsius,
             use_current_cpu_total: false,
             unnormalized_cpu: false,
             last_collection_time,
-            total_rx: 0,
-            total_tx: 0,
             show_average_cpu: false,
             widgets_to_harvest: UsedWidgets::default(),
-            #[cfg(feature = "battery")]
-            battery_manager: None,
-            #[cfg(feature = "battery")]
-            battery_list: None,
+            sources: Vec::new(),
+            current_source: "",
             filters,
+            network_namespaces: false,
+            extra_sensors: ExtraSensors::default(),
+            max_processes: None,
             #[cfg(target_family = "unix")]
             user_table: Default::default(),
+            interner: StringInterner::default(),
             #[cfg(feature = "gpu")]
             gpu_pids: None,
             #[cfg(feature = "gpu")]
             gpus_total_mem: None,
+        };
+
+        for source in source::default_sources() {
+            collector.add_source(source);
         }
+
+        collector
     }
 
-    pub fn init(&mut self) {
-        #[cfg(feature = "battery")]
-        {
-            if self.widgets_to_harvest.use_battery {
-                if let Ok(battery_manager) = Manager::new() {
-                    if let Ok(batteries) = battery_manager.batteries() {
-                        let battery_list: Vec<Battery> = batteries.filter_map(Result::ok).collect();
-                        if !battery_list.is_empty() {
-                            self.battery_list = Some(battery_list);
-                            self.battery_manager = Some(battery_manager);
-                        }
-                    }
-                }
-            }
-        }
+    /// Registers a source to collect from, after all previously registered
+    /// sources.
+    pub fn add_source(&mut self, source: Box<dyn DataSource>) {
+        self.sources.push(source);
+    }
 
+    /// Collects data once, then waits a bit so that rates (e.g. CPU usage)
+    /// measured by the next update are accurate. The initial data is discarded.
+    pub fn init(&mut self) {
         self.update_data();
 
         // Sleep a few seconds to avoid potentially weird data.
@@ -240,6 +447,10 @@ impl DataCollector {
         self.widgets_to_harvest = used_widgets;
     }
 
+    pub fn set_filters(&mut self, filters: DataFilters) {
+        self.filters = filters;
+    }
+
     pub fn set_temperature_type(&mut self, temperature_type: TemperatureType) {
         self.temperature_type = temperature_type;
     }
@@ -256,13 +467,38 @@ impl DataCollector {
         self.show_average_cpu = show_average_cpu;
     }
 
+    /// Sets whether to also count the interfaces of other network namespaces,
+    /// such as those of containers. This is only supported on Linux, and
+    /// usually needs root to see other users' namespaces.
+    pub fn set_network_namespaces(&mut self, network_namespaces: bool) {
+        self.network_namespaces = network_namespaces;
+    }
+
+    /// Sets which readings to collect alongside temperatures, such as
+    /// voltages. This is only supported on Linux.
+    pub fn set_extra_sensors(&mut self, extra_sensors: ExtraSensors) {
+        self.extra_sensors = extra_sensors;
+    }
+
+    /// Sets a soft limit on how many processes to keep. If there are more, only
+    /// the ones using the most CPU are kept.
+    pub fn set_max_processes(&mut self, max_processes: Option<usize>) {
+        self.max_processes = max_processes;
+    }
+
+    /// Sets which optional details are collected for each process.
+    #[cfg(target_os = "linux")]
+    pub fn set_process_fields(&mut self, process_fields: processes::ProcessFields) {
+        self.process_fields = process_fields;
+    }
+
     /// Refresh sysinfo data. We use sysinfo for the following data:
     /// - CPU usage
     /// - Memory usage
     /// - Network usage
     /// - Processes (non-Linux)
     /// - Disk (Windows)
-    /// - Temperatures (non-Linux)
+    /// - Temperatures (non-Linux, non-macOS)
     fn refresh_sysinfo_data(&mut self) {
         // Refresh the list of objects once every minute. If it's too frequent it can
         // cause segfaults.
@@ -305,6 +541,7 @@ impl DataCollector {
                 }
             }
 
+            #[cfg(not(target_os = "macos"))]
             if self.widgets_to_harvest.use_temp {
                 if refresh_start.duration_since(self.last_collection_time) > LIST_REFRESH_TIME {
                     self.sys.temps.refresh_list();
@@ -322,190 +559,53 @@ impl DataCollector {
         }
     }
 
+    /// Collects new data, storing it in [`DataCollector::data`].
     pub fn update_data(&mut self) {
-        self.refresh_sysinfo_data();
+        self.timed("sysinfo", Self::refresh_sysinfo_data);
 
         self.data.collection_time = Instant::now();
 
-        self.update_cpu_usage();
-        self.update_memory_usage();
-        self.update_temps();
-
-        #[cfg(feature = "battery")]
-        self.update_batteries();
-
-        #[cfg(feature = "gpu")]
-        self.update_gpus(); // update_gpus before procs for gpu_pids but after temps for appending
-
-        self.update_processes();
-        self.update_network_usage();
-        self.update_disks();
-
-        // Update times for future reference.
-        self.last_collection_time = self.data.collection_time;
-    }
-
-    #[cfg(feature = "gpu")]
-    #[inline]
-    fn update_gpus(&mut self) {
-        if self.widgets_to_harvest.use_gpu {
-            let mut local_gpu: Vec<(String, memory::MemHarvest)> = Vec::new();
-            let mut local_gpu_pids: Vec<HashMap<u32, (u64, u32)>> = Vec::new();
-            let mut local_gpu_total_mem: u64 = 0;
-
-            #[cfg(feature = "nvidia")]
-            if let Some(data) = nvidia::get_nvidia_vecs(
-                &self.temperature_type,
-                &self.filters.temp_filter,
-                &self.widgets_to_harvest,
-            ) {
-                if let Some(mut temp) = data.temperature {
-                    if let Some(sensors) = &mut self.data.temperature_sensors {
-                        sensors.append(&mut temp);
-                    } else {
-                        self.data.temperature_sensors = Some(temp);
-                    }
-                }
-                if let Some(mut mem) = data.memory {
-                    local_gpu.append(&mut mem);
+        // Take the sources out while collecting so that each one can be given the
+        // collector.
+        let mut sources = std::mem::take(&mut self.sources);
+        for source in &mut sources {
+            let status = if source.is_enabled(&self.widgets_to_harvest) {
+                self.current_source = source.name();
+                self.timed(source.name(), |collector| source.collect(collector));
+
+                if source.has_data(&self.data) {
+                    SourceStatus::Active
+                } else {
+                    SourceStatus::NoData
                 }
-                if let Some(mut proc) = data.procs {
-                    local_gpu_pids.append(&mut proc.1);
-                    local_gpu_total_mem += proc.0;
-                }
-            }
-
-            #[cfg(target_os = "linux")]
-            if let Some(data) = amd::get_amd_vecs(
-                &self.temperature_type,
-                &self.filters.temp_filter,
-                &self.widgets_to_harvest,
-                self.last_collection_time,
-            ) {
-                if let Some(mut temp) = data.temperature {
-                    if let Some(sensors) = &mut self.data.temperature_sensors {
-                        sensors.append(&mut temp);
-                    } else {
-                        self.data.temperature_sensors = Some(temp);
-                    }
-                }
-                if let Some(mut mem) = data.memory {
-                    local_gpu.append(&mut mem);
-                }
-                if let Some(mut proc) = data.procs {
-                    local_gpu_pids.append(&mut proc.1);
-                    local_gpu_total_mem += proc.0;
-                }
-            }
-
-            self.data.gpu = (!local_gpu.is_empty()).then_some(local_gpu);
-            self.gpu_pids = (!local_gpu_pids.is_empty()).then_some(local_gpu_pids);
-            self.gpus_total_mem = (local_gpu_total_mem > 0).then_some(local_gpu_total_mem);
+            } else {
+                SourceStatus::Disabled
+            };
+            self.data.timings.statuses.push((source.name(), status));
         }
-    }
-
-    #[inline]
-    fn update_cpu_usage(&mut self) {
-        if self.widgets_to_harvest.use_cpu {
-            self.data.cpu = cpu::get_cpu_data_list(&self.sys.system, self.show_average_cpu).ok();
-
-            #[cfg(target_family = "unix")]
-            {
-                self.data.load_avg = Some(cpu::get_load_avg());
-            }
-        }
-    }
-
-    #[inline]
-    fn update_processes(&mut self) {
-        if self.widgets_to_harvest.use_proc {
-            if let Ok(mut process_list) = self.get_processes() {
-                // NB: To avoid duplicate sorts on rerenders/events, we sort the processes by
-                // PID here. We also want to avoid re-sorting *again* later on
-                // if we're sorting by PID, since we already did it here!
-                process_list.sort_unstable_by_key(|p| p.pid);
-                self.data.list_of_processes = Some(process_list);
-            }
-        }
-    }
+        // Keep any sources that were added while collecting.
+        sources.append(&mut self.sources);
+        self.sources = sources;
 
-    #[inline]
-    fn update_temps(&mut self) {
-        if self.widgets_to_harvest.use_temp {
-            #[cfg(not(target_os = "linux"))]
-            if let Ok(data) = temperature::get_temperature_data(
-                &self.sys.temps,
-                &self.temperature_type,
-                &self.filters.temp_filter,
-            ) {
-                self.data.temperature_sensors = data;
-            }
-
-            #[cfg(target_os = "linux")]
-            if let Ok(data) =
-                temperature::get_temperature_data(&self.temperature_type, &self.filters.temp_filter)
-            {
-                self.data.temperature_sensors = data;
-            }
-        }
-    }
-
-    #[inline]
-    fn update_memory_usage(&mut self) {
-        if self.widgets_to_harvest.use_mem {
-            self.data.memory = memory::get_ram_usage(&self.sys.system);
-
-            #[cfg(not(target_os = "windows"))]
-            if self.widgets_to_harvest.use_cache {
-                self.data.cache = memory::get_cache_usage(&self.sys.system);
-            }
-
-            self.data.swap = memory::get_swap_usage(&self.sys.system);
-
-            #[cfg(feature = "zfs")]
-            {
-                self.data.arc = memory::arc::get_arc_usage();
-            }
-        }
-    }
-
-    #[inline]
-    fn update_network_usage(&mut self) {
-        let current_instant = self.data.collection_time;
-
-        if self.widgets_to_harvest.use_net {
-            let net_data = network::get_network_data(
-                &self.sys.network,
-                self.last_collection_time,
-                &mut self.total_rx,
-                &mut self.total_tx,
-                current_instant,
-                &self.filters.net_filter,
-            );
-
-            self.total_rx = net_data.total_rx;
-            self.total_tx = net_data.total_tx;
-            self.data.network = Some(net_data);
-        }
+        // Update times for future reference.
+        self.last_collection_time = self.data.collection_time;
+        self.data.timings.finished = Some(Instant::now());
     }
 
-    #[inline]
-    #[cfg(feature = "battery")]
-    fn update_batteries(&mut self) {
-        if let Some(battery_manager) = &self.battery_manager {
-            if let Some(battery_list) = &mut self.battery_list {
-                self.data.list_of_batteries =
-                    Some(batteries::refresh_batteries(battery_manager, battery_list));
-            }
-        }
+    /// Records a problem the source being collected from ran into that didn't
+    /// stop it from collecting, to be shown to the user.
+    pub(crate) fn report_issue(&mut self, message: String) {
+        self.data.timings.issues.push(CollectionIssue {
+            source: self.current_source,
+            message,
+        });
     }
 
-    #[inline]
-    fn update_disks(&mut self) {
-        if self.widgets_to_harvest.use_disk {
-            self.data.disks = disks::get_disk_usage(self).ok();
-            self.data.io = disks::get_io_usage().ok();
-        }
+    /// Runs `update`, recording how long it took for the given data source.
+    fn timed(&mut self, source: &'static str, update: impl FnOnce(&mut Self)) {
+        let start = Instant::now();
+        update(self);
+        self.data.timings.sources.push((source, start.elapsed()));
     }
 
     /// Returns the total memory of the system.
@@ -557,3 +657,73 @@ where
         .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "key not found"))
         .and_then(|val| serde_json::from_value(val).map_err(|err| err.into()))
 }
+
+#[cfg(target_os = "netbsd")]
+/// Reads the [sysctl](https://man.netbsd.org/sysctl.3) value with the given name into `buf`,
+/// returning how many bytes were read.
+fn sysctl_by_name(name: &str, buf: &mut [u8]) -> std::io::Result<usize> {
+    let name = std::ffi::CString::new(name)?;
+    let mut len = buf.len();
+
+    // SAFETY: `name` is a valid C string, and `buf` is valid for writes of up to
+    // `len` bytes.
+    let result = unsafe {
+        libc::sysctlbyname(
+            name.as_ptr(),
+            buf.as_mut_ptr().cast(),
+            &mut len,
+            std::ptr::null(),
+            0,
+        )
+    };
+
+    if result == 0 {
+        Ok(len)
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn data_round_trips() {
+        let data = Data {
+            cpu: Some(vec![cpu::CpuData {
+                data_type: cpu::CpuDataType::Avg,
+                cpu_usage: 12.5,
+            }]),
+            memory: Some(memory::MemHarvest {
+                used_bytes: 1,
+                total_bytes: 2,
+            }),
+            list_of_processes: Some(vec![processes::ProcessHarvest {
+                pid: 1,
+                name: "init".into(),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(VersionedData::from(data)).unwrap();
+        assert_eq!(json["version"], DATA_FORMAT_VERSION);
+        assert_eq!(json["cpu"][0]["data_type"], "avg");
+        assert_eq!(json["list_of_processes"][0]["name"], "init");
+
+        let data: Data = serde_json::from_value::<VersionedData>(json)
+            .unwrap()
+            .into();
+        assert_eq!(data.cpu.unwrap()[0].cpu_usage, 12.5);
+        assert_eq!(data.memory.unwrap().total_bytes, 2);
+        assert_eq!(&*data.list_of_processes.unwrap()[0].name, "init");
+        assert!(data.disks.is_none());
+    }
+
+    #[test]
+    fn unknown_data_version() {
+        assert!(serde_json::from_str::<VersionedData>(r#"{"version":"0"}"#).is_err());
+        assert!(serde_json::from_str::<VersionedData>(r#"{"cpu":null}"#).is_err());
+    }
+}
@@ -1,11 +1,10 @@
 mod amdgpu_marketing;
 
-use crate::{
-    app::{filter::Filter, layout_manager::UsedWidgets},
-    data_collection::{
-        memory::MemHarvest,
-        temperature::{TempHarvest, TemperatureType},
-    },
+use crate::data_collection::{
+    filter::Filter,
+    memory::MemHarvest,
+    temperature::{SensorKind, TempHarvest, TemperatureType},
+    UsedWidgets,
 };
 use hashbrown::{HashMap, HashSet};
 use std::{
@@ -441,6 +440,8 @@ pub fn get_amd_vecs(
                     temp_vec.push(TempHarvest {
                         name: format!("{} {}", device_name, info.name),
                         temperature: Some(temperature),
+                        kind: SensorKind::Temperature,
+                        is_drive: false,
                     });
                 }
             }
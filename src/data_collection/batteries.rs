@@ -10,13 +10,18 @@
 //!
 //! For more information, refer to the [starship_battery](https://github.com/starship/rust-battery) repo/docs.
 
+use serde::{Deserialize, Serialize};
 use starship_battery::{
     units::{power::watt, ratio::percent, time::second},
     Battery, Manager, State,
 };
 
+use super::{source::DataSource, DataCollector};
+use crate::data_collection::{Data, UsedWidgets};
+
 /// Battery state.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum BatteryState {
     Charging {
         /// Time to full in seconds.
@@ -44,7 +49,7 @@ impl BatteryState {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatteryData {
     /// Current charge percent.
     pub charge_percent: f64,
@@ -99,3 +104,45 @@ pub fn refresh_batteries(manager: &Manager, batteries: &mut [Battery]) -> Vec<Ba
         })
         .collect::<Vec<_>>()
 }
+
+/// Collects battery data.
+#[derive(Debug, Default)]
+pub(crate) struct BatterySource {
+    is_init: bool,
+    manager: Option<Manager>,
+    batteries: Vec<Battery>,
+}
+
+impl DataSource for BatterySource {
+    fn name(&self) -> &'static str {
+        "battery"
+    }
+
+    fn is_enabled(&self, used_widgets: &UsedWidgets) -> bool {
+        used_widgets.use_battery
+    }
+
+    fn collect(&mut self, collector: &mut DataCollector) {
+        if !self.is_init {
+            self.is_init = true;
+
+            if let Ok(manager) = Manager::new() {
+                if let Ok(batteries) = manager.batteries() {
+                    self.batteries = batteries.filter_map(Result::ok).collect();
+                    if !self.batteries.is_empty() {
+                        self.manager = Some(manager);
+                    }
+                }
+            }
+        }
+
+        if let Some(manager) = &self.manager {
+            collector.data.list_of_batteries =
+                Some(refresh_batteries(manager, &mut self.batteries));
+        }
+    }
+
+    fn has_data(&self, data: &Data) -> bool {
+        data.list_of_batteries.is_some()
+    }
+}
@@ -1,20 +1,82 @@
 //! Data collection for CPU usage and load average.
 
+#[cfg(any(target_os = "illumos", test))]
+pub mod illumos;
+#[cfg(any(target_os = "netbsd", test))]
+pub mod netbsd;
+#[cfg(not(any(target_os = "netbsd", target_os = "illumos")))]
 pub mod sysinfo;
+
+#[cfg(target_os = "illumos")]
+pub use self::illumos::*;
+#[cfg(target_os = "netbsd")]
+pub use self::netbsd::*;
+#[cfg(not(any(target_os = "netbsd", target_os = "illumos")))]
 pub use self::sysinfo::*;
 
+use serde::{Deserialize, Serialize};
+
+use super::{source::DataSource, DataCollector};
+use crate::data_collection::{Data, UsedWidgets};
+
+/// The 1, 5, and 15 minute load averages.
 pub type LoadAvgHarvest = [f32; 3];
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CpuDataType {
+    /// The average across all CPUs.
     Avg,
+    /// A single CPU, by index.
     Cpu(usize),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CpuData {
     pub data_type: CpuDataType,
+    /// Usage as a percentage.
     pub cpu_usage: f64,
 }
 
 pub type CpuHarvest = Vec<CpuData>;
+
+/// Collects CPU usage and load averages.
+#[derive(Debug, Default)]
+pub(crate) struct CpuSource {
+    /// The previous CPU times, used to calculate usage since the last update.
+    #[cfg(any(target_os = "netbsd", target_os = "illumos"))]
+    cpu_times: Vec<CpuTimes>,
+}
+
+impl DataSource for CpuSource {
+    fn name(&self) -> &'static str {
+        "cpu"
+    }
+
+    fn is_enabled(&self, used_widgets: &UsedWidgets) -> bool {
+        used_widgets.use_cpu
+    }
+
+    fn collect(&mut self, collector: &mut DataCollector) {
+        #[cfg(not(any(target_os = "netbsd", target_os = "illumos")))]
+        {
+            collector.data.cpu =
+                get_cpu_data_list(&collector.sys.system, collector.show_average_cpu).ok();
+        }
+
+        #[cfg(any(target_os = "netbsd", target_os = "illumos"))]
+        {
+            collector.data.cpu =
+                get_cpu_data_list(&mut self.cpu_times, collector.show_average_cpu).ok();
+        }
+
+        #[cfg(target_family = "unix")]
+        {
+            collector.data.load_avg = Some(get_load_avg());
+        }
+    }
+
+    fn has_data(&self, data: &Data) -> bool {
+        data.cpu.is_some()
+    }
+}
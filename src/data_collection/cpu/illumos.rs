@@ -0,0 +1,172 @@
+//! CPU stats for illumos, which sysinfo doesn't support. These are read from
+//! the `cpu:*:sys` kstats, which give how long each CPU has spent in each
+//! state.
+
+use std::collections::BTreeMap;
+
+use super::{CpuData, CpuDataType, CpuHarvest};
+use crate::data_collection::kstat::parse_kstats;
+
+/// How long a CPU has spent busy and in total, in nanoseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuTimes {
+    busy: u64,
+    total: u64,
+}
+
+/// Returns the CPU usage since the last call, using and then updating
+/// `prev_times`.
+#[cfg(target_os = "illumos")]
+pub fn get_cpu_data_list(
+    prev_times: &mut Vec<CpuTimes>, show_average_cpu: bool,
+) -> crate::data_collection::error::CollectionResult<CpuHarvest> {
+    let output = crate::data_collection::kstat::read_kstats(&["cpu:::/^cpu_nsec_/"])?;
+    let times = parse_cpu_times(&output);
+
+    let harvest = cpu_harvest(prev_times, &times, show_average_cpu);
+    *prev_times = times;
+
+    Ok(harvest)
+}
+
+/// Returns the number of online CPUs.
+#[cfg(target_os = "illumos")]
+pub(crate) fn num_cpus() -> std::io::Result<usize> {
+    // SAFETY: `sysconf` has no preconditions.
+    match unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) } {
+        -1 => Err(std::io::Error::last_os_error()),
+        num_cpus => Ok((num_cpus as usize).max(1)),
+    }
+}
+
+#[cfg(target_os = "illumos")]
+pub(crate) fn get_load_avg() -> super::LoadAvgHarvest {
+    let mut loads = [0.0; 3];
+
+    // SAFETY: `loads` has room for the 3 values requested.
+    if unsafe { libc::getloadavg(loads.as_mut_ptr(), 3) } == 3 {
+        loads.map(|load| load as f32)
+    } else {
+        [0.0; 3]
+    }
+}
+
+/// Parses the output of `kstat -p` for the `cpu_nsec_*` statistics, returning
+/// the times of each CPU in order of instance number.
+fn parse_cpu_times(output: &str) -> Vec<CpuTimes> {
+    let mut cpus: BTreeMap<usize, CpuTimes> = BTreeMap::new();
+
+    for kstat in parse_kstats(output) {
+        let (Ok(instance), Some(value)) = (kstat.instance.parse(), kstat.value_u64()) else {
+            continue;
+        };
+        let times = cpus.entry(instance).or_default();
+
+        match kstat.statistic {
+            "cpu_nsec_idle" => {
+                times.total += value;
+            }
+            "cpu_nsec_user" | "cpu_nsec_kernel" => {
+                times.busy += value;
+                times.total += value;
+            }
+            _ => {}
+        }
+    }
+
+    cpus.into_values().collect()
+}
+
+/// Returns the percentage of time spent busy between two readings.
+fn usage(prev: &CpuTimes, curr: &CpuTimes) -> f64 {
+    let total = curr.total.saturating_sub(prev.total);
+    let busy = curr.busy.saturating_sub(prev.busy);
+
+    if total == 0 {
+        0.0
+    } else {
+        busy as f64 / total as f64 * 100.0
+    }
+}
+
+fn sum_times(times: &[CpuTimes]) -> CpuTimes {
+    times.iter().fold(CpuTimes::default(), |sum, cpu| CpuTimes {
+        busy: sum.busy + cpu.busy,
+        total: sum.total + cpu.total,
+    })
+}
+
+/// Converts two readings of CPU times into the usage of each CPU. CPUs without
+/// a previous reading are treated as having started from zero.
+fn cpu_harvest(prev: &[CpuTimes], curr: &[CpuTimes], show_average_cpu: bool) -> CpuHarvest {
+    let mut harvest = Vec::with_capacity(curr.len() + 1);
+
+    if show_average_cpu {
+        harvest.push(CpuData {
+            data_type: CpuDataType::Avg,
+            cpu_usage: usage(&sum_times(prev), &sum_times(curr)),
+        });
+    }
+
+    harvest.extend(curr.iter().enumerate().map(|(itx, curr)| CpuData {
+        data_type: CpuDataType::Cpu(itx),
+        cpu_usage: usage(prev.get(itx).unwrap_or(&CpuTimes::default()), curr),
+    }));
+
+    harvest
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_and_convert_cpu_times() {
+        let prev = [
+            CpuTimes {
+                busy: 150,
+                total: 1000,
+            },
+            CpuTimes {
+                busy: 0,
+                total: 1000,
+            },
+        ];
+        let output = "\
+cpu:1:sys:cpu_nsec_idle\t1100
+cpu:1:sys:cpu_nsec_kernel\t0
+cpu:1:sys:cpu_nsec_user\t0
+cpu:0:sys:cpu_nsec_dtrace\t5
+cpu:0:sys:cpu_nsec_idle\t900
+cpu:0:sys:cpu_nsec_intr\t10
+cpu:0:sys:cpu_nsec_kernel\t100
+cpu:0:sys:cpu_nsec_user\t200
+";
+
+        let curr = parse_cpu_times(output);
+        assert_eq!(
+            curr,
+            vec![
+                CpuTimes {
+                    busy: 300,
+                    total: 1200,
+                },
+                CpuTimes {
+                    busy: 0,
+                    total: 1100,
+                },
+            ]
+        );
+
+        let harvest = cpu_harvest(&prev, &curr, true);
+        assert_eq!(harvest.len(), 3);
+        assert!(matches!(harvest[0].data_type, CpuDataType::Avg));
+        assert_eq!(harvest[0].cpu_usage, 150.0 / 300.0 * 100.0);
+        assert_eq!(harvest[1].cpu_usage, 75.0);
+        assert_eq!(harvest[2].cpu_usage, 0.0);
+
+        let harvest = cpu_harvest(&prev, &curr, false);
+        assert_eq!(harvest.len(), 2);
+        assert!(matches!(harvest[0].data_type, CpuDataType::Cpu(0)));
+    }
+}
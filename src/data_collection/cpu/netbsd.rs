@@ -0,0 +1,151 @@
+//! CPU stats for NetBSD, which sysinfo doesn't support. These are read from
+//! `kern.cp_time`, which gives how long each CPU has spent in each state.
+
+use super::{CpuData, CpuDataType, CpuHarvest};
+
+/// The number of states in `kern.cp_time`; these are user, nice, system,
+/// interrupt, and idle.
+const CPU_STATES: usize = 5;
+
+/// The index of the idle state in `kern.cp_time`.
+const CP_IDLE: usize = 4;
+
+/// How long a CPU has spent in each state, in ticks.
+pub type CpuTimes = [u64; CPU_STATES];
+
+/// Returns the CPU usage since the last call, using and then updating
+/// `prev_times`.
+#[cfg(target_os = "netbsd")]
+pub fn get_cpu_data_list(
+    prev_times: &mut Vec<CpuTimes>, show_average_cpu: bool,
+) -> crate::data_collection::error::CollectionResult<CpuHarvest> {
+    let mut buf = vec![0; num_cpus()? * CPU_STATES * size_of::<u64>()];
+
+    // This gives the times for each CPU, rather than the total, as long as the
+    // buffer is large enough to hold all of them.
+    let len = crate::data_collection::sysctl_by_name("kern.cp_time", &mut buf)?;
+    let times = parse_cpu_times(&buf[..len]);
+
+    let harvest = cpu_harvest(prev_times, &times, show_average_cpu);
+    *prev_times = times;
+
+    Ok(harvest)
+}
+
+/// Returns the number of CPUs.
+#[cfg(target_os = "netbsd")]
+pub(crate) fn num_cpus() -> std::io::Result<usize> {
+    let mut buf = [0; size_of::<libc::c_int>()];
+    crate::data_collection::sysctl_by_name("hw.ncpu", &mut buf)?;
+
+    Ok(libc::c_int::from_ne_bytes(buf).max(1) as usize)
+}
+
+#[cfg(target_os = "netbsd")]
+pub(crate) fn get_load_avg() -> super::LoadAvgHarvest {
+    let mut loads = [0.0; 3];
+
+    // SAFETY: `loads` has room for the 3 values requested.
+    if unsafe { libc::getloadavg(loads.as_mut_ptr(), 3) } == 3 {
+        loads.map(|load| load as f32)
+    } else {
+        [0.0; 3]
+    }
+}
+
+/// Parses the raw contents of `kern.cp_time`, which is a `u64` per state per
+/// CPU.
+fn parse_cpu_times(bytes: &[u8]) -> Vec<CpuTimes> {
+    bytes
+        .chunks_exact(CPU_STATES * size_of::<u64>())
+        .map(|cpu| {
+            let mut times = [0; CPU_STATES];
+            for (time, bytes) in times.iter_mut().zip(cpu.chunks_exact(size_of::<u64>())) {
+                *time = u64::from_ne_bytes(bytes.try_into().unwrap_or_default());
+            }
+
+            times
+        })
+        .collect()
+}
+
+/// Returns the percentage of time spent not idle between two readings.
+fn usage(prev: &CpuTimes, curr: &CpuTimes) -> f64 {
+    let total: u64 = curr
+        .iter()
+        .zip(prev)
+        .map(|(curr, prev)| curr.saturating_sub(*prev))
+        .sum();
+    let idle = curr[CP_IDLE].saturating_sub(prev[CP_IDLE]);
+
+    if total == 0 {
+        0.0
+    } else {
+        (total - idle) as f64 / total as f64 * 100.0
+    }
+}
+
+fn sum_times(times: &[CpuTimes]) -> CpuTimes {
+    times.iter().fold([0; CPU_STATES], |mut sum, cpu| {
+        for (sum, time) in sum.iter_mut().zip(cpu) {
+            *sum += time;
+        }
+
+        sum
+    })
+}
+
+/// Converts two readings of CPU times into the usage of each CPU. CPUs without
+/// a previous reading are treated as having started from zero.
+fn cpu_harvest(prev: &[CpuTimes], curr: &[CpuTimes], show_average_cpu: bool) -> CpuHarvest {
+    let mut harvest = Vec::with_capacity(curr.len() + 1);
+
+    if show_average_cpu {
+        harvest.push(CpuData {
+            data_type: CpuDataType::Avg,
+            cpu_usage: usage(&sum_times(prev), &sum_times(curr)),
+        });
+    }
+
+    harvest.extend(curr.iter().enumerate().map(|(itx, curr)| CpuData {
+        data_type: CpuDataType::Cpu(itx),
+        cpu_usage: usage(prev.get(itx).unwrap_or(&[0; CPU_STATES]), curr),
+    }));
+
+    harvest
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_and_convert_cpu_times() {
+        let prev = [[100, 0, 50, 0, 850], [0, 0, 0, 0, 1000]];
+        let bytes: Vec<u8> = [[200, 0, 100, 0, 900], [0, 0, 0, 0, 1100]]
+            .iter()
+            .flatten()
+            .flat_map(|time: &u64| time.to_ne_bytes())
+            .collect();
+
+        let curr = parse_cpu_times(&bytes);
+        assert_eq!(curr, vec![[200, 0, 100, 0, 900], [0, 0, 0, 0, 1100]]);
+
+        let harvest = cpu_harvest(&prev, &curr, true);
+        assert_eq!(harvest.len(), 3);
+        assert!(matches!(harvest[0].data_type, CpuDataType::Avg));
+        assert_eq!(harvest[0].cpu_usage, 150.0 / 300.0 * 100.0);
+        assert_eq!(harvest[1].cpu_usage, 75.0);
+        assert_eq!(harvest[2].cpu_usage, 0.0);
+
+        let harvest = cpu_harvest(&prev, &curr, false);
+        assert_eq!(harvest.len(), 2);
+        assert!(matches!(harvest[0].data_type, CpuDataType::Cpu(0)));
+    }
+
+    #[test]
+    fn no_change_is_zero_usage() {
+        let times = [[1, 2, 3, 4, 5]];
+        assert_eq!(cpu_harvest(&times, &times, false)[0].cpu_usage, 0.0);
+    }
+}
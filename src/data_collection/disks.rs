@@ -1,5 +1,8 @@
 //! Data collection about disks (e.g. I/O, usage, space).
 
+#[cfg(any(target_os = "freebsd", test))]
+mod devstat;
+
 cfg_if! {
     if #[cfg(target_os = "freebsd")] {
         mod freebsd;
@@ -29,10 +32,12 @@ cfg_if! {
 
 use cfg_if::cfg_if;
 use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
 
-use crate::app::filter::Filter;
+use super::{source::DataSource, DataCollector};
+use crate::data_collection::{filter::Filter, Data, UsedWidgets};
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct DiskHarvest {
     pub name: String,
     pub mount_point: String,
@@ -47,12 +52,15 @@ pub struct DiskHarvest {
     pub total_space: Option<u64>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct IoData {
+    /// The total number of bytes read.
     pub read_bytes: u64,
+    /// The total number of bytes written.
     pub write_bytes: u64,
 }
 
+/// I/O for each disk, by disk name.
 pub type IoHarvest = HashMap<String, Option<IoData>>;
 
 cfg_if! {
@@ -127,12 +135,35 @@ pub fn keep_disk_entry(
     }
 }
 
+/// Collects disk usage and I/O.
+#[derive(Debug)]
+pub(crate) struct DiskSource;
+
+impl DataSource for DiskSource {
+    fn name(&self) -> &'static str {
+        "disks"
+    }
+
+    fn is_enabled(&self, used_widgets: &UsedWidgets) -> bool {
+        used_widgets.use_disk
+    }
+
+    fn collect(&mut self, collector: &mut DataCollector) {
+        collector.data.disks = get_disk_usage(collector).ok();
+        collector.data.io = get_io_usage().ok();
+    }
+
+    fn has_data(&self, data: &Data) -> bool {
+        data.disks.is_some()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use regex::Regex;
 
     use super::keep_disk_entry;
-    use crate::app::filter::Filter;
+    use crate::data_collection::filter::Filter;
 
     fn run_filter(disk_filter: &Option<Filter>, mount_filter: &Option<Filter>) -> Vec<usize> {
         let targets = [
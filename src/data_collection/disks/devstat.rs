@@ -0,0 +1,202 @@
+//! Disk I/O stats for FreeBSD, read from [devstat(9)](https://man.freebsd.org/cgi/man.cgi?query=devstat&sektion=9)
+//! through the `kern.devstat.all` sysctl, which is what [devstat(3)](https://man.freebsd.org/cgi/man.cgi?query=devstat&sektion=3)
+//! and `iostat` use.
+
+use super::IoData;
+
+/// The length of `device_name` in `struct devstat`.
+const DEVSTAT_NAME_LEN: usize = 16;
+
+/// The indices of each transaction type in `bytes`.
+const DEVSTAT_READ: usize = 1;
+const DEVSTAT_WRITE: usize = 2;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct BinTime {
+    sec: i64,
+    frac: u64,
+}
+
+/// Mirrors `struct devstat` from `sys/devicestat.h` on 64-bit systems. Pointers
+/// are represented as `usize`, as we never follow them.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+#[allow(
+    dead_code,
+    reason = "this mirrors a C struct, so not every field is used"
+)]
+struct Devstat {
+    sequence0: u32,
+    allocated: i32,
+    start_count: u32,
+    end_count: u32,
+    busy_from: BinTime,
+    dev_links: usize,
+    device_number: u32,
+    device_name: [u8; DEVSTAT_NAME_LEN],
+    unit_number: i32,
+    bytes: [u64; 4],
+    operations: [u64; 4],
+    duration: [BinTime; 4],
+    busy_time: BinTime,
+    creation_time: BinTime,
+    block_size: u32,
+    tag_types: [u64; 3],
+    flags: u32,
+    device_type: u32,
+    priority: u32,
+    id: usize,
+    sequence1: u32,
+}
+
+impl Devstat {
+    /// The full device name, such as `ada0`.
+    fn name(&self) -> String {
+        let len = self
+            .device_name
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(DEVSTAT_NAME_LEN);
+
+        format!(
+            "{}{}",
+            String::from_utf8_lossy(&self.device_name[..len]),
+            self.unit_number
+        )
+    }
+}
+
+/// Parses the contents of `kern.devstat.all`, which is a generation number
+/// followed by a `struct devstat` for each device, into the total bytes read
+/// and written by each device.
+fn parse_devstats(bytes: &[u8]) -> Vec<(String, IoData)> {
+    let Some(devices) = bytes.get(size_of::<i64>()..) else {
+        return Vec::new();
+    };
+
+    devices
+        .chunks_exact(size_of::<Devstat>())
+        .map(|chunk| {
+            // SAFETY: `chunk` is exactly the size of a `Devstat`, which is only made up
+            // of integers, so any bytes are valid. It may not be aligned, hence the
+            // unaligned read.
+            let devstat = unsafe { chunk.as_ptr().cast::<Devstat>().read_unaligned() };
+
+            (
+                devstat.name(),
+                IoData {
+                    read_bytes: devstat.bytes[DEVSTAT_READ],
+                    write_bytes: devstat.bytes[DEVSTAT_WRITE],
+                },
+            )
+        })
+        .collect()
+}
+
+/// Returns the device a partition is on, such as `ada0` for `ada0p2`, out of the
+/// given devices.
+pub(crate) fn parent_device<'a>(
+    partition: &str, devices: &'a [(String, IoData)],
+) -> Option<&'a IoData> {
+    devices
+        .iter()
+        .find(|(device, _)| {
+            partition.strip_prefix(device.as_str()).is_some_and(|rest| {
+                // Either the whole device, a GPT partition, or an MBR slice.
+                rest.is_empty() || rest.starts_with('p') || rest.starts_with('s')
+            })
+        })
+        .map(|(_, io)| io)
+}
+
+/// Returns the total bytes read and written by each device.
+#[cfg(target_os = "freebsd")]
+pub(crate) fn devstat_io_stats() -> std::io::Result<Vec<(String, IoData)>> {
+    use std::ptr;
+
+    let name = c"kern.devstat.all";
+    let mut len = 0;
+
+    // SAFETY: A null `oldp` asks for the size that would be written, which is
+    // written to `len`.
+    let result =
+        unsafe { libc::sysctlbyname(name.as_ptr(), ptr::null_mut(), &mut len, ptr::null(), 0) };
+    if result != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    // Leave some room in case devices are added between the two calls.
+    let mut buf = vec![0_u8; len + 4 * size_of::<Devstat>()];
+    let mut len = buf.len();
+
+    // SAFETY: `buf` is valid for writes of up to `len` bytes.
+    let result = unsafe {
+        libc::sysctlbyname(
+            name.as_ptr(),
+            buf.as_mut_ptr().cast(),
+            &mut len,
+            ptr::null(),
+            0,
+        )
+    };
+    if result != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    Ok(parse_devstats(&buf[..len]))
+}
+
+#[cfg(test)]
+mod test {
+    use std::mem::offset_of;
+
+    use super::*;
+
+    /// Returns the bytes of a `struct devstat` with the given fields set, and
+    /// everything else zeroed.
+    fn devstat_bytes(name: &[u8], unit_number: i32, read_bytes: u64, write_bytes: u64) -> Vec<u8> {
+        let mut bytes = vec![0; size_of::<Devstat>()];
+        let mut write = |offset: usize, value: &[u8]| {
+            bytes[offset..offset + value.len()].copy_from_slice(value);
+        };
+
+        write(offset_of!(Devstat, device_name), name);
+        write(offset_of!(Devstat, unit_number), &unit_number.to_ne_bytes());
+        write(
+            offset_of!(Devstat, bytes) + DEVSTAT_READ * size_of::<u64>(),
+            &read_bytes.to_ne_bytes(),
+        );
+        write(
+            offset_of!(Devstat, bytes) + DEVSTAT_WRITE * size_of::<u64>(),
+            &write_bytes.to_ne_bytes(),
+        );
+
+        bytes
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn devstat_layout() {
+        assert_eq!(size_of::<Devstat>(), 288);
+    }
+
+    #[test]
+    fn parse_devstat_sysctl() {
+        let mut bytes = 7_i64.to_ne_bytes().to_vec();
+        bytes.extend(devstat_bytes(b"ada", 0, 1024, 2048));
+        bytes.extend(devstat_bytes(b"nvd", 1, 0, 0));
+
+        let devices = parse_devstats(&bytes);
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].0, "ada0");
+        assert_eq!(devices[0].1.read_bytes, 1024);
+        assert_eq!(devices[0].1.write_bytes, 2048);
+        assert_eq!(devices[1].0, "nvd1");
+
+        assert_eq!(parent_device("ada0p2", &devices).unwrap().read_bytes, 1024);
+        assert_eq!(parent_device("ada0s1a", &devices).unwrap().read_bytes, 1024);
+        assert!(parent_device("ada01", &devices).is_none());
+        assert!(parent_device("da0p1", &devices).is_none());
+    }
+}
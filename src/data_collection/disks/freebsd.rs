@@ -5,7 +5,10 @@ use std::io;
 use hashbrown::HashMap;
 use serde::Deserialize;
 
-use super::{keep_disk_entry, DiskHarvest, IoHarvest};
+use super::{
+    devstat::{devstat_io_stats, parent_device},
+    keep_disk_entry, DiskHarvest, IoHarvest,
+};
 use crate::data_collection::{
     deserialize_xo, disks::IoData, error::CollectionResult, DataCollector,
 };
@@ -27,6 +30,10 @@ struct FileSystem {
 }
 
 pub fn get_io_usage() -> CollectionResult<IoHarvest> {
+    // devstat only tracks whole devices, so each partition shows the I/O of the
+    // device it's on.
+    let devices = devstat_io_stats().unwrap_or_default();
+
     // TODO: Should this (and other I/O collectors) fail fast? In general, should
     // collection ever fail fast?
     let mut io_harvest: HashMap<String, Option<IoData>> =
@@ -34,7 +41,14 @@ pub fn get_io_usage() -> CollectionResult<IoHarvest> {
             storage_system_information
                 .filesystem
                 .into_iter()
-                .map(|disk| (disk.name, None))
+                .map(|disk| {
+                    // This is keyed the same way disks are looked up, which is without
+                    // the `/dev/` prefix.
+                    let name = disk.name.rsplit('/').next().unwrap_or(&disk.name);
+                    let io = parent_device(name, &devices).cloned();
+
+                    (name.to_string(), io)
+                })
                 .collect()
         })?;
 
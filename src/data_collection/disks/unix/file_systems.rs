@@ -70,6 +70,9 @@ pub enum FileSystem {
     /// FUSE (<https://en.wikipedia.org/wiki/Filesystem_in_Userspace>)
     FuseBlk,
 
+    /// drvfs, which WSL uses to mount Windows drives (<https://learn.microsoft.com/en-us/windows/wsl/filesystems>)
+    Drvfs,
+
     /// Some unspecified filesystem.
     Other(String),
 }
@@ -103,6 +106,7 @@ impl FileSystem {
             FileSystem::Reiser3 => "reiserfs",
             FileSystem::Reiser4 => "reiser4",
             FileSystem::FuseBlk => "fuseblk",
+            FileSystem::Drvfs => "drvfs",
             FileSystem::ExFat => "exfat",
             FileSystem::F2fs => "f2fs",
             FileSystem::HfsPlus => "hfs+",
@@ -164,6 +168,8 @@ impl FromStr for FileSystem {
             FileSystem::Apfs
         } else if s.eq_ignore_ascii_case("fuseblk") {
             FileSystem::FuseBlk
+        } else if s.eq_ignore_ascii_case("drvfs") {
+            FileSystem::Drvfs
         } else {
             FileSystem::Other(s.to_string())
         })
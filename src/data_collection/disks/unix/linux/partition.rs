@@ -42,6 +42,10 @@ impl Partition {
 
     /// Returns the device name for the partition.
     pub fn get_device_name(&self) -> String {
+        if let FileSystem::Drvfs = self.fs_type {
+            return drvfs_name(self.device());
+        }
+
         if let Some(device) = self.device() {
             // See if this disk is actually mounted elsewhere on Linux. This is a workaround
             // properly map I/O in some cases (i.e. disk encryption, https://github.com/ClementTsang/bottom/issues/419).
@@ -103,6 +107,15 @@ impl Partition {
     }
 }
 
+/// Returns a name for a Windows drive mounted through WSL's drvfs, such as
+/// `C:\ (Windows)`, so it stands out from the Linux disks.
+fn drvfs_name(device: Option<&str>) -> String {
+    // `/proc/mounts` escapes backslashes as `\134`.
+    let drive = device.unwrap_or("drvfs").replace("\\134", "\\");
+
+    format!("{drive} (Windows)")
+}
+
 impl FromStr for Partition {
     type Err = anyhow::Error;
 
@@ -123,8 +136,14 @@ impl FromStr for Partition {
                 bail!("missing mount point");
             }
         };
-        let fs_type = match parts.next() {
-            Some(fs) => FileSystem::from_str(fs)?,
+        let fs_type = match (parts.next(), parts.next()) {
+            // WSL 2 mounts Windows drives over 9p rather than directly as drvfs.
+            (Some("9p"), Some(options))
+                if options.split([',', ';']).any(|opt| opt == "aname=drvfs") =>
+            {
+                FileSystem::Drvfs
+            }
+            (Some(fs), _) => FileSystem::from_str(fs)?,
             _ => {
                 bail!("missing filesystem type");
             }
@@ -191,3 +210,26 @@ pub(crate) fn physical_partitions() -> anyhow::Result<Vec<Partition>> {
 
     Ok(results)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_drvfs_partitions() {
+        let wsl1 = Partition::from_str("C: /mnt/c drvfs rw,noatime,uid=1000,gid=1000 0 0").unwrap();
+        assert_eq!(wsl1.fs_type(), &FileSystem::Drvfs);
+        assert_eq!(wsl1.get_device_name(), "C: (Windows)");
+
+        let wsl2 = Partition::from_str(
+            r"C:\134 /mnt/c 9p rw,noatime,dirsync,aname=drvfs;path=C:\;uid=1000 0 0",
+        )
+        .unwrap();
+        assert_eq!(wsl2.fs_type(), &FileSystem::Drvfs);
+        assert!(wsl2.fs_type().is_physical());
+        assert_eq!(wsl2.get_device_name(), "C:\\ (Windows)");
+
+        let other_9p = Partition::from_str("none /mnt/wslg 9p rw,aname=wslg 0 0").unwrap();
+        assert!(other_9p.fs_type().is_virtual());
+    }
+}
@@ -1,7 +1,6 @@
 use regex::Regex;
 
-/// Filters used by widgets to filter out certain entries.
-/// TODO: Move this out maybe?
+/// Filters used to filter out certain entries, such as disks or sensors.
 #[derive(Debug, Clone)]
 pub struct Filter {
     /// Whether the filter _accepts_ all entries that match `list`,
@@ -14,9 +13,10 @@ pub struct Filter {
 }
 
 impl Filter {
-    /// Create a new filter.
+    /// Create a new filter. If `ignore_matches` is true, entries matching
+    /// any of `list` are rejected; otherwise, only those entries are kept.
     #[inline]
-    pub(crate) fn new(ignore_matches: bool, list: Vec<Regex>) -> Self {
+    pub fn new(ignore_matches: bool, list: Vec<Regex>) -> Self {
         Self {
             is_list_ignored: ignore_matches,
             list,
@@ -0,0 +1,73 @@
+//! Reading [kstat](https://illumos.org/man/8/kstat) statistics on illumos,
+//! which is where most of its system stats are exposed.
+
+/// A single statistic from the output of `kstat -p`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Kstat<'a> {
+    pub module: &'a str,
+    pub instance: &'a str,
+    pub name: &'a str,
+    pub statistic: &'a str,
+    pub value: &'a str,
+}
+
+impl Kstat<'_> {
+    /// Returns the value as an integer, if it is one.
+    pub fn value_u64(&self) -> Option<u64> {
+        self.value.parse().ok()
+    }
+}
+
+/// Parses the output of `kstat -p`, which has a line of
+/// `module:instance:name:statistic` and its value, separated by a tab, for
+/// each statistic. Lines that don't match this are skipped.
+pub fn parse_kstats(output: &str) -> impl Iterator<Item = Kstat<'_>> {
+    output.lines().filter_map(|line| {
+        let (key, value) = line.split_once('\t')?;
+        let mut parts = key.splitn(4, ':');
+
+        Some(Kstat {
+            module: parts.next()?,
+            instance: parts.next()?,
+            name: parts.next()?,
+            statistic: parts.next()?,
+            value: value.trim(),
+        })
+    })
+}
+
+/// Runs `kstat -p` with the given selectors, returning its output.
+#[cfg(target_os = "illumos")]
+pub fn read_kstats(selectors: &[&str]) -> std::io::Result<String> {
+    let output = std::process::Command::new("kstat")
+        .arg("-p")
+        .args(selectors)
+        .output()?;
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_kstat_lines() {
+        let output =
+            "cpu:0:sys:cpu_nsec_idle\t123456\nunix:0:system_pages:physmem\t  4096  \nbad line\n";
+        let kstats: Vec<_> = parse_kstats(output).collect();
+
+        assert_eq!(kstats.len(), 2);
+        assert_eq!(
+            kstats[0],
+            Kstat {
+                module: "cpu",
+                instance: "0",
+                name: "sys",
+                statistic: "cpu_nsec_idle",
+                value: "123456",
+            }
+        );
+        assert_eq!(kstats[1].value_u64(), Some(4096));
+    }
+}
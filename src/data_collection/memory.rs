@@ -1,10 +1,23 @@
 //! Memory data collection.
 
-#[cfg(not(target_os = "windows"))]
-pub(crate) use self::sysinfo::get_cache_usage;
-pub(crate) use self::sysinfo::{get_ram_usage, get_swap_usage};
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "netbsd")] {
+        pub(crate) use self::netbsd::{get_cache_usage, get_ram_usage, get_swap_usage};
+    } else if #[cfg(target_os = "illumos")] {
+        pub(crate) use self::illumos::{get_cache_usage, get_ram_usage, get_swap_usage};
+    } else {
+        #[cfg(not(target_os = "windows"))]
+        pub(crate) use self::sysinfo::get_cache_usage;
+        pub(crate) use self::sysinfo::{get_ram_usage, get_swap_usage};
 
-pub mod sysinfo;
+        pub mod sysinfo;
+    }
+}
+
+#[cfg(any(target_os = "illumos", test))]
+pub mod illumos;
+#[cfg(any(target_os = "netbsd", test))]
+pub mod netbsd;
 // cfg_if::cfg_if! {
 //     if #[cfg(target_os = "windows")] {
 //         mod windows;
@@ -15,7 +28,12 @@ pub mod sysinfo;
 #[cfg(feature = "zfs")]
 pub mod arc;
 
-#[derive(Debug, Clone, Default)]
+use serde::{Deserialize, Serialize};
+
+use super::{source::DataSource, DataCollector};
+use crate::data_collection::{Data, UsedWidgets};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MemHarvest {
     pub used_bytes: u64,
     pub total_bytes: u64,
@@ -34,3 +52,38 @@ impl MemHarvest {
         }
     }
 }
+
+/// Collects RAM, cache, swap, and ARC usage.
+#[derive(Debug)]
+pub(crate) struct MemorySource;
+
+impl DataSource for MemorySource {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn is_enabled(&self, used_widgets: &UsedWidgets) -> bool {
+        used_widgets.use_mem
+    }
+
+    fn collect(&mut self, collector: &mut DataCollector) {
+        let sys = &collector.sys.system;
+        collector.data.memory = get_ram_usage(sys);
+
+        #[cfg(not(target_os = "windows"))]
+        if collector.widgets_to_harvest.use_cache {
+            collector.data.cache = get_cache_usage(sys);
+        }
+
+        collector.data.swap = get_swap_usage(sys);
+
+        #[cfg(feature = "zfs")]
+        {
+            collector.data.arc = arc::get_arc_usage();
+        }
+    }
+
+    fn has_data(&self, data: &Data) -> bool {
+        data.memory.is_some()
+    }
+}
@@ -0,0 +1,104 @@
+//! Memory stats for illumos, which sysinfo doesn't support. RAM usage is read
+//! from the `unix:0:system_pages` kstats, and swap usage from `swap -s`.
+//!
+//! These take a [`System`](sysinfo::System) to match other platforms, but
+//! don't use it.
+
+use super::MemHarvest;
+use crate::data_collection::kstat::parse_kstats;
+
+/// Parses the output of `kstat -p` for `unix:0:system_pages` into RAM usage,
+/// given the page size in bytes.
+fn parse_system_pages(output: &str, page_size: u64) -> Option<MemHarvest> {
+    let (mut physmem, mut freemem) = (None, None);
+
+    for kstat in parse_kstats(output) {
+        match kstat.statistic {
+            "physmem" => physmem = kstat.value_u64(),
+            "freemem" => freemem = kstat.value_u64(),
+            _ => {}
+        }
+    }
+
+    let (physmem, freemem) = (physmem?, freemem?);
+
+    Some(MemHarvest {
+        used_bytes: physmem.saturating_sub(freemem) * page_size,
+        total_bytes: physmem * page_size,
+    })
+}
+
+/// Parses the output of `swap -s`, which looks like:
+///
+/// ```text
+/// total: 269016k bytes allocated + 44484k reserved = 313500k used, 4090248k available
+/// ```
+fn parse_swap_summary(output: &str) -> Option<MemHarvest> {
+    let kilobytes =
+        |field: &str| -> Option<u64> { field.trim().strip_suffix('k')?.parse::<u64>().ok() };
+
+    let (_, rest) = output.split_once('=')?;
+    let (used, available) = rest.split_once(',')?;
+    let used = kilobytes(used.trim().strip_suffix("used")?)?;
+    let available = kilobytes(available.trim().strip_suffix("available")?)?;
+
+    Some(MemHarvest {
+        used_bytes: used * 1024,
+        total_bytes: (used + available) * 1024,
+    })
+}
+
+/// Returns RAM usage.
+#[cfg(target_os = "illumos")]
+pub(crate) fn get_ram_usage(_sys: &sysinfo::System) -> Option<MemHarvest> {
+    // SAFETY: `sysconf` has no preconditions.
+    let page_size = u64::try_from(unsafe { libc::sysconf(libc::_SC_PAGESIZE) }).ok()?;
+    let output = crate::data_collection::kstat::read_kstats(&["unix:0:system_pages"]).ok()?;
+
+    parse_system_pages(&output, page_size)
+}
+
+/// Returns SWAP usage.
+#[cfg(target_os = "illumos")]
+pub(crate) fn get_swap_usage(_sys: &sysinfo::System) -> Option<MemHarvest> {
+    let output = std::process::Command::new("swap").arg("-s").output().ok()?;
+
+    parse_swap_summary(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Returns cache usage. This isn't exposed in a way we can read, so this is
+/// always `None`.
+#[cfg(target_os = "illumos")]
+pub(crate) fn get_cache_usage(_sys: &sysinfo::System) -> Option<MemHarvest> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_ram() {
+        let output = "\
+unix:0:system_pages:availrmem\t900
+unix:0:system_pages:freemem\t200
+unix:0:system_pages:physmem\t1000
+";
+        let ram = parse_system_pages(output, 4096).unwrap();
+        assert_eq!(ram.used_bytes, 800 * 4096);
+        assert_eq!(ram.total_bytes, 1000 * 4096);
+
+        assert!(parse_system_pages("unix:0:system_pages:freemem\t200", 4096).is_none());
+    }
+
+    #[test]
+    fn parse_swap() {
+        let output =
+            "total: 269016k bytes allocated + 44484k reserved = 313500k used, 4090248k available\n";
+        let swap = parse_swap_summary(output).unwrap();
+        assert_eq!(swap.used_bytes, 313500 * 1024);
+        assert_eq!(swap.total_bytes, (313500 + 4090248) * 1024);
+
+        assert!(parse_swap_summary("").is_none());
+    }
+}
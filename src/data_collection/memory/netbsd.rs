@@ -0,0 +1,131 @@
+//! Memory stats for NetBSD, which sysinfo doesn't support. These are read from
+//! `vm.uvmexp2`.
+//!
+//! These take a [`System`](sysinfo::System) to match other platforms, but
+//! don't use it.
+
+use super::MemHarvest;
+
+/// The indices of the fields in `struct uvmexp_sysctl` that we use. Every
+/// field in it is an `int64_t`, so this is all we need to read them.
+mod field {
+    pub const PAGE_SIZE: usize = 0;
+    pub const NUM_PAGES: usize = 3;
+    pub const FREE: usize = 4;
+    pub const INACTIVE: usize = 6;
+    pub const SWAP_PAGES: usize = 17;
+    pub const SWAP_PAGES_IN_USE: usize = 18;
+}
+
+/// The parts of `vm.uvmexp2` that we use, in pages.
+#[derive(Debug, PartialEq, Eq)]
+struct UvmExp {
+    page_size: u64,
+    num_pages: u64,
+    free: u64,
+    inactive: u64,
+    swap_pages: u64,
+    swap_pages_in_use: u64,
+}
+
+impl UvmExp {
+    #[cfg(target_os = "netbsd")]
+    fn read() -> Option<Self> {
+        // The struct has grown over time, so leave plenty of room.
+        let mut buf = [0; 128 * size_of::<i64>()];
+        let len = crate::data_collection::sysctl_by_name("vm.uvmexp2", &mut buf).ok()?;
+
+        Self::parse(&buf[..len])
+    }
+
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        let read_field = |index: usize| -> Option<u64> {
+            let bytes = bytes.get(index * size_of::<i64>()..(index + 1) * size_of::<i64>())?;
+            Some(i64::from_ne_bytes(bytes.try_into().ok()?).max(0) as u64)
+        };
+
+        Some(Self {
+            page_size: read_field(field::PAGE_SIZE)?,
+            num_pages: read_field(field::NUM_PAGES)?,
+            free: read_field(field::FREE)?,
+            inactive: read_field(field::INACTIVE)?,
+            swap_pages: read_field(field::SWAP_PAGES)?,
+            swap_pages_in_use: read_field(field::SWAP_PAGES_IN_USE)?,
+        })
+    }
+
+    /// Memory that is neither free nor inactive, and so can't be reclaimed.
+    fn ram(&self) -> MemHarvest {
+        MemHarvest {
+            used_bytes: self
+                .num_pages
+                .saturating_sub(self.free)
+                .saturating_sub(self.inactive)
+                * self.page_size,
+            total_bytes: self.num_pages * self.page_size,
+        }
+    }
+
+    /// Inactive memory, which is treated as cache since it can be reclaimed.
+    fn cache(&self) -> MemHarvest {
+        MemHarvest {
+            used_bytes: self.inactive * self.page_size,
+            total_bytes: self.num_pages * self.page_size,
+        }
+    }
+
+    fn swap(&self) -> MemHarvest {
+        MemHarvest {
+            used_bytes: self.swap_pages_in_use * self.page_size,
+            total_bytes: self.swap_pages * self.page_size,
+        }
+    }
+}
+
+/// Returns RAM usage.
+#[cfg(target_os = "netbsd")]
+pub(crate) fn get_ram_usage(_sys: &sysinfo::System) -> Option<MemHarvest> {
+    UvmExp::read().map(|uvmexp| uvmexp.ram())
+}
+
+/// Returns SWAP usage.
+#[cfg(target_os = "netbsd")]
+pub(crate) fn get_swap_usage(_sys: &sysinfo::System) -> Option<MemHarvest> {
+    UvmExp::read().map(|uvmexp| uvmexp.swap())
+}
+
+/// Returns cache usage.
+#[cfg(target_os = "netbsd")]
+pub(crate) fn get_cache_usage(_sys: &sysinfo::System) -> Option<MemHarvest> {
+    UvmExp::read().map(|uvmexp| uvmexp.cache())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_uvmexp() {
+        let mut fields = [0_i64; 20];
+        fields[field::PAGE_SIZE] = 4096;
+        fields[field::NUM_PAGES] = 1000;
+        fields[field::FREE] = 200;
+        fields[field::INACTIVE] = 300;
+        fields[field::SWAP_PAGES] = 500;
+        fields[field::SWAP_PAGES_IN_USE] = 50;
+        let bytes: Vec<u8> = fields
+            .iter()
+            .flat_map(|value| value.to_ne_bytes())
+            .collect();
+
+        let uvmexp = UvmExp::parse(&bytes).unwrap();
+        assert_eq!(uvmexp.ram().used_bytes, 500 * 4096);
+        assert_eq!(uvmexp.ram().total_bytes, 1000 * 4096);
+        assert_eq!(uvmexp.cache().used_bytes, 300 * 4096);
+        assert_eq!(uvmexp.swap().used_bytes, 50 * 4096);
+        assert_eq!(uvmexp.swap().total_bytes, 500 * 4096);
+
+        // Too short to contain everything we need.
+        assert_eq!(UvmExp::parse(&bytes[..10 * size_of::<i64>()]), None);
+    }
+}
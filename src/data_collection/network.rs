@@ -1,15 +1,39 @@
 //! Data collection for network usage/IO.
 
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use super::{source::DataSource, DataCollector};
+use crate::data_collection::UsedWidgets;
+
+#[cfg(any(target_os = "illumos", test))]
+pub mod illumos;
+#[cfg(any(target_os = "linux", test))]
+pub mod linux;
+#[cfg(not(target_os = "illumos"))]
 pub mod sysinfo;
+
+#[cfg(target_os = "illumos")]
+pub use self::illumos::*;
+#[cfg(not(target_os = "illumos"))]
 pub use self::sysinfo::*;
 
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
 /// All units in bits.
 pub struct NetworkHarvest {
+    /// Received since the last collection, per second.
     pub rx: u64,
+    /// Transmitted since the last collection, per second.
     pub tx: u64,
     pub total_rx: u64,
     pub total_tx: u64,
+    /// The names of the interfaces that were counted.
+    #[serde(default)]
+    pub interfaces: Vec<String>,
+    /// The links of the counted interfaces that report a speed.
+    #[serde(default)]
+    pub links: Vec<LinkInfo>,
 }
 
 impl NetworkHarvest {
@@ -17,4 +41,102 @@ impl NetworkHarvest {
         self.rx = 0;
         self.tx = 0;
     }
+
+    /// The combined speed of the links, in bits per second, if any reported
+    /// one.
+    pub fn link_capacity(&self) -> Option<u64> {
+        if self.links.is_empty() {
+            None
+        } else {
+            Some(self.links.iter().map(|link| link.speed).sum())
+        }
+    }
+
+    /// The duplex of the links, if they all share one.
+    pub fn link_duplex(&self) -> Option<Duplex> {
+        let duplex = self.links.first()?.duplex;
+        self.links
+            .iter()
+            .all(|link| link.duplex == duplex)
+            .then_some(duplex)
+    }
+}
+
+/// The negotiated link of a network interface.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkInfo {
+    /// In bits per second.
+    pub speed: u64,
+    pub duplex: Duplex,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Duplex {
+    Full,
+    Half,
+    Unknown,
+}
+
+/// Converts the total bits received and transmitted into a [`NetworkHarvest`],
+/// using and then updating the previous totals to get the current rates.
+fn harvest_totals(
+    total_rx: u64, total_tx: u64, prev_net_access_time: Instant, prev_net_rx: &mut u64,
+    prev_net_tx: &mut u64, curr_time: Instant,
+) -> NetworkHarvest {
+    let elapsed_time = curr_time.duration_since(prev_net_access_time).as_secs_f64();
+
+    let (rx, tx) = if elapsed_time == 0.0 {
+        (0, 0)
+    } else {
+        (
+            ((total_rx.saturating_sub(*prev_net_rx)) as f64 / elapsed_time) as u64,
+            ((total_tx.saturating_sub(*prev_net_tx)) as f64 / elapsed_time) as u64,
+        )
+    };
+
+    *prev_net_rx = total_rx;
+    *prev_net_tx = total_tx;
+    NetworkHarvest {
+        rx,
+        tx,
+        total_rx,
+        total_tx,
+        interfaces: Vec::new(),
+        links: Vec::new(),
+    }
+}
+
+/// Collects network usage.
+#[derive(Debug, Default)]
+pub(crate) struct NetworkSource {
+    /// The total bits received and transmitted as of the last update.
+    total_rx: u64,
+    total_tx: u64,
+}
+
+impl DataSource for NetworkSource {
+    fn name(&self) -> &'static str {
+        "network"
+    }
+
+    fn is_enabled(&self, used_widgets: &UsedWidgets) -> bool {
+        used_widgets.use_net
+    }
+
+    fn collect(&mut self, collector: &mut DataCollector) {
+        let net_data = get_network_data(
+            &collector.sys.network,
+            collector.last_collection_time,
+            &mut self.total_rx,
+            &mut self.total_tx,
+            collector.data.collection_time,
+            &collector.filters.net_filter,
+            collector.network_namespaces,
+        );
+
+        self.total_rx = net_data.total_rx;
+        self.total_tx = net_data.total_tx;
+        collector.data.network = Some(net_data);
+    }
 }
@@ -0,0 +1,88 @@
+//! Gets network data on illumos, which sysinfo doesn't support. This is read
+//! from the `link:*:*` kstats, which have the totals for each datalink.
+
+#[cfg(target_os = "illumos")]
+use std::time::Instant;
+
+#[cfg(target_os = "illumos")]
+use super::NetworkHarvest;
+use crate::data_collection::{filter::Filter, kstat::parse_kstats};
+
+/// Sums the bytes received and transmitted by each link in the output of
+/// `kstat -p` for `rbytes64` and `obytes64`, returning the totals in bits along
+/// with the names of the links.
+fn parse_link_totals(output: &str, filter: &Option<Filter>) -> (u64, u64, Vec<String>) {
+    let mut total_rx: u64 = 0;
+    let mut total_tx: u64 = 0;
+    let mut links: Vec<String> = Vec::new();
+
+    for kstat in parse_kstats(output) {
+        let to_keep = if let Some(filter) = filter {
+            filter.should_keep(kstat.name)
+        } else {
+            true
+        };
+
+        if to_keep {
+            if !links.iter().any(|link| link == kstat.name) {
+                links.push(kstat.name.to_string());
+            }
+
+            match (kstat.statistic, kstat.value_u64()) {
+                ("rbytes64", Some(bytes)) => total_rx += bytes * 8,
+                ("obytes64", Some(bytes)) => total_tx += bytes * 8,
+                _ => {}
+            }
+        }
+    }
+
+    (total_rx, total_tx, links)
+}
+
+/// This takes [`Networks`](sysinfo::Networks) to match other platforms, but
+/// doesn't use it.
+#[cfg(target_os = "illumos")]
+pub fn get_network_data(
+    _networks: &sysinfo::Networks, prev_net_access_time: Instant, prev_net_rx: &mut u64,
+    prev_net_tx: &mut u64, curr_time: Instant, filter: &Option<Filter>, _namespaces: bool,
+) -> NetworkHarvest {
+    let output =
+        crate::data_collection::kstat::read_kstats(&["link:::rbytes64", "link:::obytes64"])
+            .unwrap_or_default();
+    let (total_rx, total_tx, interfaces) = parse_link_totals(&output, filter);
+
+    NetworkHarvest {
+        interfaces,
+        ..super::harvest_totals(
+            total_rx,
+            total_tx,
+            prev_net_access_time,
+            prev_net_rx,
+            prev_net_tx,
+            curr_time,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_links() {
+        let output = "\
+link:0:e1000g0:obytes64\t100
+link:0:e1000g0:rbytes64\t200
+link:0:net1:obytes64\t10
+link:0:net1:rbytes64\t20
+";
+        assert_eq!(
+            parse_link_totals(output, &None),
+            (
+                220 * 8,
+                110 * 8,
+                vec!["e1000g0".to_string(), "net1".to_string()]
+            )
+        );
+    }
+}
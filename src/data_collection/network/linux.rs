@@ -0,0 +1,194 @@
+//! Gets Linux-specific network data: the link speed and duplex of interfaces,
+//! read from sysfs, which reports the same settings as ethtool, and the
+//! interfaces of other network namespaces.
+
+#[cfg(target_os = "linux")]
+use std::{fs, os::unix::fs::MetadataExt, path::Path};
+
+#[cfg(target_os = "linux")]
+use hashbrown::{HashMap, HashSet};
+
+use super::{Duplex, LinkInfo};
+#[cfg(target_os = "linux")]
+use crate::data_collection::filter::Filter;
+
+/// Parses the contents of an interface's `speed` and `duplex` files in sysfs.
+/// The speed is in megabits per second, and is -1 if it's unknown.
+fn parse_link(speed: &str, duplex: &str) -> Option<LinkInfo> {
+    let speed: i64 = speed.trim().parse().ok()?;
+    if speed <= 0 {
+        return None;
+    }
+
+    let duplex = match duplex.trim() {
+        "full" => Duplex::Full,
+        "half" => Duplex::Half,
+        _ => Duplex::Unknown,
+    };
+
+    Some(LinkInfo {
+        speed: speed as u64 * 1_000_000,
+        duplex,
+    })
+}
+
+/// Returns the link of an interface, if it reports a speed. Virtual and
+/// wireless interfaces usually don't, and reading it fails if the interface is
+/// down.
+#[cfg(target_os = "linux")]
+pub fn link_info(interface: &str) -> Option<LinkInfo> {
+    let path = Path::new("/sys/class/net").join(interface);
+    let speed = fs::read_to_string(path.join("speed")).ok()?;
+    let duplex = fs::read_to_string(path.join("duplex")).unwrap_or_default();
+
+    parse_link(&speed, &duplex)
+}
+
+/// Parses the bytes received and transmitted by each interface in the contents
+/// of a `/proc/<pid>/net/dev` file.
+fn parse_net_dev(contents: &str) -> impl Iterator<Item = (&str, u64, u64)> {
+    // The first two lines are headers.
+    contents.lines().skip(2).filter_map(|line| {
+        let (interface, counters) = line.split_once(':')?;
+        let mut counters = counters.split_whitespace();
+        let rx = counters.next()?.parse().ok()?;
+        let tx = counters.nth(7)?.parse().ok()?;
+
+        Some((interface.trim(), rx, tx))
+    })
+}
+
+/// Parses the inode of a namespace from the target of a `/proc/<pid>/ns/net`
+/// link, which looks like `net:[4026531840]`.
+fn parse_namespace_inode(link: &str) -> Option<u64> {
+    link.strip_prefix("net:[")?.strip_suffix(']')?.parse().ok()
+}
+
+#[cfg(target_os = "linux")]
+fn namespace_inode(path: &Path) -> Option<u64> {
+    parse_namespace_inode(fs::read_link(path).ok()?.to_str()?)
+}
+
+/// Returns the total bits received and transmitted by the interfaces of the
+/// network namespaces other than bottom's own, along with their names as
+/// `<namespace>/<interface>`.
+///
+/// Each namespace is read through the `/proc/<pid>/net/dev` of the first
+/// process found in it, so namespaces without any processes are skipped, as
+/// are those of processes that bottom isn't allowed to inspect.
+#[cfg(target_os = "linux")]
+pub fn namespace_totals(filter: &Option<Filter>) -> (u64, u64, Vec<String>) {
+    let mut total_rx: u64 = 0;
+    let mut total_tx: u64 = 0;
+    let mut interfaces = Vec::new();
+
+    // Namespaces named with `ip netns` are bind mounted here.
+    let names: HashMap<u64, String> = fs::read_dir("/run/netns")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let inode = fs::metadata(entry.path()).ok()?.ino();
+            Some((inode, entry.file_name().to_string_lossy().into_owned()))
+        })
+        .collect();
+
+    let mut seen = HashSet::new();
+    if let Some(own) = namespace_inode(Path::new("/proc/self/ns/net")) {
+        seen.insert(own);
+    }
+
+    let Ok(processes) = fs::read_dir("/proc") else {
+        return (0, 0, interfaces);
+    };
+
+    for process in processes.flatten() {
+        let path = process.path();
+        let is_pid = process
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.bytes().all(|b| b.is_ascii_digit()));
+
+        if !is_pid {
+            continue;
+        }
+
+        let Some(inode) = namespace_inode(&path.join("ns/net")) else {
+            continue;
+        };
+
+        if !seen.insert(inode) {
+            continue;
+        }
+
+        let Ok(net_dev) = fs::read_to_string(path.join("net/dev")) else {
+            continue;
+        };
+
+        let namespace = names
+            .get(&inode)
+            .cloned()
+            .unwrap_or_else(|| inode.to_string());
+
+        for (interface, rx, tx) in parse_net_dev(&net_dev) {
+            let name = format!("{namespace}/{interface}");
+            let to_keep = if let Some(filter) = filter {
+                filter.should_keep(&name)
+            } else {
+                true
+            };
+
+            if to_keep {
+                total_rx += rx * 8;
+                total_tx += tx * 8;
+                interfaces.push(name);
+            }
+        }
+    }
+
+    (total_rx, total_tx, interfaces)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_links() {
+        assert_eq!(
+            parse_link("1000\n", "full\n"),
+            Some(LinkInfo {
+                speed: 1_000_000_000,
+                duplex: Duplex::Full,
+            })
+        );
+        assert_eq!(
+            parse_link("100\n", "\n"),
+            Some(LinkInfo {
+                speed: 100_000_000,
+                duplex: Duplex::Unknown,
+            })
+        );
+        assert_eq!(parse_link("-1\n", "unknown\n"), None);
+    }
+
+    #[test]
+    fn parse_net_devs() {
+        let contents = "\
+Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:     200       2    0    0    0     0          0         0      200       2    0    0    0     0       0          0
+  eth0: 1000 10 0 0 0 0 0 0 500 5 0 0 0 0 0 0
+";
+        assert_eq!(
+            parse_net_dev(contents).collect::<Vec<_>>(),
+            vec![("lo", 200, 200), ("eth0", 1000, 500)]
+        );
+    }
+
+    #[test]
+    fn parse_namespace_inodes() {
+        assert_eq!(parse_namespace_inode("net:[4026531840]"), Some(4026531840));
+        assert_eq!(parse_namespace_inode("mnt:[4026531840]"), None);
+    }
+}
@@ -5,16 +5,20 @@ use std::time::Instant;
 use sysinfo::Networks;
 
 use super::NetworkHarvest;
-use crate::app::filter::Filter;
+use crate::data_collection::filter::Filter;
 
 // TODO: Eventually make it so that this thing also takes individual usage into
 // account, so we can show per-interface!
 pub fn get_network_data(
     networks: &Networks, prev_net_access_time: Instant, prev_net_rx: &mut u64,
     prev_net_tx: &mut u64, curr_time: Instant, filter: &Option<Filter>,
+    #[cfg_attr(not(target_os = "linux"), allow(unused_variables))] namespaces: bool,
 ) -> NetworkHarvest {
     let mut total_rx: u64 = 0;
     let mut total_tx: u64 = 0;
+    let mut interfaces = Vec::new();
+    #[cfg_attr(not(target_os = "linux"), allow(unused_mut))]
+    let mut links = Vec::new();
 
     for (name, network) in networks {
         let to_keep = if let Some(filter) = filter {
@@ -24,28 +28,35 @@ pub fn get_network_data(
         };
 
         if to_keep {
+            #[cfg(target_os = "linux")]
+            links.extend(super::linux::link_info(name));
+
+            interfaces.push(name.clone());
             total_rx += network.total_received() * 8;
             total_tx += network.total_transmitted() * 8;
         }
     }
 
-    let elapsed_time = curr_time.duration_since(prev_net_access_time).as_secs_f64();
+    #[cfg(target_os = "linux")]
+    if namespaces {
+        let (namespace_rx, namespace_tx, namespace_interfaces) =
+            super::linux::namespace_totals(filter);
 
-    let (rx, tx) = if elapsed_time == 0.0 {
-        (0, 0)
-    } else {
-        (
-            ((total_rx.saturating_sub(*prev_net_rx)) as f64 / elapsed_time) as u64,
-            ((total_tx.saturating_sub(*prev_net_tx)) as f64 / elapsed_time) as u64,
-        )
-    };
+        total_rx += namespace_rx;
+        total_tx += namespace_tx;
+        interfaces.extend(namespace_interfaces);
+    }
 
-    *prev_net_rx = total_rx;
-    *prev_net_tx = total_tx;
     NetworkHarvest {
-        rx,
-        tx,
-        total_rx,
-        total_tx,
+        interfaces,
+        links,
+        ..super::harvest_totals(
+            total_rx,
+            total_tx,
+            prev_net_access_time,
+            prev_net_rx,
+            prev_net_tx,
+            curr_time,
+        )
     }
 }
@@ -5,12 +5,11 @@ use nvml_wrapper::{
     enum_wrappers::device::TemperatureSensor, enums::device::UsedGpuMemory, error::NvmlError, Nvml,
 };
 
-use crate::{
-    app::{filter::Filter, layout_manager::UsedWidgets},
-    data_collection::{
-        memory::MemHarvest,
-        temperature::{TempHarvest, TemperatureType},
-    },
+use crate::data_collection::{
+    filter::Filter,
+    memory::MemHarvest,
+    temperature::{SensorKind, TempHarvest, TemperatureType},
+    UsedWidgets,
 };
 
 pub static NVML_DATA: OnceLock<Result<Nvml, NvmlError>> = OnceLock::new();
@@ -44,6 +43,15 @@ fn init_nvml() -> Result<Nvml, NvmlError> {
     }
 }
 
+/// Returns why NVML couldn't be set up, unless it's just because there's no
+/// NVIDIA driver installed, which is expected on most systems.
+pub fn nvml_init_error() -> Option<&'static NvmlError> {
+    match NVML_DATA.get()? {
+        Err(NvmlError::LibloadingError(_) | NvmlError::LibraryNotFound) | Ok(_) => None,
+        Err(err) => Some(err),
+    }
+}
+
 /// Returns the GPU data from NVIDIA cards.
 #[inline]
 pub fn get_nvidia_vecs(
@@ -80,11 +88,15 @@ pub fn get_nvidia_vecs(
                                 temp_vec.push(TempHarvest {
                                     name,
                                     temperature: Some(temperature),
+                                    kind: SensorKind::Temperature,
+                                    is_drive: false,
                                 });
                             } else {
                                 temp_vec.push(TempHarvest {
                                     name,
                                     temperature: None,
+                                    kind: SensorKind::Temperature,
+                                    is_drive: false,
                                 });
                             }
                         }
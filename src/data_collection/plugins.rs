@@ -0,0 +1,371 @@
+//! Experimental support for Lua plugins, which can add columns to process
+//! widgets and simple widgets of their own, fed by their own sampling
+//! functions.
+//!
+//! Each plugin is a Lua script in the plugin directory that returns a table
+//! describing what it provides:
+//!
+//! ```lua
+//! return {
+//!     name = "open_files",
+//!
+//!     -- Adds a column to process widgets. `sample` is given a list of PIDs,
+//!     -- and returns a table mapping PIDs to values.
+//!     column = {
+//!         header = "FDs",
+//!         sample = function(pids) ... end,
+//!     },
+//!
+//!     -- Adds a widget, which can be placed in a layout with
+//!     -- `type = "plugin:open_files"`. For graphs, `sample` returns a number,
+//!     -- and for tables, `sample` returns a list of rows.
+//!     widget = {
+//!         kind = "table",
+//!         headers = { "Name", "Open" },
+//!         sample = function() ... end,
+//!     },
+//! }
+//! ```
+
+use std::{collections::VecDeque, fs, path::Path};
+
+use anyhow::{bail, Context};
+use hashbrown::HashMap;
+use mlua::{Function, Lua, Table, Value};
+use serde::{Deserialize, Serialize};
+
+use super::{processes::Pid, source::DataSource, DataCollector};
+
+/// How many points to keep for plugin graphs.
+const MAX_GRAPH_POINTS: usize = 1000;
+
+/// The kind of widget a plugin provides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginWidgetKind {
+    /// A graph of a single value over time.
+    Graph,
+    /// A table with the given column headers.
+    Table { headers: Vec<String> },
+}
+
+/// What a plugin provides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginInfo {
+    pub name: String,
+    /// The header of the process column, if the plugin provides one.
+    pub column_header: Option<String>,
+    /// The kind of widget, if the plugin provides one.
+    pub widget: Option<PluginWidgetKind>,
+}
+
+/// A single sample from a plugin's widget.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginWidgetHarvest {
+    Graph(f64),
+    Table(Vec<Vec<String>>),
+}
+
+/// The data shown by a plugin's widget.
+#[derive(Debug, Clone)]
+pub enum PluginWidgetData {
+    /// The most recent points of a graph, oldest first.
+    Graph(VecDeque<f64>),
+    Table(Vec<Vec<String>>),
+}
+
+impl PluginWidgetData {
+    /// Adds a new sample, replacing the data if the kind of widget changed.
+    pub fn update(&mut self, harvest: PluginWidgetHarvest) {
+        match (self, harvest) {
+            (PluginWidgetData::Graph(points), PluginWidgetHarvest::Graph(point)) => {
+                if points.len() >= MAX_GRAPH_POINTS {
+                    points.pop_front();
+                }
+                points.push_back(point);
+            }
+            (data, harvest) => *data = harvest.into(),
+        }
+    }
+}
+
+impl From<PluginWidgetHarvest> for PluginWidgetData {
+    fn from(harvest: PluginWidgetHarvest) -> Self {
+        match harvest {
+            PluginWidgetHarvest::Graph(point) => PluginWidgetData::Graph(VecDeque::from([point])),
+            PluginWidgetHarvest::Table(rows) => PluginWidgetData::Table(rows),
+        }
+    }
+}
+
+/// A loaded plugin.
+pub struct Plugin {
+    info: PluginInfo,
+    column_sampler: Option<Function>,
+    widget_sampler: Option<Function>,
+
+    /// The Lua state the samplers belong to, which has to be kept around for
+    /// as long as they are.
+    _lua: Lua,
+}
+
+impl std::fmt::Debug for Plugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Plugin").field("info", &self.info).finish()
+    }
+}
+
+impl Plugin {
+    /// Loads all plugins in `directory`, in order of file name. Only files
+    /// ending in `.lua` are loaded.
+    pub fn load_dir(directory: &Path) -> anyhow::Result<Vec<Self>> {
+        let mut paths = fs::read_dir(directory)
+            .with_context(|| format!("couldn't read '{}'", directory.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "lua"))
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        let plugins = paths
+            .iter()
+            .map(|path| {
+                let source = fs::read_to_string(path)?;
+                Self::load(&source, &path.to_string_lossy())
+                    .with_context(|| format!("couldn't load '{}'", path.display()))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        for (itx, plugin) in plugins.iter().enumerate() {
+            if plugins[..itx]
+                .iter()
+                .any(|other| other.info.name == plugin.info.name)
+            {
+                bail!("more than one plugin is named '{}'", plugin.info.name);
+            }
+        }
+
+        Ok(plugins)
+    }
+
+    /// Loads a plugin from its source.
+    fn load(source: &str, chunk_name: &str) -> anyhow::Result<Self> {
+        let lua = Lua::new();
+        let table: Table = lua.load(source).set_name(chunk_name).eval()?;
+
+        let name: String = table.get("name")?;
+
+        let (column_header, column_sampler) = match table.get::<Option<Table>>("column")? {
+            Some(column) => (
+                Some(column.get::<String>("header")?),
+                Some(column.get::<Function>("sample")?),
+            ),
+            None => (None, None),
+        };
+
+        let (widget, widget_sampler) = match table.get::<Option<Table>>("widget")? {
+            Some(widget) => {
+                let kind = match widget.get::<String>("kind")?.to_lowercase().as_str() {
+                    "graph" => PluginWidgetKind::Graph,
+                    "table" => PluginWidgetKind::Table {
+                        headers: widget
+                            .get::<Option<Vec<String>>>("headers")?
+                            .unwrap_or_default(),
+                    },
+                    kind => bail!("'{kind}' is an invalid widget kind, use one of: [graph, table]"),
+                };
+
+                (Some(kind), Some(widget.get::<Function>("sample")?))
+            }
+            None => (None, None),
+        };
+
+        Ok(Self {
+            info: PluginInfo {
+                name,
+                column_header,
+                widget,
+            },
+            column_sampler,
+            widget_sampler,
+            _lua: lua,
+        })
+    }
+
+    pub fn info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    /// Returns the value of this plugin's process column for each of `pids`.
+    fn sample_column(&self, pids: Vec<Pid>) -> Option<HashMap<Pid, String>> {
+        let sampler = self.column_sampler.as_ref()?;
+        let values: Table = sampler.call(pids).ok()?;
+
+        Some(
+            values
+                .pairs::<Pid, Value>()
+                .filter_map(|pair| {
+                    let (pid, value) = pair.ok()?;
+                    Some((pid, value_to_string(&value)?))
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns a sample for this plugin's widget.
+    fn sample_widget(&self) -> Option<PluginWidgetHarvest> {
+        let sampler = self.widget_sampler.as_ref()?;
+
+        match self.info.widget.as_ref()? {
+            PluginWidgetKind::Graph => sampler.call(()).ok().map(PluginWidgetHarvest::Graph),
+            PluginWidgetKind::Table { .. } => {
+                let rows: Vec<Vec<Value>> = sampler.call(()).ok()?;
+
+                Some(PluginWidgetHarvest::Table(
+                    rows.iter()
+                        .map(|row| {
+                            row.iter()
+                                .map(|value| value_to_string(value).unwrap_or_default())
+                                .collect()
+                        })
+                        .collect(),
+                ))
+            }
+        }
+    }
+}
+
+/// Converts a Lua value to a string to display, if it can be displayed.
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(string) => Some(string.to_string_lossy()),
+        Value::Integer(integer) => Some(integer.to_string()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Boolean(boolean) => Some(boolean.to_string()),
+        _ => None,
+    }
+}
+
+/// Collects data from plugins. This should be registered after processes, as
+/// it adds to their data.
+#[derive(Debug)]
+pub struct PluginSource {
+    plugins: Vec<Plugin>,
+}
+
+impl PluginSource {
+    pub fn new(plugins: Vec<Plugin>) -> Self {
+        Self { plugins }
+    }
+}
+
+impl DataSource for PluginSource {
+    fn name(&self) -> &'static str {
+        "plugins"
+    }
+
+    fn collect(&mut self, collector: &mut DataCollector) {
+        let mut widgets = Vec::new();
+
+        for (index, plugin) in self.plugins.iter().enumerate() {
+            if let Some(processes) = &mut collector.data.list_of_processes {
+                if let Some(mut values) =
+                    plugin.sample_column(processes.iter().map(|process| process.pid).collect())
+                {
+                    for process in processes.iter_mut() {
+                        process
+                            .plugin_values
+                            .resize(self.plugins.len(), String::new());
+                        process.plugin_values[index] =
+                            values.remove(&process.pid).unwrap_or_default();
+                    }
+                }
+            }
+
+            if let Some(harvest) = plugin.sample_widget() {
+                widgets.push((plugin.info.name.clone(), harvest));
+            }
+        }
+
+        collector.data.plugins = Some(widgets);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PLUGIN: &str = r#"
+        return {
+            name = "test",
+            column = {
+                header = "Double",
+                sample = function(pids)
+                    local values = {}
+                    for _, pid in ipairs(pids) do
+                        values[pid] = pid * 2
+                    end
+                    return values
+                end,
+            },
+            widget = {
+                kind = "table",
+                headers = { "Name", "Value" },
+                sample = function()
+                    return { { "a", 1 }, { "b", 2.5 } }
+                end,
+            },
+        }
+    "#;
+
+    #[test]
+    fn load_and_sample_plugin() {
+        let plugin = Plugin::load(PLUGIN, "test.lua").unwrap();
+        assert_eq!(
+            plugin.info(),
+            &PluginInfo {
+                name: "test".to_string(),
+                column_header: Some("Double".to_string()),
+                widget: Some(PluginWidgetKind::Table {
+                    headers: vec!["Name".to_string(), "Value".to_string()]
+                }),
+            }
+        );
+
+        let values = plugin.sample_column(vec![1, 2]).unwrap();
+        assert_eq!(values.get(&1).map(String::as_str), Some("2"));
+        assert_eq!(values.get(&2).map(String::as_str), Some("4"));
+
+        match plugin.sample_widget() {
+            Some(PluginWidgetHarvest::Table(rows)) => {
+                assert_eq!(rows, vec![vec!["a", "1"], vec!["b", "2.5"]]);
+            }
+            other => panic!("expected a table, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn invalid_plugins() {
+        assert!(Plugin::load("return {}", "empty.lua").is_err());
+        assert!(Plugin::load(
+            r#"return { name = "x", widget = { kind = "pie", sample = function() end } }"#,
+            "pie.lua"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn graph_data_is_bounded() {
+        let mut data = PluginWidgetData::from(PluginWidgetHarvest::Graph(0.0));
+        for point in 1..=MAX_GRAPH_POINTS {
+            data.update(PluginWidgetHarvest::Graph(point as f64));
+        }
+
+        match data {
+            PluginWidgetData::Graph(points) => {
+                assert_eq!(points.len(), MAX_GRAPH_POINTS);
+                assert_eq!(points.front(), Some(&1.0));
+            }
+            PluginWidgetData::Table(_) => panic!("expected a graph"),
+        }
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! For Linux, this is handled by a custom set of functions.
 //! For Windows, macOS, FreeBSD, Android, and Linux, this is handled by sysinfo.
+//! For NetBSD and illumos, this is handled by parsing the output of `ps`.
 
 use cfg_if::cfg_if;
 
@@ -18,12 +19,17 @@ cfg_if! {
     } else if #[cfg(target_os = "freebsd")] {
         pub mod freebsd;
         pub(crate) use self::freebsd::*;
+    } else if #[cfg(any(target_os = "netbsd", target_os = "illumos"))] {
+        pub(crate) use self::ps::*;
     } else if #[cfg(target_family = "unix")] {
         pub(crate) struct GenericProcessExt;
         impl UnixProcessExt for GenericProcessExt {}
     }
 }
 
+#[cfg(any(target_os = "netbsd", target_os = "illumos", test))]
+pub mod ps;
+
 cfg_if! {
     if #[cfg(target_family = "unix")] {
         pub mod unix;
@@ -31,9 +37,12 @@ cfg_if! {
     }
 }
 
-use std::{borrow::Cow, time::Duration};
+use std::{sync::Arc, time::Duration};
+
+use serde::{Deserialize, Serialize};
 
-use super::{error::CollectionResult, DataCollector};
+use super::{error::CollectionResult, source::DataSource, DataCollector};
+use crate::data_collection::{Data, UsedWidgets};
 
 cfg_if! {
     if #[cfg(target_family = "windows")] {
@@ -45,7 +54,7 @@ cfg_if! {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ProcessHarvest {
     /// The pid of the process.
     pub pid: Pid,
@@ -53,6 +62,12 @@ pub struct ProcessHarvest {
     /// The parent PID of the process. A `parent_pid` of 0 is usually the root.
     pub parent_pid: Option<Pid>,
 
+    /// When the process started, which along with the PID tells apart
+    /// processes that reuse a PID. The unit depends on the platform, and it is
+    /// 0 where it isn't known.
+    #[serde(default)]
+    pub start_time: u64,
+
     /// CPU usage as a percentage.
     pub cpu_usage_percent: f32,
 
@@ -63,10 +78,10 @@ pub struct ProcessHarvest {
     pub mem_usage_bytes: u64,
 
     /// The name of the process.
-    pub name: String,
+    pub name: Arc<str>,
 
     /// The exact command for the process.
-    pub command: String,
+    pub command: Arc<str>,
 
     /// Bytes read per second.
     pub read_bytes_per_sec: u64,
@@ -81,7 +96,7 @@ pub struct ProcessHarvest {
     pub total_write_bytes: u64,
 
     /// The current state of the process (e.g. zombie, asleep).
-    pub process_state: (String, char),
+    pub process_state: (Arc<str>, char),
 
     /// Cumulative process uptime.
     pub time: Duration,
@@ -93,7 +108,42 @@ pub struct ProcessHarvest {
     pub uid: Option<libc::uid_t>,
 
     /// This is the process' user.
-    pub user: Cow<'static, str>,
+    pub user: Arc<str>,
+
+    /// The SELinux context or AppArmor profile of the process, if there is
+    /// one. This is only used on Linux.
+    #[cfg(target_os = "linux")]
+    pub label: Option<Arc<str>>,
+
+    /// The namespaces of the process. This is only used on Linux.
+    #[cfg(target_os = "linux")]
+    #[serde(default)]
+    pub namespaces: linux::namespace::Namespaces,
+
+    /// The percentage of time spent waiting to run on a CPU. This is only used
+    /// on Linux.
+    #[cfg(target_os = "linux")]
+    #[serde(default)]
+    pub cpu_wait_percent: f32,
+
+    /// The percentage of time spent waiting for block I/O. This is only used on
+    /// Linux.
+    #[cfg(target_os = "linux")]
+    #[serde(default)]
+    pub io_wait_percent: f32,
+
+    /// The I/O scheduling class and priority, if it could be read. This is only
+    /// used on Linux.
+    #[cfg(target_os = "linux")]
+    #[serde(default)]
+    pub io_priority: Option<linux::io_priority::IoPriority>,
+
+    /// The capabilities and seccomp mode of the process, which are only
+    /// collected for the process whose details are shown. This is only used on
+    /// Linux.
+    #[cfg(target_os = "linux")]
+    #[serde(default)]
+    pub security: Option<linux::security::ProcessSecurity>,
 
     /// Gpu memory usage as bytes.
     #[cfg(feature = "gpu")]
@@ -106,12 +156,17 @@ pub struct ProcessHarvest {
     /// Gpu utilization as a percentage.
     #[cfg(feature = "gpu")]
     pub gpu_util: u32,
+
+    /// Values of plugin process columns, indexed by plugin.
+    #[cfg(feature = "plugins")]
+    pub plugin_values: Vec<String>,
     // TODO: Additional fields
     // pub rss_kb: u64,
     // pub virt_kb: u64,
 }
 
 impl ProcessHarvest {
+    #[cfg(feature = "app")]
     pub(crate) fn add(&mut self, rhs: &ProcessHarvest) {
         self.cpu_usage_percent += rhs.cpu_usage_percent;
         self.mem_usage_bytes += rhs.mem_usage_bytes;
@@ -121,6 +176,11 @@ impl ProcessHarvest {
         self.total_read_bytes += rhs.total_read_bytes;
         self.total_write_bytes += rhs.total_write_bytes;
         self.time = self.time.max(rhs.time);
+        #[cfg(target_os = "linux")]
+        {
+            self.cpu_wait_percent += rhs.cpu_wait_percent;
+            self.io_wait_percent += rhs.io_wait_percent;
+        }
         #[cfg(feature = "gpu")]
         {
             self.gpu_mem += rhs.gpu_mem;
@@ -144,9 +204,56 @@ impl DataCollector {
                 )
             } else if #[cfg(any(target_os = "freebsd", target_os = "macos", target_os = "windows", target_os = "android", target_os = "ios"))] {
                 sysinfo_process_data(self)
+            } else if #[cfg(any(target_os = "netbsd", target_os = "illumos"))] {
+                ps_process_data(self)
             } else {
                 Err(crate::data_collection::error::CollectionError::Unsupported)
             }
         }
     }
 }
+
+/// Collects the list of processes.
+#[derive(Debug)]
+pub(crate) struct ProcessSource;
+
+impl DataSource for ProcessSource {
+    fn name(&self) -> &'static str {
+        "processes"
+    }
+
+    fn is_enabled(&self, used_widgets: &UsedWidgets) -> bool {
+        used_widgets.use_proc
+    }
+
+    fn collect(&mut self, collector: &mut DataCollector) {
+        // Drop any strings that are no longer used by any process.
+        collector.interner.prune();
+
+        if let Ok(mut process_list) = collector.get_processes() {
+            if let Some(max) = collector.max_processes {
+                let total = process_list.len();
+                if total > max {
+                    // Keep the ones using the most CPU, which are most likely to be of interest.
+                    process_list.select_nth_unstable_by(max - 1, |a, b| {
+                        b.cpu_usage_percent.total_cmp(&a.cpu_usage_percent)
+                    });
+                    process_list.truncate(max);
+                    collector.report_issue(format!(
+                        "Only kept the {max} of {total} processes using the most CPU"
+                    ));
+                }
+            }
+
+            // NB: To avoid duplicate sorts on rerenders/events, we sort the processes by
+            // PID here. We also want to avoid re-sorting *again* later on
+            // if we're sorting by PID, since we already did it here!
+            process_list.sort_unstable_by_key(|p| p.pid);
+            collector.data.list_of_processes = Some(process_list);
+        }
+    }
+
+    fn has_data(&self, data: &Data) -> bool {
+        data.list_of_processes.is_some()
+    }
+}
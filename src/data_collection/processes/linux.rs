@@ -1,19 +1,31 @@
 //! Process data collection for Linux.
 
+mod delay;
+pub mod io_priority;
+pub mod namespace;
 mod process;
+pub mod security;
 
 use std::{
+    fmt::Write,
     fs::{self, File},
     io::{BufRead, BufReader},
+    path::PathBuf,
     time::Duration,
 };
 
-use hashbrown::HashSet;
+use delay::{read_delays, Delays};
+use io_priority::IoPriority;
 use process::*;
 use sysinfo::ProcessStatus;
 
-use super::{Pid, ProcessHarvest, UserTable};
-use crate::data_collection::{error::CollectionResult, DataCollector};
+use super::{ProcessHarvest, UserTable};
+use crate::{
+    data_collection::{error::CollectionResult, DataCollector, Pid},
+    utils::interner::StringInterner,
+};
+
+pub(crate) use delay::TaskStats;
 
 /// Maximum character length of a `/proc/<PID>/stat`` process name.
 /// If it's equal or greater, then we instead refer to the command for the name.
@@ -24,11 +36,17 @@ pub struct PrevProcDetails {
     total_read_bytes: u64,
     total_write_bytes: u64,
     cpu_time: u64,
+    /// The delays when last collected, or `None` for a new process or if they
+    /// weren't collected.
+    delays: Option<Delays>,
 }
 
 /// Given `/proc/stat` file contents, determine the idle and non-idle values of
 /// the CPU used to calculate CPU usage.
-fn fetch_cpu_usage(line: &str) -> (f64, f64) {
+///
+/// Under Hyper-V (such as with WSL), steal time is time the host spent on
+/// other work rather than time this system was busy, so it's counted as idle.
+fn fetch_cpu_usage(line: &str, is_hyper_v: bool) -> (f64, f64) {
     /// Converts a `Option<&str>` value to an f64. If it fails to parse or is
     /// `None`, it will return `0_f64`.
     fn str_to_f64(val: Option<&str>) -> f64 {
@@ -47,12 +65,24 @@ fn fetch_cpu_usage(line: &str) -> (f64, f64) {
 
     // Note we do not get guest/guest_nice, as they are calculated as part of
     // user/nice respectively See https://github.com/htop-dev/htop/blob/main/linux/LinuxProcessList.c
-    let idle = idle + iowait;
-    let non_idle = user + nice + system + irq + softirq + steal;
+    let (idle, non_idle) = if is_hyper_v {
+        (idle + iowait + steal, user + nice + system + irq + softirq)
+    } else {
+        (idle + iowait, user + nice + system + irq + softirq + steal)
+    };
 
     (idle, non_idle)
 }
 
+/// Given `/proc/uptime` file contents, estimate the total CPU time across all
+/// CPUs, in clock ticks. This is used in place of `/proc/stat` if it can't be
+/// read.
+fn estimate_cpu_time(uptime: &str, ticks_per_sec: u64, num_cpus: usize) -> Option<f64> {
+    let uptime_secs = uptime.split_whitespace().next()?.parse::<f64>().ok()?;
+
+    Some(uptime_secs * ticks_per_sec as f64 * num_cpus as f64)
+}
+
 struct CpuUsage {
     /// Difference between the total delta and the idle delta.
     cpu_usage: f64,
@@ -64,18 +94,32 @@ struct CpuUsage {
 fn cpu_usage_calculation(
     prev_idle: &mut f64, prev_non_idle: &mut f64,
 ) -> CollectionResult<CpuUsage> {
-    let (idle, non_idle) = {
-        // From SO answer: https://stackoverflow.com/a/23376195
-        let first_line = {
-            // We just need a single line from this file. Read it and return it.
-            let mut reader = BufReader::new(File::open("/proc/stat")?);
-            let mut buffer = String::new();
-            reader.read_line(&mut buffer)?;
-
-            buffer
-        };
+    // From SO answer: https://stackoverflow.com/a/23376195
+    let first_line = || -> std::io::Result<String> {
+        // We just need a single line from this file. Read it and return it.
+        let mut reader = BufReader::new(File::open("/proc/stat")?);
+        let mut buffer = String::new();
+        reader.read_line(&mut buffer)?;
+
+        Ok(buffer)
+    };
 
-        fetch_cpu_usage(&first_line)
+    let (idle, non_idle) = match first_line() {
+        Ok(first_line) => fetch_cpu_usage(&first_line, crate::data_collection::wsl::is_wsl()),
+        Err(_) => {
+            // Some systems, like newer versions of Android, don't let unprivileged users
+            // read `/proc/stat`. In that case, treat all time since boot as busy, which
+            // means each process still gets its correct share of the total CPU time.
+            let num_cpus = std::thread::available_parallelism().map_or(1, |num| num.get());
+            let non_idle = estimate_cpu_time(
+                &fs::read_to_string("/proc/uptime")?,
+                rustix::param::clock_ticks_per_second(),
+                num_cpus,
+            )
+            .ok_or("failed to parse /proc/uptime")?;
+
+            (0.0, non_idle)
+        }
     };
 
     let total = idle + non_idle;
@@ -130,14 +174,18 @@ fn get_linux_cpu_usage(
 }
 
 fn read_proc(
-    prev_proc: &PrevProcDetails, process: Process, args: ReadProcArgs, user_table: &mut UserTable,
+    prev_proc: &PrevProcDetails, process: Process, delays: Option<Delays>, args: ReadProcArgs,
+    fields: &ProcessFields, user_table: &mut UserTable, interner: &mut StringInterner,
 ) -> CollectionResult<(ProcessHarvest, u64)> {
     let Process {
-        pid: _,
+        pid,
         uid,
         stat,
         io,
         cmdline,
+        label,
+        namespaces,
+        security,
     } = process;
 
     let ReadProcArgs {
@@ -153,35 +201,53 @@ fn read_proc(
         let truncated_name = stat.comm.as_str();
         if let Ok(cmdline) = cmdline {
             if cmdline.is_empty() {
-                (format!("[{truncated_name}]"), truncated_name.to_string())
+                (
+                    interner.intern_with(|command| {
+                        command.push('[');
+                        command.push_str(truncated_name);
+                        command.push(']');
+                    }),
+                    interner.intern(truncated_name),
+                )
             } else {
                 (
-                    cmdline.join(" "),
+                    interner.intern_with(|command| {
+                        for (index, arg) in cmdline.iter().enumerate() {
+                            if index > 0 {
+                                command.push(' ');
+                            }
+                            command.push_str(arg);
+                        }
+                    }),
                     if truncated_name.len() >= MAX_STAT_NAME_LEN {
                         if let Some(first_part) = cmdline.first() {
                             // We're only interested in the executable part... not the file path.
                             // That's for command.
-                            first_part
-                                .rsplit_once('/')
-                                .map(|(_prefix, suffix)| suffix)
-                                .unwrap_or(truncated_name)
-                                .to_string()
+                            interner.intern(
+                                first_part
+                                    .rsplit_once('/')
+                                    .map(|(_prefix, suffix)| suffix)
+                                    .unwrap_or(truncated_name),
+                            )
                         } else {
-                            truncated_name.to_string()
+                            interner.intern(truncated_name)
                         }
                     } else {
-                        truncated_name.to_string()
+                        interner.intern(truncated_name)
                     },
                 )
             }
         } else {
-            (truncated_name.to_string(), truncated_name.to_string())
+            let name = interner.intern(truncated_name);
+            (name.clone(), name)
         }
     };
 
     let process_state_char = stat.state;
     let process_state = (
-        ProcessStatus::from(process_state_char).to_string(),
+        interner.intern_with(|state| {
+            let _ = write!(state, "{}", ProcessStatus::from(process_state_char));
+        }),
         process_state_char,
     );
     let (cpu_usage_percent, new_process_times) = get_linux_cpu_usage(
@@ -224,13 +290,31 @@ fn read_proc(
         };
 
     let user = uid
-        .and_then(|uid| {
-            user_table
-                .get_uid_to_username_mapping(uid)
-                .map(Into::into)
-                .ok()
-        })
-        .unwrap_or_else(|| "N/A".into());
+        .and_then(|uid| user_table.get_uid_to_username_mapping(uid).ok())
+        .unwrap_or_else(|| interner.intern("N/A"));
+
+    let label = label.map(|label| interner.intern(&label));
+    let io_priority = if fields.io_priority || fields.details_pid == Some(pid) {
+        IoPriority::read(pid, stat.nice).ok()
+    } else {
+        None
+    };
+
+    // Delays are summed over threads, so like CPU usage, these can go past 100%.
+    let (cpu_wait_percent, io_wait_percent) = match (delays, prev_proc.delays) {
+        (Some(delays), Some(prev_delays)) if time_difference_in_secs > 0 => {
+            let wait_percent = |delay: u64, prev_delay: u64| {
+                (delay.saturating_sub(prev_delay) as f64 / time_difference_in_secs as f64 / 1e9
+                    * 100.0) as f32
+            };
+
+            (
+                wait_percent(delays.cpu, prev_delays.cpu),
+                wait_percent(delays.block_io, prev_delays.block_io),
+            )
+        }
+        _ => (0.0, 0.0),
+    };
 
     let time = if let Ok(ticks_per_sec) = u32::try_from(rustix::param::clock_ticks_per_second()) {
         if ticks_per_sec == 0 {
@@ -244,8 +328,9 @@ fn read_proc(
 
     Ok((
         ProcessHarvest {
-            pid: process.pid,
+            pid,
             parent_pid,
+            start_time: stat.start_time,
             cpu_usage_percent,
             mem_usage_percent,
             mem_usage_bytes,
@@ -258,6 +343,12 @@ fn read_proc(
             process_state,
             uid,
             user,
+            label,
+            namespaces,
+            cpu_wait_percent,
+            io_wait_percent,
+            io_priority,
+            security,
             time,
             #[cfg(feature = "gpu")]
             gpu_mem: 0,
@@ -265,6 +356,8 @@ fn read_proc(
             gpu_mem_percent: 0.0,
             #[cfg(feature = "gpu")]
             gpu_util: 0,
+            #[cfg(feature = "plugins")]
+            plugin_values: Vec::new(),
         },
         new_process_times,
     ))
@@ -280,6 +373,35 @@ pub(crate) struct ProcHarvestOptions {
     pub unnormalized_cpu: bool,
 }
 
+/// Which optional details are collected for each process. Each takes another
+/// file read or system call per process, so they're only collected while a
+/// column, search, or the details of a process show them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessFields {
+    pub label: bool,
+    pub namespaces: namespace::NamespaceKinds,
+    /// How long processes wait for the CPU and block I/O.
+    pub delays: bool,
+    pub io_priority: bool,
+    /// The process whose details are shown, which also has its label, I/O
+    /// priority, and security context collected.
+    pub details_pid: Option<Pid>,
+}
+
+impl ProcessFields {
+    /// Returns the fields collected for either `self` or `other`, with the
+    /// details of `self`'s process.
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            label: self.label || other.label,
+            namespaces: self.namespaces.union(other.namespaces),
+            delays: self.delays || other.delays,
+            io_priority: self.io_priority || other.io_priority,
+            details_pid: self.details_pid.or(other.details_pid),
+        }
+    }
+}
+
 fn is_str_numeric(s: &str) -> bool {
     s.chars().all(|c| c.is_ascii_digit())
 }
@@ -307,8 +429,19 @@ pub(crate) fn linux_process_data(
         use_current_cpu_total: collector.use_current_cpu_total,
         unnormalized_cpu: collector.unnormalized_cpu,
     };
+    // We double-buffer the previous process details - the details from the last
+    // run are moved to `prev_pid_mapping`, and we only re-add the ones for
+    // processes that still exist. This avoids reallocating either map each run.
+    std::mem::swap(&mut collector.pid_mapping, &mut collector.prev_pid_mapping);
+    collector.pid_mapping.clear();
+
     let pid_mapping = &mut collector.pid_mapping;
+    let prev_pid_mapping = &collector.prev_pid_mapping;
     let user_table = &mut collector.user_table;
+    let buffer = &mut collector.proc_buffer;
+    let interner = &mut collector.interner;
+    let task_stats = &mut collector.task_stats;
+    let fields = &collector.process_fields;
 
     let ProcHarvestOptions {
         use_current_cpu_total,
@@ -335,15 +468,22 @@ pub(crate) fn linux_process_data(
         cpu_usage /= num_processors;
     }
 
-    let mut pids_to_clear: HashSet<Pid> = pid_mapping.keys().cloned().collect();
-
-    let pids = fs::read_dir("/proc")?.flatten().filter_map(|dir| {
-        if is_str_numeric(dir.file_name().to_string_lossy().trim()) {
-            Some(dir.path())
-        } else {
-            None
-        }
-    });
+    // Processes we don't have access to may be hidden from `/proc` (e.g. with
+    // `hidepid`, or on Android), in which case we only see the ones we do. If we
+    // can't list it at all, we can at least still show ourselves.
+    let pids: Vec<PathBuf> = match fs::read_dir("/proc") {
+        Ok(dir) => dir
+            .flatten()
+            .filter_map(|dir| {
+                if is_str_numeric(dir.file_name().to_string_lossy().trim()) {
+                    Some(dir.path())
+                } else {
+                    None
+                }
+            })
+            .collect(),
+        Err(_) => vec![PathBuf::from("/proc/self")],
+    };
 
     let args = ReadProcArgs {
         use_current_cpu_total,
@@ -354,16 +494,37 @@ pub(crate) fn linux_process_data(
         uptime: sysinfo::System::uptime(),
     };
 
-    let process_vector: Vec<ProcessHarvest> = pids
-        .filter_map(|pid_path| {
-            if let Ok(process) = Process::from_path(pid_path) {
+    let new_proc_details = PrevProcDetails::default();
+
+    // Processes that exit (or are zombies) while being read aren't a problem, but
+    // not being allowed to read them is worth letting the user know about.
+    let (mut num_denied, mut num_io_denied) = (0, 0);
+
+    let mut process_vector: Vec<ProcessHarvest> = Vec::with_capacity(prev_pid_mapping.len());
+
+    process_vector.extend(pids.into_iter().filter_map(|pid_path| {
+        match Process::from_path(pid_path, buffer, fields) {
+            Ok(process) => {
                 let pid = process.pid;
-                let prev_proc_details = pid_mapping.entry(pid).or_default();
+                let is_zombie = process.stat.state == 'Z';
+                if !is_zombie && matches!(&process.io, Err(err) if is_permission_denied(err)) {
+                    num_io_denied += 1;
+                }
+                let prev_proc_details = prev_pid_mapping.get(&pid).unwrap_or(&new_proc_details);
+                let delays = fields
+                    .delays
+                    .then(|| read_delays(task_stats, pid, process.stat.block_io_ticks, buffer));
 
                 #[cfg_attr(not(feature = "gpu"), expect(unused_mut))]
-                if let Ok((mut process_harvest, new_process_times)) =
-                    read_proc(prev_proc_details, process, args, user_table)
-                {
+                if let Ok((mut process_harvest, new_process_times)) = read_proc(
+                    prev_proc_details,
+                    process,
+                    delays,
+                    args,
+                    fields,
+                    user_table,
+                    interner,
+                ) {
                     #[cfg(feature = "gpu")]
                     if let Some(gpus) = &collector.gpu_pids {
                         gpus.iter().for_each(|gpu| {
@@ -380,22 +541,36 @@ pub(crate) fn linux_process_data(
                         }
                     }
 
-                    prev_proc_details.cpu_time = new_process_times;
-                    prev_proc_details.total_read_bytes = process_harvest.total_read_bytes;
-                    prev_proc_details.total_write_bytes = process_harvest.total_write_bytes;
+                    pid_mapping.insert(
+                        pid,
+                        PrevProcDetails {
+                            cpu_time: new_process_times,
+                            total_read_bytes: process_harvest.total_read_bytes,
+                            total_write_bytes: process_harvest.total_write_bytes,
+                            delays,
+                        },
+                    );
 
-                    pids_to_clear.remove(&pid);
                     return Some(process_harvest);
                 }
             }
+            Err(err) if is_permission_denied(&err) => num_denied += 1,
+            Err(_) => {}
+        }
 
-            None
-        })
-        .collect();
+        None
+    }));
 
-    pids_to_clear.iter().for_each(|pid| {
-        pid_mapping.remove(pid);
-    });
+    if num_denied > 0 {
+        collector.report_issue(format!(
+            "Permission denied reading {num_denied} processes, so they aren't shown"
+        ));
+    }
+    if num_io_denied > 0 {
+        collector.report_issue(format!(
+            "Permission denied reading I/O for {num_io_denied} processes, so it's shown as 0"
+        ));
+    }
 
     Ok(process_vector)
 }
@@ -408,38 +583,52 @@ mod tests {
     fn test_proc_cpu_parse() {
         assert_eq!(
             (100_f64, 200_f64),
-            fetch_cpu_usage("100 0 100 100"),
+            fetch_cpu_usage("100 0 100 100", false),
             "Failed to properly calculate idle/non-idle for /proc/stat CPU with 4 values"
         );
         assert_eq!(
             (120_f64, 200_f64),
-            fetch_cpu_usage("100 0 100 100 20"),
+            fetch_cpu_usage("100 0 100 100 20", false),
             "Failed to properly calculate idle/non-idle for /proc/stat CPU with 5 values"
         );
         assert_eq!(
             (120_f64, 230_f64),
-            fetch_cpu_usage("100 0 100 100 20 30"),
+            fetch_cpu_usage("100 0 100 100 20 30", false),
             "Failed to properly calculate idle/non-idle for /proc/stat CPU with 6 values"
         );
         assert_eq!(
             (120_f64, 270_f64),
-            fetch_cpu_usage("100 0 100 100 20 30 40"),
+            fetch_cpu_usage("100 0 100 100 20 30 40", false),
             "Failed to properly calculate idle/non-idle for /proc/stat CPU with 7 values"
         );
         assert_eq!(
             (120_f64, 320_f64),
-            fetch_cpu_usage("100 0 100 100 20 30 40 50"),
+            fetch_cpu_usage("100 0 100 100 20 30 40 50", false),
             "Failed to properly calculate idle/non-idle for /proc/stat CPU with 8 values"
         );
         assert_eq!(
             (120_f64, 320_f64),
-            fetch_cpu_usage("100 0 100 100 20 30 40 50 100"),
+            fetch_cpu_usage("100 0 100 100 20 30 40 50 100", false),
             "Failed to properly calculate idle/non-idle for /proc/stat CPU with 9 values"
         );
         assert_eq!(
             (120_f64, 320_f64),
-            fetch_cpu_usage("100 0 100 100 20 30 40 50 100 200"),
+            fetch_cpu_usage("100 0 100 100 20 30 40 50 100 200", false),
             "Failed to properly calculate idle/non-idle for /proc/stat CPU with 10 values"
         );
+        assert_eq!(
+            (170_f64, 270_f64),
+            fetch_cpu_usage("100 0 100 100 20 30 40 50", true),
+            "Failed to count steal time as idle under Hyper-V"
+        );
+    }
+
+    #[test]
+    fn test_estimate_cpu_time() {
+        assert_eq!(
+            estimate_cpu_time("350.5 1200.25\n", 100, 4),
+            Some(140_200.0)
+        );
+        assert_eq!(estimate_cpu_time("", 100, 4), None);
     }
 }
@@ -0,0 +1,455 @@
+//! How long Linux processes have spent waiting rather than running, from
+//! [delay accounting](https://docs.kernel.org/accounting/delay-accounting.html).
+//!
+//! This is read with [taskstats](https://docs.kernel.org/accounting/taskstats.html)
+//! over netlink if allowed, which needs `CAP_NET_ADMIN`. Otherwise, it's read
+//! from `/proc`, which has the same totals, but only in clock ticks for block
+//! I/O.
+
+use std::{
+    fs::File,
+    io::{self, Read},
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+};
+
+use crate::data_collection::processes::Pid;
+
+/// The name of the taskstats generic netlink family, whose ID has to be looked
+/// up.
+const TASKSTATS_FAMILY_NAME: &[u8] = b"TASKSTATS\0";
+const TASKSTATS_CMD_GET: u8 = 1;
+const TASKSTATS_CMD_ATTR_TGID: u16 = 2;
+const TASKSTATS_TYPE_STATS: u16 = 3;
+const TASKSTATS_TYPE_AGGR_TGID: u16 = 5;
+
+/// Where `cpu_delay_total` and `blkio_delay_total` are in `struct taskstats`,
+/// which haven't moved since its first version.
+const CPU_DELAY_OFFSET: usize = 24;
+const BLOCK_IO_DELAY_OFFSET: usize = 40;
+
+const NETLINK_HEADER_LEN: usize = 16;
+const GENERIC_HEADER_LEN: usize = 4;
+const ATTRIBUTE_HEADER_LEN: usize = 4;
+
+/// The top two bits of an attribute's type are flags.
+const ATTRIBUTE_TYPE_MASK: u16 = 0x3fff;
+
+const NANOSECONDS_PER_SECOND: u64 = 1_000_000_000;
+
+/// How long to wait for a reply from taskstats before giving up on it, so a
+/// reply that never comes doesn't hold up collection.
+const RECEIVE_TIMEOUT_MICROS: libc::suseconds_t = 100_000;
+
+/// The total time a process has spent waiting, in nanoseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Delays {
+    /// Waiting to run on a CPU.
+    pub cpu: u64,
+    /// Waiting for block I/O to finish.
+    pub block_io: u64,
+}
+
+/// A netlink socket to get taskstats from.
+#[derive(Debug)]
+pub(crate) struct TaskStats {
+    socket: OwnedFd,
+    family: u16,
+    sequence: u32,
+    buffer: Vec<u8>,
+}
+
+impl TaskStats {
+    /// Opens a socket to get taskstats from. This fails if the kernel doesn't
+    /// support taskstats.
+    pub(crate) fn open() -> io::Result<Self> {
+        // SAFETY: libc call with valid arguments, and the result is checked
+        // before it's used as a file descriptor.
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+                libc::NETLINK_GENERIC,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // SAFETY: `fd` is a valid file descriptor that nothing else owns.
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        let timeout = libc::timeval {
+            tv_sec: 0,
+            tv_usec: RECEIVE_TIMEOUT_MICROS,
+        };
+        // SAFETY: libc call with a valid socket, and an option value of the
+        // given length.
+        let result = unsafe {
+            libc::setsockopt(
+                socket.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                std::ptr::from_ref(&timeout).cast(),
+                std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+            )
+        };
+        if result != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut task_stats = Self {
+            socket,
+            family: libc::GENL_ID_CTRL as u16,
+            sequence: 0,
+            buffer: vec![0; 4096],
+        };
+        let family = parse_family_id(task_stats.request(
+            libc::CTRL_CMD_GETFAMILY as u8,
+            libc::CTRL_ATTR_FAMILY_NAME as u16,
+            TASKSTATS_FAMILY_NAME,
+        )?)
+        .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidData))?;
+        task_stats.family = family;
+
+        Ok(task_stats)
+    }
+
+    /// Returns the delays of the process with the given PID, summed over its
+    /// threads.
+    pub(crate) fn delays(&mut self, pid: Pid) -> io::Result<Delays> {
+        let response = self.request(
+            TASKSTATS_CMD_GET,
+            TASKSTATS_CMD_ATTR_TGID,
+            &(pid as u32).to_ne_bytes(),
+        )?;
+
+        parse_delays(response).ok_or_else(|| io::Error::from(io::ErrorKind::InvalidData))
+    }
+
+    /// Sends a request with one attribute, returning the response. This fails
+    /// with [`io::ErrorKind::WouldBlock`] if no response came in time.
+    fn request(&mut self, command: u8, attribute: u16, value: &[u8]) -> io::Result<&[u8]> {
+        self.sequence = self.sequence.wrapping_add(1);
+        let message = encode_request(self.family, self.sequence, command, attribute, value);
+
+        // SAFETY: libc call with a valid socket, and a message of the given
+        // length. Unbound netlink sockets send to the kernel.
+        let sent = unsafe {
+            libc::send(
+                self.socket.as_raw_fd(),
+                message.as_ptr().cast(),
+                message.len(),
+                0,
+            )
+        };
+        if sent < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // A response to an earlier request that timed out may still be queued,
+        // so skip any that aren't for this one.
+        loop {
+            // SAFETY: libc call with a valid socket, and a buffer of the given
+            // length to write to.
+            let received = unsafe {
+                libc::recv(
+                    self.socket.as_raw_fd(),
+                    self.buffer.as_mut_ptr().cast(),
+                    self.buffer.len(),
+                    0,
+                )
+            };
+            if received < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let response = &self.buffer[..received as usize];
+            if let Some(result) = check_response(response, self.family, self.sequence) {
+                return result.map(|len| &self.buffer[..len]);
+            }
+        }
+    }
+}
+
+/// Reads the delays of the process with the given PID with taskstats, or from
+/// `/proc` if it can't be used. If taskstats isn't allowed, it's dropped so
+/// it isn't tried again, as it is if it stops responding.
+///
+/// `block_io_ticks` is the block I/O delay from `/proc/<PID>/stat`.
+pub(crate) fn read_delays(
+    task_stats: &mut Option<TaskStats>, pid: Pid, block_io_ticks: u64, buffer: &mut String,
+) -> Delays {
+    if let Some(stats) = task_stats {
+        match stats.delays(pid) {
+            Ok(delays) => return delays,
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::PermissionDenied
+                        | io::ErrorKind::WouldBlock
+                        | io::ErrorKind::TimedOut
+                ) =>
+            {
+                *task_stats = None
+            }
+            Err(_) => {}
+        }
+    }
+
+    buffer.clear();
+    let cpu = File::open(format!("/proc/{pid}/schedstat"))
+        .and_then(|mut file| file.read_to_string(buffer))
+        .ok()
+        .and_then(|_| parse_schedstat(buffer))
+        .unwrap_or(0);
+    let block_io = block_io_ticks
+        .saturating_mul(NANOSECONDS_PER_SECOND)
+        .checked_div(rustix::param::clock_ticks_per_second())
+        .unwrap_or(0);
+
+    Delays { cpu, block_io }
+}
+
+/// Returns how long a process has waited to run, in nanoseconds, from its
+/// `/proc/<PID>/schedstat`.
+fn parse_schedstat(schedstat: &str) -> Option<u64> {
+    schedstat.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// Encodes a generic netlink request with one attribute.
+fn encode_request(
+    family: u16, sequence: u32, command: u8, attribute: u16, value: &[u8],
+) -> Vec<u8> {
+    let attribute_len = ATTRIBUTE_HEADER_LEN + value.len();
+    let len = NETLINK_HEADER_LEN + GENERIC_HEADER_LEN + align(attribute_len);
+
+    let mut message = Vec::with_capacity(len);
+    message.extend_from_slice(&(len as u32).to_ne_bytes());
+    message.extend_from_slice(&family.to_ne_bytes());
+    message.extend_from_slice(&(libc::NLM_F_REQUEST as u16).to_ne_bytes());
+    message.extend_from_slice(&sequence.to_ne_bytes());
+    // The port ID, which the kernel fills in.
+    message.extend_from_slice(&0_u32.to_ne_bytes());
+    // The command, its version, and two reserved bytes.
+    message.extend_from_slice(&[command, 1, 0, 0]);
+    message.extend_from_slice(&(attribute_len as u16).to_ne_bytes());
+    message.extend_from_slice(&attribute.to_ne_bytes());
+    message.extend_from_slice(value);
+    message.resize(len, 0);
+
+    message
+}
+
+/// Checks that `response` answers the request with `sequence` sent to
+/// `family`, returning its length. This is an error if the kernel sent one
+/// instead, or `None` if it answers another request.
+fn check_response(response: &[u8], family: u16, sequence: u32) -> Option<io::Result<usize>> {
+    let header = || {
+        Some((
+            u32::from_ne_bytes(response.get(0..4)?.try_into().ok()?) as usize,
+            u16::from_ne_bytes(response.get(4..6)?.try_into().ok()?),
+            u32::from_ne_bytes(response.get(8..12)?.try_into().ok()?),
+        ))
+    };
+    let Some((len, message_type, response_sequence)) =
+        header().filter(|(len, ..)| (NETLINK_HEADER_LEN..=response.len()).contains(len))
+    else {
+        return Some(Err(io::Error::from(io::ErrorKind::InvalidData)));
+    };
+
+    if response_sequence != sequence {
+        None
+    } else if message_type == libc::NLMSG_ERROR as u16 {
+        Some(Err(netlink_error(&response[..len])))
+    } else if message_type != family {
+        Some(Err(io::Error::from(io::ErrorKind::InvalidData)))
+    } else {
+        Some(Ok(len))
+    }
+}
+
+/// Returns the error in a netlink error message. An acknowledgement (an error
+/// of 0) isn't a response to a request, so it's also treated as one.
+fn netlink_error(message: &[u8]) -> io::Error {
+    match message
+        .get(16..20)
+        .and_then(|code| code.try_into().ok())
+        .map(i32::from_ne_bytes)
+    {
+        Some(code) if code < 0 => io::Error::from_raw_os_error(-code),
+        _ => io::Error::from(io::ErrorKind::InvalidData),
+    }
+}
+
+/// Returns the attributes in `data` as their types and values.
+fn attributes(mut data: &[u8]) -> impl Iterator<Item = (u16, &[u8])> {
+    std::iter::from_fn(move || {
+        let len = usize::from(u16::from_ne_bytes(data.get(0..2)?.try_into().ok()?));
+        let attribute_type = u16::from_ne_bytes(data.get(2..4)?.try_into().ok()?);
+        let value = data.get(ATTRIBUTE_HEADER_LEN..len)?;
+        data = data.get(align(len)..).unwrap_or_default();
+
+        Some((attribute_type & ATTRIBUTE_TYPE_MASK, value))
+    })
+}
+
+/// Returns the attributes of a generic netlink response.
+fn response_attributes(response: &[u8]) -> impl Iterator<Item = (u16, &[u8])> {
+    attributes(
+        response
+            .get(NETLINK_HEADER_LEN + GENERIC_HEADER_LEN..)
+            .unwrap_or_default(),
+    )
+}
+
+fn parse_family_id(response: &[u8]) -> Option<u16> {
+    let (_, value) = response_attributes(response)
+        .find(|(attribute_type, _)| *attribute_type == libc::CTRL_ATTR_FAMILY_ID as u16)?;
+
+    Some(u16::from_ne_bytes(value.get(0..2)?.try_into().ok()?))
+}
+
+fn parse_delays(response: &[u8]) -> Option<Delays> {
+    let (_, aggregate) = response_attributes(response)
+        .find(|(attribute_type, _)| *attribute_type == TASKSTATS_TYPE_AGGR_TGID)?;
+    let (_, stats) = attributes(aggregate)
+        .find(|(attribute_type, _)| *attribute_type == TASKSTATS_TYPE_STATS)?;
+
+    let read_u64 = |offset: usize| {
+        Some(u64::from_ne_bytes(
+            stats.get(offset..offset + 8)?.try_into().ok()?,
+        ))
+    };
+
+    Some(Delays {
+        cpu: read_u64(CPU_DELAY_OFFSET)?,
+        block_io: read_u64(BLOCK_IO_DELAY_OFFSET)?,
+    })
+}
+
+/// Rounds `len` up to the 4-byte alignment of netlink attributes.
+fn align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn attribute(attribute_type: u16, value: &[u8]) -> Vec<u8> {
+        let len = ATTRIBUTE_HEADER_LEN + value.len();
+        let mut attribute = Vec::new();
+        attribute.extend_from_slice(&(len as u16).to_ne_bytes());
+        attribute.extend_from_slice(&attribute_type.to_ne_bytes());
+        attribute.extend_from_slice(value);
+        attribute.resize(align(len), 0);
+
+        attribute
+    }
+
+    fn response(attributes: &[u8]) -> Vec<u8> {
+        let mut response = vec![0; NETLINK_HEADER_LEN + GENERIC_HEADER_LEN];
+        response.extend_from_slice(attributes);
+
+        response
+    }
+
+    fn message(message_type: u16, sequence: u32, payload: &[u8]) -> Vec<u8> {
+        let mut message = Vec::new();
+        message.extend_from_slice(&((NETLINK_HEADER_LEN + payload.len()) as u32).to_ne_bytes());
+        message.extend_from_slice(&message_type.to_ne_bytes());
+        message.extend_from_slice(&0_u16.to_ne_bytes());
+        message.extend_from_slice(&sequence.to_ne_bytes());
+        message.extend_from_slice(&0_u32.to_ne_bytes());
+        message.extend_from_slice(payload);
+
+        message
+    }
+
+    #[test]
+    fn requests() {
+        let request = encode_request(0x10, 7, 3, 2, b"TASKSTATS\0");
+
+        assert_eq!(request.len(), 36);
+        assert_eq!(request[0..4], 36_u32.to_ne_bytes());
+        assert_eq!(request[4..6], 0x10_u16.to_ne_bytes());
+        assert_eq!(request[8..12], 7_u32.to_ne_bytes());
+        assert_eq!(request[16], 3);
+        assert_eq!(request[20..22], 14_u16.to_ne_bytes());
+        assert_eq!(&request[24..34], b"TASKSTATS\0");
+    }
+
+    #[test]
+    fn family_ids() {
+        let mut attributes = attribute(2, b"TASKSTATS\0");
+        attributes.extend(attribute(1, &0x1a_u16.to_ne_bytes()));
+
+        assert_eq!(parse_family_id(&response(&attributes)), Some(0x1a));
+        assert_eq!(parse_family_id(&response(&[])), None);
+    }
+
+    #[test]
+    fn delays() {
+        let mut stats = vec![0; 328];
+        stats[CPU_DELAY_OFFSET..CPU_DELAY_OFFSET + 8].copy_from_slice(&1500_u64.to_ne_bytes());
+        stats[BLOCK_IO_DELAY_OFFSET..BLOCK_IO_DELAY_OFFSET + 8]
+            .copy_from_slice(&2500_u64.to_ne_bytes());
+
+        let mut aggregate = attribute(2, &42_u32.to_ne_bytes());
+        aggregate.extend(attribute(TASKSTATS_TYPE_STATS, &stats));
+
+        assert_eq!(
+            parse_delays(&response(&attribute(
+                TASKSTATS_TYPE_AGGR_TGID | 0x8000,
+                &aggregate
+            ))),
+            Some(Delays {
+                cpu: 1500,
+                block_io: 2500,
+            })
+        );
+        assert_eq!(parse_delays(&response(&aggregate)), None);
+    }
+
+    #[test]
+    fn responses() {
+        let reply = message(0x1a, 5, &[0; 8]);
+        assert_eq!(
+            check_response(&reply, 0x1a, 5).map(|result| result.ok()),
+            Some(Some(reply.len()))
+        );
+
+        // Replies to earlier requests are skipped.
+        assert!(check_response(&reply, 0x1a, 6).is_none());
+
+        let other_family = message(0x1b, 5, &[0; 8]);
+        assert_eq!(
+            check_response(&other_family, 0x1a, 5).map(|result| result.unwrap_err().kind()),
+            Some(io::ErrorKind::InvalidData)
+        );
+
+        let truncated = &reply[..NETLINK_HEADER_LEN + 4];
+        assert_eq!(
+            check_response(truncated, 0x1a, 5).map(|result| result.unwrap_err().kind()),
+            Some(io::ErrorKind::InvalidData)
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = message(libc::NLMSG_ERROR as u16, 5, &(-libc::EPERM).to_ne_bytes());
+        assert_eq!(
+            check_response(&error, 0x1a, 5).map(|result| result.unwrap_err().kind()),
+            Some(io::ErrorKind::PermissionDenied)
+        );
+
+        let ack = message(libc::NLMSG_ERROR as u16, 5, &0_i32.to_ne_bytes());
+        assert_eq!(netlink_error(&ack).kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn schedstat() {
+        assert_eq!(parse_schedstat("1234567 89012 34\n"), Some(89012));
+        assert_eq!(parse_schedstat("1234567"), None);
+    }
+}
@@ -0,0 +1,198 @@
+//! The I/O scheduling class and priority of Linux processes, which is what
+//! `ionice` shows and sets. See [ioprio_set(2)](https://man7.org/linux/man-pages/man2/ioprio_set.2.html).
+
+use std::{
+    fmt::{self, Display},
+    io,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data_collection::processes::Pid;
+
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+const IOPRIO_LEVEL_MASK: libc::c_int = 0x7;
+
+const IOPRIO_CLASS_NONE: libc::c_int = 0;
+const IOPRIO_CLASS_RT: libc::c_int = 1;
+const IOPRIO_CLASS_BE: libc::c_int = 2;
+const IOPRIO_CLASS_IDLE: libc::c_int = 3;
+
+/// The lowest level within a class. Lower levels are served first.
+const MAX_LEVEL: u8 = 7;
+
+/// The I/O scheduling class of a process, with its level within the class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IoPriority {
+    /// Served before any other class. Setting this needs elevated permissions.
+    Realtime(u8),
+    /// Served in turn with other best-effort processes, by level. This is the
+    /// default.
+    BestEffort(u8),
+    /// Only served when no other process is using the disk.
+    Idle,
+}
+
+impl IoPriority {
+    /// Reads the I/O priority of the process with the given PID. A process
+    /// without one set is treated as best-effort, at the level its nice value
+    /// `nice` maps to, as that is what the kernel does.
+    pub fn read(pid: Pid, nice: i32) -> io::Result<Self> {
+        // SAFETY: ioprio_get only reads, and the arguments are valid.
+        let raw =
+            unsafe { libc::syscall(libc::SYS_ioprio_get, IOPRIO_WHO_PROCESS, pid as libc::c_int) };
+        if raw < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Self::from_raw(raw as libc::c_int, nice)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidData))
+    }
+
+    /// Sets the I/O priority of the process with the given PID.
+    pub fn set(self, pid: Pid) -> io::Result<()> {
+        // SAFETY: the arguments are valid, and we act properly on an error
+        // (return value not 0).
+        let output = unsafe {
+            libc::syscall(
+                libc::SYS_ioprio_set,
+                IOPRIO_WHO_PROCESS,
+                pid as libc::c_int,
+                self.to_raw(),
+            )
+        };
+        if output != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    fn from_raw(raw: libc::c_int, nice: i32) -> Option<Self> {
+        let level = (raw & IOPRIO_LEVEL_MASK) as u8;
+
+        match raw >> IOPRIO_CLASS_SHIFT {
+            IOPRIO_CLASS_NONE => Some(Self::BestEffort(((nice.clamp(-20, 19) + 20) / 5) as u8)),
+            IOPRIO_CLASS_RT => Some(Self::Realtime(level)),
+            IOPRIO_CLASS_BE => Some(Self::BestEffort(level)),
+            IOPRIO_CLASS_IDLE => Some(Self::Idle),
+            _ => None,
+        }
+    }
+
+    fn to_raw(self) -> libc::c_int {
+        let (class, level) = match self {
+            Self::Realtime(level) => (IOPRIO_CLASS_RT, level),
+            Self::BestEffort(level) => (IOPRIO_CLASS_BE, level),
+            Self::Idle => (IOPRIO_CLASS_IDLE, 0),
+        };
+
+        (class << IOPRIO_CLASS_SHIFT) | libc::c_int::from(level)
+    }
+
+    /// Returns where this is in order from the highest priority, which is 0.
+    pub fn rank(self) -> u8 {
+        match self {
+            Self::Realtime(level) => level,
+            Self::BestEffort(level) => MAX_LEVEL + 1 + level,
+            Self::Idle => 2 * (MAX_LEVEL + 1),
+        }
+    }
+
+    /// Returns the priority one level higher. This stops at the highest
+    /// best-effort level rather than going realtime, as that can starve every
+    /// other process of I/O.
+    pub fn raised(self) -> Self {
+        match self {
+            Self::Realtime(level) => Self::Realtime(level.saturating_sub(1)),
+            Self::BestEffort(level) => Self::BestEffort(level.saturating_sub(1)),
+            Self::Idle => Self::BestEffort(MAX_LEVEL),
+        }
+    }
+
+    /// Returns the priority one level lower, down to idle.
+    pub fn lowered(self) -> Self {
+        match self {
+            Self::Realtime(level) if level < MAX_LEVEL => Self::Realtime(level + 1),
+            Self::Realtime(_) => Self::BestEffort(0),
+            Self::BestEffort(level) if level < MAX_LEVEL => Self::BestEffort(level + 1),
+            Self::BestEffort(_) | Self::Idle => Self::Idle,
+        }
+    }
+}
+
+impl Display for IoPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Realtime(level) => write!(f, "rt/{level}"),
+            Self::BestEffort(level) => write!(f, "be/{level}"),
+            Self::Idle => f.write_str("idle"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn raw_priorities() {
+        assert_eq!(IoPriority::from_raw(0, 0), Some(IoPriority::BestEffort(4)));
+        assert_eq!(
+            IoPriority::from_raw(0, -20),
+            Some(IoPriority::BestEffort(0))
+        );
+        assert_eq!(IoPriority::from_raw(0, 19), Some(IoPriority::BestEffort(7)));
+        assert_eq!(
+            IoPriority::from_raw(0x2000 | 3, 0),
+            Some(IoPriority::Realtime(3))
+        );
+        assert_eq!(
+            IoPriority::from_raw(0x4000 | 7, 0),
+            Some(IoPriority::BestEffort(7))
+        );
+        assert_eq!(IoPriority::from_raw(0x6000, 0), Some(IoPriority::Idle));
+        assert_eq!(IoPriority::from_raw(0x8000, 0), None);
+
+        for priority in [
+            IoPriority::Realtime(0),
+            IoPriority::BestEffort(5),
+            IoPriority::Idle,
+        ] {
+            assert_eq!(IoPriority::from_raw(priority.to_raw(), 0), Some(priority));
+        }
+    }
+
+    #[test]
+    fn changing_priorities() {
+        assert_eq!(
+            IoPriority::BestEffort(4).raised(),
+            IoPriority::BestEffort(3)
+        );
+        assert_eq!(
+            IoPriority::BestEffort(0).raised(),
+            IoPriority::BestEffort(0)
+        );
+        assert_eq!(IoPriority::Idle.raised(), IoPriority::BestEffort(7));
+        assert_eq!(IoPriority::Realtime(1).raised(), IoPriority::Realtime(0));
+
+        assert_eq!(
+            IoPriority::BestEffort(4).lowered(),
+            IoPriority::BestEffort(5)
+        );
+        assert_eq!(IoPriority::BestEffort(7).lowered(), IoPriority::Idle);
+        assert_eq!(IoPriority::Idle.lowered(), IoPriority::Idle);
+        assert_eq!(IoPriority::Realtime(7).lowered(), IoPriority::BestEffort(0));
+
+        assert!(IoPriority::Realtime(7).rank() < IoPriority::BestEffort(0).rank());
+        assert!(IoPriority::BestEffort(7).rank() < IoPriority::Idle.rank());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(IoPriority::Realtime(0).to_string(), "rt/0");
+        assert_eq!(IoPriority::BestEffort(4).to_string(), "be/4");
+        assert_eq!(IoPriority::Idle.to_string(), "idle");
+    }
+}
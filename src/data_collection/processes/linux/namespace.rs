@@ -0,0 +1,130 @@
+//! The namespaces of Linux processes, which tell apart the processes of
+//! different containers. See [namespaces(7)](https://man7.org/linux/man-pages/man7/namespaces.7.html).
+
+use rustix::{
+    fd::AsFd,
+    fs::{Mode, OFlags},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::data_collection::processes::Pid;
+
+/// Which namespaces to read, as each is another system call per process.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NamespaceKinds {
+    pub pid: bool,
+    pub net: bool,
+    pub mnt: bool,
+}
+
+impl NamespaceKinds {
+    /// Every kind of namespace.
+    pub const ALL: Self = Self {
+        pid: true,
+        net: true,
+        mnt: true,
+    };
+
+    /// Returns the kinds in either `self` or `other`.
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            pid: self.pid || other.pid,
+            net: self.net || other.net,
+            mnt: self.mnt || other.mnt,
+        }
+    }
+}
+
+/// The IDs (inode numbers) of some of the namespaces of a process. Each is
+/// `None` if it couldn't be read, as reading them needs the same permissions
+/// as tracing the process.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Namespaces {
+    pub pid: Option<u64>,
+    pub net: Option<u64>,
+    pub mnt: Option<u64>,
+}
+
+impl Namespaces {
+    /// Reads the given kinds of namespaces from the links in `ns/`, given the
+    /// `/proc/<PID>` directory. The others are left as `None`.
+    pub(crate) fn read(dir: impl AsFd, kinds: NamespaceKinds) -> Self {
+        let read = |wanted: bool, link: &str| {
+            if !wanted {
+                return None;
+            }
+
+            rustix::fs::readlinkat(dir.as_fd(), link, Vec::new())
+                .ok()
+                .and_then(|target| parse_namespace_link(&target.to_string_lossy()))
+        };
+
+        Self {
+            pid: read(kinds.pid, "ns/pid"),
+            net: read(kinds.net, "ns/net"),
+            mnt: read(kinds.mnt, "ns/mnt"),
+        }
+    }
+
+    /// Reads every namespace of the process with the given PID, for when they
+    /// aren't being collected.
+    pub fn read_process(pid: Pid) -> Self {
+        rustix::fs::openat(
+            rustix::fs::CWD,
+            format!("/proc/{pid}"),
+            OFlags::PATH | OFlags::DIRECTORY | OFlags::CLOEXEC,
+            Mode::empty(),
+        )
+        .map(|dir| Self::read(dir, NamespaceKinds::ALL))
+        .unwrap_or_default()
+    }
+
+    /// Returns a process search query that matches processes in the same
+    /// namespaces, or `None` if none of them are known.
+    pub fn search_query(&self) -> Option<String> {
+        let conditions: Vec<String> = [
+            ("pidns", self.pid),
+            ("netns", self.net),
+            ("mntns", self.mnt),
+        ]
+        .into_iter()
+        .filter_map(|(prefix, id)| id.map(|id| format!("{prefix} = {id}")))
+        .collect();
+
+        (!conditions.is_empty()).then(|| conditions.join(" and "))
+    }
+}
+
+/// Parses the target of a namespace link, like `net:[4026531840]`.
+fn parse_namespace_link(target: &str) -> Option<u64> {
+    let (_, id) = target.split_once(":[")?;
+
+    id.strip_suffix(']')?.parse().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn namespace_links() {
+        assert_eq!(parse_namespace_link("net:[4026531840]"), Some(4026531840));
+        assert_eq!(parse_namespace_link("pid:[4026532281]"), Some(4026532281));
+        assert_eq!(parse_namespace_link("net:[4026531840"), None);
+        assert_eq!(parse_namespace_link("4026531840"), None);
+    }
+
+    #[test]
+    fn search_queries() {
+        let namespaces = Namespaces {
+            pid: Some(1),
+            net: None,
+            mnt: Some(3),
+        };
+        assert_eq!(
+            namespaces.search_query().as_deref(),
+            Some("pidns = 1 and mntns = 3")
+        );
+        assert_eq!(Namespaces::default().search_query(), None);
+    }
+}
@@ -16,6 +16,11 @@ use rustix::{
     path::Arg,
 };
 
+use super::{
+    namespace::Namespaces,
+    security::{parse_label, ProcessSecurity},
+    ProcessFields,
+};
 use crate::data_collection::processes::Pid;
 
 static PAGESIZE: OnceLock<u64> = OnceLock::new();
@@ -49,12 +54,19 @@ pub(crate) struct Stat {
     /// clock ticks.
     pub stime: u64,
 
+    /// The nice value, from 19 (lowest priority) to -20 (highest).
+    pub nice: i32,
+
     /// The resident set size, or the number of pages the process has in real
     /// memory.
     pub rss: u64,
 
     /// The start time of the process, represented in clock ticks.
     pub start_time: u64,
+
+    /// How long the process has waited for block I/O, in clock ticks. This is
+    /// 0 if delay accounting is off.
+    pub block_io_ticks: u64,
 }
 
 impl Stat {
@@ -92,23 +104,38 @@ impl Stat {
         let utime: u64 = next_part(&mut rest)?.parse()?;
         let stime: u64 = next_part(&mut rest)?.parse()?;
 
-        // Skip 6 fields until starttime (cutime, cstime, priority, nice, num_threads,
-        // itrealvalue).
-        let mut rest = rest.skip(6);
+        // Skip 3 fields until nice (cutime, cstime, priority).
+        let mut rest = rest.skip(3);
+        let nice: i32 = next_part(&mut rest)?.parse()?;
+
+        // Skip 2 fields until starttime (num_threads, itrealvalue).
+        let mut rest = rest.skip(2);
         let start_time: u64 = next_part(&mut rest)?.parse()?;
 
         // Skip one field until rss (vsize)
         let mut rest = rest.skip(1);
         let rss: u64 = next_part(&mut rest)?.parse()?;
 
+        // Skip 17 fields until delayacct_blkio_ticks (rsslim, startcode, endcode,
+        // startstack, kstkesp, kstkeip, signal, blocked, sigignore, sigcatch, wchan,
+        // nswap, cnswap, exit_signal, processor, rt_priority, policy). Older kernels
+        // may not have it.
+        let mut rest = rest.skip(17);
+        let block_io_ticks: u64 = next_part(&mut rest)
+            .ok()
+            .and_then(|ticks| ticks.parse().ok())
+            .unwrap_or(0);
+
         Ok(Stat {
             comm,
             state,
             ppid,
             utime,
             stime,
+            nice,
             rss,
             start_time,
+            block_io_ticks,
         })
     }
 
@@ -206,6 +233,22 @@ pub(crate) struct Process {
     pub stat: Stat,
     pub io: anyhow::Result<Io>,
     pub cmdline: anyhow::Result<Vec<String>>,
+    pub label: Option<String>,
+    pub namespaces: Namespaces,
+    /// Only read for the process whose details are shown.
+    pub security: Option<ProcessSecurity>,
+}
+
+/// Whether reading something failed because we aren't allowed to, rather than
+/// because, say, the process exited while it was being read.
+pub(crate) fn is_permission_denied(err: &anyhow::Error) -> bool {
+    if let Some(errno) = err.downcast_ref::<rustix::io::Errno>() {
+        matches!(*errno, rustix::io::Errno::ACCESS | rustix::io::Errno::PERM)
+    } else if let Some(err) = err.downcast_ref::<io::Error>() {
+        err.kind() == io::ErrorKind::PermissionDenied
+    } else {
+        false
+    }
 }
 
 #[inline]
@@ -223,8 +266,14 @@ impl Process {
     /// methods. Therefore, this struct is only useful for either fields
     /// that are unlikely to change, or are short-lived and
     /// will be discarded quickly.
-    pub(crate) fn from_path(pid_path: PathBuf) -> anyhow::Result<Process> {
-        // TODO: Pass in a buffer vec/string to share?
+    ///
+    /// The given buffer is only used as scratch space while reading files, and
+    /// can be shared across calls to avoid reallocating it for each process.
+    /// Optional details are only read if `fields` asks for them.
+    pub(crate) fn from_path(
+        pid_path: PathBuf, buffer: &mut String, fields: &ProcessFields,
+    ) -> anyhow::Result<Process> {
+        buffer.clear();
 
         let fd = rustix::fs::openat(
             rustix::fs::CWD,
@@ -236,7 +285,7 @@ impl Process {
         let pid = pid_path
             .as_path()
             .components()
-            .last()
+            .next_back()
             .and_then(|s| s.to_string_lossy().parse::<Pid>().ok())
             .or_else(|| {
                 rustix::fs::readlinkat(rustix::fs::CWD, &pid_path, vec![])
@@ -254,18 +303,44 @@ impl Process {
         };
 
         let mut root = pid_path;
-        let mut buffer = String::new();
 
         // NB: Whenever you add a new stat, make sure to pop the root and clear the
         // buffer!
         let stat =
-            open_at(&mut root, "stat", &fd).and_then(|file| Stat::from_file(file, &mut buffer))?;
-        reset(&mut root, &mut buffer);
+            open_at(&mut root, "stat", &fd).and_then(|file| Stat::from_file(file, buffer))?;
+        reset(&mut root, buffer);
+
+        let cmdline = cmdline(&mut root, &fd, buffer);
+        reset(&mut root, buffer);
+
+        let io = open_at(&mut root, "io", &fd).and_then(|file| Io::from_file(file, buffer));
+        reset(&mut root, buffer);
+
+        let is_details = fields.details_pid == Some(pid);
+
+        let label = if fields.label || is_details {
+            let label = open_at(&mut root, "attr/current", &fd)
+                .and_then(|mut file| Ok(file.read_to_string(buffer)?))
+                .ok()
+                .and_then(|_| parse_label(buffer));
+            reset(&mut root, buffer);
+            label
+        } else {
+            None
+        };
 
-        let cmdline = cmdline(&mut root, &fd, &mut buffer);
-        reset(&mut root, &mut buffer);
+        let security = if is_details {
+            let security = open_at(&mut root, "status", &fd)
+                .and_then(|mut file| Ok(file.read_to_string(buffer)?))
+                .ok()
+                .map(|_| ProcessSecurity::from_status(buffer));
+            reset(&mut root, buffer);
+            security
+        } else {
+            None
+        };
 
-        let io = open_at(&mut root, "io", &fd).and_then(|file| Io::from_file(file, &mut buffer));
+        let namespaces = Namespaces::read(&fd, fields.namespaces);
 
         Ok(Process {
             pid,
@@ -273,6 +348,9 @@ impl Process {
             stat,
             io,
             cmdline,
+            label,
+            namespaces,
+            security,
         })
     }
 }
@@ -293,7 +371,6 @@ fn cmdline(root: &mut PathBuf, fd: &OwnedFd, buffer: &mut String) -> anyhow::Res
                 })
                 .collect::<Vec<_>>()
         })
-        .map_err(Into::into)
 }
 
 /// Opens a path. Note that this function takes in a mutable root - this will
@@ -0,0 +1,199 @@
+//! The security context of Linux processes: their effective capabilities,
+//! seccomp mode, and LSM (SELinux or AppArmor) label. See
+//! [capabilities(7)](https://man7.org/linux/man-pages/man7/capabilities.7.html)
+//! and [proc_pid_status(5)](https://man7.org/linux/man-pages/man5/proc_pid_status.5.html).
+
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+/// The names of the capabilities the kernel knows of, by bit.
+#[cfg(feature = "app")]
+const CAPABILITIES: [&str; 41] = [
+    "cap_chown",
+    "cap_dac_override",
+    "cap_dac_read_search",
+    "cap_fowner",
+    "cap_fsetid",
+    "cap_kill",
+    "cap_setgid",
+    "cap_setuid",
+    "cap_setpcap",
+    "cap_linux_immutable",
+    "cap_net_bind_service",
+    "cap_net_broadcast",
+    "cap_net_admin",
+    "cap_net_raw",
+    "cap_ipc_lock",
+    "cap_ipc_owner",
+    "cap_sys_module",
+    "cap_sys_rawio",
+    "cap_sys_chroot",
+    "cap_sys_ptrace",
+    "cap_sys_pacct",
+    "cap_sys_admin",
+    "cap_sys_boot",
+    "cap_sys_nice",
+    "cap_sys_resource",
+    "cap_sys_time",
+    "cap_sys_tty_config",
+    "cap_mknod",
+    "cap_lease",
+    "cap_audit_write",
+    "cap_audit_control",
+    "cap_setfcap",
+    "cap_mac_override",
+    "cap_mac_admin",
+    "cap_syslog",
+    "cap_wake_alarm",
+    "cap_block_suspend",
+    "cap_audit_read",
+    "cap_perfmon",
+    "cap_bpf",
+    "cap_checkpoint_restore",
+];
+
+/// A mask with every capability in [`CAPABILITIES`] set.
+#[cfg(feature = "app")]
+const ALL_CAPABILITIES: u64 = (1 << CAPABILITIES.len()) - 1;
+
+/// Which system calls a process is limited to with seccomp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Seccomp {
+    /// Not limited.
+    Disabled,
+    /// Only `read`, `write`, `_exit`, and `sigreturn` are allowed.
+    Strict,
+    /// Limited by a BPF filter.
+    Filter,
+}
+
+impl Display for Seccomp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Seccomp::Disabled => "disabled",
+            Seccomp::Strict => "strict",
+            Seccomp::Filter => "filter",
+        })
+    }
+}
+
+/// The capabilities and seccomp mode of a process, from
+/// `/proc/<PID>/status`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessSecurity {
+    /// The mask of effective capabilities.
+    pub capabilities: Option<u64>,
+    pub seccomp: Option<Seccomp>,
+}
+
+impl ProcessSecurity {
+    /// Parses the security context from the contents of `/proc/<PID>/status`.
+    /// Anything that isn't there is left as `None`.
+    pub(crate) fn from_status(status: &str) -> Self {
+        let mut security = Self::default();
+
+        for line in status.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+
+            match key {
+                "CapEff" => security.capabilities = u64::from_str_radix(value.trim(), 16).ok(),
+                "Seccomp" => {
+                    security.seccomp = match value.trim() {
+                        "0" => Some(Seccomp::Disabled),
+                        "1" => Some(Seccomp::Strict),
+                        "2" => Some(Seccomp::Filter),
+                        _ => None,
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        security
+    }
+}
+
+/// Returns the names of the capabilities in `mask`, or `all` or `none` if it
+/// has every or no capability. Capabilities newer than bottom are shown by
+/// number.
+#[cfg(feature = "app")]
+pub fn capability_names(mask: u64) -> String {
+    if mask == 0 {
+        return "none".to_string();
+    } else if mask & ALL_CAPABILITIES == ALL_CAPABILITIES {
+        return "all".to_string();
+    }
+
+    (0..u64::BITS as usize)
+        .filter(|bit| mask & (1 << bit) != 0)
+        .map(|bit| match CAPABILITIES.get(bit) {
+            Some(name) => name.to_string(),
+            None => format!("cap_{bit}"),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parses the contents of `/proc/<PID>/attr/current`, which is the SELinux
+/// context or AppArmor profile of a process. This is `None` if there is no
+/// label, such as if no LSM is in use.
+pub(crate) fn parse_label(contents: &str) -> Option<String> {
+    let label = contents.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+
+    (!label.is_empty()).then(|| label.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn status() {
+        let status = "Name:\tsshd\n\
+                      CapInh:\t0000000000000000\n\
+                      CapPrm:\t000001ffffffffff\n\
+                      CapEff:\t0000000000003000\n\
+                      NoNewPrivs:\t0\n\
+                      Seccomp:\t2\n\
+                      Seccomp_filters:\t1\n";
+
+        assert_eq!(
+            ProcessSecurity::from_status(status),
+            ProcessSecurity {
+                capabilities: Some(0x3000),
+                seccomp: Some(Seccomp::Filter),
+            }
+        );
+        assert_eq!(
+            ProcessSecurity::from_status("Name:\told\n"),
+            ProcessSecurity::default()
+        );
+    }
+
+    #[cfg(feature = "app")]
+    #[test]
+    fn capabilities() {
+        assert_eq!(capability_names(0), "none");
+        assert_eq!(capability_names(0x1ffffffffff), "all");
+        assert_eq!(capability_names(0xffffffffffffffff), "all");
+        assert_eq!(capability_names(0x3000), "cap_net_admin, cap_net_raw");
+        assert_eq!(capability_names((1 << 41) | 1), "cap_chown, cap_41");
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(
+            parse_label("system_u:system_r:sshd_t:s0-s0:c0.c1023\0"),
+            Some("system_u:system_r:sshd_t:s0-s0:c0.c1023".to_string())
+        );
+        assert_eq!(
+            parse_label("/usr/sbin/cupsd (enforce)\n"),
+            Some("/usr/sbin/cupsd (enforce)".to_string())
+        );
+        assert_eq!(parse_label("\0"), None);
+        assert_eq!(parse_label(""), None);
+    }
+}
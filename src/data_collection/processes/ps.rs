@@ -0,0 +1,223 @@
+//! Process data collection for platforms that sysinfo doesn't support (NetBSD
+//! and illumos). This parses the output of `ps`.
+
+use std::time::Duration;
+
+use super::Pid;
+
+// The fields we ask `ps` for are in the same order on every platform; `args`
+// must be last, as it may contain spaces.
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "netbsd")] {
+        const PS_ARGS: [&str; 3] = ["-axww", "-o", "pid=,ppid=,uid=,pcpu=,pmem=,rss=,state=,etime=,comm=,args="];
+
+        /// Whether `pcpu` is a percentage of all CPUs, rather than of one CPU.
+        const IS_PCPU_NORMALIZED: bool = false;
+    } else if #[cfg(target_os = "illumos")] {
+        const PS_ARGS: [&str; 3] = ["-e", "-o", "pid=,ppid=,uid=,pcpu=,pmem=,rss=,s=,etime=,comm=,args="];
+
+        /// Whether `pcpu` is a percentage of all CPUs, rather than of one CPU.
+        const IS_PCPU_NORMALIZED: bool = true;
+    }
+}
+
+/// A single process from the output of `ps`.
+#[derive(Debug, PartialEq)]
+struct PsRow<'a> {
+    pid: Pid,
+    parent_pid: Pid,
+    uid: u32,
+    cpu_percent: f64,
+    mem_percent: f64,
+    rss_kib: u64,
+    state: char,
+    elapsed: Duration,
+    name: &'a str,
+    command: &'a str,
+}
+
+/// Returns the next whitespace-separated field in `rest`, advancing it past
+/// the field.
+fn next_field<'a>(rest: &mut &'a str) -> Option<&'a str> {
+    let trimmed = rest.trim_start();
+    let (field, remaining) = trimmed
+        .split_once(char::is_whitespace)
+        .unwrap_or((trimmed, ""));
+    *rest = remaining;
+
+    (!field.is_empty()).then_some(field)
+}
+
+/// Parses a line of `ps` output with the fields in `PS_ARGS`.
+fn parse_ps_line(line: &str) -> Option<PsRow<'_>> {
+    let mut rest = line;
+
+    let pid = next_field(&mut rest)?.parse().ok()?;
+    let parent_pid = next_field(&mut rest)?.parse().ok()?;
+    let uid = next_field(&mut rest)?.parse().ok()?;
+    let cpu_percent = next_field(&mut rest)?.parse().ok()?;
+    let mem_percent = next_field(&mut rest)?.parse().ok()?;
+    let rss_kib = next_field(&mut rest)?.parse().ok()?;
+    let state = next_field(&mut rest)?.chars().next()?;
+    let elapsed = parse_elapsed(next_field(&mut rest)?)?;
+    // Some platforms give the path to the executable rather than just its name.
+    let name = next_field(&mut rest)?.rsplit('/').next()?;
+    let command = match rest.trim() {
+        "" => name,
+        command => command,
+    };
+
+    Some(PsRow {
+        pid,
+        parent_pid,
+        uid,
+        cpu_percent,
+        mem_percent,
+        rss_kib,
+        state,
+        elapsed,
+        name,
+        command,
+    })
+}
+
+/// Parses an elapsed time from `ps`, which is in the form `[[dd-]hh:]mm:ss`.
+fn parse_elapsed(elapsed: &str) -> Option<Duration> {
+    let (days, time) = match elapsed.split_once('-') {
+        Some((days, time)) => (days.parse::<u64>().ok()?, time),
+        None => (0, elapsed),
+    };
+
+    let secs = time.split(':').try_fold(0, |secs, part| {
+        part.parse::<u64>().ok().map(|part| secs * 60 + part)
+    })?;
+
+    Some(Duration::from_secs(days * 24 * 60 * 60 + secs))
+}
+
+/// Returns the name of a process state, given its first character in `ps`.
+fn process_state_name(state: char) -> &'static str {
+    match state {
+        'D' => "Disk Sleep",
+        'I' => "Idle",
+        'O' => "Running",
+        'R' => "Runnable",
+        'S' => "Sleeping",
+        'T' => "Stopped",
+        'W' => "Waiting",
+        'Z' => "Zombie",
+        _ => "Unknown",
+    }
+}
+
+#[cfg(any(target_os = "netbsd", target_os = "illumos"))]
+pub(crate) fn ps_process_data(
+    collector: &mut crate::data_collection::DataCollector,
+) -> crate::data_collection::error::CollectionResult<Vec<super::ProcessHarvest>> {
+    use std::process::Command;
+
+    let output = Command::new("ps").args(PS_ARGS).output()?;
+    let output = String::from_utf8_lossy(&output.stdout);
+
+    let num_cpus = crate::data_collection::cpu::num_cpus()? as f64;
+    let unnormalized_cpu = collector.unnormalized_cpu;
+    let user_table = &mut collector.user_table;
+    let interner = &mut collector.interner;
+
+    let processes = output
+        .lines()
+        .filter_map(parse_ps_line)
+        .map(|row| {
+            let name = interner.intern(row.name);
+
+            super::ProcessHarvest {
+                pid: row.pid,
+                parent_pid: Some(row.parent_pid),
+                cpu_usage_percent: match (unnormalized_cpu, IS_PCPU_NORMALIZED) {
+                    (true, true) => row.cpu_percent * num_cpus,
+                    (false, false) => row.cpu_percent / num_cpus,
+                    _ => row.cpu_percent,
+                } as f32,
+                mem_usage_percent: row.mem_percent as f32,
+                mem_usage_bytes: row.rss_kib * 1024,
+                command: if row.command == row.name {
+                    name.clone()
+                } else {
+                    interner.intern(row.command)
+                },
+                name,
+                process_state: (interner.intern(process_state_name(row.state)), row.state),
+                time: row.elapsed,
+                uid: Some(row.uid),
+                user: user_table
+                    .get_uid_to_username_mapping(row.uid)
+                    .unwrap_or_else(|_| interner.intern("N/A")),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    Ok(processes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_ps_lines() {
+        assert_eq!(
+            parse_ps_line(
+                "  412     1     0  1.5  0.3  2048 Ss  1-02:03:04 sshd sshd: /usr/sbin/sshd -D"
+            ),
+            Some(PsRow {
+                pid: 412,
+                parent_pid: 1,
+                uid: 0,
+                cpu_percent: 1.5,
+                mem_percent: 0.3,
+                rss_kib: 2048,
+                state: 'S',
+                elapsed: Duration::from_secs(24 * 60 * 60 + 2 * 60 * 60 + 3 * 60 + 4),
+                name: "sshd",
+                command: "sshd: /usr/sbin/sshd -D",
+            })
+        );
+
+        let row = parse_ps_line("1000 412 1000 0.0 0.1 512 R+ 05:06 top").unwrap();
+        assert_eq!(row.elapsed, Duration::from_secs(5 * 60 + 6));
+        assert_eq!(row.command, "top");
+
+        let row = parse_ps_line("7 1 0 0.0 0.1 512 S 05:06 /usr/sbin/nscd /usr/sbin/nscd").unwrap();
+        assert_eq!(row.name, "nscd");
+        assert_eq!(row.command, "/usr/sbin/nscd");
+
+        assert_eq!(parse_ps_line(""), None);
+        assert_eq!(parse_ps_line("1000 412 1000 0.0"), None);
+        assert_eq!(
+            parse_ps_line("pid ppid uid pcpu pmem rss state etime comm args"),
+            None
+        );
+    }
+
+    #[test]
+    fn process_state_names() {
+        assert_eq!(process_state_name('S'), "Sleeping");
+        assert_eq!(process_state_name('Z'), "Zombie");
+        assert_eq!(process_state_name('?'), "Unknown");
+    }
+
+    #[test]
+    fn parse_elapsed_times() {
+        assert_eq!(parse_elapsed("00:05"), Some(Duration::from_secs(5)));
+        assert_eq!(
+            parse_elapsed("01:00:00"),
+            Some(Duration::from_secs(60 * 60))
+        );
+        assert_eq!(
+            parse_elapsed("2-00:00:01"),
+            Some(Duration::from_secs(2 * 24 * 60 * 60 + 1))
+        );
+        assert_eq!(parse_elapsed("soon"), None);
+    }
+}
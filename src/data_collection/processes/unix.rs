@@ -6,7 +6,7 @@ use cfg_if::cfg_if;
 pub use user_table::*;
 
 cfg_if! {
-    if #[cfg(all(target_family = "unix", not(target_os = "linux")))] {
+    if #[cfg(all(target_family = "unix", not(any(target_os = "linux", target_os = "netbsd", target_os = "illumos"))))] {
         mod process_ext;
         pub(crate) use process_ext::*;
 
@@ -21,14 +21,15 @@ cfg_if! {
             let unnormalized_cpu = collector.unnormalized_cpu;
             let total_memory = collector.total_memory();
             let user_table = &mut collector.user_table;
+            let interner = &mut collector.interner;
 
             cfg_if! {
                 if #[cfg(target_os = "macos")] {
-                    MacOSProcessExt::sysinfo_process_data(sys, use_current_cpu_total, unnormalized_cpu, total_memory, user_table)
+                    MacOSProcessExt::sysinfo_process_data(sys, use_current_cpu_total, unnormalized_cpu, total_memory, user_table, interner)
                 } else if #[cfg(target_os = "freebsd")] {
-                    FreeBSDProcessExt::sysinfo_process_data(sys, use_current_cpu_total, unnormalized_cpu, total_memory, user_table)
+                    FreeBSDProcessExt::sysinfo_process_data(sys, use_current_cpu_total, unnormalized_cpu, total_memory, user_table, interner)
                 } else {
-                    GenericProcessExt::sysinfo_process_data(sys, use_current_cpu_total, unnormalized_cpu, total_memory, user_table)
+                    GenericProcessExt::sysinfo_process_data(sys, use_current_cpu_total, unnormalized_cpu, total_memory, user_table, interner)
                 }
             }
         }
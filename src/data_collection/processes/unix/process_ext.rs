@@ -6,12 +6,15 @@ use hashbrown::HashMap;
 use sysinfo::{ProcessStatus, System};
 
 use super::ProcessHarvest;
-use crate::data_collection::{error::CollectionResult, processes::UserTable, Pid};
+use crate::{
+    data_collection::{error::CollectionResult, processes::UserTable, Pid},
+    utils::interner::StringInterner,
+};
 
 pub(crate) trait UnixProcessExt {
     fn sysinfo_process_data(
         sys: &System, use_current_cpu_total: bool, unnormalized_cpu: bool, total_memory: u64,
-        user_table: &mut UserTable,
+        user_table: &mut UserTable, interner: &mut StringInterner,
     ) -> CollectionResult<Vec<ProcessHarvest>> {
         let mut process_vector: Vec<ProcessHarvest> = Vec::new();
         let process_hashmap = sys.processes();
@@ -22,24 +25,25 @@ pub(crate) trait UnixProcessExt {
             let name = if process_val.name().is_empty() {
                 let process_cmd = process_val.cmd();
                 if process_cmd.len() > 1 {
-                    process_cmd[0].clone()
+                    interner.intern(&process_cmd[0])
                 } else {
-                    process_val
-                        .exe()
-                        .and_then(|exe| exe.file_stem())
-                        .and_then(|stem| stem.to_str())
-                        .map(|s| s.to_string())
-                        .unwrap_or(String::new())
+                    interner.intern(
+                        process_val
+                            .exe()
+                            .and_then(|exe| exe.file_stem())
+                            .and_then(|stem| stem.to_str())
+                            .unwrap_or_default(),
+                    )
                 }
             } else {
-                process_val.name().to_string()
+                interner.intern(process_val.name())
             };
             let command = {
                 let command = process_val.cmd().join(" ");
                 if command.is_empty() {
-                    name.to_string()
+                    name.clone()
                 } else {
-                    command
+                    interner.intern(&command)
                 }
             };
 
@@ -60,13 +64,17 @@ pub(crate) trait UnixProcessExt {
             let disk_usage = process_val.disk_usage();
             let process_state = {
                 let ps = process_val.status();
-                (ps.to_string(), convert_process_status_to_char(ps))
+                (
+                    interner.intern(&ps.to_string()),
+                    convert_process_status_to_char(ps),
+                )
             };
             let uid = process_val.user_id().map(|u| **u);
             let pid = process_val.pid().as_u32() as Pid;
             process_vector.push(ProcessHarvest {
                 pid,
                 parent_pid: Self::parent_pid(process_val),
+                start_time: process_val.start_time(),
                 name,
                 command,
                 mem_usage_percent: if total_memory > 0 {
@@ -83,13 +91,8 @@ pub(crate) trait UnixProcessExt {
                 process_state,
                 uid,
                 user: uid
-                    .and_then(|uid| {
-                        user_table
-                            .get_uid_to_username_mapping(uid)
-                            .map(Into::into)
-                            .ok()
-                    })
-                    .unwrap_or_else(|| "N/A".into()),
+                    .and_then(|uid| user_table.get_uid_to_username_mapping(uid).ok())
+                    .unwrap_or_else(|| interner.intern("N/A")),
                 time: if process_val.start_time() == 0 {
                     // Workaround for sysinfo occasionally returning a start time equal to UNIX
                     // epoch, giving a run time in the range of 50+ years. We just
@@ -106,6 +109,8 @@ pub(crate) trait UnixProcessExt {
                 gpu_mem_percent: 0.0,
                 #[cfg(feature = "gpu")]
                 gpu_util: 0,
+                #[cfg(feature = "plugins")]
+                plugin_values: Vec::new(),
             });
         }
 
@@ -113,7 +118,7 @@ pub(crate) trait UnixProcessExt {
             let unknown_state = ProcessStatus::Unknown(0).to_string();
             let cpu_usage_unknown_pids: Vec<Pid> = process_vector
                 .iter()
-                .filter(|process| process.process_state.0 == unknown_state)
+                .filter(|process| *process.process_state.0 == unknown_state)
                 .map(|process| process.pid)
                 .collect();
             let cpu_usages = Self::backup_proc_cpu(&cpu_usage_unknown_pids)?;
@@ -1,14 +1,16 @@
+use std::sync::Arc;
+
 use hashbrown::HashMap;
 
 use crate::data_collection::error::{CollectionError, CollectionResult};
 
 #[derive(Debug, Default)]
 pub struct UserTable {
-    pub uid_user_mapping: HashMap<libc::uid_t, String>,
+    pub uid_user_mapping: HashMap<libc::uid_t, Arc<str>>,
 }
 
 impl UserTable {
-    pub fn get_uid_to_username_mapping(&mut self, uid: libc::uid_t) -> CollectionResult<String> {
+    pub fn get_uid_to_username_mapping(&mut self, uid: libc::uid_t) -> CollectionResult<Arc<str>> {
         if let Some(user) = self.uid_user_mapping.get(&uid) {
             Ok(user.clone())
         } else {
@@ -20,10 +22,10 @@ impl UserTable {
                 Err("passwd is inaccessible".into())
             } else {
                 // SAFETY: We return early if passwd is null.
-                let username = unsafe { std::ffi::CStr::from_ptr((*passwd).pw_name) }
+                let username: Arc<str> = unsafe { std::ffi::CStr::from_ptr((*passwd).pw_name) }
                     .to_str()
                     .map_err(|err| CollectionError::General(err.into()))?
-                    .to_string();
+                    .into();
                 self.uid_user_mapping.insert(uid, username.clone());
 
                 Ok(username)
@@ -14,6 +14,7 @@ pub fn sysinfo_process_data(
     let use_current_cpu_total = collector.use_current_cpu_total;
     let unnormalized_cpu = collector.unnormalized_cpu;
     let total_memory = collector.total_memory();
+    let interner = &mut collector.interner;
 
     let mut process_vector: Vec<ProcessHarvest> = Vec::new();
     let process_hashmap = sys.processes();
@@ -24,24 +25,25 @@ pub fn sysinfo_process_data(
         let name = if process_val.name().is_empty() {
             let process_cmd = process_val.cmd();
             if process_cmd.len() > 1 {
-                process_cmd[0].clone()
+                interner.intern(&process_cmd[0])
             } else {
-                process_val
-                    .exe()
-                    .and_then(|exe| exe.file_stem())
-                    .and_then(|stem| stem.to_str())
-                    .map(|s| s.to_string())
-                    .unwrap_or(String::new())
+                interner.intern(
+                    process_val
+                        .exe()
+                        .and_then(|exe| exe.file_stem())
+                        .and_then(|stem| stem.to_str())
+                        .unwrap_or_default(),
+                )
             }
         } else {
-            process_val.name().to_string()
+            interner.intern(process_val.name())
         };
         let command = {
             let command = process_val.cmd().join(" ");
             if command.is_empty() {
-                name.to_string()
+                name.clone()
             } else {
-                command
+                interner.intern(&command)
             }
         };
 
@@ -60,7 +62,7 @@ pub fn sysinfo_process_data(
         } as f32;
 
         let disk_usage = process_val.disk_usage();
-        let process_state = (process_val.status().to_string(), 'R');
+        let process_state = (interner.intern(&process_val.status().to_string()), 'R');
 
         #[cfg(feature = "gpu")]
         let (gpu_mem, gpu_util, gpu_mem_percent) = {
@@ -84,6 +86,7 @@ pub fn sysinfo_process_data(
         process_vector.push(ProcessHarvest {
             pid: process_val.pid().as_u32() as _,
             parent_pid: process_val.parent().map(|p| p.as_u32() as _),
+            start_time: process_val.start_time(),
             name,
             command,
             mem_usage_percent: if total_memory > 0 {
@@ -98,10 +101,12 @@ pub fn sysinfo_process_data(
             total_read_bytes: disk_usage.total_read_bytes,
             total_write_bytes: disk_usage.total_written_bytes,
             process_state,
-            user: process_val
-                .user_id()
-                .and_then(|uid| users.get_user_by_id(uid))
-                .map_or_else(|| "N/A".into(), |user| user.name().to_owned().into()),
+            user: interner.intern(
+                process_val
+                    .user_id()
+                    .and_then(|uid| users.get_user_by_id(uid))
+                    .map_or("N/A", |user| user.name()),
+            ),
             time: if process_val.start_time() == 0 {
                 // Workaround for sysinfo occasionally returning a start time equal to UNIX
                 // epoch, giving a run time in the range of 50+ years. We just
@@ -118,6 +123,8 @@ pub fn sysinfo_process_data(
             gpu_util,
             #[cfg(feature = "gpu")]
             gpu_mem_percent,
+            #[cfg(feature = "plugins")]
+            plugin_values: Vec::new(),
         });
     }
 
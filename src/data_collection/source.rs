@@ -0,0 +1,240 @@
+//! Data sources, which [`DataCollector`] collects from each update.
+//!
+//! Each kind of data is collected by its own [`DataSource`], which are
+//! registered when the collector is created. New sources can be added with
+//! [`DataCollector::add_source`] without touching the rest of the collector.
+
+use std::fmt::Debug;
+
+use super::DataCollector;
+use crate::data_collection::{Data, UsedWidgets};
+
+/// A source of data, such as CPU usage or temperatures.
+pub trait DataSource: Debug {
+    /// The name of this source, used for debugging (e.g. collection timings).
+    fn name(&self) -> &'static str;
+
+    /// Whether this source should be collected from, given the widgets in use.
+    /// By default, sources are always collected from.
+    fn is_enabled(&self, _used_widgets: &UsedWidgets) -> bool {
+        true
+    }
+
+    /// Collects data, storing it in [`DataCollector::data`]. This is called
+    /// once per update, in the order sources were registered.
+    fn collect(&mut self, collector: &mut DataCollector);
+
+    /// Whether the last collection from this source got any data, used to show
+    /// which sources aren't working. By default, sources are assumed to work.
+    fn has_data(&self, _data: &Data) -> bool {
+        true
+    }
+}
+
+/// Returns the sources used by default, in the order they should be collected
+/// from.
+///
+/// Order matters for some of these; memory is collected before processes as
+/// the total memory is used for process memory usage, and GPUs are collected
+/// after temperatures (as they append to them) but before processes (as
+/// processes use GPU usage).
+pub(crate) fn default_sources() -> Vec<Box<dyn DataSource>> {
+    vec![
+        Box::new(super::cpu::CpuSource::default()),
+        Box::new(super::memory::MemorySource),
+        Box::new(super::temperature::TempSource::default()),
+        #[cfg(feature = "battery")]
+        Box::new(super::batteries::BatterySource::default()),
+        #[cfg(feature = "gpu")]
+        Box::new(GpuSource),
+        Box::new(super::processes::ProcessSource),
+        Box::new(super::network::NetworkSource::default()),
+        Box::new(super::disks::DiskSource),
+    ]
+}
+
+/// Collects GPU memory, temperatures, and per-process usage from all supported
+/// GPU vendors.
+#[cfg(feature = "gpu")]
+#[derive(Debug)]
+pub(crate) struct GpuSource;
+
+#[cfg(feature = "gpu")]
+impl DataSource for GpuSource {
+    fn name(&self) -> &'static str {
+        "gpu"
+    }
+
+    fn is_enabled(&self, used_widgets: &UsedWidgets) -> bool {
+        used_widgets.use_gpu
+    }
+
+    fn collect(&mut self, collector: &mut DataCollector) {
+        use hashbrown::HashMap;
+
+        use super::memory::MemHarvest;
+
+        let mut local_gpu: Vec<(String, MemHarvest)> = Vec::new();
+        let mut local_gpu_pids: Vec<HashMap<u32, (u64, u32)>> = Vec::new();
+        let mut local_gpu_total_mem: u64 = 0;
+
+        #[cfg(feature = "nvidia")]
+        if let Some(data) = super::nvidia::get_nvidia_vecs(
+            &collector.temperature_type,
+            &collector.filters.temp_filter,
+            &collector.widgets_to_harvest,
+        ) {
+            if let Some(mut temp) = data.temperature {
+                if let Some(sensors) = &mut collector.data.temperature_sensors {
+                    sensors.append(&mut temp);
+                } else {
+                    collector.data.temperature_sensors = Some(temp);
+                }
+            }
+            if let Some(mut mem) = data.memory {
+                local_gpu.append(&mut mem);
+            }
+            if let Some(mut proc) = data.procs {
+                local_gpu_pids.append(&mut proc.1);
+                local_gpu_total_mem += proc.0;
+            }
+        }
+
+        #[cfg(feature = "nvidia")]
+        if let Some(err) = super::nvidia::nvml_init_error() {
+            collector.report_issue(format!("Couldn't load NVML for NVIDIA GPUs: {err}"));
+        }
+
+        #[cfg(target_os = "linux")]
+        if let Some(data) = super::amd::get_amd_vecs(
+            &collector.temperature_type,
+            &collector.filters.temp_filter,
+            &collector.widgets_to_harvest,
+            collector.last_collection_time,
+        ) {
+            if let Some(mut temp) = data.temperature {
+                if let Some(sensors) = &mut collector.data.temperature_sensors {
+                    sensors.append(&mut temp);
+                } else {
+                    collector.data.temperature_sensors = Some(temp);
+                }
+            }
+            if let Some(mut mem) = data.memory {
+                local_gpu.append(&mut mem);
+            }
+            if let Some(mut proc) = data.procs {
+                local_gpu_pids.append(&mut proc.1);
+                local_gpu_total_mem += proc.0;
+            }
+        }
+
+        collector.data.gpu = (!local_gpu.is_empty()).then_some(local_gpu);
+        collector.gpu_pids = (!local_gpu_pids.is_empty()).then_some(local_gpu_pids);
+        collector.gpus_total_mem = (local_gpu_total_mem > 0).then_some(local_gpu_total_mem);
+    }
+
+    fn has_data(&self, data: &Data) -> bool {
+        data.gpu.is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::data_collection::{CollectionIssue, DataFilters, SourceStatus};
+
+    #[derive(Debug, Default)]
+    struct CountingSource {
+        count: usize,
+    }
+
+    impl DataSource for CountingSource {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        fn collect(&mut self, collector: &mut DataCollector) {
+            self.count += 1;
+            collector.data.load_avg = Some([self.count as f32; 3]);
+        }
+    }
+
+    #[derive(Debug)]
+    struct FailingSource;
+
+    impl DataSource for FailingSource {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        fn collect(&mut self, collector: &mut DataCollector) {
+            collector.report_issue("permission denied".to_string());
+        }
+
+        fn has_data(&self, _data: &Data) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn added_sources_are_collected() {
+        let mut collector = DataCollector::new(DataFilters {
+            disk_filter: None,
+            mount_filter: None,
+            temp_filter: None,
+            net_filter: None,
+        });
+        collector.set_data_collection(UsedWidgets::default());
+        collector.add_source(Box::new(CountingSource::default()));
+
+        collector.update_data();
+
+        // Sources for unused widgets shouldn't be collected from, so only the
+        // added source should be timed.
+        let sources: Vec<_> = collector
+            .data
+            .timings
+            .sources
+            .iter()
+            .map(|(name, _)| *name)
+            .collect();
+        assert_eq!(sources, vec!["sysinfo", "counting"]);
+
+        // Skipped sources are still listed as disabled.
+        let (counting, skipped) = collector.data.timings.statuses.split_last().unwrap();
+        assert_eq!(*counting, ("counting", SourceStatus::Active));
+        assert!(!skipped.is_empty());
+        assert!(skipped
+            .iter()
+            .all(|(_, status)| *status == SourceStatus::Disabled));
+
+        collector.update_data();
+        assert_eq!(collector.data.load_avg, Some([2.0; 3]));
+    }
+
+    #[test]
+    fn issues_are_reported_by_source() {
+        let mut collector = DataCollector::new(DataFilters {
+            disk_filter: None,
+            mount_filter: None,
+            temp_filter: None,
+            net_filter: None,
+        });
+        collector.set_data_collection(UsedWidgets::default());
+        collector.add_source(Box::new(FailingSource));
+
+        collector.update_data();
+
+        assert_eq!(
+            collector.data.timings.issues,
+            vec![CollectionIssue {
+                source: "failing",
+                message: "permission denied".to_string(),
+            }]
+        );
+        assert_eq!(
+            collector.data.timings.statuses.last(),
+            Some(&("failing", SourceStatus::NoData))
+        );
+    }
+}
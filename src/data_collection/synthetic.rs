@@ -0,0 +1,238 @@
+//! Synthetic data for stress testing. This is used in place of actually
+//! collecting data, so that the performance of the rest of the pipeline can be
+//! measured reproducibly, regardless of what the machine is doing.
+
+use std::time::{Duration, Instant};
+
+use hashbrown::HashMap;
+
+use super::{
+    cpu::{CpuData, CpuDataType},
+    disks::{DiskHarvest, IoData},
+    memory::MemHarvest,
+    network::{Duplex, LinkInfo, NetworkHarvest},
+    processes::{Pid, ProcessHarvest},
+    temperature::{SensorKind, TempHarvest},
+    CollectionTimings, Data, SourceStatus,
+};
+use crate::utils::interner::StringInterner;
+
+/// Settings for stress testing with synthetic data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StressConfig {
+    /// How many processes to generate each tick.
+    pub num_processes: usize,
+    /// How many ticks to run for before exiting, if set.
+    pub num_ticks: Option<u64>,
+}
+
+const NUM_CPUS: usize = 16;
+const NUM_DISKS: usize = 4;
+const NUM_SENSORS: usize = 8;
+const NUM_NAMES: usize = 64;
+const TOTAL_MEMORY: u64 = 32 * 1024 * 1024 * 1024;
+const TOTAL_DISK: u64 = 512 * 1024 * 1024 * 1024;
+
+/// Generates synthetic data. The generated data only depends on the number of
+/// ticks so far, so each run generates the same data.
+pub struct SyntheticCollector {
+    num_processes: usize,
+    show_average_cpu: bool,
+    rng_state: u64,
+    total_rx: u64,
+    total_tx: u64,
+    interner: StringInterner,
+}
+
+impl SyntheticCollector {
+    pub fn new(num_processes: usize, show_average_cpu: bool) -> Self {
+        Self {
+            num_processes,
+            show_average_cpu,
+            rng_state: 0x2545_f491_4f6c_dd1d,
+            total_rx: 0,
+            total_tx: 0,
+            interner: StringInterner::default(),
+        }
+    }
+
+    /// Returns a pseudo-random number in `[0, 1)`, using xorshift.
+    fn next_f64(&mut self) -> f64 {
+        self.rng_state ^= self.rng_state << 13;
+        self.rng_state ^= self.rng_state >> 7;
+        self.rng_state ^= self.rng_state << 17;
+
+        (self.rng_state >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn next_mem(&mut self, total_bytes: u64) -> MemHarvest {
+        MemHarvest {
+            used_bytes: (total_bytes as f64 * self.next_f64()) as u64,
+            total_bytes,
+        }
+    }
+
+    /// Generates the next tick's worth of data.
+    pub fn next_data(&mut self) -> Data {
+        let start = Instant::now();
+
+        let cpu = (0..NUM_CPUS)
+            .map(|itx| CpuData {
+                data_type: CpuDataType::Cpu(itx),
+                cpu_usage: self.next_f64() * 100.0,
+            })
+            .collect::<Vec<_>>();
+        let cpu = if self.show_average_cpu {
+            let avg = cpu.iter().map(|cpu| cpu.cpu_usage).sum::<f64>() / NUM_CPUS as f64;
+            std::iter::once(CpuData {
+                data_type: CpuDataType::Avg,
+                cpu_usage: avg,
+            })
+            .chain(cpu)
+            .collect()
+        } else {
+            cpu
+        };
+
+        let rx = (self.next_f64() * 1e9) as u64;
+        let tx = (self.next_f64() * 1e9) as u64;
+        self.total_rx += rx;
+        self.total_tx += tx;
+
+        let mut disks = Vec::with_capacity(NUM_DISKS);
+        let mut io = HashMap::with_capacity(NUM_DISKS);
+        for itx in 0..NUM_DISKS {
+            let name = format!("sd{}", (b'a' + itx as u8) as char);
+            let used_space = (TOTAL_DISK as f64 * self.next_f64()) as u64;
+
+            io.insert(
+                name.clone(),
+                Some(IoData {
+                    read_bytes: (self.next_f64() * 1e12) as u64,
+                    write_bytes: (self.next_f64() * 1e12) as u64,
+                }),
+            );
+            disks.push(DiskHarvest {
+                name: format!("/dev/{name}"),
+                mount_point: format!("/mnt/{name}"),
+                free_space: Some(TOTAL_DISK - used_space),
+                used_space: Some(used_space),
+                total_space: Some(TOTAL_DISK),
+                #[cfg(target_os = "windows")]
+                volume_name: None,
+            });
+        }
+
+        let temperature_sensors = (0..NUM_SENSORS)
+            .map(|itx| TempHarvest {
+                name: format!("sensor {itx}"),
+                temperature: Some(30.0 + self.next_f64() as f32 * 60.0),
+                kind: SensorKind::Temperature,
+                is_drive: itx + 1 == NUM_SENSORS,
+            })
+            .collect();
+
+        let list_of_processes = (0..self.num_processes)
+            .map(|itx| self.next_process(itx))
+            .collect();
+        self.interner.prune();
+
+        Data {
+            collection_time: start,
+            timings: CollectionTimings {
+                sources: vec![("synthetic", start.elapsed())],
+                statuses: vec![("synthetic", SourceStatus::Active)],
+                issues: Vec::new(),
+                finished: Some(Instant::now()),
+            },
+            cpu: Some(cpu),
+            load_avg: Some([
+                self.next_f64() as f32 * NUM_CPUS as f32,
+                self.next_f64() as f32 * NUM_CPUS as f32,
+                self.next_f64() as f32 * NUM_CPUS as f32,
+            ]),
+            memory: Some(self.next_mem(TOTAL_MEMORY)),
+            #[cfg(not(target_os = "windows"))]
+            cache: Some(self.next_mem(TOTAL_MEMORY)),
+            swap: Some(self.next_mem(TOTAL_MEMORY / 4)),
+            temperature_sensors: Some(temperature_sensors),
+            network: Some(NetworkHarvest {
+                rx,
+                tx,
+                total_rx: self.total_rx,
+                total_tx: self.total_tx,
+                interfaces: vec!["eth0".to_string()],
+                links: vec![LinkInfo {
+                    speed: 1_000_000_000,
+                    duplex: Duplex::Full,
+                }],
+            }),
+            list_of_processes: Some(list_of_processes),
+            disks: Some(disks),
+            io: Some(io),
+            #[cfg(feature = "battery")]
+            list_of_batteries: None,
+            #[cfg(feature = "zfs")]
+            arc: None,
+            #[cfg(feature = "gpu")]
+            gpu: None,
+            #[cfg(feature = "plugins")]
+            plugins: None,
+        }
+    }
+
+    fn next_process(&mut self, itx: usize) -> ProcessHarvest {
+        // Processes form a tree, where each process has up to 4 children.
+        let pid = itx + 1;
+        let parent_pid = (itx > 0).then(|| ((itx - 1) / 4 + 1) as Pid);
+        let name = format!("process_{}", itx % NUM_NAMES);
+        let is_running = self.next_f64() < 0.1;
+
+        ProcessHarvest {
+            pid: pid as Pid,
+            parent_pid,
+            cpu_usage_percent: self.next_f64() as f32 * 100.0,
+            mem_usage_percent: self.next_f64() as f32,
+            mem_usage_bytes: (self.next_f64() * 1e9) as u64,
+            command: self.interner.intern(&format!("/usr/bin/{name} --id {itx}")),
+            name: self.interner.intern(&name),
+            read_bytes_per_sec: (self.next_f64() * 1e6) as u64,
+            write_bytes_per_sec: (self.next_f64() * 1e6) as u64,
+            total_read_bytes: pid as u64 * 1024,
+            total_write_bytes: pid as u64 * 512,
+            process_state: if is_running {
+                (self.interner.intern("Running"), 'R')
+            } else {
+                (self.interner.intern("Sleeping"), 'S')
+            },
+            time: Duration::from_secs(pid as u64),
+            user: self
+                .interner
+                .intern(if itx % 2 == 0 { "root" } else { "user" }),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn synthetic_data_is_reproducible() {
+        let mut a = SyntheticCollector::new(100, true);
+        let mut b = SyntheticCollector::new(100, true);
+
+        for _ in 0..3 {
+            let (a, b) = (a.next_data(), b.next_data());
+            let (a_procs, b_procs) = (a.list_of_processes.unwrap(), b.list_of_processes.unwrap());
+
+            assert_eq!(a_procs.len(), 100);
+            assert!(a_procs
+                .iter()
+                .zip(&b_procs)
+                .all(|(a, b)| a.pid == b.pid && a.cpu_usage_percent == b.cpu_usage_percent));
+            assert_eq!(a.cpu.unwrap().len(), NUM_CPUS + 1);
+        }
+    }
+}
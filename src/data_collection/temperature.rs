@@ -1,13 +1,19 @@
 //! Data collection for temperature metrics.
 //!
-//! For Linux and macOS, this is handled by Heim.
-//! For Windows, this is handled by sysinfo.
+//! For Linux, this is handled by reading hwmon and thermal zones.
+//! For macOS, this is handled by reading the SMC and IOHID sensors.
+//! For Windows and FreeBSD, this is handled by sysinfo.
+
+#[cfg(any(target_os = "macos", test))]
+pub mod macos;
 
 cfg_if::cfg_if! {
     if #[cfg(target_os = "linux")] {
         pub mod linux;
         pub use self::linux::*;
-    } else if #[cfg(any(target_os = "freebsd", target_os = "macos", target_os = "windows", target_os = "android", target_os = "ios"))] {
+    } else if #[cfg(target_os = "macos")] {
+        pub use self::macos::*;
+    } else if #[cfg(any(target_os = "freebsd", target_os = "windows", target_os = "android", target_os = "ios"))] {
         pub mod sysinfo;
         pub use self::sysinfo::*;
     }
@@ -15,10 +21,43 @@ cfg_if::cfg_if! {
 
 use std::str::FromStr;
 
-#[derive(Default, Debug, Clone)]
+use serde::{Deserialize, Serialize};
+
+use super::{source::DataSource, DataCollector};
+use crate::data_collection::{Data, UsedWidgets};
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct TempHarvest {
     pub name: String,
+    /// The reading. Temperatures are in the unit the collector was set to use,
+    /// voltages are in volts, and power is in watts.
     pub temperature: Option<f32>,
+    /// What the sensor measures.
+    #[serde(default)]
+    pub kind: SensorKind,
+    /// Whether the sensor is of a drive, such as an NVMe or SATA drive.
+    #[serde(default)]
+    pub is_drive: bool,
+}
+
+/// What a sensor measures.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SensorKind {
+    #[default]
+    Temperature,
+    Voltage,
+    Power,
+}
+
+/// Which readings to collect alongside temperatures. These are only supported
+/// on Linux, from hwmon.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExtraSensors {
+    /// Whether to collect voltages, from `in*_input`.
+    pub voltages: bool,
+    /// Whether to collect power readings, from `power*_input`.
+    pub power: bool,
 }
 
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
@@ -64,6 +103,55 @@ impl TemperatureType {
     }
 }
 
+/// Collects temperature sensor readings.
+#[derive(Debug, Default)]
+pub(crate) struct TempSource {
+    #[cfg(target_os = "macos")]
+    sensors: TempSensors,
+}
+
+impl DataSource for TempSource {
+    fn name(&self) -> &'static str {
+        "temperature"
+    }
+
+    fn is_enabled(&self, used_widgets: &UsedWidgets) -> bool {
+        used_widgets.use_temp
+    }
+
+    fn collect(&mut self, collector: &mut DataCollector) {
+        #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+        let result = get_temperature_data(
+            &collector.sys.temps,
+            &collector.temperature_type,
+            &collector.filters.temp_filter,
+        );
+
+        #[cfg(target_os = "linux")]
+        let result = get_temperature_data(
+            &collector.temperature_type,
+            &collector.filters.temp_filter,
+            collector.extra_sensors,
+        );
+
+        #[cfg(target_os = "macos")]
+        let result = get_temperature_data(
+            &mut self.sensors,
+            &collector.temperature_type,
+            &collector.filters.temp_filter,
+        );
+
+        match result {
+            Ok(data) => collector.data.temperature_sensors = data,
+            Err(err) => collector.report_issue(err.to_string()),
+        }
+    }
+
+    fn has_data(&self, data: &Data) -> bool {
+        data.temperature_sensors.is_some()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::data_collection::temperature::TemperatureType;
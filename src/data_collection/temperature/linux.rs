@@ -5,11 +5,11 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use hashbrown::{HashMap, HashSet};
 
-use super::{TempHarvest, TemperatureType};
-use crate::app::filter::Filter;
+use super::{ExtraSensors, SensorKind, TempHarvest, TemperatureType};
+use crate::data_collection::filter::Filter;
 
 const EMPTY_NAME: &str = "Unknown";
 
@@ -18,6 +18,9 @@ const EMPTY_NAME: &str = "Unknown";
 struct HwmonResults {
     temperatures: Vec<TempHarvest>,
     num_hwmon: usize,
+    /// Whether every hwmon sensor we tried to read failed, such as when they
+    /// all require root (as is common on Android).
+    all_unreadable: bool,
 }
 
 /// Parses and reads temperatures that were in millidegree Celsius, and if
@@ -26,6 +29,26 @@ fn parse_temp(path: &Path) -> Result<f32> {
     Ok(fs::read_to_string(path)?.trim_end().parse::<f32>()? / 1_000.0)
 }
 
+/// Returns what a hwmon sensor file measures, if it's a reading we want. Note
+/// that voltages are in millivolts and power is in microwatts.
+fn sensor_kind(file_name: &str, extra: ExtraSensors) -> Option<SensorKind> {
+    let prefix = file_name.strip_suffix("_input")?;
+
+    if prefix.starts_with("temp") {
+        Some(SensorKind::Temperature)
+    } else if extra.voltages
+        && prefix
+            .strip_prefix("in")
+            .is_some_and(|index| index.bytes().all(|b| b.is_ascii_digit()))
+    {
+        Some(SensorKind::Voltage)
+    } else if extra.power && prefix.starts_with("power") {
+        Some(SensorKind::Power)
+    } else {
+        None
+    }
+}
+
 /// Get all candidates from hwmon and coretemp. It will also return the number
 /// of entries from hwmon.
 fn get_hwmon_candidates() -> (HashSet<PathBuf>, usize) {
@@ -176,6 +199,32 @@ fn finalize_name(
     counted_name(seen_names, candidate_name)
 }
 
+/// Whether a hwmon device is a drive, going by its hwmon name or the name its
+/// `device` symlink points to. NVMe drives use the `nvme` driver (or link to
+/// `nvme*`), while SATA drives use the `drivetemp` driver.
+#[inline]
+fn is_drive_sensor(sensor_name: Option<&str>, device_link: Option<&str>) -> bool {
+    matches!(sensor_name, Some("nvme" | "drivetemp"))
+        || device_link.is_some_and(|link| link.starts_with("nvme"))
+}
+
+/// Gets the kernel name of a SCSI device's disk, ex `sda`. The `device` symlink
+/// of `drivetemp` sensors points to a SCSI address like `0:0:0:0`, which isn't
+/// a very useful name.
+fn block_device_name(device: &Path) -> Option<String> {
+    device
+        .join("block")
+        .read_dir()
+        .ok()?
+        .flatten()
+        .find_map(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .map(|name| name.trim().to_owned())
+        })
+}
+
 /// Whether the temperature should *actually* be read during enumeration.
 /// Will return false if the state is not D0/unknown, or if it does not support
 /// `device/power_state`.
@@ -223,11 +272,14 @@ fn is_device_awake(path: &Path) -> bool {
 /// the device is already in ACPI D0. This has the notable issue that
 /// once this happens, the device will be *kept* on through the sensor
 /// reading, and not be able to re-enter ACPI D3cold.
-fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> HwmonResults {
+fn hwmon_temperatures(
+    temp_type: &TemperatureType, filter: &Option<Filter>, extra: ExtraSensors,
+) -> HwmonResults {
     let mut temperatures: Vec<TempHarvest> = vec![];
     let mut seen_names: HashMap<String, u32> = HashMap::new();
 
     let (dirs, num_hwmon) = get_hwmon_candidates();
+    let (mut num_read, mut num_unreadable) = (0, 0);
 
     // Note that none of this is async if we ever go back to it, but sysfs is in
     // memory, so in theory none of this should block if we're slightly careful.
@@ -243,12 +295,20 @@ fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> H
     // also allow easy cancellation/timeouts.
     for file_path in dirs {
         let sensor_name = read_to_string_lossy(file_path.join("name"));
+        let is_drive = {
+            let link = fs::read_link(file_path.join("device")).ok();
+            let link = link.as_ref().and_then(|link| link.file_name()?.to_str());
+
+            is_drive_sensor(sensor_name.as_deref(), link)
+        };
 
         if !is_device_awake(&file_path) {
             let name = finalize_name(None, None, &sensor_name, &mut seen_names);
             temperatures.push(TempHarvest {
                 name,
                 temperature: None,
+                kind: SensorKind::Temperature,
+                is_drive,
             });
 
             continue;
@@ -260,19 +320,30 @@ fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> H
                 let name = file.file_name();
                 let name = name.to_string_lossy();
 
-                // We only want temperature sensors, skip others early
-                if !(name.starts_with("temp") && name.ends_with("input")) {
+                // We only want temperature sensors (and voltage/power ones if enabled), skip
+                // others early
+                let Some(kind) = sensor_kind(&name, extra) else {
                     continue;
-                }
+                };
 
                 let temp_path = file.path();
                 let sensor_label_path = file_path.join(name.replace("input", "label"));
-                let sensor_label = read_to_string_lossy(sensor_label_path);
+                let sensor_label = match kind {
+                    SensorKind::Temperature => read_to_string_lossy(sensor_label_path),
+                    // Unlabelled voltages and power readings are common, so fall back to the
+                    // file name (ex `in0`) rather than numbering them.
+                    SensorKind::Voltage | SensorKind::Power => {
+                        read_to_string_lossy(sensor_label_path)
+                            .or_else(|| name.strip_suffix("_input").map(str::to_owned))
+                    }
+                };
 
                 // Do some messing around to get a more sensible name for sensors:
                 // - For GPUs, this will use the kernel device name, ex `card0`
                 // - For nvme drives, this will also use the kernel name, ex `nvme0`. This is
                 //   found differently than for GPUs
+                // - For SATA drives using drivetemp, this will use the disk's kernel name, ex
+                //   `sda`.
                 // - For whatever acpitz is, on my machine this is now `thermal_zone0`.
                 // - For k10temp, this will still be k10temp, but it has to be handled special.
                 let hwmon_name = {
@@ -307,7 +378,7 @@ fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> H
                         // for nvme drives, but to PCI buses for anything
                         // else. If the first character is alphabetic, it's an actual name like
                         // k10temp or nvme0, not a PCI bus.
-                        fs::read_link(device).ok().and_then(|link| {
+                        fs::read_link(&device).ok().and_then(|link| {
                             let link = link
                                 .file_name()
                                 .and_then(|f| f.to_str())
@@ -317,6 +388,8 @@ fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> H
                                 Some(link) if link.as_bytes()[0].is_ascii_alphabetic() => {
                                     Some(humanize_name(link, sensor_name.as_ref()))
                                 }
+                                _ if is_drive => block_device_name(&device)
+                                    .map(|block| humanize_name(block, sensor_name.as_ref())),
                                 _ => None,
                             }
                         })
@@ -328,11 +401,21 @@ fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> H
                 // TODO: It's possible we may want to move the filter check further up to avoid
                 // probing hwmon if not needed?
                 if Filter::optional_should_keep(filter, &name) {
-                    if let Ok(temp_celsius) = parse_temp(&temp_path) {
+                    if let Ok(value) = parse_temp(&temp_path) {
+                        num_read += 1;
+                        let value = match kind {
+                            SensorKind::Temperature => temp_type.convert_temp_unit(value),
+                            SensorKind::Voltage => value,
+                            SensorKind::Power => value / 1_000.0,
+                        };
                         temperatures.push(TempHarvest {
                             name,
-                            temperature: Some(temp_type.convert_temp_unit(temp_celsius)),
+                            temperature: Some(value),
+                            kind,
+                            is_drive,
                         });
+                    } else {
+                        num_unreadable += 1;
                     }
                 }
             }
@@ -342,24 +425,27 @@ fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> H
     HwmonResults {
         temperatures,
         num_hwmon,
+        all_unreadable: num_read == 0 && num_unreadable > 0,
     }
 }
 
 /// Gets data from `/sys/class/thermal/thermal_zone*`. This should only be used
-/// if [`hwmon_temperatures`] doesn't return anything to avoid duplicate sensor
-/// results.
+/// if [`hwmon_temperatures`] doesn't return anything (or couldn't read any of
+/// its sensors) to avoid duplicate sensor results. Zones that can't be read,
+/// such as ones requiring root, are skipped. Returns how many zones there were.
 ///
 /// See [the Linux kernel documentation](https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-thermal)
 /// for more details.
 fn add_thermal_zone_temperatures(
     temperatures: &mut Vec<TempHarvest>, temp_type: &TemperatureType, filter: &Option<Filter>,
-) {
+) -> usize {
     let path = Path::new("/sys/class/thermal");
     let Ok(read_dir) = path.read_dir() else {
-        return;
+        return 0;
     };
 
     let mut seen_names: HashMap<String, u32> = HashMap::new();
+    let mut num_zones = 0;
 
     for entry in read_dir.flatten() {
         if entry
@@ -367,6 +453,7 @@ fn add_thermal_zone_temperatures(
             .to_string_lossy()
             .starts_with("thermal_zone")
         {
+            num_zones += 1;
             let file_path = entry.path();
             let name_path = file_path.join("type");
 
@@ -385,22 +472,35 @@ fn add_thermal_zone_temperatures(
                         temperatures.push(TempHarvest {
                             name,
                             temperature: Some(temp_type.convert_temp_unit(temp_celsius)),
+                            kind: SensorKind::Temperature,
+                            is_drive: false,
                         });
                     }
                 }
             }
         }
     }
+
+    num_zones
 }
 
-/// Gets temperature sensors and data.
+/// Gets temperature sensors and data. This fails if there are no sensors at all,
+/// or if none of them could be read.
 pub fn get_temperature_data(
-    temp_type: &TemperatureType, filter: &Option<Filter>,
+    temp_type: &TemperatureType, filter: &Option<Filter>, extra: ExtraSensors,
 ) -> Result<Option<Vec<TempHarvest>>> {
-    let mut results = hwmon_temperatures(temp_type, filter);
+    let mut results = hwmon_temperatures(temp_type, filter, extra);
 
-    if results.num_hwmon == 0 {
-        add_thermal_zone_temperatures(&mut results.temperatures, temp_type, filter);
+    if results.num_hwmon == 0 || results.all_unreadable {
+        let num_zones = add_thermal_zone_temperatures(&mut results.temperatures, temp_type, filter);
+
+        if results.temperatures.is_empty() {
+            if results.all_unreadable {
+                bail!("Couldn't read any hwmon sensors, which may need root");
+            } else if num_zones == 0 {
+                bail!("No hwmon or thermal zone sensors were found in /sys/class");
+            }
+        }
     }
 
     Ok(Some(results.temperatures))
@@ -410,7 +510,8 @@ pub fn get_temperature_data(
 mod tests {
     use hashbrown::HashMap;
 
-    use super::finalize_name;
+    use super::{finalize_name, is_drive_sensor, sensor_kind};
+    use crate::data_collection::temperature::{ExtraSensors, SensorKind};
 
     #[test]
     fn test_finalize_name() {
@@ -488,4 +589,40 @@ mod tests {
             "Unknown (4)"
         );
     }
+
+    #[test]
+    fn test_is_drive_sensor() {
+        assert!(is_drive_sensor(Some("nvme"), Some("nvme0")));
+        assert!(is_drive_sensor(None, Some("nvme1")));
+        assert!(is_drive_sensor(Some("drivetemp"), Some("0:0:0:0")));
+        assert!(!is_drive_sensor(Some("k10temp"), Some("0000:00:18.3")));
+        assert!(!is_drive_sensor(Some("amdgpu"), None));
+    }
+
+    #[test]
+    fn test_sensor_kind() {
+        let all = ExtraSensors {
+            voltages: true,
+            power: true,
+        };
+
+        assert_eq!(
+            sensor_kind("temp1_input", all),
+            Some(SensorKind::Temperature)
+        );
+        assert_eq!(sensor_kind("in0_input", all), Some(SensorKind::Voltage));
+        assert_eq!(sensor_kind("power1_input", all), Some(SensorKind::Power));
+        assert_eq!(sensor_kind("in0_label", all), None);
+        assert_eq!(sensor_kind("fan1_input", all), None);
+        assert_eq!(sensor_kind("intrusion0_input", all), None);
+        assert_eq!(sensor_kind("power1_input_highest", all), None);
+
+        let none = ExtraSensors::default();
+        assert_eq!(
+            sensor_kind("temp1_input", none),
+            Some(SensorKind::Temperature)
+        );
+        assert_eq!(sensor_kind("in0_input", none), None);
+        assert_eq!(sensor_kind("power1_input", none), None);
+    }
 }
@@ -0,0 +1,82 @@
+//! Gets temperature data on macOS natively, since sysinfo only finds a few
+//! sensors. Intel Macs expose their sensors through the SMC, and Apple Silicon
+//! Macs through IOHID.
+
+#[cfg(target_os = "macos")]
+mod hid;
+mod smc;
+
+#[cfg(target_os = "macos")]
+use anyhow::Result;
+
+#[cfg(target_os = "macos")]
+use super::{SensorKind, TempHarvest, TemperatureType};
+#[cfg(target_os = "macos")]
+use crate::data_collection::filter::Filter;
+
+/// The temperature sources on this machine, which are opened on first use and
+/// kept open afterwards.
+#[cfg(target_os = "macos")]
+#[derive(Debug, Default)]
+pub struct TempSensors {
+    is_init: bool,
+    smc: Option<smc::Smc>,
+    hid: Option<hid::HidSensors>,
+}
+
+#[cfg(target_os = "macos")]
+impl TempSensors {
+    fn init(&mut self) {
+        if !self.is_init {
+            self.is_init = true;
+            self.smc = smc::Smc::open();
+            self.hid = hid::HidSensors::new();
+        }
+    }
+
+    /// Returns the name and temperature in Celsius of every sensor we can read.
+    fn read(&mut self) -> Vec<(String, f32)> {
+        self.init();
+
+        let mut sensors = Vec::new();
+
+        if let Some(smc) = &self.smc {
+            sensors.extend(smc::KNOWN_KEYS.iter().filter_map(|(key, name)| {
+                let temp = smc::encode_key(key).and_then(|key| smc.read_temperature(key))?;
+                Some((name.to_string(), temp))
+            }));
+
+            // Stop at the first core that's missing, as cores are numbered in order.
+            sensors.extend((0..).map_while(|core| {
+                let temp = smc::core_key(core).and_then(|key| smc.read_temperature(key))?;
+                Some((format!("CPU Core {core}"), temp))
+            }));
+        }
+
+        if let Some(hid) = &mut self.hid {
+            sensors.extend(hid.read());
+        }
+
+        sensors
+    }
+}
+
+/// Gets temperature sensors and data.
+#[cfg(target_os = "macos")]
+pub fn get_temperature_data(
+    sensors: &mut TempSensors, temp_type: &TemperatureType, filter: &Option<Filter>,
+) -> Result<Option<Vec<TempHarvest>>> {
+    let temperatures = sensors
+        .read()
+        .into_iter()
+        .filter(|(name, _)| Filter::optional_should_keep(filter, name))
+        .map(|(name, temp)| TempHarvest {
+            name,
+            temperature: Some(temp_type.convert_temp_unit(temp)),
+            kind: SensorKind::Temperature,
+            is_drive: false,
+        })
+        .collect();
+
+    Ok(Some(temperatures))
+}
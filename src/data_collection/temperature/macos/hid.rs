@@ -0,0 +1,144 @@
+//! Reading temperatures from IOHID sensors, which is where Apple Silicon Macs
+//! expose their sensors. These APIs are private, but are what `powermetrics`
+//! and other tools use.
+//!
+//! Based on [sysinfo's implementation](https://github.com/GuillaumeGomez/sysinfo/blob/master/src/unix/apple/macos/component/arm.rs).
+
+use std::ffi::c_void;
+
+use core_foundation::{
+    array::{CFArrayGetCount, CFArrayGetValueAtIndex, CFArrayRef},
+    base::{kCFAllocatorDefault, CFAllocatorRef, CFRelease, TCFType},
+    dictionary::{CFDictionary, CFDictionaryRef},
+    number::CFNumber,
+    string::{CFString, CFStringRef},
+};
+
+type IOHIDEventSystemClientRef = *const c_void;
+type IOHIDServiceClientRef = *const c_void;
+type IOHIDEventRef = *const c_void;
+
+/// The usage page and usage of temperature sensors.
+const HID_USAGE_PAGE_APPLE_VENDOR: i32 = 0xff00;
+const HID_USAGE_APPLE_VENDOR_TEMPERATURE_SENSOR: i32 = 5;
+
+const HID_EVENT_TYPE_TEMPERATURE: i64 = 15;
+
+/// The field holding the value of an event of the given type.
+const fn event_field_base(event_type: i64) -> i32 {
+    (event_type << 16) as i32
+}
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    fn IOHIDEventSystemClientCreate(allocator: CFAllocatorRef) -> IOHIDEventSystemClientRef;
+
+    fn IOHIDEventSystemClientSetMatching(
+        client: IOHIDEventSystemClientRef, matching: CFDictionaryRef,
+    ) -> i32;
+
+    fn IOHIDEventSystemClientCopyServices(client: IOHIDEventSystemClientRef) -> CFArrayRef;
+
+    fn IOHIDServiceClientCopyProperty(
+        service: IOHIDServiceClientRef, key: CFStringRef,
+    ) -> CFStringRef;
+
+    fn IOHIDServiceClientCopyEvent(
+        service: IOHIDServiceClientRef, event_type: i64, options: i32, timestamp: i64,
+    ) -> IOHIDEventRef;
+
+    fn IOHIDEventGetFloatValue(event: IOHIDEventRef, field: i32) -> f64;
+}
+
+/// A client for reading IOHID temperature sensors.
+#[derive(Debug)]
+pub struct HidSensors(IOHIDEventSystemClientRef);
+
+// SAFETY: The client is only ever used through `&mut self`, and the event
+// system client isn't tied to the thread that created it.
+unsafe impl Send for HidSensors {}
+
+impl HidSensors {
+    /// Creates a client that matches temperature sensors, if possible.
+    pub fn new() -> Option<Self> {
+        let matching = CFDictionary::from_CFType_pairs(&[
+            (
+                CFString::from_static_string("PrimaryUsagePage"),
+                CFNumber::from(HID_USAGE_PAGE_APPLE_VENDOR),
+            ),
+            (
+                CFString::from_static_string("PrimaryUsage"),
+                CFNumber::from(HID_USAGE_APPLE_VENDOR_TEMPERATURE_SENSOR),
+            ),
+        ]);
+
+        // SAFETY: IOKit calls; the client is checked for null before being used,
+        // and `matching` is valid for the duration of the call.
+        unsafe {
+            let client = IOHIDEventSystemClientCreate(kCFAllocatorDefault);
+            if client.is_null() {
+                return None;
+            }
+
+            IOHIDEventSystemClientSetMatching(client, matching.as_concrete_TypeRef());
+
+            Some(HidSensors(client))
+        }
+    }
+
+    /// Returns the name and temperature in Celsius of each sensor.
+    pub fn read(&mut self) -> Vec<(String, f32)> {
+        let mut sensors = Vec::new();
+        let product_key = CFString::from_static_string("Product");
+
+        // SAFETY: IOKit and CoreFoundation calls. Everything returned by a `Copy`
+        // or `Create` function is released once we're done with it, and every
+        // pointer is checked for null before it's used.
+        unsafe {
+            let services = IOHIDEventSystemClientCopyServices(self.0);
+            if services.is_null() {
+                return sensors;
+            }
+
+            for index in 0..CFArrayGetCount(services) {
+                let service = CFArrayGetValueAtIndex(services, index) as IOHIDServiceClientRef;
+                if service.is_null() {
+                    continue;
+                }
+
+                let name =
+                    IOHIDServiceClientCopyProperty(service, product_key.as_concrete_TypeRef());
+                if name.is_null() {
+                    continue;
+                }
+                let name = CFString::wrap_under_create_rule(name).to_string();
+
+                let event = IOHIDServiceClientCopyEvent(service, HID_EVENT_TYPE_TEMPERATURE, 0, 0);
+                if event.is_null() {
+                    continue;
+                }
+                let temp =
+                    IOHIDEventGetFloatValue(event, event_field_base(HID_EVENT_TYPE_TEMPERATURE));
+                CFRelease(event);
+
+                // Sensors that aren't available report nonsense like -127.
+                if temp > 0.0 {
+                    sensors.push((name, temp as f32));
+                }
+            }
+
+            CFRelease(services.cast());
+        }
+
+        sensors
+    }
+}
+
+impl Drop for HidSensors {
+    fn drop(&mut self) {
+        // SAFETY: The client was created in `new`, and is only released here.
+        unsafe {
+            CFRelease(self.0);
+        }
+    }
+}
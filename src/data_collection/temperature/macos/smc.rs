@@ -0,0 +1,258 @@
+//! Reading temperatures from the System Management Controller (SMC), which is
+//! where Intel Macs expose their sensors.
+//!
+//! Based on [smcFanControl](https://github.com/hholtmann/smcFanControl/blob/master/smc-command/smc.c)
+//! and [sysinfo's implementation](https://github.com/GuillaumeGomez/sysinfo/blob/master/src/unix/apple/macos/component/x86.rs).
+
+/// The SMC keys of temperature sensors that we know about, and what to call
+/// them. Not every Mac has every one of these.
+pub(super) const KNOWN_KEYS: &[(&str, &str)] = &[
+    ("TC0P", "CPU Proximity"),
+    ("TC0D", "CPU Die"),
+    ("TC0E", "CPU Die (Virtual)"),
+    ("TC0F", "CPU Die (Filtered)"),
+    ("TCXC", "CPU PECI"),
+    ("TCGC", "CPU Integrated GPU"),
+    ("TG0P", "GPU Proximity"),
+    ("TG0D", "GPU Die"),
+    ("TA0P", "Ambient"),
+    ("Th0H", "Heatsink"),
+    ("Tm0P", "Memory Proximity"),
+    ("TN0P", "Northbridge Proximity"),
+    ("TB0T", "Battery"),
+    ("TW0P", "Wireless Proximity"),
+    ("Ts0P", "Palm Rest"),
+];
+
+/// Converts a four-character SMC key into the integer the SMC expects.
+pub(super) fn encode_key(key: &str) -> Option<u32> {
+    let bytes: [u8; 4] = key.as_bytes().try_into().ok()?;
+
+    Some(u32::from_be_bytes(bytes))
+}
+
+/// Returns the key for the temperature of the given CPU core. Only the first
+/// ten cores have one, as keys are four characters long.
+pub(super) fn core_key(core: usize) -> Option<u32> {
+    encode_key(&format!("TC{core}C"))
+}
+
+/// Decodes a raw SMC value of the given type into a temperature in Celsius.
+/// Readings that are out of range are treated as missing, as some keys exist
+/// but are unused on some models.
+pub(super) fn decode_temperature(data_type: u32, bytes: &[u8]) -> Option<f32> {
+    let temp = match &data_type.to_be_bytes() {
+        // Signed fixed point, with 7 integer bits and 8 fractional bits.
+        b"sp78" => f32::from(i16::from_be_bytes(bytes.get(..2)?.try_into().ok()?)) / 256.0,
+        b"flt " => f32::from_ne_bytes(bytes.get(..4)?.try_into().ok()?),
+        b"ui8 " => f32::from(*bytes.first()?),
+        _ => return None,
+    };
+
+    (temp > 0.0 && temp < 150.0).then_some(temp)
+}
+
+#[cfg(target_os = "macos")]
+pub(super) use ffi::Smc;
+
+#[cfg(target_os = "macos")]
+mod ffi {
+    use std::mem;
+
+    use core_foundation::base::mach_port_t;
+    use libc::c_char;
+    use mach2::{kern_return::kern_return_t, port::MACH_PORT_NULL, traps::mach_task_self};
+
+    use super::decode_temperature;
+
+    #[expect(non_camel_case_types)]
+    type io_object_t = mach_port_t;
+    #[expect(non_camel_case_types)]
+    type io_connect_t = io_object_t;
+
+    /// The user client method for talking to the SMC.
+    const KERNEL_INDEX_SMC: u32 = 2;
+    const SMC_CMD_READ_BYTES: u8 = 5;
+    const SMC_CMD_READ_KEYINFO: u8 = 9;
+
+    #[repr(C)]
+    #[derive(Default, Clone, Copy)]
+    struct SmcVersion {
+        major: u8,
+        minor: u8,
+        build: u8,
+        reserved: u8,
+        release: u16,
+    }
+
+    #[repr(C)]
+    #[derive(Default, Clone, Copy)]
+    struct SmcPLimitData {
+        version: u16,
+        length: u16,
+        cpu_p_limit: u32,
+        gpu_p_limit: u32,
+        mem_p_limit: u32,
+    }
+
+    #[repr(C)]
+    #[derive(Default, Clone, Copy)]
+    struct SmcKeyInfo {
+        data_size: u32,
+        data_type: u32,
+        data_attributes: u8,
+    }
+
+    /// Mirrors `SMCKeyData_t`, which is used for both input and output.
+    #[repr(C)]
+    #[derive(Default, Clone, Copy)]
+    struct SmcKeyData {
+        key: u32,
+        vers: SmcVersion,
+        p_limit_data: SmcPLimitData,
+        key_info: SmcKeyInfo,
+        result: u8,
+        status: u8,
+        data8: u8,
+        data32: u32,
+        bytes: [u8; 32],
+    }
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IOServiceMatching(
+            name: *const c_char,
+        ) -> core_foundation::dictionary::CFMutableDictionaryRef;
+
+        fn IOServiceGetMatchingService(
+            mainPort: mach_port_t, matching: core_foundation::dictionary::CFMutableDictionaryRef,
+        ) -> io_object_t;
+
+        fn IOServiceOpen(
+            service: io_object_t, owningTask: mach_port_t, r#type: u32, connect: *mut io_connect_t,
+        ) -> kern_return_t;
+
+        fn IOServiceClose(connect: io_connect_t) -> kern_return_t;
+
+        fn IOObjectRelease(obj: io_object_t) -> kern_return_t;
+
+        fn IOConnectCallStructMethod(
+            connection: mach_port_t, selector: u32, inputStruct: *const SmcKeyData,
+            inputStructCnt: usize, outputStruct: *mut SmcKeyData, outputStructCnt: *mut usize,
+        ) -> kern_return_t;
+    }
+
+    /// An open connection to the SMC.
+    #[derive(Debug)]
+    pub struct Smc(io_connect_t);
+
+    impl Smc {
+        /// Opens a connection to the SMC, if there is one.
+        pub fn open() -> Option<Self> {
+            // SAFETY: IOKit calls; `IOServiceMatching` returns a dictionary that
+            // `IOServiceGetMatchingService` takes ownership of, and `conn` is only
+            // used if opening succeeded.
+            unsafe {
+                let service = IOServiceGetMatchingService(
+                    MACH_PORT_NULL,
+                    IOServiceMatching(b"AppleSMC\0".as_ptr().cast()),
+                );
+                if service == 0 {
+                    return None;
+                }
+
+                let mut conn: io_connect_t = 0;
+                let result = IOServiceOpen(service, mach_task_self(), 0, &mut conn);
+                IOObjectRelease(service);
+
+                (result == mach2::kern_return::KERN_SUCCESS).then_some(Smc(conn))
+            }
+        }
+
+        fn call(&self, input: &SmcKeyData) -> Option<SmcKeyData> {
+            let mut output = SmcKeyData::default();
+            let mut output_size = mem::size_of::<SmcKeyData>();
+
+            // SAFETY: IOKit call; both structs match the layout the SMC expects, and
+            // the sizes passed are their actual sizes.
+            let result = unsafe {
+                IOConnectCallStructMethod(
+                    self.0,
+                    KERNEL_INDEX_SMC,
+                    input,
+                    mem::size_of::<SmcKeyData>(),
+                    &mut output,
+                    &mut output_size,
+                )
+            };
+
+            (result == mach2::kern_return::KERN_SUCCESS && output.result == 0).then_some(output)
+        }
+
+        /// Reads the temperature for the given encoded key, if it exists and
+        /// has a sensible value.
+        pub fn read_temperature(&self, key: u32) -> Option<f32> {
+            let key_info = self.call(&SmcKeyData {
+                key,
+                data8: SMC_CMD_READ_KEYINFO,
+                ..Default::default()
+            })?;
+
+            let value = self.call(&SmcKeyData {
+                key,
+                key_info: key_info.key_info,
+                data8: SMC_CMD_READ_BYTES,
+                ..Default::default()
+            })?;
+
+            let size = (key_info.key_info.data_size as usize).min(value.bytes.len());
+            decode_temperature(key_info.key_info.data_type, &value.bytes[..size])
+        }
+    }
+
+    impl Drop for Smc {
+        fn drop(&mut self) {
+            // SAFETY: The connection was opened in `open`, and is only closed here.
+            unsafe {
+                IOServiceClose(self.0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode_smc_keys() {
+        assert_eq!(encode_key("TC0P"), Some(0x5443_3050));
+        assert_eq!(encode_key("TC0"), None);
+        assert!(KNOWN_KEYS.iter().all(|(key, _)| encode_key(key).is_some()));
+
+        assert_eq!(core_key(0), encode_key("TC0C"));
+        assert_eq!(core_key(10), None);
+    }
+
+    #[test]
+    fn decode_smc_temperatures() {
+        let sp78 = encode_key("sp78").unwrap();
+        assert_eq!(decode_temperature(sp78, &[0x2d, 0x80]), Some(45.5));
+        assert_eq!(decode_temperature(sp78, &[0x00, 0x00]), None);
+        assert_eq!(decode_temperature(sp78, &[0x2d]), None);
+
+        let flt = encode_key("flt ").unwrap();
+        assert_eq!(
+            decode_temperature(flt, &42.25_f32.to_ne_bytes()),
+            Some(42.25)
+        );
+
+        let ui8 = encode_key("ui8 ").unwrap();
+        assert_eq!(decode_temperature(ui8, &[200]), None);
+
+        assert_eq!(
+            decode_temperature(encode_key("fpe2").unwrap(), &[0, 0]),
+            None
+        );
+    }
+}
@@ -2,8 +2,8 @@
 
 use anyhow::Result;
 
-use super::{TempHarvest, TemperatureType};
-use crate::app::filter::Filter;
+use super::{SensorKind, TempHarvest, TemperatureType};
+use crate::data_collection::filter::Filter;
 
 pub fn get_temperature_data(
     components: &sysinfo::Components, temp_type: &TemperatureType, filter: &Option<Filter>,
@@ -17,6 +17,8 @@ pub fn get_temperature_data(
             temperature_vec.push(TempHarvest {
                 name,
                 temperature: Some(temp_type.convert_temp_unit(component.temperature())),
+                kind: SensorKind::Temperature,
+                is_drive: false,
             });
         }
     }
@@ -39,6 +41,8 @@ pub fn get_temperature_data(
                                 TemperatureType::Kelvin => temp.kelvin(),
                                 TemperatureType::Fahrenheit => temp.fahrenheit(),
                             }),
+                            kind: SensorKind::Temperature,
+                            is_drive: false,
                         });
                     }
                 }
@@ -0,0 +1,62 @@
+//! Detection of the [Windows Subsystem for Linux](https://learn.microsoft.com/en-us/windows/wsl/),
+//! where some sources either don't exist or report data about the Windows
+//! host in odd ways.
+
+use std::sync::OnceLock;
+
+/// Which version of WSL we're running under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WslVersion {
+    /// WSL 1, which translates Linux syscalls rather than running a real kernel.
+    Wsl1,
+    /// WSL 2, which runs a real Linux kernel in a Hyper-V virtual machine.
+    Wsl2,
+}
+
+/// Parses the contents of `/proc/sys/kernel/osrelease`. Both versions of WSL
+/// mention Microsoft in their kernel release; WSL 1 capitalizes it, and WSL 2
+/// doesn't.
+fn parse_osrelease(osrelease: &str) -> Option<WslVersion> {
+    if osrelease.contains("Microsoft") {
+        Some(WslVersion::Wsl1)
+    } else if osrelease.contains("microsoft") {
+        Some(WslVersion::Wsl2)
+    } else {
+        None
+    }
+}
+
+/// Returns which version of WSL we're running under, if any. This is only
+/// checked once.
+pub fn wsl_version() -> Option<WslVersion> {
+    static WSL_VERSION: OnceLock<Option<WslVersion>> = OnceLock::new();
+
+    *WSL_VERSION.get_or_init(|| {
+        std::fs::read_to_string("/proc/sys/kernel/osrelease")
+            .ok()
+            .and_then(|osrelease| parse_osrelease(&osrelease))
+    })
+}
+
+/// Returns whether we're running under any version of WSL.
+pub fn is_wsl() -> bool {
+    wsl_version().is_some()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn detect_wsl_version() {
+        assert_eq!(
+            parse_osrelease("4.4.0-19041-Microsoft\n"),
+            Some(WslVersion::Wsl1)
+        );
+        assert_eq!(
+            parse_osrelease("5.15.153.1-microsoft-standard-WSL2\n"),
+            Some(WslVersion::Wsl2)
+        );
+        assert_eq!(parse_osrelease("6.8.0-45-generic\n"), None);
+    }
+}
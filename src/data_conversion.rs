@@ -8,7 +8,9 @@ use std::borrow::Cow;
 use crate::{
     app::{data_farmer::DataCollection, AxisScaling},
     canvas::components::time_chart::Point,
-    data_collection::{cpu::CpuDataType, memory::MemHarvest, temperature::TemperatureType},
+    data_collection::{
+        cpu::CpuDataType, memory::MemHarvest, network::Duplex, temperature::TemperatureType,
+    },
     utils::{data_prefixes::*, data_units::DataUnit},
     widgets::{DiskWidgetData, TempWidgetData},
 };
@@ -49,6 +51,9 @@ pub struct ConvertedData {
     pub total_tx_display: String,
     pub network_data_rx: Vec<Point>,
     pub network_data_tx: Vec<Point>,
+    /// The combined speed of the shown interfaces' links in bits per second,
+    /// and their duplex if they share one.
+    pub link: Option<(u64, Option<Duplex>)>,
 
     pub mem_labels: Option<(String, String)>,
     #[cfg(not(target_os = "windows"))]
@@ -116,8 +121,11 @@ impl ConvertedData {
         data.temp_harvest.iter().for_each(|temp_harvest| {
             self.temp_data.push(TempWidgetData {
                 sensor: Cow::Owned(temp_harvest.name.to_string()),
-                temperature_value: temp_harvest.temperature.map(|temp| temp.ceil() as u64),
+                value: temp_harvest.temperature,
+                kind: temp_harvest.kind,
                 temperature_type,
+                is_hidden: false,
+                is_drive: temp_harvest.is_drive,
             });
         });
 
@@ -128,7 +136,7 @@ impl ConvertedData {
         let current_time = current_data.current_instant;
 
         // (Re-)initialize the vector if the lengths don't match...
-        if let Some((_time, data)) = &current_data.timed_data_vec.last() {
+        if let Some(data) = current_data.current_timed_data() {
             if data.cpu_data.len() + 1 != self.cpu_data.len() {
                 self.cpu_data = Vec::with_capacity(data.cpu_data.len() + 1);
                 self.cpu_data.push(CpuWidgetData::All);
@@ -455,6 +463,25 @@ pub fn convert_network_points(
     }
 }
 
+/// Returns a string for a network rate given in bits per second, in the given
+/// unit.
+pub fn network_rate_string(
+    bits_per_second: f64, unit_type: &DataUnit, use_binary_prefix: bool,
+) -> String {
+    let (value, unit) = match unit_type {
+        DataUnit::Byte => (bits_per_second / 8.0, "B/s"),
+        DataUnit::Bit => (bits_per_second, "b/s"),
+    };
+
+    let (value, unit) = if use_binary_prefix {
+        get_binary_prefix(value as u64, unit)
+    } else {
+        get_decimal_prefix(value as u64, unit)
+    };
+
+    format!("{value:.1}{unit}")
+}
+
 /// Returns a string given a value that is converted to the closest binary
 /// variant. If the value is greater than a gibibyte, then it will return a
 /// decimal place.
@@ -614,6 +641,23 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_network_rate_string() {
+        assert_eq!(network_rate_string(0.0, &DataUnit::Bit, false), "0.0b/s");
+        assert_eq!(
+            network_rate_string(8000.0, &DataUnit::Byte, false),
+            "1.0KB/s"
+        );
+        assert_eq!(
+            network_rate_string(8192.0, &DataUnit::Byte, true),
+            "1.0KiB/s"
+        );
+        assert_eq!(
+            network_rate_string(1_500_000.0, &DataUnit::Bit, false),
+            "1.5Mb/s"
+        );
+    }
+
     #[test]
     fn test_dec_bytes_per_second_string() {
         assert_eq!(dec_bytes_per_second_string(0), "0B/s".to_string());
@@ -1,12 +1,27 @@
 //! Some code around handling events.
 
-use std::sync::mpsc::Sender;
+pub mod keybinds;
+#[cfg(target_os = "windows")]
+pub mod termination;
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+use std::{
+    sync::mpsc::Sender,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+use self::keybinds::{Action, Key};
+#[cfg(target_os = "linux")]
+use crate::data_collection::processes::ProcessFields;
 
 use crate::{
     app::{layout_manager::WidgetDirection, App},
-    data_collection::Data,
+    constants::DOUBLE_CLICK_TIMEOUT_IN_MILLISECONDS,
+    data_collection::{
+        temperature::{ExtraSensors, TemperatureType},
+        Data, DataFilters, UsedWidgets,
+    },
 };
 
 /// Events sent to the main thread.
@@ -16,35 +31,340 @@ pub enum BottomEvent {
     KeyInput(KeyEvent),
     MouseInput(MouseEvent),
     PasteEvent(String),
+    FocusGained,
+    FocusLost,
     Update(Box<Data>),
     Clean,
+    /// Dump the current state to a file, after being sent `SIGUSR1`.
+    DumpState,
+    /// Toggle freezing, after being sent `SIGUSR2`.
+    ToggleFreeze,
+    /// Clean up the terminal and stop, after being sent `SIGTSTP`.
+    Suspend,
+    /// Set up the terminal again, after being continued with `SIGCONT`.
+    Resume,
     Terminate,
+    /// Exit without cleaning up the terminal, as it was closed.
+    Hangup,
 }
 
 /// Events sent to the collection thread.
 #[derive(Debug)]
 pub enum CollectionThreadEvent {
     Reset,
+    /// Collect with different settings, such as after switching profiles.
+    Reconfigure(Box<CollectionSettings>),
+    /// Collect at the idle rate, as the user probably isn't looking.
+    Idle,
+    /// Go back to collecting at the normal rate.
+    Active,
+    /// Stop collecting entirely until resumed.
+    Pause,
+    /// Resume collecting after being paused.
+    Resume,
+    /// Collect these optional process details, as what's shown changed.
+    #[cfg(target_os = "linux")]
+    ProcessFields(ProcessFields),
+}
+
+/// The settings the collection thread collects data with.
+#[derive(Debug, Clone)]
+pub struct CollectionSettings {
+    pub update_rate: u64,
+    pub idle_rate: u64,
+    pub temperature_type: TemperatureType,
+    pub extra_sensors: ExtraSensors,
+    pub use_current_cpu_total: bool,
+    pub unnormalized_cpu: bool,
+    pub show_average_cpu: bool,
+    pub network_namespaces: bool,
+    pub max_processes: Option<usize>,
+    pub filters: DataFilters,
+    pub used_widgets: UsedWidgets,
+    #[cfg(target_os = "linux")]
+    pub process_fields: ProcessFields,
+}
+
+impl CollectionSettings {
+    /// Returns the settings `app` needs data to be collected with.
+    pub fn new(app: &App) -> Self {
+        let config = &app.app_config_fields;
+
+        Self {
+            update_rate: config.update_rate,
+            idle_rate: config.idle_rate.unwrap_or(config.update_rate),
+            temperature_type: config.temperature_type,
+            extra_sensors: config.extra_sensors,
+            use_current_cpu_total: config.use_current_cpu_total,
+            unnormalized_cpu: config.unnormalized_cpu,
+            show_average_cpu: config.show_average_cpu,
+            network_namespaces: config.network_namespaces,
+            max_processes: config.max_processes,
+            filters: app.filters.clone(),
+            used_widgets: app.used_widgets,
+            #[cfg(target_os = "linux")]
+            process_fields: app.process_fields(),
+        }
+    }
+}
+
+/// Tracks whether the user is idle - that is, the terminal is unfocused or
+/// there hasn't been any input for a while - so that the collection thread
+/// can be slowed down while they are.
+pub struct IdleTracker {
+    idle_timeout: Duration,
+    last_input: Instant,
+    is_focused: bool,
+    is_idle: bool,
+}
+
+impl IdleTracker {
+    pub fn new(idle_timeout: Duration) -> Self {
+        Self {
+            idle_timeout,
+            last_input: Instant::now(),
+            is_focused: true,
+            is_idle: false,
+        }
+    }
+
+    /// Updates the tracker with an event sent to the main thread.
+    pub fn on_event(&mut self, event: &BottomEvent, now: Instant) {
+        match event {
+            BottomEvent::KeyInput(_) | BottomEvent::MouseInput(_) | BottomEvent::PasteEvent(_) => {
+                self.last_input = now;
+            }
+            BottomEvent::FocusGained => {
+                self.is_focused = true;
+                self.last_input = now;
+            }
+            BottomEvent::FocusLost => {
+                self.is_focused = false;
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the event to send to the collection thread if the user has
+    /// become idle or active.
+    pub fn check(&mut self, now: Instant) -> Option<CollectionThreadEvent> {
+        let is_idle =
+            !self.is_focused || now.saturating_duration_since(self.last_input) >= self.idle_timeout;
+
+        if is_idle == self.is_idle {
+            None
+        } else {
+            self.is_idle = is_idle;
+            Some(if is_idle {
+                CollectionThreadEvent::Idle
+            } else {
+                CollectionThreadEvent::Active
+            })
+        }
+    }
+}
+
+/// Tracks left clicks to tell when two of them make a double click.
+#[derive(Debug, Default)]
+pub struct ClickTracker {
+    last_click: Option<(Instant, u16, u16)>,
+}
+
+impl ClickTracker {
+    /// Records a left click, returning whether it makes a double click - that
+    /// is, whether it was in the same place as the last click, and soon enough
+    /// after it.
+    pub fn on_click(&mut self, x: u16, y: u16, now: Instant) -> bool {
+        let timeout = Duration::from_millis(DOUBLE_CLICK_TIMEOUT_IN_MILLISECONDS);
+        let is_double_click = self.last_click.is_some_and(|(time, last_x, last_y)| {
+            (last_x, last_y) == (x, y) && now.saturating_duration_since(time) <= timeout
+        });
+
+        // A third click starts over, rather than making another double click.
+        self.last_click = if is_double_click {
+            None
+        } else {
+            Some((now, x, y))
+        };
+
+        is_double_click
+    }
+}
+
+/// Tracks scrolls to ignore those that come too soon after the last one, as
+/// some mice and trackpads send many at once.
+#[derive(Debug, Default)]
+pub struct ScrollTracker {
+    last_scroll: Option<Instant>,
+}
+
+impl ScrollTracker {
+    /// Records a scroll, returning whether it should be handled - that is,
+    /// whether at least `debounce` has passed since the last handled scroll.
+    pub fn on_scroll(&mut self, now: Instant, debounce: Duration) -> bool {
+        let should_handle = match self.last_scroll {
+            Some(time) => now.saturating_duration_since(time) >= debounce,
+            None => true,
+        };
+
+        if should_handle {
+            self.last_scroll = Some(now);
+        }
+
+        should_handle
+    }
+}
+
+/// Coalesces redraws requested by input, so that bursts of it (like a held
+/// down key repeating) are drawn at most once every `interval` rather than once
+/// per event, which can saturate slow connections.
+#[derive(Debug)]
+pub struct RedrawLimiter {
+    interval: Duration,
+    last_draw: Option<Instant>,
+    is_pending: bool,
+}
+
+impl RedrawLimiter {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_draw: None,
+            is_pending: false,
+        }
+    }
+
+    /// Requests a redraw, which should be done once [`Self::wait_time`] has
+    /// passed.
+    pub fn request(&mut self) {
+        self.is_pending = true;
+    }
+
+    /// Records that everything was just redrawn, which also takes care of any
+    /// requested redraw.
+    pub fn on_draw(&mut self, now: Instant) {
+        self.last_draw = Some(now);
+        self.is_pending = false;
+    }
+
+    /// Returns how long until a requested redraw is due, or `None` if none was
+    /// requested.
+    pub fn wait_time(&self, now: Instant) -> Option<Duration> {
+        if !self.is_pending {
+            return None;
+        }
+
+        Some(match self.last_draw {
+            Some(time) => self
+                .interval
+                .saturating_sub(now.saturating_duration_since(time)),
+            None => Duration::ZERO,
+        })
+    }
+
+    /// Returns whether a requested redraw is due.
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.wait_time(now) == Some(Duration::ZERO)
+    }
+}
+
+/// Waits for bursts of resizes to settle, like those sent while dragging a
+/// window's edge, so that the layout is only recomputed and redrawn once per
+/// size rather than once per resize.
+#[derive(Debug)]
+pub struct ResizeDebouncer {
+    settle: Duration,
+    max_wait: Duration,
+    /// When the first and last resizes since the last handled one happened.
+    pending: Option<(Instant, Instant)>,
+}
+
+impl ResizeDebouncer {
+    pub fn new(settle: Duration, max_wait: Duration) -> Self {
+        Self {
+            settle,
+            max_wait,
+            pending: None,
+        }
+    }
+
+    /// Records a resize.
+    pub fn on_resize(&mut self, now: Instant) {
+        self.pending = Some(match self.pending {
+            Some((first, _)) => (first, now),
+            None => (now, now),
+        });
+    }
+
+    /// Returns whether a resize should be handled now - that is, whether there
+    /// haven't been any more for `settle`, or they've kept on coming for
+    /// `max_wait`, in which case the current size is drawn in the meantime.
+    pub fn check(&mut self, now: Instant) -> bool {
+        let Some((first, last)) = self.pending else {
+            return false;
+        };
+
+        let is_due = now.saturating_duration_since(last) >= self.settle
+            || now.saturating_duration_since(first) >= self.max_wait;
+        if is_due {
+            self.pending = None;
+        }
+
+        is_due
+    }
 }
 
 /// Handle a [`MouseEvent`].
 pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
     match event.kind {
-        MouseEventKind::ScrollUp => app.handle_scroll_up(),
-        MouseEventKind::ScrollDown => app.handle_scroll_down(),
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+            let mouse = app.app_config_fields.mouse;
+            let debounce = Duration::from_millis(mouse.scroll_debounce_ms);
+            if app.scroll_tracker.on_scroll(Instant::now(), debounce) {
+                let is_up = matches!(event.kind, MouseEventKind::ScrollUp) != mouse.invert_scroll;
+                if is_up {
+                    app.handle_scroll_up();
+                } else {
+                    app.handle_scroll_down();
+                }
+            }
+        }
         MouseEventKind::Down(button) => {
             let (x, y) = (event.column, event.row);
             if !app.app_config_fields.disable_click {
                 match button {
-                    crossterm::event::MouseButton::Left => {
-                        // Trigger left click widget activity
-                        app.on_left_mouse_up(x, y);
+                    MouseButton::Left => {
+                        let is_double_click = app.click_tracker.on_click(x, y, Instant::now());
+                        if !app.on_popup_click(x, y) && !app.start_layout_drag(x, y) {
+                            // Trigger left click widget activity
+                            app.on_left_mouse_up(x, y);
+                            if is_double_click {
+                                app.on_double_click(x, y);
+                            } else if !app.move_inspect_cursor_to(x, y) {
+                                app.start_time_range_selection(x, y);
+                            }
+                        }
                     }
-                    crossterm::event::MouseButton::Right => {}
+                    MouseButton::Right => app.on_right_mouse_down(x, y),
                     _ => {}
                 }
             }
         }
+        MouseEventKind::Moved if !app.app_config_fields.disable_click => {
+            app.on_mouse_move(event.column, event.row);
+        }
+        MouseEventKind::Drag(MouseButton::Left) if !app.app_config_fields.disable_click => {
+            let is_resizing = app.update_layout_drag(event.column, event.row);
+            if !is_resizing {
+                app.update_time_range_selection(event.column);
+            }
+        }
+        MouseEventKind::Up(MouseButton::Left) if !app.app_config_fields.disable_click => {
+            let was_resizing = app.finish_layout_drag();
+            if !was_resizing {
+                app.finish_time_range_selection(event.column);
+            }
+        }
         _ => {}
     };
 }
@@ -55,11 +375,29 @@ pub fn handle_key_event_or_break(
 ) -> bool {
     // c_debug!("KeyEvent: {event:?}");
 
-    if event.modifiers.is_empty() {
-        // Required catch for searching - otherwise you couldn't search with q.
-        if event.code == KeyCode::Char('q') && !app.is_in_search_widget() {
-            return true;
+    let key = Key::from(event);
+    if let Some(action) = app.app_config_fields.key_bindings.get(&key) {
+        // Keys that type text are left for the search widget and dialogs -
+        // otherwise you couldn't search with q. Quitting still works in dialogs.
+        let is_typing = key.is_text()
+            && (app.is_typing() || (action != Action::Quit && app.ignore_normal_keybinds()));
+
+        if !is_typing {
+            match action {
+                Action::Quit => return true,
+                Action::Reset => {
+                    if reset_sender.send(CollectionThreadEvent::Reset).is_ok() {
+                        app.reset();
+                    }
+                }
+                action => app.on_action(action),
+            }
+
+            return false;
         }
+    }
+
+    if event.modifiers.is_empty() {
         match event.code {
             KeyCode::End => app.skip_to_last(),
             KeyCode::Home => app.skip_to_first(),
@@ -73,12 +411,6 @@ pub fn handle_key_event_or_break(
             KeyCode::Tab => app.on_tab(),
             KeyCode::Backspace => app.on_backspace(),
             KeyCode::Delete => app.on_delete(),
-            KeyCode::F(1) => app.toggle_ignore_case(),
-            KeyCode::F(2) => app.toggle_search_whole_word(),
-            KeyCode::F(3) => app.toggle_search_regex(),
-            KeyCode::F(5) => app.toggle_tree_mode(),
-            KeyCode::F(6) => app.toggle_sort_menu(),
-            KeyCode::F(9) => app.start_killing_process(),
             KeyCode::PageDown => app.on_page_down(),
             KeyCode::PageUp => app.on_page_up(),
             _ => {}
@@ -87,9 +419,6 @@ pub fn handle_key_event_or_break(
         // Otherwise, track the modifier as well...
         if let KeyModifiers::ALT = event.modifiers {
             match event.code {
-                KeyCode::Char('c') | KeyCode::Char('C') => app.toggle_ignore_case(),
-                KeyCode::Char('w') | KeyCode::Char('W') => app.toggle_search_whole_word(),
-                KeyCode::Char('r') | KeyCode::Char('R') => app.toggle_search_regex(),
                 KeyCode::Char('h') => app.on_left_key(),
                 KeyCode::Char('l') => app.on_right_key(),
                 _ => {}
@@ -99,20 +428,22 @@ pub fn handle_key_event_or_break(
                 return true;
             }
 
+            // Raw mode stops the terminal from sending SIGTSTP for Ctrl+Z, so
+            // do it ourselves.
+            #[cfg(target_family = "unix")]
+            if event.code == KeyCode::Char('z') {
+                app.is_suspending = true;
+                return false;
+            }
+
             match event.code {
-                KeyCode::Char('f') => app.on_slash(),
                 KeyCode::Left => app.move_widget_selection(&WidgetDirection::Left),
                 KeyCode::Right => app.move_widget_selection(&WidgetDirection::Right),
                 KeyCode::Up => app.move_widget_selection(&WidgetDirection::Up),
                 KeyCode::Down => app.move_widget_selection(&WidgetDirection::Down),
-                KeyCode::Char('r') => {
-                    if reset_sender.send(CollectionThreadEvent::Reset).is_ok() {
-                        app.reset();
-                    }
-                }
                 KeyCode::Char('a') => app.skip_cursor_beginning(),
                 KeyCode::Char('e') => app.skip_cursor_end(),
-                KeyCode::Char('u') if app.is_in_search_widget() => app.clear_search(),
+                KeyCode::Char('u') if app.is_typing() => app.clear_search(),
                 KeyCode::Char('w') => app.clear_previous_word(),
                 KeyCode::Char('h') => app.on_backspace(),
                 KeyCode::Char('d') => app.scroll_half_page_down(),
@@ -140,3 +471,134 @@ pub fn handle_key_event_or_break(
 
     false
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn idle_after_timeout() {
+        let start = Instant::now();
+        let mut tracker = IdleTracker::new(Duration::from_secs(60));
+        assert!(tracker.check(start).is_none());
+
+        let later = start + Duration::from_secs(61);
+        assert!(matches!(
+            tracker.check(later),
+            Some(CollectionThreadEvent::Idle)
+        ));
+        assert!(tracker.check(later).is_none());
+
+        tracker.on_event(&BottomEvent::PasteEvent(String::new()), later);
+        assert!(matches!(
+            tracker.check(later),
+            Some(CollectionThreadEvent::Active)
+        ));
+        assert!(tracker.check(later).is_none());
+    }
+
+    #[test]
+    fn idle_when_unfocused() {
+        let start = Instant::now();
+        let mut tracker = IdleTracker::new(Duration::from_secs(60));
+
+        tracker.on_event(&BottomEvent::FocusLost, start);
+        assert!(matches!(
+            tracker.check(start),
+            Some(CollectionThreadEvent::Idle)
+        ));
+
+        // Updates shouldn't wake things back up.
+        tracker.on_event(&BottomEvent::Clean, start);
+        assert!(tracker.check(start).is_none());
+
+        tracker.on_event(&BottomEvent::FocusGained, start);
+        assert!(matches!(
+            tracker.check(start),
+            Some(CollectionThreadEvent::Active)
+        ));
+    }
+
+    #[test]
+    fn double_clicks() {
+        let start = Instant::now();
+        let mut tracker = ClickTracker::default();
+        assert!(!tracker.on_click(5, 5, start));
+        assert!(tracker.on_click(5, 5, start + Duration::from_millis(200)));
+
+        // A third click doesn't make another double click.
+        assert!(!tracker.on_click(5, 5, start + Duration::from_millis(300)));
+
+        // Too slow.
+        assert!(!tracker.on_click(5, 5, start + Duration::from_secs(1)));
+
+        // Somewhere else.
+        assert!(!tracker.on_click(6, 5, start + Duration::from_millis(1100)));
+        assert!(tracker.on_click(6, 5, start + Duration::from_millis(1200)));
+    }
+
+    #[test]
+    fn debounced_scrolls() {
+        let start = Instant::now();
+        let debounce = Duration::from_millis(20);
+        let mut tracker = ScrollTracker::default();
+        assert!(tracker.on_scroll(start, debounce));
+        assert!(!tracker.on_scroll(start + Duration::from_millis(10), debounce));
+        assert!(tracker.on_scroll(start + Duration::from_millis(20), debounce));
+
+        // Ignored scrolls don't push back the next one.
+        assert!(!tracker.on_scroll(start + Duration::from_millis(30), debounce));
+        assert!(tracker.on_scroll(start + Duration::from_millis(40), debounce));
+
+        // No debounce handles everything.
+        assert!(tracker.on_scroll(start + Duration::from_millis(40), Duration::ZERO));
+    }
+
+    #[test]
+    fn coalesced_redraws() {
+        let start = Instant::now();
+        let mut limiter = RedrawLimiter::new(Duration::from_millis(16));
+        assert_eq!(limiter.wait_time(start), None);
+
+        // The first redraw isn't held back.
+        limiter.request();
+        assert!(limiter.is_due(start));
+        limiter.on_draw(start);
+        assert!(!limiter.is_due(start));
+
+        // Requests right after a redraw wait out the rest of the interval.
+        limiter.request();
+        limiter.request();
+        let soon = start + Duration::from_millis(6);
+        assert_eq!(limiter.wait_time(soon), Some(Duration::from_millis(10)));
+        assert!(!limiter.is_due(soon));
+        assert!(limiter.is_due(start + Duration::from_millis(16)));
+
+        // Redrawing for other reasons takes care of requests.
+        limiter.on_draw(soon);
+        assert_eq!(limiter.wait_time(start + Duration::from_millis(30)), None);
+    }
+
+    #[test]
+    fn debounced_resizes() {
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+        let mut debouncer =
+            ResizeDebouncer::new(Duration::from_millis(50), Duration::from_millis(250));
+        assert!(!debouncer.check(start));
+
+        // A burst is handled once, after it settles.
+        debouncer.on_resize(start);
+        debouncer.on_resize(ms(20));
+        assert!(!debouncer.check(ms(40)));
+        assert!(debouncer.check(ms(70)));
+        assert!(!debouncer.check(ms(80)));
+
+        // A long burst is still handled every so often.
+        for time in (100..=350).step_by(10) {
+            debouncer.on_resize(ms(time));
+        }
+        assert!(debouncer.check(ms(350)));
+        assert!(!debouncer.check(ms(360)));
+    }
+}
@@ -0,0 +1,546 @@
+//! Key bindings for actions that can be remapped in the config file.
+
+use std::{fmt, str::FromStr};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use hashbrown::HashMap;
+
+/// An action that can be bound to keys with the `[keybinds]` config table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Search,
+    Kill,
+    TreeToggle,
+    SortMenu,
+    Help,
+    Expand,
+    Freeze,
+    Reset,
+    ZoomIn,
+    ZoomOut,
+    ResetZoom,
+    CollapseBranch,
+    CollapseAll,
+    ExpandAll,
+    TreeTotals,
+    GroupByUser,
+    PinMount,
+    CycleMount,
+    HideSensor,
+    ShowHiddenSensors,
+    InvertSort,
+    Inspect,
+    Percentages,
+    DebugOverlay,
+    LogLevel,
+    Diagnostics,
+    ExportConfig,
+    NextProfile,
+    IgnoreCase,
+    WholeWord,
+    Regex,
+    NextPage,
+    PreviousPage,
+    HideWidget,
+    ShowWidgets,
+    CopyRow,
+    CopyPid,
+    CopyCommand,
+}
+
+impl Action {
+    /// All actions, in the order they are listed in the help menu.
+    pub const ALL: [Action; 39] = [
+        Action::Quit,
+        Action::Search,
+        Action::Kill,
+        Action::TreeToggle,
+        Action::SortMenu,
+        Action::Help,
+        Action::Expand,
+        Action::Freeze,
+        Action::Reset,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::ResetZoom,
+        Action::CollapseBranch,
+        Action::CollapseAll,
+        Action::ExpandAll,
+        Action::TreeTotals,
+        Action::GroupByUser,
+        Action::PinMount,
+        Action::CycleMount,
+        Action::HideSensor,
+        Action::ShowHiddenSensors,
+        Action::InvertSort,
+        Action::Inspect,
+        Action::Percentages,
+        Action::DebugOverlay,
+        Action::LogLevel,
+        Action::Diagnostics,
+        Action::ExportConfig,
+        Action::NextProfile,
+        Action::IgnoreCase,
+        Action::WholeWord,
+        Action::Regex,
+        Action::NextPage,
+        Action::PreviousPage,
+        Action::HideWidget,
+        Action::ShowWidgets,
+        Action::CopyRow,
+        Action::CopyPid,
+        Action::CopyCommand,
+    ];
+
+    /// The name of the action in the config file.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Search => "search",
+            Action::Kill => "kill",
+            Action::TreeToggle => "tree_toggle",
+            Action::SortMenu => "sort_menu",
+            Action::Help => "help",
+            Action::Expand => "expand",
+            Action::Freeze => "freeze",
+            Action::Reset => "reset",
+            Action::ZoomIn => "zoom_in",
+            Action::ZoomOut => "zoom_out",
+            Action::ResetZoom => "reset_zoom",
+            Action::CollapseBranch => "collapse_branch",
+            Action::CollapseAll => "collapse_all",
+            Action::ExpandAll => "expand_all",
+            Action::TreeTotals => "tree_totals",
+            Action::GroupByUser => "group_by_user",
+            Action::PinMount => "pin_mount",
+            Action::CycleMount => "cycle_mount",
+            Action::HideSensor => "hide_sensor",
+            Action::ShowHiddenSensors => "show_hidden_sensors",
+            Action::InvertSort => "invert_sort",
+            Action::Inspect => "inspect",
+            Action::Percentages => "percentages",
+            Action::DebugOverlay => "debug_overlay",
+            Action::LogLevel => "log_level",
+            Action::Diagnostics => "diagnostics",
+            Action::ExportConfig => "export_config",
+            Action::NextProfile => "next_profile",
+            Action::IgnoreCase => "ignore_case",
+            Action::WholeWord => "whole_word",
+            Action::Regex => "regex",
+            Action::NextPage => "next_page",
+            Action::PreviousPage => "previous_page",
+            Action::HideWidget => "hide_widget",
+            Action::ShowWidgets => "show_widgets",
+            Action::CopyRow => "copy_row",
+            Action::CopyPid => "copy_pid",
+            Action::CopyCommand => "copy_command",
+        }
+    }
+
+    /// A short description of the action for the help menu.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Search => "Open process search widget, or search all widgets elsewhere",
+            Action::Kill => "Kill the selected process",
+            Action::TreeToggle => "Toggle tree mode",
+            Action::SortMenu => "Open process sort widget",
+            Action::Help => "Open help menu",
+            Action::Expand => "Toggle expanding the currently selected widget",
+            Action::Freeze => "Freeze/unfreeze updating with new data",
+            Action::Reset => "Reset display and any collected data",
+            Action::ZoomIn => "Zoom in on chart, or collapse/expand a branch in tree mode",
+            Action::ZoomOut => "Zoom out of chart, or collapse/expand a branch in tree mode",
+            Action::ResetZoom => "Reset zoom",
+            Action::CollapseBranch => "Collapse the selected process's parent branch in tree mode",
+            Action::CollapseAll => "Collapse every branch in tree mode",
+            Action::ExpandAll => "Expand every branch in tree mode",
+            Action::TreeTotals => "Toggle showing branch totals on parents in tree mode",
+            Action::GroupByUser => "Toggle grouping processes by user, then by name",
+            Action::PinMount => "Pin or unpin the selected mount to the top of the disk table",
+            Action::CycleMount => "Cycle the mount shown in the basic mode disk gauge",
+            Action::HideSensor => "Hide or unhide the selected temperature sensor",
+            Action::ShowHiddenSensors => "Toggle showing hidden temperature sensors",
+            Action::InvertSort => "Invert current sort",
+            Action::Inspect => "Toggle the inspection cursor on a chart",
+            Action::Percentages => "Toggle between values and percentages for memory usage",
+            Action::DebugOverlay => "Toggle the debug overlay",
+            Action::LogLevel => "Log more detail, going back to only errors after tracing",
+            Action::Diagnostics => "Show the config in use and data source diagnostics",
+            Action::ExportConfig => "Export the config in use, including layout changes, to a file",
+            Action::NextProfile => "Switch to the next profile in the config",
+            Action::IgnoreCase => "Toggle matching case while searching",
+            Action::WholeWord => "Toggle matching the entire word while searching",
+            Action::Regex => "Toggle using regex while searching",
+            Action::NextPage => "Switch to the next layout page",
+            Action::PreviousPage => "Switch to the previous layout page",
+            Action::HideWidget => "Hide the selected widget",
+            Action::ShowWidgets => "Show all hidden widgets",
+            Action::CopyRow => "Copy the selected table row to the clipboard",
+            Action::CopyPid => "Copy the selected process's PID to the clipboard",
+            Action::CopyCommand => "Copy the selected process's command to the clipboard",
+        }
+    }
+
+    /// The keys bound to the action if the config file doesn't set any.
+    fn default_keys(&self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q"],
+            Action::Search => &["/", "ctrl+f"],
+            Action::Kill => &["F9"],
+            Action::TreeToggle => &["F5"],
+            Action::SortMenu => &["F6"],
+            Action::Help => &["?"],
+            Action::Expand => &["e"],
+            Action::Freeze => &["f"],
+            Action::Reset => &["ctrl+r"],
+            Action::ZoomIn => &["+"],
+            Action::ZoomOut => &["-"],
+            Action::ResetZoom => &["="],
+            Action::CollapseBranch => &["z"],
+            Action::CollapseAll => &["_"],
+            Action::ExpandAll => &["*"],
+            Action::TreeTotals => &["T"],
+            Action::GroupByUser => &["U"],
+            Action::PinMount => &["b"],
+            Action::CycleMount => &["o"],
+            Action::HideSensor => &["v"],
+            Action::ShowHiddenSensors => &["V"],
+            Action::InvertSort => &["I"],
+            Action::Inspect => &["i"],
+            Action::Percentages => &["%"],
+            Action::DebugOverlay => &["F12"],
+            Action::LogLevel => &["F4"],
+            Action::Diagnostics => &["ctrl+o"],
+            Action::ExportConfig => &["ctrl+s"],
+            Action::NextProfile => &["ctrl+p"],
+            Action::IgnoreCase => &["F1", "alt+c", "alt+C"],
+            Action::WholeWord => &["F2", "alt+w", "alt+W"],
+            Action::Regex => &["F3", "alt+r", "alt+R"],
+            Action::NextPage => &[">"],
+            Action::PreviousPage => &["<"],
+            Action::HideWidget => &["x"],
+            Action::ShowWidgets => &["X"],
+            Action::CopyRow => &["y"],
+            Action::CopyPid => &["Y"],
+            Action::CopyCommand => &["ctrl+y"],
+        }
+    }
+}
+
+/// A key press, along with any modifiers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    fn new(code: KeyCode, mut modifiers: KeyModifiers) -> Self {
+        // Shift is already part of the character itself, and terminals aren't
+        // consistent about also reporting it as a modifier.
+        if let KeyCode::Char(_) = code {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+
+        Self { code, modifiers }
+    }
+
+    /// Whether this key would otherwise be typed as text, such as into the
+    /// search widget.
+    pub fn is_text(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_)) && self.modifiers.is_empty()
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Key::new(event.code, event.modifiers)
+    }
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    /// Parses keys like `q`, `F9`, `ctrl+f`, or `alt+shift+c`. Modifiers and
+    /// named keys are case-insensitive, but single characters are not.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (modifiers_str, key_str) = if s == "+" {
+            ("", s)
+        } else if let Some(modifiers_str) = s.strip_suffix("++") {
+            (modifiers_str, "+")
+        } else {
+            match s.rsplit_once('+') {
+                Some((modifiers_str, key_str)) => (modifiers_str, key_str),
+                None => ("", s),
+            }
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        if !modifiers_str.is_empty() {
+            for modifier in modifiers_str.split('+') {
+                modifiers |= match modifier.to_lowercase().as_str() {
+                    "ctrl" | "control" => KeyModifiers::CONTROL,
+                    "alt" => KeyModifiers::ALT,
+                    "shift" => KeyModifiers::SHIFT,
+                    _ => return Err(format!("'{modifier}' in '{s}' is not a valid modifier")),
+                };
+            }
+        }
+
+        let mut chars = key_str.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => {
+                if modifiers.contains(KeyModifiers::SHIFT) {
+                    KeyCode::Char(c.to_ascii_uppercase())
+                } else {
+                    KeyCode::Char(c)
+                }
+            }
+            _ => match key_str.to_lowercase().as_str() {
+                "enter" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdown" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "space" => KeyCode::Char(' '),
+                other => match other.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(format!("'{s}' is not a valid key")),
+                },
+            },
+        };
+
+        Ok(Key::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift-")?;
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Delete"),
+            KeyCode::Insert => write!(f, "Insert"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDown"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// The keys bound to each [`Action`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyBindings {
+    actions: HashMap<Key, Action>,
+    keys: Vec<(Action, Vec<Key>)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::new(std::iter::empty()).expect("the default key bindings should be valid")
+    }
+}
+
+impl KeyBindings {
+    /// Creates the key bindings, where each action in `overrides` replaces
+    /// its default keys with the given ones. Returns an error if a key is
+    /// invalid or bound to more than one action.
+    pub fn new<'a>(
+        overrides: impl IntoIterator<Item = (Action, &'a [String])>,
+    ) -> Result<Self, String> {
+        let overrides: HashMap<Action, &[String]> = overrides.into_iter().collect();
+        let mut actions = HashMap::new();
+        let mut keys = Vec::with_capacity(Action::ALL.len());
+
+        for action in Action::ALL {
+            let action_keys = match overrides.get(&action) {
+                Some(keys) => keys
+                    .iter()
+                    .map(|key| key.parse::<Key>())
+                    .collect::<Result<Vec<_>, _>>()?,
+                None => action
+                    .default_keys()
+                    .iter()
+                    .map(|key| key.parse::<Key>())
+                    .collect::<Result<Vec<_>, _>>()?,
+            };
+
+            let mut unique_keys: Vec<Key> = Vec::with_capacity(action_keys.len());
+            for key in action_keys {
+                match actions.insert(key, action) {
+                    Some(existing) if existing != action => {
+                        return Err(format!(
+                            "'{key}' is bound to both '{}' and '{}'",
+                            existing.name(),
+                            action.name()
+                        ));
+                    }
+                    Some(_) => {}
+                    None => unique_keys.push(key),
+                }
+            }
+
+            keys.push((action, unique_keys));
+        }
+
+        Ok(Self { actions, keys })
+    }
+
+    /// Returns the action bound to a key press, if any.
+    pub fn get(&self, key: &Key) -> Option<Action> {
+        self.actions.get(key).copied()
+    }
+
+    /// Returns each action and the keys bound to it, in the order of
+    /// [`Action::ALL`].
+    pub fn iter(&self) -> impl Iterator<Item = (Action, &[Key])> {
+        self.keys
+            .iter()
+            .map(|(action, keys)| (*action, keys.as_slice()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_keys() {
+        assert_eq!(
+            "q".parse::<Key>(),
+            Ok(Key::new(KeyCode::Char('q'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            "F9".parse::<Key>(),
+            Ok(Key::new(KeyCode::F(9), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            "Ctrl+F".parse::<Key>(),
+            Ok(Key::new(KeyCode::Char('F'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            "ctrl+pagedown".parse::<Key>(),
+            Ok(Key::new(KeyCode::PageDown, KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            "alt+shift+c".parse::<Key>(),
+            Ok(Key::new(KeyCode::Char('C'), KeyModifiers::ALT))
+        );
+        assert_eq!(
+            "+".parse::<Key>(),
+            Ok(Key::new(KeyCode::Char('+'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            "ctrl++".parse::<Key>(),
+            Ok(Key::new(KeyCode::Char('+'), KeyModifiers::CONTROL))
+        );
+
+        assert!("".parse::<Key>().is_err());
+        assert!("F13".parse::<Key>().is_err());
+        assert!("super+q".parse::<Key>().is_err());
+        assert!("ctrl+qq".parse::<Key>().is_err());
+    }
+
+    #[test]
+    fn shift_is_ignored_for_chars() {
+        let event = KeyEvent::new(KeyCode::Char('I'), KeyModifiers::SHIFT);
+        assert_eq!(Key::from(event), "I".parse().unwrap());
+        assert_eq!(Key::from(event), "shift+i".parse().unwrap());
+    }
+
+    #[test]
+    fn display_keys() {
+        assert_eq!("ctrl+f".parse::<Key>().unwrap().to_string(), "Ctrl-f");
+        assert_eq!("f5".parse::<Key>().unwrap().to_string(), "F5");
+        assert_eq!("space".parse::<Key>().unwrap().to_string(), "Space");
+    }
+
+    #[test]
+    fn default_bindings() {
+        let bindings = KeyBindings::default();
+
+        assert_eq!(bindings.get(&"F9".parse().unwrap()), Some(Action::Kill));
+        assert_eq!(
+            bindings.get(&"ctrl+f".parse().unwrap()),
+            Some(Action::Search)
+        );
+        assert_eq!(bindings.get(&"F10".parse().unwrap()), None);
+        assert_eq!(bindings.iter().count(), Action::ALL.len());
+    }
+
+    #[test]
+    fn overridden_bindings() {
+        let kill = vec!["ctrl+k".to_string()];
+        let tree = vec!["t".to_string(), "F5".to_string()];
+        let bindings = KeyBindings::new([
+            (Action::Kill, kill.as_slice()),
+            (Action::TreeToggle, tree.as_slice()),
+        ])
+        .unwrap();
+
+        assert_eq!(bindings.get(&"ctrl+k".parse().unwrap()), Some(Action::Kill));
+        assert_eq!(bindings.get(&"F9".parse().unwrap()), None);
+        assert_eq!(
+            bindings.get(&"t".parse().unwrap()),
+            Some(Action::TreeToggle)
+        );
+        assert_eq!(
+            bindings.get(&"F5".parse().unwrap()),
+            Some(Action::TreeToggle)
+        );
+    }
+
+    #[test]
+    fn unbound_action() {
+        let bindings = KeyBindings::new([(Action::Quit, [].as_slice())]).unwrap();
+
+        assert_eq!(bindings.get(&"q".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn conflicting_bindings() {
+        let kill = vec!["q".to_string()];
+        let err = KeyBindings::new([(Action::Kill, kill.as_slice())]).unwrap_err();
+
+        assert_eq!(err, "'q' is bound to both 'quit' and 'kill'");
+    }
+
+    #[test]
+    fn invalid_binding() {
+        let search = vec!["hyper+f".to_string()];
+
+        assert!(KeyBindings::new([(Action::Search, search.as_slice())]).is_err());
+    }
+}
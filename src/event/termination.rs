@@ -0,0 +1,84 @@
+//! Handling the console being closed or interrupted on Windows, so bottom can
+//! clean up before it exits. On Unix-like systems, the equivalent signals are
+//! handled by the signal thread instead.
+//!
+//! When the console is closed, or the user logs off or shuts down, Windows
+//! kills bottom as soon as the handler returns, so the handler waits (up to a
+//! limit) for the main thread to say it's done cleaning up.
+
+use std::{
+    sync::{mpsc::Sender, Condvar, Mutex, OnceLock},
+    time::Duration,
+};
+
+use windows::Win32::{
+    Foundation::{BOOL, FALSE, TRUE},
+    System::Console::{
+        SetConsoleCtrlHandler, CTRL_BREAK_EVENT, CTRL_CLOSE_EVENT, CTRL_C_EVENT, CTRL_LOGOFF_EVENT,
+        CTRL_SHUTDOWN_EVENT,
+    },
+};
+
+use super::BottomEvent;
+
+/// How long to keep Windows waiting for bottom to clean up. Windows doesn't
+/// wait for more than 5 seconds after the console is closed anyways.
+const CLEANUP_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// Where to send events from the handler.
+static SENDER: OnceLock<Sender<BottomEvent>> = OnceLock::new();
+
+/// Whether the main thread has finished cleaning up.
+static CLEANED_UP: (Mutex<bool>, Condvar) = (Mutex::new(false), Condvar::new());
+
+/// Sets up a handler for console events that sends the main thread
+/// [`BottomEvent::Terminate`] or [`BottomEvent::Hangup`].
+pub fn set_console_handler(sender: Sender<BottomEvent>) -> anyhow::Result<()> {
+    let _ = SENDER.set(sender);
+
+    // SAFETY: The handler is a plain function that lives for the whole program.
+    unsafe { SetConsoleCtrlHandler(Some(handle_console_event), true)? };
+
+    Ok(())
+}
+
+/// Lets the handler know that the main thread has finished cleaning up, so it
+/// can stop waiting.
+pub fn finish_cleanup() {
+    let (lock, condvar) = &CLEANED_UP;
+    if let Ok(mut cleaned_up) = lock.lock() {
+        *cleaned_up = true;
+        condvar.notify_all();
+    }
+}
+
+unsafe extern "system" fn handle_console_event(ctrl_type: u32) -> BOOL {
+    let is_closing = match ctrl_type {
+        CTRL_C_EVENT | CTRL_BREAK_EVENT => false,
+        CTRL_CLOSE_EVENT | CTRL_LOGOFF_EVENT | CTRL_SHUTDOWN_EVENT => true,
+        _ => return FALSE,
+    };
+
+    let Some(sender) = SENDER.get() else {
+        return FALSE;
+    };
+
+    let event = if is_closing {
+        BottomEvent::Hangup
+    } else {
+        BottomEvent::Terminate
+    };
+    if sender.send(event).is_err() {
+        return FALSE;
+    }
+
+    if is_closing {
+        let (lock, condvar) = &CLEANED_UP;
+        if let Ok(cleaned_up) = lock.lock() {
+            let _ =
+                condvar.wait_timeout_while(cleaned_up, CLEANUP_TIMEOUT, |cleaned_up| !*cleaned_up);
+        }
+    }
+
+    TRUE
+}
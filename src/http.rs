@@ -0,0 +1,345 @@
+//! A small HTTP server that exposes the most recently collected data while
+//! bottom is running, so external scripts and dashboards can poll it without
+//! needing a separate exporter.
+//!
+//! This is intentionally very minimal - it only understands `GET` requests
+//! for a couple of fixed paths, and handles one connection at a time.
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+
+use crate::{
+    app::data_farmer::DataCollection,
+    data_collection::{cpu::CpuDataType, temperature::SensorKind},
+    utils::cancellation_token::CancellationToken,
+};
+
+/// How long to wait between checking for new connections.
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long to wait on a client before giving up on it.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The latest snapshot of data to serve, shared between the main thread and
+/// the HTTP thread. This is `None` until the first harvest arrives.
+pub(crate) type SharedSnapshot = Arc<Mutex<Option<MetricsSnapshot>>>;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct CpuSnapshot {
+    name: String,
+    usage: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct MemSnapshot {
+    name: String,
+    used_bytes: u64,
+    total_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct NetworkSnapshot {
+    rx_bits_per_second: u64,
+    tx_bits_per_second: u64,
+    total_rx_bits: u64,
+    total_tx_bits: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct TempSnapshot {
+    name: String,
+    temperature: Option<f32>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct DiskSnapshot {
+    name: String,
+    mount_point: String,
+    used_bytes: Option<u64>,
+    free_bytes: Option<u64>,
+    total_bytes: Option<u64>,
+}
+
+/// A serializable copy of the current harvested data.
+#[derive(Debug, Serialize)]
+pub(crate) struct MetricsSnapshot {
+    /// The time this snapshot was taken, in milliseconds since the Unix epoch.
+    timestamp_ms: u64,
+    cpu: Vec<CpuSnapshot>,
+    load_average: [f32; 3],
+    memory: Vec<MemSnapshot>,
+    network: NetworkSnapshot,
+    temperatures: Vec<TempSnapshot>,
+    disks: Vec<DiskSnapshot>,
+    process_count: usize,
+}
+
+impl MetricsSnapshot {
+    /// Create a new [`MetricsSnapshot`] from the data in a [`DataCollection`].
+    pub(crate) fn new(data: &DataCollection) -> Self {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0);
+
+        let cpu = data
+            .cpu_harvest
+            .iter()
+            .map(|cpu| CpuSnapshot {
+                name: match cpu.data_type {
+                    CpuDataType::Avg => "avg".to_string(),
+                    CpuDataType::Cpu(index) => format!("cpu{index}"),
+                },
+                usage: cpu.cpu_usage,
+            })
+            .collect();
+
+        #[allow(
+            unused_mut,
+            reason = "this is only mutated on some platforms and feature sets"
+        )]
+        let mut memory = vec![
+            MemSnapshot {
+                name: "ram".to_string(),
+                used_bytes: data.memory_harvest.used_bytes,
+                total_bytes: data.memory_harvest.total_bytes,
+            },
+            MemSnapshot {
+                name: "swap".to_string(),
+                used_bytes: data.swap_harvest.used_bytes,
+                total_bytes: data.swap_harvest.total_bytes,
+            },
+        ];
+
+        #[cfg(not(target_os = "windows"))]
+        memory.push(MemSnapshot {
+            name: "cache".to_string(),
+            used_bytes: data.cache_harvest.used_bytes,
+            total_bytes: data.cache_harvest.total_bytes,
+        });
+
+        #[cfg(feature = "zfs")]
+        memory.push(MemSnapshot {
+            name: "arc".to_string(),
+            used_bytes: data.arc_harvest.used_bytes,
+            total_bytes: data.arc_harvest.total_bytes,
+        });
+
+        #[cfg(feature = "gpu")]
+        memory.extend(data.gpu_harvest.iter().map(|(name, harvest)| MemSnapshot {
+            name: name.clone(),
+            used_bytes: harvest.used_bytes,
+            total_bytes: harvest.total_bytes,
+        }));
+
+        let network = NetworkSnapshot {
+            rx_bits_per_second: data.network_harvest.rx,
+            tx_bits_per_second: data.network_harvest.tx,
+            total_rx_bits: data.network_harvest.total_rx,
+            total_tx_bits: data.network_harvest.total_tx,
+        };
+
+        let temperatures = data
+            .temp_harvest
+            .iter()
+            .filter(|temp| temp.kind == SensorKind::Temperature)
+            .map(|temp| TempSnapshot {
+                name: temp.name.clone(),
+                temperature: temp.temperature,
+            })
+            .collect();
+
+        let disks = data
+            .disk_harvest
+            .iter()
+            .map(|disk| DiskSnapshot {
+                name: disk.name.clone(),
+                mount_point: disk.mount_point.clone(),
+                used_bytes: disk.used_space,
+                free_bytes: disk.free_space,
+                total_bytes: disk.total_space,
+            })
+            .collect();
+
+        Self {
+            timestamp_ms,
+            cpu,
+            load_average: data.load_avg_harvest,
+            memory,
+            network,
+            temperatures,
+            disks,
+            process_count: data.process_data.process_harvest.len(),
+        }
+    }
+}
+
+/// A response to send back to a client.
+#[derive(Debug, PartialEq, Eq)]
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn new(status: &'static str, content_type: &'static str, body: String) -> Self {
+        Self {
+            status,
+            content_type,
+            body,
+        }
+    }
+
+    fn text(status: &'static str, body: &str) -> Self {
+        Self::new(status, "text/plain; charset=utf-8", format!("{body}\n"))
+    }
+
+    fn write_to(&self, stream: &mut impl Write) -> std::io::Result<()> {
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.content_type,
+            self.body.len(),
+            self.body
+        )?;
+        stream.flush()
+    }
+}
+
+/// Figure out what response to give based on the request line.
+fn route(request_line: &str, snapshot: &SharedSnapshot) -> Response {
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Response::text("400 Bad Request", "bad request");
+    };
+
+    // Ignore any query strings.
+    let path = target.split('?').next().unwrap_or(target);
+
+    match path {
+        "/healthz" | "/metrics.json" if method != "GET" => {
+            Response::text("405 Method Not Allowed", "method not allowed")
+        }
+        "/healthz" => Response::text("200 OK", "ok"),
+        "/metrics.json" => {
+            let snapshot = match snapshot.lock() {
+                Ok(snapshot) => snapshot,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+
+            match snapshot.as_ref() {
+                Some(snapshot) => match serde_json::to_string(snapshot) {
+                    Ok(body) => Response::new("200 OK", "application/json", body),
+                    Err(_) => {
+                        Response::text("500 Internal Server Error", "failed to serialize data")
+                    }
+                },
+                None => Response::text("503 Service Unavailable", "no data collected yet"),
+            }
+        }
+        _ => Response::text("404 Not Found", "not found"),
+    }
+}
+
+fn handle_client(mut stream: TcpStream, snapshot: &SharedSnapshot) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+
+    route(&request_line, snapshot).write_to(&mut stream)
+}
+
+/// Bind to the given address. This is done separately from spawning the
+/// thread so that we can report binding failures before drawing anything.
+pub(crate) fn bind(address: SocketAddr) -> anyhow::Result<TcpListener> {
+    let listener = TcpListener::bind(address).map_err(|err| {
+        anyhow::anyhow!("bottom could not start the HTTP server at '{address}': {err}")
+    })?;
+    listener.set_nonblocking(true)?;
+
+    Ok(listener)
+}
+
+/// Create a thread to serve data over HTTP.
+pub(crate) fn create_http_thread(
+    listener: TcpListener, snapshot: SharedSnapshot, cancellation_token: Arc<CancellationToken>,
+) -> JoinHandle<()> {
+    thread::spawn(move || loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                // Failing to respond to one client shouldn't take down the server.
+                let _ = handle_client(stream, &snapshot);
+            }
+            Err(_) => {
+                // Either there's no pending connection (we're non-blocking), or something
+                // went wrong; either way, wait a bit before trying again.
+                if cancellation_token.sleep_with_cancellation(ACCEPT_POLL_INTERVAL) {
+                    break;
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn healthz() {
+        let snapshot = SharedSnapshot::default();
+
+        assert_eq!(
+            route("GET /healthz HTTP/1.1\r\n", &snapshot),
+            Response::text("200 OK", "ok")
+        );
+        assert_eq!(
+            route("POST /healthz HTTP/1.1\r\n", &snapshot).status,
+            "405 Method Not Allowed"
+        );
+    }
+
+    #[test]
+    fn metrics() {
+        let snapshot = SharedSnapshot::default();
+
+        assert_eq!(
+            route("GET /metrics.json HTTP/1.1\r\n", &snapshot).status,
+            "503 Service Unavailable"
+        );
+
+        *snapshot.lock().unwrap() = Some(MetricsSnapshot::new(&DataCollection::default()));
+
+        let response = route("GET /metrics.json?pretty HTTP/1.1\r\n", &snapshot);
+        assert_eq!(response.status, "200 OK");
+        assert_eq!(response.content_type, "application/json");
+
+        let value: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert!(value.get("cpu").is_some());
+        assert!(value.get("memory").is_some());
+        assert_eq!(value["process_count"], 0);
+    }
+
+    #[test]
+    fn bad_requests() {
+        let snapshot = SharedSnapshot::default();
+
+        assert_eq!(route("", &snapshot).status, "400 Bad Request");
+        assert_eq!(
+            route("GET /nope HTTP/1.1\r\n", &snapshot).status,
+            "404 Not Found"
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! Translations of user-facing text, like the help menu, widget titles,
+//! dialogs, and errors.
+//!
+//! Text is written in English in the code, and is looked up with [`tr`] in the
+//! catalog of the current [`Locale`] when shown, falling back to the English if
+//! it hasn't been translated. Text with values in it uses named placeholders
+//! like `{name}`, which [`tr_args`] fills in after translating, so translations
+//! can move them around.
+//!
+//! To add a language, add a module with its catalog, and add it to [`Locale`].
+
+mod de;
+mod es;
+
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// A language bottom can be shown in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Locale {
+    #[default]
+    English,
+    German,
+    Spanish,
+}
+
+impl Locale {
+    /// All supported locales.
+    pub const ALL: [Locale; 3] = [Locale::English, Locale::German, Locale::Spanish];
+
+    /// The locale's language code, as used in the config file and `LANG`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::German => "de",
+            Locale::Spanish => "es",
+        }
+    }
+
+    /// Parses a locale like `de`, `de_DE.UTF-8`, or `es-MX`. Only the language
+    /// matters, so regional variants use the same translations.
+    pub fn parse(tag: &str) -> Option<Locale> {
+        let language = tag.trim().split(['_', '-', '.', '@']).next()?;
+
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.code().eq_ignore_ascii_case(language))
+    }
+
+    /// Returns the locale set by the environment, going by `LC_ALL`,
+    /// `LC_MESSAGES`, and then `LANG`, like gettext does. Unsupported languages
+    /// fall back to English.
+    pub fn from_env() -> Locale {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    /// The translations, as pairs of English text and the translated text,
+    /// sorted by the English text.
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::English => &[],
+            Locale::German => de::CATALOG,
+            Locale::Spanish => es::CATALOG,
+        }
+    }
+
+    /// Translates `text` into this locale.
+    fn translate(self, text: &'static str) -> &'static str {
+        let catalog = self.catalog();
+
+        catalog
+            .binary_search_by(|(key, _)| key.cmp(&text))
+            .map_or(text, |index| catalog[index].1)
+    }
+}
+
+/// The locale text is currently translated into. This is global, as text is
+/// translated all over, including in places without access to the app state,
+/// like errors from parsing options.
+static LOCALE: AtomicU8 = AtomicU8::new(Locale::English as u8);
+
+/// Sets the locale text is translated into.
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+/// Returns the locale text is currently translated into.
+pub fn locale() -> Locale {
+    let current = LOCALE.load(Ordering::Relaxed);
+
+    Locale::ALL
+        .into_iter()
+        .find(|&locale| locale as u8 == current)
+        .unwrap_or_default()
+}
+
+/// Translates `text` into the current locale.
+pub fn tr(text: &'static str) -> &'static str {
+    locale().translate(text)
+}
+
+/// Translates `text` into the current locale, and fills in its placeholders
+/// with `args`, which are pairs of placeholder names and values.
+pub fn tr_args(text: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    fill(tr(text), args)
+}
+
+fn fill(text: &str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter().fold(text.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{name}}}"), &value.to_string())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Returns the placeholders in `text`, sorted.
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut placeholders: Vec<_> = text
+            .match_indices('{')
+            .filter_map(|(start, _)| {
+                let end = start + text[start..].find('}')?;
+                let name = &text[start + 1..end];
+                (!name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '_'))
+                    .then_some(name)
+            })
+            .collect();
+        placeholders.sort_unstable();
+
+        placeholders
+    }
+
+    #[test]
+    fn parse_locales() {
+        assert_eq!(Locale::parse("de"), Some(Locale::German));
+        assert_eq!(Locale::parse("de_DE.UTF-8"), Some(Locale::German));
+        assert_eq!(Locale::parse("es-MX"), Some(Locale::Spanish));
+        assert_eq!(Locale::parse("EN_us"), Some(Locale::English));
+        assert_eq!(Locale::parse("C"), None);
+        assert_eq!(Locale::parse(""), None);
+
+        for locale in Locale::ALL {
+            assert_eq!(Locale::parse(locale.code()), Some(locale));
+        }
+    }
+
+    #[test]
+    fn translate() {
+        assert_eq!(Locale::German.translate(" Help "), " Hilfe ");
+        assert_eq!(Locale::Spanish.translate(" Help "), " Ayuda ");
+        assert_eq!(Locale::English.translate(" Help "), " Help ");
+
+        // Untranslated text is kept as is.
+        assert_eq!(Locale::German.translate("not translated"), "not translated");
+    }
+
+    #[test]
+    fn fill_placeholders() {
+        assert_eq!(
+            fill(
+                "Kill process '{name}' with PID {pid}?",
+                &[("name", &"btm"), ("pid", &42)]
+            ),
+            "Kill process 'btm' with PID 42?"
+        );
+
+        // Braces that aren't placeholders are left alone.
+        assert_eq!(fill("{, }   Step", &[("name", &"btm")]), "{, }   Step");
+    }
+
+    #[test]
+    fn catalogs_are_sorted() {
+        for locale in Locale::ALL {
+            for pair in locale.catalog().windows(2) {
+                assert!(
+                    pair[0].0 < pair[1].0,
+                    "{locale:?} isn't sorted or has duplicates around '{}'",
+                    pair[1].0
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn translations_keep_formatting() {
+        for locale in Locale::ALL {
+            for &(text, translation) in locale.catalog() {
+                assert_ne!(
+                    text, translation,
+                    "{locale:?} doesn't need to translate '{text}'"
+                );
+
+                // Padding is part of how titles are drawn.
+                assert_eq!(
+                    text.len() - text.trim_start().len(),
+                    translation.len() - translation.trim_start().len(),
+                    "{locale:?} changes the padding of '{text}'"
+                );
+                assert_eq!(
+                    text.len() - text.trim_end().len(),
+                    translation.len() - translation.trim_end().len(),
+                    "{locale:?} changes the padding of '{text}'"
+                );
+
+                assert_eq!(
+                    placeholders(text),
+                    placeholders(translation),
+                    "{locale:?} changes the placeholders of '{text}'"
+                );
+
+                // Help menu lines start with a column of keys, which is kept.
+                if let Some(keys) = text.get(..17).filter(|keys| keys.contains("  ")) {
+                    assert!(
+                        translation.starts_with(keys),
+                        "{locale:?} changes the keys of '{text}'"
+                    );
+                }
+            }
+        }
+    }
+}
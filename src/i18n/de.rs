@@ -0,0 +1,159 @@
+//! German translations.
+
+/// Pairs of English text and its translation, sorted by the English text.
+pub(super) const CATALOG: &[(&str, &str)] = &[
+    (" Battery ", " Akku "),
+    (" Config and Diagnostics ", " Konfiguration und Diagnose "),
+    (" Confirm Kill Process ", " Beenden des Prozesses bestätigen "),
+    (" Disks ", " Datenträger "),
+    (" Error ", " Fehler "),
+    (" Esc to close ", " Esc zum Schließen "),
+    (" Esc to go back ", " Esc für zurück "),
+    (" Help ", " Hilfe "),
+    (" Memory ", " Speicher "),
+    (" Network ", " Netzwerk "),
+    (" Process Details ", " Prozessdetails "),
+    (" Processes ", " Prozesse "),
+    (" Search ", " Suche "),
+    (" Search: {query}{cursor} ", " Suche: {query}{cursor} "),
+    (" Temperatures ", " Temperaturen "),
+    (" {count} matches ", " {count} Treffer "),
+    ("'--{value}' was set with an invalid value, please update your arguments.", "'--{value}' hat einen ungültigen Wert, bitte korrigiere deine Argumente."),
+    ("'d'              Sort by disk name, press again to reverse", "'d'              Nach Datenträgername sortieren, erneut drücken zum Umkehren"),
+    ("'m'              Sort by disk mount, press again to reverse", "'m'              Nach Einhängepunkt sortieren, erneut drücken zum Umkehren"),
+    ("'n'              Sort by disk free space, press again to reverse", "'n'              Nach freiem Speicherplatz sortieren, erneut drücken zum Umkehren"),
+    ("'p'              Sort by disk usage percentage, press again to reverse", "'p'              Nach prozentualer Belegung sortieren, erneut drücken zum Umkehren"),
+    ("'r'              Sort by disk read activity, press again to reverse", "'r'              Nach Leseaktivität sortieren, erneut drücken zum Umkehren"),
+    ("'s'              Sort by sensor name, press again to reverse", "'s'              Nach Sensorname sortieren, erneut drücken zum Umkehren"),
+    ("'t'              Sort by temperature, press again to reverse", "'t'              Nach Temperatur sortieren, erneut drücken zum Umkehren"),
+    ("'t'              Sort by total disk space, press again to reverse", "'t'              Nach Gesamtspeicherplatz sortieren, erneut drücken zum Umkehren"),
+    ("'u'              Sort by disk usage, press again to reverse", "'u'              Nach belegtem Speicherplatz sortieren, erneut drücken zum Umkehren"),
+    ("'w'              Sort by disk write activity, press again to reverse", "'w'              Nach Schreibaktivität sortieren, erneut drücken zum Umkehren"),
+    ("'{value}' was set with an invalid value, please update it in your config file.", "'{value}' hat einen ungültigen Wert, bitte korrigiere ihn in deiner Konfigurationsdatei."),
+    ("(unbound)", "(nicht belegt)"),
+    ("({current} of {total}) ", "({current} von {total}) "),
+    ("1 - General", "1 - Allgemein"),
+    ("2 - CPU widget", "2 - CPU-Widget"),
+    ("3 - Process widget", "3 - Prozess-Widget"),
+    ("4 - Process search widget", "4 - Prozesssuche-Widget"),
+    ("5 - Process sort widget", "5 - Prozesssortierung-Widget"),
+    ("5 - Sort widget", "5 - Sortier-Widget"),
+    ("6 - Temperature widget", "6 - Temperatur-Widget"),
+    ("7 - Disk widget", "7 - Datenträger-Widget"),
+    ("8 - Battery widget", "8 - Akku-Widget"),
+    ("9 - Basic memory widget", "9 - Einfaches Speicher-Widget"),
+    ("Argument error: {reason}", "Fehler in den Argumenten: {reason}"),
+    ("Backspace        Delete the character behind the cursor", "Backspace        Das Zeichen vor dem Cursor löschen"),
+    ("C                Sort by GPU usage, press again to reverse", "C                Nach GPU-Auslastung sortieren, erneut drücken zum Umkehren"),
+    ("Collapse every branch in tree mode", "Alle Zweige im Baummodus einklappen"),
+    ("Collapse the selected process's parent branch in tree mode", "Übergeordneten Zweig des ausgewählten Prozesses im Baummodus einklappen"),
+    ("Comparison operators:", "Vergleichsoperatoren:"),
+    ("Configuration file error: {reason}", "Fehler in der Konfigurationsdatei: {reason}"),
+    ("Copy the selected process's PID to the clipboard", "PID des ausgewählten Prozesses in die Zwischenablage kopieren"),
+    ("Copy the selected process's command to the clipboard", "Befehl des ausgewählten Prozesses in die Zwischenablage kopieren"),
+    ("Copy the selected table row to the clipboard", "Ausgewählte Tabellenzeile in die Zwischenablage kopieren"),
+    ("Ctrl-a           Skip to the start of the search query", "Ctrl-a           Zum Anfang der Suchanfrage springen"),
+    ("Ctrl-c           Quit", "Ctrl-c           Beenden"),
+    ("Ctrl-e           Skip to the end of the search query", "Ctrl-e           Zum Ende der Suchanfrage springen"),
+    ("Ctrl-h           Delete the character behind the cursor", "Ctrl-h           Das Zeichen vor dem Cursor löschen"),
+    ("Ctrl-u           Clear the current search query", "Ctrl-u           Aktuelle Suchanfrage löschen"),
+    ("Ctrl-u, Ctrl-d   Scroll up/down a table by half a page", "Ctrl-u, Ctrl-d   Tabelle um eine halbe Seite nach oben/unten scrollen"),
+    ("Ctrl-w           Delete a word behind the cursor", "Ctrl-w           Ein Wort vor dem Cursor löschen"),
+    ("Ctrl-z           Suspend bottom, on Unix-like systems", "Ctrl-z           bottom anhalten, auf Unix-artigen Systemen"),
+    ("Delete           Delete the character at the cursor", "Delete           Das Zeichen am Cursor löschen"),
+    ("Double-click     Collapse/expand a branch while in tree mode, by default", "Double-click     Standardmäßig im Baummodus einen Zweig ein-/ausklappen"),
+    ("Down, 'j'        Scroll down in list", "Down, 'j'        In der Liste nach unten scrollen"),
+    ("Down, j          Move down within widget", "Down, j          Im Widget nach unten bewegen"),
+    ("Either scroll or press the number key to go to the corresponding help menu section, or press / to search:", "Scrolle oder drücke die Zahlentaste, um zum jeweiligen Abschnitt der Hilfe zu springen, oder drücke / zum Suchen:"),
+    ("Enter            Sort by current selected column", "Enter            Nach der ausgewählten Spalte sortieren"),
+    ("Error with the config file or the arguments: {reason}", "Fehler in der Konfigurationsdatei oder den Argumenten: {reason}"),
+    ("Esc              Close dialog windows, search, widgets, or exit expanded mode", "Esc              Dialogfenster, Suche oder Widgets schließen, oder den erweiterten Modus verlassen"),
+    ("Esc              Close the search widget (retains the filter)", "Esc              Such-Widget schließen (der Filter bleibt erhalten)"),
+    ("Esc              Close the sort widget", "Esc              Sortier-Widget schließen"),
+    ("Expand every branch in tree mode", "Alle Zweige im Baummodus ausklappen"),
+    ("Export the config in use, including layout changes, to a file", "Verwendete Konfiguration samt Layoutänderungen in eine Datei exportieren"),
+    ("Failed to kill process.", "Der Prozess konnte nicht beendet werden."),
+    ("Freeze/unfreeze updating with new data", "Aktualisierung mit neuen Daten einfrieren/fortsetzen"),
+    ("G                Jump to the last entry", "G                Zum letzten Eintrag springen"),
+    ("Hide the selected widget", "Ausgewähltes Widget ausblenden"),
+    ("Invert current sort", "Aktuelle Sortierung umkehren"),
+    ("Kill 1 process of the user '{name}'?  Press ENTER to confirm.", "1 Prozess des Benutzers '{name}' beenden?  Zum Bestätigen ENTER drücken."),
+    ("Kill 1 process with the name '{name}'?  Press ENTER to confirm.", "1 Prozess mit dem Namen '{name}' beenden?  Zum Bestätigen ENTER drücken."),
+    ("Kill process '{name}' with PID {pid}?  Press ENTER to confirm.", "Prozess '{name}' mit PID {pid} beenden?  Zum Bestätigen ENTER drücken."),
+    ("Kill the selected process", "Ausgewählten Prozess beenden"),
+    ("Kill {count} processes of the user '{name}'?  Press ENTER to confirm.", "{count} Prozesse des Benutzers '{name}' beenden?  Zum Bestätigen ENTER drücken."),
+    ("Kill {count} processes with the name '{name}'?  Press ENTER to confirm.", "{count} Prozesse mit dem Namen '{name}' beenden?  Zum Bestätigen ENTER drücken."),
+    ("Left             Go to previous battery", "Left             Zum vorherigen Akku wechseln"),
+    ("Left, Alt-h      Move cursor left", "Left, Alt-h      Cursor nach links bewegen"),
+    ("Left, h          Move left within widget", "Left, h          Im Widget nach links bewegen"),
+    ("Log more detail, going back to only errors after tracing", "Ausführlicher protokollieren, nach Trace wieder nur Fehler"),
+    ("Logical operators:", "Logische Operatoren:"),
+    ("M                Sort by GPU memory usage, press again to reverse", "M                Nach GPU-Speichernutzung sortieren, erneut drücken zum Umkehren"),
+    ("Mouse click      Selects the clicked widget, table entry, dialog option, or tab", "Mouse click      Wählt das angeklickte Widget, den Tabelleneintrag, die Dialogoption oder den Tab aus"),
+    ("Mouse scroll     Scroll through sort widget", "Mouse scroll     Durch das Sortier-Widget scrollen"),
+    ("Mouse scroll     Scroll through the tables or zoom in/out of charts by scrolling up/down", "Mouse scroll     Durch Tabellen scrollen oder Diagramme durch Scrollen nach oben/unten vergrößern/verkleinern"),
+    ("Mouse scroll     Scrolling over an CPU core/average shows only that entry on the chart", "Mouse scroll     Scrollen über einem CPU-Kern/Durchschnitt zeigt nur diesen Eintrag im Diagramm"),
+    ("No", "Nein"),
+    ("No batteries were found", "Keine Akkus gefunden"),
+    ("No data", "Keine Daten"),
+    ("No data found for this battery", "Keine Daten für diesen Akku gefunden"),
+    ("No disks match the filters; see [disk.name_filter] and [disk.mount_filter] in the config", "Keine Datenträger passen zu den Filtern; siehe [disk.name_filter] und [disk.mount_filter] in der Konfiguration"),
+    ("No disks were found", "Keine Datenträger gefunden"),
+    ("No processes match the search", "Keine Prozesse passen zur Suche"),
+    ("No processes were found", "Keine Prozesse gefunden"),
+    ("No sensors match the filter; see [temperature.sensor_filter] in the config", "Keine Sensoren passen zum Filter; siehe [temperature.sensor_filter] in der Konfiguration"),
+    ("No temperature sensors were found", "Keine Temperatursensoren gefunden"),
+    ("Open help menu", "Hilfemenü öffnen"),
+    ("Open process search widget, or search all widgets elsewhere", "Prozesssuche-Widget öffnen, oder anderswo alle Widgets durchsuchen"),
+    ("Open process sort widget", "Prozesssortierung-Widget öffnen"),
+    ("P                Toggle between showing the full command or just the process name", "P                Zwischen vollständigem Befehl und Prozessname wechseln"),
+    ("PgUp, PgDown     Scroll up/down a table by a page", "PgUp, PgDown     Tabelle um eine Seite nach oben/unten scrollen"),
+    ("Please press ENTER or ESC to close this dialog.", "Drücke ENTER oder ESC, um diesen Dialog zu schließen."),
+    ("Quit", "Beenden"),
+    ("Reset display and any collected data", "Anzeige und gesammelte Daten zurücksetzen"),
+    ("Reset zoom", "Zoom zurücksetzen"),
+    ("Right            Go to next battery", "Right            Zum nächsten Akku wechseln"),
+    ("Right, Alt-l     Move cursor right", "Right, Alt-l     Cursor nach rechts bewegen"),
+    ("Right, l         Move right within widget", "Right, l         Im Widget nach rechts bewegen"),
+    ("Shift-Down,      Move widget selection down", "Shift-Down,      Widget-Auswahl nach unten verschieben"),
+    ("Shift-Left,      Move widget selection left", "Shift-Left,      Widget-Auswahl nach links verschieben"),
+    ("Shift-Right,     Move widget selection right", "Shift-Right,     Widget-Auswahl nach rechts verschieben"),
+    ("Shift-Up,        Move widget selection up", "Shift-Up,        Widget-Auswahl nach oben verschieben"),
+    ("Show all hidden widgets", "Alle ausgeblendeten Widgets einblenden"),
+    ("Show the config in use and data source diagnostics", "Verwendete Konfiguration und Diagnose der Datenquellen anzeigen"),
+    ("Supported search types:", "Unterstützte Suchtypen:"),
+    ("Supported units:", "Unterstützte Einheiten:"),
+    ("Switch to the next layout page", "Zur nächsten Layoutseite wechseln"),
+    ("Switch to the next profile in the config", "Zum nächsten Profil der Konfiguration wechseln"),
+    ("Switch to the previous layout page", "Zur vorherigen Layoutseite wechseln"),
+    ("Tab              Group/un-group processes with the same name", "Tab              Prozesse mit gleichem Namen gruppieren/nicht gruppieren"),
+    ("Terminal too small, enlarge it to see all widgets", "Terminal zu klein, vergrößere es, um alle Widgets zu sehen"),
+    ("Toggle between values and percentages for memory usage", "Zwischen Werten und Prozenten für die Speichernutzung wechseln"),
+    ("Toggle expanding the currently selected widget", "Ausgewähltes Widget erweitern/verkleinern"),
+    ("Toggle grouping processes by user, then by name", "Gruppierung der Prozesse nach Benutzer, dann nach Name umschalten"),
+    ("Toggle matching case while searching", "Beachtung der Groß-/Kleinschreibung bei der Suche umschalten"),
+    ("Toggle matching the entire word while searching", "Suche nach ganzen Wörtern umschalten"),
+    ("Toggle showing branch totals on parents in tree mode", "Zweigsummen an übergeordneten Prozessen im Baummodus umschalten"),
+    ("Toggle the debug overlay", "Debug-Overlay umschalten"),
+    ("Toggle the inspection cursor on a chart", "Den Untersuchungscursor in einem Diagramm umschalten"),
+    ("Toggle tree mode", "Baummodus umschalten"),
+    ("Toggle using regex while searching", "Verwendung von regulären Ausdrücken bei der Suche umschalten"),
+    ("Up, 'k'          Scroll up in list", "Up, 'k'          In der Liste nach oben scrollen"),
+    ("Up, k            Move up within widget", "Up, k            Im Widget nach oben bewegen"),
+    ("Waiting for data...", "Warte auf Daten..."),
+    ("Yes", "Ja"),
+    ("Zoom in on chart, or collapse/expand a branch in tree mode", "In das Diagramm hineinzoomen, oder im Baummodus einen Zweig ein-/ausklappen"),
+    ("Zoom out of chart, or collapse/expand a branch in tree mode", "Aus dem Diagramm herauszoomen, oder im Baummodus einen Zweig ein-/ausklappen"),
+    ("[, ]             Step back/forward through graph and table history while frozen", "[, ]             Im eingefrorenen Zustand durch den Verlauf der Graphen und Tabellen zurück/vor gehen"),
+    ("c                Sort by CPU usage, press again to reverse", "c                Nach CPU-Auslastung sortieren, erneut drücken zum Umkehren"),
+    ("click on header  Sorts the entries by that column, click again to invert the sort", "click on header  Sortiert die Einträge nach dieser Spalte, erneut klicken zum Umkehren"),
+    ("dd               Kill the selected process", "dd               Ausgewählten Prozess beenden"),
+    ("gg               Jump to the first entry", "gg               Zum ersten Eintrag springen"),
+    ("m                Sort by memory usage, press again to reverse", "m                Nach Speichernutzung sortieren, erneut drücken zum Umkehren"),
+    ("n                Sort by process name, press again to reverse", "n                Nach Prozessname sortieren, erneut drücken zum Umkehren"),
+    ("p                Sort by PID name, press again to reverse", "p                Nach PID sortieren, erneut drücken zum Umkehren"),
+    ("s                Open process sort widget", "s                Prozesssortierung-Widget öffnen"),
+    ("t                Toggle tree mode", "t                Baummodus umschalten"),
+    ("{, }             Step back/forward through history by the zoom time delta while frozen", "{, }             Im eingefrorenen Zustand um die Zoom-Zeitspanne durch den Verlauf zurück/vor gehen"),
+    ("{issue}; press {key} for details", "{issue}; {key} drücken für Details"),
+];
@@ -0,0 +1,156 @@
+//! Spanish translations.
+
+/// Pairs of English text and its translation, sorted by the English text.
+pub(super) const CATALOG: &[(&str, &str)] = &[
+    (" Battery ", " Batería "),
+    (" Config and Diagnostics ", " Configuración y diagnóstico "),
+    (" Confirm Kill Process ", " Confirmar terminar proceso "),
+    (" Disks ", " Discos "),
+    (" Esc to close ", " Esc para cerrar "),
+    (" Esc to go back ", " Esc para volver "),
+    (" Help ", " Ayuda "),
+    (" Memory ", " Memoria "),
+    (" Network ", " Red "),
+    (" Process Details ", " Detalles del proceso "),
+    (" Processes ", " Procesos "),
+    (" Search ", " Buscar "),
+    (" Search: {query}{cursor} ", " Buscar: {query}{cursor} "),
+    (" Temperatures ", " Temperaturas "),
+    (" {count} matches ", " {count} resultados "),
+    ("'--{value}' was set with an invalid value, please update your arguments.", "'--{value}' tiene un valor no válido, corrige tus argumentos."),
+    ("'d'              Sort by disk name, press again to reverse", "'d'              Ordenar por nombre del disco, pulsa de nuevo para invertir"),
+    ("'m'              Sort by disk mount, press again to reverse", "'m'              Ordenar por punto de montaje, pulsa de nuevo para invertir"),
+    ("'n'              Sort by disk free space, press again to reverse", "'n'              Ordenar por espacio libre, pulsa de nuevo para invertir"),
+    ("'p'              Sort by disk usage percentage, press again to reverse", "'p'              Ordenar por porcentaje de uso, pulsa de nuevo para invertir"),
+    ("'r'              Sort by disk read activity, press again to reverse", "'r'              Ordenar por actividad de lectura, pulsa de nuevo para invertir"),
+    ("'s'              Sort by sensor name, press again to reverse", "'s'              Ordenar por nombre del sensor, pulsa de nuevo para invertir"),
+    ("'t'              Sort by temperature, press again to reverse", "'t'              Ordenar por temperatura, pulsa de nuevo para invertir"),
+    ("'t'              Sort by total disk space, press again to reverse", "'t'              Ordenar por espacio total, pulsa de nuevo para invertir"),
+    ("'u'              Sort by disk usage, press again to reverse", "'u'              Ordenar por espacio usado, pulsa de nuevo para invertir"),
+    ("'w'              Sort by disk write activity, press again to reverse", "'w'              Ordenar por actividad de escritura, pulsa de nuevo para invertir"),
+    ("'{value}' was set with an invalid value, please update it in your config file.", "'{value}' tiene un valor no válido, corrígelo en tu archivo de configuración."),
+    ("(unbound)", "(sin asignar)"),
+    ("({current} of {total}) ", "({current} de {total}) "),
+    ("2 - CPU widget", "2 - Widget de CPU"),
+    ("3 - Process widget", "3 - Widget de procesos"),
+    ("4 - Process search widget", "4 - Widget de búsqueda de procesos"),
+    ("5 - Process sort widget", "5 - Widget de orden de procesos"),
+    ("5 - Sort widget", "5 - Widget de orden"),
+    ("6 - Temperature widget", "6 - Widget de temperatura"),
+    ("7 - Disk widget", "7 - Widget de discos"),
+    ("8 - Battery widget", "8 - Widget de batería"),
+    ("9 - Basic memory widget", "9 - Widget de memoria básico"),
+    ("Argument error: {reason}", "Error en los argumentos: {reason}"),
+    ("Backspace        Delete the character behind the cursor", "Backspace        Borrar el carácter antes del cursor"),
+    ("C                Sort by GPU usage, press again to reverse", "C                Ordenar por uso de GPU, pulsa de nuevo para invertir"),
+    ("Collapse every branch in tree mode", "Contraer todas las ramas en el modo árbol"),
+    ("Collapse the selected process's parent branch in tree mode", "Contraer la rama padre del proceso seleccionado en el modo árbol"),
+    ("Comparison operators:", "Operadores de comparación:"),
+    ("Configuration file error: {reason}", "Error en el archivo de configuración: {reason}"),
+    ("Copy the selected process's PID to the clipboard", "Copiar el PID del proceso seleccionado al portapapeles"),
+    ("Copy the selected process's command to the clipboard", "Copiar el comando del proceso seleccionado al portapapeles"),
+    ("Copy the selected table row to the clipboard", "Copiar la fila seleccionada de la tabla al portapapeles"),
+    ("Ctrl-a           Skip to the start of the search query", "Ctrl-a           Ir al inicio de la búsqueda"),
+    ("Ctrl-c           Quit", "Ctrl-c           Salir"),
+    ("Ctrl-e           Skip to the end of the search query", "Ctrl-e           Ir al final de la búsqueda"),
+    ("Ctrl-h           Delete the character behind the cursor", "Ctrl-h           Borrar el carácter antes del cursor"),
+    ("Ctrl-u           Clear the current search query", "Ctrl-u           Borrar la búsqueda actual"),
+    ("Ctrl-u, Ctrl-d   Scroll up/down a table by half a page", "Ctrl-u, Ctrl-d   Desplazar una tabla media página hacia arriba/abajo"),
+    ("Ctrl-w           Delete a word behind the cursor", "Ctrl-w           Borrar una palabra antes del cursor"),
+    ("Ctrl-z           Suspend bottom, on Unix-like systems", "Ctrl-z           Suspender bottom, en sistemas tipo Unix"),
+    ("Delete           Delete the character at the cursor", "Delete           Borrar el carácter en el cursor"),
+    ("Double-click     Collapse/expand a branch while in tree mode, by default", "Double-click     Contraer/expandir una rama en modo árbol, por defecto"),
+    ("Down, 'j'        Scroll down in list", "Down, 'j'        Desplazarse hacia abajo en la lista"),
+    ("Down, j          Move down within widget", "Down, j          Moverse hacia abajo dentro del widget"),
+    ("Either scroll or press the number key to go to the corresponding help menu section, or press / to search:", "Desplázate o pulsa la tecla numérica para ir a la sección de ayuda correspondiente, o pulsa / para buscar:"),
+    ("Enter            Sort by current selected column", "Enter            Ordenar por la columna seleccionada"),
+    ("Error with the config file or the arguments: {reason}", "Error en el archivo de configuración o en los argumentos: {reason}"),
+    ("Esc              Close dialog windows, search, widgets, or exit expanded mode", "Esc              Cerrar diálogos, la búsqueda o widgets, o salir del modo expandido"),
+    ("Esc              Close the search widget (retains the filter)", "Esc              Cerrar el widget de búsqueda (mantiene el filtro)"),
+    ("Esc              Close the sort widget", "Esc              Cerrar el widget de orden"),
+    ("Expand every branch in tree mode", "Expandir todas las ramas en el modo árbol"),
+    ("Export the config in use, including layout changes, to a file", "Exportar la configuración en uso, incluidos los cambios de diseño, a un archivo"),
+    ("Failed to kill process.", "No se pudo terminar el proceso."),
+    ("Freeze/unfreeze updating with new data", "Congelar/descongelar la actualización con datos nuevos"),
+    ("G                Jump to the last entry", "G                Ir a la última entrada"),
+    ("Hide the selected widget", "Ocultar el widget seleccionado"),
+    ("Invert current sort", "Invertir el orden actual"),
+    ("Kill 1 process of the user '{name}'?  Press ENTER to confirm.", "¿Terminar 1 proceso del usuario '{name}'?  Pulsa ENTER para confirmar."),
+    ("Kill 1 process with the name '{name}'?  Press ENTER to confirm.", "¿Terminar 1 proceso con el nombre '{name}'?  Pulsa ENTER para confirmar."),
+    ("Kill process '{name}' with PID {pid}?  Press ENTER to confirm.", "¿Terminar el proceso '{name}' con PID {pid}?  Pulsa ENTER para confirmar."),
+    ("Kill the selected process", "Terminar el proceso seleccionado"),
+    ("Kill {count} processes of the user '{name}'?  Press ENTER to confirm.", "¿Terminar {count} procesos del usuario '{name}'?  Pulsa ENTER para confirmar."),
+    ("Kill {count} processes with the name '{name}'?  Press ENTER to confirm.", "¿Terminar {count} procesos con el nombre '{name}'?  Pulsa ENTER para confirmar."),
+    ("Left             Go to previous battery", "Left             Ir a la batería anterior"),
+    ("Left, Alt-h      Move cursor left", "Left, Alt-h      Mover el cursor a la izquierda"),
+    ("Left, h          Move left within widget", "Left, h          Moverse a la izquierda dentro del widget"),
+    ("Log more detail, going back to only errors after tracing", "Registrar más detalle, volviendo a solo errores después de trace"),
+    ("Logical operators:", "Operadores lógicos:"),
+    ("M                Sort by GPU memory usage, press again to reverse", "M                Ordenar por uso de memoria de GPU, pulsa de nuevo para invertir"),
+    ("Mouse click      Selects the clicked widget, table entry, dialog option, or tab", "Mouse click      Selecciona el widget, la entrada de tabla, la opción de diálogo o la pestaña en la que se hace clic"),
+    ("Mouse scroll     Scroll through sort widget", "Mouse scroll     Desplazarse por el widget de orden"),
+    ("Mouse scroll     Scroll through the tables or zoom in/out of charts by scrolling up/down", "Mouse scroll     Desplazarse por las tablas o acercar/alejar los gráficos al desplazar hacia arriba/abajo"),
+    ("Mouse scroll     Scrolling over an CPU core/average shows only that entry on the chart", "Mouse scroll     Desplazarse sobre un núcleo/promedio de CPU muestra solo esa entrada en el gráfico"),
+    ("No batteries were found", "No se encontraron baterías"),
+    ("No data", "Sin datos"),
+    ("No data found for this battery", "No se encontraron datos para esta batería"),
+    ("No disks match the filters; see [disk.name_filter] and [disk.mount_filter] in the config", "Ningún disco coincide con los filtros; consulte [disk.name_filter] y [disk.mount_filter] en la configuración"),
+    ("No disks were found", "No se encontraron discos"),
+    ("No processes match the search", "Ningún proceso coincide con la búsqueda"),
+    ("No processes were found", "No se encontraron procesos"),
+    ("No sensors match the filter; see [temperature.sensor_filter] in the config", "Ningún sensor coincide con el filtro; consulte [temperature.sensor_filter] en la configuración"),
+    ("No temperature sensors were found", "No se encontraron sensores de temperatura"),
+    ("Open help menu", "Abrir el menú de ayuda"),
+    ("Open process search widget, or search all widgets elsewhere", "Abrir el widget de búsqueda de procesos, o buscar en todos los widgets en otros lugares"),
+    ("Open process sort widget", "Abrir el widget de orden de procesos"),
+    ("P                Toggle between showing the full command or just the process name", "P                Alternar entre mostrar el comando completo o solo el nombre del proceso"),
+    ("PgUp, PgDown     Scroll up/down a table by a page", "PgUp, PgDown     Desplazar una tabla una página hacia arriba/abajo"),
+    ("Please press ENTER or ESC to close this dialog.", "Pulsa ENTER o ESC para cerrar este diálogo."),
+    ("Quit", "Salir"),
+    ("Reset display and any collected data", "Reiniciar la vista y los datos recopilados"),
+    ("Reset zoom", "Restablecer el zoom"),
+    ("Right            Go to next battery", "Right            Ir a la siguiente batería"),
+    ("Right, Alt-l     Move cursor right", "Right, Alt-l     Mover el cursor a la derecha"),
+    ("Right, l         Move right within widget", "Right, l         Moverse a la derecha dentro del widget"),
+    ("Shift-Down,      Move widget selection down", "Shift-Down,      Mover la selección de widget hacia abajo"),
+    ("Shift-Left,      Move widget selection left", "Shift-Left,      Mover la selección de widget a la izquierda"),
+    ("Shift-Right,     Move widget selection right", "Shift-Right,     Mover la selección de widget a la derecha"),
+    ("Shift-Up,        Move widget selection up", "Shift-Up,        Mover la selección de widget hacia arriba"),
+    ("Show all hidden widgets", "Mostrar todos los widgets ocultos"),
+    ("Show the config in use and data source diagnostics", "Mostrar la configuración en uso y el diagnóstico de las fuentes de datos"),
+    ("Supported search types:", "Tipos de búsqueda admitidos:"),
+    ("Supported units:", "Unidades admitidas:"),
+    ("Switch to the next layout page", "Cambiar a la siguiente página del diseño"),
+    ("Switch to the next profile in the config", "Cambiar al siguiente perfil de la configuración"),
+    ("Switch to the previous layout page", "Cambiar a la página anterior del diseño"),
+    ("Tab              Group/un-group processes with the same name", "Tab              Agrupar/desagrupar procesos con el mismo nombre"),
+    ("Terminal too small, enlarge it to see all widgets", "Terminal demasiado pequeña, amplíala para ver todos los widgets"),
+    ("Toggle between values and percentages for memory usage", "Alternar entre valores y porcentajes para el uso de memoria"),
+    ("Toggle expanding the currently selected widget", "Alternar la expansión del widget seleccionado"),
+    ("Toggle grouping processes by user, then by name", "Alternar agrupar los procesos por usuario y luego por nombre"),
+    ("Toggle matching case while searching", "Alternar distinguir mayúsculas y minúsculas al buscar"),
+    ("Toggle matching the entire word while searching", "Alternar buscar la palabra completa"),
+    ("Toggle showing branch totals on parents in tree mode", "Alternar mostrar los totales de cada rama en los procesos padre en el modo árbol"),
+    ("Toggle the debug overlay", "Alternar la superposición de depuración"),
+    ("Toggle the inspection cursor on a chart", "Alternar el cursor de inspección en un gráfico"),
+    ("Toggle tree mode", "Alternar el modo árbol"),
+    ("Toggle using regex while searching", "Alternar el uso de expresiones regulares al buscar"),
+    ("Up, 'k'          Scroll up in list", "Up, 'k'          Desplazarse hacia arriba en la lista"),
+    ("Up, k            Move up within widget", "Up, k            Moverse hacia arriba dentro del widget"),
+    ("Waiting for data...", "Esperando datos..."),
+    ("Yes", "Sí"),
+    ("Zoom in on chart, or collapse/expand a branch in tree mode", "Acercar el gráfico, o contraer/expandir una rama en modo árbol"),
+    ("Zoom out of chart, or collapse/expand a branch in tree mode", "Alejar el gráfico, o contraer/expandir una rama en modo árbol"),
+    ("[, ]             Step back/forward through graph and table history while frozen", "[, ]             Retroceder/avanzar por el historial de los gráficos y las tablas mientras está congelado"),
+    ("c                Sort by CPU usage, press again to reverse", "c                Ordenar por uso de CPU, pulsa de nuevo para invertir"),
+    ("click on header  Sorts the entries by that column, click again to invert the sort", "click on header  Ordena las entradas por esa columna, haz clic de nuevo para invertir el orden"),
+    ("dd               Kill the selected process", "dd               Terminar el proceso seleccionado"),
+    ("gg               Jump to the first entry", "gg               Ir a la primera entrada"),
+    ("m                Sort by memory usage, press again to reverse", "m                Ordenar por uso de memoria, pulsa de nuevo para invertir"),
+    ("n                Sort by process name, press again to reverse", "n                Ordenar por nombre de proceso, pulsa de nuevo para invertir"),
+    ("p                Sort by PID name, press again to reverse", "p                Ordenar por PID, pulsa de nuevo para invertir"),
+    ("s                Open process sort widget", "s                Abrir el widget de orden de procesos"),
+    ("t                Toggle tree mode", "t                Alternar el modo árbol"),
+    ("{, }             Step back/forward through history by the zoom time delta while frozen", "{, }             Retroceder/avanzar por el historial según el intervalo de zoom mientras está congelado"),
+    ("{issue}; press {key} for details", "{issue}; pulse {key} para ver detalles"),
+];
@@ -3,6 +3,7 @@
 // TODO: Break this apart or do something a bit smarter.
 
 pub mod args;
+mod check_config;
 pub mod config;
 mod error;
 
@@ -10,12 +11,14 @@ use std::{
     convert::TryInto,
     fs,
     io::Write,
+    net::SocketAddr,
     path::{Path, PathBuf},
     str::FromStr,
     time::{Duration, Instant},
 };
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+pub(crate) use check_config::check_config;
 use config::style::Styles;
 pub use config::Config;
 pub(crate) use error::{OptionError, OptionResult};
@@ -27,14 +30,32 @@ use starship_battery::Manager;
 
 use self::{
     args::BottomArgs,
-    config::{layout::Row, IgnoreList, StringOrNum},
+    config::{
+        basic::BasicTable,
+        layout::{LayoutPreset, Row},
+        meter_header::{Meter, MeterHeader},
+        mouse::MouseSettings,
+        network::NetworkYAxis,
+        status_bar::{StatusBar, StatusBarField},
+        IgnoreList, StringOrNum,
+    },
 };
 use crate::{
-    app::{filter::Filter, layout_manager::*, *},
-    canvas::components::time_chart::LegendPosition,
+    app::{layout_manager::*, *},
+    canvas::{
+        components::time_chart::{GraphMarker, LegendPosition},
+        terminal::{ColourDepth, TerminalFeatures},
+    },
     constants::*,
-    data_collection::temperature::TemperatureType,
-    utils::data_units::DataUnit,
+    data_collection::{
+        filter::Filter,
+        synthetic::StressConfig,
+        temperature::{ExtraSensors, TemperatureType},
+        DataFilters, UsedWidgets,
+    },
+    event::keybinds::KeyBindings,
+    i18n::Locale,
+    utils::{data_units::DataUnit, time_format::TimeFormat},
     widgets::*,
 };
 
@@ -134,9 +155,24 @@ fn create_config_at_path(path: &Path) -> anyhow::Result<Config> {
     Ok(Config::default())
 }
 
+/// Where the config in use came from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// The config was read from the file at this path.
+    File(PathBuf),
+    /// There was no config file, so one with the default settings was created
+    /// at this path.
+    Created(PathBuf),
+    /// No config file could be read or created, so the default settings are
+    /// used.
+    #[default]
+    Defaults,
+}
+
 /// Get the config at `config_path`. If there is no config file at the specified
 /// path, it will try to create a new file with the default settings, and return
-/// the default config.
+/// the default config. Where the config came from is also returned, so it can
+/// be shown in the app. If `profile` is given, that profile is applied.
 ///
 /// We're going to use the following behaviour on when we'll return an error rather
 /// than just "silently" continuing on:
@@ -144,16 +180,26 @@ fn create_config_at_path(path: &Path) -> anyhow::Result<Config> {
 /// - If the user does NOT pass in a path explicitly, then just show a warning,
 ///   but continue. This is in case they do not want to write a default config file at
 ///   the XDG locations, for example.
-pub(crate) fn get_or_create_config(config_path: Option<&Path>) -> anyhow::Result<Config> {
+pub(crate) fn get_or_create_config(
+    config_path: Option<&Path>, profile: Option<&str>,
+) -> anyhow::Result<(Config, ConfigSource)> {
     let adjusted_config_path = get_config_path(config_path);
 
     match &adjusted_config_path {
         Some(path) => {
             if let Ok(config_string) = fs::read_to_string(path) {
-                Ok(toml_edit::de::from_str(&config_string)?)
+                let config = Config::parse(&config_string, path, profile)?;
+                Ok((config, ConfigSource::File(path.clone())))
             } else {
+                if let Some(profile) = profile {
+                    bail!(
+                        "there is no profile named '{profile}', as there is no config file at '{}'.",
+                        path.display()
+                    );
+                }
+
                 match create_config_at_path(path) {
-                    Ok(cfg) => Ok(cfg),
+                    Ok(cfg) => Ok((cfg, ConfigSource::Created(path.clone()))),
                     Err(err) => {
                         if config_path.is_some() {
                             Err(err.context(format!(
@@ -171,7 +217,7 @@ pub(crate) fn get_or_create_config(config_path: Option<&Path>) -> anyhow::Result
                                 path.display()
                             );
 
-                            Ok(Config::default())
+                            Ok((Config::default(), ConfigSource::Defaults))
                         }
                     }
                 }
@@ -184,19 +230,48 @@ pub(crate) fn get_or_create_config(config_path: Option<&Path>) -> anyhow::Result
             // TODO: For now, just print a message to stderr indicating this. In the future,
             // probably show in-app (too).
 
+            if let Some(profile) = profile {
+                bail!("there is no profile named '{profile}', as there is no config file.");
+            }
+
             eprintln!(
                 "Note: bottom couldn't find a location to create or read a config file, so \
                 the application has fallen back to the default configuration. \
                 This could be for a variety of reasons, such as issues with file permissions."
             );
 
-            Ok(Config::default())
+            Ok((Config::default(), ConfigSource::Defaults))
         }
     }
 }
 
+/// Reads the config in use from `source` again, applying the profile named
+/// `profile` if given. This is used to switch profiles while running.
+pub(crate) fn read_config(source: &ConfigSource, profile: Option<&str>) -> anyhow::Result<Config> {
+    match source {
+        ConfigSource::File(path) | ConfigSource::Created(path) => {
+            let text = fs::read_to_string(path).with_context(|| {
+                format!(
+                    "bottom couldn't read the config file at '{}'.",
+                    path.display()
+                )
+            })?;
+
+            Config::parse(&text, path, profile)
+        }
+        ConfigSource::Defaults => match profile {
+            Some(profile) => {
+                bail!("there is no profile named '{profile}', as there is no config file.")
+            }
+            None => Ok(Config::default()),
+        },
+    }
+}
+
 /// Initialize the app.
-pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomLayout, Styles)> {
+pub(crate) fn init_app(
+    args: BottomArgs, config: Config,
+) -> Result<(App, Vec<BottomLayout>, Styles, HashMap<u64, Styles>)> {
     use BottomWidgetType::*;
 
     // Since everything takes a reference, but we want to take ownership here to
@@ -206,11 +281,12 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
 
     let styling = Styles::new(args, config)?;
 
-    let (widget_layout, default_widget_id, default_widget_type_option) =
-        get_widget_layout(args, config)
-            .context("Found an issue while trying to build the widget layout.")?;
+    let (layout_pages, default_widget_type_option) = get_widget_layout(args, config)
+        .context("Found an issue while trying to build the widget layout.")?;
 
+    let update_rate = get_update_rate(args, config)?;
     let retention_ms = get_retention(args, config)?;
+    let long_term_retention_ms = get_long_term_retention(args, config, retention_ms)?;
     let autohide_time = is_flag_enabled!(autohide_time, args.general, config);
     let default_time_value = get_default_time_value(args, config, retention_ms)?;
 
@@ -223,6 +299,7 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
     let is_match_whole_word = is_flag_enabled!(whole_word, args.process, config);
     let is_use_regex = is_flag_enabled!(regex, args.process, config);
     let is_default_tree = is_flag_enabled!(tree, args.process, config);
+    let show_tree_totals = is_flag_enabled!(tree_totals, args.process, config);
     let is_default_command = is_flag_enabled!(process_command, args.process, config);
     let is_advanced_kill = !(is_flag_enabled!(disable_advanced_kill, args.process, config));
     let process_memory_as_value = is_flag_enabled!(process_memory_as_value, args.process, config);
@@ -231,6 +308,7 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
     let default_cpu_selection = get_default_cpu_selection(args, config);
 
     let mut widget_map = HashMap::new();
+    let mut widget_styles: HashMap<u64, Styles> = HashMap::new();
     let mut cpu_state_map: HashMap<u64, CpuWidgetState> = HashMap::new();
     let mut mem_state_map: HashMap<u64, MemWidgetState> = HashMap::new();
     let mut net_state_map: HashMap<u64, NetWidgetState> = HashMap::new();
@@ -245,13 +323,24 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         None
     };
 
-    let mut initial_widget_id: u64 = default_widget_id;
+    let mut initial_widget_id: u64 = layout_pages[0].default_widget_id;
     let mut initial_widget_type = Proc;
-    let is_custom_layout = config.row.is_some();
+    let is_custom_layout =
+        config.row.is_some() || config.page.is_some() || get_layout_preset(args, config)?.is_some();
     let mut used_widget_set = HashSet::new();
 
     let network_unit_type = get_network_unit_type(args, config);
-    let network_scale_type = get_network_scale_type(args, config);
+    let network_y_axis = config
+        .network
+        .as_ref()
+        .and_then(|network| network.y_axis)
+        .unwrap_or_default();
+    // Percentages of the link capacity only make sense on a linear scale.
+    let network_scale_type = if network_y_axis == NetworkYAxis::Capacity {
+        AxisScaling::Linear
+    } else {
+        get_network_scale_type(args, config)
+    };
     let network_use_binary_prefix =
         is_flag_enabled!(network_use_binary_prefix, args.network, config);
 
@@ -271,13 +360,17 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
     let network_legend_position = get_network_legend_position(args, config)?;
     let memory_legend_position = get_memory_legend_position(args, config)?;
 
+    let terminal = get_terminal_features(args, config, TerminalFeatures::detect())?;
+
     // TODO: Can probably just reuse the options struct.
     let app_config_fields = AppConfigFields {
-        update_rate: get_update_rate(args, config)?,
+        update_rate,
         temperature_type: get_temperature(args, config)
             .context("Update 'temperature_type' in your config file.")?,
+        extra_sensors: get_extra_sensors(config),
         show_average_cpu: get_show_average_cpu(args, config),
-        use_dot: is_flag_enabled!(dot_marker, args.general, config),
+        graph_marker: get_graph_marker(args, config, terminal.unicode)?,
+        graph_gradients: is_flag_enabled!(graph_gradients, args.general, config),
         cpu_left_legend: is_flag_enabled!(cpu_left_legend, args.cpu, config),
         use_current_cpu_total: is_flag_enabled!(current_usage, args.process, config),
         unnormalized_cpu: is_flag_enabled!(unnormalized_cpu, args.process, config),
@@ -288,7 +381,13 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         autohide_time,
         use_old_network_legend: is_flag_enabled!(use_old_network_legend, args.network, config),
         table_gap: u16::from(!(is_flag_enabled!(hide_table_gap, args.general, config))),
-        disable_click: is_flag_enabled!(disable_click, args.general, config),
+        disable_click: args.general.disable_click
+            || config
+                .flags
+                .as_ref()
+                .and_then(|flags| flags.disable_click)
+                .unwrap_or(!terminal.mouse),
+        double_click_action: get_double_click_action(args, config)?,
         enable_gpu: get_enable_gpu(args, config),
         enable_cache_memory: get_enable_cache_memory(args, config),
         show_table_scroll_position: is_flag_enabled!(
@@ -296,14 +395,63 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
             args.general,
             config
         ),
+        show_table_scrollbar: is_flag_enabled!(show_table_scrollbar, args.general, config),
+        table_stripes: is_flag_enabled!(table_stripes, args.general, config),
+        table_row_separators: is_flag_enabled!(table_row_separators, args.general, config),
         is_advanced_kill,
         memory_legend_position,
         network_legend_position,
         network_scale_type,
         network_unit_type,
         network_use_binary_prefix,
+        network_y_axis,
+        network_namespaces: config
+            .network
+            .as_ref()
+            .and_then(|network| network.namespaces)
+            .unwrap_or(false),
         retention_ms,
+        long_term_retention_ms,
         dedicated_average_row: get_dedicated_avg_row(config),
+        http_address: get_http_address(args, config)?,
+        dump_path: args.general.dump_path.clone().or_else(|| {
+            config
+                .flags
+                .as_ref()
+                .and_then(|flags| flags.dump_path.as_ref())
+                .map(PathBuf::from)
+        }),
+        idle_rate: get_idle_rate(args, config, update_rate)?,
+        max_history_memory: get_limit(
+            args.general.max_history_memory,
+            config
+                .flags
+                .as_ref()
+                .and_then(|flags| flags.max_history_memory),
+            "max_history_memory",
+        )?
+        .map(|mib| mib.saturating_mul(1024 * 1024)),
+        max_processes: get_limit(
+            args.process.max_processes,
+            config.flags.as_ref().and_then(|flags| flags.max_processes),
+            "max_processes",
+        )?
+        .map(|count| count as usize),
+        pause_on_blur: is_flag_enabled!(pause_on_blur, args.general, config),
+        mobile: is_flag_enabled!(mobile, args.general, config),
+        alt_screen: !is_flag_enabled!(no_alt_screen, args.general, config),
+        stress: args.general.stress.map(|num_processes| StressConfig {
+            num_processes,
+            num_ticks: args.general.stress_ticks,
+        }),
+        save_session: is_flag_enabled!(save_session, args.general, config),
+        key_bindings: get_key_bindings(config)?,
+        status_bar: get_status_bar(args, config),
+        meter_header: get_meter_header(args, config),
+        mouse: get_mouse(config)?,
+        terminal,
+        locale: get_locale(args, config)?,
+        time_format: get_time_format(args, config)?,
     };
 
     let table_config = ProcTableConfig {
@@ -312,9 +460,11 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         is_use_regex,
         show_memory_as_values: process_memory_as_value,
         is_command: is_default_command,
+        show_tree_totals,
+        default_sort: get_default_sort(args, config)?,
     };
 
-    for row in &widget_layout.rows {
+    for row in layout_pages.iter().flat_map(|page| &page.layout.rows) {
         for col in &row.children {
             for col_row in &col.children {
                 for widget in &col_row.children {
@@ -352,72 +502,105 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
 
                     used_widget_set.insert(widget.widget_type.clone());
 
+                    // Custom titles are padded like the default ones.
+                    let title = widget.title.as_ref().map(|title| format!(" {title} "));
+
+                    if let Some(widget_config) = &widget.styles {
+                        widget_styles.insert(widget.widget_id, styling.for_widget(widget_config)?);
+                    }
+                    let palette = widget_styles.get(&widget.widget_id).unwrap_or(&styling);
+
                     match widget.widget_type {
                         Cpu => {
-                            cpu_state_map.insert(
-                                widget.widget_id,
-                                CpuWidgetState::new(
-                                    &app_config_fields,
-                                    default_cpu_selection,
-                                    default_time_value,
-                                    autohide_timer,
-                                    &styling,
-                                ),
+                            let mut state = CpuWidgetState::new(
+                                &app_config_fields,
+                                default_cpu_selection,
+                                default_time_value,
+                                autohide_timer,
+                                palette,
                             );
+                            state.title = title;
+                            cpu_state_map.insert(widget.widget_id, state);
                         }
                         Mem => {
-                            mem_state_map.insert(
-                                widget.widget_id,
-                                MemWidgetState::init(default_time_value, autohide_timer),
-                            );
+                            let mut state =
+                                MemWidgetState::init(default_time_value, autohide_timer);
+                            state.title = title;
+                            mem_state_map.insert(widget.widget_id, state);
                         }
                         Net => {
-                            net_state_map.insert(
-                                widget.widget_id,
-                                NetWidgetState::init(default_time_value, autohide_timer),
-                            );
+                            let mut state =
+                                NetWidgetState::init(default_time_value, autohide_timer);
+                            state.title = title;
+                            net_state_map.insert(widget.widget_id, state);
                         }
                         Proc => {
                             let mode = if is_grouped {
                                 ProcWidgetMode::Grouped
                             } else if is_default_tree {
                                 ProcWidgetMode::Tree {
-                                    collapsed_pids: Default::default(),
+                                    collapsed: Default::default(),
                                 }
                             } else {
                                 ProcWidgetMode::Normal
                             };
 
-                            proc_state_map.insert(
-                                widget.widget_id,
-                                ProcWidgetState::new(
-                                    &app_config_fields,
-                                    mode,
-                                    table_config,
-                                    &styling,
-                                    &proc_columns,
-                                ),
+                            let mut state = ProcWidgetState::new(
+                                &app_config_fields,
+                                mode,
+                                table_config,
+                                palette,
+                                &proc_columns,
                             );
+                            if let Some(title) = title {
+                                state.table.props.title = Some(title.into());
+                            }
+                            // A filter from the command line takes priority.
+                            let filter = args.filter.filter.as_ref().or(widget.filter.as_ref());
+                            if let Some(filter) = filter {
+                                state.set_search_query(filter.clone());
+                                if let Some(err) = &state.proc_search.search_state.error_message {
+                                    return Err(OptionError::config(format!(
+                                        "'{filter}' is an invalid process filter: {err}"
+                                    ))
+                                    .into());
+                                }
+                            }
+                            proc_state_map.insert(widget.widget_id, state);
                         }
                         Disk => {
-                            disk_state_map.insert(
-                                widget.widget_id,
-                                DiskTableWidget::new(
-                                    &app_config_fields,
-                                    &styling,
-                                    config.disk.as_ref().map(|cfg| cfg.columns.as_slice()),
-                                ),
+                            let mut state = DiskTableWidget::new(
+                                &app_config_fields,
+                                palette,
+                                config.disk.as_ref().map(|cfg| cfg.columns.as_slice()),
                             );
+                            if let Some(title) = title {
+                                state.table.props.title = Some(title.into());
+                            }
+                            if let Some(disk_config) = &config.disk {
+                                state.pinned_mounts = disk_config.pinned.clone();
+                                state.focus_mount = disk_config
+                                    .focus
+                                    .clone()
+                                    .or_else(|| disk_config.pinned.first().cloned());
+                            }
+                            disk_state_map.insert(widget.widget_id, state);
                         }
                         Temp => {
-                            temp_state_map.insert(
-                                widget.widget_id,
-                                TempWidgetState::new(&app_config_fields, &styling),
-                            );
+                            let mut state = TempWidgetState::new(&app_config_fields, palette);
+                            if let Some(title) = title {
+                                state.table.props.title = Some(title.into());
+                            }
+                            temp_state_map.insert(widget.widget_id, state);
                         }
                         Battery => {
-                            battery_state_map
-                                .insert(widget.widget_id, BatteryWidgetState::default());
+                            battery_state_map.insert(
+                                widget.widget_id,
+                                BatteryWidgetState {
+                                    title,
+                                    ..Default::default()
+                                },
+                            );
                         }
                         _ => {}
                     }
@@ -426,32 +609,54 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         }
     }
 
+    // The parts of a widget, like the process widget's search and sort widgets,
+    // are drawn with the same styles as the widget itself.
+    let part_styles: Vec<(u64, Styles)> = widget_map
+        .values()
+        .filter(|widget| widget.parent_reflector.is_some())
+        .filter_map(|widget| {
+            widget_styles
+                .get(&widget.parent_id())
+                .map(|styles| (widget.widget_id, styles.clone()))
+        })
+        .collect();
+    widget_styles.extend(part_styles);
+
     let basic_table_widget_state = if use_basic_mode {
-        Some(match initial_widget_type {
-            Proc | Disk | Temp => BasicTableWidgetState {
-                currently_displayed_widget_type: initial_widget_type,
-                currently_displayed_widget_id: initial_widget_id,
-                left_tlc: None,
-                left_brc: None,
-                right_tlc: None,
-                right_brc: None,
-            },
-            _ => BasicTableWidgetState {
-                currently_displayed_widget_type: Proc,
-                currently_displayed_widget_id: DEFAULT_WIDGET_ID,
-                left_tlc: None,
-                left_brc: None,
-                right_tlc: None,
-                right_brc: None,
-            },
+        let tables = get_basic_tables(args, config)?;
+        let (currently_displayed_widget_type, currently_displayed_widget_id) =
+            if tables.contains(&initial_widget_type) {
+                (initial_widget_type, initial_widget_id)
+            } else {
+                (tables[0].clone(), basic_table_id(&tables[0]))
+            };
+
+        Some(BasicTableWidgetState {
+            currently_displayed_widget_type,
+            currently_displayed_widget_id,
+            table_ids: tables.iter().map(basic_table_id).collect(),
+            left_tlc: None,
+            left_brc: None,
+            right_tlc: None,
+            right_brc: None,
         })
     } else {
         None
     };
 
-    let use_mem = used_widget_set.get(&Mem).is_some() || used_widget_set.get(&BasicMem).is_some();
+    // The status bar and meter header need CPU and memory data even if there are no
+    // widgets for them.
+    let status_bar = app_config_fields.status_bar.as_ref();
+    let meter_header = app_config_fields.meter_header.as_ref();
+    let use_mem = used_widget_set.get(&Mem).is_some()
+        || used_widget_set.get(&BasicMem).is_some()
+        || status_bar.is_some_and(StatusBar::uses_mem)
+        || meter_header.is_some_and(MeterHeader::uses_mem);
     let used_widgets = UsedWidgets {
-        use_cpu: used_widget_set.get(&Cpu).is_some() || used_widget_set.get(&BasicCpu).is_some(),
+        use_cpu: used_widget_set.get(&Cpu).is_some()
+            || used_widget_set.get(&BasicCpu).is_some()
+            || status_bar.is_some_and(StatusBar::uses_cpu)
+            || meter_header.is_some_and(MeterHeader::uses_cpu),
         use_mem,
         use_cache: use_mem && get_enable_cache_memory(args, config),
         use_gpu: get_enable_gpu(args, config),
@@ -486,6 +691,24 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         None => None,
     };
 
+    // Filters from the command line take priority over the config file.
+    let disk_name_filter = match &args.filter.disk_filter {
+        Some(names) => get_allow_list(names)?,
+        None => disk_name_filter,
+    };
+    let disk_mount_filter = match &args.filter.mount_filter {
+        Some(mounts) => get_allow_list(mounts)?,
+        None => disk_mount_filter,
+    };
+    let temp_sensor_filter = match &args.filter.temp_filter {
+        Some(sensors) => get_allow_list(sensors)?,
+        None => temp_sensor_filter,
+    };
+    let net_interface_filter = match &args.filter.net_filter {
+        Some(interfaces) => get_allow_list(interfaces)?,
+        None => net_interface_filter,
+    };
+
     let states = AppWidgetStates {
         cpu_state: CpuState::init(cpu_state_map),
         mem_state: MemState::init(mem_state_map),
@@ -506,6 +729,21 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
     };
     let is_expanded = expanded && !use_basic_mode;
 
+    let (pages, widget_layouts): (Vec<_>, Vec<_>) = layout_pages
+        .into_iter()
+        .map(|page| {
+            (
+                PageState {
+                    name: page.name,
+                    widget_ids: page.widget_ids,
+                    selected_widget_id: page.default_widget_id,
+                    rows: page.rows,
+                },
+                page.layout,
+            )
+        })
+        .unzip();
+
     Ok((
         App::new(
             app_config_fields,
@@ -515,75 +753,132 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
             used_widgets,
             filters,
             is_expanded,
+            pages,
         ),
-        widget_layout,
+        widget_layouts,
         styling,
+        widget_styles,
     ))
 }
 
+/// The name of the page made from the top-level `[[row]]` entries.
+pub(crate) const MAIN_PAGE_NAME: &str = "main";
+
 fn get_widget_layout(
     args: &BottomArgs, config: &Config,
-) -> OptionResult<(BottomLayout, u64, Option<BottomWidgetType>)> {
+) -> OptionResult<(Vec<LayoutPage>, Option<BottomWidgetType>)> {
     let cpu_left_legend = is_flag_enabled!(cpu_left_legend, args.cpu, config);
 
     let (default_widget_type, mut default_widget_count) =
         get_default_widget_and_count(args, config)?;
-    let mut default_widget_id = 1;
-
-    let bottom_layout = if is_flag_enabled!(basic, args.general, config) {
-        default_widget_id = DEFAULT_WIDGET_ID;
 
-        BottomLayout::init_basic_default(get_use_battery(args, config))
+    let pages = if is_flag_enabled!(basic, args.general, config) {
+        vec![LayoutPage {
+            name: MAIN_PAGE_NAME.to_string(),
+            layout: BottomLayout::init_basic_default(&get_basic_tables(args, config)?),
+            widget_ids: 0..=u64::MAX,
+            default_widget_id: DEFAULT_WIDGET_ID,
+            rows: Vec::new(),
+        }]
     } else {
         let ref_row: Vec<Row>; // Required to handle reference
-        let rows = match &config.row {
-            Some(r) => r,
-            None => {
+        let preset = get_layout_preset(args, config)?;
+        let main_rows = match (preset, &config.row, &config.page) {
+            (Some(preset), _, _) => {
+                // This cannot (like it really shouldn't) fail!
+                ref_row = toml_edit::de::from_str::<Config>(preset.layout())?
+                    .row
+                    .unwrap();
+                Some(&ref_row)
+            }
+            (None, Some(r), _) => Some(r),
+            (None, None, Some(pages)) if !pages.is_empty() => None,
+            _ => {
                 // This cannot (like it really shouldn't) fail!
                 ref_row = toml_edit::de::from_str::<Config>(if get_use_battery(args, config) {
                     DEFAULT_BATTERY_LAYOUT
+                } else if is_wsl() {
+                    DEFAULT_WSL_LAYOUT
                 } else {
                     DEFAULT_LAYOUT
                 })?
                 .row
                 .unwrap();
-                &ref_row
+                Some(&ref_row)
             }
         };
 
+        let page_rows = main_rows
+            .map(|rows| (MAIN_PAGE_NAME, rows.as_slice()))
+            .into_iter()
+            .chain(
+                config
+                    .page
+                    .iter()
+                    .filter(|_| preset.is_none())
+                    .flatten()
+                    .map(|page| (page.name.as_str(), page.row.as_slice())),
+            );
+
         let mut iter_id = 0; // A lazy way of forcing unique IDs *shrugs*
-        let mut total_height_ratio = 0;
-
-        let mut ret_bottom_layout = BottomLayout {
-            rows: rows
-                .iter()
-                .map(|row| {
-                    row.convert_row_to_bottom_row(
-                        &mut iter_id,
-                        &mut total_height_ratio,
-                        &mut default_widget_id,
-                        &default_widget_type,
-                        &mut default_widget_count,
-                        cpu_left_legend,
-                    )
-                    .map_err(|err| OptionError::config(err.to_string()))
-                })
-                .collect::<OptionResult<Vec<_>>>()?,
-            total_row_height_ratio: total_height_ratio,
-        };
+        let mut pages = Vec::new();
 
-        // Confirm that we have at least ONE widget left - if not, error out!
-        if iter_id > 0 {
-            ret_bottom_layout.get_movement_mappings();
-            ret_bottom_layout
-        } else {
-            return Err(OptionError::config(
-                "have at least one widget under the '[[row]]' section.",
-            ));
+        for (name, rows) in page_rows {
+            let first_widget_id = iter_id + 1;
+            let mut default_widget_id = first_widget_id;
+            let mut total_height_ratio = 0;
+
+            // Only the first page uses the default widget type, as that is what's shown
+            // on startup.
+            let page_default_widget_type = if pages.is_empty() {
+                &default_widget_type
+            } else {
+                &None
+            };
+
+            let mut layout = BottomLayout {
+                rows: rows
+                    .iter()
+                    .map(|row| {
+                        row.convert_row_to_bottom_row(
+                            &mut iter_id,
+                            &mut total_height_ratio,
+                            &mut default_widget_id,
+                            page_default_widget_type,
+                            &mut default_widget_count,
+                            cpu_left_legend,
+                        )
+                        .map_err(|err| OptionError::config(err.to_string()))
+                    })
+                    .collect::<OptionResult<Vec<_>>>()?,
+                total_row_height_ratio: total_height_ratio,
+            };
+
+            // Confirm that we have at least ONE widget left - if not, error out!
+            if iter_id < first_widget_id {
+                return Err(OptionError::config(
+                    if pages.is_empty() && main_rows.is_some() {
+                        "have at least one widget under the '[[row]]' section.".to_string()
+                    } else {
+                        format!("have at least one widget in the '{name}' page.")
+                    },
+                ));
+            }
+
+            layout.get_movement_mappings();
+            pages.push(LayoutPage {
+                name: name.to_string(),
+                layout,
+                widget_ids: first_widget_id..=iter_id,
+                default_widget_id,
+                rows: rows.to_vec(),
+            });
         }
+
+        pages
     };
 
-    Ok((bottom_layout, default_widget_id, default_widget_type))
+    Ok((pages, default_widget_type))
 }
 
 #[inline]
@@ -682,6 +977,16 @@ fn get_update_rate(args: &BottomArgs, config: &Config) -> OptionResult<u64> {
     )
 }
 
+fn get_extra_sensors(config: &Config) -> ExtraSensors {
+    match &config.temperature {
+        Some(temperature) => ExtraSensors {
+            voltages: temperature.voltages.unwrap_or(false),
+            power: temperature.power.unwrap_or(false),
+        },
+        None => ExtraSensors::default(),
+    }
+}
+
 fn get_temperature(args: &BottomArgs, config: &Config) -> OptionResult<TemperatureType> {
     if args.temperature.fahrenheit {
         return Ok(TemperatureType::Fahrenheit);
@@ -763,6 +1068,20 @@ fn get_time_interval(args: &BottomArgs, config: &Config, retention_ms: u64) -> O
     )
 }
 
+fn get_layout_preset(args: &BottomArgs, config: &Config) -> OptionResult<Option<LayoutPreset>> {
+    if let Some(preset) = &args.general.preset {
+        parse_arg_value!(preset.parse::<LayoutPreset>(), "preset").map(Some)
+    } else if let Some(preset) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.preset.as_ref())
+    {
+        parse_config_value!(preset.parse::<LayoutPreset>(), "preset").map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
 fn get_default_widget_and_count(
     args: &BottomArgs, config: &Config,
 ) -> OptionResult<(Option<BottomWidgetType>, u64)> {
@@ -815,6 +1134,12 @@ fn get_default_widget_and_count(
 
 #[cfg(feature = "battery")]
 fn get_use_battery(args: &BottomArgs, config: &Config) -> bool {
+    // Any battery under WSL is just one that Hyper-V reports for the VM, so it's
+    // meaningless.
+    if is_wsl() {
+        return false;
+    }
+
     // TODO: Move this so it's dynamic in the app itself and automatically hide if
     // there are no batteries?
     if let Ok(battery_manager) = Manager::new() {
@@ -841,6 +1166,20 @@ fn get_use_battery(_args: &BottomArgs, _config: &Config) -> bool {
     false
 }
 
+/// Returns whether we're running under WSL, which has no temperature sensors
+/// or real batteries.
+fn is_wsl() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        crate::data_collection::wsl::is_wsl()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        false
+    }
+}
+
 #[cfg(feature = "gpu")]
 fn get_enable_gpu(args: &BottomArgs, config: &Config) -> bool {
     if args.gpu.disable_gpu {
@@ -877,6 +1216,26 @@ fn get_enable_cache_memory(_args: &BottomArgs, _config: &Config) -> bool {
     false
 }
 
+fn get_key_bindings(config: &Config) -> OptionResult<KeyBindings> {
+    match &config.keybinds {
+        Some(keybinds) => KeyBindings::new(keybinds.overrides())
+            .map_err(|err| OptionError::config(format!("invalid keybinds: {err}"))),
+        None => Ok(KeyBindings::default()),
+    }
+}
+
+/// Loads the plugins in the configured plugin directory, if there is one.
+#[cfg(feature = "plugins")]
+pub(crate) fn get_plugins(
+    config: &Config,
+) -> OptionResult<Vec<crate::data_collection::plugins::Plugin>> {
+    match config.plugins.as_ref().and_then(|p| p.directory.as_ref()) {
+        Some(directory) => crate::data_collection::plugins::Plugin::load_dir(Path::new(directory))
+            .map_err(|err| OptionError::config(format!("couldn't load plugins: {err:#}"))),
+        None => Ok(Vec::new()),
+    }
+}
+
 fn get_ignore_list(ignore_list: &Option<IgnoreList>) -> OptionResult<Option<Filter>> {
     if let Some(ignore_list) = ignore_list {
         let list: Result<Vec<_>, _> = ignore_list
@@ -913,6 +1272,18 @@ fn get_ignore_list(ignore_list: &Option<IgnoreList>) -> OptionResult<Option<Filt
     }
 }
 
+/// Returns a filter that only keeps entries containing one of `names`, ignoring
+/// case. This is used for filters set from the command line.
+fn get_allow_list(names: &[String]) -> OptionResult<Option<Filter>> {
+    get_ignore_list(&Some(IgnoreList {
+        is_list_ignored: false,
+        list: names.to_vec(),
+        regex: false,
+        case_sensitive: false,
+        whole_word: false,
+    }))
+}
+
 fn get_network_unit_type(args: &BottomArgs, config: &Config) -> DataUnit {
     if args.network.network_use_bytes {
         return DataUnit::Byte;
@@ -957,6 +1328,76 @@ fn get_retention(args: &BottomArgs, config: &Config) -> OptionResult<u64> {
     )
 }
 
+fn get_long_term_retention(
+    args: &BottomArgs, config: &Config, retention_ms: u64,
+) -> OptionResult<Option<u64>> {
+    let arg_value = &args.general.long_term_retention;
+    let config_value = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.long_term_retention.as_ref());
+
+    if arg_value.is_none() && config_value.is_none() {
+        return Ok(None);
+    }
+
+    parse_ms_option!(
+        arg_value,
+        config_value,
+        retention_ms,
+        "long_term_retention",
+        Some(retention_ms),
+        None,
+    )
+    .map(Some)
+}
+
+fn get_idle_rate(
+    args: &BottomArgs, config: &Config, update_rate: u64,
+) -> OptionResult<Option<u64>> {
+    let arg_value = &args.general.idle_rate;
+    let config_value = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.idle_rate.as_ref());
+
+    if arg_value.is_none() && config_value.is_none() {
+        return Ok(None);
+    }
+
+    parse_ms_option!(
+        arg_value,
+        config_value,
+        update_rate,
+        "idle_rate",
+        Some(update_rate),
+        None,
+    )
+    .map(Some)
+}
+
+/// Gets an optional limit, which has to be above zero if set.
+fn get_limit(
+    arg_value: Option<u64>, config_value: Option<u64>, name: &str,
+) -> OptionResult<Option<u64>> {
+    match (arg_value, config_value) {
+        (Some(0), _) => Err(OptionError::invalid_arg_value(name)),
+        (Some(value), _) => Ok(Some(value)),
+        (None, Some(0)) => Err(OptionError::invalid_config_value(name)),
+        (None, value) => Ok(value),
+    }
+}
+
+fn get_http_address(args: &BottomArgs, config: &Config) -> OptionResult<Option<SocketAddr>> {
+    if let Some(address) = &args.general.http {
+        Ok(Some(parse_arg_value!(address.trim().parse(), "http")?))
+    } else if let Some(address) = config.flags.as_ref().and_then(|flags| flags.http.as_ref()) {
+        Ok(Some(parse_config_value!(address.trim().parse(), "http")?))
+    } else {
+        Ok(None)
+    }
+}
+
 fn get_network_legend_position(
     args: &BottomArgs, config: &Config,
 ) -> OptionResult<Option<LegendPosition>> {
@@ -981,6 +1422,221 @@ fn get_network_legend_position(
     Ok(result)
 }
 
+fn get_default_sort(args: &BottomArgs, config: &Config) -> OptionResult<Option<ProcDefaultSort>> {
+    if let Some(sort) = &args.process.default_sort {
+        parse_arg_value!(sort.parse(), "default_sort").map(Some)
+    } else if let Some(sort) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.default_sort.as_ref())
+    {
+        parse_config_value!(sort.parse(), "default_sort").map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+fn get_double_click_action(args: &BottomArgs, config: &Config) -> OptionResult<DoubleClickAction> {
+    if let Some(action) = &args.general.double_click_action {
+        parse_arg_value!(
+            action.to_ascii_lowercase().trim().parse(),
+            "double_click_action"
+        )
+    } else if let Some(action) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.double_click_action.as_ref())
+    {
+        parse_config_value!(
+            action.to_ascii_lowercase().trim().parse(),
+            "double_click_action"
+        )
+    } else {
+        Ok(DoubleClickAction::default())
+    }
+}
+
+/// Returns the graph marker to use. If none is set, Braille is used if the
+/// terminal supports `unicode`, and dots otherwise.
+fn get_graph_marker(
+    args: &BottomArgs, config: &Config, unicode: bool,
+) -> OptionResult<GraphMarker> {
+    let flags = config.flags.as_ref();
+
+    if let Some(style) = &args.general.graph_style {
+        parse_arg_value!(style.to_ascii_lowercase().trim().parse(), "graph_style")
+    } else if args.general.dot_marker {
+        Ok(GraphMarker::Dot)
+    } else if let Some(style) = flags.and_then(|flags| flags.graph_style.as_ref()) {
+        parse_config_value!(style.to_ascii_lowercase().trim().parse(), "graph_style")
+    } else if flags.and_then(|flags| flags.dot_marker).unwrap_or(false) || !unicode {
+        Ok(GraphMarker::Dot)
+    } else {
+        Ok(GraphMarker::default())
+    }
+}
+
+/// Returns what the terminal supports, going by what was `detected` unless
+/// set with `--color_depth` or `--ascii`. Explicitly turning clicks on in the
+/// config also turns on mouse support.
+fn get_terminal_features(
+    args: &BottomArgs, config: &Config, detected: TerminalFeatures,
+) -> OptionResult<TerminalFeatures> {
+    let flags = config.flags.as_ref();
+
+    let colour_depth: ColourDepth = if let Some(depth) = &args.style.color_depth {
+        parse_arg_value!(depth.to_ascii_lowercase().trim().parse(), "color_depth")?
+    } else if let Some(depth) = flags.and_then(|flags| flags.color_depth.as_ref()) {
+        let depth = match depth {
+            StringOrNum::String(depth) => depth.to_ascii_lowercase().trim().parse(),
+            StringOrNum::Num(depth) => depth.to_string().parse(),
+        };
+        parse_config_value!(depth, "color_depth")?
+    } else {
+        detected.colour_depth
+    };
+
+    let unicode = if args.style.ascii {
+        false
+    } else {
+        flags
+            .and_then(|flags| flags.ascii)
+            .map_or(detected.unicode, |ascii| !ascii)
+    };
+
+    let mouse = detected.mouse || flags.and_then(|flags| flags.disable_click) == Some(false);
+
+    Ok(TerminalFeatures {
+        colour_depth,
+        unicode,
+        mouse,
+    })
+}
+
+/// Returns the table widgets that basic mode switches between, in order.
+fn get_basic_tables(args: &BottomArgs, config: &Config) -> OptionResult<Vec<BottomWidgetType>> {
+    let configured = config
+        .basic
+        .as_ref()
+        .and_then(|basic| basic.tables.as_ref());
+    let tables = configured.map_or(BasicTable::DEFAULT.as_slice(), Vec::as_slice);
+
+    let mut seen = HashSet::new();
+    if !tables.iter().all(|table| seen.insert(*table)) {
+        return Err(OptionError::config(
+            "'basic.tables' can't list the same table more than once.",
+        ));
+    }
+
+    let use_battery = get_use_battery(args, config);
+    let tables: Vec<_> = tables
+        .iter()
+        .filter_map(|table| match table {
+            BasicTable::Proc => Some(BottomWidgetType::Proc),
+            BasicTable::Disk => Some(BottomWidgetType::Disk),
+            BasicTable::Temp => Some(BottomWidgetType::Temp),
+            BasicTable::Battery => use_battery.then_some(BottomWidgetType::Battery),
+        })
+        .collect();
+
+    if tables.is_empty() {
+        return Err(OptionError::config(
+            "'basic.tables' has no tables to show. Note that batteries are only shown if the 'battery' flag is enabled.",
+        ));
+    }
+
+    Ok(tables)
+}
+
+/// Returns the status bar's settings if it is enabled.
+fn get_status_bar(args: &BottomArgs, config: &Config) -> Option<StatusBar> {
+    if !is_flag_enabled!(status_bar, args.general, config) {
+        return None;
+    }
+
+    let status_bar = config.status_bar.as_ref();
+    Some(StatusBar {
+        position: status_bar
+            .and_then(|status_bar| status_bar.position)
+            .unwrap_or_default(),
+        fields: status_bar
+            .and_then(|status_bar| status_bar.fields.clone())
+            .unwrap_or_else(|| StatusBarField::DEFAULT.to_vec()),
+    })
+}
+
+/// Returns the meter header's settings if it is enabled.
+fn get_meter_header(args: &BottomArgs, config: &Config) -> Option<MeterHeader> {
+    if !is_flag_enabled!(meter_header, args.general, config) {
+        return None;
+    }
+
+    Some(MeterHeader {
+        meters: config
+            .meter_header
+            .as_ref()
+            .and_then(|meter_header| meter_header.meters.clone())
+            .unwrap_or_else(|| Meter::DEFAULT.to_vec()),
+    })
+}
+
+/// Returns the locale to translate text into, which is the one set by the
+/// arguments, the config, or the environment, in that order.
+pub(crate) fn get_locale(args: &BottomArgs, config: &Config) -> OptionResult<Locale> {
+    if let Some(locale) = &args.general.locale {
+        parse_arg_value!(Locale::parse(locale).ok_or(()), "locale")
+    } else if let Some(locale) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.locale.as_ref())
+    {
+        parse_config_value!(Locale::parse(locale).ok_or(()), "locale")
+    } else {
+        Ok(Locale::from_env())
+    }
+}
+
+fn get_time_format(args: &BottomArgs, config: &Config) -> OptionResult<TimeFormat> {
+    if let Some(format) = &args.general.time_format {
+        parse_arg_value!(format.parse(), "time_format")
+    } else if let Some(format) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.time_format.as_ref())
+    {
+        parse_config_value!(format.parse(), "time_format")
+    } else {
+        Ok(TimeFormat::default())
+    }
+}
+
+fn get_mouse(config: &Config) -> OptionResult<MouseSettings> {
+    let default = MouseSettings::default();
+    let Some(mouse) = &config.mouse else {
+        return Ok(default);
+    };
+
+    let table_scroll_rows = mouse.table_scroll_rows.unwrap_or(default.table_scroll_rows);
+    if table_scroll_rows == 0 {
+        return Err(OptionError::config(
+            "'table_scroll_rows' must be at least 1.",
+        ));
+    }
+
+    let scroll_debounce_ms = match &mouse.scroll_debounce {
+        Some(StringOrNum::String(s)) => parse_config_value!(try_parse_ms(s), "scroll_debounce")?,
+        Some(StringOrNum::Num(n)) => *n,
+        None => default.scroll_debounce_ms,
+    };
+
+    Ok(MouseSettings {
+        table_scroll_rows,
+        graph_scroll: mouse.graph_scroll.unwrap_or(default.graph_scroll),
+        invert_scroll: mouse.invert_scroll.unwrap_or(default.invert_scroll),
+        scroll_debounce_ms,
+    })
+}
+
 fn get_memory_legend_position(
     args: &BottomArgs, config: &Config,
 ) -> OptionResult<Option<LegendPosition>> {
@@ -1008,15 +1664,33 @@ fn get_memory_legend_position(
 #[cfg(test)]
 mod test {
     use clap::Parser;
+    use hashbrown::HashSet;
 
     use super::{get_time_interval, Config};
     use crate::{
-        app::App,
-        args::BottomArgs,
+        app::{
+            layout_manager::{BottomLayout, BottomWidgetType::*, WidgetDirection},
+            App, DoubleClickAction,
+        },
+        canvas::{
+            components::time_chart::GraphMarker,
+            terminal::{ColourDepth, TerminalFeatures},
+        },
+        constants::DEFAULT_WIDGET_ID,
+        i18n::Locale,
         options::{
-            config::flags::FlagConfig, get_default_time_value, get_retention, get_update_rate,
-            try_parse_ms,
+            args::{self, BottomArgs},
+            config::{
+                flags::FlagConfig,
+                mouse::{GraphScroll, MouseSettings},
+                StringOrNum,
+            },
+            get_basic_tables, get_default_time_value, get_double_click_action, get_graph_marker,
+            get_http_address, get_idle_rate, get_limit, get_locale, get_long_term_retention,
+            get_mouse, get_retention, get_terminal_features, get_time_format, get_update_rate,
+            get_widget_layout, try_parse_ms,
         },
+        utils::time_format::TimeFormat,
     };
 
     #[test]
@@ -1178,6 +1852,531 @@ mod test {
         assert_eq!(get_retention(&args, &config), Ok(600000));
     }
 
+    #[test]
+    fn http_address() {
+        let config = Config::default();
+
+        let args = BottomArgs::parse_from(["btm", "--http", "127.0.0.1:8080"]);
+        assert_eq!(
+            get_http_address(&args, &config),
+            Ok(Some("127.0.0.1:8080".parse().unwrap()))
+        );
+
+        let args = BottomArgs::parse_from(["btm", "--http", "localhost"]);
+        assert!(get_http_address(&args, &config).is_err());
+
+        let args = BottomArgs::parse_from(["btm"]);
+        assert_eq!(get_http_address(&args, &config), Ok(None));
+
+        let config = Config {
+            flags: Some(FlagConfig {
+                http: Some("[::1]:9000".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            get_http_address(&args, &config),
+            Ok(Some("[::1]:9000".parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn long_term_retention() {
+        let config = Config::default();
+
+        let args = BottomArgs::parse_from(["btm"]);
+        assert_eq!(get_long_term_retention(&args, &config, 600_000), Ok(None));
+
+        let args = BottomArgs::parse_from(["btm", "--long_term_retention", "1d"]);
+        assert_eq!(
+            get_long_term_retention(&args, &config, 600_000),
+            Ok(Some(86_400_000))
+        );
+
+        let args = BottomArgs::parse_from(["btm", "--long_term_retention", "1m"]);
+        assert!(get_long_term_retention(&args, &config, 600_000).is_err());
+    }
+
+    #[test]
+    fn idle_rate() {
+        let config = Config::default();
+
+        let args = BottomArgs::parse_from(["btm"]);
+        assert_eq!(get_idle_rate(&args, &config, 1000), Ok(None));
+
+        let args = BottomArgs::parse_from(["btm", "--idle_rate", "5s"]);
+        assert_eq!(get_idle_rate(&args, &config, 1000), Ok(Some(5000)));
+
+        let args = BottomArgs::parse_from(["btm", "--idle_rate", "500"]);
+        assert!(get_idle_rate(&args, &config, 1000).is_err());
+    }
+
+    #[test]
+    fn limits() {
+        assert_eq!(get_limit(None, None, "max_processes"), Ok(None));
+        assert_eq!(get_limit(None, Some(100), "max_processes"), Ok(Some(100)));
+        assert_eq!(
+            get_limit(Some(50), Some(100), "max_processes"),
+            Ok(Some(50))
+        );
+        assert!(get_limit(Some(0), None, "max_processes").is_err());
+        assert!(get_limit(None, Some(0), "max_processes").is_err());
+    }
+
+    const PAGES_CONFIG: &str = r#"
+[[row]]
+  [[row.child]]
+    type="cpu"
+  [[row.child]]
+    type="proc"
+
+[[page]]
+  name="network"
+  [[page.row]]
+    [[page.row.child]]
+      type="temp"
+    [[page.row.child]]
+      type="net"
+      default=true
+"#;
+
+    #[test]
+    fn layout_pages() {
+        let config: Config = toml_edit::de::from_str(PAGES_CONFIG).unwrap();
+        let args = BottomArgs::parse_from(["btm"]);
+
+        let (pages, _) = get_widget_layout(&args, &config).unwrap();
+        assert_eq!(pages.len(), 2);
+
+        // The CPU and its legend, followed by the process table, search, and sort.
+        assert_eq!(pages[0].name, "main");
+        assert_eq!(pages[0].widget_ids, 1..=5);
+        assert_eq!(pages[0].default_widget_id, 1);
+
+        assert_eq!(pages[1].name, "network");
+        assert_eq!(pages[1].widget_ids, 6..=7);
+        assert_eq!(pages[1].default_widget_id, 7);
+    }
+
+    #[test]
+    fn layout_presets() {
+        let config: Config = toml_edit::de::from_str(PAGES_CONFIG).unwrap();
+
+        for preset in [
+            "minimal",
+            "netadmin",
+            #[cfg(feature = "battery")]
+            "battery",
+        ] {
+            let args = BottomArgs::parse_from(["btm", "--preset", preset]);
+
+            // Presets replace any layout in the config file.
+            let (pages, _) = get_widget_layout(&args, &config).unwrap();
+            assert_eq!(pages.len(), 1, "{preset} should only have one page");
+        }
+
+        let config: Config = toml_edit::de::from_str(
+            r#"
+[flags]
+preset = "not_a_preset"
+"#,
+        )
+        .unwrap();
+        let args = BottomArgs::parse_from(["btm"]);
+        assert!(get_widget_layout(&args, &config).is_err());
+    }
+
+    #[test]
+    fn switch_layout_pages() {
+        let config: Config = toml_edit::de::from_str(PAGES_CONFIG).unwrap();
+        let mut app = super::init_app(BottomArgs::parse_from(["btm"]), config)
+            .unwrap()
+            .0;
+
+        assert_eq!(app.current_page, 0);
+        assert_eq!(app.current_widget.widget_id, 1);
+
+        app.next_page();
+        assert_eq!(app.current_page, 1);
+        assert_eq!(app.current_widget.widget_id, 7);
+
+        // Wraps around, and goes back to the widget last selected on the page.
+        app.next_page();
+        assert_eq!(app.current_page, 0);
+        assert_eq!(app.current_widget.widget_id, 1);
+
+        app.previous_page();
+        assert_eq!(app.current_page, 1);
+        assert_eq!(app.page_of_widget(app.current_widget.widget_id), Some(1));
+    }
+
+    #[test]
+    fn hide_widgets() {
+        let config: Config = toml_edit::de::from_str(PAGES_CONFIG).unwrap();
+        let (pages, _) = get_widget_layout(&BottomArgs::parse_from(["btm"]), &config).unwrap();
+        let mut app = super::init_app(BottomArgs::parse_from(["btm"]), config)
+            .unwrap()
+            .0;
+
+        // Hiding the CPU widget also hides its legend, and selects the process widget.
+        app.hide_widget();
+        assert_eq!(app.hidden_widgets, HashSet::from_iter([1]));
+        assert_eq!(app.current_widget.widget_id, 3);
+
+        let layout = pages[0].layout.without_widgets(&app.hidden_widgets);
+        assert_eq!(layout.rows.len(), 1);
+        assert_eq!(layout.rows[0].children.len(), 1);
+        assert_eq!(layout.rows[0].total_col_ratio, 1);
+
+        // The last widget in a page can't be hidden, and hidden widgets are skipped over.
+        app.hide_widget();
+        assert_eq!(app.hidden_widgets.len(), 1);
+        app.move_widget_selection(&WidgetDirection::Left);
+        assert_eq!(app.current_widget.widget_id, 3);
+
+        app.show_widgets();
+        assert!(app.hidden_widgets.is_empty());
+        app.move_widget_selection(&WidgetDirection::Left);
+        assert_ne!(app.current_widget.widget_id, 3);
+    }
+
+    #[test]
+    fn visible_widgets() {
+        let config: Config = toml_edit::de::from_str(PAGES_CONFIG).unwrap();
+        let mut app = super::init_app(BottomArgs::parse_from(["btm"]), config)
+            .unwrap()
+            .0;
+
+        // Only the widgets on the current page are visible.
+        let visible = app.visible_widgets();
+        assert!(visible.use_cpu && visible.use_proc);
+        assert!(!visible.use_temp && !visible.use_net);
+
+        // Hiding the CPU widget also stops its legend from counting as visible.
+        app.hide_widget();
+        let visible = app.visible_widgets();
+        assert!(!visible.use_cpu && visible.use_proc);
+
+        app.next_page();
+        let visible = app.visible_widgets();
+        assert!(!visible.use_cpu && !visible.use_proc);
+        assert!(visible.use_temp && visible.use_net);
+    }
+
+    #[test]
+    fn graph_markers() {
+        let args = BottomArgs::parse_from(["btm"]);
+        let mut config = Config::default();
+        assert_eq!(
+            get_graph_marker(&args, &config, true),
+            Ok(GraphMarker::Braille)
+        );
+
+        config.flags = Some(FlagConfig {
+            dot_marker: Some(true),
+            ..Default::default()
+        });
+        assert_eq!(get_graph_marker(&args, &config, true), Ok(GraphMarker::Dot));
+
+        // The graph style takes precedence over the dot marker.
+        config.flags = Some(FlagConfig {
+            dot_marker: Some(true),
+            graph_style: Some("Line".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(
+            get_graph_marker(&args, &config, true),
+            Ok(GraphMarker::Line)
+        );
+
+        let args = BottomArgs::parse_from(["btm", "--graph_style", "block"]);
+        assert_eq!(
+            get_graph_marker(&args, &config, true),
+            Ok(GraphMarker::Block)
+        );
+
+        config.flags = Some(FlagConfig {
+            graph_style: Some("dashes".to_string()),
+            ..Default::default()
+        });
+        let args = BottomArgs::parse_from(["btm"]);
+        assert!(get_graph_marker(&args, &config, true).is_err());
+
+        // Terminals without Unicode get dots, unless a style is set.
+        let config = Config::default();
+        assert_eq!(
+            get_graph_marker(&args, &config, false),
+            Ok(GraphMarker::Dot)
+        );
+        let args = BottomArgs::parse_from(["btm", "--graph_style", "braille"]);
+        assert_eq!(
+            get_graph_marker(&args, &config, false),
+            Ok(GraphMarker::Braille)
+        );
+    }
+
+    #[test]
+    fn terminal_features() {
+        let serial = TerminalFeatures {
+            colour_depth: ColourDepth::Basic,
+            unicode: false,
+            mouse: false,
+        };
+
+        let args = BottomArgs::parse_from(["btm"]);
+        let mut config = Config::default();
+        assert_eq!(get_terminal_features(&args, &config, serial), Ok(serial));
+
+        config.flags = Some(FlagConfig {
+            color_depth: Some(StringOrNum::Num(256)),
+            ascii: Some(false),
+            disable_click: Some(false),
+            ..Default::default()
+        });
+        assert_eq!(
+            get_terminal_features(&args, &config, serial),
+            Ok(TerminalFeatures::default())
+        );
+
+        let args = BottomArgs::parse_from(["btm", "--color_depth", "truecolor", "--ascii"]);
+        assert_eq!(
+            get_terminal_features(&args, &config, TerminalFeatures::default()),
+            Ok(TerminalFeatures {
+                colour_depth: ColourDepth::TrueColour,
+                unicode: false,
+                mouse: true,
+            })
+        );
+
+        let args = BottomArgs::parse_from(["btm", "--color_depth", "8"]);
+        assert!(get_terminal_features(&args, &config, serial).is_err());
+
+        config.flags = Some(FlagConfig {
+            color_depth: Some(StringOrNum::String("lots".to_string())),
+            ..Default::default()
+        });
+        let args = BottomArgs::parse_from(["btm"]);
+        assert!(get_terminal_features(&args, &config, serial).is_err());
+    }
+
+    #[test]
+    fn double_click_actions() {
+        let args = BottomArgs::parse_from(["btm"]);
+        let mut config = Config::default();
+        assert_eq!(
+            get_double_click_action(&args, &config),
+            Ok(DoubleClickAction::Collapse)
+        );
+
+        config.flags = Some(FlagConfig {
+            double_click_action: Some("Details".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(
+            get_double_click_action(&args, &config),
+            Ok(DoubleClickAction::Details)
+        );
+
+        let args = BottomArgs::parse_from(["btm", "--double_click_action", "kill"]);
+        assert_eq!(
+            get_double_click_action(&args, &config),
+            Ok(DoubleClickAction::Kill)
+        );
+
+        config.flags = Some(FlagConfig {
+            double_click_action: Some("triple".to_string()),
+            ..Default::default()
+        });
+        let args = BottomArgs::parse_from(["btm"]);
+        assert!(get_double_click_action(&args, &config).is_err());
+    }
+
+    #[test]
+    fn cli_filters() {
+        let app = create_app(BottomArgs::parse_from([
+            "btm",
+            "--disk_filter",
+            "sda,NVME",
+            "--filter",
+            "user=tomcat",
+        ]));
+
+        let disk_filter = app.filters.disk_filter.unwrap();
+        assert!(disk_filter.should_keep("/dev/sda1"));
+        assert!(disk_filter.should_keep("/dev/nvme0n1"));
+        assert!(!disk_filter.should_keep("/dev/sdb1"));
+        assert!(app.filters.net_filter.is_none());
+
+        assert!(app
+            .states
+            .proc_state
+            .widget_states
+            .values()
+            .all(|state| state.current_search_query() == "user=tomcat"));
+    }
+
+    #[test]
+    fn dump_state() {
+        let path = std::env::temp_dir().join(format!("btm_dump_test_{}.json", std::process::id()));
+        let mut app = create_app(BottomArgs::parse_from([
+            "btm",
+            "--dump_path",
+            &path.to_string_lossy(),
+        ]));
+
+        assert_eq!(crate::app::state_dump::dump_state(&app).unwrap(), path);
+        let dump: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(dump["is_frozen"], false);
+        assert!(dump["processes"].is_array());
+        assert!(dump["metrics"]["cpu"].is_array());
+
+        app.on_action(crate::event::keybinds::Action::Freeze);
+        crate::app::state_dump::dump_state(&app).unwrap();
+        let dump: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(dump["is_frozen"], true);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn cycle_profiles() {
+        let mut app = create_app(BottomArgs::parse_from(["btm"]));
+        assert_eq!(app.next_profile(), None);
+
+        app.profiles = vec!["laptop".to_string(), "server".to_string()];
+        assert_eq!(app.next_profile(), Some("laptop"));
+
+        app.profile = Some("laptop".to_string());
+        assert_eq!(app.next_profile(), Some("server"));
+
+        // Go back to not using a profile after the last one.
+        app.profile = Some("server".to_string());
+        assert_eq!(app.next_profile(), None);
+    }
+
+    #[test]
+    fn locale() {
+        let config: Config = toml_edit::de::from_str(
+            r#"
+[flags]
+locale = "de_DE"
+"#,
+        )
+        .unwrap();
+
+        let args = BottomArgs::parse_from(["btm"]);
+        assert_eq!(get_locale(&args, &config), Ok(Locale::German));
+
+        // Arguments take priority over the config.
+        let args = BottomArgs::parse_from(["btm", "--locale", "es"]);
+        assert_eq!(get_locale(&args, &config), Ok(Locale::Spanish));
+
+        let args = BottomArgs::parse_from(["btm", "--locale", "xx"]);
+        assert!(get_locale(&args, &config).is_err());
+    }
+
+    #[test]
+    fn time_format() {
+        let config: Config = toml_edit::de::from_str(
+            r#"
+[flags]
+time_format = "clock"
+"#,
+        )
+        .unwrap();
+
+        let args = BottomArgs::parse_from(["btm"]);
+        assert_eq!(
+            get_time_format(&args, &config).map(|format| format.to_string()),
+            Ok("[hour]:[minute]:[second]".to_string())
+        );
+
+        // Arguments take priority over the config.
+        let args = BottomArgs::parse_from(["btm", "--time_format", "relative"]);
+        assert_eq!(get_time_format(&args, &config), Ok(TimeFormat::Relative));
+
+        let args = BottomArgs::parse_from(["btm", "--time_format", "hh:mm"]);
+        assert!(get_time_format(&args, &config).is_err());
+
+        let args = BottomArgs::parse_from(["btm"]);
+        assert_eq!(
+            get_time_format(&args, &Config::default()),
+            Ok(TimeFormat::Relative)
+        );
+    }
+
+    #[test]
+    fn mouse_settings() {
+        let config = Config::default();
+        assert_eq!(get_mouse(&config).unwrap(), MouseSettings::default());
+
+        let config: Config = toml_edit::de::from_str(
+            r#"
+[mouse]
+table_scroll_rows = 3
+graph_scroll = "none"
+scroll_debounce = "5ms"
+"#,
+        )
+        .unwrap();
+        let mouse = get_mouse(&config).unwrap();
+        assert_eq!(mouse.table_scroll_rows, 3);
+        assert_eq!(mouse.graph_scroll, GraphScroll::None);
+        assert!(!mouse.invert_scroll);
+        assert_eq!(mouse.scroll_debounce_ms, 5);
+
+        let config: Config = toml_edit::de::from_str(
+            r#"
+[mouse]
+table_scroll_rows = 0
+"#,
+        )
+        .unwrap();
+        assert!(get_mouse(&config).is_err());
+    }
+
+    #[test]
+    fn basic_tables() {
+        let args = BottomArgs::parse_from(["btm"]);
+        let config = Config::default();
+        assert_eq!(
+            get_basic_tables(&args, &config).unwrap(),
+            vec![Disk, Proc, Temp]
+        );
+
+        let config: Config = toml_edit::de::from_str(
+            r#"
+[basic]
+tables = ["temp", "proc"]
+"#,
+        )
+        .unwrap();
+        let tables = get_basic_tables(&args, &config).unwrap();
+        assert_eq!(tables, vec![Temp, Proc]);
+
+        // The tables wrap around in the given order.
+        let layout = BottomLayout::init_basic_default(&tables);
+        let temp = layout
+            .rows
+            .iter()
+            .flat_map(|row| &row.children)
+            .flat_map(|col| &col.children)
+            .flat_map(|col_row| &col_row.children)
+            .find(|widget| widget.widget_type == Temp)
+            .unwrap();
+        assert_eq!(temp.left_neighbour, Some(DEFAULT_WIDGET_ID));
+        assert_eq!(temp.right_neighbour, Some(DEFAULT_WIDGET_ID + 2));
+
+        for tables in [r#"["proc", "proc"]"#, r#"["battery"]"#, "[]"] {
+            let config: Config =
+                toml_edit::de::from_str(&format!("[basic]\ntables = {tables}")).unwrap();
+            assert!(get_basic_tables(&args, &config).is_err());
+        }
+    }
+
     fn create_app(args: BottomArgs) -> App {
         let config = Config::default();
         super::init_app(args, config).unwrap().0
@@ -1188,13 +2387,23 @@ mod test {
     // one struct?
     #[test]
     fn verify_cli_options_build() {
-        let app = crate::args::build_cmd();
+        let app = args::build_cmd();
 
         let default_app = create_app(BottomArgs::parse_from(["btm"]));
 
         // Skip battery since it's tricky to test depending on the platform/features
         // we're testing with.
-        let skip = ["help", "version", "celsius", "battery", "generate_schema"];
+        let skip = [
+            "help",
+            "version",
+            "celsius",
+            "battery",
+            "generate_schema",
+            "check_config",
+            "print_default_config",
+            "batch",
+            "force_tty",
+        ];
 
         for arg in app.get_arguments().collect::<Vec<_>>() {
             let arg_name = arg
@@ -1219,7 +2428,7 @@ mod test {
                         .widget_states
                         .iter()
                         .zip(testing_app.states.proc_state.widget_states.iter())
-                        .all(|(a, b)| (a.1.test_equality(b.1)))
+                        .all(|(a, b)| a.1.test_equality(b.1))
                 {
                     panic!("failed on {arg_name}");
                 }
@@ -41,7 +41,7 @@ const CHART_WIDGET_POSITIONS: [&str; 9] = [
 ];
 
 /// Represents the arguments that can be passed in to bottom.
-#[derive(Parser, Debug)]
+#[derive(Parser, Clone, Debug)]
 #[command(
     name = crate_name!(),
     version = VERSION,
@@ -72,6 +72,9 @@ pub struct BottomArgs {
     #[command(flatten)]
     pub network: NetworkArgs,
 
+    #[command(flatten)]
+    pub filter: FilterArgs,
+
     #[cfg(feature = "battery")]
     #[command(flatten)]
     pub battery: BatteryArgs,
@@ -109,6 +112,29 @@ pub struct GeneralArgs {
     )]
     pub basic: bool,
 
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Prints a plain-text summary every refresh instead of running the UI.",
+        long_help = "Prints a plain-text summary of the system and its processes to stdout every refresh \
+                    instead of running the terminal UI, similar to 'top -b'. This is meant for logging or \
+                    piping to other programs like grep. The refresh rate can be set with --rate, and the \
+                    number of summaries with --iterations."
+    )]
+    pub batch: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        alias = "check-config",
+        help = "Checks the config file for problems and exits.",
+        long_help = "Checks the config file for problems and exits, without starting bottom. This reports \
+                    syntax errors, unknown keys, invalid values, and invalid layouts, along with where they \
+                    are in the file when possible, and exits with an error if any were found. Any other \
+                    arguments are checked as well."
+    )]
+    pub check_config: bool,
+
     #[arg(
         short = 'C',
         long,
@@ -210,6 +236,36 @@ pub struct GeneralArgs {
     )]
     pub dot_marker: bool,
 
+    #[arg(
+        long,
+        value_name = "ACTION",
+        ignore_case = true,
+        help = "Sets what double-clicking a table entry does.",
+        long_help = indoc! {
+            "Sets what double-clicking an entry in the process, disk, or temperature widgets does. The actions are:
+
+            - collapse: collapses or expands the entry's children, if the process widget is in tree mode. This is \
+            the default.
+            - details: shows the details of the process. Disk and temperature entries have no more details to \
+            show, so their widget is expanded instead.
+            - kill: opens the dialog to kill the process.
+            - none: does nothing."
+        },
+        value_parser = ["collapse", "details", "kill", "none"],
+    )]
+    pub double_click_action: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        help = "Sets where the state is dumped to when bottom is sent SIGUSR1.",
+        long_help = "Sets the file the current state is dumped to as JSON when bottom is sent SIGUSR1, which \
+                    is only supported on Unix-like systems. Defaults to 'bottom-<PID>.json' in the temporary \
+                    directory."
+    )]
+    pub dump_path: Option<PathBuf>,
+
     #[arg(
         short = 'e',
         long,
@@ -219,12 +275,207 @@ pub struct GeneralArgs {
     )]
     pub expanded: bool,
 
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        aliases = ["force-tty", "no_tty_check", "no-tty-check"],
+        help = "Runs the UI even if the output isn't a terminal.",
+        long_help = "Runs the terminal UI even if stdout isn't a terminal. Otherwise, bottom falls back to \
+                    printing plain-text summaries like --batch when its output is piped or redirected."
+    )]
+    pub force_tty: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Fills the area under graph lines with gradients.",
+        long_help = "Fills the area under the lines of the CPU, memory, and network graphs with vertical gradients \
+                    of each line's colour. This needs a terminal with 24-bit colour support, which is detected \
+                    with the COLORTERM environment variable; otherwise, graphs are drawn as usual."
+    )]
+    pub graph_gradients: bool,
+
+    #[arg(
+        long,
+        value_name = "STYLE",
+        ignore_case = true,
+        help = "Sets how graph lines are drawn.",
+        long_help = indoc! {
+            "Sets how the lines of the CPU, memory, and network graphs are drawn. The styles are:
+
+            - braille: braille patterns, which are the most detailed. This is the default.
+            - dot: a dot per point.
+            - block: a full block per point.
+            - line: box-drawing lines, for terminals or fonts where braille renders poorly.
+
+            This takes precedence over --dot_marker."
+        },
+        value_parser = ["braille", "dot", "block", "line"],
+    )]
+    pub graph_style: Option<String>,
+
     #[arg(long, action = ArgAction::SetTrue, help = "Hides spacing between table headers and entries.")]
     pub hide_table_gap: bool,
 
     #[arg(long, action = ArgAction::SetTrue, help = "Hides the time scale from being shown.")]
     pub hide_time: bool,
 
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Serves current data over HTTP at the given address.",
+        long_help = "Starts a lightweight HTTP server at the given address (e.g. 127.0.0.1:8080) while bottom \
+                    is running. The server exposes '/metrics.json', which returns the most recently collected \
+                    data as JSON, and '/healthz', which can be used to check if bottom is still running."
+    )]
+    pub http: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "TIME",
+        help = "Sets the refresh rate while unfocused or idle.",
+        long_help = "Sets how often data is refreshed while the terminal is unfocused or there has been no \
+                    input for a minute. Either a number in milliseconds or a 'human duration' (e.g. 5s, 1m). \
+                    Any input or the terminal regaining focus will return to the normal refresh rate. Must be \
+                    at least as large as the refresh rate. Disabled by default."
+    )]
+    pub idle_rate: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "COUNT",
+        help = "Exits after printing the given number of summaries in batch mode.",
+        long_help = "Exits after printing the given number of summaries in batch mode, which is used with \
+                    --batch or if stdout isn't a terminal. Otherwise, summaries are printed until bottom is \
+                    stopped. This has no effect when running the UI."
+    )]
+    pub iterations: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "LOCALE",
+        help = "Sets the language of the interface.",
+        long_help = "Sets the language of the interface, such as 'de' or 'es_MX'. Supported languages are \
+                    'en', 'de', and 'es'. Defaults to the language set by the LC_ALL, LC_MESSAGES, or LANG \
+                    environment variables, or English if none are set or the language isn't supported."
+    )]
+    pub locale: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Logs to the given file.",
+        long_help = "Logs what bottom is doing to the given file, which is moved to '<PATH>.1' once it reaches \
+                    10 MiB, keeping up to 3 old files. How much is logged is set with --log_level."
+    )]
+    pub log_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "FILTER",
+        requires = "log_file",
+        help = "Sets how much is logged.",
+        long_help = "Sets how much is logged to the --log_file, as one of 'off', 'error', 'warn', 'info', \
+                    'debug', or 'trace', optionally followed by levels for specific modules. For example, \
+                    'warn,bottom::data_collection=debug'. Defaults to 'info'. The overall level can be \
+                    changed while running with the log_level key binding (F4 by default)."
+    )]
+    pub log_level: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "TIME",
+        help = "How far back downsampled data will be stored up to.",
+        long_help = "How far back downsampled data will be stored up to. Either a number in milliseconds or a \
+                    'human duration' (e.g. 1d, 12h). Rather than being discarded, data older than the retention \
+                    duration is downsampled into one point per minute, keeping its average, minimum, and maximum, \
+                    and kept until it is older than this duration, which allows for zooming out much \
+                    further with bounded memory usage. Must be at least as large as the retention duration. \
+                    Disabled by default."
+    )]
+    pub long_term_retention: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "MIB",
+        help = "Caps how much memory graph history may use, in MiB.",
+        long_help = "Caps roughly how much memory the history behind the graphs may use, in MiB. If the \
+                    retention would take more than this, the oldest data is dropped early. Unlimited by \
+                    default."
+    )]
+    pub max_history_memory: Option<u64>,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Uses larger click targets for touchscreens.",
+        long_help = "Uses larger click and touch targets, such as taller table rows, which makes bottom easier \
+                    to use with a touchscreen (e.g. in Termux on Android)."
+    )]
+    pub mobile: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        alias = "no-alt-screen",
+        help = "Draws inline instead of on the alternate screen.",
+        long_help = "Draws inline in the normal screen and its scrollback instead of on the alternate screen, \
+                    like `watch`, so the last frame stays visible after quitting. The height is fixed to the \
+                    terminal's height at startup."
+    )]
+    pub no_alt_screen: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Pauses data collection while unfocused.",
+        long_help = "Pauses data collection entirely while the terminal is unfocused, and resumes it once focus \
+                    returns. Useful when running bottom in a background pane on battery. Requires a terminal \
+                    that reports focus changes."
+    )]
+    pub pause_on_blur: bool,
+
+    #[arg(
+        long,
+        value_name = "PRESET",
+        help = "Uses a built-in layout.",
+        long_help = indoc! {
+            "Uses a built-in layout instead of the default one, or the one in the config file. The presets are:
+
+            - minimal: only the CPU, memory, and process widgets.
+            - netadmin: a large network graph, along with processes, CPU, and disk usage.
+            - battery: battery and temperature info, along with CPU and process usage."
+        },
+        value_parser = [
+            "minimal",
+            "netadmin",
+            #[cfg(feature = "battery")]
+            "battery",
+        ],
+    )]
+    pub preset: Option<String>,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        alias = "print-default-config",
+        help = "Prints the default config file and exits.",
+        long_help = "Prints the default config file, with every setting commented out, and exits. This is the \
+                    same file bottom creates if there isn't one, and can be redirected to a file to start a \
+                    new config from."
+    )]
+    pub print_default_config: bool,
+
+    #[arg(
+        long,
+        value_name = "PROFILE",
+        help = "Uses a profile from the config file.",
+        long_help = "Uses the profile with this name from the config file, which overrides the settings \
+                    outside of it. Profiles are defined as tables like [profile.laptop], and can also be \
+                    switched between while running."
+    )]
+    pub profile: Option<String>,
+
     #[arg(
         short = 'r',
         long,
@@ -246,6 +497,16 @@ pub struct GeneralArgs {
     )]
     pub retention: Option<String>,
 
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Saves the session state on exit and restores it on the next launch.",
+        long_help = "Saves runtime state - such as the sort column, process mode, search queries, zoom levels, \
+                    and the selected and expanded widget - to a state file on exit, and restores it on the \
+                    next launch. The state file is kept separate from the config file."
+    )]
+    pub save_session: bool,
+
     #[arg(
         long,
         action = ArgAction::SetTrue,
@@ -253,6 +514,76 @@ pub struct GeneralArgs {
     )]
     pub show_table_scroll_position: bool,
 
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Shows a scrollbar on table widgets with more entries than can fit."
+    )]
+    pub show_table_scrollbar: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Draws a line between the rows of table widgets.",
+        long_help = "Draws a line between the rows of the process, disk, and temperature widgets, which \
+                    makes wide tables easier to follow at the cost of showing fewer rows."
+    )]
+    pub table_row_separators: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Alternates the background of rows in table widgets.",
+        long_help = "Gives every other row of the process, disk, and temperature widgets a different \
+                    background, which makes wide tables easier to follow. The background can be set with \
+                    'stripes' under [styles.tables] in the config file."
+    )]
+    pub table_stripes: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Shows a status bar with a summary of the system.",
+        long_help = "Shows a one-line status bar with the hostname, load average, CPU usage, memory usage, \
+                    and swap usage, regardless of which widgets are shown. Its position and fields can be \
+                    set in the config file."
+    )]
+    pub status_bar: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Shows a header of htop-style meters above the layout.",
+        long_help = "Shows a compact header of htop-style meters above the layout, with bars for CPU, memory, \
+                    and swap usage along with the load average and uptime, regardless of which widgets are \
+                    shown. Its meters can be set in the config file."
+    )]
+    pub meter_header: bool,
+
+    #[arg(
+        long,
+        value_name = "PROCESSES",
+        num_args = 0..=1,
+        default_missing_value = "1000",
+        hide = true,
+        help = "Runs using synthetic data for stress testing.",
+        long_help = "Runs using synthetic data with the given number of processes (defaults to 1000) instead of \
+                    collecting any data, for measuring the performance of converting and drawing data. The tick \
+                    rate can be set with --rate."
+    )]
+    pub stress: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "TICKS",
+        requires = "stress",
+        hide = true,
+        help = "Exits after the given number of ticks when stress testing.",
+        long_help = "Exits after the given number of ticks when stress testing, and prints how long recent \
+                    ticks took."
+    )]
+    pub stress_ticks: Option<u64>,
+
     #[arg(
         short = 'd',
         long,
@@ -263,6 +594,16 @@ pub struct GeneralArgs {
                     defaults to 15s."
     )]
     pub time_delta: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        help = "How times on graphs are shown.",
+        long_help = "How times on graph axes and the frozen indicator are shown. Takes 'relative' for how \
+                    long ago they were (e.g. 60s), 'clock' for the time of day (e.g. 14:05:30), or a \
+                    custom time of day format like '[hour]:[minute]'. Defaults to 'relative'."
+    )]
+    pub time_format: Option<String>,
 }
 
 /// Process arguments/config options.
@@ -287,6 +628,18 @@ pub struct ProcessArgs {
     )]
     pub current_usage: bool,
 
+    #[arg(
+        long,
+        value_name = "COLUMN[:ORDER]",
+        alias = "default-sort",
+        help = "Sets the column processes are sorted by at first.",
+        long_help = "Sets the column processes are sorted by at first, along with an optional order of \
+                    'asc' or 'desc', like 'mem:desc'. Columns are named the same way as in the \
+                    '[processes]' section of the config file. If no order is given, the column's usual \
+                    order is used."
+    )]
+    pub default_sort: Option<String>,
+
     // TODO: Disable this on Windows?
     #[arg(
         long,
@@ -305,6 +658,16 @@ pub struct ProcessArgs {
     )]
     pub group_processes: bool,
 
+    #[arg(
+        long,
+        value_name = "COUNT",
+        help = "Only keeps the top processes by CPU usage if there are more than this.",
+        long_help = "Sets a soft limit on how many processes are kept each refresh. If there are more, \
+                    only this many are kept, picking the ones using the most CPU. This bounds bottom's own \
+                    usage on systems with very many processes. Unlimited by default."
+    )]
+    pub max_processes: Option<u64>,
+
     #[arg(
         long,
         action = ArgAction::SetTrue,
@@ -331,6 +694,16 @@ pub struct ProcessArgs {
     )]
     pub tree: bool,
 
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Shows the totals of each branch on its parent in tree mode by default.",
+        long_help = "Shows the totals of each branch on its parent in tree mode by default. Parents then show the \
+                    combined CPU, memory, and I/O usage of themselves and all of their descendants, rather than just \
+                    their own. Collapsed processes always show the totals of their branch."
+    )]
+    pub tree_totals: bool,
+
     #[arg(
         short = 'n',
         long,
@@ -499,6 +872,61 @@ pub struct NetworkArgs {
     pub use_old_network_legend: bool,
 }
 
+/// Filter arguments/config options.
+#[derive(Args, Clone, Debug, Default)]
+#[command(next_help_heading = "Filter Options", rename_all = "snake_case")]
+pub struct FilterArgs {
+    #[arg(
+        long,
+        value_name = "NAMES",
+        value_delimiter = ',',
+        help = "Only shows disks with names containing one of these.",
+        long_help = "Only shows disks with names containing one of these comma-separated names, ignoring case. \
+                    Overrides 'disk.name_filter' in the config file."
+    )]
+    pub disk_filter: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        value_name = "QUERY",
+        help = "Starts with the process widgets filtered by a search query.",
+        long_help = "Starts with the process widgets filtered by a search query, such as \
+                    \"name=java user=tomcat\", which uses the same syntax as searching. Overrides any filter \
+                    set on a process widget in the config file, and any search restored from the last session."
+    )]
+    pub filter: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "MOUNTS",
+        value_delimiter = ',',
+        help = "Only shows disks with mount points containing one of these.",
+        long_help = "Only shows disks with mount points containing one of these comma-separated mount points, \
+                    ignoring case. Overrides 'disk.mount_filter' in the config file."
+    )]
+    pub mount_filter: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        value_name = "INTERFACES",
+        value_delimiter = ',',
+        help = "Only shows network interfaces with names containing one of these.",
+        long_help = "Only shows network interfaces with names containing one of these comma-separated names, \
+                    ignoring case. Overrides 'network.interface_filter' in the config file."
+    )]
+    pub net_filter: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        value_name = "SENSORS",
+        value_delimiter = ',',
+        help = "Only shows temperature sensors with names containing one of these.",
+        long_help = "Only shows temperature sensors with names containing one of these comma-separated names, \
+                    ignoring case. Overrides 'temperature.sensor_filter' in the config file."
+    )]
+    pub temp_filter: Option<Vec<String>>,
+}
+
 /// Battery arguments/config options.
 #[cfg(feature = "battery")]
 #[derive(Args, Clone, Debug, Default)]
@@ -528,20 +956,31 @@ pub struct GpuArgs {
 #[derive(Args, Clone, Debug, Default)]
 #[command(next_help_heading = "Style Options", rename_all = "snake_case")]
 pub struct StyleArgs {
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Only draws ASCII characters.",
+        long_help = "Only draws ASCII characters, using them in place of box-drawing characters for borders \
+                    and Braille for graphs. This is used by default if the terminal or locale doesn't seem \
+                    to support Unicode, like serial consoles."
+    )]
+    pub ascii: bool,
+
+    #[arg(
+        long,
+        value_name = "DEPTH",
+        help = "Sets how many colors the terminal can show.",
+        long_help = "Sets how many colors the terminal can show. With '16', every color is shown as the \
+                    closest of the basic 16 ANSI colors, and 'truecolor' allows graph gradients. By default, \
+                    this is detected from COLORTERM, TERM, and the terminfo database."
+    )]
+    pub color_depth: Option<String>,
+
     #[arg(
         long,
         value_name = "SCHEME",
-        value_parser = [
-            "default",
-            "default-light",
-            "gruvbox",
-            "gruvbox-light",
-            "nord",
-            "nord-light",
-        ],
-        hide_possible_values = true,
         help = indoc! {
-            "Use a built-in color theme, use '--help' for info on the colors. [possible values: default, default-light, gruvbox, gruvbox-light, nord, nord-light]",
+            "Use a built-in color theme or a base16 YAML file, use '--help' for info on the colors. [possible values: default, default-light, gruvbox, gruvbox-light, nord, nord-light, dracula, solarized, high-contrast, <FILE>.yaml]",
         },
         long_help = indoc! {
             "Use a pre-defined color theme. Currently supported themes are:
@@ -550,7 +989,12 @@ pub struct StyleArgs {
             - gruvbox       (a bright theme with 'retro groove' colors)
             - gruvbox-light (gruvbox but adjusted for lighter backgrounds)
             - nord          (an arctic, north-bluish color palette)
-            - nord-light    (nord but adjusted for lighter backgrounds)"
+            - nord-light    (nord but adjusted for lighter backgrounds)
+            - dracula       (a dark theme with vivid colors)
+            - solarized     (the dark variant of solarized)
+            - high-contrast (bright basic colors, bold text, and thick borders)
+
+            A path to a base16 YAML scheme ending in '.yaml' or '.yml' can also be given to use its palette."
         }
     )]
     pub theme: Option<String>,
@@ -0,0 +1,221 @@
+//! Checking a config file for problems without starting bottom, for
+//! `--check_config`.
+
+use std::{fs, ops::Range};
+
+use anyhow::{bail, Context};
+use toml_edit::{ImDocument, Item, Value};
+
+use super::{
+    args::BottomArgs,
+    config::unused_keys::{deserialize_tracking_unused, PathSegment, UnusedKey},
+    get_config_path, init_app, Config,
+};
+
+/// Returns the 1-indexed line and column of a byte offset in `text`.
+fn line_col(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+        + 1;
+
+    (line, column)
+}
+
+/// Returns the number of single character insertions, deletions, or
+/// substitutions needed to turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != *b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Returns the expected key closest to `key`, if one is close enough that it
+/// was likely what was meant.
+fn closest_key(key: &str, expected: &[&'static str]) -> Option<&'static str> {
+    let key = key.to_lowercase();
+    let max_distance = (key.chars().count() / 3).max(1);
+
+    expected
+        .iter()
+        .map(|&candidate| (edit_distance(&key, candidate), candidate))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Returns where the key at `path` is in `document`, if it can be found.
+fn key_span(document: &ImDocument<&str>, path: &[PathSegment]) -> Option<Range<usize>> {
+    let mut item = document.as_item().clone();
+    let mut span = None;
+
+    for segment in path {
+        item = match segment {
+            PathSegment::Key(key) => {
+                let (key, value) = item.as_table_like()?.get_key_value(key)?;
+                span = key.span();
+                value.clone()
+            }
+            PathSegment::Index(index) => match &item {
+                Item::ArrayOfTables(tables) => Item::Table(tables.get(*index)?.clone()),
+                Item::Value(Value::Array(array)) => Item::Value(array.get(*index)?.clone()),
+                _ => return None,
+            },
+        };
+    }
+
+    span
+}
+
+/// Describes an unused key, along with where it is and what may have been
+/// meant instead.
+fn describe_unused_key(key: &UnusedKey, text: &str, document: &ImDocument<&str>) -> String {
+    let mut description = format!("Unknown key '{}'", key.display_path());
+
+    if let Some(span) = key_span(document, &key.path) {
+        let (line, column) = line_col(text, span.start);
+        description.push_str(&format!(" at line {line}, column {column}"));
+    }
+
+    if let Some(suggestion) = key.name().and_then(|name| closest_key(name, key.expected)) {
+        description.push_str(&format!(" - did you mean '{suggestion}'?"));
+    }
+
+    description
+}
+
+/// Checks the config file bottom would use for problems, such as syntax
+/// errors, unknown keys, invalid values, and invalid layouts, printing any
+/// found. Command-line arguments are checked along with it.
+///
+/// This returns an error if there were any problems.
+pub(crate) fn check_config(args: BottomArgs) -> anyhow::Result<()> {
+    let Some(path) = get_config_path(args.general.config_location.as_deref()) else {
+        bail!("bottom couldn't find a location to read a config file from.");
+    };
+
+    if !path.exists() {
+        init_app(args, Config::default())?;
+        println!(
+            "There is no config file at '{}', so one with the default settings would be created.",
+            path.display()
+        );
+
+        return Ok(());
+    }
+
+    let text = fs::read_to_string(&path).with_context(|| {
+        format!(
+            "bottom couldn't read the config file at '{}'.",
+            path.display()
+        )
+    })?;
+
+    // Syntax errors and invalid types are reported by toml_edit, along with where
+    // they are.
+    let deserializer = toml_edit::de::Deserializer::parse(text.as_str())?;
+    let (config, unused): (Config, _) = deserialize_tracking_unused(deserializer)?;
+
+    let document = ImDocument::parse(text.as_str())?;
+    let mut num_problems = 0;
+    for key in &unused {
+        eprintln!("{}", describe_unused_key(key, &text, &document));
+        num_problems += 1;
+    }
+
+    // Included files and the selected profile are merged in as they would be on
+    // startup, which also checks them.
+    let config = if config.include.is_some() || args.general.profile.is_some() {
+        Config::parse(&text, &path, args.general.profile.as_deref())
+    } else {
+        Ok(config)
+    };
+
+    // Anything else, like invalid values or layouts, is caught when setting up
+    // the app as it would be on startup.
+    if let Err(err) = config.and_then(|config| init_app(args, config).map(|_| ())) {
+        eprintln!("{err:#}");
+        num_problems += 1;
+    }
+
+    if num_problems > 0 {
+        let problems = if num_problems == 1 {
+            "problem"
+        } else {
+            "problems"
+        };
+        bail!("found {num_problems} {problems} in '{}'.", path.display());
+    }
+
+    println!("No problems found in '{}'.", path.display());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn line_cols() {
+        let text = "[flags]\nrate = 1000\n";
+
+        assert_eq!(line_col(text, 0), (1, 1));
+        assert_eq!(line_col(text, 8), (2, 1));
+        assert_eq!(line_col(text, 15), (2, 8));
+    }
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("rate", "rate"), 0);
+        assert_eq!(edit_distance("rat", "rate"), 1);
+        assert_eq!(edit_distance("colour", "color"), 1);
+        assert_eq!(edit_distance("tempreature", "temperature"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn closest_keys() {
+        let expected = &["rate", "regex", "retention"];
+
+        assert_eq!(closest_key("rat", expected), Some("rate"));
+        assert_eq!(closest_key("Regex", expected), Some("regex"));
+        assert_eq!(closest_key("unrelated", expected), None);
+    }
+
+    #[test]
+    fn unused_key_locations() {
+        let text = "[flags]\nrate = 1000\n\n[[row]]\nratio = 1\n  rato = 2\n";
+        let document = ImDocument::parse(text).unwrap();
+        let key = UnusedKey {
+            path: vec![
+                PathSegment::Key("row".to_string()),
+                PathSegment::Index(0),
+                PathSegment::Key("rato".to_string()),
+            ],
+            expected: &["ratio", "child"],
+        };
+
+        assert_eq!(
+            describe_unused_key(&key, text, &document),
+            "Unknown key 'row[0].rato' at line 6, column 3 - did you mean 'ratio'?"
+        );
+    }
+}
@@ -1,36 +1,98 @@
+pub mod basic;
 pub mod cpu;
 pub mod disk;
 pub mod flags;
 mod ignore_list;
+pub(crate) mod include;
+pub mod keybinds;
 pub mod layout;
+pub mod meter_header;
+pub mod mouse;
 pub mod network;
+pub mod plugins;
 pub mod process;
+pub(crate) mod profile;
+pub mod status_bar;
 pub mod style;
 pub mod temperature;
+pub(crate) mod unused_keys;
 
+use std::{collections::BTreeMap, path::Path};
+
+use basic::BasicConfig;
 use disk::DiskConfig;
 use flags::FlagConfig;
+use keybinds::KeybindsConfig;
+use meter_header::MeterHeaderConfig;
+use mouse::MouseConfig;
 use network::NetworkConfig;
+use plugins::PluginsConfig;
+use profile::ProfileConfig;
 use serde::{Deserialize, Serialize};
+use status_bar::StatusBarConfig;
 use style::StyleConfig;
 use temperature::TempConfig;
 
 pub use self::ignore_list::IgnoreList;
-use self::{cpu::CpuConfig, layout::Row, process::ProcessesConfig};
+use self::{
+    cpu::CpuConfig,
+    layout::{Page, Row},
+    process::ProcessesConfig,
+};
 
 /// Overall config for `bottom`.
 #[derive(Clone, Debug, Default, Deserialize)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
 #[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
 pub struct Config {
+    /// Other config files to merge into this one.
+    pub(crate) include: Option<Vec<String>>,
     pub(crate) flags: Option<FlagConfig>,
     pub(crate) styles: Option<StyleConfig>,
     pub(crate) row: Option<Vec<Row>>,
+    pub(crate) page: Option<Vec<Page>>,
     pub(crate) processes: Option<ProcessesConfig>,
     pub(crate) disk: Option<DiskConfig>,
     pub(crate) temperature: Option<TempConfig>,
     pub(crate) network: Option<NetworkConfig>,
     pub(crate) cpu: Option<CpuConfig>,
+    pub(crate) basic: Option<BasicConfig>,
+    pub(crate) keybinds: Option<KeybindsConfig>,
+    pub(crate) status_bar: Option<StatusBarConfig>,
+    pub(crate) meter_header: Option<MeterHeaderConfig>,
+    pub(crate) mouse: Option<MouseConfig>,
+    #[cfg_attr(not(feature = "plugins"), allow(dead_code))]
+    pub(crate) plugins: Option<PluginsConfig>,
+    /// Named profiles, which override the other settings when selected.
+    pub(crate) profile: Option<BTreeMap<String, ProfileConfig>>,
+}
+
+impl Config {
+    /// Parses `text`, the contents of the config file at `path`, merging in any
+    /// files it includes, and then the profile named `profile` if given.
+    pub(crate) fn parse(text: &str, path: &Path, profile: Option<&str>) -> anyhow::Result<Config> {
+        let config: Config = toml_edit::de::from_str(text)?;
+
+        // Keep the common case of no includes or profiles as simple as possible.
+        if config.include.is_none() && profile.is_none() {
+            return Ok(config);
+        }
+
+        let mut document = include::read_document(text, path)?;
+        if let Some(profile) = profile {
+            profile::apply_profile(&mut document, profile)?;
+        }
+
+        Ok(toml_edit::de::from_document(document)?)
+    }
+
+    /// Returns the names of the profiles in the config, in order.
+    pub(crate) fn profile_names(&self) -> Vec<String> {
+        self.profile
+            .as_ref()
+            .map(|profiles| profiles.keys().cloned().collect())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
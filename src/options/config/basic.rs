@@ -0,0 +1,70 @@
+use serde::Deserialize;
+
+/// A table widget that can be shown in basic mode.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum BasicTable {
+    #[serde(alias = "process", alias = "processes")]
+    Proc,
+    #[serde(alias = "disks")]
+    Disk,
+    #[serde(alias = "temperature")]
+    Temp,
+    #[serde(alias = "batt")]
+    Battery,
+}
+
+impl BasicTable {
+    /// The tables shown if none are set, in order.
+    pub(crate) const DEFAULT: [BasicTable; 4] = [
+        BasicTable::Disk,
+        BasicTable::Proc,
+        BasicTable::Temp,
+        BasicTable::Battery,
+    ];
+}
+
+/// Basic mode settings.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub(crate) struct BasicConfig {
+    /// The tables that can be switched between, in order. Defaults to disks,
+    /// processes, temperatures, and batteries. Batteries are only shown if
+    /// the `battery` flag is enabled.
+    pub(crate) tables: Option<Vec<BasicTable>>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty_basic() {
+        let generated: BasicConfig = toml_edit::de::from_str("").unwrap();
+        assert_eq!(generated, BasicConfig::default());
+    }
+
+    #[test]
+    fn basic_tables() {
+        let config = r#"
+            tables = ["process", "temp", "disk"]
+        "#;
+
+        let generated: BasicConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            generated.tables,
+            Some(vec![BasicTable::Proc, BasicTable::Temp, BasicTable::Disk])
+        );
+    }
+
+    #[test]
+    fn invalid_basic_table() {
+        let config = r#"
+            tables = ["cpu"]
+        "#;
+
+        assert!(toml_edit::de::from_str::<BasicConfig>(config).is_err());
+    }
+}
@@ -17,6 +17,14 @@ pub(crate) struct DiskConfig {
     /// A list of disk widget columns.
     #[serde(default)]
     pub(crate) columns: Vec<DiskColumn>, // TODO: make this more composable(?) in the future, we might need to rethink how it's done for custom widgets
+
+    /// Mount points that are always shown first in the disk table, in this order.
+    #[serde(default)]
+    pub(crate) pinned: Vec<String>,
+
+    /// The mount point whose usage is shown as a gauge in basic mode. Defaults to
+    /// the first pinned mount point.
+    pub(crate) focus: Option<String>,
 }
 
 #[cfg(test)]
@@ -36,6 +44,17 @@ mod test {
         toml_edit::de::from_str::<DiskConfig>(config).expect("Should succeed!");
     }
 
+    #[test]
+    fn pinned_and_focus_settings() {
+        let config = r#"
+            pinned = ["/", "/home"]
+            focus = "/home"
+        "#;
+        let generated: DiskConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(generated.pinned, ["/", "/home"]);
+        assert_eq!(generated.focus.as_deref(), Some("/home"));
+    }
+
     #[test]
     fn bad_disk_column_settings() {
         let config = r#"columns = ["diskk"]"#;
@@ -12,6 +12,7 @@ pub(crate) struct FlagConfig {
     pub(crate) rate: Option<StringOrNum>,
     pub(crate) cpu_left_legend: Option<bool>,
     pub(crate) current_usage: Option<bool>,
+    pub(crate) default_sort: Option<String>,
     pub(crate) unnormalized_cpu: Option<bool>,
     pub(crate) group_processes: Option<bool>,
     pub(crate) case_sensitive: Option<bool>,
@@ -34,6 +35,7 @@ pub(crate) struct FlagConfig {
     pub(crate) memory_legend: Option<String>,
     pub(crate) process_memory_as_value: Option<bool>,
     pub(crate) tree: Option<bool>,
+    pub(crate) tree_totals: Option<bool>,
     pub(crate) show_table_scroll_position: Option<bool>,
     pub(crate) process_command: Option<bool>,
     pub(crate) disable_advanced_kill: Option<bool>,
@@ -43,5 +45,28 @@ pub(crate) struct FlagConfig {
     pub(crate) disable_gpu: Option<bool>,
     pub(crate) enable_cache_memory: Option<bool>,
     pub(crate) retention: Option<StringOrNum>,
+    pub(crate) long_term_retention: Option<StringOrNum>,
     pub(crate) average_cpu_row: Option<bool>,
+    pub(crate) http: Option<String>,
+    pub(crate) idle_rate: Option<StringOrNum>,
+    pub(crate) pause_on_blur: Option<bool>,
+    pub(crate) mobile: Option<bool>,
+    pub(crate) no_alt_screen: Option<bool>,
+    pub(crate) save_session: Option<bool>,
+    pub(crate) preset: Option<String>,
+    pub(crate) graph_gradients: Option<bool>,
+    pub(crate) graph_style: Option<String>,
+    pub(crate) status_bar: Option<bool>,
+    pub(crate) meter_header: Option<bool>,
+    pub(crate) show_table_scrollbar: Option<bool>,
+    pub(crate) table_stripes: Option<bool>,
+    pub(crate) table_row_separators: Option<bool>,
+    pub(crate) double_click_action: Option<String>,
+    pub(crate) dump_path: Option<String>,
+    pub(crate) locale: Option<String>,
+    pub(crate) time_format: Option<String>,
+    pub(crate) max_history_memory: Option<u64>,
+    pub(crate) max_processes: Option<u64>,
+    pub(crate) ascii: Option<bool>,
+    pub(crate) color_depth: Option<StringOrNum>,
 }
@@ -0,0 +1,243 @@
+//! Merging config files listed under `include` into the config file that lists
+//! them.
+//!
+//! Included files are merged in the order they are listed, and then the file
+//! including them is merged on top, so its own settings take priority. Tables
+//! are merged key by key, while anything else, including arrays like `[[row]]`
+//! and `[[page]]`, is replaced entirely by whichever file is merged last.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+use toml_edit::{DocumentMut, Item, Table};
+
+use super::Config;
+
+/// The key listing the files to include.
+const INCLUDE_KEY: &str = "include";
+
+/// Parses `text`, the contents of the config file at `path`, into a document
+/// with all the files it includes merged in.
+pub(crate) fn read_document(text: &str, path: &Path) -> anyhow::Result<DocumentMut> {
+    let mut including = vec![canonical_path(path)];
+
+    parse_document(text, path, &mut including)
+}
+
+/// Parses `text`, the contents of the config file at `path`, into a document
+/// with everything it includes merged in. `including` is the chain of files
+/// currently being included, to catch files that end up including themselves.
+fn parse_document(
+    text: &str, path: &Path, including: &mut Vec<PathBuf>,
+) -> anyhow::Result<DocumentMut> {
+    // Every file has to be a valid config on its own, and checking that before
+    // merging means any errors point to where they are in the file.
+    let config: Config = toml_edit::de::from_str(text)?;
+    let mut document: DocumentMut = text.parse()?;
+    document.remove(INCLUDE_KEY);
+
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let mut merged = DocumentMut::new();
+
+    for include in config.include.unwrap_or_default() {
+        let include_path = resolve_path(&include, dir);
+        let canonical = canonical_path(&include_path);
+
+        if including.contains(&canonical) {
+            bail!(
+                "'{}' includes '{}', which ends up including itself.",
+                path.display(),
+                include_path.display()
+            );
+        }
+
+        let include_text = fs::read_to_string(&include_path).with_context(|| {
+            format!(
+                "bottom couldn't read '{}', which is included by '{}'.",
+                include_path.display(),
+                path.display()
+            )
+        })?;
+
+        including.push(canonical);
+        let mut included =
+            parse_document(&include_text, &include_path, including).with_context(|| {
+                format!(
+                    "there is a problem with '{}', which is included by '{}'.",
+                    include_path.display(),
+                    path.display()
+                )
+            })?;
+        including.pop();
+
+        merge(
+            merged.as_table_mut(),
+            std::mem::take(included.as_table_mut()),
+        );
+    }
+
+    merge(
+        merged.as_table_mut(),
+        std::mem::take(document.as_table_mut()),
+    );
+
+    Ok(merged)
+}
+
+/// Resolves the path of an included file. A leading `~` is expanded to the home
+/// directory, and relative paths are relative to `dir`, the directory of the
+/// file including it.
+fn resolve_path(include: &str, dir: &Path) -> PathBuf {
+    let path = if include == "~" {
+        dirs::home_dir()
+    } else if let Some(rest) = include.strip_prefix("~/") {
+        dirs::home_dir().map(|home| home.join(rest))
+    } else {
+        None
+    };
+
+    let path = path.unwrap_or_else(|| PathBuf::from(include));
+
+    if path.is_relative() {
+        dir.join(path)
+    } else {
+        path
+    }
+}
+
+/// Returns the canonical form of `path`, or `path` itself if that isn't
+/// possible, such as if it doesn't exist.
+fn canonical_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Merges `from` into `into`. Tables in both are merged recursively, and
+/// anything else in `from` replaces what is in `into`.
+pub(crate) fn merge(into: &mut Table, from: Table) {
+    for (key, item) in from {
+        let existing = into.remove(&key).map(Item::into_table);
+
+        match (existing, item.into_table()) {
+            (Some(Ok(mut existing)), Ok(table)) => {
+                merge(&mut existing, table);
+                into.insert(&key, Item::Table(existing));
+            }
+            (_, Ok(table)) => {
+                into.insert(&key, Item::Table(table));
+            }
+            (_, Err(item)) => {
+                into.insert(&key, item);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn merged(base: &str, top: &str) -> String {
+        let mut base: DocumentMut = base.parse().unwrap();
+        let mut top: DocumentMut = top.parse().unwrap();
+        merge(base.as_table_mut(), std::mem::take(top.as_table_mut()));
+
+        base.to_string()
+    }
+
+    #[test]
+    fn merges_tables_by_key() {
+        let config: Config = toml_edit::de::from_str(&merged(
+            "[flags]\nrate = 1000\ntree = true\n[styles.cpu]\nall_entry_color = \"red\"\n",
+            "[flags]\nrate = 2000\n[styles]\ntheme = \"gruvbox\"\n",
+        ))
+        .unwrap();
+
+        let flags = config.flags.unwrap();
+        assert_eq!(flags.rate, Some(2000u64.into()));
+        assert_eq!(flags.tree, Some(true));
+
+        let styles = config.styles.unwrap();
+        assert_eq!(styles.theme.as_deref(), Some("gruvbox"));
+        assert!(styles.cpu.is_some());
+    }
+
+    #[test]
+    fn replaces_arrays() {
+        let config: Config = toml_edit::de::from_str(&merged(
+            "[[row]]\n[[row.child]]\ntype = \"cpu\"\n[[row]]\n[[row.child]]\ntype = \"mem\"\n",
+            "[[row]]\n[[row.child]]\ntype = \"proc\"\n",
+        ))
+        .unwrap();
+
+        assert_eq!(config.row.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn merges_inline_tables() {
+        let config: Config = toml_edit::de::from_str(&merged(
+            "flags = { rate = 1000, tree = true }\n",
+            "[flags]\nrate = 2000\n",
+        ))
+        .unwrap();
+
+        let flags = config.flags.unwrap();
+        assert_eq!(flags.rate, Some(2000u64.into()));
+        assert_eq!(flags.tree, Some(true));
+    }
+
+    #[test]
+    fn resolve_paths() {
+        let dir = Path::new("configs");
+        let absolute = std::env::temp_dir().join("colors.toml");
+
+        assert_eq!(resolve_path("colors.toml", dir), dir.join("colors.toml"));
+        assert_eq!(resolve_path(&absolute.to_string_lossy(), dir), absolute);
+
+        if let Some(home) = dirs::home_dir() {
+            assert_eq!(resolve_path("~/colors.toml", dir), home.join("colors.toml"));
+            assert_eq!(resolve_path("~", dir), home);
+        }
+    }
+
+    #[test]
+    fn includes_files() {
+        let dir = std::env::temp_dir().join(format!("btm_include_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        fs::write(
+            dir.join("colors.toml"),
+            "[flags]\nrate = 1000\ntree = true\n[styles]\ntheme = \"gruvbox\"\n",
+        )
+        .unwrap();
+
+        let path = dir.join("bottom.toml");
+        let text = "include = [\"colors.toml\"]\n[flags]\nrate = 2000\n";
+        let config = Config::parse(text, &path, None).unwrap();
+
+        let flags = config.flags.unwrap();
+        assert_eq!(flags.rate, Some(2000u64.into()));
+        assert_eq!(flags.tree, Some(true));
+        assert_eq!(config.styles.unwrap().theme.as_deref(), Some("gruvbox"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_include_cycles() {
+        let dir =
+            std::env::temp_dir().join(format!("btm_include_cycle_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        fs::write(dir.join("a.toml"), "include = [\"b.toml\"]\n").unwrap();
+        fs::write(dir.join("b.toml"), "include = [\"a.toml\"]\n").unwrap();
+
+        let path = dir.join("a.toml");
+        let err = Config::parse("include = [\"b.toml\"]\n", &path, None).unwrap_err();
+        assert!(format!("{err:#}").contains("ends up including itself"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,158 @@
+use serde::Deserialize;
+
+use crate::event::keybinds::Action;
+
+/// One key or a list of keys.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(PartialEq, Eq))]
+pub(crate) enum Keys {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Keys {
+    fn as_slice(&self) -> &[String] {
+        match self {
+            Keys::One(key) => std::slice::from_ref(key),
+            Keys::Many(keys) => keys,
+        }
+    }
+}
+
+/// Key binding configuration. Each action takes a key like `"q"`, `"F9"`, or
+/// `"ctrl+f"`, or a list of them, which replaces the action's default keys.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub(crate) struct KeybindsConfig {
+    /// Quit. Defaults to `q`; Ctrl-c always quits.
+    pub(crate) quit: Option<Keys>,
+    /// Open the process search widget, or search all widgets outside of the
+    /// process widget. Defaults to `/` and `ctrl+f`.
+    pub(crate) search: Option<Keys>,
+    /// Kill the selected process. Defaults to `F9`; `dd` always works.
+    pub(crate) kill: Option<Keys>,
+    /// Toggle tree mode in the process widget. Defaults to `F5`.
+    pub(crate) tree_toggle: Option<Keys>,
+    /// Open the process sort widget. Defaults to `F6`.
+    pub(crate) sort_menu: Option<Keys>,
+    /// Open the help menu. Defaults to `?`.
+    pub(crate) help: Option<Keys>,
+    /// Toggle expanding the selected widget. Defaults to `e`.
+    pub(crate) expand: Option<Keys>,
+    /// Freeze/unfreeze updating with new data. Defaults to `f`.
+    pub(crate) freeze: Option<Keys>,
+    /// Reset the display and any collected data. Defaults to `ctrl+r`.
+    pub(crate) reset: Option<Keys>,
+    /// Zoom in on charts, or collapse/expand a process tree branch. Defaults to `+`.
+    pub(crate) zoom_in: Option<Keys>,
+    /// Zoom out of charts, or collapse/expand a process tree branch. Defaults to `-`.
+    pub(crate) zoom_out: Option<Keys>,
+    /// Reset the zoom. Defaults to `=`.
+    pub(crate) reset_zoom: Option<Keys>,
+    /// Collapse the process tree branch the selected process is in. Defaults to `z`.
+    pub(crate) collapse_branch: Option<Keys>,
+    /// Collapse every process tree branch. Defaults to `_`.
+    pub(crate) collapse_all: Option<Keys>,
+    /// Expand every process tree branch. Defaults to `*`.
+    pub(crate) expand_all: Option<Keys>,
+    /// Toggle showing branch totals on parents in tree mode. Defaults to `T`.
+    pub(crate) tree_totals: Option<Keys>,
+    /// Toggle grouping processes by user, then by name. Defaults to `U`.
+    pub(crate) group_by_user: Option<Keys>,
+    /// Pin or unpin the selected mount to the top of the disk table. Defaults to `b`.
+    pub(crate) pin_mount: Option<Keys>,
+    /// Cycle the mount shown in the basic mode disk gauge. Defaults to `o`.
+    pub(crate) cycle_mount: Option<Keys>,
+    /// Hide or unhide the selected temperature sensor. Defaults to `v`.
+    pub(crate) hide_sensor: Option<Keys>,
+    /// Toggle showing hidden temperature sensors. Defaults to `V`.
+    pub(crate) show_hidden_sensors: Option<Keys>,
+    /// Invert the current sort. Defaults to `I`.
+    pub(crate) invert_sort: Option<Keys>,
+    /// Toggle the inspection cursor on charts. Defaults to `i`.
+    pub(crate) inspect: Option<Keys>,
+    /// Toggle showing memory usage as values or percentages. Defaults to `%`.
+    pub(crate) percentages: Option<Keys>,
+    /// Toggle the debug overlay. Defaults to `F12`.
+    pub(crate) debug_overlay: Option<Keys>,
+    /// Log more detail, going back to only errors after tracing. Defaults to `F4`.
+    pub(crate) log_level: Option<Keys>,
+    /// Show the config in use and data source diagnostics. Defaults to `ctrl+o`.
+    pub(crate) diagnostics: Option<Keys>,
+    /// Export the config in use, including layout changes, to a file. Defaults to `ctrl+s`.
+    pub(crate) export_config: Option<Keys>,
+    /// Switch to the next profile in the config. Defaults to `ctrl+p`.
+    pub(crate) next_profile: Option<Keys>,
+    /// Toggle matching case while searching. Defaults to `F1` and `alt+c`.
+    pub(crate) ignore_case: Option<Keys>,
+    /// Toggle matching the entire word while searching. Defaults to `F2` and `alt+w`.
+    pub(crate) whole_word: Option<Keys>,
+    /// Toggle using regex while searching. Defaults to `F3` and `alt+r`.
+    pub(crate) regex: Option<Keys>,
+    /// Switch to the next layout page. Defaults to `>`.
+    pub(crate) next_page: Option<Keys>,
+    /// Switch to the previous layout page. Defaults to `<`.
+    pub(crate) previous_page: Option<Keys>,
+    /// Hide the selected widget. Defaults to `x`.
+    pub(crate) hide_widget: Option<Keys>,
+    /// Show all hidden widgets. Defaults to `X`.
+    pub(crate) show_widgets: Option<Keys>,
+    /// Copy the selected table row to the clipboard. Defaults to `y`.
+    pub(crate) copy_row: Option<Keys>,
+    /// Copy the selected process's PID to the clipboard. Defaults to `Y`.
+    pub(crate) copy_pid: Option<Keys>,
+    /// Copy the selected process's command to the clipboard. Defaults to `ctrl+y`.
+    pub(crate) copy_command: Option<Keys>,
+}
+
+impl KeybindsConfig {
+    /// Returns the keys set for each action that has any.
+    pub(crate) fn overrides(&self) -> impl Iterator<Item = (Action, &[String])> {
+        [
+            (Action::Quit, &self.quit),
+            (Action::Search, &self.search),
+            (Action::Kill, &self.kill),
+            (Action::TreeToggle, &self.tree_toggle),
+            (Action::SortMenu, &self.sort_menu),
+            (Action::Help, &self.help),
+            (Action::Expand, &self.expand),
+            (Action::Freeze, &self.freeze),
+            (Action::Reset, &self.reset),
+            (Action::ZoomIn, &self.zoom_in),
+            (Action::ZoomOut, &self.zoom_out),
+            (Action::ResetZoom, &self.reset_zoom),
+            (Action::CollapseBranch, &self.collapse_branch),
+            (Action::CollapseAll, &self.collapse_all),
+            (Action::ExpandAll, &self.expand_all),
+            (Action::TreeTotals, &self.tree_totals),
+            (Action::GroupByUser, &self.group_by_user),
+            (Action::PinMount, &self.pin_mount),
+            (Action::CycleMount, &self.cycle_mount),
+            (Action::HideSensor, &self.hide_sensor),
+            (Action::ShowHiddenSensors, &self.show_hidden_sensors),
+            (Action::InvertSort, &self.invert_sort),
+            (Action::Inspect, &self.inspect),
+            (Action::Percentages, &self.percentages),
+            (Action::DebugOverlay, &self.debug_overlay),
+            (Action::LogLevel, &self.log_level),
+            (Action::Diagnostics, &self.diagnostics),
+            (Action::ExportConfig, &self.export_config),
+            (Action::NextProfile, &self.next_profile),
+            (Action::IgnoreCase, &self.ignore_case),
+            (Action::WholeWord, &self.whole_word),
+            (Action::Regex, &self.regex),
+            (Action::NextPage, &self.next_page),
+            (Action::PreviousPage, &self.previous_page),
+            (Action::HideWidget, &self.hide_widget),
+            (Action::ShowWidgets, &self.show_widgets),
+            (Action::CopyRow, &self.copy_row),
+            (Action::CopyPid, &self.copy_pid),
+            (Action::CopyCommand, &self.copy_command),
+        ]
+        .into_iter()
+        .filter_map(|(action, keys)| keys.as_ref().map(|keys| (action, keys.as_slice())))
+    }
+}
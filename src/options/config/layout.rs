@@ -1,6 +1,47 @@
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
-use crate::{app::layout_manager::*, options::OptionResult};
+use crate::{
+    app::layout_manager::*,
+    constants::*,
+    options::{config::style::StyleConfig, OptionError, OptionResult},
+};
+
+/// A built-in layout, selected with `--preset`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutPreset {
+    Minimal,
+    NetAdmin,
+    #[cfg(feature = "battery")]
+    Battery,
+}
+
+impl FromStr for LayoutPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "minimal" => Ok(LayoutPreset::Minimal),
+            "netadmin" => Ok(LayoutPreset::NetAdmin),
+            #[cfg(feature = "battery")]
+            "battery" => Ok(LayoutPreset::Battery),
+            _ => Err(format!("'{s}' is an invalid preset.")),
+        }
+    }
+}
+
+impl LayoutPreset {
+    /// The preset's layout, written like the `[[row]]` entries of a config file.
+    pub fn layout(&self) -> &'static str {
+        match self {
+            LayoutPreset::Minimal => MINIMAL_PRESET_LAYOUT,
+            LayoutPreset::NetAdmin => NETADMIN_PRESET_LAYOUT,
+            #[cfg(feature = "battery")]
+            LayoutPreset::Battery => BATTERY_PRESET_LAYOUT,
+        }
+    }
+}
 
 /// Represents a row. This has a length of some sort (optional) and a vector
 /// of children.
@@ -13,7 +54,16 @@ pub struct Row {
     pub child: Option<Vec<RowChildren>>,
 }
 
-fn new_cpu(cpu_left_legend: bool, iter_id: &mut u64) -> BottomColRow {
+/// Represents a named layout page, made up of rows like the main layout.
+#[derive(Clone, Deserialize, Debug, Serialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub struct Page {
+    pub name: String,
+    pub row: Vec<Row>,
+}
+
+fn new_cpu(cpu_left_legend: bool, iter_id: &mut u64, widget: &FinalWidget) -> BottomColRow {
     let cpu_id = *iter_id;
     *iter_id += 1;
     let legend_id = *iter_id;
@@ -23,11 +73,17 @@ fn new_cpu(cpu_left_legend: bool, iter_id: &mut u64) -> BottomColRow {
             BottomWidget::new(BottomWidgetType::CpuLegend, legend_id)
                 .canvas_with_ratio(3)
                 .parent_reflector(Some((WidgetDirection::Right, 1))),
-            BottomWidget::new(BottomWidgetType::Cpu, cpu_id).grow(Some(17)),
+            BottomWidget::new(BottomWidgetType::Cpu, cpu_id)
+                .grow(Some(17))
+                .title(widget.title.clone())
+                .styles(widget.styles.clone()),
         ])
     } else {
         BottomColRow::new(vec![
-            BottomWidget::new(BottomWidgetType::Cpu, cpu_id).grow(Some(17)),
+            BottomWidget::new(BottomWidgetType::Cpu, cpu_id)
+                .grow(Some(17))
+                .title(widget.title.clone())
+                .styles(widget.styles.clone()),
             BottomWidget::new(BottomWidgetType::CpuLegend, legend_id)
                 .canvas_with_ratio(3)
                 .parent_reflector(Some((WidgetDirection::Left, 1))),
@@ -42,8 +98,12 @@ fn new_proc_sort(sort_id: u64) -> BottomWidget {
         .parent_reflector(Some((WidgetDirection::Right, 2)))
 }
 
-fn new_proc(proc_id: u64) -> BottomWidget {
-    BottomWidget::new(BottomWidgetType::Proc, proc_id).ratio(2)
+fn new_proc(proc_id: u64, widget: &FinalWidget) -> BottomWidget {
+    BottomWidget::new(BottomWidgetType::Proc, proc_id)
+        .ratio(2)
+        .title(widget.title.clone())
+        .filter(widget.filter.clone())
+        .styles(widget.styles.clone())
 }
 
 fn new_proc_search(search_id: u64) -> BottomWidget {
@@ -73,6 +133,7 @@ impl Row {
                         let width_ratio = widget.ratio.unwrap_or(1);
                         total_col_ratio += width_ratio;
                         let widget_type = widget.widget_type.parse::<BottomWidgetType>()?;
+                        widget.check_settings(&widget_type)?;
 
                         if let Some(default_widget_type_val) = default_widget_type {
                             if *default_widget_type_val == widget_type && *default_widget_count > 0
@@ -93,7 +154,7 @@ impl Row {
 
                         children.push(match widget_type {
                             BottomWidgetType::Cpu => {
-                                BottomCol::new(vec![new_cpu(cpu_left_legend, iter_id)])
+                                BottomCol::new(vec![new_cpu(cpu_left_legend, iter_id, widget)])
                                     .ratio(width_ratio)
                             }
                             BottomWidgetType::Proc => {
@@ -103,7 +164,7 @@ impl Row {
                                 BottomCol::new(vec![
                                     BottomColRow::new(vec![
                                         new_proc_sort(*iter_id),
-                                        new_proc(proc_id),
+                                        new_proc(proc_id, widget),
                                     ])
                                     .grow(None)
                                     .total_widget_ratio(3),
@@ -116,7 +177,10 @@ impl Row {
                             _ => BottomCol::new(vec![BottomColRow::new(vec![BottomWidget::new(
                                 widget_type,
                                 *iter_id,
-                            )])])
+                            )
+                            .title(widget.title.clone())
+                            .styles(widget.styles.clone())
+                            .gauge(widget.gauge.unwrap_or(false))])])
                             .ratio(width_ratio),
                         });
                     }
@@ -129,6 +193,7 @@ impl Row {
 
                         for widget in child {
                             let widget_type = widget.widget_type.parse::<BottomWidgetType>()?;
+                            widget.check_settings(&widget_type)?;
                             *iter_id += 1;
 
                             if let Some(default_widget_type_val) = default_widget_type {
@@ -155,7 +220,7 @@ impl Row {
                                     total_col_row_ratio += col_row_height_ratio;
 
                                     col_row_children.push(
-                                        new_cpu(cpu_left_legend, iter_id)
+                                        new_cpu(cpu_left_legend, iter_id, widget)
                                             .ratio(col_row_height_ratio),
                                     );
                                 }
@@ -169,7 +234,7 @@ impl Row {
                                     col_row_children.push(
                                         BottomColRow::new(vec![
                                             new_proc_sort(*iter_id),
-                                            new_proc(proc_id),
+                                            new_proc(proc_id, widget),
                                         ])
                                         .ratio(col_row_height_ratio)
                                         .total_widget_ratio(3),
@@ -187,7 +252,10 @@ impl Row {
                                         BottomColRow::new(vec![BottomWidget::new(
                                             widget_type,
                                             *iter_id,
-                                        )])
+                                        )
+                                        .title(widget.title.clone())
+                                        .styles(widget.styles.clone())
+                                        .gauge(widget.gauge.unwrap_or(false))])
                                         .ratio(col_row_height_ratio),
                                     )
                                 }
@@ -236,6 +304,56 @@ pub struct FinalWidget {
     #[serde(rename = "type")]
     pub widget_type: String,
     pub default: Option<bool>,
+    /// A title to show instead of the widget type's usual one.
+    pub title: Option<String>,
+    /// The search query a process widget starts with.
+    pub filter: Option<String>,
+    /// Styles to use for this widget over the ones set in `[styles]`.
+    pub(crate) styles: Option<Box<StyleConfig>>,
+    /// Whether to show usage as labelled bar gauges instead of a table or graph.
+    pub gauge: Option<bool>,
+}
+
+impl FinalWidget {
+    /// Checks that the widget's type supports the settings it was given.
+    fn check_settings(&self, widget_type: &BottomWidgetType) -> OptionResult<()> {
+        if self.filter.is_some() && *widget_type != BottomWidgetType::Proc {
+            return Err(OptionError::config(format!(
+                "'filter' is only supported by process widgets, but was set on a '{}' widget.",
+                self.widget_type
+            )));
+        }
+
+        if self.gauge.is_some()
+            && !matches!(widget_type, BottomWidgetType::Mem | BottomWidgetType::Disk)
+        {
+            return Err(OptionError::config(format!(
+                "'gauge' is only supported by memory and disk widgets, but was set on a '{}' widget.",
+                self.widget_type
+            )));
+        }
+
+        if self.styles.is_some() && *widget_type == BottomWidgetType::Empty {
+            return Err(OptionError::config(
+                "'styles' isn't supported by 'empty' widgets.",
+            ));
+        }
+
+        let supports_title = match widget_type {
+            BottomWidgetType::Empty => false,
+            #[cfg(feature = "plugins")]
+            BottomWidgetType::Plugin(_) => false,
+            _ => true,
+        };
+        if self.title.is_some() && !supports_title {
+            return Err(OptionError::config(format!(
+                "'title' isn't supported by '{}' widgets.",
+                self.widget_type
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -708,4 +826,123 @@ mod test {
             Some(13)
         );
     }
+
+    #[test]
+    /// Tests that the WSL layout is valid and doesn't have temperatures.
+    fn test_default_wsl_layout() {
+        use crate::constants::DEFAULT_WSL_LAYOUT;
+
+        let rows = from_str::<Config>(DEFAULT_WSL_LAYOUT).unwrap().row.unwrap();
+        let ret_bottom_layout = test_create_layout(&rows, DEFAULT_WIDGET_ID, None, 1, false);
+
+        let widget_types: Vec<_> = ret_bottom_layout
+            .rows
+            .iter()
+            .flat_map(|row| &row.children)
+            .flat_map(|col| &col.children)
+            .flat_map(|col_row| &col_row.children)
+            .map(|widget| &widget.widget_type)
+            .collect();
+        assert!(widget_types.contains(&&BottomWidgetType::Disk));
+        assert!(!widget_types.contains(&&BottomWidgetType::Temp));
+    }
+
+    #[test]
+    /// Tests that widget settings are passed on to the right widgets.
+    fn test_widget_settings() {
+        let rows = from_str::<Config>(
+            r#"
+            [[row]]
+                [[row.child]]
+                    type="cpu"
+                    title="Cores"
+                [[row.child]]
+                    type="proc"
+                    title="Web"
+                    filter="user=www-data"
+                    [row.child.styles.tables]
+                    headers="red"
+                [[row.child]]
+                    type="disk"
+                    gauge=true
+            "#,
+        )
+        .unwrap()
+        .row
+        .unwrap();
+        let ret_bottom_layout = test_create_layout(&rows, DEFAULT_WIDGET_ID, None, 1, false);
+
+        let widgets: Vec<_> = ret_bottom_layout
+            .rows
+            .iter()
+            .flat_map(|row| &row.children)
+            .flat_map(|col| &col.children)
+            .flat_map(|col_row| &col_row.children)
+            .collect();
+
+        for widget in widgets {
+            let (title, filter) = match widget.widget_type {
+                BottomWidgetType::Cpu => (Some("Cores"), None),
+                BottomWidgetType::Proc => (Some("Web"), Some("user=www-data")),
+                _ => (None, None),
+            };
+
+            assert_eq!(widget.title.as_deref(), title);
+            assert_eq!(widget.filter.as_deref(), filter);
+            assert_eq!(widget.gauge, widget.widget_type == BottomWidgetType::Disk);
+            assert_eq!(
+                widget.styles.is_some(),
+                widget.widget_type == BottomWidgetType::Proc
+            );
+        }
+    }
+
+    #[test]
+    /// Tests that unsupported widget settings are errors.
+    fn test_unsupported_widget_settings() {
+        for layout in [
+            r#"
+            [[row]]
+                [[row.child]]
+                    type="cpu"
+                    filter="btm"
+            "#,
+            r#"
+            [[row]]
+                [[row.child]]
+                    type="net"
+                    gauge=true
+            "#,
+            r#"
+            [[row]]
+                [[row.child]]
+                    type="empty"
+                    title="Nothing"
+            "#,
+            r#"
+            [[row]]
+                [[row.child]]
+                    type="empty"
+                    [row.child.styles]
+                    theme="nord"
+            "#,
+        ] {
+            let rows = from_str::<Config>(layout).unwrap().row.unwrap();
+            let mut iter_id = 0;
+            let mut total_height_ratio = 0;
+            let mut default_widget_id = DEFAULT_WIDGET_ID;
+            let mut default_widget_count = 1;
+
+            assert!(rows[0]
+                .convert_row_to_bottom_row(
+                    &mut iter_id,
+                    &mut total_height_ratio,
+                    &mut default_widget_id,
+                    &None,
+                    &mut default_widget_count,
+                    false,
+                )
+                .is_err());
+        }
+    }
 }
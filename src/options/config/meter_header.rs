@@ -0,0 +1,101 @@
+use serde::Deserialize;
+
+/// A meter shown in the meter header. `cpu` is a bar of the average CPU usage,
+/// while `cores` is a bar for each core.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Meter {
+    Cpu,
+    Cores,
+    #[serde(alias = "memory")]
+    Mem,
+    Swap,
+    Load,
+    Uptime,
+}
+
+impl Meter {
+    /// The meters shown if none are set.
+    pub(crate) const DEFAULT: [Meter; 5] = [
+        Meter::Cpu,
+        Meter::Mem,
+        Meter::Swap,
+        Meter::Load,
+        Meter::Uptime,
+    ];
+}
+
+/// Meter header settings. The meter header itself is enabled with the
+/// `meter_header` flag.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub(crate) struct MeterHeaderConfig {
+    /// The meters to show, in order. Defaults to all of them but `cores`.
+    pub(crate) meters: Option<Vec<Meter>>,
+}
+
+/// The settings of an enabled meter header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MeterHeader {
+    pub(crate) meters: Vec<Meter>,
+}
+
+impl MeterHeader {
+    /// Whether any of the meters need CPU data.
+    pub(crate) fn uses_cpu(&self) -> bool {
+        self.meters
+            .iter()
+            .any(|meter| matches!(meter, Meter::Cpu | Meter::Cores | Meter::Load))
+    }
+
+    /// Whether any of the meters need memory data.
+    pub(crate) fn uses_mem(&self) -> bool {
+        self.meters
+            .iter()
+            .any(|meter| matches!(meter, Meter::Mem | Meter::Swap))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty_meter_header() {
+        let generated: MeterHeaderConfig = toml_edit::de::from_str("").unwrap();
+        assert_eq!(generated, MeterHeaderConfig::default());
+    }
+
+    #[test]
+    fn meter_header_meters() {
+        let config = r#"
+            meters = ["cores", "memory", "uptime"]
+        "#;
+
+        let generated: MeterHeaderConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            generated.meters,
+            Some(vec![Meter::Cores, Meter::Mem, Meter::Uptime])
+        );
+    }
+
+    #[test]
+    fn invalid_meter() {
+        let config = r#"
+            meters = ["hostname"]
+        "#;
+
+        assert!(toml_edit::de::from_str::<MeterHeaderConfig>(config).is_err());
+    }
+
+    #[test]
+    fn meter_data_usage() {
+        let header = MeterHeader {
+            meters: vec![Meter::Uptime, Meter::Swap],
+        };
+        assert!(!header.uses_cpu());
+        assert!(header.uses_mem());
+    }
+}
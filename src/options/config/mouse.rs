@@ -0,0 +1,100 @@
+use serde::Deserialize;
+
+use super::StringOrNum;
+
+/// What scrolling over a graph does.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum GraphScroll {
+    /// Zoom the graph in or out.
+    #[default]
+    Zoom,
+    /// Step through the frozen data, if frozen.
+    Scrub,
+    /// Do nothing.
+    None,
+}
+
+/// Mouse settings.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub(crate) struct MouseConfig {
+    /// How many rows tables move by per scroll. Defaults to 1.
+    pub(crate) table_scroll_rows: Option<u16>,
+
+    /// What scrolling over a graph does. Defaults to zooming.
+    pub(crate) graph_scroll: Option<GraphScroll>,
+
+    /// Whether to swap scrolling up and down. Defaults to false.
+    pub(crate) invert_scroll: Option<bool>,
+
+    /// How long to ignore further scrolls for after one. Defaults to 20ms;
+    /// lower it if scrolling feels sluggish on a high-resolution trackpad.
+    pub(crate) scroll_debounce: Option<StringOrNum>,
+}
+
+/// The mouse settings in use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MouseSettings {
+    pub(crate) table_scroll_rows: u16,
+    pub(crate) graph_scroll: GraphScroll,
+    pub(crate) invert_scroll: bool,
+    pub(crate) scroll_debounce_ms: u64,
+}
+
+impl MouseSettings {
+    /// How long to ignore further scrolls for if not set.
+    pub(crate) const DEFAULT_SCROLL_DEBOUNCE_MS: u64 = 20;
+}
+
+impl Default for MouseSettings {
+    fn default() -> Self {
+        Self {
+            table_scroll_rows: 1,
+            graph_scroll: GraphScroll::default(),
+            invert_scroll: false,
+            scroll_debounce_ms: Self::DEFAULT_SCROLL_DEBOUNCE_MS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty_mouse() {
+        let generated: MouseConfig = toml_edit::de::from_str("").unwrap();
+        assert_eq!(generated, MouseConfig::default());
+    }
+
+    #[test]
+    fn mouse_settings() {
+        let config = r#"
+            table_scroll_rows = 3
+            graph_scroll = "scrub"
+            invert_scroll = true
+            scroll_debounce = "5ms"
+        "#;
+
+        let generated: MouseConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(generated.table_scroll_rows, Some(3));
+        assert_eq!(generated.graph_scroll, Some(GraphScroll::Scrub));
+        assert_eq!(generated.invert_scroll, Some(true));
+        assert_eq!(
+            generated.scroll_debounce,
+            Some(StringOrNum::String("5ms".to_string()))
+        );
+    }
+
+    #[test]
+    fn invalid_graph_scroll() {
+        let config = r#"
+            graph_scroll = "pan"
+        "#;
+
+        assert!(toml_edit::de::from_str::<MouseConfig>(config).is_err());
+    }
+}
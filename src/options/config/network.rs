@@ -2,6 +2,18 @@ use serde::Deserialize;
 
 use super::IgnoreList;
 
+/// How the network graph's y-axis is shared between received and sent data.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkYAxis {
+    #[default]
+    Shared,
+    Mirrored,
+    Independent,
+    Capacity,
+}
+
 /// Network configuration.
 #[derive(Clone, Debug, Default, Deserialize)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
@@ -9,4 +21,22 @@ use super::IgnoreList;
 pub(crate) struct NetworkConfig {
     /// A filter over the network interface names.
     pub(crate) interface_filter: Option<IgnoreList>,
+
+    /// How RX and TX share the graph's y-axis. `shared` draws both on the same
+    /// axis, `mirrored` draws RX above the axis and TX below it, and
+    /// `independent` also mirrors them but scales each half to its own data
+    /// so a much smaller direction is still visible. With a log scale,
+    /// `independent` is the same as `mirrored`. `capacity` draws both as a
+    /// percentage of the combined link speed of the shown interfaces, which
+    /// always uses a linear scale, and falls back to `shared` if no interface
+    /// reports a link speed. Defaults to `shared`.
+    pub(crate) y_axis: Option<NetworkYAxis>,
+
+    /// On Linux, whether to also count the interfaces of other network
+    /// namespaces that have a process in them, such as those of containers.
+    /// Their interfaces are named `<namespace>/<interface>` for filtering,
+    /// using the name from `ip netns` if there is one, or else the
+    /// namespace's inode number. Seeing other users' namespaces usually
+    /// requires root. Defaults to false.
+    pub(crate) namespaces: Option<bool>,
 }
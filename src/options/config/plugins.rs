@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+/// Plugin configuration. Plugins are experimental, and are only loaded if
+/// bottom was built with the `plugins` feature.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(not(feature = "plugins"), allow(dead_code))]
+pub(crate) struct PluginsConfig {
+    /// The directory to load `.lua` plugins from.
+    pub(crate) directory: Option<String>,
+}
@@ -92,4 +92,39 @@ mod test {
             vec![ProcWidgetColumn::WritePerSecond; 3]
         );
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_process_column_config() {
+        let config = r#"columns = ["Label", "label"]"#;
+        let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            to_columns(generated.columns),
+            vec![ProcWidgetColumn::Label; 2]
+        );
+
+        let config = r#"columns = ["PidNS", "netns", "MNTNS"]"#;
+        let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            to_columns(generated.columns),
+            vec![
+                ProcWidgetColumn::PidNamespace,
+                ProcWidgetColumn::NetNamespace,
+                ProcWidgetColumn::MntNamespace,
+            ]
+        );
+
+        let config = r#"columns = ["CPUWait%", "cpuwait", "IOWait%", "iowait", "IOPrio"]"#;
+        let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            to_columns(generated.columns),
+            vec![
+                ProcWidgetColumn::CpuWait,
+                ProcWidgetColumn::CpuWait,
+                ProcWidgetColumn::IoWait,
+                ProcWidgetColumn::IoWait,
+                ProcWidgetColumn::IoPriority,
+            ]
+        );
+    }
 }
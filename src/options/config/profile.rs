@@ -0,0 +1,142 @@
+//! Named profiles in the config file, like `[profile.laptop]`, which override
+//! the settings outside of them when selected.
+
+use anyhow::bail;
+use serde::Deserialize;
+use toml_edit::DocumentMut;
+
+use super::{
+    basic::BasicConfig,
+    cpu::CpuConfig,
+    disk::DiskConfig,
+    flags::FlagConfig,
+    include::merge,
+    keybinds::KeybindsConfig,
+    layout::{Page, Row},
+    meter_header::MeterHeaderConfig,
+    mouse::MouseConfig,
+    network::NetworkConfig,
+    process::ProcessesConfig,
+    status_bar::StatusBarConfig,
+    style::StyleConfig,
+    temperature::TempConfig,
+};
+
+/// The key profiles are under.
+const PROFILE_KEY: &str = "profile";
+
+/// The settings a profile can override. These are the same as in the rest of
+/// the config file.
+///
+/// Profiles are merged into the rest of the config file before it's read, so
+/// these are only used to check profiles and describe them in the schema.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[allow(dead_code, reason = "profiles are read after being merged in")]
+pub(crate) struct ProfileConfig {
+    pub(crate) flags: Option<FlagConfig>,
+    pub(crate) styles: Option<StyleConfig>,
+    pub(crate) row: Option<Vec<Row>>,
+    pub(crate) page: Option<Vec<Page>>,
+    pub(crate) processes: Option<ProcessesConfig>,
+    pub(crate) disk: Option<DiskConfig>,
+    pub(crate) temperature: Option<TempConfig>,
+    pub(crate) network: Option<NetworkConfig>,
+    pub(crate) cpu: Option<CpuConfig>,
+    pub(crate) basic: Option<BasicConfig>,
+    pub(crate) keybinds: Option<KeybindsConfig>,
+    pub(crate) status_bar: Option<StatusBarConfig>,
+    pub(crate) meter_header: Option<MeterHeaderConfig>,
+    pub(crate) mouse: Option<MouseConfig>,
+}
+
+/// Merges the profile named `name` in `document` on top of the rest of it, in
+/// the same way as included files are.
+pub(crate) fn apply_profile(document: &mut DocumentMut, name: &str) -> anyhow::Result<()> {
+    let profile = document
+        .get(PROFILE_KEY)
+        .and_then(|profiles| profiles.get(name))
+        .cloned()
+        .and_then(|profile| profile.into_table().ok());
+
+    let Some(profile) = profile else {
+        let names = document
+            .get(PROFILE_KEY)
+            .and_then(|profiles| profiles.as_table_like())
+            .map(|profiles| {
+                profiles
+                    .iter()
+                    .map(|(name, _)| format!("'{name}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default();
+
+        if names.is_empty() {
+            bail!("there is no profile named '{name}', as the config file has no profiles.");
+        } else {
+            bail!("there is no profile named '{name}'. The profiles are: {names}.");
+        }
+    };
+
+    merge(document.as_table_mut(), profile);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::options::Config;
+
+    const PROFILES: &str = r#"
+[flags]
+rate = 1000
+tree = true
+
+[profile.laptop.flags]
+rate = 5000
+
+[profile.server]
+flags = { rate = 500 }
+"#;
+
+    fn config_with_profile(name: &str) -> anyhow::Result<Config> {
+        let mut document: DocumentMut = PROFILES.parse().unwrap();
+        apply_profile(&mut document, name)?;
+
+        Ok(toml_edit::de::from_document(document)?)
+    }
+
+    #[test]
+    fn applies_profiles() {
+        let flags = config_with_profile("laptop").unwrap().flags.unwrap();
+        assert_eq!(flags.rate, Some(5000u64.into()));
+        assert_eq!(flags.tree, Some(true));
+
+        let flags = config_with_profile("server").unwrap().flags.unwrap();
+        assert_eq!(flags.rate, Some(500u64.into()));
+        assert_eq!(flags.tree, Some(true));
+    }
+
+    #[test]
+    fn keeps_profile_names() {
+        let config = config_with_profile("laptop").unwrap();
+        let names: Vec<_> = config.profile.unwrap().into_keys().collect();
+
+        assert_eq!(names, vec!["laptop", "server"]);
+    }
+
+    #[test]
+    fn missing_profiles() {
+        let err = config_with_profile("desktop").unwrap_err().to_string();
+        assert!(err.contains("'laptop', 'server'"));
+
+        let mut document: DocumentMut = "[flags]\nrate = 1000\n".parse().unwrap();
+        let err = apply_profile(&mut document, "laptop")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("has no profiles"));
+    }
+}
@@ -0,0 +1,111 @@
+use serde::Deserialize;
+
+/// Where the status bar is drawn.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum StatusBarPosition {
+    Top,
+    #[default]
+    Bottom,
+}
+
+/// A value shown in the status bar.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum StatusBarField {
+    Hostname,
+    Load,
+    Cpu,
+    #[serde(alias = "memory")]
+    Mem,
+    Swap,
+}
+
+impl StatusBarField {
+    /// The fields shown if none are set.
+    pub(crate) const DEFAULT: [StatusBarField; 5] = [
+        StatusBarField::Hostname,
+        StatusBarField::Load,
+        StatusBarField::Cpu,
+        StatusBarField::Mem,
+        StatusBarField::Swap,
+    ];
+}
+
+/// Status bar settings. The status bar itself is enabled with the
+/// `status_bar` flag.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub(crate) struct StatusBarConfig {
+    /// Whether to draw the status bar at the top or bottom. Defaults to the
+    /// bottom.
+    pub(crate) position: Option<StatusBarPosition>,
+
+    /// The values to show, in order. Defaults to all of them.
+    pub(crate) fields: Option<Vec<StatusBarField>>,
+}
+
+/// The settings of an enabled status bar.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusBar {
+    pub(crate) position: StatusBarPosition,
+    pub(crate) fields: Vec<StatusBarField>,
+}
+
+impl StatusBar {
+    /// Whether any of the fields need CPU data.
+    pub(crate) fn uses_cpu(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|field| matches!(field, StatusBarField::Load | StatusBarField::Cpu))
+    }
+
+    /// Whether any of the fields need memory data.
+    pub(crate) fn uses_mem(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|field| matches!(field, StatusBarField::Mem | StatusBarField::Swap))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty_status_bar() {
+        let generated: StatusBarConfig = toml_edit::de::from_str("").unwrap();
+        assert_eq!(generated, StatusBarConfig::default());
+    }
+
+    #[test]
+    fn status_bar_fields() {
+        let config = r#"
+            position = "top"
+            fields = ["cpu", "memory", "hostname"]
+        "#;
+
+        let generated: StatusBarConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(generated.position, Some(StatusBarPosition::Top));
+        assert_eq!(
+            generated.fields,
+            Some(vec![
+                StatusBarField::Cpu,
+                StatusBarField::Mem,
+                StatusBarField::Hostname
+            ])
+        );
+    }
+
+    #[test]
+    fn invalid_status_bar_field() {
+        let config = r#"
+            fields = ["uptime"]
+        "#;
+
+        assert!(toml_edit::de::from_str::<StatusBarConfig>(config).is_err());
+    }
+}
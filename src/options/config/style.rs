@@ -20,6 +20,7 @@ use memory::MemoryStyle;
 use network::NetworkStyle;
 use serde::{Deserialize, Serialize};
 use tables::TableStyle;
+use themes::base16::Base16Palette;
 use tui::{style::Style, widgets::BorderType};
 use utils::{opt, set_colour, set_colour_list, set_style};
 use widgets::WidgetStyle;
@@ -63,7 +64,8 @@ pub(crate) enum TextStyleConfig {
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
 #[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
 pub(crate) struct StyleConfig {
-    /// A built-in theme.
+    /// A built-in theme, or a path to a base16 YAML scheme ending in `.yaml`
+    /// or `.yml`.
     ///
     /// If this is and a custom colour are both set, in the config file,
     /// the custom colour scheme will be prioritized first. If a theme
@@ -94,7 +96,7 @@ pub(crate) struct StyleConfig {
 }
 
 /// The actual internal representation of the configured styles.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Styles {
     pub(crate) ram_style: Style,
     #[cfg(not(target_os = "windows"))]
@@ -116,6 +118,7 @@ pub struct Styles {
     pub(crate) text_style: Style,
     pub(crate) selected_text_style: Style,
     pub(crate) table_header_style: Style,
+    pub(crate) table_stripe_style: Style,
     pub(crate) widget_title_style: Style,
     pub(crate) graph_style: Style,
     pub(crate) graph_legend_style: Style,
@@ -125,6 +128,7 @@ pub struct Styles {
     pub(crate) invalid_query_style: Style,
     pub(crate) disabled_text_style: Style,
     pub(crate) border_type: BorderType,
+    pub(crate) series_symbols: bool,
 }
 
 impl Default for Styles {
@@ -160,6 +164,12 @@ impl Styles {
             "gruvbox-light" => Ok(Self::gruvbox_light_palette()),
             "nord" => Ok(Self::nord_palette()),
             "nord-light" => Ok(Self::nord_light_palette()),
+            "dracula" => Ok(Self::dracula_palette()),
+            "solarized" => Ok(Self::solarized_palette()),
+            "high-contrast" => Ok(Self::high_contrast_palette()),
+            _ if lower_case.ends_with(".yaml") || lower_case.ends_with(".yml") => {
+                Self::from_base16_file(theme)
+            }
             _ => Err(
                 OptionError::other(format!("'{theme}' is an invalid built-in color scheme."))
                     .into(),
@@ -167,7 +177,37 @@ impl Styles {
         }
     }
 
+    /// Returns the styles for a widget with its own style config, which is
+    /// applied on top of these styles, or on top of its own theme if it sets
+    /// one.
+    pub(crate) fn for_widget(&self, config: &StyleConfig) -> anyhow::Result<Self> {
+        let mut styles = match &config.theme {
+            Some(theme) => Self::from_theme(theme)?,
+            None => self.clone(),
+        };
+        styles.set_styles_from_config(config)?;
+
+        Ok(styles)
+    }
+
+    /// Loads a base16 YAML scheme from a file.
+    fn from_base16_file(path: &str) -> anyhow::Result<Self> {
+        let scheme = std::fs::read_to_string(path).map_err(|err| {
+            OptionError::other(format!("Couldn't read the base16 theme '{path}': {err}"))
+        })?;
+        let palette = Base16Palette::parse(&scheme).map_err(|err| {
+            OptionError::other(format!("'{path}' is an invalid base16 theme. {err}"))
+        })?;
+
+        Ok(Self::base16_palette(&palette))
+    }
+
     fn set_styles_from_config(&mut self, config: &StyleConfig) -> OptionResult<()> {
+        // Graph series, which specific colours below take precedence over.
+        if let Some(mode) = config.graphs.as_ref().and_then(|graphs| graphs.series_mode) {
+            self.set_graph_series_mode(mode);
+        }
+
         // CPU
         set_colour!(self.avg_cpu_colour, config.cpu, avg_entry_color);
         set_colour!(self.all_cpu_colour, config.cpu, all_entry_color);
@@ -199,6 +239,7 @@ impl Styles {
 
         // Tables
         set_style!(self.table_header_style, config.tables, headers);
+        set_style!(self.table_stripe_style, config.tables, stripes);
 
         // Widget graphs
         set_colour!(self.graph_style, config.graphs, graph_color);
@@ -233,8 +274,8 @@ mod test {
 
     use tui::style::{Color, Style};
 
-    use super::Styles;
-    use crate::options::config::style::utils::str_to_colour;
+    use super::{StyleConfig, Styles};
+    use crate::options::config::style::{themes::base16::Base16Palette, utils::str_to_colour};
 
     #[test]
     fn default_selected_colour_works() {
@@ -268,5 +309,120 @@ mod test {
         Styles::from_theme("gruvbox-light").unwrap();
         Styles::from_theme("nord").unwrap();
         Styles::from_theme("nord-light").unwrap();
+        Styles::from_theme("dracula").unwrap();
+        Styles::from_theme("solarized").unwrap();
+        Styles::from_theme("high-contrast").unwrap();
+
+        assert!(Styles::from_theme("not-a-theme").is_err());
+        assert!(Styles::from_theme("./does/not/exist.yaml").is_err());
+    }
+
+    #[test]
+    fn widget_styles() {
+        let styles = Styles::from_theme("gruvbox").unwrap();
+
+        let config: StyleConfig = toml_edit::de::from_str(
+            r#"
+            [cpu]
+            avg_entry_color = "red"
+            "#,
+        )
+        .unwrap();
+        let widget_styles = styles.for_widget(&config).unwrap();
+        assert_eq!(widget_styles.avg_cpu_colour.fg, Some(Color::Red));
+        assert_eq!(widget_styles.text_style, styles.text_style);
+
+        // A widget's theme replaces the other styles.
+        let config: StyleConfig = toml_edit::de::from_str(r#"theme = "nord""#).unwrap();
+        let widget_styles = styles.for_widget(&config).unwrap();
+        assert_eq!(widget_styles.text_style, Styles::nord_palette().text_style);
+    }
+
+    #[test]
+    fn graph_series_modes() {
+        let config: StyleConfig = toml_edit::de::from_str(
+            r#"
+            [graphs]
+            series_mode = "deuteranopia"
+
+            [memory]
+            ram_color = "red"
+            "#,
+        )
+        .unwrap();
+        let styles = Styles::default().for_widget(&config).unwrap();
+        assert_eq!(styles.ram_style.fg, Some(Color::Red));
+        assert_eq!(styles.swap_style.fg, Some(Color::Rgb(0x56, 0xb4, 0xe9)));
+        assert_eq!(styles.series_symbol(0), None);
+
+        let config: StyleConfig = toml_edit::de::from_str(
+            r#"
+            [graphs]
+            series_mode = "monochrome"
+            "#,
+        )
+        .unwrap();
+        let styles = Styles::default().for_widget(&config).unwrap();
+        assert_eq!(styles.rx_style.fg, styles.text_style.fg);
+        assert_eq!(styles.tx_style.fg, styles.text_style.fg);
+        assert_eq!(styles.series_symbol(0), Some('•'));
+        assert_ne!(styles.series_symbol(0), styles.series_symbol(1));
+
+        assert!(toml_edit::de::from_str::<StyleConfig>(
+            r#"
+            [graphs]
+            series_mode = "sepia"
+            "#,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn base16_palettes() {
+        // The original format.
+        let palette = Base16Palette::parse(
+            r##"scheme: "Test"
+author: "bottom"
+base00: "000000" # ----
+base01: "111111"
+base02: '222222'
+base03: 333333
+base04: "#444444"
+base05: "555555"
+base06: "666666"
+base07: "777777"
+base08: "888888"
+base09: "999999"
+base0A: "aaaaaa"
+base0B: "bbbbbb"
+base0C: "cccccc"
+base0D: "dddddd"
+base0E: "eeeeee"
+base0F: "ffffff"
+"##,
+        )
+        .unwrap();
+        let styles = Styles::base16_palette(&palette);
+        assert_eq!(styles.text_style.fg, Some(Color::Rgb(0x55, 0x55, 0x55)));
+        assert_eq!(
+            styles.selected_text_style,
+            Style::default()
+                .fg(Color::Rgb(0, 0, 0))
+                .bg(Color::Rgb(0xdd, 0xdd, 0xdd))
+        );
+
+        // The newer format, with the colours under `palette`.
+        let scheme = (0..16)
+            .map(|index| format!("  base0{index:X}: \"#{index:X}{index:X}{index:X}{index:X}{index:X}{index:X}\"\n"))
+            .collect::<String>();
+        let palette = Base16Palette::parse(&format!(
+            "system: \"base16\"\nname: \"Test\"\npalette:\n{scheme}"
+        ))
+        .unwrap();
+        let styles = Styles::base16_palette(&palette);
+        assert_eq!(styles.text_style.fg, Some(Color::Rgb(0x55, 0x55, 0x55)));
+
+        assert!(Base16Palette::parse("base00: \"000000\"").is_err());
+        assert!(Base16Palette::parse(&scheme.replace("#FFFFFF", "#FFF")).is_err());
     }
 }
@@ -2,6 +2,40 @@ use serde::{Deserialize, Serialize};
 
 use super::{ColorStr, TextStyleConfig};
 
+/// How the series of a graph are told apart.
+#[derive(Default, Clone, Copy, Debug, Serialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(PartialEq, Eq))]
+pub(crate) enum GraphSeriesMode {
+    /// Use the colours of the theme.
+    #[default]
+    Default,
+    /// Use colours that can be told apart with deuteranopia.
+    Deuteranopia,
+    /// Use colours that can be told apart with protanopia.
+    Protanopia,
+    /// Use a single colour, and a different symbol for each series.
+    Monochrome,
+}
+
+impl<'de> Deserialize<'de> for GraphSeriesMode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?.to_lowercase();
+        match value.as_str() {
+            "default" => Ok(GraphSeriesMode::Default),
+            "deuteranopia" => Ok(GraphSeriesMode::Deuteranopia),
+            "protanopia" => Ok(GraphSeriesMode::Protanopia),
+            "monochrome" => Ok(GraphSeriesMode::Monochrome),
+            _ => Err(serde::de::Error::custom(
+                "doesn't match any graph series mode",
+            )),
+        }
+    }
+}
+
 /// General styling for graph widgets.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
@@ -13,4 +47,8 @@ pub(crate) struct GraphStyle {
 
     /// Text styling for graph's legend text.
     pub(crate) legend_text: Option<TextStyleConfig>,
+
+    /// How the series of the CPU, memory, and network graphs are told apart.
+    /// Any colours set for specific series are still used.
+    pub(crate) series_mode: Option<GraphSeriesMode>,
 }
@@ -9,4 +9,7 @@ use super::TextStyleConfig;
 pub(crate) struct TableStyle {
     /// Text styling for table headers.
     pub(crate) headers: Option<TextStyleConfig>,
+
+    /// Styling for every other row, if table rows are striped.
+    pub(crate) stripes: Option<TextStyleConfig>,
 }
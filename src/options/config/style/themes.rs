@@ -1,8 +1,13 @@
 //! A set of pre-defined themes.
 
+pub(super) mod base16;
 pub(super) mod default;
+pub(super) mod dracula;
 pub(super) mod gruvbox;
+pub(super) mod high_contrast;
 pub(super) mod nord;
+pub(super) mod series;
+pub(super) mod solarized;
 
 macro_rules! color {
     ($value:expr) => {
@@ -17,5 +22,14 @@ macro_rules! hex {
     };
 }
 
+/// A background colour from a hex string, such as for striped table rows.
+macro_rules! hex_bg {
+    ($value:literal) => {
+        tui::style::Style::new()
+            .bg(crate::options::config::style::utils::convert_hex_to_color($value.into()).unwrap())
+    };
+}
+
 pub(super) use color;
 pub(super) use hex;
+pub(super) use hex_bg;
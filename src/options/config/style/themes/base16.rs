@@ -0,0 +1,115 @@
+use tui::{
+    style::{Color, Modifier, Style},
+    widgets::BorderType,
+};
+
+use super::color;
+use crate::options::config::style::{utils::convert_hex_to_color, Styles};
+
+/// The 16 colours of a [base16](https://github.com/chriskempson/base16) palette,
+/// from `base00` to `base0F`.
+pub(in crate::options::config::style) struct Base16Palette([Color; 16]);
+
+impl Base16Palette {
+    /// Parses the colours of a base16 YAML scheme. Both the original format,
+    /// where the colours are top-level keys, and the newer one, where they are
+    /// under `palette`, are supported; any other keys are ignored.
+    ///
+    /// This only handles the simple `key: "value"` lines that base16 schemes
+    /// use rather than all of YAML.
+    pub(in crate::options::config::style) fn parse(scheme: &str) -> Result<Self, String> {
+        let mut colours: [Option<Color>; 16] = [None; 16];
+
+        for line in scheme.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+
+            let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+            let Some(index) = key
+                .strip_prefix("base0")
+                .filter(|index| index.len() == 1)
+                .and_then(|index| u8::from_str_radix(index, 16).ok())
+            else {
+                continue;
+            };
+
+            let value = value.trim();
+            let value = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+                _ => value.split_whitespace().next().unwrap_or_default(),
+            };
+            let hex = value.strip_prefix('#').unwrap_or(value);
+            if hex.len() != 6 {
+                return Err(format!(
+                    "'{key}' must be a 6 character hex colour, got '{value}'."
+                ));
+            }
+
+            colours[usize::from(index)] = Some(convert_hex_to_color(&format!("#{hex}"))?);
+        }
+
+        let mut palette = [Color::Reset; 16];
+        for (index, colour) in colours.into_iter().enumerate() {
+            palette[index] = colour.ok_or_else(|| format!("'base0{index:X}' is missing."))?;
+        }
+
+        Ok(Self(palette))
+    }
+
+    fn style(&self, index: usize) -> Style {
+        color!(self.0[index])
+    }
+}
+
+impl Styles {
+    /// Maps a base16 palette onto the styles, following the base16 styling
+    /// guidelines: `base00` to `base07` go from the background to the
+    /// foreground, and `base08` to `base0F` are the accent colours.
+    pub(in crate::options::config::style) fn base16_palette(palette: &Base16Palette) -> Self {
+        let p = |index| palette.style(index);
+
+        Self {
+            ram_style: p(0xE),
+            #[cfg(not(target_os = "windows"))]
+            cache_style: p(0x8),
+            swap_style: p(0xA),
+            #[cfg(feature = "zfs")]
+            arc_style: p(0xC),
+            #[cfg(feature = "gpu")]
+            gpu_colours: vec![p(0xB), p(0xD), p(0x8), p(0xC), p(0x9), p(0xE), p(0xF)],
+            rx_style: p(0xE),
+            tx_style: p(0xA),
+            total_rx_style: p(0xC),
+            total_tx_style: p(0xB),
+            all_cpu_colour: p(0xB),
+            avg_cpu_colour: p(0x8),
+            cpu_colour_styles: vec![
+                p(0xE),
+                p(0xA),
+                p(0xC),
+                p(0xB),
+                p(0xD),
+                p(0x9),
+                p(0x8),
+                p(0xF),
+            ],
+            border_style: p(0x4),
+            highlighted_border_style: p(0xD),
+            text_style: p(0x5),
+            selected_text_style: p(0x0).bg(palette.0[0xD]),
+            table_header_style: p(0xD).add_modifier(Modifier::BOLD),
+            table_stripe_style: Style::new().bg(palette.0[0x1]),
+            widget_title_style: p(0x5),
+            graph_style: p(0x4),
+            graph_legend_style: p(0x5),
+            high_battery: p(0xB),
+            medium_battery: p(0xA),
+            low_battery: p(0x8),
+            invalid_query_style: p(0x8),
+            disabled_text_style: p(0x3),
+            border_type: BorderType::Plain,
+            series_symbols: false,
+        }
+    }
+}
@@ -58,6 +58,7 @@ impl Styles {
             text_style: color!(TEXT_COLOUR),
             selected_text_style: DEFAULT_SELECTED_TEXT_STYLE,
             table_header_style: color!(HIGHLIGHT_COLOUR).add_modifier(Modifier::BOLD),
+            table_stripe_style: Style::new().bg(Color::Indexed(236)),
             widget_title_style: color!(TEXT_COLOUR),
             graph_style: color!(TEXT_COLOUR),
             graph_legend_style: color!(TEXT_COLOUR),
@@ -67,6 +68,7 @@ impl Styles {
             invalid_query_style: color!(Color::Red),
             disabled_text_style: color!(Color::DarkGray),
             border_type: BorderType::Plain,
+            series_symbols: false,
         }
     }
 
@@ -105,6 +107,7 @@ impl Styles {
             text_style: color!(Color::Black),
             selected_text_style: color!(Color::White).bg(Color::LightBlue),
             table_header_style: color!(Color::Black).add_modifier(Modifier::BOLD),
+            table_stripe_style: Style::new().bg(Color::Indexed(254)),
             widget_title_style: color!(Color::Black),
             graph_style: color!(Color::Black),
             graph_legend_style: color!(Color::Black),
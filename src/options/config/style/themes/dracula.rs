@@ -0,0 +1,62 @@
+use tui::{
+    style::{Color, Modifier},
+    widgets::BorderType,
+};
+
+use super::{color, hex, hex_bg};
+use crate::options::config::style::{utils::convert_hex_to_color, Styles};
+
+impl Styles {
+    pub(crate) fn dracula_palette() -> Self {
+        Self {
+            ram_style: hex!("#bd93f9"),
+            #[cfg(not(target_os = "windows"))]
+            cache_style: hex!("#ff5555"),
+            swap_style: hex!("#f1fa8c"),
+            #[cfg(feature = "zfs")]
+            arc_style: hex!("#8be9fd"),
+            #[cfg(feature = "gpu")]
+            gpu_colours: vec![
+                hex!("#50fa7b"),
+                hex!("#8be9fd"),
+                hex!("#ff79c6"),
+                hex!("#ffb86c"),
+                hex!("#bd93f9"),
+                hex!("#f1fa8c"),
+                hex!("#ff5555"),
+            ],
+            rx_style: hex!("#8be9fd"),
+            tx_style: hex!("#ff79c6"),
+            total_rx_style: hex!("#bd93f9"),
+            total_tx_style: hex!("#ffb86c"),
+            all_cpu_colour: hex!("#50fa7b"),
+            avg_cpu_colour: hex!("#ff5555"),
+            cpu_colour_styles: vec![
+                hex!("#ff79c6"),
+                hex!("#f1fa8c"),
+                hex!("#8be9fd"),
+                hex!("#50fa7b"),
+                hex!("#bd93f9"),
+                hex!("#ffb86c"),
+                hex!("#6272a4"),
+                hex!("#ff5555"),
+            ],
+            border_style: hex!("#6272a4"),
+            highlighted_border_style: hex!("#bd93f9"),
+            text_style: hex!("#f8f8f2"),
+            selected_text_style: hex!("#282a36").bg(convert_hex_to_color("#bd93f9").unwrap()),
+            table_header_style: hex!("#ff79c6").add_modifier(Modifier::BOLD),
+            table_stripe_style: hex_bg!("#343746"),
+            widget_title_style: hex!("#f8f8f2"),
+            graph_style: hex!("#f8f8f2"),
+            graph_legend_style: hex!("#f8f8f2"),
+            high_battery: hex!("#50fa7b"),
+            medium_battery: hex!("#f1fa8c"),
+            low_battery: hex!("#ff5555"),
+            invalid_query_style: color!(Color::Red),
+            disabled_text_style: hex!("#44475a"),
+            border_type: BorderType::Plain,
+            series_symbols: false,
+        }
+    }
+}
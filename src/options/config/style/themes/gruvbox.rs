@@ -3,7 +3,7 @@ use tui::{
     widgets::BorderType,
 };
 
-use super::{color, hex};
+use super::{color, hex, hex_bg};
 use crate::options::config::style::{utils::convert_hex_to_color, Styles};
 
 impl Styles {
@@ -58,6 +58,7 @@ impl Styles {
             text_style: hex!("#ebdbb2"),
             selected_text_style: hex!("#1d2021").bg(convert_hex_to_color("#ebdbb2").unwrap()),
             table_header_style: hex!("#83a598").add_modifier(Modifier::BOLD),
+            table_stripe_style: hex_bg!("#32302f"),
             widget_title_style: hex!("#ebdbb2"),
             graph_style: hex!("#ebdbb2"),
             graph_legend_style: hex!("#ebdbb2"),
@@ -67,6 +68,7 @@ impl Styles {
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#665c54"),
             border_type: BorderType::Plain,
+            series_symbols: false,
         }
     }
 
@@ -121,6 +123,7 @@ impl Styles {
             text_style: hex!("#3c3836"),
             selected_text_style: hex!("#ebdbb2").bg(convert_hex_to_color("#3c3836").unwrap()),
             table_header_style: hex!("#076678").add_modifier(Modifier::BOLD),
+            table_stripe_style: hex_bg!("#f2e5bc"),
             widget_title_style: hex!("#3c3836"),
             graph_style: hex!("#3c3836"),
             graph_legend_style: hex!("#3c3836"),
@@ -130,6 +133,7 @@ impl Styles {
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#d5c4a1"),
             border_type: BorderType::Plain,
+            series_symbols: false,
         }
     }
 }
@@ -0,0 +1,65 @@
+use tui::{
+    style::{Color, Modifier, Style},
+    widgets::BorderType,
+};
+
+use super::color;
+use crate::options::config::style::Styles;
+
+impl Styles {
+    /// A theme that only uses the brightest basic terminal colours, with bold
+    /// text and thick borders.
+    pub(crate) fn high_contrast_palette() -> Self {
+        Self {
+            ram_style: color!(Color::LightMagenta),
+            #[cfg(not(target_os = "windows"))]
+            cache_style: color!(Color::LightRed),
+            swap_style: color!(Color::LightYellow),
+            #[cfg(feature = "zfs")]
+            arc_style: color!(Color::LightCyan),
+            #[cfg(feature = "gpu")]
+            gpu_colours: vec![
+                color!(Color::LightGreen),
+                color!(Color::LightBlue),
+                color!(Color::LightRed),
+                color!(Color::LightCyan),
+                color!(Color::LightMagenta),
+                color!(Color::LightYellow),
+                color!(Color::White),
+            ],
+            rx_style: color!(Color::LightCyan),
+            tx_style: color!(Color::LightYellow),
+            total_rx_style: color!(Color::LightBlue),
+            total_tx_style: color!(Color::LightRed),
+            all_cpu_colour: color!(Color::LightGreen),
+            avg_cpu_colour: color!(Color::LightRed),
+            cpu_colour_styles: vec![
+                color!(Color::LightMagenta),
+                color!(Color::LightYellow),
+                color!(Color::LightCyan),
+                color!(Color::LightGreen),
+                color!(Color::LightBlue),
+                color!(Color::LightRed),
+                color!(Color::White),
+            ],
+            border_style: color!(Color::White),
+            highlighted_border_style: color!(Color::LightYellow),
+            text_style: color!(Color::White),
+            selected_text_style: color!(Color::Black)
+                .bg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+            table_header_style: color!(Color::LightYellow).add_modifier(Modifier::BOLD),
+            table_stripe_style: Style::new().bg(Color::Indexed(236)),
+            widget_title_style: color!(Color::White).add_modifier(Modifier::BOLD),
+            graph_style: color!(Color::White),
+            graph_legend_style: color!(Color::White),
+            high_battery: color!(Color::LightGreen),
+            medium_battery: color!(Color::LightYellow),
+            low_battery: color!(Color::LightRed),
+            invalid_query_style: color!(Color::LightRed).add_modifier(Modifier::BOLD),
+            disabled_text_style: color!(Color::Gray),
+            border_type: BorderType::Thick,
+            series_symbols: false,
+        }
+    }
+}
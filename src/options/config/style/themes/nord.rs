@@ -3,7 +3,7 @@ use tui::{
     widgets::BorderType,
 };
 
-use super::{color, hex};
+use super::{color, hex, hex_bg};
 use crate::options::config::style::{utils::convert_hex_to_color, Styles};
 
 impl Styles {
@@ -46,6 +46,7 @@ impl Styles {
             text_style: hex!("#e5e9f0"),
             selected_text_style: hex!("#2e3440").bg(convert_hex_to_color("#88c0d0").unwrap()),
             table_header_style: hex!("#81a1c1").add_modifier(Modifier::BOLD),
+            table_stripe_style: hex_bg!("#3b4252"),
             widget_title_style: hex!("#e5e9f0"),
             graph_style: hex!("#e5e9f0"),
             graph_legend_style: hex!("#e5e9f0"),
@@ -55,6 +56,7 @@ impl Styles {
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#4c566a"),
             border_type: BorderType::Plain,
+            series_symbols: false,
         }
     }
 
@@ -97,6 +99,7 @@ impl Styles {
             text_style: hex!("#2e3440"),
             selected_text_style: hex!("#f5f5f5").bg(convert_hex_to_color("#5e81ac").unwrap()),
             table_header_style: hex!("#5e81ac").add_modifier(Modifier::BOLD),
+            table_stripe_style: hex_bg!("#e5e9f0"),
             widget_title_style: hex!("#2e3440"),
             graph_style: hex!("#2e3440"),
             graph_legend_style: hex!("#2e3440"),
@@ -106,6 +109,7 @@ impl Styles {
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#d8dee9"),
             border_type: BorderType::Plain,
+            series_symbols: false,
         }
     }
 }
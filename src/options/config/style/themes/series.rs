@@ -0,0 +1,85 @@
+//! Colours and symbols for telling graph series apart.
+
+use tui::style::{Color, Style};
+
+use crate::options::config::style::{graphs::GraphSeriesMode, Styles};
+
+/// Series colours for deuteranopia, based on the Okabe-Ito palette.
+const DEUTERANOPIA_SERIES: [Color; 8] = [
+    Color::Rgb(0x00, 0x72, 0xb2),
+    Color::Rgb(0xe6, 0x9f, 0x00),
+    Color::Rgb(0x56, 0xb4, 0xe9),
+    Color::Rgb(0xd5, 0x5e, 0x00),
+    Color::Rgb(0xf0, 0xe4, 0x42),
+    Color::Rgb(0xcc, 0x79, 0xa7),
+    Color::Rgb(0x00, 0x9e, 0x73),
+    Color::Rgb(0xbb, 0xbb, 0xbb),
+];
+
+/// Series colours for protanopia, which avoid reds since those look dark.
+const PROTANOPIA_SERIES: [Color; 8] = [
+    Color::Rgb(0x64, 0x8f, 0xff),
+    Color::Rgb(0xff, 0xb0, 0x00),
+    Color::Rgb(0x56, 0xb4, 0xe9),
+    Color::Rgb(0xf0, 0xe4, 0x42),
+    Color::Rgb(0x78, 0x5e, 0xf0),
+    Color::Rgb(0xdc, 0x26, 0x7f),
+    Color::Rgb(0x00, 0x9e, 0x73),
+    Color::Rgb(0xdd, 0xdd, 0xdd),
+];
+
+/// The symbols that series are drawn with in the monochrome mode.
+const SERIES_SYMBOLS: [char; 8] = ['•', '+', 'x', '*', 'o', '#', '=', '~'];
+
+impl Styles {
+    /// Changes how graph series are told apart.
+    pub(in crate::options::config::style) fn set_graph_series_mode(
+        &mut self, mode: GraphSeriesMode,
+    ) {
+        match mode {
+            GraphSeriesMode::Default => {}
+            GraphSeriesMode::Deuteranopia => self.set_series_colours(&DEUTERANOPIA_SERIES),
+            GraphSeriesMode::Protanopia => self.set_series_colours(&PROTANOPIA_SERIES),
+            GraphSeriesMode::Monochrome => {
+                let colour = self.text_style.fg.unwrap_or(Color::Reset);
+                self.set_series_colours(&[colour]);
+                self.series_symbols = true;
+            }
+        }
+    }
+
+    fn set_series_colours(&mut self, colours: &[Color]) {
+        let style = |index: usize| Style::new().fg(colours[index % colours.len()]);
+
+        self.ram_style = style(0);
+        #[cfg(not(target_os = "windows"))]
+        {
+            self.cache_style = style(1);
+        }
+        self.swap_style = style(2);
+        #[cfg(feature = "zfs")]
+        {
+            self.arc_style = style(3);
+        }
+        #[cfg(feature = "gpu")]
+        {
+            self.gpu_colours = (4..4 + colours.len()).map(style).collect();
+        }
+
+        self.rx_style = style(0);
+        self.tx_style = style(1);
+        self.total_rx_style = style(2);
+        self.total_tx_style = style(3);
+
+        self.all_cpu_colour = style(0);
+        self.avg_cpu_colour = style(1);
+        self.cpu_colour_styles = (0..colours.len()).map(style).collect();
+    }
+
+    /// Returns the symbol to draw the `index`-th series of a graph with, if
+    /// series are told apart by their symbols rather than their colours.
+    pub(crate) fn series_symbol(&self, index: usize) -> Option<char> {
+        self.series_symbols
+            .then(|| SERIES_SYMBOLS[index % SERIES_SYMBOLS.len()])
+    }
+}
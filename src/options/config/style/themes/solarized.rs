@@ -0,0 +1,62 @@
+use tui::{
+    style::{Color, Modifier},
+    widgets::BorderType,
+};
+
+use super::{color, hex, hex_bg};
+use crate::options::config::style::{utils::convert_hex_to_color, Styles};
+
+impl Styles {
+    pub(crate) fn solarized_palette() -> Self {
+        Self {
+            ram_style: hex!("#268bd2"),
+            #[cfg(not(target_os = "windows"))]
+            cache_style: hex!("#d33682"),
+            swap_style: hex!("#b58900"),
+            #[cfg(feature = "zfs")]
+            arc_style: hex!("#2aa198"),
+            #[cfg(feature = "gpu")]
+            gpu_colours: vec![
+                hex!("#859900"),
+                hex!("#2aa198"),
+                hex!("#6c71c4"),
+                hex!("#d33682"),
+                hex!("#cb4b16"),
+                hex!("#b58900"),
+                hex!("#dc322f"),
+            ],
+            rx_style: hex!("#268bd2"),
+            tx_style: hex!("#cb4b16"),
+            total_rx_style: hex!("#2aa198"),
+            total_tx_style: hex!("#d33682"),
+            all_cpu_colour: hex!("#859900"),
+            avg_cpu_colour: hex!("#dc322f"),
+            cpu_colour_styles: vec![
+                hex!("#d33682"),
+                hex!("#b58900"),
+                hex!("#2aa198"),
+                hex!("#859900"),
+                hex!("#268bd2"),
+                hex!("#6c71c4"),
+                hex!("#cb4b16"),
+                hex!("#dc322f"),
+            ],
+            border_style: hex!("#586e75"),
+            highlighted_border_style: hex!("#268bd2"),
+            text_style: hex!("#93a1a1"),
+            selected_text_style: hex!("#002b36").bg(convert_hex_to_color("#268bd2").unwrap()),
+            table_header_style: hex!("#268bd2").add_modifier(Modifier::BOLD),
+            table_stripe_style: hex_bg!("#073642"),
+            widget_title_style: hex!("#93a1a1"),
+            graph_style: hex!("#93a1a1"),
+            graph_legend_style: hex!("#93a1a1"),
+            high_battery: hex!("#859900"),
+            medium_battery: hex!("#b58900"),
+            low_battery: hex!("#dc322f"),
+            invalid_query_style: color!(Color::Red),
+            disabled_text_style: hex!("#586e75"),
+            border_type: BorderType::Plain,
+            series_symbols: false,
+        }
+    }
+}
@@ -9,4 +9,10 @@ use super::IgnoreList;
 pub(crate) struct TempConfig {
     /// A filter over the sensor names.
     pub(crate) sensor_filter: Option<IgnoreList>,
+    /// Whether to also list voltages, such as those of PSU rails, in the
+    /// temperature widget. Only supported on Linux. Defaults to false.
+    pub(crate) voltages: Option<bool>,
+    /// Whether to also list power readings, such as package power, in the
+    /// temperature widget. Only supported on Linux. Defaults to false.
+    pub(crate) power: Option<bool>,
 }
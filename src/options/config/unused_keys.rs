@@ -0,0 +1,496 @@
+//! Finding keys in a config file that bottom doesn't use. These are otherwise
+//! silently ignored, which makes typos easy to miss.
+//!
+//! This works by wrapping the deserializer, keeping track of where in the
+//! config each value is, and noting whenever a value is skipped. Keys within
+//! values that can take more than one form (e.g. layout children, which may be
+//! a widget or a column) can't be tracked, and aren't reported.
+
+use std::{cell::RefCell, fmt};
+
+use serde::de::{
+    self, Deserialize, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+
+/// A part of the path to a value in a config file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum PathSegment {
+    /// A key in a table.
+    Key(String),
+    /// An index into an array.
+    Index(usize),
+}
+
+/// A key that was skipped when deserializing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct UnusedKey {
+    /// The path to the key, ending with the key itself.
+    pub(crate) path: Vec<PathSegment>,
+    /// The keys accepted where this key was found.
+    pub(crate) expected: &'static [&'static str],
+}
+
+impl UnusedKey {
+    /// Returns the key itself.
+    pub(crate) fn name(&self) -> Option<&str> {
+        match self.path.last() {
+            Some(PathSegment::Key(key)) => Some(key),
+            _ => None,
+        }
+    }
+
+    /// Returns the path to the key, like `flags.rate` or `row[0].ratio`.
+    pub(crate) fn display_path(&self) -> String {
+        let mut path = String::new();
+        for segment in &self.path {
+            match segment {
+                PathSegment::Key(key) => {
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(key);
+                }
+                PathSegment::Index(index) => path.push_str(&format!("[{index}]")),
+            }
+        }
+
+        path
+    }
+}
+
+/// State shared by everything wrapped while deserializing.
+#[derive(Default)]
+struct Tracker {
+    unused: RefCell<Vec<UnusedKey>>,
+    /// The last key deserialized, which the next value belongs to.
+    last_key: RefCell<Option<String>>,
+}
+
+/// Deserializes a `T`, also returning any keys that were skipped.
+pub(crate) fn deserialize_tracking_unused<'de, T, D>(
+    deserializer: D,
+) -> Result<(T, Vec<UnusedKey>), D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let tracker = Tracker::default();
+    let value = T::deserialize(Wrap::new(deserializer, Vec::new(), &tracker))?;
+
+    Ok((value, tracker.unused.into_inner()))
+}
+
+/// Wraps a deserializer, visitor, seed, or enum access, along with where in
+/// the config it is.
+struct Wrap<'t, X> {
+    inner: X,
+    path: Vec<PathSegment>,
+    /// The keys accepted alongside this value, or by the struct being visited.
+    fields: &'static [&'static str],
+    /// Whether this is for a key rather than a value.
+    is_key: bool,
+    tracker: &'t Tracker,
+}
+
+impl<'t, X> Wrap<'t, X> {
+    fn new(inner: X, path: Vec<PathSegment>, tracker: &'t Tracker) -> Self {
+        Self {
+            inner,
+            path,
+            fields: &[],
+            is_key: false,
+            tracker,
+        }
+    }
+
+    /// Wraps something else at the same place in the config.
+    fn wrap<Y>(&self, inner: Y) -> Wrap<'t, Y> {
+        Wrap {
+            inner,
+            path: self.path.clone(),
+            fields: self.fields,
+            is_key: self.is_key,
+            tracker: self.tracker,
+        }
+    }
+
+    /// Wraps a visitor for this deserializer.
+    fn wrap_visitor<V>(&self, visitor: V) -> Wrap<'t, V> {
+        Wrap {
+            fields: &[],
+            ..self.wrap(visitor)
+        }
+    }
+
+    fn capture_key(&self, key: &str) {
+        if self.is_key {
+            *self.tracker.last_key.borrow_mut() = Some(key.to_string());
+        }
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(
+                self, $($arg: $ty,)* visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                let visitor = self.wrap_visitor(visitor);
+                self.inner.$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Wrap<'_, D> {
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self, name: &'static str, fields: &'static [&'static str], visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let visitor = Wrap {
+            fields,
+            ..self.wrap_visitor(visitor)
+        };
+        self.inner.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.tracker.unused.borrow_mut().push(UnusedKey {
+            path: self.path,
+            expected: self.fields,
+        });
+        self.inner.deserialize_ignored_any(visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<E: de::Error>(self, v: $ty) -> Result<Self::Value, E> {
+                self.inner.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for Wrap<'_, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_bytes(&[u8]),
+        visit_borrowed_bytes(&'de [u8]),
+        visit_byte_buf(Vec<u8>),
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        self.capture_key(v);
+        self.inner.visit_str(v)
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        self.capture_key(v);
+        self.inner.visit_borrowed_str(v)
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        self.capture_key(&v);
+        self.inner.visit_string(v)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_none()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let deserializer = self.wrap(deserializer);
+        self.inner.visit_some(deserializer)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self, deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        let deserializer = self.wrap(deserializer);
+        self.inner.visit_newtype_struct(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        let seq = SeqWrap {
+            inner: seq,
+            path: self.path.clone(),
+            index: 0,
+            tracker: self.tracker,
+        };
+        self.inner.visit_seq(seq)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        let map = MapWrap {
+            inner: map,
+            path: self.path.clone(),
+            fields: self.fields,
+            tracker: self.tracker,
+        };
+        self.inner.visit_map(map)
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        let data = self.wrap(data);
+        self.inner.visit_enum(data)
+    }
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Wrap<'_, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let deserializer = self.wrap(deserializer);
+        self.inner.deserialize(deserializer)
+    }
+}
+
+impl<'de, 't, A: EnumAccess<'de>> EnumAccess<'de> for Wrap<'t, A> {
+    type Error = A::Error;
+    type Variant = Wrap<'t, A::Variant>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self, seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let (value, variant) = self.inner.variant_seed(seed)?;
+        Ok((value, Wrap::new(variant, self.path, self.tracker)))
+    }
+}
+
+impl<'de, A: VariantAccess<'de>> VariantAccess<'de> for Wrap<'_, A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self, seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        let seed = self.wrap(seed);
+        self.inner.newtype_variant_seed(seed)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self, len: usize, visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let visitor = self.wrap_visitor(visitor);
+        self.inner.tuple_variant(len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self, fields: &'static [&'static str], visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let visitor = Wrap {
+            fields,
+            ..self.wrap_visitor(visitor)
+        };
+        self.inner.struct_variant(fields, visitor)
+    }
+}
+
+/// Wraps a sequence, keeping track of the index of each element.
+struct SeqWrap<'t, A> {
+    inner: A,
+    path: Vec<PathSegment>,
+    index: usize,
+    tracker: &'t Tracker,
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for SeqWrap<'_, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self, seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let mut path = self.path.clone();
+        path.push(PathSegment::Index(self.index));
+        self.index += 1;
+
+        self.inner
+            .next_element_seed(Wrap::new(seed, path, self.tracker))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+/// Wraps a map, keeping track of the key of each value.
+struct MapWrap<'t, A> {
+    inner: A,
+    path: Vec<PathSegment>,
+    fields: &'static [&'static str],
+    tracker: &'t Tracker,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for MapWrap<'_, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self, seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let seed = Wrap {
+            is_key: true,
+            ..Wrap::new(seed, self.path.clone(), self.tracker)
+        };
+        self.inner.next_key_seed(seed)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self, seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let key = self
+            .tracker
+            .last_key
+            .borrow_mut()
+            .take()
+            .unwrap_or_default();
+        let mut path = self.path.clone();
+        path.push(PathSegment::Key(key));
+
+        let seed = Wrap {
+            fields: self.fields,
+            ..Wrap::new(seed, path, self.tracker)
+        };
+        self.inner.next_value_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hashbrown::HashMap;
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Outer {
+        inner: Option<Inner>,
+        list: Option<Vec<Inner>>,
+        map: Option<HashMap<String, u32>>,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Inner {
+        name: Option<String>,
+        value: Option<u32>,
+    }
+
+    fn unused_paths(text: &str) -> Vec<String> {
+        let deserializer = toml_edit::de::Deserializer::parse(text).unwrap();
+        let (_, unused): (Outer, _) = deserialize_tracking_unused(deserializer).unwrap();
+
+        unused.iter().map(UnusedKey::display_path).collect()
+    }
+
+    #[test]
+    fn finds_unused_keys() {
+        let text = r#"
+            extra = 1
+
+            [inner]
+            name = "a"
+            valeu = 2
+
+            [[list]]
+            value = 1
+
+            [[list]]
+            nmae = "b"
+            nested = { a = 1 }
+
+            [map]
+            anything = 1
+        "#;
+
+        assert_eq!(
+            unused_paths(text),
+            vec!["extra", "inner.valeu", "list[1].nmae", "list[1].nested"]
+        );
+    }
+
+    #[test]
+    fn unused_keys_have_expected_keys() {
+        let deserializer = toml_edit::de::Deserializer::parse("[inner]\nvalu = 1").unwrap();
+        let (_, unused): (Outer, _) = deserialize_tracking_unused(deserializer).unwrap();
+
+        assert_eq!(unused.len(), 1);
+        assert_eq!(unused[0].name(), Some("valu"));
+        assert_eq!(unused[0].expected, &["name", "value"]);
+    }
+
+    #[test]
+    fn no_unused_keys() {
+        assert!(unused_paths("[inner]\nname = \"a\"\n[map]\nkey = 2").is_empty());
+    }
+}
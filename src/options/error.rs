@@ -1,5 +1,7 @@
 use std::borrow::Cow;
 
+use crate::i18n::tr_args;
+
 /// An error around some option-setting, and the reason.
 ///
 /// These are meant to potentially be user-facing (e.g. explain
@@ -23,8 +25,9 @@ impl OptionError {
 
     /// Create a new [`OptionError::Config`] for an invalid value.
     pub(crate) fn invalid_config_value(value: &str) -> Self {
-        OptionError::Config(Cow::Owned(format!(
-            "'{value}' was set with an invalid value, please update it in your config file."
+        OptionError::Config(Cow::Owned(tr_args(
+            "'{value}' was set with an invalid value, please update it in your config file.",
+            &[("value", &value)],
         )))
     }
 
@@ -35,8 +38,9 @@ impl OptionError {
 
     /// Create a new [`OptionError::Argument`] for an invalid value.
     pub(crate) fn invalid_arg_value(value: &str) -> Self {
-        OptionError::Argument(Cow::Owned(format!(
-            "'--{value}' was set with an invalid value, please update your arguments."
+        OptionError::Argument(Cow::Owned(tr_args(
+            "'--{value}' was set with an invalid value, please update your arguments.",
+            &[("value", &value)],
         )))
     }
 
@@ -51,11 +55,17 @@ pub(crate) type OptionResult<T> = Result<T, OptionError>;
 impl std::fmt::Display for OptionError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            OptionError::Config(reason) => write!(f, "Configuration file error: {reason}"),
-            OptionError::Argument(reason) => write!(f, "Argument error: {reason}"),
-            OptionError::Other(reason) => {
-                write!(f, "Error with the config file or the arguments: {reason}")
+            OptionError::Config(reason) => f.write_str(&tr_args(
+                "Configuration file error: {reason}",
+                &[("reason", reason)],
+            )),
+            OptionError::Argument(reason) => {
+                f.write_str(&tr_args("Argument error: {reason}", &[("reason", reason)]))
             }
+            OptionError::Other(reason) => f.write_str(&tr_args(
+                "Error with the config file or the arguments: {reason}",
+                &[("reason", reason)],
+            )),
         }
     }
 }
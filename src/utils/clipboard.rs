@@ -0,0 +1,99 @@
+//! Copying text to the clipboard through the terminal, or the platform's
+//! clipboard tool.
+
+use std::{
+    io::{self, Write},
+    process::{Command, ExitStatus, Stdio},
+};
+
+use crate::utils::strings::base64;
+
+/// The clipboard tools to try, in order, along with their arguments.
+#[cfg(target_os = "macos")]
+const CLIPBOARD_COMMANDS: &[&[&str]] = &[&["pbcopy"]];
+#[cfg(target_os = "windows")]
+const CLIPBOARD_COMMANDS: &[&[&str]] = &[&["clip"]];
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const CLIPBOARD_COMMANDS: &[&[&str]] = &[
+    &["wl-copy"],
+    &["xclip", "-selection", "clipboard"],
+    &["xsel", "--clipboard", "--input"],
+];
+
+/// Returns the OSC 52 escape code that asks the terminal to set the clipboard
+/// to `text`. Inside tmux, the code has to be wrapped so that tmux passes it on.
+fn osc52(text: &str, is_tmux: bool) -> String {
+    let code = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+
+    if is_tmux {
+        format!("\x1bPtmux;{}\x1b\\", code.replace('\x1b', "\x1b\x1b"))
+    } else {
+        code
+    }
+}
+
+/// Whether bottom is running over SSH, where the clipboard tools would set the
+/// remote machine's clipboard rather than the user's.
+fn is_ssh() -> bool {
+    std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+}
+
+/// Pipes `text` into a clipboard tool and waits for it to finish.
+fn pipe_to_command(args: &[&str], text: &str) -> io::Result<ExitStatus> {
+    let mut child = Command::new(args[0])
+        .args(&args[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // Always wait on the child, even if writing failed, so it gets reaped.
+    let written = match child.stdin.take() {
+        Some(mut stdin) => stdin.write_all(text.as_bytes()),
+        None => Ok(()),
+    };
+    let status = child.wait()?;
+    written?;
+
+    Ok(status)
+}
+
+/// Hands `text` to the first clipboard tool that works. This runs in the
+/// background, and missing or failing tools are skipped.
+fn copy_with_clipboard_tool(text: String) {
+    std::thread::spawn(move || {
+        for args in CLIPBOARD_COMMANDS {
+            if pipe_to_command(args, &text).is_ok_and(|status| status.success()) {
+                break;
+            }
+        }
+    });
+}
+
+/// Copies `text` to the clipboard.
+///
+/// This sends an OSC 52 escape code, which not all terminals support and which
+/// can't report whether it worked, so outside of SSH the text is also handed to
+/// the platform's clipboard tool if there is one.
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    if !is_ssh() {
+        copy_with_clipboard_tool(text.to_string());
+    }
+
+    let is_tmux = std::env::var_os("TMUX").is_some();
+
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(osc52(text, is_tmux).as_bytes())?;
+    stdout.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn osc52_codes() {
+        assert_eq!(osc52("foo", false), "\x1b]52;c;Zm9v\x07");
+        assert_eq!(osc52("foo", true), "\x1bPtmux;\x1b\x1b]52;c;Zm9v\x07\x1b\\");
+    }
+}
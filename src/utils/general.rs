@@ -1,4 +1,9 @@
-use std::cmp::Ordering;
+use std::{
+    cmp::Ordering,
+    fs::{self, File, OpenOptions},
+    io,
+    path::Path,
+};
 
 #[inline]
 pub const fn sort_partial_fn<T: PartialOrd>(is_descending: bool) -> fn(T, T) -> Ordering {
@@ -24,6 +29,29 @@ pub fn partial_ordering_desc<T: PartialOrd>(a: T, b: T) -> Ordering {
     partial_ordering(a, b).reverse()
 }
 
+/// Creates a file at `path` that only the current user can read, replacing
+/// anything already there. Whatever is at `path` is removed rather than
+/// written through, so a symlink planted in a shared directory like the
+/// temporary directory can't be used to have us overwrite another file.
+pub fn create_private_file(path: &Path) -> io::Result<File> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+        _ => {}
+    }
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        options.mode(0o600);
+    }
+
+    options.open(path)
+}
+
 /// A trait for additional clamping functions on numeric types.
 pub trait ClampExt {
     /// Restrict a value by a lower bound. If the current value is _lower_ than
@@ -107,4 +135,34 @@ mod test {
         y.sort_by(|a, b| sort_partial_fn(true)(a, b));
         assert_eq!(y, vec![16.15, 15.0, 1.0, -1.0, -100.0, -100.0, -100.1]);
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn private_files() {
+        use std::{io::Write, os::unix::fs::PermissionsExt};
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+        let path = dir.path().join("report.json");
+        fs::write(&target, "untouched").unwrap();
+        std::os::unix::fs::symlink(&target, &path).unwrap();
+
+        // The symlink is replaced, not written through.
+        create_private_file(&path)
+            .unwrap()
+            .write_all(b"report")
+            .unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), "untouched");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "report");
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        // Writing again replaces the file.
+        create_private_file(&path)
+            .unwrap()
+            .write_all(b"again")
+            .unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "again");
+    }
 }
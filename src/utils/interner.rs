@@ -0,0 +1,90 @@
+//! A simple string interner.
+
+use std::sync::Arc;
+
+use hashbrown::HashSet;
+
+/// A pool of strings, used so that identical strings (e.g. process names and
+/// users) can share one allocation rather than being reallocated for each
+/// process every time we collect data.
+///
+/// Interned strings are [`Arc<str>`]s, so they are cheap to clone and can be
+/// handed off to other threads.
+#[derive(Debug, Default)]
+pub struct StringInterner {
+    strings: HashSet<Arc<str>>,
+    /// Where strings passed to [`StringInterner::intern_with`] are built, which
+    /// is reused so that building them doesn't allocate every time.
+    scratch: String,
+}
+
+impl StringInterner {
+    /// Returns a shared copy of `s`, adding it to the pool if needed.
+    pub fn intern(&mut self, s: &str) -> Arc<str> {
+        self.strings.get_or_insert_with(s, |s| Arc::from(s)).clone()
+    }
+
+    /// Returns a shared copy of the string that `write` builds, adding it to
+    /// the pool if needed. Nothing is allocated if it's already in the pool.
+    pub fn intern_with(&mut self, write: impl FnOnce(&mut String)) -> Arc<str> {
+        self.scratch.clear();
+        write(&mut self.scratch);
+
+        self.strings
+            .get_or_insert_with(self.scratch.as_str(), |s| Arc::from(s))
+            .clone()
+    }
+
+    /// Removes any strings that are no longer used outside of the pool.
+    pub fn prune(&mut self) {
+        self.strings.retain(|s| Arc::strong_count(s) > 1);
+    }
+
+    /// Returns the number of strings in the pool.
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.strings.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn intern_shares_strings() {
+        let mut interner = StringInterner::default();
+
+        let a = interner.intern("btm");
+        let b = interner.intern("btm");
+        let c = interner.intern("bash");
+
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert_eq!(interner.len(), 2);
+
+        let d = interner.intern_with(|s| {
+            s.push_str("bt");
+            s.push('m');
+        });
+        assert!(Arc::ptr_eq(&a, &d));
+
+        let e = interner.intern_with(|s| s.push_str("[kthreadd]"));
+        assert_eq!(&*e, "[kthreadd]");
+        assert_eq!(interner.len(), 3);
+    }
+
+    #[test]
+    fn prune_unused_strings() {
+        let mut interner = StringInterner::default();
+
+        let a = interner.intern("btm");
+        interner.intern("bash");
+        interner.prune();
+        assert_eq!(interner.len(), 1);
+
+        drop(a);
+        interner.prune();
+        assert_eq!(interner.len(), 0);
+    }
+}
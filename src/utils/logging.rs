@@ -1,14 +1,158 @@
-#[cfg(feature = "logging")]
-use std::sync::OnceLock;
+//! Logging to a file, which is off unless one is set with `--log_file`. How much
+//! is logged can be set per module with `--log_level`, and the overall level
+//! can be changed while running with the `log_level` key binding.
+
+use std::{
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{OnceLock, RwLock},
+};
+
+use log::{LevelFilter, Metadata};
 
-#[cfg(feature = "logging")]
 pub static OFFSET: OnceLock<time::UtcOffset> = OnceLock::new();
 
-#[cfg(feature = "logging")]
-pub fn init_logger(
-    min_level: log::LevelFilter, debug_file_name: Option<&std::ffi::OsStr>,
-) -> anyhow::Result<()> {
-    let dispatch = fern::Dispatch::new()
+/// How many rotated log files are kept, as `<file>.1` (the newest) to
+/// `<file>.N`.
+const ROTATED_LOGS: usize = 3;
+
+/// Which levels are logged. Until the logger is set up, nothing is.
+static FILTER: RwLock<LogFilter> = RwLock::new(LogFilter {
+    default: LevelFilter::Off,
+    modules: Vec::new(),
+});
+
+/// Which levels are logged, overall and for specific modules. This is parsed
+/// from a comma-separated list of levels like `warn,bottom::app=debug`, where
+/// the longest matching module prefix is used.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogFilter {
+    default: LevelFilter,
+    modules: Vec<(String, LevelFilter)>,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            default: LevelFilter::Info,
+            modules: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseLogFilterError(String);
+
+impl fmt::Display for ParseLogFilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is not a valid log level", self.0)
+    }
+}
+
+impl FromStr for LogFilter {
+    type Err = ParseLogFilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_level = |level: &str| {
+            level
+                .trim()
+                .parse::<LevelFilter>()
+                .map_err(|_| ParseLogFilterError(level.trim().to_string()))
+        };
+
+        let mut filter = Self::default();
+
+        for directive in s.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((module, level)) => {
+                    filter
+                        .modules
+                        .push((module.trim().to_string(), parse_level(level)?));
+                }
+                None => filter.default = parse_level(directive)?,
+            }
+        }
+
+        Ok(filter)
+    }
+}
+
+impl LogFilter {
+    /// Returns the most detailed level logged for `target`.
+    fn level_for(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .filter(|(module, _)| {
+                target
+                    .strip_prefix(module.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|(module, _)| module.len())
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level_for(metadata.target())
+    }
+
+    /// Returns the most detailed level logged for any module.
+    fn max_level(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, Ord::max)
+    }
+
+    /// Logs one level more, going back to only errors after tracing.
+    fn cycle(&mut self) {
+        self.default = match self.default {
+            LevelFilter::Off | LevelFilter::Trace => LevelFilter::Error,
+            LevelFilter::Error => LevelFilter::Warn,
+            LevelFilter::Warn => LevelFilter::Info,
+            LevelFilter::Info => LevelFilter::Debug,
+            LevelFilter::Debug => LevelFilter::Trace,
+        };
+    }
+}
+
+/// Sets the filter of what is logged, and tells [`log`] the most detailed
+/// level so that anything past it is skipped early.
+fn set_filter(filter: LogFilter) {
+    log::set_max_level(filter.max_level());
+    if let Ok(mut guard) = FILTER.write() {
+        *guard = filter;
+    }
+}
+
+/// Returns the overall level being logged, or `None` if logging is off.
+pub fn log_level() -> Option<LevelFilter> {
+    let level = FILTER.read().ok()?.default;
+    (level != LevelFilter::Off).then_some(level)
+}
+
+/// Logs one level more overall, going back to only errors after tracing. This
+/// does nothing if logging is off.
+pub fn cycle_log_level() {
+    if log_level().is_none() {
+        return;
+    }
+
+    let Ok(mut filter) = FILTER.read().map(|guard| guard.clone()) else {
+        return;
+    };
+    filter.cycle();
+    set_filter(filter);
+}
+
+/// Sets up logging to the file at `path` with `filter`. The file is rotated
+/// once it grows past `max_size` bytes.
+pub fn init_logger(filter: LogFilter, path: &Path, max_size: u64) -> anyhow::Result<()> {
+    let file: Box<dyn Write + Send> = Box::new(RotatingFile::open(path, max_size)?);
+
+    fern::Dispatch::new()
         .format(|out, message, record| {
             let offset = OFFSET.get_or_init(|| {
                 time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC)
@@ -33,96 +177,205 @@ pub fn init_logger(
                 message
             ))
         })
-        .level(min_level);
+        .filter(|metadata| FILTER.read().is_ok_and(|filter| filter.enabled(metadata)))
+        .chain(file)
+        .apply()?;
 
-    if let Some(debug_file_name) = debug_file_name {
-        dispatch.chain(fern::log_file(debug_file_name)?).apply()?;
-    } else {
-        dispatch.chain(std::io::stdout()).apply()?;
-    }
+    // Set afterwards, as setting up the logger sets the max level too.
+    set_filter(filter);
 
     Ok(())
 }
 
+/// A log file that is moved to `<path>.1` once it grows past `max_size`, with
+/// older ones moved along to `<path>.2` and so on, up to [`ROTATED_LOGS`].
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: u64,
+}
+
+impl RotatingFile {
+    fn open(path: &Path, max_size: u64) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            size,
+            max_size,
+        })
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        path.into()
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        for index in (1..ROTATED_LOGS).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                fs::rename(from, self.rotated_path(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated_path(1))?;
+
+        self.file = File::create(&self.path)?;
+        self.size = 0;
+
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    // Records are flushed once written whole, so this is where the file is
+    // rotated to avoid splitting one across files.
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.size > self.max_size {
+            self.rotate()?;
+        }
+
+        Ok(())
+    }
+}
+
 #[macro_export]
 macro_rules! error {
     ($($x:tt)*) => {
-        #[cfg(feature = "logging")]
-        {
-            log::error!($($x)*)
-        }
+        log::error!($($x)*)
     };
 }
 
 #[macro_export]
 macro_rules! warn {
     ($($x:tt)*) => {
-        #[cfg(feature = "logging")]
-        {
-            log::warn!($($x)*)
-        }
+        log::warn!($($x)*)
     };
 }
 
 #[macro_export]
 macro_rules! info {
     ($($x:tt)*) => {
-        #[cfg(feature = "logging")]
-        {
-            log::info!($($x)*)
-        }
+        log::info!($($x)*)
     };
 }
 
 #[macro_export]
 macro_rules! debug {
     ($($x:tt)*) => {
-        #[cfg(feature = "logging")]
-        {
-            log::debug!($($x)*)
-        }
+        log::debug!($($x)*)
     };
 }
 
 #[macro_export]
 macro_rules! trace {
     ($($x:tt)*) => {
-        #[cfg(feature = "logging")]
-        {
-            log::trace!($($x)*)
-        }
+        log::trace!($($x)*)
     };
 }
 
 #[macro_export]
 macro_rules! log {
     ($($x:tt)*) => {
-        #[cfg(feature = "logging")]
-        {
-            log::log!(log::Level::Trace, $($x)*)
-        }
+        log::log!(log::Level::Trace, $($x)*)
     };
     ($level:expr, $($x:tt)*) => {
-        #[cfg(feature = "logging")]
-        {
-            log::log!($level, $($x)*)
-        }
+        log::log!($level, $($x)*)
     };
 }
 
 #[cfg(test)]
 mod test {
+    use super::*;
+
+    #[test]
+    fn parse_filters() {
+        assert_eq!("".parse(), Ok(LogFilter::default()));
+        assert_eq!(
+            "debug, bottom::app = trace,bottom::data_collection=off".parse(),
+            Ok(LogFilter {
+                default: LevelFilter::Debug,
+                modules: vec![
+                    ("bottom::app".to_string(), LevelFilter::Trace),
+                    ("bottom::data_collection".to_string(), LevelFilter::Off),
+                ],
+            })
+        );
+        assert_eq!(
+            "loud".parse::<LogFilter>(),
+            Err(ParseLogFilterError("loud".to_string()))
+        );
+        assert_eq!(
+            "bottom=".parse::<LogFilter>(),
+            Err(ParseLogFilterError(String::new()))
+        );
+    }
 
-    #[cfg(feature = "logging")]
     #[test]
-    fn test_logging_macros() {
-        super::init_logger(log::LevelFilter::Trace, None)
-            .expect("initializing the logger should succeed");
-
-        error!("This is an error.");
-        warn!("This is a warning.");
-        info!("This is an info.");
-        debug!("This is a debug.");
-        info!("This is a trace.");
+    fn module_levels() {
+        let filter: LogFilter = "warn,bottom=info,bottom::app=trace".parse().unwrap();
+        assert_eq!(filter.level_for("other"), LevelFilter::Warn);
+        assert_eq!(filter.level_for("bottom"), LevelFilter::Info);
+        assert_eq!(filter.level_for("bottom::canvas"), LevelFilter::Info);
+        assert_eq!(filter.level_for("bottom::app::states"), LevelFilter::Trace);
+        assert_eq!(filter.level_for("bottom::apple"), LevelFilter::Info);
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+    }
+
+    #[test]
+    fn cycling() {
+        let mut filter: LogFilter = "error".parse().unwrap();
+        let levels: Vec<LevelFilter> = (0..5)
+            .map(|_| {
+                filter.cycle();
+                filter.default
+            })
+            .collect();
+        assert_eq!(
+            levels,
+            [
+                LevelFilter::Warn,
+                LevelFilter::Info,
+                LevelFilter::Debug,
+                LevelFilter::Trace,
+                LevelFilter::Error
+            ]
+        );
+    }
+
+    #[test]
+    fn rotation() {
+        let dir = std::env::temp_dir().join(format!("btm-log-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("bottom.log");
+
+        let mut file = RotatingFile::open(&path, 10).unwrap();
+        for record in ["first record\n", "second record\n", "third\n"] {
+            file.write_all(record.as_bytes()).unwrap();
+            file.flush().unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "third\n");
+        assert_eq!(
+            fs::read_to_string(file.rotated_path(1)).unwrap(),
+            "second record\n"
+        );
+        assert_eq!(
+            fs::read_to_string(file.rotated_path(2)).unwrap(),
+            "first record\n"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 }
@@ -1,14 +1,51 @@
+#[cfg(feature = "app")]
 use tui::text::Text;
-use unicode_ellipsis::truncate_str;
+#[cfg(feature = "app")]
+use unicode_ellipsis::{grapheme_width, truncate_str};
+#[cfg(feature = "app")]
+use unicode_segmentation::UnicodeSegmentation;
 
 /// Truncates text if it is too long, and adds an ellipsis at the end if needed.
 ///
 /// TODO: Maybe cache results from this function for some cases? e.g. columns
+#[cfg(feature = "app")]
 #[inline]
 pub fn truncate_to_text<'a, U: Into<usize>>(content: &str, width: U) -> Text<'a> {
     Text::raw(truncate_str(content, width.into()).to_string())
 }
 
+/// Returns how many columns `content` takes up when drawn. This goes by
+/// grapheme, so wide characters like CJK and emoji take up two columns, and
+/// combining characters take up none of their own.
+#[cfg(feature = "app")]
+pub fn str_width(content: &str) -> usize {
+    UnicodeSegmentation::graphemes(content, true)
+        .map(grapheme_width)
+        .sum()
+}
+
+/// Cuts `content` down to at most `width` columns without an ellipsis, and pads
+/// it with spaces to exactly `width` columns. A wide character that would only
+/// partly fit is dropped and padded over instead.
+#[cfg(feature = "app")]
+pub fn fit_to_width(content: &str, width: usize) -> String {
+    let mut fitted = String::with_capacity(width);
+    let mut used = 0;
+
+    for grapheme in UnicodeSegmentation::graphemes(content, true) {
+        let grapheme_width = grapheme_width(grapheme);
+        if used + grapheme_width > width {
+            break;
+        }
+
+        fitted.push_str(grapheme);
+        used += grapheme_width;
+    }
+
+    fitted.extend(std::iter::repeat(' ').take(width - used));
+    fitted
+}
+
 /// Checks that the first string is equal to any of the other ones in a ASCII
 /// case-insensitive match.
 ///
@@ -33,6 +70,30 @@ macro_rules! multi_eq_ignore_ascii_case {
     };
 }
 
+/// Encodes bytes as standard, padded base64.
+#[cfg(feature = "app")]
+pub fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (index, byte)| {
+            group | (u32::from(*byte) << (16 - 8 * index))
+        });
+
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (group >> (18 - 6 * index)) & 0x3f;
+                encoded.push(char::from(ALPHABET[sextet as usize]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -64,4 +125,68 @@ mod tests {
             "multi non-matching should fail"
         );
     }
+
+    #[cfg(feature = "app")]
+    #[test]
+    fn test_str_width() {
+        use super::str_width;
+
+        assert_eq!(str_width(""), 0);
+        assert_eq!(str_width("firefox"), 7);
+        assert_eq!(str_width("Größe"), 5);
+        assert_eq!(str_width("火狐"), 4);
+        assert_eq!(str_width("btm 🦀"), 6);
+        assert_eq!(str_width("e\u{301}"), 1);
+        assert_eq!(str_width("├─ init"), 7);
+    }
+
+    #[cfg(feature = "app")]
+    #[test]
+    fn test_fit_to_width() {
+        use super::{fit_to_width, str_width};
+
+        assert_eq!(fit_to_width("root", 6), "root  ");
+        assert_eq!(fit_to_width("firefox", 4), "fire");
+        assert_eq!(fit_to_width("火狐浏览器", 6), "火狐浏");
+
+        // A wide character that only half fits is padded over.
+        assert_eq!(fit_to_width("火狐浏览器", 5), "火狐 ");
+        assert_eq!(fit_to_width("a🦀b", 2), "a ");
+
+        for text in ["用户", "e\u{301}clair", "mixed 字 text 🦀", ""] {
+            for width in 0..12 {
+                assert_eq!(str_width(&fit_to_width(text, width)), width);
+            }
+        }
+    }
+
+    #[cfg(feature = "app")]
+    #[test]
+    fn test_truncate_to_text_mixed_width() {
+        use super::{str_width, truncate_to_text};
+
+        for (text, width) in [("火狐浏览器", 5), ("a🦀b🦀c", 4), ("Größenänderung", 6)]
+        {
+            let truncated = truncate_to_text(text, width);
+            assert!(str_width(&truncated.lines[0].to_string()) <= width);
+        }
+
+        // Text that fits isn't changed.
+        assert_eq!(
+            truncate_to_text("火狐", 4usize),
+            tui::text::Text::raw("火狐")
+        );
+    }
+
+    #[cfg(feature = "app")]
+    #[test]
+    fn test_base64() {
+        use super::base64;
+
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
 }
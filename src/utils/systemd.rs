@@ -0,0 +1,149 @@
+//! Support for running under systemd: readiness and watchdog notifications with
+//! the [`sd_notify`](https://www.freedesktop.org/software/systemd/man/latest/sd_notify.html)
+//! protocol, and structured logging with the
+//! [native journal protocol](https://systemd.io/JOURNAL_NATIVE_PROTOCOL/).
+//!
+//! These are implemented directly over Unix sockets rather than linking
+//! against libsystemd, and quietly do nothing if not running under systemd.
+
+use std::{
+    env,
+    io::{self, stderr},
+    os::{
+        linux::net::SocketAddrExt,
+        unix::net::{SocketAddr, UnixDatagram},
+    },
+    time::Duration,
+};
+
+/// The socket journald listens to for native protocol messages.
+const JOURNAL_SOCKET: &str = "/run/systemd/journal/socket";
+
+/// Sends a state (ex. `READY=1`) to the service manager, if it asked for them
+/// by setting `NOTIFY_SOCKET`. Returns whether the state was sent.
+pub(crate) fn notify(state: &str) -> bool {
+    let Some(path) = env::var_os("NOTIFY_SOCKET") else {
+        return false;
+    };
+    let path = path.to_string_lossy();
+
+    // Names starting with `@` are in the abstract namespace.
+    let addr = match path.strip_prefix('@') {
+        Some(name) => SocketAddr::from_abstract_name(name.as_bytes()),
+        None => SocketAddr::from_pathname(path.as_ref()),
+    };
+
+    addr.and_then(|addr| UnixDatagram::unbound()?.send_to_addr(state.as_bytes(), &addr))
+        .is_ok()
+}
+
+/// Returns how often the service manager expects watchdog pings, if it
+/// enabled the watchdog for this process.
+pub(crate) fn watchdog_interval() -> Option<Duration> {
+    parse_watchdog(
+        env::var("WATCHDOG_USEC").ok().as_deref(),
+        env::var("WATCHDOG_PID").ok().as_deref(),
+        std::process::id(),
+    )
+}
+
+/// Parses `WATCHDOG_USEC` and `WATCHDOG_PID`. If `WATCHDOG_PID` is set, the
+/// watchdog is only meant for the process with that PID.
+fn parse_watchdog(usec: Option<&str>, pid: Option<&str>, own_pid: u32) -> Option<Duration> {
+    if let Some(pid) = pid {
+        if pid.trim().parse::<u32>().ok()? != own_pid {
+            return None;
+        }
+    }
+
+    match usec?.trim().parse::<u64>().ok()? {
+        0 => None,
+        usec => Some(Duration::from_micros(usec)),
+    }
+}
+
+/// Whether stderr is connected to the journal, as systemd signals with
+/// `JOURNAL_STREAM` (the device and inode numbers of the stream).
+pub(crate) fn is_journal_stream() -> bool {
+    let Some(stream) = env::var_os("JOURNAL_STREAM") else {
+        return false;
+    };
+    let Ok(stat) = rustix::fs::fstat(stderr()) else {
+        return false;
+    };
+
+    stream.to_string_lossy() == format!("{}:{}", stat.st_dev, stat.st_ino)
+}
+
+/// Sends a warning to the journal as a structured entry. `fields` are extra
+/// fields, whose keys must be uppercase letters, digits, and underscores.
+pub(crate) fn journal_warn(message: &str, fields: &[(&str, &str)]) -> io::Result<()> {
+    // Priorities are the same as syslog's, where 4 is a warning.
+    let mut entry_fields = vec![
+        ("PRIORITY", "4"),
+        ("SYSLOG_IDENTIFIER", "btm"),
+        ("MESSAGE", message),
+    ];
+    entry_fields.extend_from_slice(fields);
+
+    UnixDatagram::unbound()?
+        .send_to(&encode_journal_entry(&entry_fields), JOURNAL_SOCKET)
+        .map(|_| ())
+}
+
+/// Encodes fields for the native journal protocol. Values with a newline in
+/// them are sent as a length-prefixed binary field instead of `KEY=value`.
+fn encode_journal_entry(fields: &[(&str, &str)]) -> Vec<u8> {
+    let mut entry = Vec::new();
+
+    for (key, value) in fields {
+        entry.extend_from_slice(key.as_bytes());
+
+        if value.contains('\n') {
+            entry.push(b'\n');
+            entry.extend_from_slice(&(value.len() as u64).to_le_bytes());
+            entry.extend_from_slice(value.as_bytes());
+        } else {
+            entry.push(b'=');
+            entry.extend_from_slice(value.as_bytes());
+        }
+
+        entry.push(b'\n');
+    }
+
+    entry
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn watchdog() {
+        assert_eq!(
+            parse_watchdog(Some("30000000"), None, 10),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_watchdog(Some("30000000"), Some("10"), 10),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(parse_watchdog(Some("30000000"), Some("11"), 10), None);
+        assert_eq!(parse_watchdog(Some("0"), None, 10), None);
+        assert_eq!(parse_watchdog(None, None, 10), None);
+        assert_eq!(parse_watchdog(Some("soon"), None, 10), None);
+    }
+
+    #[test]
+    fn journal_entry() {
+        assert_eq!(
+            encode_journal_entry(&[("MESSAGE", "hi"), ("BTM_SOURCE", "disk")]),
+            b"MESSAGE=hi\nBTM_SOURCE=disk\n"
+        );
+
+        let mut multiline = b"MESSAGE\n".to_vec();
+        multiline.extend_from_slice(&3_u64.to_le_bytes());
+        multiline.extend_from_slice(b"a\nb\n");
+        assert_eq!(encode_journal_entry(&[("MESSAGE", "a\nb")]), multiline);
+    }
+}
@@ -0,0 +1,142 @@
+//! How times are shown in graph labels and the frozen indicator, either as how
+//! long ago they were, or as the time of day.
+
+use std::{
+    fmt::Display,
+    str::FromStr,
+    sync::OnceLock,
+    time::{Duration, SystemTime},
+};
+
+use time::{format_description::OwnedFormatItem, OffsetDateTime, UtcOffset};
+
+/// The format used by `clock`.
+const CLOCK_FORMAT: &str = "[hour]:[minute]:[second]";
+
+/// The local time zone's offset, which is cached as reading it can fail once
+/// other threads have started on some platforms.
+static LOCAL_OFFSET: OnceLock<UtcOffset> = OnceLock::new();
+
+/// Returns the local time zone's offset, or UTC if it can't be read. Call this
+/// before starting other threads so the right offset is cached.
+pub(crate) fn local_offset() -> UtcOffset {
+    *LOCAL_OFFSET.get_or_init(|| UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC))
+}
+
+/// How to show a point in time.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum TimeFormat {
+    /// How long ago it was, like `60s`.
+    #[default]
+    Relative,
+
+    /// The local time, formatted with a `time` format description like
+    /// `[hour]:[minute]:[second]`.
+    Clock {
+        description: String,
+        items: OwnedFormatItem,
+    },
+}
+
+impl TimeFormat {
+    /// Formats the time that was `ago` before `latest`.
+    pub fn format(&self, ago: Duration, latest: SystemTime) -> String {
+        match self {
+            TimeFormat::Relative => format!("{}s", ago.as_secs()),
+            TimeFormat::Clock { items, .. } => {
+                let time = OffsetDateTime::from(latest.checked_sub(ago).unwrap_or(latest));
+                let time = time.checked_to_offset(local_offset()).unwrap_or(time);
+
+                time.format(items).unwrap_or_default()
+            }
+        }
+    }
+}
+
+impl FromStr for TimeFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let description = match s.trim() {
+            "relative" => return Ok(TimeFormat::Relative),
+            "clock" => CLOCK_FORMAT,
+            custom if custom.contains('[') => custom,
+            _ => {
+                return Err(format!(
+                    "'{s}' isn't 'relative', 'clock', or a format like '{CLOCK_FORMAT}'"
+                ));
+            }
+        };
+
+        let items = time::format_description::parse_owned::<1>(description)
+            .map_err(|err| format!("'{s}' isn't a valid time format: {err}"))?;
+
+        Ok(TimeFormat::Clock {
+            description: description.to_string(),
+            items,
+        })
+    }
+}
+
+impl Display for TimeFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeFormat::Relative => f.write_str("relative"),
+            TimeFormat::Clock { description, .. } => f.write_str(description),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_formats() {
+        assert_eq!("relative".parse(), Ok(TimeFormat::Relative));
+        assert_eq!(
+            "clock".parse::<TimeFormat>().unwrap().to_string(),
+            CLOCK_FORMAT
+        );
+        assert_eq!(
+            "[hour]:[minute]".parse::<TimeFormat>().unwrap().to_string(),
+            "[hour]:[minute]"
+        );
+
+        assert!("hh:mm".parse::<TimeFormat>().is_err());
+        assert!("[hour".parse::<TimeFormat>().is_err());
+        assert!("[not_a_component]".parse::<TimeFormat>().is_err());
+    }
+
+    #[test]
+    fn format_relative() {
+        let latest = SystemTime::now();
+
+        assert_eq!(
+            TimeFormat::Relative.format(Duration::from_millis(60_500), latest),
+            "60s"
+        );
+        assert_eq!(TimeFormat::Relative.format(Duration::ZERO, latest), "0s");
+    }
+
+    #[test]
+    fn format_clock() {
+        let format: TimeFormat = "[hour]:[minute]:[second]".parse().unwrap();
+        let latest = SystemTime::UNIX_EPOCH + Duration::from_secs(12 * 60 * 60);
+
+        // Compare against the same time formatted directly, as the local time
+        // zone differs between machines.
+        let expected =
+            OffsetDateTime::from(SystemTime::UNIX_EPOCH + Duration::from_secs(11 * 60 * 60))
+                .to_offset(local_offset());
+        assert_eq!(
+            format.format(Duration::from_secs(60 * 60), latest),
+            format!(
+                "{:02}:{:02}:{:02}",
+                expected.hour(),
+                expected.minute(),
+                expected.second()
+            )
+        );
+    }
+}
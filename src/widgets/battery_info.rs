@@ -2,4 +2,6 @@
 pub struct BatteryWidgetState {
     pub currently_selected_battery_index: usize,
     pub tab_click_locs: Option<Vec<((u16, u16), (u16, u16))>>,
+    /// A title to use instead of the default one.
+    pub title: Option<String>,
 }
@@ -1,16 +1,13 @@
 use std::{borrow::Cow, num::NonZeroU16, time::Instant};
 
 use concat_string::concat_string;
-use tui::widgets::Row;
+use tui::{layout::Rect, widgets::Row};
 
 use crate::{
     app::AppConfigFields,
-    canvas::{
-        components::data_table::{
-            Column, ColumnHeader, DataTable, DataTableColumn, DataTableProps, DataTableStyling,
-            DataToCell,
-        },
-        Painter,
+    canvas::components::data_table::{
+        Column, ColumnHeader, DataTable, DataTableColumn, DataTableProps, DataTableStyling,
+        DataToCell,
     },
     data_collection::cpu::CpuDataType,
     data_conversion::CpuWidgetData,
@@ -104,16 +101,16 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
     }
 
     #[inline(always)]
-    fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
+    fn style_row<'a>(&self, row: Row<'a>, styles: &Styles) -> Row<'a> {
         let style = match self {
-            CpuWidgetTableData::All => painter.styles.all_cpu_colour,
+            CpuWidgetTableData::All => styles.all_cpu_colour,
             CpuWidgetTableData::Entry {
                 data_type,
                 last_entry: _,
             } => match data_type {
-                CpuDataType::Avg => painter.styles.avg_cpu_colour,
+                CpuDataType::Avg => styles.avg_cpu_colour,
                 CpuDataType::Cpu(index) => {
-                    painter.styles.cpu_colour_styles[index % painter.styles.cpu_colour_styles.len()]
+                    styles.cpu_colour_styles[index % styles.cpu_colour_styles.len()]
                 }
             },
         };
@@ -133,9 +130,13 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
 
 pub struct CpuWidgetState {
     pub current_display_time: u64,
+    pub display_offset: u64,
     pub is_legend_hidden: bool,
     pub autohide_timer: Option<Instant>,
+    pub graph_area: Rect,
     pub table: DataTable<CpuWidgetTableData, CpuWidgetColumn>,
+    /// A title to use instead of the default one.
+    pub title: Option<String>,
 }
 
 impl CpuWidgetState {
@@ -151,10 +152,14 @@ impl CpuWidgetState {
         let props = DataTableProps {
             title: None,
             table_gap: config.table_gap,
+            row_height: config.table_row_height(),
             left_to_right: false,
             is_basic: false,
             show_table_scroll_position: false, // TODO: Should this be possible?
+            show_table_scrollbar: false,
             show_current_entry_when_unfocused: true,
+            is_striped: false,
+            show_row_separators: false,
         };
 
         let styling = DataTableStyling::from_palette(colours);
@@ -169,16 +174,37 @@ impl CpuWidgetState {
 
         CpuWidgetState {
             current_display_time,
+            display_offset: 0,
             is_legend_hidden: false,
             autohide_timer,
+            graph_area: Rect::default(),
             table,
+            title: None,
         }
     }
 
-    pub fn update_table(&mut self, data: &[CpuWidgetData]) {
+    /// Updates the legend table. If `inspected_values` is set, then those are
+    /// shown as the usage values instead of the latest ones.
+    pub fn update_table(&mut self, data: &[CpuWidgetData], inspected_values: Option<&[f64]>) {
         self.table.set_data(
             data.iter()
-                .map(CpuWidgetTableData::from_cpu_widget_data)
+                .enumerate()
+                .map(|(index, data)| {
+                    let mut table_data = CpuWidgetTableData::from_cpu_widget_data(data);
+                    if let (CpuWidgetTableData::Entry { last_entry, .. }, Some(inspected_values)) =
+                        (&mut table_data, inspected_values)
+                    {
+                        // The first entry is always "All", which has no value.
+                        if let Some(value) = index
+                            .checked_sub(1)
+                            .and_then(|index| inspected_values.get(index))
+                        {
+                            *last_entry = *value;
+                        }
+                    }
+
+                    table_data
+                })
                 .collect(),
         );
     }
@@ -8,8 +8,9 @@ use crate::{
         ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
         SortDataTable, SortDataTableProps, SortOrder, SortsRow,
     },
+    i18n::tr,
     options::config::style::Styles,
-    utils::{data_prefixes::get_decimal_bytes, general::sort_partial_fn},
+    utils::{data_prefixes::get_decimal_bytes, general::sort_partial_fn, strings::str_width},
 };
 
 #[derive(Clone, Debug)]
@@ -25,7 +26,7 @@ pub struct DiskWidgetData {
 }
 
 impl DiskWidgetData {
-    fn total_space(&self) -> Cow<'static, str> {
+    pub fn total_space(&self) -> Cow<'static, str> {
         if let Some(total_bytes) = self.total_bytes {
             let converted_total_space = get_decimal_bytes(total_bytes);
             format!("{:.0}{}", converted_total_space.0, converted_total_space.1).into()
@@ -43,7 +44,7 @@ impl DiskWidgetData {
         }
     }
 
-    fn used_space(&self) -> Cow<'static, str> {
+    pub fn used_space(&self) -> Cow<'static, str> {
         if let Some(used_bytes) = self.used_bytes {
             let converted_free_space = get_decimal_bytes(used_bytes);
             format!("{:.0}{}", converted_free_space.0, converted_free_space.1).into()
@@ -66,7 +67,7 @@ impl DiskWidgetData {
         }
     }
 
-    fn used_percent(&self) -> Option<f64> {
+    pub fn used_percent(&self) -> Option<f64> {
         if let (Some(used_bytes), Some(summed_total_bytes)) =
             (self.used_bytes, self.summed_total_bytes)
         {
@@ -190,8 +191,8 @@ impl DataToCell<DiskColumn> for DiskWidgetData {
         let mut widths = vec![0; 7];
 
         data.iter().for_each(|row| {
-            widths[0] = max(widths[0], row.name.len() as u16);
-            widths[1] = max(widths[1], row.mount_point.len() as u16);
+            widths[0] = max(widths[0], str_width(&row.name) as u16);
+            widths[1] = max(widths[1], str_width(&row.mount_point) as u16);
         });
 
         widths
@@ -201,6 +202,12 @@ impl DataToCell<DiskColumn> for DiskWidgetData {
 pub struct DiskTableWidget {
     pub table: SortDataTable<DiskWidgetData, DiskColumn>,
     pub force_update_data: bool,
+
+    /// Mount points that are always shown first, in this order.
+    pub pinned_mounts: Vec<String>,
+
+    /// The mount point whose usage is shown as a gauge in basic mode.
+    pub focus_mount: Option<String>,
 }
 
 impl SortsRow for DiskColumn {
@@ -278,12 +285,16 @@ impl DiskTableWidget {
     pub fn new(config: &AppConfigFields, palette: &Styles, columns: Option<&[DiskColumn]>) -> Self {
         let props = SortDataTableProps {
             inner: DataTableProps {
-                title: Some(" Disks ".into()),
+                title: Some(tr(" Disks ").into()),
                 table_gap: config.table_gap,
+                row_height: config.table_row_height(),
                 left_to_right: true,
                 is_basic: config.use_basic_mode,
                 show_table_scroll_position: config.show_table_scroll_position,
+                show_table_scrollbar: config.show_table_scrollbar,
                 show_current_entry_when_unfocused: false,
+                is_striped: config.table_stripes,
+                show_row_separators: config.table_row_separators,
             },
             sort_index: 0,
             order: SortOrder::Ascending,
@@ -297,11 +308,15 @@ impl DiskTableWidget {
                 Self {
                     table: SortDataTable::new_sortable(columns, props, styling),
                     force_update_data: false,
+                    pinned_mounts: Vec::new(),
+                    focus_mount: None,
                 }
             }
             None => Self {
                 table: SortDataTable::new_sortable(default_disk_columns(), props, styling),
                 force_update_data: false,
+                pinned_mounts: Vec::new(),
+                focus_mount: None,
             },
         }
     }
@@ -318,9 +333,62 @@ impl DiskTableWidget {
         if let Some(column) = self.table.columns.get(self.table.sort_index()) {
             column.sort_by(&mut data, self.table.order());
         }
+
+        // Pinned mounts go first, while the rest keep their sorted order.
+        if !self.pinned_mounts.is_empty() {
+            data.sort_by_key(|row| {
+                self.pinned_mounts
+                    .iter()
+                    .position(|mount| *mount == row.mount_point)
+                    .unwrap_or(usize::MAX)
+            });
+        }
         self.table.set_data(data);
     }
 
+    /// Pins the selected disk's mount point after the other pinned ones, or
+    /// unpins it if it's already pinned.
+    pub fn toggle_pin_current(&mut self) {
+        let Some(row) = self.table.current_item() else {
+            return;
+        };
+
+        if let Some(index) = self
+            .pinned_mounts
+            .iter()
+            .position(|mount| *mount == row.mount_point)
+        {
+            self.pinned_mounts.remove(index);
+        } else {
+            self.pinned_mounts.push(row.mount_point.to_string());
+        }
+        self.force_data_update();
+    }
+
+    /// Moves the focus to the next mount point in the table, wrapping around
+    /// to the first.
+    pub fn focus_next_mount(&mut self) {
+        let rows = self.table.data();
+        let next = self
+            .focus_mount
+            .as_ref()
+            .and_then(|focus| rows.iter().position(|row| row.mount_point == *focus))
+            .map_or(0, |index| (index + 1) % rows.len());
+
+        if let Some(row) = rows.get(next) {
+            self.focus_mount = Some(row.mount_point.to_string());
+        }
+    }
+
+    /// The disk whose usage is shown as a gauge in basic mode, if it's shown.
+    pub fn focused_disk(&self) -> Option<&DiskWidgetData> {
+        let focus = self.focus_mount.as_ref()?;
+        self.table
+            .data()
+            .iter()
+            .find(|row| row.mount_point == *focus)
+    }
+
     pub fn set_index(&mut self, index: usize) {
         self.table.set_sort_index(index);
         self.force_data_update();
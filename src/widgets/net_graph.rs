@@ -1,15 +1,24 @@
 use std::time::Instant;
 
+use tui::layout::Rect;
+
 pub struct NetWidgetState {
     pub current_display_time: u64,
+    pub display_offset: u64,
     pub autohide_timer: Option<Instant>,
+    pub graph_area: Rect,
+    /// A title to use instead of the default one.
+    pub title: Option<String>,
 }
 
 impl NetWidgetState {
     pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
         NetWidgetState {
             current_display_time,
+            display_offset: 0,
             autohide_timer,
+            graph_area: Rect::default(),
+            title: None,
         }
     }
 }
@@ -1,9 +1,10 @@
 pub mod process_columns;
 pub mod process_data;
 pub mod query;
+mod sort_keys;
 mod sort_table;
 
-use std::{borrow::Cow, collections::BTreeMap};
+use std::{borrow::Cow, collections::BTreeMap, hash::Hash, mem, str::FromStr, sync::Arc};
 
 use hashbrown::{HashMap, HashSet};
 use indexmap::IndexSet;
@@ -11,8 +12,13 @@ use itertools::Itertools;
 pub use process_columns::*;
 pub use process_data::*;
 use query::{parse_query, ProcessQuery};
+use serde::{de::IntoDeserializer, Deserialize};
+use sort_keys::SortKeyCache;
 use sort_table::SortTableColumn;
+use unicode_segmentation::GraphemeCursor;
 
+#[cfg(target_os = "linux")]
+use crate::data_collection::processes::{linux::namespace::NamespaceKinds, ProcessFields};
 use crate::{
     app::{
         data_farmer::{DataCollection, ProcessData},
@@ -20,9 +26,10 @@ use crate::{
     },
     canvas::components::data_table::{
         Column, ColumnHeader, ColumnWidthBounds, DataTable, DataTableColumn, DataTableProps,
-        DataTableStyling, SortColumn, SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+        DataTableStyling, SortColumn, SortDataTable, SortDataTableProps, SortOrder,
     },
     data_collection::processes::{Pid, ProcessHarvest},
+    i18n::tr,
     options::config::style::Styles,
 };
 
@@ -60,16 +67,78 @@ impl ProcessSearchState {
     }
 }
 
+/// Which branches of the process tree, or which users' groups of processes,
+/// are collapsed. In the tree, processes are told apart by their PID and start
+/// time, so a branch stays collapsed across refreshes, but a new process that
+/// reuses its PID doesn't start off collapsed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollapsedBranches<K: Eq + Hash = (Pid, u64)> {
+    /// Whether branches are collapsed unless toggled.
+    all_collapsed: bool,
+
+    /// The branches toggled away from `all_collapsed`.
+    toggled: HashSet<K>,
+}
+
+impl<K: Eq + Hash> Default for CollapsedBranches<K> {
+    fn default() -> Self {
+        Self {
+            all_collapsed: false,
+            toggled: HashSet::default(),
+        }
+    }
+}
+
+impl<K: Eq + Hash> CollapsedBranches<K> {
+    /// Whether the given branch is collapsed.
+    fn is_collapsed(&self, key: &K, has_children: bool) -> bool {
+        if self.toggled.contains(key) {
+            !self.all_collapsed
+        } else {
+            // Collapsing everything shouldn't mark processes without children.
+            self.all_collapsed && has_children
+        }
+    }
+
+    fn toggle(&mut self, key: K) {
+        if !self.toggled.remove(&key) {
+            self.toggled.insert(key);
+        }
+    }
+
+    fn collapse(&mut self, key: K) {
+        if self.all_collapsed {
+            self.toggled.remove(&key);
+        } else {
+            self.toggled.insert(key);
+        }
+    }
+
+    fn set_all(&mut self, collapsed: bool) {
+        self.all_collapsed = collapsed;
+        self.toggled.clear();
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ProcWidgetMode {
-    Tree { collapsed_pids: HashSet<Pid> },
+    Tree {
+        collapsed: CollapsedBranches,
+    },
     Grouped,
+    /// Grouped by user, then by name under each user.
+    UserGrouped {
+        collapsed: CollapsedBranches<Arc<str>>,
+    },
     Normal,
 }
 
+/// The PIDs of each user's processes, by name.
+type UserPidMap = HashMap<Arc<str>, StringPidMap>;
+
 type ProcessTable = SortDataTable<ProcWidgetData, ProcColumn>;
 type SortTable = DataTable<Cow<'static, str>, SortTableColumn>;
-type StringPidMap = HashMap<String, Vec<Pid>>;
+type StringPidMap = HashMap<Arc<str>, Vec<Pid>>;
 
 fn make_column(column: ProcColumn) -> SortColumn<ProcColumn> {
     use ProcColumn::*;
@@ -89,12 +158,28 @@ fn make_column(column: ProcColumn) -> SortColumn<ProcColumn> {
         User => SortColumn::soft(User, Some(0.05)),
         State => SortColumn::hard(State, 9),
         Time => SortColumn::new(Time),
+        #[cfg(target_os = "linux")]
+        Label => SortColumn::soft(Label, Some(0.1)),
+        #[cfg(target_os = "linux")]
+        PidNamespace => SortColumn::hard(PidNamespace, 10),
+        #[cfg(target_os = "linux")]
+        NetNamespace => SortColumn::hard(NetNamespace, 10),
+        #[cfg(target_os = "linux")]
+        MntNamespace => SortColumn::hard(MntNamespace, 10),
+        #[cfg(target_os = "linux")]
+        CpuWaitPercent => SortColumn::new(CpuWaitPercent).default_descending(),
+        #[cfg(target_os = "linux")]
+        IoWaitPercent => SortColumn::new(IoWaitPercent).default_descending(),
+        #[cfg(target_os = "linux")]
+        IoPriority => SortColumn::new(IoPriority),
         #[cfg(feature = "gpu")]
         GpuMemValue => SortColumn::new(GpuMemValue).default_descending(),
         #[cfg(feature = "gpu")]
         GpuMemPercent => SortColumn::new(GpuMemPercent).default_descending(),
         #[cfg(feature = "gpu")]
         GpuUtilPercent => SortColumn::new(GpuUtilPercent).default_descending(),
+        #[cfg(feature = "plugins")]
+        Plugin { index, header } => SortColumn::new(Plugin { index, header }),
     }
 }
 
@@ -105,6 +190,49 @@ pub struct ProcTableConfig {
     pub is_use_regex: bool,
     pub show_memory_as_values: bool,
     pub is_command: bool,
+    pub show_tree_totals: bool,
+    pub default_sort: Option<ProcDefaultSort>,
+}
+
+/// The column and order process widgets are sorted by when created, like
+/// `mem:desc`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProcDefaultSort {
+    pub column: ProcWidgetColumn,
+    /// The order to sort in, or the column's usual order if not given.
+    pub order: Option<SortOrder>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseProcDefaultSortError;
+
+impl FromStr for ProcDefaultSort {
+    type Err = ParseProcDefaultSortError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (column, order) = match s.split_once(':') {
+            Some((column, order)) => (column, Some(order)),
+            None => (s, None),
+        };
+
+        // Columns are named the same way as in the config file.
+        let column = ProcColumn::deserialize(column.trim().into_deserializer())
+            .map_err(|_: serde::de::value::Error| ParseProcDefaultSortError)?;
+        let order = match order
+            .map(|order| order.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("asc" | "ascending") => Some(SortOrder::Ascending),
+            Some("desc" | "descending") => Some(SortOrder::Descending),
+            Some(_) => return Err(ParseProcDefaultSortError),
+            None => None,
+        };
+
+        Ok(Self {
+            column: ProcWidgetColumn::from(&column),
+            order,
+        })
+    }
 }
 
 /// A hacky workaround for now.
@@ -121,10 +249,29 @@ pub enum ProcWidgetColumn {
     User,
     State,
     Time,
+    #[cfg(target_os = "linux")]
+    Label,
+    #[cfg(target_os = "linux")]
+    PidNamespace,
+    #[cfg(target_os = "linux")]
+    NetNamespace,
+    #[cfg(target_os = "linux")]
+    MntNamespace,
+    #[cfg(target_os = "linux")]
+    CpuWait,
+    #[cfg(target_os = "linux")]
+    IoWait,
+    #[cfg(target_os = "linux")]
+    IoPriority,
     #[cfg(feature = "gpu")]
     GpuMem,
     #[cfg(feature = "gpu")]
     GpuUtil,
+    #[cfg(feature = "plugins")]
+    Plugin {
+        index: usize,
+        header: &'static str,
+    },
 }
 
 // This is temporary. Switch back to `ProcColumn` later!
@@ -148,12 +295,22 @@ pub struct ProcWidgetState {
     /// A name-to-pid mapping.
     pub id_pid_map: StringPidMap,
 
+    /// The name-to-pid mapping of each user, when grouping by user.
+    user_pid_map: UserPidMap,
+
     /// The default sort index.
     default_sort_index: usize,
 
     /// The default sort order.
     default_sort_order: SortOrder,
 
+    /// Sort keys and row order kept from the last refresh.
+    sort_cache: SortKeyCache,
+
+    /// Whether parents in tree mode show the totals of their branch rather
+    /// than just their own usage.
+    pub show_tree_totals: bool,
+
     pub is_sort_open: bool,
     pub force_rerender: bool,
     pub force_update_data: bool,
@@ -166,10 +323,14 @@ impl ProcWidgetState {
         let props = DataTableProps {
             title: None,
             table_gap: config.table_gap,
+            row_height: config.table_row_height(),
             left_to_right: true,
             is_basic: false,
             show_table_scroll_position: false,
+            show_table_scrollbar: false,
             show_current_entry_when_unfocused: false,
+            is_striped: false,
+            show_row_separators: false,
         };
         let styling = DataTableStyling::from_palette(palette);
 
@@ -181,12 +342,16 @@ impl ProcWidgetState {
         default_index: usize, default_order: SortOrder,
     ) -> ProcessTable {
         let inner_props = DataTableProps {
-            title: Some(" Processes ".into()),
+            title: Some(tr(" Processes ").into()),
             table_gap: config.table_gap,
+            row_height: config.table_row_height(),
             left_to_right: true,
             is_basic: config.use_basic_mode,
             show_table_scroll_position: config.show_table_scroll_position,
+            show_table_scrollbar: config.show_table_scrollbar,
             show_current_entry_when_unfocused: false,
+            is_striped: config.table_stripes,
+            show_row_separators: config.table_row_separators,
         };
         let props = SortDataTableProps {
             inner: inner_props,
@@ -222,7 +387,10 @@ impl ProcWidgetState {
         let columns: Vec<SortColumn<ProcColumn>> = {
             use ProcColumn::*;
 
-            let is_count = matches!(mode, ProcWidgetMode::Grouped);
+            let is_count = matches!(
+                mode,
+                ProcWidgetMode::Grouped | ProcWidgetMode::UserGrouped { .. }
+            );
             let is_command = table_config.is_command;
             let mem_as_values = table_config.show_memory_as_values;
 
@@ -260,6 +428,20 @@ impl ProcWidgetState {
                             ProcWidgetColumn::User => User,
                             ProcWidgetColumn::State => State,
                             ProcWidgetColumn::Time => Time,
+                            #[cfg(target_os = "linux")]
+                            ProcWidgetColumn::Label => Label,
+                            #[cfg(target_os = "linux")]
+                            ProcWidgetColumn::PidNamespace => PidNamespace,
+                            #[cfg(target_os = "linux")]
+                            ProcWidgetColumn::NetNamespace => NetNamespace,
+                            #[cfg(target_os = "linux")]
+                            ProcWidgetColumn::MntNamespace => MntNamespace,
+                            #[cfg(target_os = "linux")]
+                            ProcWidgetColumn::CpuWait => CpuWaitPercent,
+                            #[cfg(target_os = "linux")]
+                            ProcWidgetColumn::IoWait => IoWaitPercent,
+                            #[cfg(target_os = "linux")]
+                            ProcWidgetColumn::IoPriority => IoPriority,
                             #[cfg(feature = "gpu")]
                             ProcWidgetColumn::GpuMem => {
                                 if mem_as_values {
@@ -270,6 +452,11 @@ impl ProcWidgetState {
                             }
                             #[cfg(feature = "gpu")]
                             ProcWidgetColumn::GpuUtil => GpuUtilPercent,
+                            #[cfg(feature = "plugins")]
+                            ProcWidgetColumn::Plugin { index, header } => Plugin {
+                                index: *index,
+                                header: *header,
+                            },
                         };
 
                         make_column(col)
@@ -312,26 +499,52 @@ impl ProcWidgetState {
                     State => ProcWidgetColumn::State,
                     User => ProcWidgetColumn::User,
                     Time => ProcWidgetColumn::Time,
+                    #[cfg(target_os = "linux")]
+                    Label => ProcWidgetColumn::Label,
+                    #[cfg(target_os = "linux")]
+                    PidNamespace => ProcWidgetColumn::PidNamespace,
+                    #[cfg(target_os = "linux")]
+                    NetNamespace => ProcWidgetColumn::NetNamespace,
+                    #[cfg(target_os = "linux")]
+                    MntNamespace => ProcWidgetColumn::MntNamespace,
+                    #[cfg(target_os = "linux")]
+                    CpuWaitPercent => ProcWidgetColumn::CpuWait,
+                    #[cfg(target_os = "linux")]
+                    IoWaitPercent => ProcWidgetColumn::IoWait,
+                    #[cfg(target_os = "linux")]
+                    IoPriority => ProcWidgetColumn::IoPriority,
                     #[cfg(feature = "gpu")]
                     GpuMemValue | GpuMemPercent => ProcWidgetColumn::GpuMem,
                     #[cfg(feature = "gpu")]
                     GpuUtilPercent => ProcWidgetColumn::GpuUtil,
+                    #[cfg(feature = "plugins")]
+                    Plugin { index, header } => ProcWidgetColumn::Plugin {
+                        index: *index,
+                        header: *header,
+                    },
                 }
             })
             .collect::<IndexSet<_>>();
 
-        let (default_sort_index, default_sort_order) =
-            if matches!(mode, ProcWidgetMode::Tree { .. }) {
-                if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::PidOrCount) {
-                    (index, columns[index].default_order)
-                } else {
-                    (0, columns[0].default_order)
-                }
-            } else if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::Cpu) {
+        let initial_sort = table_config.default_sort.and_then(|sort| {
+            column_mapping
+                .get_index_of(&sort.column)
+                .map(|index| (index, sort.order.unwrap_or(columns[index].default_order)))
+        });
+
+        let (default_sort_index, default_sort_order) = if let Some(initial_sort) = initial_sort {
+            initial_sort
+        } else if matches!(mode, ProcWidgetMode::Tree { .. }) {
+            if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::PidOrCount) {
                 (index, columns[index].default_order)
             } else {
                 (0, columns[0].default_order)
-            };
+            }
+        } else if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::Cpu) {
+            (index, columns[index].default_order)
+        } else {
+            (0, columns[0].default_order)
+        };
 
         let sort_table = Self::new_sort_table(config, colours);
         let table = Self::new_process_table(
@@ -349,6 +562,7 @@ impl ProcWidgetState {
             table,
             sort_table,
             id_pid_map,
+            user_pid_map: HashMap::default(),
             column_mapping,
             is_sort_open: false,
             mode,
@@ -356,6 +570,8 @@ impl ProcWidgetState {
             force_update_data: false,
             default_sort_index,
             default_sort_order,
+            sort_cache: SortKeyCache::default(),
+            show_tree_totals: table_config.show_tree_totals,
         };
         table.sort_table.set_data(table.column_text());
 
@@ -382,6 +598,40 @@ impl ProcWidgetState {
             .unwrap_or(false)
     }
 
+    /// Returns whether `column` is in this widget and not hidden.
+    #[cfg(target_os = "linux")]
+    fn is_showing(&self, column: ProcWidgetColumn) -> bool {
+        self.column_mapping
+            .get_index_of(&column)
+            .and_then(|index| self.table.columns.get(index))
+            .is_some_and(|col| !col.is_hidden)
+    }
+
+    /// Returns the optional process details this widget shows or searches by,
+    /// so only those are collected.
+    #[cfg(target_os = "linux")]
+    pub fn process_fields(&self) -> ProcessFields {
+        let shown = NamespaceKinds {
+            pid: self.is_showing(ProcWidgetColumn::PidNamespace),
+            net: self.is_showing(ProcWidgetColumn::NetNamespace),
+            mnt: self.is_showing(ProcWidgetColumn::MntNamespace),
+        };
+        let searched = self
+            .get_query()
+            .as_ref()
+            .map(ProcessQuery::namespace_kinds)
+            .unwrap_or_default();
+
+        ProcessFields {
+            label: self.is_showing(ProcWidgetColumn::Label),
+            namespaces: shown.union(searched),
+            delays: self.is_showing(ProcWidgetColumn::CpuWait)
+                || self.is_showing(ProcWidgetColumn::IoWait),
+            io_priority: self.is_showing(ProcWidgetColumn::IoPriority),
+            details_pid: None,
+        }
+    }
+
     fn get_query(&self) -> &Option<ProcessQuery> {
         if self.proc_search.search_state.is_invalid_or_blank_search() {
             &None
@@ -400,21 +650,58 @@ impl ProcWidgetState {
             ProcWidgetMode::Grouped | ProcWidgetMode::Normal => {
                 self.get_normal_data(&data_collection.process_data.process_harvest)
             }
-            ProcWidgetMode::Tree { collapsed_pids } => {
-                self.get_tree_data(collapsed_pids, data_collection)
+            ProcWidgetMode::Tree { collapsed } => {
+                let mut sort_cache = mem::take(&mut self.sort_cache);
+                let data = self.get_tree_data(collapsed, &mut sort_cache, data_collection);
+                self.sort_cache = sort_cache;
+                data
+            }
+            ProcWidgetMode::UserGrouped { collapsed } => {
+                let mut sort_cache = mem::take(&mut self.sort_cache);
+                let (data, user_pid_map) = self.get_user_grouped_data(
+                    collapsed,
+                    &mut sort_cache,
+                    &data_collection.process_data.process_harvest,
+                );
+                self.sort_cache = sort_cache;
+                self.user_pid_map = user_pid_map;
+                data
             }
         };
         self.table.set_data(data);
     }
 
+    /// The PIDs of the processes a row stands for.
+    pub fn row_pids(&self, row: &ProcWidgetData) -> Vec<Pid> {
+        match self.mode {
+            ProcWidgetMode::UserGrouped { .. } => {
+                let Some(names) = self.user_pid_map.get(&row.user) else {
+                    return vec![row.pid];
+                };
+
+                if row.id.is_user() {
+                    let mut pids = names.values().flatten().copied().collect_vec();
+                    pids.sort_unstable();
+                    pids
+                } else {
+                    names
+                        .get(row.id.as_str())
+                        .cloned()
+                        .unwrap_or_else(|| vec![row.pid])
+                }
+            }
+            _ => self
+                .id_pid_map
+                .get(row.id.as_str())
+                .cloned()
+                .unwrap_or_else(|| vec![row.pid]),
+        }
+    }
+
     fn get_tree_data(
-        &self, collapsed_pids: &HashSet<Pid>, data_collection: &DataCollection,
+        &self, collapsed: &CollapsedBranches, sort_cache: &mut SortKeyCache,
+        data_collection: &DataCollection,
     ) -> Vec<ProcWidgetData> {
-        const BRANCH_END: char = '└';
-        const BRANCH_SPLIT: char = '├';
-        const BRANCH_HORIZONTAL: char = '─';
-        const SPACED_BRANCH_VERTICAL: &str = "│  ";
-
         let search_query = self.get_query();
         let is_using_command = self.is_using_command();
         let is_mem_percent = self.is_mem_percent();
@@ -424,7 +711,7 @@ impl ProcWidgetState {
             process_parent_mapping,
             orphan_pids,
             ..
-        } = &data_collection.process_data;
+        } = data_collection.process_data.as_ref();
 
         // Only keep a set of the kept PIDs.
         let kept_pids = data_collection
@@ -538,25 +825,49 @@ impl ProcWidgetState {
             filtered_tree
         };
 
-        let mut data = vec![];
-        let mut prefixes = vec![];
-        let mut stack = orphan_pids
-            .iter()
-            .filter_map(|pid| {
-                if filtered_tree.contains_key(pid) {
-                    process_harvest.get(pid).map(|process| {
-                        ProcWidgetData::from_data(process, is_using_command, is_mem_percent)
-                    })
-                } else {
-                    None
+        // Collapsed processes (and parents, if showing totals) show the totals of
+        // their branch, so they're summed up before sorting to keep the order
+        // consistent with what's shown.
+        let to_row = |pid: &Pid| {
+            let process = process_harvest.get(pid)?;
+            let mut row = ProcWidgetData::from_data(process, is_using_command, is_mem_percent);
+            let children = filtered_tree
+                .get(pid)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+
+            let key = (row.pid, row.start_time);
+            if self.show_tree_totals || collapsed.is_collapsed(&key, !children.is_empty()) {
+                let mut sum_queue = children.to_vec();
+                while let Some(child) = sum_queue.pop() {
+                    if let Some(process) = process_harvest.get(&child) {
+                        row.add(&ProcWidgetData::from_data(
+                            process,
+                            is_using_command,
+                            is_mem_percent,
+                        ));
+                    }
+                    if let Some(pids) = filtered_tree.get(&child) {
+                        sum_queue.extend(pids);
+                    }
                 }
-            })
-            .collect_vec();
+            }
 
-        stack.sort_unstable_by_key(|p| p.pid);
+            Some(row)
+        };
 
         let column = self.table.columns.get(self.table.sort_index()).unwrap();
-        sort_skip_pid_asc(column.inner(), &mut stack, self.table.order());
+        let order = self.table.order();
+
+        let mut data = vec![];
+        // Whether each ancestor of the current process has more siblings after it.
+        let mut continuing = 0;
+        let mut stack = orphan_pids
+            .iter()
+            .filter(|pid| filtered_tree.contains_key(*pid))
+            .filter_map(to_row)
+            .collect_vec();
+        sort_cache.sort_siblings(column.inner(), &mut stack, order);
 
         let mut length_stack = vec![stack.len()];
         stack.reverse();
@@ -564,101 +875,128 @@ impl ProcWidgetState {
         while let (Some(process), Some(siblings_left)) = (stack.pop(), length_stack.last_mut()) {
             *siblings_left -= 1;
 
-            let disabled = !kept_pids.contains(&process.pid);
+            let pid = process.pid;
+            let disabled = !kept_pids.contains(&pid);
             let is_last = *siblings_left == 0;
+            let depth = u16::try_from(length_stack.len() - 1).unwrap_or(u16::MAX);
 
-            if collapsed_pids.contains(&process.pid) {
-                let mut summed_process = process.clone();
+            let children_pids = filtered_tree.get(&pid);
+            let has_children = children_pids.is_some_and(|children| !children.is_empty());
+            let is_collapsed =
+                collapsed.is_collapsed(&(process.pid, process.start_time), has_children);
 
-                if let Some(children_pids) = filtered_tree.get(&process.pid) {
-                    let mut sum_queue = children_pids
-                        .iter()
-                        .filter_map(|child| {
-                            process_harvest.get(child).map(|p| {
-                                ProcWidgetData::from_data(p, is_using_command, is_mem_percent)
-                            })
-                        })
-                        .collect_vec();
-
-                    while let Some(process) = sum_queue.pop() {
-                        summed_process.add(&process);
-
-                        if let Some(pids) = filtered_tree.get(&process.pid) {
-                            sum_queue.extend(pids.iter().filter_map(|child| {
-                                process_harvest.get(child).map(|p| {
-                                    ProcWidgetData::from_data(p, is_using_command, is_mem_percent)
-                                })
-                            }));
-                        }
-                    }
-                }
+            let prefix = TreePrefix::new(depth, continuing, is_last, is_collapsed);
+            data.push(process.prefix(Some(prefix)).disabled(disabled));
 
-                let prefix = if prefixes.is_empty() {
-                    "+ ".to_string()
-                } else {
-                    format!(
-                        "{}{}{} + ",
-                        prefixes.join(""),
-                        if is_last { BRANCH_END } else { BRANCH_SPLIT },
-                        BRANCH_HORIZONTAL
-                    )
-                };
+            if let Some(children_pids) = children_pids.filter(|_| !is_collapsed) {
+                continuing = TreePrefix::set_continuing(continuing, depth, !is_last);
 
-                data.push(summed_process.prefix(Some(prefix)).disabled(disabled));
-            } else {
-                let prefix = if prefixes.is_empty() {
-                    String::default()
-                } else {
-                    format!(
-                        "{}{}{} ",
-                        prefixes.join(""),
-                        if is_last { BRANCH_END } else { BRANCH_SPLIT },
-                        BRANCH_HORIZONTAL
-                    )
-                };
-                let pid = process.pid;
-                data.push(process.prefix(Some(prefix)).disabled(disabled));
-
-                if let Some(children_pids) = filtered_tree.get(&pid) {
-                    if prefixes.is_empty() {
-                        prefixes.push("");
-                    } else {
-                        prefixes.push(if is_last {
-                            "   "
-                        } else {
-                            SPACED_BRANCH_VERTICAL
-                        });
-                    }
+                let mut children = children_pids.iter().filter_map(to_row).collect_vec();
+                sort_cache.sort_siblings(column.inner(), &mut children, order);
 
-                    let mut children = children_pids
-                        .iter()
-                        .filter_map(|child_pid| {
-                            process_harvest.get(child_pid).map(|p| {
-                                ProcWidgetData::from_data(p, is_using_command, is_mem_percent)
-                            })
-                        })
-                        .collect_vec();
-
-                    column.sort_by(&mut children, self.table.order().rev());
-
-                    length_stack.push(children.len());
-                    stack.extend(children);
-                }
+                length_stack.push(children.len());
+                stack.extend(children.into_iter().rev());
             }
 
             while let Some(children_left) = length_stack.last() {
                 if *children_left == 0 {
                     length_stack.pop();
-                    prefixes.pop();
                 } else {
                     break;
                 }
             }
         }
 
+        sort_cache.prune();
         data
     }
 
+    /// Groups processes by user, with a header for each user showing the
+    /// totals of their processes, followed by their processes grouped by name.
+    fn get_user_grouped_data(
+        &self, collapsed: &CollapsedBranches<Arc<str>>, sort_cache: &mut SortKeyCache,
+        process_harvest: &BTreeMap<Pid, ProcessHarvest>,
+    ) -> (Vec<ProcWidgetData>, UserPidMap) {
+        let search_query = self.get_query();
+        let is_using_command = self.is_using_command();
+        let is_mem_percent = self.is_mem_percent();
+
+        let filtered_iter = process_harvest.values().filter(|process| {
+            search_query
+                .as_ref()
+                .map(|query| query.check(process, is_using_command))
+                .unwrap_or(true)
+        });
+
+        let mut user_pid_map: UserPidMap = HashMap::default();
+        let mut user_groups: HashMap<Arc<str>, HashMap<Arc<str>, ProcWidgetData>> =
+            HashMap::default();
+        for process in filtered_iter {
+            let id = if is_using_command {
+                &process.command
+            } else {
+                &process.name
+            };
+
+            user_pid_map
+                .entry(process.user.clone())
+                .or_default()
+                .entry(id.clone())
+                .or_default()
+                .push(process.pid);
+
+            // Processes are visited in PID order, so each group keeps its lowest PID.
+            let row = ProcWidgetData::from_data(process, is_using_command, is_mem_percent);
+            let groups = user_groups.entry(process.user.clone()).or_default();
+            if let Some(grouped) = groups.get_mut(id) {
+                grouped.add(&row);
+                grouped.num_similar += 1;
+            } else {
+                groups.insert(id.clone(), row);
+            }
+        }
+
+        let column = self.table.columns.get(self.table.sort_index()).unwrap();
+        let order = self.table.order();
+
+        let mut headers = vec![];
+        let mut user_rows: HashMap<Arc<str>, Vec<ProcWidgetData>> = HashMap::default();
+        for (user, groups) in user_groups {
+            let mut groups = groups.into_values().collect_vec();
+            groups.sort_unstable_by_key(|row| row.pid);
+            let Some((first, rest)) = groups.split_first() else {
+                continue;
+            };
+
+            let mut header = first.clone();
+            for group in rest {
+                header.add(group);
+                header.num_similar += group.num_similar;
+            }
+            headers.push(header.user_header());
+            user_rows.insert(user, groups);
+        }
+        sort_cache.sort_siblings(column.inner(), &mut headers, order);
+
+        let mut data = vec![];
+        for header in headers {
+            let is_collapsed = collapsed.is_collapsed(&header.user, true);
+            let groups = user_rows.remove(&header.user).filter(|_| !is_collapsed);
+            data.push(header.prefix(Some(TreePrefix::new(0, 0, false, is_collapsed))));
+
+            if let Some(mut groups) = groups {
+                sort_cache.sort_siblings(column.inner(), &mut groups, order);
+                let last = groups.len() - 1;
+                data.extend(groups.into_iter().enumerate().map(|(index, group)| {
+                    group.prefix(Some(TreePrefix::new(1, 0, index == last, false)))
+                }));
+            }
+        }
+
+        sort_cache.prune();
+        (data, user_pid_map)
+    }
+
     fn get_normal_data(
         &mut self, process_harvest: &BTreeMap<Pid, ProcessHarvest>,
     ) -> Vec<ProcWidgetData> {
@@ -673,9 +1011,9 @@ impl ProcWidgetState {
                 .unwrap_or(true)
         });
 
-        let mut id_pid_map: HashMap<String, Vec<Pid>> = HashMap::default();
+        let mut id_pid_map: StringPidMap = HashMap::default();
         let mut filtered_data: Vec<ProcWidgetData> = if let ProcWidgetMode::Grouped = self.mode {
-            let mut id_process_mapping: HashMap<&String, ProcessHarvest> = HashMap::default();
+            let mut id_process_mapping: HashMap<&Arc<str>, ProcessHarvest> = HashMap::default();
             for process in filtered_iter {
                 let id = if is_using_command {
                     &process.command
@@ -723,7 +1061,8 @@ impl ProcWidgetState {
         self.id_pid_map = id_pid_map;
 
         if let Some(column) = self.table.columns.get(self.table.sort_index()) {
-            sort_skip_pid_asc(column.inner(), &mut filtered_data, self.table.order());
+            self.sort_cache
+                .sort(column.inner(), &mut filtered_data, self.table.order());
         }
 
         filtered_data
@@ -783,6 +1122,18 @@ impl ProcWidgetState {
         self.force_update_data = true;
     }
 
+    /// Adds a column for a plugin, after all other columns.
+    #[cfg(feature = "plugins")]
+    pub fn add_plugin_column(&mut self, index: usize, header: &'static str) {
+        self.table
+            .columns
+            .push(make_column(ProcColumn::Plugin { index, header }));
+        self.column_mapping
+            .insert(ProcWidgetColumn::Plugin { index, header });
+        self.sort_table.set_data(self.column_text());
+        self.force_rerender_and_update();
+    }
+
     /// Marks the selected column as hidden, and automatically resets the
     /// selected column to the default sort index and order.
     fn hide_column(&mut self, column: ProcWidgetColumn) {
@@ -817,18 +1168,111 @@ impl ProcWidgetState {
     }
 
     pub fn toggle_current_tree_branch_entry(&mut self) {
-        if let ProcWidgetMode::Tree { collapsed_pids } = &mut self.mode {
+        match &mut self.mode {
+            ProcWidgetMode::Tree { collapsed } => {
+                if let Some(process) = self.table.current_item() {
+                    collapsed.toggle((process.pid, process.start_time));
+                    self.force_data_update();
+                }
+            }
+            ProcWidgetMode::UserGrouped { collapsed } => {
+                if let Some(process) = self.table.current_item() {
+                    collapsed.toggle(process.user.clone());
+                    self.select_current_user_header();
+                    self.force_data_update();
+                }
+            }
+            ProcWidgetMode::Grouped | ProcWidgetMode::Normal => {}
+        }
+    }
+
+    /// Selects the header of the selected process's user, when grouping by
+    /// user.
+    fn select_current_user_header(&mut self) {
+        let Some(process) = self.table.current_item() else {
+            return;
+        };
+
+        // Headers are always shown above their processes, and collapsing only
+        // hides rows below the header, so its index stays the same.
+        let current_index = self.table.current_index();
+        if let Some(index) = self.table.data()[..=current_index]
+            .iter()
+            .rposition(|row| row.id.is_user() && row.user == process.user)
+        {
+            self.table.set_position(index);
+        }
+    }
+
+    /// Collapses the branch the selected process is in, selecting its parent.
+    /// If the selected process has no parent shown, its own branch is
+    /// collapsed instead. When grouping by user, the selected process's user
+    /// is collapsed.
+    pub fn collapse_current_tree_branch(&mut self) {
+        if let ProcWidgetMode::UserGrouped { collapsed } = &mut self.mode {
             if let Some(process) = self.table.current_item() {
-                let pid = process.pid;
+                collapsed.collapse(process.user.clone());
+                self.select_current_user_header();
+                self.force_data_update();
+            }
+        } else if let ProcWidgetMode::Tree { collapsed } = &mut self.mode {
+            let Some(process) = self.table.current_item() else {
+                return;
+            };
 
-                if !collapsed_pids.remove(&pid) {
-                    collapsed_pids.insert(pid);
+            // Parents are always shown above their children, and collapsing
+            // only hides rows below the parent, so its index stays the same.
+            let current_index = self.table.current_index();
+            let parent_index = process.ppid.and_then(|ppid| {
+                self.table.data()[..current_index]
+                    .iter()
+                    .rposition(|row| row.pid == ppid)
+            });
+
+            match parent_index {
+                Some(index) => {
+                    let parent = &self.table.data()[index];
+                    collapsed.collapse((parent.pid, parent.start_time));
+                    self.table.set_position(index);
                 }
-                self.force_data_update();
+                None => collapsed.collapse((process.pid, process.start_time)),
             }
+            self.force_data_update();
         }
     }
 
+    /// Toggles whether parents in tree mode show the totals of their branch.
+    pub fn toggle_tree_totals(&mut self) {
+        self.show_tree_totals = !self.show_tree_totals;
+        if let ProcWidgetMode::Tree { .. } = self.mode {
+            self.force_data_update();
+        }
+    }
+
+    /// Collapses or expands every branch in tree mode, or every user's
+    /// processes when grouping by user.
+    pub fn set_all_tree_branches_collapsed(&mut self, is_collapsed: bool) {
+        match &mut self.mode {
+            ProcWidgetMode::Tree { collapsed } => collapsed.set_all(is_collapsed),
+            ProcWidgetMode::UserGrouped { collapsed } => collapsed.set_all(is_collapsed),
+            ProcWidgetMode::Grouped | ProcWidgetMode::Normal => return,
+        }
+
+        // The selected process may no longer be shown after collapsing.
+        if is_collapsed {
+            self.table.scroll_to_first();
+        }
+        self.force_data_update();
+    }
+
+    /// Whether the name column is currently showing the full command.
+    pub fn is_showing_command(&self) -> bool {
+        self.column_mapping
+            .get_index_of(&ProcWidgetColumn::ProcNameOrCommand)
+            .and_then(|index| self.table.columns.get(index))
+            .is_some_and(|col| matches!(col.inner(), ProcColumn::Command))
+    }
+
     pub fn toggle_command(&mut self) {
         if let Some(index) = self
             .column_mapping
@@ -847,7 +1291,8 @@ impl ProcWidgetState {
                         *inner = ProcColumn::Name;
                         if let ColumnWidthBounds::Soft { max_percentage, .. } = col.bounds_mut() {
                             *max_percentage = match self.mode {
-                                ProcWidgetMode::Tree { .. } => Some(0.5),
+                                ProcWidgetMode::Tree { .. }
+                                | ProcWidgetMode::UserGrouped { .. } => Some(0.5),
                                 ProcWidgetMode::Grouped | ProcWidgetMode::Normal => Some(0.3),
                             };
                         }
@@ -872,7 +1317,7 @@ impl ProcWidgetState {
     /// State columns should be re-enabled, and the mode switched to
     /// [`ProcWidgetMode::Normal`].
     pub fn toggle_tab(&mut self) {
-        if !matches!(self.mode, ProcWidgetMode::Tree { .. }) {
+        if matches!(self.mode, ProcWidgetMode::Grouped | ProcWidgetMode::Normal) {
             if let Some(index) = self
                 .column_mapping
                 .get_index_of(&ProcWidgetColumn::PidOrCount)
@@ -906,6 +1351,53 @@ impl ProcWidgetState {
         }
     }
 
+    /// Toggles grouping processes by user, then by name under each user. This
+    /// does nothing in tree mode.
+    ///
+    /// Like [`ProcWidgetMode::Grouped`], the PID column is switched to a count
+    /// and the State column is hidden, but the User column stays shown.
+    pub fn toggle_user_grouping(&mut self) {
+        let Some(index) = self
+            .column_mapping
+            .get_index_of(&ProcWidgetColumn::PidOrCount)
+        else {
+            return;
+        };
+        let Some(sort_col) = self.table.columns.get_mut(index) else {
+            return;
+        };
+
+        match self.mode {
+            ProcWidgetMode::Normal => {
+                *sort_col.inner_mut() = ProcColumn::Count;
+                sort_col.default_order = SortOrder::Descending;
+
+                self.hide_column(ProcWidgetColumn::State);
+                self.mode = ProcWidgetMode::UserGrouped {
+                    collapsed: Default::default(),
+                };
+            }
+            ProcWidgetMode::Grouped => {
+                self.show_column(ProcWidgetColumn::User);
+                self.mode = ProcWidgetMode::UserGrouped {
+                    collapsed: Default::default(),
+                };
+            }
+            ProcWidgetMode::UserGrouped { .. } => {
+                *sort_col.inner_mut() = ProcColumn::Pid;
+                sort_col.default_order = SortOrder::Ascending;
+
+                self.show_column(ProcWidgetColumn::State);
+                self.mode = ProcWidgetMode::Normal;
+                self.user_pid_map.clear();
+            }
+            ProcWidgetMode::Tree { .. } => return,
+        }
+
+        self.sort_table.set_data(self.column_text());
+        self.force_rerender_and_update();
+    }
+
     pub fn column_text(&self) -> Vec<Cow<'static, str>> {
         self.table
             .columns
@@ -966,6 +1458,14 @@ impl ProcWidgetState {
         self.force_data_update();
     }
 
+    /// Replaces the search query, moving the cursor to its end.
+    pub fn set_search_query(&mut self, query: String) {
+        let len = query.len();
+        self.proc_search.search_state.current_search_query = query;
+        self.proc_search.search_state.grapheme_cursor = GraphemeCursor::new(len, len, true);
+        self.update_query();
+    }
+
     pub fn clear_search(&mut self) {
         self.proc_search.search_state.reset();
         self.force_data_update();
@@ -991,6 +1491,7 @@ impl ProcWidgetState {
     #[cfg(test)]
     pub(crate) fn test_equality(&self, other: &Self) -> bool {
         self.mode == other.mode
+            && self.show_tree_totals == other.show_tree_totals
             && self.proc_search.is_ignoring_case == other.proc_search.is_ignoring_case
             && self.proc_search.is_searching_whole_word == other.proc_search.is_searching_whole_word
             && self.proc_search.is_searching_with_regex == other.proc_search.is_searching_with_regex
@@ -1009,17 +1510,6 @@ impl ProcWidgetState {
     }
 }
 
-#[inline]
-fn sort_skip_pid_asc(column: &ProcColumn, data: &mut [ProcWidgetData], order: SortOrder) {
-    let descending = matches!(order, SortOrder::Descending);
-    match column {
-        ProcColumn::Pid if !descending => {}
-        _ => {
-            column.sort_data(data, descending);
-        }
-    }
-}
-
 #[cfg(test)]
 mod test {
     use std::time::Duration;
@@ -1032,6 +1522,7 @@ mod test {
         let a = ProcWidgetData {
             pid: 1,
             ppid: None,
+            start_time: 0,
             id: "A".into(),
             cpu_usage_percent: 0.0,
             mem_usage: MemUsage::Percent(1.1),
@@ -1039,12 +1530,22 @@ mod test {
             wps: 0,
             total_read: 0,
             total_write: 0,
-            process_state: "N/A".to_string(),
+            process_state: "N/A".into(),
             process_char: '?',
             #[cfg(target_family = "unix")]
-            user: "root".to_string(),
+            user: "root".into(),
             #[cfg(not(target_family = "unix"))]
-            user: "N/A".to_string(),
+            user: "N/A".into(),
+            #[cfg(target_os = "linux")]
+            label: None,
+            #[cfg(target_os = "linux")]
+            namespaces: Default::default(),
+            #[cfg(target_os = "linux")]
+            cpu_wait_percent: 0.0,
+            #[cfg(target_os = "linux")]
+            io_wait_percent: 0.0,
+            #[cfg(target_os = "linux")]
+            io_priority: None,
             num_similar: 0,
             disabled: false,
             time: Duration::from_secs(0),
@@ -1081,37 +1582,187 @@ mod test {
             ..(a.clone())
         };
         let mut data = vec![d.clone(), b.clone(), c.clone(), a.clone()];
+        let mut sort_cache = SortKeyCache::default();
 
-        // Assume we had sorted over by pid.
-        data.sort_by_key(|p| p.pid);
-        sort_skip_pid_asc(&ProcColumn::CpuPercent, &mut data, SortOrder::Descending);
+        sort_cache.sort_siblings(&ProcColumn::CpuPercent, &mut data, SortOrder::Descending);
         assert_eq!(
-            [&c, &b, &a, &d].iter().map(|d| (d.pid)).collect::<Vec<_>>(),
-            data.iter().map(|d| (d.pid)).collect::<Vec<_>>(),
+            [&c, &b, &a, &d].iter().map(|d| d.pid).collect::<Vec<_>>(),
+            data.iter().map(|d| d.pid).collect::<Vec<_>>(),
         );
 
         // Note that the PID ordering for ties is still ascending.
-        data.sort_by_key(|p| p.pid);
-        sort_skip_pid_asc(&ProcColumn::CpuPercent, &mut data, SortOrder::Ascending);
+        sort_cache.sort_siblings(&ProcColumn::CpuPercent, &mut data, SortOrder::Ascending);
         assert_eq!(
-            [&a, &d, &b, &c].iter().map(|d| (d.pid)).collect::<Vec<_>>(),
-            data.iter().map(|d| (d.pid)).collect::<Vec<_>>(),
+            [&a, &d, &b, &c].iter().map(|d| d.pid).collect::<Vec<_>>(),
+            data.iter().map(|d| d.pid).collect::<Vec<_>>(),
         );
 
-        data.sort_by_key(|p| p.pid);
-        sort_skip_pid_asc(&ProcColumn::MemPercent, &mut data, SortOrder::Descending);
+        sort_cache.sort_siblings(&ProcColumn::MemPercent, &mut data, SortOrder::Descending);
         assert_eq!(
-            [&b, &a, &c, &d].iter().map(|d| (d.pid)).collect::<Vec<_>>(),
-            data.iter().map(|d| (d.pid)).collect::<Vec<_>>(),
+            [&b, &a, &c, &d].iter().map(|d| d.pid).collect::<Vec<_>>(),
+            data.iter().map(|d| d.pid).collect::<Vec<_>>(),
         );
 
         // Note that the PID ordering for ties is still ascending.
-        data.sort_by_key(|p| p.pid);
-        sort_skip_pid_asc(&ProcColumn::MemPercent, &mut data, SortOrder::Ascending);
+        sort_cache.sort_siblings(&ProcColumn::MemPercent, &mut data, SortOrder::Ascending);
+        assert_eq!(
+            [&c, &d, &a, &b].iter().map(|d| d.pid).collect::<Vec<_>>(),
+            data.iter().map(|d| d.pid).collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn tree_totals() {
+        let process = |pid, parent_pid, cpu_usage_percent| ProcessHarvest {
+            pid,
+            parent_pid,
+            cpu_usage_percent,
+            ..Default::default()
+        };
+
+        let process_data = ProcessData {
+            process_harvest: [
+                process(1, None, 1.0),
+                process(2, Some(1), 2.0),
+                process(3, Some(2), 4.0),
+            ]
+            .into_iter()
+            .map(|process| (process.pid, process))
+            .collect(),
+            process_parent_mapping: [(1, vec![2]), (2, vec![3])].into_iter().collect(),
+            orphan_pids: vec![1],
+        };
+        let data = DataCollection {
+            process_data: Arc::new(process_data),
+            ..Default::default()
+        };
+
+        let mut state = init_default_state(&[ProcWidgetColumn::PidOrCount, ProcWidgetColumn::Cpu]);
+        state.mode = ProcWidgetMode::Tree {
+            collapsed: Default::default(),
+        };
+        let cpu = |state: &ProcWidgetState| {
+            state
+                .table
+                .data()
+                .iter()
+                .map(|row| row.cpu_usage_percent)
+                .collect::<Vec<_>>()
+        };
+
+        state.set_table_data(&data);
+        assert_eq!(cpu(&state), [1.0, 2.0, 4.0]);
+
+        state.toggle_tree_totals();
+        state.set_table_data(&data);
+        assert_eq!(cpu(&state), [7.0, 6.0, 4.0]);
+
+        // Collapsed processes show their totals either way.
+        state.toggle_tree_totals();
+        state.set_all_tree_branches_collapsed(true);
+        state.set_table_data(&data);
+        assert_eq!(cpu(&state), [7.0]);
+    }
+
+    #[test]
+    fn user_grouping() {
+        let process = |pid, name: &str, user: &str, cpu_usage_percent| ProcessHarvest {
+            pid,
+            name: name.into(),
+            user: user.into(),
+            cpu_usage_percent,
+            ..Default::default()
+        };
+
+        let mut data = DataCollection::default();
+        Arc::make_mut(&mut data.process_data).process_harvest = [
+            process(1, "a", "root", 1.0),
+            process(2, "b", "root", 2.0),
+            process(3, "b", "root", 3.0),
+            process(4, "a", "user", 8.0),
+        ]
+        .into_iter()
+        .map(|process| (process.pid, process))
+        .collect();
+
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+        ]);
+        let rows = |state: &ProcWidgetState| {
+            state
+                .table
+                .data()
+                .iter()
+                .map(|row| (row.id.to_string(), row.num_similar, row.cpu_usage_percent))
+                .collect::<Vec<_>>()
+        };
+
+        state.toggle_user_grouping();
+        state.set_table_data(&data);
+        assert_eq!(
+            rows(&state),
+            [
+                ("user".to_string(), 1, 8.0),
+                ("a".to_string(), 1, 8.0),
+                ("root".to_string(), 3, 6.0),
+                ("b".to_string(), 2, 5.0),
+                ("a".to_string(), 1, 1.0),
+            ]
+        );
+        assert_eq!(state.row_pids(&state.table.data()[2]), [1, 2, 3]);
+        assert_eq!(state.row_pids(&state.table.data()[3]), [2, 3]);
+
+        // Collapsing from a process selects its user's header.
+        state.table.set_position(3);
+        state.collapse_current_tree_branch();
+        state.set_table_data(&data);
+        assert_eq!(state.table.current_index(), 2);
         assert_eq!(
-            [&c, &d, &a, &b].iter().map(|d| (d.pid)).collect::<Vec<_>>(),
-            data.iter().map(|d| (d.pid)).collect::<Vec<_>>(),
+            rows(&state),
+            [
+                ("user".to_string(), 1, 8.0),
+                ("a".to_string(), 1, 8.0),
+                ("root".to_string(), 3, 6.0),
+            ]
         );
+
+        state.toggle_user_grouping();
+        state.set_table_data(&data);
+        assert_eq!(state.mode, ProcWidgetMode::Normal);
+        assert_eq!(state.table.data().len(), 4);
+    }
+
+    #[test]
+    fn collapsed_branches() {
+        let mut collapsed = CollapsedBranches::default();
+        let (a, b) = ((1, 10), (2, 20));
+        assert!(!collapsed.is_collapsed(&a, true));
+
+        collapsed.toggle(a);
+        assert!(collapsed.is_collapsed(&a, true));
+        assert!(collapsed.is_collapsed(&a, false));
+
+        // A new process reusing the PID isn't collapsed.
+        assert!(!collapsed.is_collapsed(&(1, 30), true));
+
+        // Collapsing everything only marks processes with children, and
+        // toggling then expands a branch.
+        collapsed.set_all(true);
+        assert!(collapsed.is_collapsed(&a, true));
+        assert!(!collapsed.is_collapsed(&b, false));
+        collapsed.toggle(a);
+        assert!(!collapsed.is_collapsed(&a, true));
+
+        collapsed.collapse(a);
+        collapsed.collapse(a);
+        assert!(collapsed.is_collapsed(&a, true));
+
+        collapsed.set_all(false);
+        assert!(!collapsed.is_collapsed(&a, true));
+        collapsed.collapse(b);
+        assert!(collapsed.is_collapsed(&b, true));
     }
 
     fn get_columns(table: &ProcessTable) -> Vec<ProcColumn> {
@@ -1494,4 +2145,72 @@ mod test {
         state.toggle_command();
         assert_eq!(get_columns(&state.table), original_columns);
     }
+
+    #[test]
+    fn parse_default_sorts() {
+        assert_eq!(
+            "mem:desc".parse(),
+            Ok(ProcDefaultSort {
+                column: ProcWidgetColumn::Mem,
+                order: Some(SortOrder::Descending),
+            })
+        );
+        assert_eq!(
+            "PID:Ascending".parse(),
+            Ok(ProcDefaultSort {
+                column: ProcWidgetColumn::PidOrCount,
+                order: Some(SortOrder::Ascending),
+            })
+        );
+        assert_eq!(
+            "cpu%".parse(),
+            Ok(ProcDefaultSort {
+                column: ProcWidgetColumn::Cpu,
+                order: None,
+            })
+        );
+
+        assert!("memory:desc".parse::<ProcDefaultSort>().is_err());
+        assert!("mem:up".parse::<ProcDefaultSort>().is_err());
+    }
+
+    #[test]
+    fn default_sort() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::Mem,
+        ];
+
+        // Without a default sort, the CPU column is sorted by.
+        let state = init_default_state(&init_columns);
+        assert_eq!(state.table.sort_index(), 2);
+        assert_eq!(state.table.order(), SortOrder::Descending);
+
+        let table_config = ProcTableConfig {
+            default_sort: Some("mem:asc".parse().unwrap()),
+            ..Default::default()
+        };
+        let state = init_state(table_config, &init_columns);
+        assert_eq!(state.table.sort_index(), 3);
+        assert_eq!(state.table.order(), SortOrder::Ascending);
+
+        // The column's usual order is used if none is given.
+        let table_config = ProcTableConfig {
+            default_sort: Some("name".parse().unwrap()),
+            ..Default::default()
+        };
+        let state = init_state(table_config, &init_columns);
+        assert_eq!(state.table.sort_index(), 1);
+        assert_eq!(state.table.order(), SortOrder::Ascending);
+
+        // Columns that aren't shown are ignored.
+        let table_config = ProcTableConfig {
+            default_sort: Some("user:asc".parse().unwrap()),
+            ..Default::default()
+        };
+        let state = init_state(table_config, &init_columns);
+        assert_eq!(state.table.sort_index(), 2);
+    }
 }
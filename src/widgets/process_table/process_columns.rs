@@ -1,8 +1,10 @@
-use std::{borrow::Cow, cmp::Reverse};
+use std::{borrow::Cow, cmp::Ordering};
 
 use serde::Deserialize;
 
 use super::{ProcWidgetColumn, ProcWidgetData};
+#[cfg(target_os = "linux")]
+use crate::data_collection::processes::linux::io_priority::IoPriority;
 use crate::{
     canvas::components::data_table::{ColumnHeader, SortsRow},
     utils::general::sort_partial_fn,
@@ -29,12 +31,33 @@ pub enum ProcColumn {
     State,
     User,
     Time,
+    #[cfg(target_os = "linux")]
+    Label,
+    #[cfg(target_os = "linux")]
+    PidNamespace,
+    #[cfg(target_os = "linux")]
+    NetNamespace,
+    #[cfg(target_os = "linux")]
+    MntNamespace,
+    #[cfg(target_os = "linux")]
+    CpuWaitPercent,
+    #[cfg(target_os = "linux")]
+    IoWaitPercent,
+    #[cfg(target_os = "linux")]
+    IoPriority,
     #[cfg(feature = "gpu")]
     GpuMemValue,
     #[cfg(feature = "gpu")]
     GpuMemPercent,
     #[cfg(feature = "gpu")]
     GpuUtilPercent,
+    /// A column provided by a plugin. The header lives for the rest of the
+    /// program, as it's set once on startup.
+    #[cfg(feature = "plugins")]
+    Plugin {
+        index: usize,
+        header: &'static str,
+    },
 }
 
 impl ProcColumn {
@@ -56,11 +79,27 @@ impl ProcColumn {
             ProcColumn::State => &["State"],
             ProcColumn::User => &["User"],
             ProcColumn::Time => &["Time"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::Label => &["Label"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::PidNamespace => &["PidNS"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::NetNamespace => &["NetNS"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::MntNamespace => &["MntNS"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::CpuWaitPercent => &["CPUWait%"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::IoWaitPercent => &["IOWait%"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::IoPriority => &["IOPrio"],
             #[cfg(feature = "gpu")]
             // TODO: Change this
             ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => &["GMem", "GMem%"],
             #[cfg(feature = "gpu")]
             ProcColumn::GpuUtilPercent => &["GPU%"],
+            #[cfg(feature = "plugins")]
+            ProcColumn::Plugin { .. } => &[],
         }
     }
 }
@@ -82,12 +121,28 @@ impl ColumnHeader for ProcColumn {
             ProcColumn::State => "State",
             ProcColumn::User => "User",
             ProcColumn::Time => "Time",
+            #[cfg(target_os = "linux")]
+            ProcColumn::Label => "Label",
+            #[cfg(target_os = "linux")]
+            ProcColumn::PidNamespace => "PidNS",
+            #[cfg(target_os = "linux")]
+            ProcColumn::NetNamespace => "NetNS",
+            #[cfg(target_os = "linux")]
+            ProcColumn::MntNamespace => "MntNS",
+            #[cfg(target_os = "linux")]
+            ProcColumn::CpuWaitPercent => "CPUWait%",
+            #[cfg(target_os = "linux")]
+            ProcColumn::IoWaitPercent => "IOWait%",
+            #[cfg(target_os = "linux")]
+            ProcColumn::IoPriority => "IOPrio",
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemValue => "GMem",
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemPercent => "GMem%",
             #[cfg(feature = "gpu")]
             ProcColumn::GpuUtilPercent => "GPU%",
+            #[cfg(feature = "plugins")]
+            ProcColumn::Plugin { header, .. } => *header,
         }
         .into()
     }
@@ -125,11 +180,7 @@ impl SortsRow for ProcColumn {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.num_similar, b.num_similar));
             }
             ProcColumn::Name | ProcColumn::Command => {
-                if descending {
-                    data.sort_by_cached_key(|pd| Reverse(pd.id.to_lowercase()));
-                } else {
-                    data.sort_by_cached_key(|pd| pd.id.to_lowercase());
-                }
+                data.sort_by(|a, b| cmp_ignore_case(a.id.as_str(), b.id.as_str(), descending));
             }
             ProcColumn::ReadPerSecond => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.rps, b.rps));
@@ -144,22 +195,65 @@ impl SortsRow for ProcColumn {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.total_write, b.total_write));
             }
             ProcColumn::State => {
-                if descending {
-                    data.sort_by_cached_key(|pd| Reverse(pd.process_state.to_lowercase()));
-                } else {
-                    data.sort_by_cached_key(|pd| pd.process_state.to_lowercase());
-                }
+                data.sort_by(|a, b| {
+                    cmp_ignore_case(&a.process_state, &b.process_state, descending)
+                });
             }
             ProcColumn::User => {
-                if descending {
-                    data.sort_by_cached_key(|pd| Reverse(pd.user.to_lowercase()));
-                } else {
-                    data.sort_by_cached_key(|pd| pd.user.to_lowercase());
-                }
+                data.sort_by(|a, b| cmp_ignore_case(&a.user, &b.user, descending));
             }
             ProcColumn::Time => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.time, b.time));
             }
+            #[cfg(target_os = "linux")]
+            ProcColumn::Label => {
+                data.sort_by(|a, b| {
+                    cmp_ignore_case(
+                        a.label.as_deref().unwrap_or_default(),
+                        b.label.as_deref().unwrap_or_default(),
+                        descending,
+                    )
+                });
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::PidNamespace => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.namespaces.pid, b.namespaces.pid)
+                });
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::NetNamespace => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.namespaces.net, b.namespaces.net)
+                });
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::MntNamespace => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.namespaces.mnt, b.namespaces.mnt)
+                });
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::CpuWaitPercent => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.cpu_wait_percent, b.cpu_wait_percent)
+                });
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::IoWaitPercent => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.io_wait_percent, b.io_wait_percent)
+                });
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::IoPriority => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(
+                        a.io_priority.map(IoPriority::rank),
+                        b.io_priority.map(IoPriority::rank),
+                    )
+                });
+            }
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => {
                 data.sort_by(|a, b| {
@@ -170,6 +264,17 @@ impl SortsRow for ProcColumn {
             ProcColumn::GpuUtilPercent => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.gpu_usage, b.gpu_usage));
             }
+            #[cfg(feature = "plugins")]
+            ProcColumn::Plugin { index, .. } => {
+                // Plugin values can be anything, so sort numerically if possible.
+                data.sort_by(|a, b| {
+                    let (a, b) = (a.plugin_value(*index), b.plugin_value(*index));
+                    match (a.parse::<f64>(), b.parse::<f64>()) {
+                        (Ok(a), Ok(b)) => sort_partial_fn(descending)(a, b),
+                        _ => sort_partial_fn(descending)(a, b),
+                    }
+                });
+            }
         }
     }
 }
@@ -195,6 +300,20 @@ impl<'de> Deserialize<'de> for ProcColumn {
             "state" => Ok(ProcColumn::State),
             "user" => Ok(ProcColumn::User),
             "time" => Ok(ProcColumn::Time),
+            #[cfg(target_os = "linux")]
+            "label" => Ok(ProcColumn::Label),
+            #[cfg(target_os = "linux")]
+            "pidns" => Ok(ProcColumn::PidNamespace),
+            #[cfg(target_os = "linux")]
+            "netns" => Ok(ProcColumn::NetNamespace),
+            #[cfg(target_os = "linux")]
+            "mntns" => Ok(ProcColumn::MntNamespace),
+            #[cfg(target_os = "linux")]
+            "cpuwait%" | "cpuwait" => Ok(ProcColumn::CpuWaitPercent),
+            #[cfg(target_os = "linux")]
+            "iowait%" | "iowait" => Ok(ProcColumn::IoWaitPercent),
+            #[cfg(target_os = "linux")]
+            "ioprio" => Ok(ProcColumn::IoPriority),
             #[cfg(feature = "gpu")]
             // TODO: Maybe change this in the future.
             "gmem" | "gmem%" => Ok(ProcColumn::GpuMemPercent),
@@ -221,10 +340,44 @@ impl From<&ProcColumn> for ProcWidgetColumn {
             ProcColumn::State => ProcWidgetColumn::State,
             ProcColumn::User => ProcWidgetColumn::User,
             ProcColumn::Time => ProcWidgetColumn::Time,
+            #[cfg(target_os = "linux")]
+            ProcColumn::Label => ProcWidgetColumn::Label,
+            #[cfg(target_os = "linux")]
+            ProcColumn::PidNamespace => ProcWidgetColumn::PidNamespace,
+            #[cfg(target_os = "linux")]
+            ProcColumn::NetNamespace => ProcWidgetColumn::NetNamespace,
+            #[cfg(target_os = "linux")]
+            ProcColumn::MntNamespace => ProcWidgetColumn::MntNamespace,
+            #[cfg(target_os = "linux")]
+            ProcColumn::CpuWaitPercent => ProcWidgetColumn::CpuWait,
+            #[cfg(target_os = "linux")]
+            ProcColumn::IoWaitPercent => ProcWidgetColumn::IoWait,
+            #[cfg(target_os = "linux")]
+            ProcColumn::IoPriority => ProcWidgetColumn::IoPriority,
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemPercent | ProcColumn::GpuMemValue => ProcWidgetColumn::GpuMem,
             #[cfg(feature = "gpu")]
             ProcColumn::GpuUtilPercent => ProcWidgetColumn::GpuUtil,
+            #[cfg(feature = "plugins")]
+            ProcColumn::Plugin { index, header } => ProcWidgetColumn::Plugin {
+                index: *index,
+                header: *header,
+            },
         }
     }
 }
+
+/// Compares two strings ignoring case (e.g. `Discord` comes before `dkms`),
+/// without making lowercase copies of them.
+fn cmp_ignore_case(a: &str, b: &str, descending: bool) -> Ordering {
+    let ordering = a
+        .chars()
+        .flat_map(char::to_lowercase)
+        .cmp(b.chars().flat_map(char::to_lowercase));
+
+    if descending {
+        ordering.reverse()
+    } else {
+        ordering
+    }
+}
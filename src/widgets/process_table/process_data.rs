@@ -1,81 +1,170 @@
-use std::{
-    borrow::Cow,
-    cmp::{max, Ordering},
-    fmt::Display,
-    num::NonZeroU16,
-    time::Duration,
-};
+use std::{borrow::Cow, cmp::Ordering, fmt::Display, num::NonZeroU16, sync::Arc, time::Duration};
 
-use concat_string::concat_string;
 use tui::widgets::Row;
 
 use super::process_columns::ProcColumn;
+#[cfg(target_os = "linux")]
+use crate::data_collection::processes::linux::{io_priority::IoPriority, namespace::Namespaces};
 use crate::{
-    canvas::{
-        components::data_table::{DataTableColumn, DataToCell},
-        Painter,
-    },
+    canvas::components::data_table::{DataTableColumn, DataToCell},
     data_collection::processes::{Pid, ProcessHarvest},
     data_conversion::{binary_byte_string, dec_bytes_per_second_string, dec_bytes_string},
+    options::config::style::Styles,
+    utils::strings::str_width,
 };
 
 #[derive(Clone, Debug)]
 enum IdType {
-    Name(String),
-    Command(String),
+    Name(Arc<str>),
+    Command(Arc<str>),
+    /// The header of a user's processes when grouping by user.
+    User(Arc<str>),
+}
+
+/// Where a process is in the process tree, used to draw the branches before
+/// its name. This is kept as a few numbers rather than as a string, so the
+/// branches are only drawn for the rows that are shown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TreePrefix {
+    /// How many ancestors the process has in the tree.
+    depth: u16,
+
+    /// For each ancestor past the first, by depth, whether it has more siblings
+    /// after it, in which case a line is drawn down past the process. Ancestors
+    /// deeper than this can hold are drawn without lines.
+    continuing: u128,
+
+    /// Whether the process is the last of its siblings.
+    is_last: bool,
+
+    /// Whether the process's children are collapsed into it.
+    is_collapsed: bool,
+}
+
+impl TreePrefix {
+    const BRANCH_END: char = '└';
+    const BRANCH_SPLIT: char = '├';
+    const BRANCH_HORIZONTAL: char = '─';
+    const SPACED_BRANCH_VERTICAL: &'static str = "│  ";
+    const SPACED_BLANK: &'static str = "   ";
+
+    /// Creates the prefix of a process `depth` deep, where `continuing` is
+    /// whether each of its ancestors has more siblings after it.
+    pub fn new(depth: u16, continuing: u128, is_last: bool, is_collapsed: bool) -> Self {
+        Self {
+            depth,
+            continuing,
+            is_last,
+            is_collapsed,
+        }
+    }
+
+    /// Whether the ancestor at `depth` has more siblings after it.
+    pub fn is_continuing(continuing: u128, depth: u16) -> bool {
+        depth
+            .checked_sub(1)
+            .and_then(|bit| continuing.checked_shr(u32::from(bit)))
+            .is_some_and(|bits| bits & 1 == 1)
+    }
+
+    /// Returns `continuing` with whether the ancestor at `depth` has more
+    /// siblings after it set to `is_continuing`.
+    pub fn set_continuing(continuing: u128, depth: u16, is_continuing: bool) -> u128 {
+        match depth
+            .checked_sub(1)
+            .and_then(|bit| 1u128.checked_shl(u32::from(bit)))
+        {
+            Some(mask) if is_continuing => continuing | mask,
+            Some(mask) => continuing & !mask,
+            None => continuing,
+        }
+    }
+
+    /// Appends the prefix to `out`.
+    fn write_to(&self, out: &mut String) {
+        if self.depth == 0 {
+            if self.is_collapsed {
+                out.push_str("+ ");
+            }
+            return;
+        }
+
+        for depth in 1..self.depth {
+            out.push_str(if Self::is_continuing(self.continuing, depth) {
+                Self::SPACED_BRANCH_VERTICAL
+            } else {
+                Self::SPACED_BLANK
+            });
+        }
+
+        out.push(if self.is_last {
+            Self::BRANCH_END
+        } else {
+            Self::BRANCH_SPLIT
+        });
+        out.push(Self::BRANCH_HORIZONTAL);
+        out.push_str(if self.is_collapsed { " + " } else { " " });
+    }
+
+    /// How many columns the prefix takes up.
+    fn width(&self) -> usize {
+        match (self.depth, self.is_collapsed) {
+            (0, false) => 0,
+            (0, true) => 2,
+            (depth, is_collapsed) => usize::from(depth - 1) * 3 + if is_collapsed { 5 } else { 3 },
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct Id {
     id_type: IdType,
-    prefix: Option<String>,
+    prefix: Option<TreePrefix>,
 }
 
 impl From<&'static str> for Id {
     fn from(s: &'static str) -> Self {
         Id {
-            id_type: IdType::Name(s.to_string()),
+            id_type: IdType::Name(s.into()),
             prefix: None,
         }
     }
 }
 
 impl Id {
-    /// Returns the ID as a lowercase [`String`], with no prefix. This is
-    /// primarily useful for cases like sorting where we treat everything as
-    /// the same case (e.g. `Discord` comes before `dkms`).
-    pub fn to_lowercase(&self) -> String {
-        match &self.id_type {
-            IdType::Name(name) => name.to_lowercase(),
-            IdType::Command(cmd) => cmd.to_lowercase(),
-        }
-    }
-
     /// Return the ID as a borrowed [`str`] with no prefix.
     pub fn as_str(&self) -> &str {
         match &self.id_type {
-            IdType::Name(name) => name.as_str(),
-            IdType::Command(cmd) => cmd.as_str(),
+            IdType::Name(name) => name,
+            IdType::Command(cmd) => cmd,
+            IdType::User(user) => user,
         }
     }
 
+    /// Whether this is the header of a user's processes.
+    pub fn is_user(&self) -> bool {
+        matches!(self.id_type, IdType::User(_))
+    }
+
     /// Returns the ID as a [`String`] with prefix.
     pub fn to_prefixed_string(&self) -> String {
-        if let Some(prefix) = &self.prefix {
-            concat_string!(
-                prefix,
-                match &self.id_type {
-                    IdType::Name(name) => name,
-                    IdType::Command(cmd) => cmd,
-                }
-            )
-        } else {
-            match &self.id_type {
-                IdType::Name(name) => name.to_string(),
-                IdType::Command(cmd) => cmd.to_string(),
+        match &self.prefix {
+            Some(prefix) => {
+                let mut prefixed = String::new();
+                prefix.write_to(&mut prefixed);
+                prefixed.push_str(self.as_str());
+
+                prefixed
             }
+            None => self.as_str().to_string(),
         }
     }
+
+    /// Returns how many columns the ID takes up with its prefix, without
+    /// building the prefixed string.
+    pub fn prefixed_width(&self) -> usize {
+        self.prefix.map_or(0, |prefix| prefix.width()) + str_width(self.as_str())
+    }
 }
 
 impl Display for Id {
@@ -139,7 +228,7 @@ impl DurationExt for Duration {
     }
 }
 
-fn format_time(dur: Duration) -> String {
+pub fn format_time(dur: Duration) -> String {
     if dur.num_days() > 0 {
         format!(
             "{}d {}h {}m",
@@ -169,8 +258,8 @@ fn format_time(dur: Duration) -> String {
 #[derive(Clone)]
 pub struct ProcWidgetData {
     pub pid: Pid,
-    #[allow(dead_code)]
     pub ppid: Option<Pid>,
+    pub start_time: u64,
     pub id: Id,
     pub cpu_usage_percent: f32,
     pub mem_usage: MemUsage,
@@ -178,9 +267,19 @@ pub struct ProcWidgetData {
     pub wps: u64,
     pub total_read: u64,
     pub total_write: u64,
-    pub process_state: String,
+    pub process_state: Arc<str>,
     pub process_char: char,
-    pub user: String,
+    pub user: Arc<str>,
+    #[cfg(target_os = "linux")]
+    pub label: Option<Arc<str>>,
+    #[cfg(target_os = "linux")]
+    pub namespaces: Namespaces,
+    #[cfg(target_os = "linux")]
+    pub cpu_wait_percent: f32,
+    #[cfg(target_os = "linux")]
+    pub io_wait_percent: f32,
+    #[cfg(target_os = "linux")]
+    pub io_priority: Option<IoPriority>,
     pub num_similar: u64,
     pub disabled: bool,
     pub time: Duration,
@@ -188,6 +287,8 @@ pub struct ProcWidgetData {
     pub gpu_mem_usage: MemUsage,
     #[cfg(feature = "gpu")]
     pub gpu_usage: u32,
+    #[cfg(feature = "plugins")]
+    pub plugin_values: Vec<String>,
 }
 
 impl ProcWidgetData {
@@ -210,6 +311,7 @@ impl ProcWidgetData {
         Self {
             pid: process.pid,
             ppid: process.parent_pid,
+            start_time: process.start_time,
             id,
             cpu_usage_percent: process.cpu_usage_percent,
             mem_usage,
@@ -219,7 +321,17 @@ impl ProcWidgetData {
             total_write: process.total_write_bytes,
             process_state: process.process_state.0.clone(),
             process_char: process.process_state.1,
-            user: process.user.to_string(),
+            user: process.user.clone(),
+            #[cfg(target_os = "linux")]
+            label: process.label.clone(),
+            #[cfg(target_os = "linux")]
+            namespaces: process.namespaces,
+            #[cfg(target_os = "linux")]
+            cpu_wait_percent: process.cpu_wait_percent,
+            #[cfg(target_os = "linux")]
+            io_wait_percent: process.io_wait_percent,
+            #[cfg(target_os = "linux")]
+            io_priority: process.io_priority,
             num_similar: 1,
             disabled: false,
             time: process.time,
@@ -231,9 +343,21 @@ impl ProcWidgetData {
             },
             #[cfg(feature = "gpu")]
             gpu_usage: process.gpu_util,
+            #[cfg(feature = "plugins")]
+            plugin_values: process.plugin_values.clone(),
         }
     }
 
+    /// Returns the value of the plugin column with the given index, or an empty
+    /// string if there isn't one.
+    #[cfg(feature = "plugins")]
+    pub fn plugin_value(&self, index: usize) -> &str {
+        self.plugin_values
+            .get(index)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
     pub fn num_similar(mut self, num_similar: u64) -> Self {
         self.num_similar = num_similar;
         self
@@ -244,11 +368,18 @@ impl ProcWidgetData {
         self
     }
 
-    pub fn prefix(mut self, prefix: Option<String>) -> Self {
+    pub fn prefix(mut self, prefix: Option<TreePrefix>) -> Self {
         self.id.prefix = prefix;
         self
     }
 
+    /// Turns this into the header of its user's processes, named after the
+    /// user.
+    pub fn user_header(mut self) -> Self {
+        self.id.id_type = IdType::User(self.user.clone());
+        self
+    }
+
     pub fn add(&mut self, other: &Self) {
         self.cpu_usage_percent += other.cpu_usage_percent;
         self.mem_usage = match (&self.mem_usage, &other.mem_usage) {
@@ -264,6 +395,11 @@ impl ProcWidgetData {
         self.total_read += other.total_read;
         self.total_write += other.total_write;
         self.time = self.time.max(other.time);
+        #[cfg(target_os = "linux")]
+        {
+            self.cpu_wait_percent += other.cpu_wait_percent;
+            self.io_wait_percent += other.io_wait_percent;
+        }
         #[cfg(feature = "gpu")]
         {
             self.gpu_mem_usage = match (&self.gpu_mem_usage, &other.gpu_mem_usage) {
@@ -277,27 +413,13 @@ impl ProcWidgetData {
             self.gpu_usage += other.gpu_usage;
         }
     }
+}
 
-    fn to_string(&self, column: &ProcColumn) -> String {
-        match column {
-            ProcColumn::CpuPercent => format!("{:.1}%", self.cpu_usage_percent),
-            ProcColumn::MemValue | ProcColumn::MemPercent => self.mem_usage.to_string(),
-            ProcColumn::Pid => self.pid.to_string(),
-            ProcColumn::Count => self.num_similar.to_string(),
-            ProcColumn::Name | ProcColumn::Command => self.id.to_prefixed_string(),
-            ProcColumn::ReadPerSecond => dec_bytes_per_second_string(self.rps),
-            ProcColumn::WritePerSecond => dec_bytes_per_second_string(self.wps),
-            ProcColumn::TotalRead => dec_bytes_string(self.total_read),
-            ProcColumn::TotalWrite => dec_bytes_string(self.total_write),
-            ProcColumn::State => self.process_char.to_string(),
-            ProcColumn::User => self.user.clone(),
-            ProcColumn::Time => format_time(self.time),
-            #[cfg(feature = "gpu")]
-            ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => self.gpu_mem_usage.to_string(),
-            #[cfg(feature = "gpu")]
-            ProcColumn::GpuUtilPercent => format!("{:.1}%", self.gpu_usage),
-        }
-    }
+/// Returns the text of a namespace cell, which is `N/A` if the namespace
+/// couldn't be read.
+#[cfg(target_os = "linux")]
+fn namespace_id(id: Option<u64>) -> Cow<'static, str> {
+    id.map_or("N/A".into(), |id| id.to_string().into())
 }
 
 impl DataToCell<ProcColumn> for ProcWidgetData {
@@ -323,24 +445,42 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
                 if calculated_width < 8 {
                     self.process_char.to_string().into()
                 } else {
-                    self.process_state.clone().into()
+                    self.process_state.to_string().into()
                 }
             }
-            ProcColumn::User => self.user.clone().into(),
+            ProcColumn::User => self.user.to_string().into(),
             ProcColumn::Time => format_time(self.time).into(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Label => self.label.as_deref().unwrap_or("N/A").to_string().into(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::PidNamespace => namespace_id(self.namespaces.pid),
+            #[cfg(target_os = "linux")]
+            ProcColumn::NetNamespace => namespace_id(self.namespaces.net),
+            #[cfg(target_os = "linux")]
+            ProcColumn::MntNamespace => namespace_id(self.namespaces.mnt),
+            #[cfg(target_os = "linux")]
+            ProcColumn::CpuWaitPercent => format!("{:.1}%", self.cpu_wait_percent).into(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::IoWaitPercent => format!("{:.1}%", self.io_wait_percent).into(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::IoPriority => self
+                .io_priority
+                .map_or("N/A".into(), |priority| priority.to_string().into()),
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => {
                 self.gpu_mem_usage.to_string().into()
             }
             #[cfg(feature = "gpu")]
             ProcColumn::GpuUtilPercent => format!("{:.1}%", self.gpu_usage).into(),
+            #[cfg(feature = "plugins")]
+            ProcColumn::Plugin { index, .. } => self.plugin_value(*index).to_string().into(),
         })
     }
 
     #[inline(always)]
-    fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
+    fn style_row<'a>(&self, row: Row<'a>, styles: &Styles) -> Row<'a> {
         if self.disabled {
-            row.style(painter.styles.disabled_text_style)
+            row.style(styles.disabled_text_style)
         } else {
             row
         }
@@ -350,15 +490,28 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
     where
         Self: Sized,
     {
-        let mut widths = vec![0; columns.len()];
-
-        for d in data {
-            for (w, c) in widths.iter_mut().zip(columns) {
-                *w = max(*w, d.to_string(c.inner()).len() as u16);
-            }
-        }
-
-        widths
+        // Only the name, command, user, and label columns are sized by their
+        // contents, so those are the only ones measured. They're measured from
+        // the data as is, rather than by building the text of every cell.
+        columns
+            .iter()
+            .map(|column| {
+                let width = match column.inner() {
+                    ProcColumn::Name | ProcColumn::Command => {
+                        data.iter().map(|d| d.id.prefixed_width()).max()
+                    }
+                    ProcColumn::User => data.iter().map(|d| str_width(&d.user)).max(),
+                    #[cfg(target_os = "linux")]
+                    ProcColumn::Label => data
+                        .iter()
+                        .map(|d| str_width(d.label.as_deref().unwrap_or("N/A")))
+                        .max(),
+                    _ => None,
+                };
+
+                width.unwrap_or(0) as u16
+            })
+            .collect()
     }
 }
 
@@ -366,7 +519,8 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
 mod test {
     use std::time::Duration;
 
-    use crate::widgets::process_data::format_time;
+    use super::TreePrefix;
+    use crate::{utils::strings::str_width, widgets::process_data::format_time};
 
     #[test]
     fn test_format_time() {
@@ -398,4 +552,45 @@ mod test {
             "364d 23h 59m"
         );
     }
+
+    #[test]
+    fn test_tree_prefix() {
+        fn render(prefix: TreePrefix) -> String {
+            let mut out = String::new();
+            prefix.write_to(&mut out);
+
+            assert_eq!(str_width(&out), prefix.width());
+            out
+        }
+
+        assert_eq!(render(TreePrefix::new(0, 0, true, false)), "");
+        assert_eq!(render(TreePrefix::new(0, 0, true, true)), "+ ");
+        assert_eq!(render(TreePrefix::new(1, 0, false, false)), "├─ ");
+        assert_eq!(render(TreePrefix::new(1, 0, true, true)), "└─ + ");
+
+        let continuing = TreePrefix::set_continuing(0, 1, true);
+        assert!(TreePrefix::is_continuing(continuing, 1));
+        assert!(!TreePrefix::is_continuing(continuing, 2));
+        assert_eq!(
+            render(TreePrefix::new(2, continuing, true, false)),
+            "│  └─ "
+        );
+        assert_eq!(
+            render(TreePrefix::new(3, continuing, false, false)),
+            "│     ├─ "
+        );
+
+        let continuing = TreePrefix::set_continuing(continuing, 1, false);
+        assert_eq!(continuing, 0);
+        assert_eq!(
+            render(TreePrefix::new(2, continuing, false, true)),
+            "   ├─ + "
+        );
+
+        // Ancestors deeper than can be tracked are drawn without lines.
+        assert!(!TreePrefix::is_continuing(
+            TreePrefix::set_continuing(0, 200, true),
+            200
+        ));
+    }
 }
@@ -8,6 +8,8 @@ use std::{
 use humantime::parse_duration;
 use regex::Regex;
 
+#[cfg(target_os = "linux")]
+use crate::data_collection::processes::linux::namespace::NamespaceKinds;
 use crate::{
     data_collection::processes::ProcessHarvest, multi_eq_ignore_ascii_case, utils::data_prefixes::*,
 };
@@ -558,6 +560,22 @@ impl ProcessQuery {
             .iter()
             .all(|ok| ok.check(process, is_using_command))
     }
+
+    /// Returns which namespaces this matches on, so that they're collected.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn namespace_kinds(&self) -> NamespaceKinds {
+        let mut kinds = NamespaceKinds::default();
+        for or in &self.query {
+            or.visit_prefix_types(&mut |prefix_type| match prefix_type {
+                PrefixType::PidNs => kinds.pid = true,
+                PrefixType::NetNs => kinds.net = true,
+                PrefixType::MntNs => kinds.mnt = true,
+                _ => {}
+            });
+        }
+
+        kinds
+    }
 }
 
 impl Debug for ProcessQuery {
@@ -600,6 +618,14 @@ impl Or {
             self.lhs.check(process, is_using_command)
         }
     }
+
+    #[cfg(target_os = "linux")]
+    fn visit_prefix_types(&self, visit: &mut impl FnMut(&PrefixType)) {
+        self.lhs.visit_prefix_types(visit);
+        if let Some(rhs) = &self.rhs {
+            rhs.visit_prefix_types(visit);
+        }
+    }
 }
 
 impl Debug for Or {
@@ -645,6 +671,14 @@ impl And {
             self.lhs.check(process, is_using_command)
         }
     }
+
+    #[cfg(target_os = "linux")]
+    fn visit_prefix_types(&self, visit: &mut impl FnMut(&PrefixType)) {
+        self.lhs.visit_prefix_types(visit);
+        if let Some(rhs) = &self.rhs {
+            rhs.visit_prefix_types(visit);
+        }
+    }
 }
 
 impl Debug for And {
@@ -676,6 +710,12 @@ enum PrefixType {
     GMem,
     #[cfg(feature = "gpu")]
     PGMem,
+    #[cfg(target_os = "linux")]
+    PidNs,
+    #[cfg(target_os = "linux")]
+    NetNs,
+    #[cfg(target_os = "linux")]
+    MntNs,
     __Nonexhaustive,
 }
 
@@ -722,6 +762,16 @@ impl std::str::FromStr for PrefixType {
                 result = PGpu;
             }
         }
+        #[cfg(target_os = "linux")]
+        {
+            if multi_eq_ignore_ascii_case!(s, "pidns") {
+                result = PidNs;
+            } else if multi_eq_ignore_ascii_case!(s, "netns") {
+                result = NetNs;
+            } else if multi_eq_ignore_ascii_case!(s, "mntns") {
+                result = MntNs;
+            }
+        }
         Ok(result)
     }
 }
@@ -777,6 +827,19 @@ impl Prefix {
         Ok(())
     }
 
+    #[cfg(target_os = "linux")]
+    fn visit_prefix_types(&self, visit: &mut impl FnMut(&PrefixType)) {
+        if let Some(or) = &self.or {
+            or.visit_prefix_types(visit);
+        }
+        if let Some((prefix_type, _)) = &self.regex_prefix {
+            visit(prefix_type);
+        }
+        if let Some((prefix_type, _)) = &self.compare_prefix {
+            visit(prefix_type);
+        }
+    }
+
     fn check(&self, process: &ProcessHarvest, is_using_command: bool) -> bool {
         fn matches_condition<I: Into<f64>, J: Into<f64>>(
             condition: &QueryComparison, lhs: I, rhs: J,
@@ -793,6 +856,12 @@ impl Prefix {
             }
         }
 
+        /// Processes whose namespace couldn't be read never match.
+        #[cfg(target_os = "linux")]
+        fn matches_namespace(condition: &QueryComparison, lhs: Option<u64>, rhs: f64) -> bool {
+            lhs.is_some_and(|lhs| matches_condition(condition, lhs as f64, rhs))
+        }
+
         fn matches_duration(condition: &QueryComparison, lhs: Duration, rhs: Duration) -> bool {
             match condition {
                 QueryComparison::Equal => lhs == rhs,
@@ -809,12 +878,12 @@ impl Prefix {
             if let StringQuery::Regex(r) = query_content {
                 match prefix_type {
                     PrefixType::Name => r.is_match(if is_using_command {
-                        process.command.as_str()
+                        &process.command
                     } else {
-                        process.name.as_str()
+                        &process.name
                     }),
                     PrefixType::Pid => r.is_match(process.pid.to_string().as_str()),
-                    PrefixType::State => r.is_match(process.process_state.0.as_str()),
+                    PrefixType::State => r.is_match(&process.process_state.0),
                     PrefixType::User => r.is_match(process.user.as_ref()),
                     _ => true,
                 }
@@ -877,6 +946,24 @@ impl Prefix {
                         process.gpu_mem_percent,
                         numerical_query.value,
                     ),
+                    #[cfg(target_os = "linux")]
+                    PrefixType::PidNs => matches_namespace(
+                        &numerical_query.condition,
+                        process.namespaces.pid,
+                        numerical_query.value,
+                    ),
+                    #[cfg(target_os = "linux")]
+                    PrefixType::NetNs => matches_namespace(
+                        &numerical_query.condition,
+                        process.namespaces.net,
+                        numerical_query.value,
+                    ),
+                    #[cfg(target_os = "linux")]
+                    PrefixType::MntNs => matches_namespace(
+                        &numerical_query.condition,
+                        process.namespaces.mnt,
+                        numerical_query.value,
+                    ),
                     _ => true,
                 },
                 ComparableQuery::Time(time_query) => match prefix_type {
@@ -0,0 +1,304 @@
+//! Sorting for the process widget that reuses work from the last refresh.
+//!
+//! Each row's sort key is worked out once per sort rather than on every
+//! comparison, case-folded strings are kept between refreshes, and rows start
+//! off in the order they were last sorted into. As most rows don't move much
+//! between refreshes, the (stable) sort then mostly just checks that they're
+//! still in order.
+//!
+//! Tree mode sorts each group of siblings the same way, so ties and case are
+//! handled like they are in the flat table.
+
+use std::{cmp::Ordering, mem, sync::Arc};
+
+use hashbrown::HashMap;
+
+use super::{MemUsage, ProcColumn, ProcWidgetData};
+use crate::{
+    canvas::components::data_table::SortOrder, data_collection::processes::Pid,
+    utils::general::partial_ordering,
+};
+
+/// The value a row is sorted by.
+#[derive(Debug)]
+enum SortKey {
+    Float(f64),
+    Unsigned(u64),
+    Text(Arc<str>),
+    /// Plugin values are sorted numerically if both can be parsed as numbers,
+    /// and as text otherwise.
+    #[cfg(feature = "plugins")]
+    Plugin(Option<f64>, Arc<str>),
+}
+
+impl SortKey {
+    fn compare(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortKey::Float(a), SortKey::Float(b)) => partial_ordering(a, b),
+            (SortKey::Unsigned(a), SortKey::Unsigned(b)) => a.cmp(b),
+            (SortKey::Text(a), SortKey::Text(b)) => a.cmp(b),
+            #[cfg(feature = "plugins")]
+            (SortKey::Plugin(Some(a), _), SortKey::Plugin(Some(b), _)) => partial_ordering(a, b),
+            #[cfg(feature = "plugins")]
+            (SortKey::Plugin(_, a), SortKey::Plugin(_, b)) => a.cmp(b),
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+impl From<&MemUsage> for SortKey {
+    fn from(usage: &MemUsage) -> Self {
+        match usage {
+            MemUsage::Percent(percent) => SortKey::Float(f64::from(*percent)),
+            MemUsage::Bytes(bytes) => SortKey::Unsigned(*bytes),
+        }
+    }
+}
+
+/// What's kept between sorts of a process widget's rows.
+#[derive(Debug, Default)]
+pub(crate) struct SortKeyCache {
+    /// Lowercase copies of the strings last sorted on.
+    folded: HashMap<Arc<str>, Arc<str>>,
+
+    /// Lowercase copies of the strings sorted on since the last
+    /// [`SortKeyCache::prune`].
+    seen: HashMap<Arc<str>, Arc<str>>,
+
+    /// Where each row, by PID, ended up after the last sort.
+    positions: HashMap<Pid, usize>,
+}
+
+impl SortKeyCache {
+    /// Sorts `data` by `column` in the given order. Rows that tie are sorted by
+    /// ascending PID.
+    pub(crate) fn sort(
+        &mut self, column: &ProcColumn, data: &mut Vec<ProcWidgetData>, order: SortOrder,
+    ) {
+        let rows = self.previous_order(mem::take(data));
+        let keyed = self.sort_keyed(column, rows, order);
+
+        self.prune();
+        self.positions.clear();
+        self.positions.extend(
+            keyed
+                .iter()
+                .enumerate()
+                .map(|(position, (_, row))| (row.pid, position)),
+        );
+
+        data.extend(keyed.into_iter().map(|(_, row)| row));
+    }
+
+    /// Sorts a group of siblings in a process tree by `column` in the given
+    /// order, like [`SortKeyCache::sort`]. Call [`SortKeyCache::prune`] once
+    /// the whole tree is sorted.
+    pub(crate) fn sort_siblings(
+        &mut self, column: &ProcColumn, siblings: &mut Vec<ProcWidgetData>, order: SortOrder,
+    ) {
+        let keyed = self.sort_keyed(column, mem::take(siblings), order);
+        siblings.extend(keyed.into_iter().map(|(_, row)| row));
+    }
+
+    /// Drops strings that weren't sorted on since the last prune, so this
+    /// doesn't grow forever.
+    pub(crate) fn prune(&mut self) {
+        self.folded = mem::take(&mut self.seen);
+    }
+
+    fn sort_keyed(
+        &mut self, column: &ProcColumn, rows: Vec<ProcWidgetData>, order: SortOrder,
+    ) -> Vec<(SortKey, ProcWidgetData)> {
+        let descending = matches!(order, SortOrder::Descending);
+        let mut keyed: Vec<(SortKey, ProcWidgetData)> = rows
+            .into_iter()
+            .map(|row| (self.key(column, &row), row))
+            .collect();
+
+        keyed.sort_by(|(a_key, a), (b_key, b)| {
+            let ordering = a_key.compare(b_key);
+            let ordering = if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+
+            ordering.then(a.pid.cmp(&b.pid))
+        });
+
+        keyed
+    }
+
+    /// Puts rows in the order they were last sorted into, with new rows at the
+    /// end.
+    fn previous_order(&self, data: Vec<ProcWidgetData>) -> Vec<ProcWidgetData> {
+        let mut placed: Vec<Option<ProcWidgetData>> = Vec::new();
+        placed.resize_with(self.positions.len(), || None);
+
+        let mut new_rows = Vec::new();
+        for row in data {
+            match self.positions.get(&row.pid) {
+                Some(&position) if placed[position].is_none() => placed[position] = Some(row),
+                _ => new_rows.push(row),
+            }
+        }
+
+        placed.into_iter().flatten().chain(new_rows).collect()
+    }
+
+    fn key(&mut self, column: &ProcColumn, row: &ProcWidgetData) -> SortKey {
+        match column {
+            ProcColumn::CpuPercent => SortKey::Float(f64::from(row.cpu_usage_percent)),
+            ProcColumn::MemValue | ProcColumn::MemPercent => SortKey::from(&row.mem_usage),
+            ProcColumn::Pid => SortKey::Unsigned(u64::try_from(row.pid).unwrap_or_default()),
+            ProcColumn::Count => SortKey::Unsigned(row.num_similar),
+            ProcColumn::Name | ProcColumn::Command => self.fold(row.id.as_str()),
+            ProcColumn::ReadPerSecond => SortKey::Unsigned(row.rps),
+            ProcColumn::WritePerSecond => SortKey::Unsigned(row.wps),
+            ProcColumn::TotalRead => SortKey::Unsigned(row.total_read),
+            ProcColumn::TotalWrite => SortKey::Unsigned(row.total_write),
+            ProcColumn::State => self.fold(&row.process_state),
+            ProcColumn::User => self.fold(&row.user),
+            ProcColumn::Time => SortKey::Float(row.time.as_secs_f64()),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Label => self.fold(row.label.as_deref().unwrap_or_default()),
+            #[cfg(target_os = "linux")]
+            ProcColumn::PidNamespace => SortKey::Unsigned(row.namespaces.pid.unwrap_or_default()),
+            #[cfg(target_os = "linux")]
+            ProcColumn::NetNamespace => SortKey::Unsigned(row.namespaces.net.unwrap_or_default()),
+            #[cfg(target_os = "linux")]
+            ProcColumn::MntNamespace => SortKey::Unsigned(row.namespaces.mnt.unwrap_or_default()),
+            #[cfg(target_os = "linux")]
+            ProcColumn::CpuWaitPercent => SortKey::Float(f64::from(row.cpu_wait_percent)),
+            #[cfg(target_os = "linux")]
+            ProcColumn::IoWaitPercent => SortKey::Float(f64::from(row.io_wait_percent)),
+            #[cfg(target_os = "linux")]
+            ProcColumn::IoPriority => {
+                SortKey::Unsigned(row.io_priority.map_or(0, |priority| priority.rank().into()))
+            }
+            #[cfg(feature = "gpu")]
+            ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => {
+                SortKey::from(&row.gpu_mem_usage)
+            }
+            #[cfg(feature = "gpu")]
+            ProcColumn::GpuUtilPercent => SortKey::Unsigned(u64::from(row.gpu_usage)),
+            #[cfg(feature = "plugins")]
+            ProcColumn::Plugin { index, .. } => {
+                let value = row.plugin_value(*index);
+                SortKey::Plugin(value.parse().ok(), value.into())
+            }
+        }
+    }
+
+    /// Returns the lowercase copy of `text`, reusing the one from the last sort
+    /// if there is one.
+    fn fold(&mut self, text: &str) -> SortKey {
+        if let Some(lowercase) = self.seen.get(text) {
+            return SortKey::Text(lowercase.clone());
+        }
+
+        let (text, lowercase) = match self.folded.get_key_value(text) {
+            Some((text, lowercase)) => (text.clone(), lowercase.clone()),
+            None => (Arc::from(text), Arc::from(text.to_lowercase())),
+        };
+        self.seen.insert(text, lowercase.clone());
+
+        SortKey::Text(lowercase)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn row(pid: Pid, name: &'static str, cpu_usage_percent: f32) -> ProcWidgetData {
+        ProcWidgetData {
+            pid,
+            ppid: None,
+            start_time: 0,
+            id: name.into(),
+            cpu_usage_percent,
+            mem_usage: MemUsage::Percent(0.0),
+            rps: 0,
+            wps: 0,
+            total_read: 0,
+            total_write: 0,
+            process_state: "N/A".into(),
+            process_char: '?',
+            user: "N/A".into(),
+            #[cfg(target_os = "linux")]
+            label: None,
+            #[cfg(target_os = "linux")]
+            namespaces: Default::default(),
+            #[cfg(target_os = "linux")]
+            cpu_wait_percent: 0.0,
+            #[cfg(target_os = "linux")]
+            io_wait_percent: 0.0,
+            #[cfg(target_os = "linux")]
+            io_priority: None,
+            num_similar: 0,
+            disabled: false,
+            time: Duration::from_secs(0),
+            #[cfg(feature = "gpu")]
+            gpu_mem_usage: MemUsage::Percent(0.0),
+            #[cfg(feature = "gpu")]
+            gpu_usage: 0,
+            #[cfg(feature = "plugins")]
+            plugin_values: vec![],
+        }
+    }
+
+    fn pids(data: &[ProcWidgetData]) -> Vec<Pid> {
+        data.iter().map(|row| row.pid).collect()
+    }
+
+    #[test]
+    fn sorts_ignoring_case() {
+        let mut cache = SortKeyCache::default();
+        let mut data = vec![
+            row(1, "dkms", 0.0),
+            row(2, "Discord", 0.0),
+            row(3, "bash", 0.0),
+        ];
+
+        cache.sort(&ProcColumn::Name, &mut data, SortOrder::Ascending);
+        assert_eq!(pids(&data), [3, 2, 1]);
+
+        cache.sort(&ProcColumn::Name, &mut data, SortOrder::Descending);
+        assert_eq!(pids(&data), [1, 2, 3]);
+    }
+
+    #[test]
+    fn ties_are_by_pid() {
+        let mut cache = SortKeyCache::default();
+        let mut data = vec![row(3, "a", 1.0), row(1, "b", 1.0), row(2, "c", 2.0)];
+
+        cache.sort(&ProcColumn::CpuPercent, &mut data, SortOrder::Descending);
+        assert_eq!(pids(&data), [2, 1, 3]);
+
+        // Starting from the last order shouldn't change how ties are broken.
+        let mut data = vec![row(2, "c", 1.0), row(1, "b", 1.0), row(3, "a", 1.0)];
+        cache.sort(&ProcColumn::CpuPercent, &mut data, SortOrder::Descending);
+        assert_eq!(pids(&data), [1, 2, 3]);
+    }
+
+    #[test]
+    fn handles_changed_rows() {
+        let mut cache = SortKeyCache::default();
+        let mut data = vec![row(1, "a", 3.0), row(2, "b", 2.0), row(3, "c", 1.0)];
+        cache.sort(&ProcColumn::CpuPercent, &mut data, SortOrder::Descending);
+        assert_eq!(pids(&data), [1, 2, 3]);
+
+        // PID 2 is gone, PID 4 is new, and PID 3 now uses the most CPU.
+        let mut data = vec![row(1, "a", 3.0), row(3, "c", 5.0), row(4, "d", 4.0)];
+        cache.sort(&ProcColumn::CpuPercent, &mut data, SortOrder::Descending);
+        assert_eq!(pids(&data), [3, 4, 1]);
+
+        // Only the strings from the last sort are kept.
+        cache.sort(&ProcColumn::Name, &mut data, SortOrder::Ascending);
+        assert_eq!(pids(&data), [1, 3, 4]);
+        assert_eq!(cache.folded.len(), 3);
+    }
+}
@@ -1,6 +1,9 @@
 use std::{borrow::Cow, num::NonZeroU16};
 
-use crate::canvas::components::data_table::{ColumnHeader, DataTableColumn, DataToCell};
+use crate::{
+    canvas::components::data_table::{ColumnHeader, DataTableColumn, DataToCell},
+    utils::strings::str_width,
+};
 
 pub struct SortTableColumn;
 
@@ -21,7 +24,7 @@ impl DataToCell<SortTableColumn> for &'static str {
     where
         Self: Sized,
     {
-        vec![data.iter().map(|d| d.len() as u16).max().unwrap_or(0)]
+        vec![data.iter().map(|d| str_width(d) as u16).max().unwrap_or(0)]
     }
 }
 
@@ -36,6 +39,6 @@ impl DataToCell<SortTableColumn> for Cow<'static, str> {
     where
         Self: Sized,
     {
-        vec![data.iter().map(|d| d.len() as u16).max().unwrap_or(0)]
+        vec![data.iter().map(|d| str_width(d) as u16).max().unwrap_or(0)]
     }
 }
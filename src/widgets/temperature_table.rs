@@ -8,16 +8,25 @@ use crate::{
         ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
         SortDataTable, SortDataTableProps, SortOrder, SortsRow,
     },
-    data_collection::temperature::TemperatureType,
+    data_collection::temperature::{SensorKind, TemperatureType},
+    i18n::tr,
     options::config::style::Styles,
-    utils::general::sort_partial_fn,
+    utils::{general::sort_partial_fn, strings::str_width},
 };
 
 #[derive(Clone, Debug)]
 pub struct TempWidgetData {
     pub sensor: Cow<'static, str>,
-    pub temperature_value: Option<u64>,
+    /// The reading, which is in volts for voltages and watts for power.
+    pub value: Option<f32>,
+    pub kind: SensorKind,
     pub temperature_type: TemperatureType,
+    /// Whether the sensor was hidden, which is only shown while hidden sensors
+    /// are revealed.
+    pub is_hidden: bool,
+    /// Whether the sensor is of a drive. These are listed together, after the
+    /// other sensors.
+    pub is_drive: bool,
 }
 
 pub enum TempWidgetColumn {
@@ -35,17 +44,28 @@ impl ColumnHeader for TempWidgetColumn {
 }
 
 impl TempWidgetData {
+    fn sensor_name(&self) -> Cow<'static, str> {
+        match (self.is_drive, self.is_hidden) {
+            (false, false) => self.sensor.clone(),
+            (false, true) => concat_string!(self.sensor, " (hidden)").into(),
+            (true, false) => concat_string!("Drives: ", self.sensor).into(),
+            (true, true) => concat_string!("Drives: ", self.sensor, " (hidden)").into(),
+        }
+    }
+
     pub fn temperature(&self) -> Cow<'static, str> {
-        match self.temperature_value {
-            Some(temp_val) => {
+        match (self.value, self.kind) {
+            (Some(temp_val), SensorKind::Temperature) => {
                 let temp_type = match self.temperature_type {
                     TemperatureType::Celsius => "°C",
                     TemperatureType::Kelvin => "K",
                     TemperatureType::Fahrenheit => "°F",
                 };
-                concat_string!(temp_val.to_string(), temp_type).into()
+                concat_string!((temp_val.ceil() as u64).to_string(), temp_type).into()
             }
-            None => "N/A".to_string().into(),
+            (Some(volts), SensorKind::Voltage) => format!("{volts:.2}V").into(),
+            (Some(watts), SensorKind::Power) => format!("{watts:.1}W").into(),
+            (None, _) => "N/A".to_string().into(),
         }
     }
 }
@@ -55,7 +75,7 @@ impl DataToCell<TempWidgetColumn> for TempWidgetData {
         &self, column: &TempWidgetColumn, _calculated_width: NonZeroU16,
     ) -> Option<Cow<'static, str>> {
         Some(match column {
-            TempWidgetColumn::Sensor => self.sensor.clone(),
+            TempWidgetColumn::Sensor => self.sensor_name(),
             TempWidgetColumn::Temp => self.temperature(),
         })
     }
@@ -69,8 +89,8 @@ impl DataToCell<TempWidgetColumn> for TempWidgetData {
         let mut widths = vec![0; 2];
 
         data.iter().for_each(|row| {
-            widths[0] = max(widths[0], row.sensor.len() as u16);
-            widths[1] = max(widths[1], row.temperature().len() as u16);
+            widths[0] = max(widths[0], str_width(&row.sensor_name()) as u16);
+            widths[1] = max(widths[1], str_width(&row.temperature()) as u16);
         });
 
         widths
@@ -86,9 +106,7 @@ impl SortsRow for TempWidgetColumn {
                 data.sort_by(move |a, b| sort_partial_fn(descending)(&a.sensor, &b.sensor));
             }
             TempWidgetColumn::Temp => {
-                data.sort_by(|a, b| {
-                    sort_partial_fn(descending)(a.temperature_value, b.temperature_value)
-                });
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.value, b.value));
             }
         }
     }
@@ -97,6 +115,12 @@ impl SortsRow for TempWidgetColumn {
 pub struct TempWidgetState {
     pub table: SortDataTable<TempWidgetData, TempWidgetColumn>,
     pub force_update_data: bool,
+
+    /// Sensors that aren't shown, unless `show_hidden` is set.
+    pub hidden_sensors: Vec<String>,
+
+    /// Whether hidden sensors are shown anyway, so they can be unhidden.
+    pub show_hidden: bool,
 }
 
 impl TempWidgetState {
@@ -108,12 +132,16 @@ impl TempWidgetState {
 
         let props = SortDataTableProps {
             inner: DataTableProps {
-                title: Some(" Temperatures ".into()),
+                title: Some(tr(" Temperatures ").into()),
                 table_gap: config.table_gap,
+                row_height: config.table_row_height(),
                 left_to_right: false,
                 is_basic: config.use_basic_mode,
                 show_table_scroll_position: config.show_table_scroll_position,
+                show_table_scrollbar: config.show_table_scrollbar,
                 show_current_entry_when_unfocused: false,
+                is_striped: config.table_stripes,
+                show_row_separators: config.table_row_separators,
             },
             sort_index: 0,
             order: SortOrder::Ascending,
@@ -124,6 +152,8 @@ impl TempWidgetState {
         Self {
             table: SortDataTable::new_sortable(columns, props, styling),
             force_update_data: false,
+            hidden_sensors: Vec::new(),
+            show_hidden: false,
         }
     }
 
@@ -135,10 +165,51 @@ impl TempWidgetState {
 
     /// Update the current table data.
     pub fn set_table_data(&mut self, data: &[TempWidgetData]) {
-        let mut data = data.to_vec();
+        let mut data: Vec<TempWidgetData> = data
+            .iter()
+            .filter_map(|row| {
+                let is_hidden = self
+                    .hidden_sensors
+                    .iter()
+                    .any(|sensor| *sensor == row.sensor);
+
+                (self.show_hidden || !is_hidden).then(|| TempWidgetData {
+                    is_hidden,
+                    ..row.clone()
+                })
+            })
+            .collect();
         if let Some(column) = self.table.columns.get(self.table.sort_index()) {
             column.sort_by(&mut data, self.table.order());
         }
+        // Keep drives grouped after the other sensors, and voltages and power
+        // readings after temperatures; the sort is stable, so each group keeps
+        // the column's order.
+        data.sort_by_key(|row| (row.kind, row.is_drive));
         self.table.set_data(data);
     }
+
+    /// Hides the selected sensor, or unhides it if it's already hidden.
+    pub fn toggle_hide_current(&mut self) {
+        let Some(row) = self.table.current_item() else {
+            return;
+        };
+
+        if let Some(index) = self
+            .hidden_sensors
+            .iter()
+            .position(|sensor| *sensor == row.sensor)
+        {
+            self.hidden_sensors.remove(index);
+        } else {
+            self.hidden_sensors.push(row.sensor.to_string());
+        }
+        self.force_data_update();
+    }
+
+    /// Toggles whether hidden sensors are shown.
+    pub fn toggle_show_hidden(&mut self) {
+        self.show_hidden = !self.show_hidden;
+        self.force_data_update();
+    }
 }
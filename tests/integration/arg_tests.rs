@@ -129,6 +129,26 @@ fn test_invalid_default_widget_2() {
         .stderr(predicate::str::contains("number too large"));
 }
 
+#[test]
+fn test_invalid_double_click_action() {
+    no_cfg_btm_command()
+        .arg("--double_click_action")
+        .arg("triple")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn test_invalid_graph_style() {
+    no_cfg_btm_command()
+        .arg("--graph_style")
+        .arg("dashes")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
 #[test]
 fn test_missing_default_widget_type() {
     no_cfg_btm_command()
@@ -178,3 +198,133 @@ fn test_help() {
     btm_command(&["--help"]).assert().success();
     btm_command(&["-h"]).assert().success();
 }
+
+#[test]
+fn test_check_config() {
+    btm_command(&["-C", "./tests/valid_configs/empty_config.toml"])
+        .arg("--check_config")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No problems found"));
+}
+
+#[test]
+fn test_check_config_with_includes() {
+    btm_command(&["-C", "./tests/valid_configs/include.toml"])
+        .arg("--check_config")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No problems found"));
+}
+
+#[test]
+fn test_print_default_config() {
+    no_cfg_btm_command()
+        .arg("--print_default_config")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "# This is a default config file for bottom.",
+        ));
+}
+
+#[test]
+fn test_check_config_with_profile() {
+    btm_command(&["-C", "./tests/valid_configs/profiles.toml"])
+        .args(["--check_config", "--profile", "laptop"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No problems found"));
+}
+
+#[test]
+fn test_missing_profile() {
+    btm_command(&["-C", "./tests/valid_configs/profiles.toml"])
+        .args(["--check_config", "--profile", "desktop"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "there is no profile named 'desktop'",
+        ));
+}
+
+#[test]
+fn test_invalid_filter() {
+    btm_command(&["-C", "./tests/valid_configs/empty_config.toml"])
+        .args(["--check_config", "--filter", "(cpu > 5"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is an invalid process filter"));
+}
+
+#[test]
+fn test_invalid_default_sort() {
+    btm_command(&["-C", "./tests/valid_configs/empty_config.toml"])
+        .args(["--check_config", "--default_sort", "mem:up"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "'--default_sort' was set with an invalid value",
+        ));
+}
+
+#[test]
+fn test_invalid_locale() {
+    btm_command(&["-C", "./tests/valid_configs/empty_config.toml"])
+        .args(["--check_config", "--locale", "xx"])
+        .env("LC_ALL", "C")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "'--locale' was set with an invalid value",
+        ));
+}
+
+#[test]
+fn test_invalid_time_format() {
+    btm_command(&["-C", "./tests/valid_configs/empty_config.toml"])
+        .args(["--check_config", "--time_format", "hh:mm"])
+        .env("LC_ALL", "C")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "'--time_format' was set with an invalid value",
+        ));
+}
+
+#[test]
+fn test_translated_errors() {
+    btm_command(&["-C", "./tests/valid_configs/empty_config.toml"])
+        .args([
+            "--check_config",
+            "--locale",
+            "de",
+            "--default_sort",
+            "mem:up",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "'--default_sort' hat einen ungültigen Wert",
+        ));
+}
+
+#[test]
+fn test_batch() {
+    btm_command(&["-C", "./tests/valid_configs/empty_config.toml"])
+        .args(["--batch", "--iterations", "2", "--rate", "250ms"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Tasks:").count(2))
+        .stdout(predicate::str::contains("PID"));
+}
+
+#[test]
+fn test_batch_when_piped() {
+    // Output is piped here, so this should print summaries without --batch.
+    btm_command(&["-C", "./tests/valid_configs/empty_config.toml"])
+        .args(["--iterations", "2", "--rate", "250ms"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Tasks:").count(2));
+}
@@ -139,3 +139,64 @@ fn test_invalid_disk_column() {
         .failure()
         .stderr(predicate::str::contains("doesn't match"));
 }
+
+#[test]
+fn test_conflicting_keybinds() {
+    btm_command(&["-C", "./tests/invalid_configs/conflicting_keybinds.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is bound to both"));
+}
+
+#[test]
+fn test_empty_page() {
+    btm_command(&["-C", "./tests/invalid_configs/empty_page.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "at least one widget in the 'empty' page",
+        ));
+}
+
+#[test]
+fn test_invalid_process_filter() {
+    btm_command(&["-C", "./tests/invalid_configs/invalid_process_filter.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid process filter"));
+}
+
+#[test]
+fn test_missing_base16_theme() {
+    btm_command(&["-C", "./tests/invalid_configs/missing_base16_theme.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Couldn't read the base16 theme"));
+}
+
+#[test]
+fn test_check_config_unknown_key() {
+    btm_command(&["-C", "./tests/invalid_configs/unknown_key.toml"])
+        .arg("--check_config")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown key 'flags.rat'"))
+        .stderr(predicate::str::contains("did you mean 'rate'"));
+}
+
+#[test]
+fn test_check_config_invalid_value() {
+    btm_command(&["-C", "./tests/invalid_configs/invalid_colour_name.toml"])
+        .arg("--check_config")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("found 1 problem"));
+}
+
+#[test]
+fn test_include_cycle() {
+    btm_command(&["-C", "./tests/invalid_configs/include_cycle.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ends up including itself"));
+}
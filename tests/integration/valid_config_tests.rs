@@ -170,6 +170,31 @@ fn test_theme() {
     run_and_kill(&["-C", "./tests/valid_configs/theme.toml"]);
 }
 
+#[test]
+fn test_widget_styles() {
+    run_and_kill(&["-C", "./tests/valid_configs/widget_styles.toml"]);
+}
+
+#[test]
+fn test_graph_series() {
+    run_and_kill(&["-C", "./tests/valid_configs/graph_series.toml"]);
+}
+
+#[test]
+fn test_status_bar() {
+    run_and_kill(&["-C", "./tests/valid_configs/status_bar.toml"]);
+}
+
+#[test]
+fn test_meter_header() {
+    run_and_kill(&["-C", "./tests/valid_configs/meter_header.toml"]);
+}
+
+#[test]
+fn test_basic_tables() {
+    run_and_kill(&["-C", "./tests/valid_configs/basic_tables.toml"]);
+}
+
 #[test]
 fn test_styling_sanity_check() {
     run_and_kill(&["-C", "./tests/valid_configs/styling.toml"]);
@@ -184,3 +209,8 @@ fn test_styling_sanity_check_2() {
 fn test_filtering() {
     run_and_kill(&["-C", "./tests/valid_configs/filtering.toml"]);
 }
+
+#[test]
+fn test_pages() {
+    run_and_kill(&["-C", "./tests/valid_configs/pages.toml"]);
+}